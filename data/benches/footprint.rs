@@ -0,0 +1,165 @@
+//! Footprint aggregation over a long synthetic history, one trade at a time against the
+//! chunked path. Run with `cargo bench -p lux-chart-data --bench footprint`
+
+use lux_chart_data::aggr::footprint;
+use lux_chart_data::aggr::ticks::{TickAccumulation, TickAggr};
+use lux_chart_data::aggr::{TickCount, time::TimeSeries};
+use lux_chart_data::chart::kline::{KlineDataPoint, KlineTrades};
+
+use exchange::util::{Price, PriceStep};
+use exchange::{Kline, Timeframe, Trade};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const TRADES: usize = 500_000;
+const RUNS: usize = 10;
+
+fn history(len: usize) -> Vec<Trade> {
+    let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+    let mut price = 50_000.0_f32;
+
+    (0..len)
+        .map(|idx| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            price = (price + ((seed % 41) as f32 - 20.0) * 0.5).max(1.0);
+
+            Trade {
+                time: 1_700_000_000_000 + idx as u64 * 11,
+                is_sell: seed.is_multiple_of(3),
+                price: Price::from_f32(price),
+                qty: (seed % 5000) as f32 / 1000.0,
+                sub_ms_nanos: 0,
+            }
+        })
+        .collect()
+}
+
+fn klines(trades: &[Trade], interval: u64) -> Vec<Kline> {
+    let mut klines: Vec<Kline> = Vec::new();
+
+    for trade in trades {
+        let time = (trade.time / interval) * interval;
+        match klines.last_mut() {
+            Some(kline) if kline.time == time => {
+                kline.high = kline.high.max(trade.price);
+                kline.low = kline.low.min(trade.price);
+                kline.close = trade.price;
+            }
+            _ => klines.push(Kline {
+                time,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: (0.0, 0.0),
+            }),
+        }
+    }
+    klines
+}
+
+fn measure(mut run: impl FnMut()) -> (Duration, Duration) {
+    run();
+
+    let mut times = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    times.sort();
+
+    (times[0], times[RUNS / 2])
+}
+
+fn report(name: &str, mut one_by_one: impl FnMut(), mut chunked: impl FnMut()) {
+    let (base_best, base_median) = measure(&mut one_by_one);
+    let (best, median) = measure(&mut chunked);
+
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    println!(
+        "{name:<12} one by one {:>8.2} ms (median {:>8.2})  chunked {:>8.2} ms (median {:>8.2})  x{:.2}",
+        ms(base_best),
+        ms(base_median),
+        ms(best),
+        ms(median),
+        ms(base_median) / ms(median),
+    );
+}
+
+fn main() {
+    let trades = history(TRADES);
+    let step = PriceStep::from_f32(5.0);
+
+    println!("{TRADES} trades, best and median of {RUNS} runs");
+
+    report(
+        "single bar",
+        || {
+            let mut footprint = KlineTrades::new();
+            for trade in &trades {
+                footprint.add_trade_to_nearest_bin(trade, step);
+            }
+            black_box(footprint);
+        },
+        || {
+            black_box(footprint::collect(&trades, step));
+        },
+    );
+
+    let interval = Timeframe::M1;
+    let series = TimeSeries::<KlineDataPoint>::new(
+        interval,
+        step,
+        &klines(&trades, interval.to_milliseconds()),
+    );
+    report(
+        "time bars",
+        || {
+            let mut datapoints = series.datapoints.clone();
+            let aggr_time = interval.to_milliseconds();
+            for trade in &trades {
+                let time = (trade.time / aggr_time) * aggr_time;
+                if let Some(dp) = datapoints.get_mut(&time) {
+                    dp.add_trade(trade, step);
+                }
+            }
+            datapoints
+                .values_mut()
+                .for_each(KlineDataPoint::calculate_poc);
+            black_box(datapoints);
+        },
+        || {
+            let mut fresh = TimeSeries {
+                datapoints: series.datapoints.clone(),
+                interval,
+                tick_size: step,
+            };
+            fresh.insert_trades_existing_buckets(&trades);
+            black_box(fresh);
+        },
+    );
+
+    let count = TickCount(1000);
+    report(
+        "tick bars",
+        || {
+            let mut bars: Vec<TickAccumulation> = Vec::new();
+            for trade in &trades {
+                match bars.last_mut() {
+                    Some(bar) if !bar.is_full(count) => bar.update_with_trade(trade, step),
+                    _ => bars.push(TickAccumulation::new(trade, step)),
+                }
+            }
+            bars.iter_mut().for_each(TickAccumulation::calculate_poc);
+            black_box(bars);
+        },
+        || {
+            black_box(TickAggr::new(count, step, &trades));
+        },
+    );
+}
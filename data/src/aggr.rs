@@ -1,5 +1,8 @@
+pub mod footprint;
+pub mod range;
 pub mod ticks;
 pub mod time;
+pub mod volume;
 
 use serde::{Deserialize, Serialize};
 
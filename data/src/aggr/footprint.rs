@@ -0,0 +1,149 @@
+//! Footprints of long trade histories, built over threads. Trades are folded into partial
+//! footprints per chunk which merge in order, so the result is the one adding them one by
+//! one gives
+
+use crate::chart::kline::KlineTrades;
+use exchange::Trade;
+use exchange::util::PriceStep;
+
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+
+/// Below this many trades one thread is faster than splitting them up
+pub const PARALLEL_MIN: usize = 32_768;
+/// Trades per partial footprint
+const CHUNK: usize = 8_192;
+
+/// Footprint of `trades`, binned to the nearest step
+pub fn collect(trades: &[Trade], step: PriceStep) -> KlineTrades {
+    let partial = |chunk: &[Trade]| {
+        let mut footprint = KlineTrades::new();
+        for trade in chunk {
+            footprint.add_trade_to_nearest_bin(trade, step);
+        }
+        footprint
+    };
+
+    if trades.len() < PARALLEL_MIN {
+        return partial(trades);
+    }
+
+    trades
+        .par_chunks(CHUNK)
+        .map(partial)
+        .reduce(KlineTrades::new, |mut earlier, later| {
+            earlier.merge(later);
+            earlier
+        })
+}
+
+/// Footprints of `trades` per `interval` (ms) bucket, for the buckets `keep` takes
+pub fn collect_by_time(
+    trades: &[Trade],
+    interval: u64,
+    step: PriceStep,
+    keep: impl Fn(u64) -> bool + Sync,
+) -> FxHashMap<u64, KlineTrades> {
+    let partial = |chunk: &[Trade]| {
+        let mut buckets: FxHashMap<u64, KlineTrades> = FxHashMap::default();
+        for trade in chunk {
+            let time = (trade.time / interval) * interval;
+            if keep(time) {
+                buckets
+                    .entry(time)
+                    .or_default()
+                    .add_trade_to_nearest_bin(trade, step);
+            }
+        }
+        buckets
+    };
+
+    if trades.len() < PARALLEL_MIN {
+        return partial(trades);
+    }
+
+    trades
+        .par_chunks(CHUNK)
+        .map(partial)
+        .reduce(FxHashMap::default, |mut earlier, later| {
+            for (time, footprint) in later {
+                match earlier.entry(time) {
+                    Entry::Occupied(mut entry) => entry.get_mut().merge(footprint),
+                    Entry::Vacant(entry) => {
+                        entry.insert(footprint);
+                    }
+                }
+            }
+            earlier
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn history(len: usize) -> Vec<Trade> {
+        let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut price = 50_000.0_f32;
+
+        (0..len)
+            .map(|idx| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                price += ((seed % 21) as f32 - 10.0) * 0.5;
+
+                Trade {
+                    time: 1_700_000_000_000 + idx as u64 * 7,
+                    is_sell: seed.is_multiple_of(3),
+                    price: Price::from_f32(price),
+                    qty: (seed % 1000) as f32 / 100.0,
+                    sub_ms_nanos: 0,
+                }
+            })
+            .collect()
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() <= 1e-3 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn chunked_matches_one_by_one() {
+        let trades = history(PARALLEL_MIN * 3);
+        let step = PriceStep::from_f32(5.0);
+
+        let mut expected = KlineTrades::new();
+        for trade in &trades {
+            expected.add_trade_to_nearest_bin(trade, step);
+        }
+        let chunked = collect(&trades, step);
+
+        assert_eq!(chunked.trades.len(), expected.trades.len());
+        for (price, group) in &expected.trades {
+            let merged = &chunked.trades[price];
+            assert_eq!(
+                (
+                    merged.buy_count,
+                    merged.sell_count,
+                    merged.first_time,
+                    merged.last_time
+                ),
+                (
+                    group.buy_count,
+                    group.sell_count,
+                    group.first_time,
+                    group.last_time
+                )
+            );
+            assert!(close(merged.buy_qty, group.buy_qty));
+            assert!(close(merged.sell_qty, group.sell_qty));
+        }
+
+        let (chunked, expected) = (chunked.stats(), expected.stats());
+        assert!(close(chunked.max_delta, expected.max_delta));
+        assert_eq!(chunked.trades, expected.trades);
+    }
+}
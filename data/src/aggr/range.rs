@@ -0,0 +1,101 @@
+use crate::aggr::ticks::{self, TickAccumulation};
+use crate::chart::kline::ClusterKind;
+use exchange::Trade;
+use exchange::util::{MinTicksize, Price, PriceStep};
+
+use std::collections::BTreeMap;
+
+/// Range sizes offered in the basis picker, as multiples of the ticker's min tick
+pub const TICK_PRESETS: [u16; 9] = [4, 5, 10, 15, 20, 25, 50, 100, 200];
+
+pub const MIN_RANGE_TICKS: u16 = 1;
+pub const MAX_RANGE_TICKS: u16 = 10_000;
+
+/// Bar range in price units spanning `ticks` minimum ticks of the ticker
+pub fn range_from_ticks(ticks: u16, min_tick: MinTicksize) -> f32 {
+    Price::from_f32(f32::from(ticks) * min_tick.as_f32())
+        .round_to_min_tick(min_tick)
+        .to_f32()
+}
+
+/// Bars that close once price has moved `range` away from the bar open, in either direction.
+///
+/// The trade that reaches the range is kept in the bar, the next trade opens a new one.
+pub struct RangeAggr {
+    pub datapoints: Vec<TickAccumulation>,
+    pub range: Price,
+    pub tick_size: PriceStep,
+}
+
+impl RangeAggr {
+    pub fn new(range: f32, tick_size: PriceStep, raw_trades: &[Trade]) -> Self {
+        let mut range_aggr = Self {
+            datapoints: Vec::new(),
+            range: Price::from_f32(range).max(Price::from_units(1)),
+            tick_size,
+        };
+
+        if !raw_trades.is_empty() {
+            range_aggr.insert_trades(raw_trades);
+        }
+
+        range_aggr
+    }
+
+    pub fn change_tick_size(&mut self, tick_size: f32, raw_trades: &[Trade]) {
+        self.tick_size = PriceStep::from_f32(tick_size);
+
+        self.datapoints.clear();
+
+        if !raw_trades.is_empty() {
+            self.insert_trades(raw_trades);
+        }
+    }
+
+    /// return latest data point and its index
+    pub fn latest_dp(&self) -> Option<(&TickAccumulation, usize)> {
+        self.datapoints
+            .last()
+            .map(|dp| (dp, self.datapoints.len() - 1))
+    }
+
+    pub fn volume_data(&self) -> BTreeMap<u64, (f32, f32)> {
+        ticks::volume_data(&self.datapoints)
+    }
+
+    pub fn insert_trades(&mut self, buffer: &[Trade]) {
+        let range = self.range;
+
+        ticks::insert_trades(&mut self.datapoints, buffer, self.tick_size, |dp| {
+            let kline = &dp.kline;
+            kline.high - kline.open >= range || kline.open - kline.low >= range
+        });
+    }
+
+    pub fn update_poc_status(&mut self) {
+        ticks::update_poc_status(&mut self.datapoints, self.tick_size);
+    }
+
+    pub fn min_max_price_in_range(&self, earliest: usize, latest: usize) -> Option<(f32, f32)> {
+        ticks::min_max_price_in_range(&self.datapoints, earliest, latest)
+            .map(|(min_p, max_p)| (min_p.to_f32(), max_p.to_f32()))
+    }
+
+    pub fn max_qty_idx_range(
+        &self,
+        cluster_kind: ClusterKind,
+        earliest: usize,
+        latest: usize,
+        highest: Price,
+        lowest: Price,
+    ) -> f32 {
+        ticks::max_qty_idx_range(
+            &self.datapoints,
+            cluster_kind,
+            earliest,
+            latest,
+            highest,
+            lowest,
+        )
+    }
+}
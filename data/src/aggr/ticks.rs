@@ -1,9 +1,11 @@
-use crate::aggr;
+use crate::aggr::{self, footprint};
 use crate::chart::kline::{ClusterKind, KlineTrades, NPoc};
 use exchange::util::{Price, PriceStep};
 use exchange::{Kline, Trade};
 
+use rayon::prelude::*;
 use std::collections::BTreeMap;
+use std::ops::Range;
 
 #[derive(Debug, Clone)]
 pub struct TickAccumulation {
@@ -14,9 +16,13 @@ pub struct TickAccumulation {
 
 impl TickAccumulation {
     pub fn new(trade: &Trade, step: PriceStep) -> Self {
-        let mut footprint = KlineTrades::new();
-        footprint.add_trade_to_nearest_bin(trade, step);
+        let mut bar = Self::open(trade);
+        bar.add_trade(trade, step);
+        bar
+    }
 
+    /// Bar opened by `trade`, its footprint left empty
+    fn open(trade: &Trade) -> Self {
         let kline = Kline {
             time: trade.time,
             open: trade.price,
@@ -32,11 +38,17 @@ impl TickAccumulation {
         Self {
             tick_count: 1,
             kline,
-            footprint,
+            footprint: KlineTrades::new(),
         }
     }
 
     pub fn update_with_trade(&mut self, trade: &Trade, step: PriceStep) {
+        self.extend(trade);
+        self.add_trade(trade, step);
+    }
+
+    /// Counts `trade` into the bar's kline, leaving the footprint
+    fn extend(&mut self, trade: &Trade) {
         self.tick_count += 1;
         self.kline.high = self.kline.high.max(trade.price);
         self.kline.low = self.kline.low.min(trade.price);
@@ -47,8 +59,6 @@ impl TickAccumulation {
         } else {
             self.kline.volume.0 += trade.qty;
         }
-
-        self.add_trade(trade, step);
     }
 
     fn add_trade(&mut self, trade: &Trade, step: PriceStep) {
@@ -72,6 +82,10 @@ impl TickAccumulation {
         self.tick_count >= interval.0 as usize
     }
 
+    pub fn total_volume(&self) -> f32 {
+        self.kline.volume.0 + self.kline.volume.1
+    }
+
     pub fn poc_price(&self) -> Option<Price> {
         self.footprint.poc_price()
     }
@@ -128,73 +142,15 @@ impl TickAggr {
     }
 
     pub fn insert_trades(&mut self, buffer: &[Trade]) {
-        let mut updated_indices = Vec::new();
+        let interval = self.interval;
 
-        for trade in buffer {
-            if self.datapoints.is_empty() {
-                self.datapoints
-                    .push(TickAccumulation::new(trade, self.tick_size));
-                updated_indices.push(0);
-            } else {
-                let last_idx = self.datapoints.len() - 1;
-
-                if self.datapoints[last_idx].is_full(self.interval) {
-                    self.datapoints
-                        .push(TickAccumulation::new(trade, self.tick_size));
-                    updated_indices.push(self.datapoints.len() - 1);
-                } else {
-                    self.datapoints[last_idx].update_with_trade(trade, self.tick_size);
-                    if !updated_indices.contains(&last_idx) {
-                        updated_indices.push(last_idx);
-                    }
-                }
-            }
-        }
-
-        for idx in updated_indices {
-            if idx < self.datapoints.len() {
-                self.datapoints[idx].calculate_poc();
-            }
-        }
-
-        self.update_poc_status();
+        insert_trades(&mut self.datapoints, buffer, self.tick_size, |dp| {
+            dp.is_full(interval)
+        });
     }
 
     pub fn update_poc_status(&mut self) {
-        let updates = self
-            .datapoints
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, dp)| dp.poc_price().map(|price| (idx, price)))
-            .collect::<Vec<_>>();
-
-        let total_points = self.datapoints.len();
-
-        for (current_idx, poc_price) in updates {
-            let mut npoc = NPoc::default();
-
-            for next_idx in (current_idx + 1)..total_points {
-                let next_dp = &self.datapoints[next_idx];
-
-                let next_dp_low = next_dp.kline.low.round_to_side_step(true, self.tick_size);
-                let next_dp_high = next_dp.kline.high.round_to_side_step(false, self.tick_size);
-
-                if next_dp_low <= poc_price && next_dp_high >= poc_price {
-                    // on render we reverse the order of the points
-                    // as it is easier to just take the idx=0 as latest candle for coords
-                    let reversed_idx = (total_points - 1) - next_idx;
-                    npoc.filled(reversed_idx as u64);
-                    break;
-                } else {
-                    npoc.unfilled();
-                }
-            }
-
-            if current_idx < total_points {
-                let data_point = &mut self.datapoints[current_idx];
-                data_point.set_poc_status(npoc);
-            }
-        }
+        update_poc_status(&mut self.datapoints, self.tick_size);
     }
 
     pub fn min_max_price_in_range_prices(
@@ -202,36 +158,7 @@ impl TickAggr {
         earliest: usize,
         latest: usize,
     ) -> Option<(Price, Price)> {
-        if earliest > latest {
-            return None;
-        }
-
-        let mut min_p: Option<Price> = None;
-        let mut max_p: Option<Price> = None;
-
-        self.datapoints
-            .iter()
-            .rev()
-            .enumerate()
-            .filter(|(idx, _)| *idx >= earliest && *idx <= latest)
-            .for_each(|(_, dp)| {
-                let low = dp.kline.low;
-                let high = dp.kline.high;
-
-                min_p = Some(match min_p {
-                    Some(value) => value.min(low),
-                    None => low,
-                });
-                max_p = Some(match max_p {
-                    Some(value) => value.max(high),
-                    None => high,
-                });
-            });
-
-        match (min_p, max_p) {
-            (Some(low), Some(high)) => Some((low, high)),
-            _ => None,
-        }
+        min_max_price_in_range(&self.datapoints, earliest, latest)
     }
 
     pub fn min_max_price_in_range(&self, earliest: usize, latest: usize) -> Option<(f32, f32)> {
@@ -247,30 +174,172 @@ impl TickAggr {
         highest: Price,
         lowest: Price,
     ) -> f32 {
-        let mut max_cluster_qty: f32 = 0.0;
+        max_qty_idx_range(
+            &self.datapoints,
+            cluster_kind,
+            earliest,
+            latest,
+            highest,
+            lowest,
+        )
+    }
+}
 
-        self.datapoints
-            .iter()
-            .rev()
-            .enumerate()
-            .filter(|(index, _)| *index <= latest && *index >= earliest)
-            .for_each(|(_, dp)| {
-                max_cluster_qty =
-                    max_cluster_qty.max(dp.max_cluster_qty(cluster_kind, highest, lowest));
+/// Appends trades to trade-driven bars, opening a new bar whenever `is_full` says the last one is done.
+/// Shared by every basis whose bars close on trade flow rather than on time
+pub fn insert_trades(
+    datapoints: &mut Vec<TickAccumulation>,
+    buffer: &[Trade],
+    tick_size: PriceStep,
+    is_full: impl Fn(&TickAccumulation) -> bool,
+) {
+    // bars close on their kline alone, so they're cut first and the footprints, where the
+    // time goes, are filled in after from each bar's span of the buffer
+    let mut first_bar = None;
+    let mut spans: Vec<Range<usize>> = Vec::new();
+
+    for (idx, trade) in buffer.iter().enumerate() {
+        match datapoints.last_mut() {
+            Some(bar) if !is_full(bar) => {
+                bar.extend(trade);
+                match spans.last_mut() {
+                    Some(span) => span.end = idx + 1,
+                    None => spans.push(idx..idx + 1),
+                }
+            }
+            _ => {
+                datapoints.push(TickAccumulation::open(trade));
+                spans.push(idx..idx + 1);
+            }
+        }
+        first_bar.get_or_insert(datapoints.len() - 1);
+    }
+
+    if let Some(first_bar) = first_bar {
+        let fill = |(bar, span): (&mut TickAccumulation, Range<usize>)| {
+            bar.footprint
+                .merge(footprint::collect(&buffer[span], tick_size));
+            bar.calculate_poc();
+        };
+
+        let bars = &mut datapoints[first_bar..];
+        if buffer.len() < footprint::PARALLEL_MIN {
+            bars.iter_mut().zip(spans).for_each(fill);
+        } else {
+            bars.par_iter_mut().zip(spans).for_each(fill);
+        }
+    }
+
+    update_poc_status(datapoints, tick_size);
+}
+
+pub fn update_poc_status(datapoints: &mut [TickAccumulation], tick_size: PriceStep) {
+    let updates = datapoints
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, dp)| dp.poc_price().map(|price| (idx, price)))
+        .collect::<Vec<_>>();
+
+    let total_points = datapoints.len();
+
+    for (current_idx, poc_price) in updates {
+        let mut npoc = NPoc::default();
+
+        for (next_idx, next_dp) in datapoints.iter().enumerate().skip(current_idx + 1) {
+            let next_dp_low = next_dp.kline.low.round_to_side_step(true, tick_size);
+            let next_dp_high = next_dp.kline.high.round_to_side_step(false, tick_size);
+
+            if next_dp_low <= poc_price && next_dp_high >= poc_price {
+                // on render we reverse the order of the points
+                // as it is easier to just take the idx=0 as latest candle for coords
+                let reversed_idx = (total_points - 1) - next_idx;
+                npoc.filled(reversed_idx as u64);
+                break;
+            } else {
+                npoc.unfilled();
+            }
+        }
+
+        if current_idx < total_points {
+            let data_point = &mut datapoints[current_idx];
+            data_point.set_poc_status(npoc);
+        }
+    }
+}
+
+/// `earliest` and `latest` are reversed indices, 0 being the latest bar
+pub fn min_max_price_in_range(
+    datapoints: &[TickAccumulation],
+    earliest: usize,
+    latest: usize,
+) -> Option<(Price, Price)> {
+    if earliest > latest {
+        return None;
+    }
+
+    let mut min_p: Option<Price> = None;
+    let mut max_p: Option<Price> = None;
+
+    datapoints
+        .iter()
+        .rev()
+        .enumerate()
+        .filter(|(idx, _)| *idx >= earliest && *idx <= latest)
+        .for_each(|(_, dp)| {
+            let low = dp.kline.low;
+            let high = dp.kline.high;
+
+            min_p = Some(match min_p {
+                Some(value) => value.min(low),
+                None => low,
             });
+            max_p = Some(match max_p {
+                Some(value) => value.max(high),
+                None => high,
+            });
+        });
 
-        max_cluster_qty
+    match (min_p, max_p) {
+        (Some(low), Some(high)) => Some((low, high)),
+        _ => None,
     }
 }
 
+pub fn max_qty_idx_range(
+    datapoints: &[TickAccumulation],
+    cluster_kind: ClusterKind,
+    earliest: usize,
+    latest: usize,
+    highest: Price,
+    lowest: Price,
+) -> f32 {
+    let mut max_cluster_qty: f32 = 0.0;
+
+    datapoints
+        .iter()
+        .rev()
+        .enumerate()
+        .filter(|(index, _)| *index <= latest && *index >= earliest)
+        .for_each(|(_, dp)| {
+            max_cluster_qty =
+                max_cluster_qty.max(dp.max_cluster_qty(cluster_kind, highest, lowest));
+        });
+
+    max_cluster_qty
+}
+
+/// Volume per bar keyed by bar index, oldest first
+pub fn volume_data(datapoints: &[TickAccumulation]) -> BTreeMap<u64, (f32, f32)> {
+    datapoints
+        .iter()
+        .enumerate()
+        .map(|(idx, dp)| (idx as u64, (dp.kline.volume.0, dp.kline.volume.1)))
+        .collect()
+}
+
 impl From<&TickAggr> for BTreeMap<u64, (f32, f32)> {
     /// Converts datapoints into a map of timestamps and volume data
     fn from(tick_aggr: &TickAggr) -> Self {
-        tick_aggr
-            .datapoints
-            .iter()
-            .enumerate()
-            .map(|(idx, dp)| (idx as u64, (dp.kline.volume.0, dp.kline.volume.1)))
-            .collect()
+        volume_data(&tick_aggr.datapoints)
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 
+use crate::aggr::footprint;
 use crate::chart::Basis;
 use crate::chart::heatmap::HeatmapDataPoint;
 use crate::chart::kline::{ClusterKind, KlineDataPoint, KlineTrades, NPoc};
@@ -256,22 +257,17 @@ impl TimeSeries<KlineDataPoint> {
         if buffer.is_empty() {
             return;
         }
-        let aggr_time = self.interval.to_milliseconds();
-        let mut updated_times: Vec<u64> = Vec::new();
-
-        for trade in buffer {
-            let rounded_time = (trade.time / aggr_time) * aggr_time;
-
-            if let Some(entry) = self.datapoints.get_mut(&rounded_time) {
-                if !updated_times.contains(&rounded_time) {
-                    updated_times.push(rounded_time);
-                }
-                entry.add_trade(trade, self.tick_size);
-            }
-        }
-
-        for time in updated_times {
+        let datapoints = &self.datapoints;
+        let footprints = footprint::collect_by_time(
+            buffer,
+            self.interval.to_milliseconds(),
+            self.tick_size,
+            |time| datapoints.contains_key(&time),
+        );
+
+        for (time, footprint) in footprints {
             if let Some(data_point) = self.datapoints.get_mut(&time) {
+                data_point.footprint.merge(footprint);
                 data_point.calculate_poc();
             }
         }
@@ -0,0 +1,94 @@
+use crate::aggr::ticks::{self, TickAccumulation};
+use crate::chart::kline::ClusterKind;
+use exchange::Trade;
+use exchange::util::{Price, PriceStep};
+
+use std::collections::BTreeMap;
+
+/// Volume targets offered in the basis picker, in traded size units
+pub const PRESETS: [u32; 9] = [10, 50, 100, 500, 1_000, 5_000, 10_000, 50_000, 100_000];
+
+pub const MIN_VOLUME: u32 = 1;
+pub const MAX_VOLUME: u32 = 10_000_000;
+
+/// Bars that close once their traded volume (buy + sell) reaches `interval`.
+///
+/// A trade that crosses the target stays in the bar it lands in, so bars
+/// can end up slightly above the target rather than splitting fills.
+pub struct VolumeAggr {
+    pub datapoints: Vec<TickAccumulation>,
+    pub interval: u32,
+    pub tick_size: PriceStep,
+}
+
+impl VolumeAggr {
+    pub fn new(interval: u32, tick_size: PriceStep, raw_trades: &[Trade]) -> Self {
+        let mut volume_aggr = Self {
+            datapoints: Vec::new(),
+            interval: interval.max(1),
+            tick_size,
+        };
+
+        if !raw_trades.is_empty() {
+            volume_aggr.insert_trades(raw_trades);
+        }
+
+        volume_aggr
+    }
+
+    pub fn change_tick_size(&mut self, tick_size: f32, raw_trades: &[Trade]) {
+        self.tick_size = PriceStep::from_f32(tick_size);
+
+        self.datapoints.clear();
+
+        if !raw_trades.is_empty() {
+            self.insert_trades(raw_trades);
+        }
+    }
+
+    /// return latest data point and its index
+    pub fn latest_dp(&self) -> Option<(&TickAccumulation, usize)> {
+        self.datapoints
+            .last()
+            .map(|dp| (dp, self.datapoints.len() - 1))
+    }
+
+    pub fn volume_data(&self) -> BTreeMap<u64, (f32, f32)> {
+        ticks::volume_data(&self.datapoints)
+    }
+
+    pub fn insert_trades(&mut self, buffer: &[Trade]) {
+        let target = self.interval as f32;
+
+        ticks::insert_trades(&mut self.datapoints, buffer, self.tick_size, |dp| {
+            dp.total_volume() >= target
+        });
+    }
+
+    pub fn update_poc_status(&mut self) {
+        ticks::update_poc_status(&mut self.datapoints, self.tick_size);
+    }
+
+    pub fn min_max_price_in_range(&self, earliest: usize, latest: usize) -> Option<(f32, f32)> {
+        ticks::min_max_price_in_range(&self.datapoints, earliest, latest)
+            .map(|(min_p, max_p)| (min_p.to_f32(), max_p.to_f32()))
+    }
+
+    pub fn max_qty_idx_range(
+        &self,
+        cluster_kind: ClusterKind,
+        earliest: usize,
+        latest: usize,
+        highest: Price,
+        lowest: Price,
+    ) -> f32 {
+        ticks::max_qty_idx_range(
+            &self.datapoints,
+            cluster_kind,
+            earliest,
+            latest,
+            highest,
+            lowest,
+        )
+    }
+}
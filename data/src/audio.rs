@@ -23,6 +23,9 @@ impl std::fmt::Display for Threshold {
 pub struct StreamCfg {
     pub enabled: bool,
     pub threshold: Threshold,
+    /// Also play a sound for each liquidation on perpetual streams
+    #[serde(default)]
+    pub liquidations: bool,
 }
 
 impl Default for StreamCfg {
@@ -30,6 +33,7 @@ impl Default for StreamCfg {
         StreamCfg {
             enabled: true,
             threshold: Threshold::Count(10),
+            liquidations: false,
         }
     }
 }
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Quiet time after a structural change before it's written, so a burst of splits saves once
+pub const DEBOUNCE: Duration = Duration::from_secs(3);
+
+/// Writing the saved state in the background, so an abrupt exit loses little of the layouts
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AutosaveSettings {
+    /// Seconds between periodic saves, `None` only saves on exit
+    pub interval_secs: Option<u64>,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: Some(Self::DEFAULT_INTERVAL_SECS),
+        }
+    }
+}
+
+impl AutosaveSettings {
+    pub const INTERVAL_CHOICES: [u64; 5] = [15, 30, 60, 120, 300];
+    pub const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+    pub fn is_enabled(&self) -> bool {
+        self.interval_secs.is_some()
+    }
+}
+
+/// When the state was last written and whether a structural change is waiting to be
+#[derive(Debug, Clone, Copy)]
+pub struct Autosave {
+    last_saved: Instant,
+    changed_at: Option<Instant>,
+}
+
+impl Autosave {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_saved: now,
+            changed_at: None,
+        }
+    }
+
+    /// Panes added or removed, or a stream changed; restarts the debounce
+    pub fn mark_changed(&mut self, now: Instant) {
+        self.changed_at = Some(now);
+    }
+
+    pub fn is_due(&self, settings: &AutosaveSettings, now: Instant) -> bool {
+        let Some(interval_secs) = settings.interval_secs else {
+            return false;
+        };
+
+        match self.changed_at {
+            Some(changed_at) => now.saturating_duration_since(changed_at) >= DEBOUNCE,
+            None => {
+                now.saturating_duration_since(self.last_saved) >= Duration::from_secs(interval_secs)
+            }
+        }
+    }
+
+    pub fn saved(&mut self, now: Instant) {
+        self.last_saved = now;
+        self.changed_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_saved_after_the_debounce_and_otherwise_periodically() {
+        let start = Instant::now();
+        let settings = AutosaveSettings {
+            interval_secs: Some(60),
+        };
+        let mut autosave = Autosave::new(start);
+
+        assert!(!autosave.is_due(&settings, start + Duration::from_secs(59)));
+        assert!(autosave.is_due(&settings, start + Duration::from_secs(60)));
+
+        autosave.mark_changed(start + Duration::from_secs(10));
+        autosave.mark_changed(start + Duration::from_secs(12));
+        assert!(!autosave.is_due(&settings, start + Duration::from_secs(14)));
+        assert!(autosave.is_due(&settings, start + Duration::from_secs(15)));
+
+        autosave.saved(start + Duration::from_secs(15));
+        assert!(!autosave.is_due(&settings, start + Duration::from_secs(70)));
+
+        autosave.mark_changed(start + Duration::from_secs(20));
+        assert!(!autosave.is_due(
+            &AutosaveSettings {
+                interval_secs: None
+            },
+            start + DEBOUNCE * 10
+        ));
+    }
+}
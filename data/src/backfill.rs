@@ -0,0 +1,253 @@
+//! Historical trades over a range for footprint backfill, paged the way each venue allows:
+//! forwards by time through archives and REST, or backwards by trade id
+
+use exchange::adapter::{AdapterError, ExchangeInclusive, binance, bybit, okex};
+use exchange::{TickerInfo, Trade};
+
+/// Trades of one page within the range, and whether the range is covered with it
+#[derive(Debug, Default)]
+pub struct Batch {
+    pub trades: Vec<Trade>,
+    pub is_complete: bool,
+}
+
+/// Fetches the pages of `from..=to` one after another, until a batch comes back complete
+pub struct Backfill {
+    ticker_info: TickerInfo,
+    paging: Paging,
+}
+
+impl Backfill {
+    pub fn new(ticker_info: TickerInfo, from: u64, to: u64) -> Self {
+        let paging = match ExchangeInclusive::of(ticker_info.exchange()) {
+            ExchangeInclusive::Okex => Paging::backward(from, to),
+            _ => Paging::forward(from, to),
+        };
+
+        Self {
+            ticker_info,
+            paging,
+        }
+    }
+
+    pub async fn next_batch(&mut self) -> Result<Batch, AdapterError> {
+        if self.paging.is_complete {
+            return Ok(Batch {
+                trades: vec![],
+                is_complete: true,
+            });
+        }
+
+        let venue = ExchangeInclusive::of(self.ticker_info.exchange());
+
+        match self.paging.cursor {
+            Cursor::Forward { since, .. } => {
+                let page = match venue {
+                    ExchangeInclusive::Binance => {
+                        let data_path = crate::data_path(Some("market_data/binance/"));
+                        binance::fetch_trades(self.ticker_info, since, data_path).await?
+                    }
+                    ExchangeInclusive::Bybit => {
+                        let data_path = crate::data_path(Some("market_data/bybit/"));
+                        bybit::fetch_trades(self.ticker_info, since, data_path).await?
+                    }
+                    _ => {
+                        return Err(AdapterError::InvalidRequest(format!(
+                            "Historical trades aren't available for {venue}"
+                        )));
+                    }
+                };
+                Ok(self.paging.take_forward(page))
+            }
+            Cursor::Backward { before_id } => {
+                let (page, oldest_id) =
+                    okex::fetch_trades_before(self.ticker_info, self.paging.to, before_id).await?;
+                Ok(self.paging.take_backward(page, oldest_id))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cursor {
+    /// Pages start at `since` inclusive, where the last `seen` trades were delivered already
+    Forward { since: u64, seen: usize },
+    /// Pages end before trade `before_id`, at `to` for the first page
+    Backward { before_id: Option<u64> },
+}
+
+/// Where the next page starts, and when the range is covered
+#[derive(Debug)]
+struct Paging {
+    from: u64,
+    to: u64,
+    cursor: Cursor,
+    is_complete: bool,
+}
+
+impl Paging {
+    fn forward(from: u64, to: u64) -> Self {
+        Self {
+            from,
+            to,
+            cursor: Cursor::Forward {
+                since: from,
+                seen: 0,
+            },
+            is_complete: false,
+        }
+    }
+
+    fn backward(from: u64, to: u64) -> Self {
+        Self {
+            from,
+            to,
+            cursor: Cursor::Backward { before_id: None },
+            is_complete: false,
+        }
+    }
+
+    /// Takes a page sorted oldest first, done once it comes back empty or reaches `to`
+    fn take_forward(&mut self, page: Vec<Trade>) -> Batch {
+        let Cursor::Forward { since, seen } = self.cursor else {
+            return self.complete();
+        };
+
+        // the page starts over at `since`, skip what the last one ended with
+        let mut skip = seen;
+        let fresh: Vec<Trade> = page
+            .into_iter()
+            .filter(|trade| {
+                if trade.time == since && skip > 0 {
+                    skip -= 1;
+                    false
+                } else {
+                    trade.time >= since
+                }
+            })
+            .collect();
+
+        let Some(latest) = fresh.last().map(|trade| trade.time) else {
+            return self.complete();
+        };
+
+        let mut seen_at_latest = fresh.iter().rev().take_while(|t| t.time == latest).count();
+        if latest == since {
+            seen_at_latest += seen;
+        }
+        self.cursor = Cursor::Forward {
+            since: latest,
+            seen: seen_at_latest,
+        };
+        self.is_complete = latest >= self.to;
+
+        self.batch(fresh)
+    }
+
+    /// Takes a page sorted oldest first, done once it comes back empty, reaches `from`
+    /// or has nothing older to continue before
+    fn take_backward(&mut self, page: Vec<Trade>, oldest_id: Option<u64>) -> Batch {
+        let Some(oldest) = page.first().map(|trade| trade.time) else {
+            return self.complete();
+        };
+
+        self.cursor = Cursor::Backward {
+            before_id: oldest_id,
+        };
+        self.is_complete = oldest <= self.from || oldest_id.is_none();
+
+        self.batch(page)
+    }
+
+    fn batch(&self, page: Vec<Trade>) -> Batch {
+        Batch {
+            trades: page
+                .into_iter()
+                .filter(|trade| (self.from..=self.to).contains(&trade.time))
+                .collect(),
+            is_complete: self.is_complete,
+        }
+    }
+
+    fn complete(&mut self) -> Batch {
+        self.is_complete = true;
+        Batch {
+            trades: vec![],
+            is_complete: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn trades(times: &[u64]) -> Vec<Trade> {
+        times
+            .iter()
+            .map(|&time| Trade {
+                time,
+                is_sell: false,
+                price: Price::from_f32(100.0),
+                qty: 1.0,
+                sub_ms_nanos: 0,
+            })
+            .collect()
+    }
+
+    fn times(batch: &Batch) -> Vec<u64> {
+        batch.trades.iter().map(|trade| trade.time).collect()
+    }
+
+    #[test]
+    fn backward_page_ending_at_until_time_is_not_the_end() {
+        let mut paging = Paging::backward(1_000, 5_000);
+
+        // the first page is the newest, its last trade sits right at the end of the range
+        let batch = paging.take_backward(trades(&[4_000, 4_500, 5_000, 5_001]), Some(40));
+        assert_eq!(times(&batch), [4_000, 4_500, 5_000]);
+        assert!(!batch.is_complete);
+        assert_eq!(
+            paging.cursor,
+            Cursor::Backward {
+                before_id: Some(40)
+            }
+        );
+
+        let batch = paging.take_backward(trades(&[900, 2_000]), Some(30));
+        assert_eq!(times(&batch), [2_000]);
+        assert!(batch.is_complete);
+    }
+
+    #[test]
+    fn empty_page_completes_either_way() {
+        let mut paging = Paging::backward(1_000, 5_000);
+        assert!(paging.take_backward(vec![], None).is_complete);
+
+        let mut paging = Paging::forward(1_000, 5_000);
+        assert!(!paging.take_forward(trades(&[1_000, 2_000])).is_complete);
+        assert!(paging.take_forward(vec![]).is_complete);
+    }
+
+    #[test]
+    fn forward_pages_skip_trades_repeated_at_the_cursor() {
+        let mut paging = Paging::forward(1_000, 5_000);
+
+        let batch = paging.take_forward(trades(&[1_000, 2_000, 2_000]));
+        assert_eq!(times(&batch), [1_000, 2_000, 2_000]);
+        assert!(!batch.is_complete);
+
+        // the next page restarts at 2_000 and holds a third trade of that millisecond
+        let batch = paging.take_forward(trades(&[2_000, 2_000, 2_000, 3_000]));
+        assert_eq!(times(&batch), [2_000, 3_000]);
+
+        // only repeats left means nothing newer exists yet
+        assert!(paging.take_forward(trades(&[3_000])).is_complete);
+
+        let mut paging = Paging::forward(1_000, 5_000);
+        let batch = paging.take_forward(trades(&[4_000, 5_000, 6_000]));
+        assert_eq!(times(&batch), [4_000, 5_000]);
+        assert!(batch.is_complete);
+    }
+}
@@ -1,18 +1,45 @@
+pub mod alert;
+pub mod colors;
 pub mod comparison;
+pub mod divergence;
+pub mod drawing;
 pub mod heatmap;
 pub mod indicator;
 pub mod kline;
+pub mod moving_average;
+pub mod pin;
+pub mod session;
 
 use exchange::Timeframe;
 use serde::{Deserialize, Serialize};
 
 use super::aggr::{
     self,
-    ticks::TickAggr,
+    range::RangeAggr,
+    ticks::{TickAccumulation, TickAggr},
     time::{DataPoint, TimeSeries},
+    volume::VolumeAggr,
 };
 pub use kline::KlineChartKind;
 
+/// Liquidation markers drawn over heatmap and footprint charts of perpetual markets
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LiquidationMarkers {
+    pub enabled: bool,
+    /// Liquidations below this size in quote currency are hidden
+    pub min_size: f32,
+}
+
+impl Default for LiquidationMarkers {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size: 10_000.0,
+        }
+    }
+}
+
 // NEW: Enhanced data structures for professional trading features
 #[derive(Debug, Clone)]
 pub struct MarketAnalysis {
@@ -48,7 +75,7 @@ pub enum LevelType {
     Breakout,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TradingConfig {
     pub show_volume_profile: bool,
     pub show_delta_profile: bool,
@@ -80,6 +107,8 @@ impl Default for TradingConfig {
 pub enum PlotData<D: DataPoint> {
     TimeBased(TimeSeries<D>),
     TickBased(TickAggr),
+    VolumeBased(VolumeAggr),
+    RangeBased(RangeAggr),
 }
 
 impl<D: DataPoint> PlotData<D> {
@@ -91,6 +120,12 @@ impl<D: DataPoint> PlotData<D> {
             PlotData::TickBased(tick_aggr) => tick_aggr
                 .latest_dp()
                 .map_or(0.0, |(dp, _)| calculate_target_y(dp.kline)),
+            PlotData::VolumeBased(volume_aggr) => volume_aggr
+                .latest_dp()
+                .map_or(0.0, |(dp, _)| calculate_target_y(dp.kline)),
+            PlotData::RangeBased(range_aggr) => range_aggr
+                .latest_dp()
+                .map_or(0.0, |(dp, _)| calculate_target_y(dp.kline)),
         }
     }
 
@@ -106,6 +141,12 @@ impl<D: DataPoint> PlotData<D> {
             PlotData::TickBased(tick_aggr) => {
                 tick_aggr.min_max_price_in_range(start_interval as usize, end_interval as usize)
             }
+            PlotData::VolumeBased(volume_aggr) => {
+                volume_aggr.min_max_price_in_range(start_interval as usize, end_interval as usize)
+            }
+            PlotData::RangeBased(range_aggr) => {
+                range_aggr.min_max_price_in_range(start_interval as usize, end_interval as usize)
+            }
         }
     }
 
@@ -133,9 +174,24 @@ impl<D: DataPoint> PlotData<D> {
                     &mut analysis,
                 );
             }
-            PlotData::TickBased(tick_aggr) => {
-                self.analyze_tick_based_data(
-                    tick_aggr,
+            PlotData::TickBased(TickAggr {
+                datapoints,
+                tick_size,
+                ..
+            })
+            | PlotData::VolumeBased(VolumeAggr {
+                datapoints,
+                tick_size,
+                ..
+            })
+            | PlotData::RangeBased(RangeAggr {
+                datapoints,
+                tick_size,
+                ..
+            }) => {
+                self.analyze_trade_based_data(
+                    datapoints,
+                    *tick_size,
                     start_interval,
                     end_interval,
                     config,
@@ -162,9 +218,14 @@ impl<D: DataPoint> PlotData<D> {
         }
 
         if config.auto_detect_support_resistance {
-            self.detect_support_resistance(
-                timeseries,
-                start_interval,
+            let klines = timeseries
+                .datapoints
+                .range(start_interval..=end_interval)
+                .filter_map(|(_, dp)| dp.kline());
+
+            Self::detect_support_resistance(
+                klines,
+                timeseries.tick_size,
                 end_interval,
                 config,
                 analysis,
@@ -172,9 +233,11 @@ impl<D: DataPoint> PlotData<D> {
         }
     }
 
-    fn analyze_tick_based_data(
+    /// Tick and volume bars are indexed latest first, matching how they are laid out on the x-axis
+    fn analyze_trade_based_data(
         &self,
-        tick_aggr: &TickAggr,
+        datapoints: &[TickAccumulation],
+        tick_size: exchange::util::PriceStep,
         start_interval: u64,
         end_interval: u64,
         config: &TradingConfig,
@@ -183,9 +246,9 @@ impl<D: DataPoint> PlotData<D> {
         let start_idx = start_interval as usize;
         let end_idx = end_interval as usize;
 
-        for (index, dp) in tick_aggr
-            .datapoints
+        for (index, dp) in datapoints
             .iter()
+            .rev()
             .enumerate()
             .filter(|(index, _)| *index >= start_idx && *index <= end_idx)
         {
@@ -193,7 +256,15 @@ impl<D: DataPoint> PlotData<D> {
         }
 
         if config.auto_detect_support_resistance {
-            // Support/resistance detection for tick-based data would need different logic
+            let klines = datapoints
+                .iter()
+                .rev()
+                .enumerate()
+                .filter(|(index, _)| *index >= start_idx && *index <= end_idx)
+                .map(|(_, dp)| &dp.kline);
+
+            // the latest bar has the lowest index
+            Self::detect_support_resistance(klines, tick_size, start_interval, config, analysis);
         }
     }
 
@@ -269,49 +340,47 @@ impl<D: DataPoint> PlotData<D> {
         }
     }
 
-    fn detect_support_resistance(
-        &self,
-        timeseries: &TimeSeries<D>,
-        start_interval: u64,
-        end_interval: u64,
+    /// Counts how often bar highs and lows revisit the same tick-rounded price,
+    /// levels touched at least `sr_touch_threshold` times are reported
+    fn detect_support_resistance<'a>(
+        klines: impl Iterator<Item = &'a exchange::Kline>,
+        tick_size: exchange::util::PriceStep,
+        last_touch: u64,
         config: &TradingConfig,
         analysis: &mut MarketAnalysis,
     ) {
-        // Simplified support/resistance detection based on price touches
-        // In a real implementation, this would be more sophisticated
-
-        let price_levels: Vec<f32> = timeseries
-            .datapoints
-            .range(start_interval..=end_interval)
-            .flat_map(|(_, dp)| {
-                dp.kline()
-                    .map(|k| vec![k.low.to_f32(), k.high.to_f32()])
-                    .unwrap_or_default()
-            })
-            .collect();
-
-        // Group nearby price levels and count touches
-        let mut level_map: std::collections::HashMap<u64, u32> = std::collections::HashMap::new();
+        let mut touches_by_level: std::collections::HashMap<exchange::util::Price, u32> =
+            std::collections::HashMap::new();
+        let (mut price_sum, mut count) = (0.0_f32, 0_u32);
+
+        for kline in klines {
+            for price in [kline.low, kline.high] {
+                *touches_by_level
+                    .entry(price.round_to_step(tick_size))
+                    .or_insert(0) += 1;
+            }
+            price_sum += kline.close.to_f32();
+            count += 1;
+        }
 
-        for price in price_levels {
-            let rounded_price = ((price / 0.01).round() * 100.0) as u64; // Use u64 as key for predictability
-            *level_map.entry(rounded_price).or_insert(0) += 1;
+        if count == 0 {
+            return;
         }
+        let average_price = price_sum / count as f32;
 
-        for (price_u64, touches) in level_map {
-            let price = price_u64 as f32 / 100.0;
+        for (price_level, touches) in touches_by_level {
             if touches >= config.sr_touch_threshold {
-                let level_type = if price <= timeseries.average_price().unwrap_or(price) {
+                let level_type = if price_level.to_f32() <= average_price {
                     LevelType::Support
                 } else {
                     LevelType::Resistance
                 };
 
                 analysis.support_resistance.push(SupportResistanceLevel {
-                    price_level: exchange::util::Price::from_f32(price),
-                    strength: (touches as f32 / config.sr_touch_threshold as f32).min(1.0),
+                    price_level,
+                    strength: (touches as f32 / (config.sr_touch_threshold * 2) as f32).min(1.0),
                     touches,
-                    last_touch: end_interval,
+                    last_touch,
                     level_type,
                 });
             }
@@ -343,13 +412,15 @@ impl<D: DataPoint> PlotData<D> {
                     }
                 }
             }
-            PlotData::TickBased(tick_aggr) => {
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
                 let start_idx = start_interval as usize;
                 let end_idx = end_interval as usize;
 
-                for (index, dp) in tick_aggr
-                    .datapoints
+                for (index, dp) in datapoints
                     .iter()
+                    .rev()
                     .enumerate()
                     .filter(|(index, _)| *index >= start_idx && *index <= end_idx)
                 {
@@ -386,6 +457,96 @@ pub struct ViewConfig {
     pub show_volume_histogram: bool,
     pub show_price_levels: bool,
     pub show_market_depth: bool,
+    #[serde(default)]
+    pub navigation: Navigation,
+    #[serde(default)]
+    pub watermark: Watermark,
+    #[serde(default)]
+    pub price_scale: PriceScale,
+}
+
+/// How the chart moves when dragged
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Navigation {
+    /// Keep the x offset on whole bars, so bars don't shift by fractions while panning
+    pub snap_to_bars: bool,
+    /// Keep the chart moving after a fast drag is released
+    pub kinetic: bool,
+    /// Share of the coasting speed lost per frame at 60fps
+    pub friction: f32,
+}
+
+impl Navigation {
+    pub const FRICTION_RANGE: std::ops::RangeInclusive<f32> = 0.01..=0.3;
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Self {
+            snap_to_bars: false,
+            kinetic: false,
+            friction: 0.06,
+        }
+    }
+}
+
+/// Faded symbol, exchange and timeframe behind the chart, so screenshots describe themselves
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Watermark {
+    pub enabled: bool,
+    pub opacity: f32,
+}
+
+impl Watermark {
+    pub const OPACITY_RANGE: std::ops::RangeInclusive<f32> = 0.02..=0.3;
+}
+
+impl Default for Watermark {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            opacity: 0.08,
+        }
+    }
+}
+
+/// How prices are laid out on the vertical axis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PriceScale {
+    /// Keep the current vertical zoom, autoscaling and axis drags leave it as is
+    pub locked: bool,
+    /// Higher prices towards the bottom
+    pub inverted: bool,
+    /// Equal percentage moves take equal space
+    pub log: bool,
+}
+
+impl PriceScale {
+    /// Distance of `price` below `base` in steps of `tick` as laid out on the axis,
+    /// on a log scale a step is the relative size of `tick` at `base`
+    pub fn ticks_below(&self, base: f32, price: f32, tick: f32) -> f32 {
+        let ticks = if self.log && base > 0.0 {
+            (base / price.max(f32::MIN_POSITIVE)).ln() / (tick / base).ln_1p()
+        } else {
+            (base - price) / tick
+        };
+
+        if self.inverted { -ticks } else { ticks }
+    }
+
+    /// Price `ticks` steps below `base`, the inverse of `ticks_below`
+    pub fn price_at(&self, base: f32, ticks: f32, tick: f32) -> f32 {
+        let ticks = if self.inverted { -ticks } else { ticks };
+
+        if self.log && base > 0.0 {
+            base * (-ticks * (tick / base).ln_1p()).exp()
+        } else {
+            base - ticks * tick
+        }
+    }
 }
 
 impl ViewConfig {
@@ -398,6 +559,9 @@ impl ViewConfig {
             show_volume_histogram: true,
             show_price_levels: true,
             show_market_depth: false,
+            navigation: Navigation::default(),
+            watermark: Watermark::default(),
+            price_scale: PriceScale::default(),
         }
     }
 }
@@ -486,8 +650,12 @@ impl std::fmt::Display for Basis {
         match self {
             Basis::Time(timeframe) => write!(f, "{timeframe}"),
             Basis::Tick(count) => write!(f, "{count}"),
-            Basis::Volume(volume) => write!(f, "Vol{}", volume),
-            Basis::Range(range) => write!(f, "Rng{:.2}", range),
+            Basis::Volume(volume) => match volume {
+                v if *v >= 1_000_000 && v % 1_000_000 == 0 => write!(f, "{}MV", v / 1_000_000),
+                v if *v >= 1_000 && v % 1_000 == 0 => write!(f, "{}kV", v / 1_000),
+                v => write!(f, "{v}V"),
+            },
+            Basis::Range(range) => write!(f, "Rng{range}"),
         }
     }
 }
@@ -498,7 +666,7 @@ impl From<exchange::Timeframe> for Basis {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Study {
     Heatmap(Vec<heatmap::HeatmapStudy>),
     Footprint(Vec<kline::FootprintStudy>),
@@ -509,7 +677,7 @@ pub enum Study {
 }
 
 // NEW: Volume profile study configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct VolumeProfileStudy {
     pub show_poc: bool,      // Point of Control
     pub show_vah: bool,      // Value Area High
@@ -531,7 +699,7 @@ impl Default for VolumeProfileStudy {
 }
 
 // NEW: Market depth study
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct MarketDepthStudy {
     pub levels: u32,           // Number of levels to show
     pub show_cumulative: bool, // Show cumulative volume
@@ -549,7 +717,7 @@ impl Default for MarketDepthStudy {
 }
 
 // NEW: Order flow study
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct OrderFlowStudy {
     pub show_imbalances: bool,
     pub show_absorption: bool,
@@ -0,0 +1,412 @@
+use exchange::Ticker;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AlertTrigger {
+    Crossing,
+    CrossingUp,
+    CrossingDown,
+}
+
+impl AlertTrigger {
+    pub const ALL: [AlertTrigger; 3] = [
+        AlertTrigger::Crossing,
+        AlertTrigger::CrossingUp,
+        AlertTrigger::CrossingDown,
+    ];
+}
+
+impl std::fmt::Display for AlertTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertTrigger::Crossing => write!(f, "Crossing"),
+            AlertTrigger::CrossingUp => write!(f, "Crossing up"),
+            AlertTrigger::CrossingDown => write!(f, "Crossing down"),
+        }
+    }
+}
+
+/// One-shot price alert, stays listed after firing until re-armed or deleted
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PriceAlert {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub price: f32,
+    pub trigger: AlertTrigger,
+    /// Muted alerts still fire and get drawn, but don't notify
+    #[serde(default)]
+    pub muted: bool,
+    /// Unix millis of the trade that fired the alert
+    #[serde(default)]
+    pub fired_at: Option<u64>,
+}
+
+impl PriceAlert {
+    pub fn is_armed(&self) -> bool {
+        self.fired_at.is_none()
+    }
+
+    /// Whether a move from `prev` to `last` fires the alert
+    pub fn is_hit(&self, prev: f32, last: f32) -> bool {
+        let up = prev < self.price && last >= self.price;
+        let down = prev > self.price && last <= self.price;
+
+        match self.trigger {
+            AlertTrigger::Crossing => up || down,
+            AlertTrigger::CrossingUp => up,
+            AlertTrigger::CrossingDown => down,
+        }
+    }
+}
+
+/// Chart series a composite condition reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Metric {
+    Price,
+    Cvd,
+    OpenInterest,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Price, Metric::Cvd, Metric::OpenInterest];
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Price => write!(f, "Price"),
+            Metric::Cvd => write!(f, "CVD"),
+            Metric::OpenInterest => write!(f, "Open interest"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ConditionOp {
+    Above,
+    Below,
+    Rising,
+    Falling,
+}
+
+impl ConditionOp {
+    pub const ALL: [ConditionOp; 4] = [
+        ConditionOp::Above,
+        ConditionOp::Below,
+        ConditionOp::Rising,
+        ConditionOp::Falling,
+    ];
+
+    /// Whether the op compares against `Condition::bars` rather than `Condition::value`
+    pub fn is_trend(&self) -> bool {
+        matches!(self, ConditionOp::Rising | ConditionOp::Falling)
+    }
+}
+
+impl std::fmt::Display for ConditionOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionOp::Above => write!(f, "above"),
+            ConditionOp::Below => write!(f, "below"),
+            ConditionOp::Rising => write!(f, "rising"),
+            ConditionOp::Falling => write!(f, "falling"),
+        }
+    }
+}
+
+/// A single test on a chart series, e.g. "CVD rising over 3 bars"
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Condition {
+    pub metric: Metric,
+    pub op: ConditionOp,
+    /// Threshold of `Above` and `Below`
+    pub value: f32,
+    /// Lookback of `Rising` and `Falling`, in bars
+    pub bars: u16,
+}
+
+impl Condition {
+    pub fn is_met(&self, state: &MarketState) -> bool {
+        let series = state.series(self.metric);
+        let Some(&last) = series.last() else {
+            return false;
+        };
+
+        match self.op {
+            ConditionOp::Above => last > self.value,
+            ConditionOp::Below => last < self.value,
+            ConditionOp::Rising | ConditionOp::Falling => {
+                let Some(&then) = series
+                    .len()
+                    .checked_sub(usize::from(self.bars.max(1)) + 1)
+                    .and_then(|idx| series.get(idx))
+                else {
+                    return false;
+                };
+
+                if self.op == ConditionOp::Rising {
+                    last > then
+                } else {
+                    last < then
+                }
+            }
+        }
+    }
+
+    /// Bars of history the condition needs, including the forming one
+    fn lookback(&self) -> usize {
+        if self.op.is_trend() {
+            usize::from(self.bars.max(1)) + 1
+        } else {
+            1
+        }
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.op.is_trend() {
+            let plural = if self.bars == 1 { "" } else { "s" };
+            write!(
+                f,
+                "{} {} over {} bar{plural}",
+                self.metric, self.op, self.bars
+            )
+        } else {
+            write!(f, "{} {} {}", self.metric, self.op, self.value)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Combinator {
+    #[default]
+    All,
+    Any,
+}
+
+impl Combinator {
+    pub const ALL: [Combinator; 2] = [Combinator::All, Combinator::Any];
+}
+
+impl std::fmt::Display for Combinator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combinator::All => write!(f, "AND"),
+            Combinator::Any => write!(f, "OR"),
+        }
+    }
+}
+
+/// Recent values of the charted series, oldest first with the forming bar last
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketState {
+    pub closes: Vec<f32>,
+    /// Cumulative delta over the window, only its changes are meaningful
+    pub cvd: Vec<f32>,
+    /// Empty when the chart has no open interest loaded
+    pub open_interest: Vec<f32>,
+}
+
+impl MarketState {
+    pub fn series(&self, metric: Metric) -> &[f32] {
+        match metric {
+            Metric::Price => &self.closes,
+            Metric::Cvd => &self.cvd,
+            Metric::OpenInterest => &self.open_interest,
+        }
+    }
+}
+
+/// Alert on several conditions combined with AND/OR, fires when they become true
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CompositeAlert {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub combinator: Combinator,
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub fired_at: Option<u64>,
+    /// Outcome of the last evaluation, so a re-armed alert waits for the next transition
+    #[serde(skip)]
+    pub was_met: bool,
+}
+
+impl CompositeAlert {
+    pub fn is_armed(&self) -> bool {
+        self.fired_at.is_none()
+    }
+
+    pub fn is_met(&self, state: &MarketState) -> bool {
+        match self.combinator {
+            Combinator::All => self.conditions.iter().all(|c| c.is_met(state)),
+            Combinator::Any => self.conditions.iter().any(|c| c.is_met(state)),
+        }
+    }
+
+    pub fn lookback(&self) -> usize {
+        self.conditions
+            .iter()
+            .map(Condition::lookback)
+            .max()
+            .unwrap_or(1)
+    }
+}
+
+impl std::fmt::Display for CompositeAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, condition) in self.conditions.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " {} ", self.combinator)?;
+            }
+            write!(f, "{condition}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Alerts of a pane, a pane can keep alerts of tickers it no longer shows
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Alerts {
+    pub items: Vec<PriceAlert>,
+    #[serde(default)]
+    pub composites: Vec<CompositeAlert>,
+}
+
+impl Alerts {
+    /// Price and composite alerts share one id space
+    fn next_id(&self) -> u32 {
+        self.items
+            .iter()
+            .map(|a| a.id + 1)
+            .chain(self.composites.iter().map(|a| a.id + 1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn add(&mut self, ticker: Ticker, price: f32, trigger: AlertTrigger) -> u32 {
+        let id = self.next_id();
+
+        self.items.push(PriceAlert {
+            id,
+            ticker,
+            price,
+            trigger,
+            muted: false,
+            fired_at: None,
+        });
+        id
+    }
+
+    pub fn add_composite(
+        &mut self,
+        ticker: Ticker,
+        combinator: Combinator,
+        conditions: Vec<Condition>,
+    ) -> u32 {
+        let id = self.next_id();
+
+        self.composites.push(CompositeAlert {
+            id,
+            ticker,
+            combinator,
+            conditions,
+            muted: false,
+            fired_at: None,
+            was_met: false,
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.items.retain(|a| a.id != id);
+        self.composites.retain(|a| a.id != id);
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut PriceAlert> {
+        self.items.iter_mut().find(|a| a.id == id)
+    }
+
+    pub fn get_composite_mut(&mut self, id: u32) -> Option<&mut CompositeAlert> {
+        self.composites.iter_mut().find(|a| a.id == id)
+    }
+
+    /// Points the alerts of `from` at `to`, keeping their levels, returns how many moved
+    pub fn replace_ticker(&mut self, from: Ticker, to: Ticker) -> usize {
+        let mut moved = 0;
+
+        for alert in self.items.iter_mut().filter(|a| a.ticker == from) {
+            alert.ticker = to;
+            moved += 1;
+        }
+        for alert in self.composites.iter_mut().filter(|a| a.ticker == from) {
+            alert.ticker = to;
+            moved += 1;
+        }
+        moved
+    }
+
+    pub fn for_ticker(&self, ticker: Ticker) -> impl Iterator<Item = &PriceAlert> {
+        self.items.iter().filter(move |a| a.ticker == ticker)
+    }
+
+    /// Fires armed alerts of `ticker` crossed between `prev` and each `(time, price)` in order,
+    /// returning the ones that fired
+    pub fn evaluate(
+        &mut self,
+        ticker: Ticker,
+        mut prev: f32,
+        prices: impl IntoIterator<Item = (u64, f32)>,
+    ) -> Vec<PriceAlert> {
+        let mut fired = vec![];
+
+        for (time, price) in prices {
+            for alert in self
+                .items
+                .iter_mut()
+                .filter(|a| a.ticker == ticker && a.is_armed())
+            {
+                if alert.is_hit(prev, price) {
+                    alert.fired_at = Some(time);
+                    fired.push(alert.clone());
+                }
+            }
+            prev = price;
+        }
+
+        fired
+    }
+
+    /// Bars of history the composites of `ticker` need, `None` when there are none
+    pub fn composite_lookback(&self, ticker: Ticker) -> Option<usize> {
+        self.composites
+            .iter()
+            .filter(|a| a.ticker == ticker)
+            .map(CompositeAlert::lookback)
+            .max()
+    }
+
+    /// Fires armed composites of `ticker` whose conditions turned true, returning the ones that fired
+    pub fn evaluate_composites(
+        &mut self,
+        ticker: Ticker,
+        time: u64,
+        state: &MarketState,
+    ) -> Vec<CompositeAlert> {
+        let mut fired = vec![];
+
+        for alert in self.composites.iter_mut().filter(|a| a.ticker == ticker) {
+            let is_met = alert.is_met(state);
+
+            if is_met && !alert.was_met && alert.is_armed() {
+                alert.fired_at = Some(time);
+                fired.push(alert.clone());
+            }
+            alert.was_met = is_met;
+        }
+
+        fired
+    }
+}
@@ -0,0 +1,166 @@
+use crate::config::theme::create_gradient;
+
+use iced_core::{Color, theme::Palette};
+use serde::{Deserialize, Serialize};
+
+/// Shades the heatmap gradient is split into
+pub const GRADIENT_STEPS: usize = 32;
+
+/// Colors each swatch cycles through in the pane settings
+pub const SWATCHES: [Color; 10] = [
+    Color::from_rgb(0.15, 0.65, 0.60),
+    Color::from_rgb(0.94, 0.33, 0.31),
+    Color::from_rgb(0.30, 0.69, 0.31),
+    Color::from_rgb(0.96, 0.26, 0.21),
+    Color::from_rgb(0.13, 0.59, 0.95),
+    Color::from_rgb(1.00, 0.60, 0.00),
+    Color::from_rgb(0.61, 0.15, 0.69),
+    Color::from_rgb(0.95, 0.95, 0.95),
+    Color::from_rgb(0.55, 0.55, 0.55),
+    Color::from_rgb(0.10, 0.10, 0.12),
+];
+
+/// Colors of a pane used instead of the theme's, `None` keeps the theme color
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChartColors {
+    /// Rising candles and buy side, replaces the palette's success color
+    pub up: Option<Color>,
+    /// Falling candles and sell side, replaces the palette's danger color
+    pub down: Option<Color>,
+    /// Candle wicks, the body color unless set
+    pub wick: Option<Color>,
+    pub text: Option<Color>,
+    /// Heatmap depth from thin to thick resting size, bid and ask colors unless both ends are set
+    pub heatmap_low: Option<Color>,
+    pub heatmap_high: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorRole {
+    Up,
+    Down,
+    Wick,
+    Text,
+    HeatmapLow,
+    HeatmapHigh,
+}
+
+impl ColorRole {
+    pub const KLINE: [ColorRole; 4] = [Self::Up, Self::Down, Self::Wick, Self::Text];
+    pub const HEATMAP: [ColorRole; 5] = [
+        Self::Up,
+        Self::Down,
+        Self::Text,
+        Self::HeatmapLow,
+        Self::HeatmapHigh,
+    ];
+}
+
+impl std::fmt::Display for ColorRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorRole::Up => write!(f, "Up"),
+            ColorRole::Down => write!(f, "Down"),
+            ColorRole::Wick => write!(f, "Wick"),
+            ColorRole::Text => write!(f, "Text"),
+            ColorRole::HeatmapLow => write!(f, "Gradient low"),
+            ColorRole::HeatmapHigh => write!(f, "Gradient high"),
+        }
+    }
+}
+
+impl ChartColors {
+    pub fn get(&self, role: ColorRole) -> Option<Color> {
+        match role {
+            ColorRole::Up => self.up,
+            ColorRole::Down => self.down,
+            ColorRole::Wick => self.wick,
+            ColorRole::Text => self.text,
+            ColorRole::HeatmapLow => self.heatmap_low,
+            ColorRole::HeatmapHigh => self.heatmap_high,
+        }
+    }
+
+    pub fn set(&mut self, role: ColorRole, color: Option<Color>) {
+        let slot = match role {
+            ColorRole::Up => &mut self.up,
+            ColorRole::Down => &mut self.down,
+            ColorRole::Wick => &mut self.wick,
+            ColorRole::Text => &mut self.text,
+            ColorRole::HeatmapLow => &mut self.heatmap_low,
+            ColorRole::HeatmapHigh => &mut self.heatmap_high,
+        };
+        *slot = color;
+    }
+
+    /// Swatch after `role`'s current color, the first one while it follows the theme
+    pub fn next_swatch(&self, role: ColorRole) -> Color {
+        self.get(role)
+            .and_then(|color| SWATCHES.iter().position(|swatch| *swatch == color))
+            .map_or(SWATCHES[0], |pos| SWATCHES[(pos + 1) % SWATCHES.len()])
+    }
+
+    /// `base` with the up, down and text overrides applied, `None` if none is set
+    pub fn palette(&self, base: Palette) -> Option<Palette> {
+        if self.up.is_none() && self.down.is_none() && self.text.is_none() {
+            return None;
+        }
+
+        Some(Palette {
+            success: self.up.unwrap_or(base.success),
+            danger: self.down.unwrap_or(base.danger),
+            text: self.text.unwrap_or(base.text),
+            ..base
+        })
+    }
+
+    /// Shades from thin to thick resting size, once both ends are set
+    pub fn heatmap_gradient(&self) -> Option<Vec<Color>> {
+        let (low, high) = self.heatmap_low.zip(self.heatmap_high)?;
+        Some(create_gradient(low, high, GRADIENT_STEPS))
+    }
+}
+
+/// Shade of `gradient` at `intensity` in `0..=1`
+pub fn gradient_at(gradient: &[Color], intensity: f32) -> Color {
+    let last = gradient.len().saturating_sub(1);
+    let idx = (intensity.clamp(0.0, 1.0) * last as f32).round() as usize;
+
+    gradient.get(idx).copied().unwrap_or(Color::TRANSPARENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_only_the_roles_set() {
+        let base = Palette {
+            background: Color::BLACK,
+            text: Color::WHITE,
+            primary: Color::from_rgb(0.0, 0.0, 1.0),
+            success: Color::from_rgb(0.0, 1.0, 0.0),
+            danger: Color::from_rgb(1.0, 0.0, 0.0),
+            warning: Color::from_rgb(1.0, 1.0, 0.0),
+        };
+
+        let mut colors = ChartColors::default();
+        assert!(colors.palette(base).is_none());
+        assert!(colors.heatmap_gradient().is_none());
+
+        colors.set(ColorRole::Up, Some(SWATCHES[0]));
+        let palette = colors.palette(base).unwrap();
+        assert_eq!(palette.success, SWATCHES[0]);
+        assert_eq!(palette.danger, base.danger);
+
+        colors.set(ColorRole::HeatmapLow, Some(Color::BLACK));
+        assert!(colors.heatmap_gradient().is_none());
+        colors.set(ColorRole::HeatmapHigh, Some(Color::WHITE));
+
+        let gradient = colors.heatmap_gradient().unwrap();
+        assert_eq!(gradient.len(), GRADIENT_STEPS);
+        assert_eq!(gradient_at(&gradient, 0.0), gradient[0]);
+        assert_eq!(gradient_at(&gradient, 2.0), gradient[GRADIENT_STEPS - 1]);
+    }
+}
@@ -1,8 +1,122 @@
+use super::PriceScale;
 use exchange::SerTicker;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub colors: Vec<(SerTicker, iced_core::Color)>,
     pub names: Vec<(SerTicker, String)>,
+    /// Plot USDT/USDC/FDUSD quotes of the same base as one volume-weighted series
+    #[serde(default)]
+    pub merge_stablecoin_quotes: bool,
+    #[serde(default)]
+    pub price_scale: PriceScale,
+    /// Percent range the vertical axis is locked to, `None` while it fits the visible series
+    #[serde(default)]
+    pub locked_range: Option<(f32, f32)>,
+    /// Time in ms every series is rebased to 0% at, `None` rebases to the left edge of the view
+    #[serde(default)]
+    pub rebase_anchor: Option<u64>,
+    /// Table of how each series' returns correlate with the base series' over the visible window
+    #[serde(default)]
+    pub show_correlation: bool,
+    /// Spreads and ratios between two of the series, plotted after them
+    #[serde(default)]
+    pub derived: Vec<DerivedSeries>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DerivedKind {
+    #[default]
+    Ratio,
+    Spread,
+}
+
+impl DerivedKind {
+    pub const ALL: [DerivedKind; 2] = [DerivedKind::Ratio, DerivedKind::Spread];
+
+    /// `None` for a ratio over a zero price
+    pub fn combine(self, a: f32, b: f32) -> Option<f32> {
+        let value = match self {
+            DerivedKind::Ratio => a / b,
+            DerivedKind::Spread => a - b,
+        };
+        value.is_finite().then_some(value)
+    }
+}
+
+impl std::fmt::Display for DerivedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DerivedKind::Ratio => write!(f, "Ratio (A / B)"),
+            DerivedKind::Spread => write!(f, "Spread (A - B)"),
+        }
+    }
+}
+
+/// Series computed bar by bar from two legs of the comparison, e.g. for pair trading
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedSeries {
+    pub a: SerTicker,
+    pub b: SerTicker,
+    pub kind: DerivedKind,
+    pub color: iced_core::Color,
+}
+
+impl DerivedSeries {
+    pub fn label(&self) -> String {
+        let op = match self.kind {
+            DerivedKind::Ratio => '/',
+            DerivedKind::Spread => '-',
+        };
+        format!("{} {op} {}", self.a.ticker, self.b.ticker)
+    }
+
+    /// Combined points of both legs at the timestamps they share, legs sorted by time
+    pub fn points(&self, a: &[(u64, f32)], b: &[(u64, f32)]) -> Vec<(u64, f32)> {
+        let mut points = Vec::with_capacity(a.len().min(b.len()));
+        let (mut i, mut j) = (0, 0);
+
+        while i < a.len() && j < b.len() {
+            match a[i].0.cmp(&b[j].0) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    if let Some(value) = self.kind.combine(a[i].1, b[j].1) {
+                        points.push((a[i].0, value));
+                    }
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::adapter::Exchange;
+
+    #[test]
+    fn derived_points_only_at_shared_bars() {
+        let ratio = DerivedSeries {
+            a: SerTicker::new(Exchange::BinanceLinear, "BTCUSDT"),
+            b: SerTicker::new(Exchange::BinanceLinear, "ETHUSDT"),
+            kind: DerivedKind::Ratio,
+            color: iced_core::Color::WHITE,
+        };
+        let a = [(0, 100.0), (1, 110.0), (3, 120.0)];
+        let b = [(1, 0.0), (2, 10.0), (3, 40.0)];
+
+        assert_eq!(ratio.points(&a, &b), vec![(3, 3.0)]);
+
+        let spread = DerivedSeries {
+            kind: DerivedKind::Spread,
+            ..ratio
+        };
+        assert_eq!(spread.points(&a, &b), vec![(1, 110.0), (3, 80.0)]);
+    }
 }
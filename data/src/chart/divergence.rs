@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+/// Flags bars where price and cumulative delta disagree about a new extreme
+/// of the preceding `lookback` bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeltaDivergence {
+    pub enabled: bool,
+    pub lookback: u16,
+}
+
+impl Default for DeltaDivergence {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookback: 20,
+        }
+    }
+}
+
+impl DeltaDivergence {
+    pub const LOOKBACK_RANGE: std::ops::RangeInclusive<u16> = 5..=100;
+
+    /// Divergence of the last bar of `window` against the bars before it,
+    /// `None` unless the window holds `lookback` bars plus the one checked
+    pub fn detect(&self, window: &[BarDelta]) -> Option<Divergence> {
+        let (bar, previous) = window.split_last()?;
+        if previous.len() != usize::from(self.lookback) {
+            return None;
+        }
+
+        let highest = previous.iter().map(|b| b.high).fold(f32::MIN, f32::max);
+        let lowest = previous.iter().map(|b| b.low).fold(f32::MAX, f32::min);
+        let highest_cvd = previous.iter().map(|b| b.cvd).fold(f32::MIN, f32::max);
+        let lowest_cvd = previous.iter().map(|b| b.cvd).fold(f32::MAX, f32::min);
+
+        let price_high = bar.high > highest;
+        let price_low = bar.low < lowest;
+        let cvd_high = bar.cvd > highest_cvd;
+        let cvd_low = bar.cvd < lowest_cvd;
+
+        let kind = match (price_high, price_low, cvd_high, cvd_low) {
+            (true, false, false, _) => DivergenceKind::PriceHighWithoutDelta,
+            (false, true, _, false) => DivergenceKind::PriceLowWithoutDelta,
+            (false, false, true, false) => DivergenceKind::DeltaHighWithoutPrice,
+            (false, false, false, true) => DivergenceKind::DeltaLowWithoutPrice,
+            _ => return None,
+        };
+
+        Some(Divergence { kind })
+    }
+}
+
+/// Range of a bar and the cumulative delta at its close
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarDelta {
+    pub high: f32,
+    pub low: f32,
+    pub cvd: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// New high the buying didn't confirm
+    PriceHighWithoutDelta,
+    /// New low the selling didn't confirm
+    PriceLowWithoutDelta,
+    /// Buyers at a new extreme while price held, absorbed by passive sellers
+    DeltaHighWithoutPrice,
+    /// Sellers at a new extreme while price held, absorbed by passive buyers
+    DeltaLowWithoutPrice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    pub kind: DivergenceKind,
+}
+
+impl Divergence {
+    /// Bearish ones are marked above the bar, bullish ones below it
+    pub fn is_bearish(&self) -> bool {
+        matches!(
+            self.kind,
+            DivergenceKind::PriceHighWithoutDelta | DivergenceKind::DeltaHighWithoutPrice
+        )
+    }
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            DivergenceKind::PriceHighWithoutDelta => write!(f, "Bearish · new high, delta lagging"),
+            DivergenceKind::PriceLowWithoutDelta => write!(f, "Bullish · new low, delta lagging"),
+            DivergenceKind::DeltaHighWithoutPrice => write!(f, "Bearish · delta high, price held"),
+            DivergenceKind::DeltaLowWithoutPrice => write!(f, "Bullish · delta low, price held"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(high: f32, low: f32, cvd: f32) -> BarDelta {
+        BarDelta { high, low, cvd }
+    }
+
+    #[test]
+    fn flags_new_extremes_delta_or_price_didnt_confirm() {
+        let study = DeltaDivergence {
+            enabled: true,
+            lookback: 2,
+        };
+        let previous = [bar(101.0, 99.0, 50.0), bar(102.0, 100.0, 80.0)];
+
+        let detect = |last: BarDelta| {
+            let mut window = previous.to_vec();
+            window.push(last);
+            study.detect(&window).map(|divergence| divergence.kind)
+        };
+
+        assert_eq!(
+            detect(bar(103.0, 101.0, 70.0)),
+            Some(DivergenceKind::PriceHighWithoutDelta)
+        );
+        assert_eq!(
+            detect(bar(101.5, 100.0, 90.0)),
+            Some(DivergenceKind::DeltaHighWithoutPrice)
+        );
+        assert_eq!(
+            detect(bar(101.0, 98.0, 60.0)),
+            Some(DivergenceKind::PriceLowWithoutDelta)
+        );
+        // both confirming a new high
+        assert_eq!(detect(bar(103.0, 101.0, 90.0)), None);
+        // window shorter than the lookback
+        assert_eq!(study.detect(&previous), None);
+    }
+}
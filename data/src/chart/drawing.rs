@@ -0,0 +1,146 @@
+use exchange::Ticker;
+use exchange::util::Price;
+use serde::{Deserialize, Serialize};
+
+/// Ratios a fib retracement can draw, 0 at the end anchor and 1 at the start anchor
+pub const FIB_LEVEL_CHOICES: [f32; 9] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0, 1.272, 1.618];
+
+const DEFAULT_FIB_LEVELS: [f32; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DrawingTool {
+    TrendLine,
+    Ray,
+    HorizontalLine,
+    Rectangle,
+    FibRetracement,
+}
+
+impl DrawingTool {
+    pub const ALL: [DrawingTool; 5] = [
+        DrawingTool::TrendLine,
+        DrawingTool::Ray,
+        DrawingTool::HorizontalLine,
+        DrawingTool::Rectangle,
+        DrawingTool::FibRetracement,
+    ];
+
+    /// Whether the drawing is placed with a single click rather than a drag
+    pub fn is_single_anchor(&self) -> bool {
+        matches!(self, DrawingTool::HorizontalLine)
+    }
+}
+
+impl std::fmt::Display for DrawingTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawingTool::TrendLine => write!(f, "Trend line"),
+            DrawingTool::Ray => write!(f, "Ray"),
+            DrawingTool::HorizontalLine => write!(f, "Horizontal line"),
+            DrawingTool::Rectangle => write!(f, "Rectangle"),
+            DrawingTool::FibRetracement => write!(f, "Fib retracement"),
+        }
+    }
+}
+
+/// Point of a drawing, `interval` is in the chart's x units:
+/// open time on time-based charts, bar index from the latest one otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Anchor {
+    pub interval: u64,
+    pub price: Price,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Drawing {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub tool: DrawingTool,
+    /// Start and end, single anchor tools keep both on the same point
+    pub anchors: [Anchor; 2],
+}
+
+impl Drawing {
+    /// Anchors that can be grabbed and moved
+    pub fn handles(&self) -> &[Anchor] {
+        if self.tool.is_single_anchor() {
+            &self.anchors[..1]
+        } else {
+            &self.anchors
+        }
+    }
+}
+
+/// Drawings of a pane, a pane keeps drawings of tickers it no longer shows
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Drawings {
+    pub items: Vec<Drawing>,
+    /// Ratios drawn by every fib retracement of the pane, ascending
+    #[serde(default = "default_fib_levels")]
+    pub fib_levels: Vec<f32>,
+}
+
+impl Default for Drawings {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            fib_levels: default_fib_levels(),
+        }
+    }
+}
+
+fn default_fib_levels() -> Vec<f32> {
+    DEFAULT_FIB_LEVELS.to_vec()
+}
+
+impl Drawings {
+    pub fn toggle_fib_level(&mut self, level: f32) {
+        if let Some(idx) = self.fib_levels.iter().position(|l| *l == level) {
+            self.fib_levels.remove(idx);
+        } else {
+            self.fib_levels.push(level);
+            self.fib_levels.sort_by(f32::total_cmp);
+        }
+    }
+
+    pub fn tool_of(&self, id: u32) -> Option<DrawingTool> {
+        self.items.iter().find(|d| d.id == id).map(|d| d.tool)
+    }
+
+    pub fn add(&mut self, ticker: Ticker, tool: DrawingTool, anchors: [Anchor; 2]) -> u32 {
+        let id = self.items.iter().map(|d| d.id + 1).max().unwrap_or(0);
+
+        self.items.push(Drawing {
+            id,
+            ticker,
+            tool,
+            anchors,
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.items.retain(|d| d.id != id);
+    }
+
+    /// Moves anchor `idx` of a drawing, single anchor tools move as a whole
+    pub fn move_anchor(&mut self, id: u32, idx: usize, anchor: Anchor) {
+        let Some(drawing) = self.items.iter_mut().find(|d| d.id == id) else {
+            return;
+        };
+
+        if drawing.tool.is_single_anchor() {
+            drawing.anchors = [anchor; 2];
+        } else if let Some(slot) = drawing.anchors.get_mut(idx) {
+            *slot = anchor;
+        }
+    }
+
+    pub fn clear(&mut self, ticker: Ticker) {
+        self.items.retain(|d| d.ticker != ticker);
+    }
+
+    pub fn for_ticker(&self, ticker: Ticker) -> impl Iterator<Item = &Drawing> {
+        self.items.iter().filter(move |d| d.ticker == ticker)
+    }
+}
@@ -1,7 +1,13 @@
 use super::Basis;
+use super::LiquidationMarkers;
 use super::aggr::time::DataPoint;
+use super::session::SessionSettings;
 use exchange::util::{Price, PriceStep};
-use exchange::{adapter::MarketKind, depth::Depth, volume_size_unit};
+use exchange::{
+    adapter::MarketKind,
+    depth::{Depth, Levels},
+    volume_size_unit,
+};
 
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use serde::{Deserialize, Serialize};
@@ -19,6 +25,24 @@ pub struct Config {
     pub order_size_filter: f32,
     pub trade_size_scale: Option<i32>,
     pub coalescing: Option<CoalesceKind>,
+    #[serde(default)]
+    pub sessions: SessionSettings,
+    #[serde(default)]
+    pub liquidations: LiquidationMarkers,
+    #[serde(default)]
+    pub depth_normalization: DepthNormalization,
+    /// Memory kept for order book snapshots to scrub back through, 0 turns it off
+    #[serde(default = "default_scrub_memory_mb")]
+    pub scrub_memory_mb: u32,
+}
+
+fn default_scrub_memory_mb() -> u32 {
+    Config::DEFAULT_SCRUB_MEMORY_MB
+}
+
+impl Config {
+    pub const DEFAULT_SCRUB_MEMORY_MB: u32 = 32;
+    pub const MAX_SCRUB_MEMORY_MB: u32 = 512;
 }
 
 impl Default for Config {
@@ -28,10 +52,116 @@ impl Default for Config {
             order_size_filter: 0.0,
             trade_size_scale: Some(100),
             coalescing: Some(CoalesceKind::Average(0.15)),
+            sessions: SessionSettings::default(),
+            liquidations: LiquidationMarkers::default(),
+            depth_normalization: DepthNormalization::default(),
+            scrub_memory_mb: Self::DEFAULT_SCRUB_MEMORY_MB,
+        }
+    }
+}
+
+/// How resting order sizes map onto the opacity of depth cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum DepthNormalization {
+    /// Relative to the largest visible order
+    #[default]
+    Linear,
+    /// Log scaled, keeps small orders visible next to walls
+    Log,
+    /// Relative to a percentile of visible orders, larger ones are fully opaque
+    Percentile(u8),
+    /// Relative to the largest order of the same time column
+    PerColumn,
+}
+
+impl DepthNormalization {
+    pub const DEFAULT_PERCENTILE: u8 = 95;
+    pub const PERCENTILE_RANGE: std::ops::RangeInclusive<u8> = 50..=99;
+
+    /// How steep the log curve rises near zero, the ratio it maps onto 0..1 is `1..=1 + LOG_CURVE`
+    const LOG_CURVE: f32 = 99.0;
+
+    /// Opacity of an order of `qty` against the reference size its mode measured
+    pub fn alpha(self, qty: f32, reference: f32) -> f32 {
+        if reference <= 0.0 {
+            return 0.0;
+        }
+        let ratio = qty / reference;
+
+        let alpha = match self {
+            DepthNormalization::Log => {
+                (1.0 + ratio * Self::LOG_CURVE).ln() / (1.0 + Self::LOG_CURVE).ln()
+            }
+            DepthNormalization::Linear
+            | DepthNormalization::Percentile(_)
+            | DepthNormalization::PerColumn => ratio,
+        };
+        alpha.clamp(0.0, 1.0)
+    }
+}
+
+impl std::fmt::Display for DepthNormalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepthNormalization::Linear => write!(f, "Linear"),
+            DepthNormalization::Log => write!(f, "Log"),
+            DepthNormalization::Percentile(pct) => write!(f, "Percentile ({pct}th)"),
+            DepthNormalization::PerColumn => write!(f, "Per column"),
+        }
+    }
+}
+
+/// Size the depth cells of the visible region are measured against
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepthReference {
+    Uniform(f32),
+    /// Largest order of each column, the first one starting at `start`
+    PerColumn {
+        start: u64,
+        step: u64,
+        maxima: Vec<f32>,
+    },
+}
+
+impl DepthReference {
+    /// Reference for an order resting at `time`
+    pub fn at(&self, time: u64) -> f32 {
+        match self {
+            DepthReference::Uniform(reference) => *reference,
+            DepthReference::PerColumn {
+                start,
+                step,
+                maxima,
+            } => {
+                let idx = (time.saturating_sub(*start) / (*step).max(1)) as usize;
+                maxima.get(idx).copied().unwrap_or(0.0)
+            }
+        }
+    }
+
+    /// End of the stretch from `time` that shares one reference, capped at `until`
+    pub fn segment_end(&self, time: u64, until: u64) -> u64 {
+        match self {
+            DepthReference::Uniform(_) => until,
+            DepthReference::PerColumn { step, .. } => {
+                let step = (*step).max(1);
+                ((time / step + 1) * step).min(until)
+            }
         }
     }
 }
 
+/// Value below which `pct` percent of `values` fall, reorders `values`
+fn percentile(values: &mut [f32], pct: u8) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let rank = (values.len() - 1) * usize::from(pct.min(100)) / 100;
+    let (_, value, _) = values.select_nth_unstable_by(rank, f32::total_cmp);
+    *value
+}
+
 pub struct HeatmapDataPoint {
     pub grouped_trades: Box<[GroupedTrade]>,
     pub buy_sell: (f32, f32),
@@ -170,7 +300,7 @@ impl HistoricalDepth {
         self.process_side(&depth.asks, time, false);
     }
 
-    fn process_side(&mut self, side: &BTreeMap<Price, f32>, time: u64, is_bid: bool) {
+    fn process_side(&mut self, side: &Levels, time: u64, is_bid: bool) {
         let mut current_price = None;
         let mut current_qty = 0.0;
 
@@ -455,6 +585,106 @@ impl HistoricalDepth {
     ) -> f32 {
         let mut max_depth_qty = 0.0f32;
 
+        self.for_each_visible_run(
+            earliest,
+            latest,
+            highest,
+            lowest,
+            market_type,
+            order_size_filter,
+            |run| max_depth_qty = max_depth_qty.max(run.qty()),
+        );
+
+        max_depth_qty
+    }
+
+    /// Size the visible depth cells get normalized against under `normalization`
+    pub fn depth_reference(
+        &self,
+        normalization: DepthNormalization,
+        earliest: u64,
+        latest: u64,
+        highest: Price,
+        lowest: Price,
+        market_type: MarketKind,
+        order_size_filter: f32,
+    ) -> DepthReference {
+        match normalization {
+            DepthNormalization::Linear | DepthNormalization::Log => {
+                DepthReference::Uniform(self.max_depth_qty_in_range(
+                    earliest,
+                    latest,
+                    highest,
+                    lowest,
+                    market_type,
+                    order_size_filter,
+                ))
+            }
+            DepthNormalization::Percentile(pct) => {
+                let mut qtys = vec![];
+                self.for_each_visible_run(
+                    earliest,
+                    latest,
+                    highest,
+                    lowest,
+                    market_type,
+                    order_size_filter,
+                    |run| qtys.push(run.qty()),
+                );
+
+                DepthReference::Uniform(percentile(&mut qtys, pct))
+            }
+            DepthNormalization::PerColumn => {
+                let step = self.aggr_time.max(1);
+                let start = (earliest / step) * step;
+                let columns = (latest.saturating_sub(start) / step) as usize + 1;
+                let mut maxima = vec![0.0f32; columns];
+
+                self.for_each_visible_run(
+                    earliest,
+                    latest,
+                    highest,
+                    lowest,
+                    market_type,
+                    order_size_filter,
+                    |run| {
+                        let first = run.start_time.max(start).saturating_sub(start) / step;
+                        // until is exclusive, a run ending on a column boundary stops short of it
+                        let last = run
+                            .until_time
+                            .saturating_sub(1)
+                            .min(latest)
+                            .saturating_sub(start)
+                            / step;
+
+                        for idx in first..=last {
+                            if let Some(max) = maxima.get_mut(idx as usize) {
+                                *max = max.max(run.qty());
+                            }
+                        }
+                    },
+                );
+
+                DepthReference::PerColumn {
+                    start,
+                    step,
+                    maxima,
+                }
+            }
+        }
+    }
+
+    /// Runs overlapping the range that are larger than the order size filter
+    fn for_each_visible_run(
+        &self,
+        earliest: u64,
+        latest: u64,
+        highest: Price,
+        lowest: Price,
+        market_type: MarketKind,
+        order_size_filter: f32,
+        mut f: impl FnMut(&OrderRun),
+    ) {
         let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
 
         self.iter_time_filtered(earliest, latest, highest, lowest)
@@ -475,12 +705,8 @@ impl HistoricalDepth {
                             None
                         }
                     })
-                    .for_each(|run| {
-                        max_depth_qty = max_depth_qty.max(run.qty());
-                    });
+                    .for_each(&mut f);
             });
-
-        max_depth_qty
     }
 }
 
@@ -580,6 +806,167 @@ impl CoalescingRun {
     }
 }
 
+/// Order book grouped to the chart's tick size at one heatmap column
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthSnapshot {
+    pub time: u64,
+    pub bids: Box<[(Price, f32)]>,
+    pub asks: Box<[(Price, f32)]>,
+}
+
+impl DepthSnapshot {
+    pub fn new(time: u64, depth: &Depth, step: PriceStep) -> Self {
+        Self {
+            time,
+            bids: group_side(&depth.bids, true, step),
+            asks: group_side(&depth.asks, false, step),
+        }
+    }
+
+    /// Levels between `lowest` and `highest` as `(price, qty, is_bid)`
+    pub fn levels_in(
+        &self,
+        highest: Price,
+        lowest: Price,
+    ) -> impl Iterator<Item = (Price, f32, bool)> {
+        let in_range = move |(price, _): &&(Price, f32)| *price >= lowest && *price <= highest;
+
+        self.bids
+            .iter()
+            .filter(in_range)
+            .map(|(price, qty)| (*price, *qty, true))
+            .chain(
+                self.asks
+                    .iter()
+                    .filter(in_range)
+                    .map(|(price, qty)| (*price, *qty, false)),
+            )
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.bids.len() + self.asks.len()) * std::mem::size_of::<(Price, f32)>()
+    }
+}
+
+fn group_side(side: &Levels, is_bid: bool, step: PriceStep) -> Box<[(Price, f32)]> {
+    let mut grouped: Vec<(Price, f32)> = Vec::with_capacity(side.len());
+
+    for (price, qty) in side {
+        let rounded_price = price.round_to_side_step(is_bid, step);
+        match grouped.last_mut() {
+            Some((last_price, last_qty)) if *last_price == rounded_price => *last_qty += qty,
+            _ => grouped.push((rounded_price, *qty)),
+        }
+    }
+    grouped.into_boxed_slice()
+}
+
+/// Spread between the best bid and ask, and the microprice weighting them by the size
+/// resting on the opposite side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookTop {
+    pub spread_ticks: f32,
+    pub spread_bps: f32,
+    pub microprice: f32,
+}
+
+impl BookTop {
+    /// `None` while either side of `depth` is empty
+    pub fn of(depth: &Depth, min_tick: f32) -> Option<Self> {
+        let (bid, bid_qty) = depth.bids.last_key_value()?;
+        let (ask, ask_qty) = depth.asks.first_key_value()?;
+        let (bid, ask) = (bid.to_f32(), ask.to_f32());
+
+        let spread = ask - bid;
+        let mid = (ask + bid) / 2.0;
+        let microprice = if bid_qty + ask_qty > 0.0 {
+            (ask * bid_qty + bid * ask_qty) / (bid_qty + ask_qty)
+        } else {
+            mid
+        };
+
+        Some(Self {
+            spread_ticks: spread / min_tick,
+            spread_bps: spread / mid * 10_000.0,
+            microprice,
+        })
+    }
+}
+
+/// Recent order book snapshots, one per heatmap column, oldest dropped once over the memory budget
+#[derive(Debug, Default)]
+pub struct DepthHistory {
+    snapshots: std::collections::VecDeque<DepthSnapshot>,
+    bytes: usize,
+    budget: usize,
+}
+
+impl DepthHistory {
+    pub fn new(budget_mb: u32) -> Self {
+        Self {
+            budget: budget_mb as usize * 1024 * 1024,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_budget(&mut self, budget_mb: u32) {
+        self.budget = budget_mb as usize * 1024 * 1024;
+        self.evict();
+    }
+
+    /// Records the book of the column at `time`, a later update of the same column replaces it
+    pub fn push(&mut self, time: u64, depth: &Depth, step: PriceStep) {
+        if self.budget == 0 {
+            return;
+        }
+
+        if let Some(last) = self.snapshots.back()
+            && last.time >= time
+        {
+            if last.time > time {
+                return;
+            }
+            if let Some(last) = self.snapshots.pop_back() {
+                self.bytes -= last.size_in_bytes();
+            }
+        }
+
+        let snapshot = DepthSnapshot::new(time, depth, step);
+        self.bytes += snapshot.size_in_bytes();
+        self.snapshots.push_back(snapshot);
+
+        self.evict();
+    }
+
+    /// Latest snapshot taken at or before `time`
+    pub fn at(&self, time: u64) -> Option<&DepthSnapshot> {
+        let idx = self
+            .snapshots
+            .partition_point(|snapshot| snapshot.time <= time);
+        idx.checked_sub(1).and_then(|idx| self.snapshots.get(idx))
+    }
+
+    /// Times of the oldest and latest snapshot
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        Some((self.snapshots.front()?.time, self.snapshots.back()?.time))
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.bytes = 0;
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.budget {
+            let Some(oldest) = self.snapshots.pop_front() else {
+                break;
+            };
+            self.bytes -= oldest.size_in_bytes();
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct QtyScale {
     pub max_trade_qty: f32,
@@ -627,6 +1014,8 @@ impl std::fmt::Display for HeatmapStudy {
 pub enum ProfileKind {
     FixedWindow(usize),
     VisibleRange,
+    /// Accumulates from the open of the latest session in view
+    Session,
 }
 
 impl std::fmt::Display for ProfileKind {
@@ -634,6 +1023,28 @@ impl std::fmt::Display for ProfileKind {
         match self {
             ProfileKind::FixedWindow(_) => write!(f, "Fixed window"),
             ProfileKind::VisibleRange => write!(f, "Visible range"),
+            ProfileKind::Session => write!(f, "Session"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn microprice_leans_towards_the_thinner_side() {
+        let depth = Depth {
+            bids: [(Price::from_f32(99.5), 3.0)].into_iter().collect(),
+            asks: [(Price::from_f32(100.5), 1.0)].into_iter().collect(),
+        };
+
+        let top = BookTop::of(&depth, 0.5).unwrap();
+        assert_eq!(top.spread_ticks, 2.0);
+        assert!((top.spread_bps - 100.0).abs() < 1e-3);
+        // three times the size bid, so the next trade more likely lifts the ask
+        assert_eq!(top.microprice, 100.25);
+
+        assert_eq!(BookTop::of(&Depth::default(), 0.5), None);
+    }
+}
@@ -14,6 +14,9 @@ pub trait Indicator: PartialEq + Display + 'static {
 pub enum KlineIndicator {
     Volume,
     OpenInterest,
+    AggregatedOpenInterest,
+    Rsi,
+    Atr,
 }
 
 impl Indicator for KlineIndicator {
@@ -29,9 +32,84 @@ impl KlineIndicator {
     // Indicator togglers on UI menus depend on these arrays.
     // Every variant needs to be in either SPOT, PERPS or both.
     /// Indicators that can be used with spot market tickers
-    const FOR_SPOT: [KlineIndicator; 1] = [KlineIndicator::Volume];
+    const FOR_SPOT: [KlineIndicator; 3] = [
+        KlineIndicator::Volume,
+        KlineIndicator::Rsi,
+        KlineIndicator::Atr,
+    ];
     /// Indicators that can be used with perpetual swap market tickers
-    const FOR_PERPS: [KlineIndicator; 2] = [KlineIndicator::Volume, KlineIndicator::OpenInterest];
+    const FOR_PERPS: [KlineIndicator; 5] = [
+        KlineIndicator::Volume,
+        KlineIndicator::OpenInterest,
+        KlineIndicator::AggregatedOpenInterest,
+        KlineIndicator::Rsi,
+        KlineIndicator::Atr,
+    ];
+
+    /// Whether the indicator has inputs to edit in its settings popover
+    pub fn has_params(self) -> bool {
+        matches!(self, KlineIndicator::Rsi | KlineIndicator::Atr)
+    }
+}
+
+/// One kline indicator added to a pane, keyed by `id` since a kind can be added more than once
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "InstanceRepr")]
+pub struct KlineInstance {
+    pub id: u16,
+    pub kind: KlineIndicator,
+    /// Inputs of this instance, the pane's defaults unless set
+    pub params: Option<IndicatorParams>,
+}
+
+impl KlineInstance {
+    pub fn new(id: u16, kind: KlineIndicator) -> Self {
+        Self {
+            id,
+            kind,
+            params: None,
+        }
+    }
+
+    /// Id not taken by any of `instances`
+    pub fn next_id(instances: &[KlineInstance]) -> u16 {
+        instances
+            .iter()
+            .map(|instance| instance.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Name in the indicator list, with its inputs once they differ from the defaults
+    pub fn label(&self) -> String {
+        match (self.kind, self.params) {
+            (KlineIndicator::Rsi, Some(params)) => format!("RSI {}", params.rsi_period),
+            (KlineIndicator::Atr, Some(params)) => format!("ATR {}", params.atr_period),
+            (kind, _) => kind.to_string(),
+        }
+    }
+}
+
+/// Layouts saved before instances were keyed by id hold the bare kind, which is unique among them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InstanceRepr {
+    Instance {
+        id: u16,
+        kind: KlineIndicator,
+        #[serde(default)]
+        params: Option<IndicatorParams>,
+    },
+    Kind(KlineIndicator),
+}
+
+impl From<InstanceRepr> for KlineInstance {
+    fn from(repr: InstanceRepr) -> Self {
+        match repr {
+            InstanceRepr::Instance { id, kind, params } => Self { id, kind, params },
+            InstanceRepr::Kind(kind) => Self::new(kind.into_usize() as u16, kind),
+        }
+    }
 }
 
 impl Display for KlineIndicator {
@@ -39,6 +117,41 @@ impl Display for KlineIndicator {
         match self {
             KlineIndicator::Volume => write!(f, "Volume"),
             KlineIndicator::OpenInterest => write!(f, "Open Interest"),
+            KlineIndicator::AggregatedOpenInterest => write!(f, "Aggregated Open Interest"),
+            KlineIndicator::Rsi => write!(f, "RSI"),
+            KlineIndicator::Atr => write!(f, "ATR"),
+        }
+    }
+}
+
+/// Tunable inputs of the computed kline indicators
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndicatorParams {
+    pub rsi_period: u16,
+    pub rsi_overbought: f32,
+    pub rsi_oversold: f32,
+    /// Line color, the theme's unless set
+    pub rsi_color: Option<iced_core::Color>,
+    pub atr_period: u16,
+    pub atr_color: Option<iced_core::Color>,
+}
+
+impl IndicatorParams {
+    pub const PERIOD_RANGE: std::ops::RangeInclusive<u16> = 2..=100;
+    pub const OVERBOUGHT_RANGE: std::ops::RangeInclusive<f32> = 50.0..=95.0;
+    pub const OVERSOLD_RANGE: std::ops::RangeInclusive<f32> = 5.0..=50.0;
+}
+
+impl Default for IndicatorParams {
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            rsi_overbought: 70.0,
+            rsi_oversold: 30.0,
+            rsi_color: None,
+            atr_period: 14,
+            atr_color: None,
         }
     }
 }
@@ -46,6 +159,8 @@ impl Display for KlineIndicator {
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Eq, Enum)]
 pub enum HeatmapIndicator {
     Volume,
+    /// Best bid/ask spread in a panel below, microprice over the heatmap
+    Spread,
 }
 
 impl Indicator for HeatmapIndicator {
@@ -61,15 +176,21 @@ impl HeatmapIndicator {
     // Indicator togglers on UI menus depend on these arrays.
     // Every variant needs to be in either SPOT, PERPS or both.
     /// Indicators that can be used with spot market tickers
-    const FOR_SPOT: [HeatmapIndicator; 1] = [HeatmapIndicator::Volume];
+    const FOR_SPOT: [HeatmapIndicator; 2] = [HeatmapIndicator::Volume, HeatmapIndicator::Spread];
     /// Indicators that can be used with perpetual swap market tickers
-    const FOR_PERPS: [HeatmapIndicator; 1] = [HeatmapIndicator::Volume];
+    const FOR_PERPS: [HeatmapIndicator; 2] = [HeatmapIndicator::Volume, HeatmapIndicator::Spread];
+
+    /// Whether the indicator is drawn in a panel of its own below the chart
+    pub fn has_panel(self) -> bool {
+        matches!(self, HeatmapIndicator::Spread)
+    }
 }
 
 impl Display for HeatmapIndicator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             HeatmapIndicator::Volume => write!(f, "Volume"),
+            HeatmapIndicator::Spread => write!(f, "Spread & Microprice"),
         }
     }
 }
@@ -93,3 +214,23 @@ impl From<HeatmapIndicator> for UiIndicator {
         UiIndicator::Heatmap(h)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bare_kinds_of_older_layouts_get_distinct_ids() {
+        let saved = r#"["Volume", "Rsi", {"id": 7, "kind": "Rsi", "params": null}]"#;
+        let instances: Vec<KlineInstance> = serde_json::from_str(saved).unwrap();
+
+        assert_eq!(instances[0], KlineInstance::new(0, KlineIndicator::Volume));
+        assert_eq!(instances[1], KlineInstance::new(3, KlineIndicator::Rsi));
+        assert_eq!(instances[2].id, 7);
+        assert_eq!(KlineInstance::next_id(&instances), 8);
+
+        let json = serde_json::to_string(&instances).unwrap();
+        let restored: Vec<KlineInstance> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, instances);
+    }
+}
@@ -4,6 +4,13 @@ use exchange::{
 };
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+
+use super::LiquidationMarkers;
+use super::divergence::DeltaDivergence;
+use super::indicator::IndicatorParams;
+use super::moving_average::BollingerBands;
+use super::session::{SessionSettings, SessionSpan};
 
 // NEW: Advanced rejection detection structure
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -160,20 +167,49 @@ impl GroupedTrades {
     pub fn delta_qty(&self) -> f32 {
         self.buy_qty - self.sell_qty
     }
+
+    fn merge(&mut self, later: &GroupedTrades) {
+        self.buy_qty += later.buy_qty;
+        self.sell_qty += later.sell_qty;
+        self.buy_count += later.buy_count;
+        self.sell_count += later.sell_count;
+        self.last_time = later.last_time;
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct KlineTrades {
     pub trades: FxHashMap<Price, GroupedTrades>,
     pub poc: Option<PointOfControl>,
+    /// Delta summed in the order trades were added, and the highest it reached
+    running_delta: f32,
+    max_delta: f32,
 }
 
 impl KlineTrades {
     pub fn new() -> Self {
-        Self {
-            trades: FxHashMap::default(),
-            poc: None,
+        Self::default()
+    }
+
+    fn track_delta(&mut self, trade: &Trade) {
+        self.running_delta += if trade.is_sell { -trade.qty } else { trade.qty };
+        self.max_delta = self.max_delta.max(self.running_delta);
+    }
+
+    /// Totals of the bar for the stats footer
+    pub fn stats(&self) -> BarStats {
+        let mut stats = BarStats {
+            max_delta: self.max_delta,
+            ..BarStats::default()
+        };
+
+        for group in self.trades.values() {
+            stats.volume += group.total_qty();
+            stats.delta += group.delta_qty();
+            stats.trades += group.buy_count + group.sell_count;
         }
+
+        stats
     }
 
     pub fn first_trade_t(&self) -> Option<u64> {
@@ -189,6 +225,7 @@ impl KlineTrades {
     /// Introduces side bias at bin edges and should not be used for OHLC/footprint aggregation
     pub fn add_trade_to_side_bin(&mut self, trade: &Trade, step: PriceStep) {
         let price = trade.price.round_to_side_step(trade.is_sell, step);
+        self.track_delta(trade);
 
         self.trades
             .entry(price)
@@ -201,6 +238,7 @@ impl KlineTrades {
     /// Intended for footprint/OHLC trade aggregation
     pub fn add_trade_to_nearest_bin(&mut self, trade: &Trade, step: PriceStep) {
         let price = trade.price.round_to_step(step);
+        self.track_delta(trade);
 
         self.trades
             .entry(price)
@@ -208,6 +246,22 @@ impl KlineTrades {
             .or_insert_with(|| GroupedTrades::new(trade));
     }
 
+    /// Folds in the trades of `later` as if they were added after this one's. The point of
+    /// control is left for [`Self::calculate_poc`]
+    pub fn merge(&mut self, later: KlineTrades) {
+        for (price, group) in later.trades {
+            match self.trades.entry(price) {
+                Entry::Occupied(mut entry) => entry.get_mut().merge(&group),
+                Entry::Vacant(entry) => {
+                    entry.insert(group);
+                }
+            }
+        }
+
+        self.max_delta = self.max_delta.max(self.running_delta + later.max_delta);
+        self.running_delta += later.running_delta;
+    }
+
     pub fn max_qty_by<F>(&self, highest: Price, lowest: Price, f: F) -> f32
     where
         F: Fn(f32, f32) -> f32,
@@ -254,9 +308,165 @@ impl KlineTrades {
         self.poc.map(|poc| poc.price)
     }
 
+    /// Diagonal imbalance between the sells at `price` and the buys one step above,
+    /// `threshold` being how much larger in percent one side has to be
+    pub fn diagonal_imbalance(
+        &self,
+        price: Price,
+        step: PriceStep,
+        threshold: usize,
+        ignore_zeros: bool,
+    ) -> Option<Imbalance> {
+        let sell_qty = self.trades.get(&price)?.sell_qty;
+        if ignore_zeros && sell_qty <= 0.0 {
+            return None;
+        }
+
+        let higher_price = price.add_steps(1, step);
+        let diagonal_buy_qty = self.trades.get(&higher_price)?.buy_qty;
+        if ignore_zeros && diagonal_buy_qty <= 0.0 {
+            return None;
+        }
+
+        let factor = (100 + threshold) as f32 / 100.0;
+
+        if diagonal_buy_qty >= sell_qty {
+            let required_qty = sell_qty * factor;
+            (diagonal_buy_qty > required_qty).then(|| Imbalance {
+                side: ImbalanceSide::Buy,
+                price: higher_price,
+                ratio: diagonal_buy_qty / required_qty,
+            })
+        } else {
+            let required_qty = diagonal_buy_qty * factor;
+            (sell_qty > required_qty).then(|| Imbalance {
+                side: ImbalanceSide::Sell,
+                price,
+                ratio: sell_qty / required_qty,
+            })
+        }
+    }
+
+    /// Runs of at least `min_stack` consecutive levels imbalanced to the same side
+    pub fn stacked_imbalances(
+        &self,
+        step: PriceStep,
+        threshold: usize,
+        ignore_zeros: bool,
+        min_stack: usize,
+    ) -> Vec<StackedImbalance> {
+        let mut imbalances = self
+            .trades
+            .keys()
+            .filter_map(|price| self.diagonal_imbalance(*price, step, threshold, ignore_zeros))
+            .collect::<Vec<_>>();
+        imbalances.sort_by_key(|imbalance| (imbalance.side, imbalance.price));
+
+        let mut stacks = vec![];
+        let mut current: Option<StackedImbalance> = None;
+
+        for imbalance in imbalances {
+            match &mut current {
+                Some(stack)
+                    if stack.side == imbalance.side
+                        && stack.high.add_steps(1, step) == imbalance.price =>
+                {
+                    stack.high = imbalance.price;
+                    stack.count += 1;
+                }
+                _ => {
+                    if let Some(stack) = current.take()
+                        && stack.count >= min_stack
+                    {
+                        stacks.push(stack);
+                    }
+                    current = Some(StackedImbalance {
+                        side: imbalance.side,
+                        low: imbalance.price,
+                        high: imbalance.price,
+                        count: 1,
+                    });
+                }
+            }
+        }
+
+        if let Some(stack) = current
+            && stack.count >= min_stack
+        {
+            stacks.push(stack);
+        }
+
+        stacks
+    }
+
     pub fn clear(&mut self) {
         self.trades.clear();
         self.poc = None;
+        self.running_delta = 0.0;
+        self.max_delta = 0.0;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BarStats {
+    pub volume: f32,
+    pub delta: f32,
+    pub trades: usize,
+    /// Highest delta reached within the bar, zero if it never went positive
+    pub max_delta: f32,
+}
+
+impl BarStats {
+    pub fn delta_pct(&self) -> f32 {
+        if self.volume > 0.0 {
+            self.delta / self.volume * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImbalanceSide {
+    /// Buyers lifting the offer one level above the sells
+    Buy,
+    /// Sellers hitting the bid one level below the buys
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Imbalance {
+    pub side: ImbalanceSide,
+    /// Level of the dominant side
+    pub price: Price,
+    /// How far past the threshold the dominant side is, 1.0 being right at it
+    pub ratio: f32,
+}
+
+/// Consecutive levels of a bar imbalanced to the same side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StackedImbalance {
+    pub side: ImbalanceSide,
+    pub low: Price,
+    pub high: Price,
+    pub count: usize,
+}
+
+impl StackedImbalance {
+    /// Edge the level is held at: bottom of a buy stack, top of a sell stack
+    pub fn level(&self) -> Price {
+        match self.side {
+            ImbalanceSide::Buy => self.low,
+            ImbalanceSide::Sell => self.high,
+        }
+    }
+
+    /// Whether a later bar spanning `low..=high` traded through the level
+    pub fn is_traded_through(&self, low: Price, high: Price) -> bool {
+        match self.side {
+            ImbalanceSide::Buy => low < self.low,
+            ImbalanceSide::Sell => high > self.high,
+        }
     }
 }
 
@@ -350,7 +560,71 @@ impl std::fmt::Display for ClusterKind {
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize, Serialize)]
-pub struct Config {}
+pub struct Config {
+    #[serde(default)]
+    pub forming_bar: FormingBar,
+    #[serde(default)]
+    pub sessions: SessionSettings,
+    #[serde(default)]
+    pub liquidations: LiquidationMarkers,
+    #[serde(default)]
+    pub indicator_params: IndicatorParams,
+    #[serde(default)]
+    pub bollinger: BollingerBands,
+    #[serde(default)]
+    pub zoom_lens: ZoomLens,
+    #[serde(default)]
+    pub delta_divergence: DeltaDivergence,
+}
+
+/// Enlarged inset of the footprint cells around the cursor, shown while Alt is held
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ZoomLens {
+    pub enabled: bool,
+    /// Zoom of the inset relative to the chart's own
+    pub magnification: f32,
+}
+
+impl Default for ZoomLens {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            magnification: 2.5,
+        }
+    }
+}
+
+impl ZoomLens {
+    pub const MAGNIFICATION_RANGE: std::ops::RangeInclusive<f32> = 1.5..=5.0;
+    /// Bars the inset spans
+    pub const BARS: f32 = 3.0;
+}
+
+/// How the bar that is still accumulating gets drawn, for traders who only act on closed bars
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FormingBar {
+    #[default]
+    Normal,
+    /// Drawn washed out until it closes
+    Faded,
+    /// Left out until it closes
+    Hidden,
+}
+
+impl FormingBar {
+    pub const ALL: [FormingBar; 3] = [FormingBar::Normal, FormingBar::Faded, FormingBar::Hidden];
+}
+
+impl std::fmt::Display for FormingBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormingBar::Normal => write!(f, "Normal"),
+            FormingBar::Faded => write!(f, "Faded"),
+            FormingBar::Hidden => write!(f, "Hidden"),
+        }
+    }
+}
 
 #[derive(Default, Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub enum ClusterScaling {
@@ -384,16 +658,148 @@ impl std::fmt::Display for ClusterScaling {
 
 impl std::cmp::Eq for ClusterScaling {}
 
+pub const DEFAULT_MIN_STACK: usize = 3;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum FootprintStudy {
     NPoC {
         lookback: usize,
+        #[serde(default)]
+        scope: PocScope,
     },
     Imbalance {
         threshold: usize,
         color_scale: Option<usize>,
         ignore_zeros: bool,
+        /// Minimum run of stacked imbalances marked with a level line
+        #[serde(default)]
+        stacked: Option<usize>,
     },
+    BarStats {
+        rows: BarStatsRows,
+    },
+}
+
+/// What the naked POCs are taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PocScope {
+    #[default]
+    Bar,
+    /// Volume of each closed session window, summed over its bars
+    Session,
+}
+
+impl PocScope {
+    pub const ALL: [PocScope; 2] = [PocScope::Bar, PocScope::Session];
+}
+
+impl std::fmt::Display for PocScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PocScope::Bar => write!(f, "Per bar"),
+            PocScope::Session => write!(f, "Per session"),
+        }
+    }
+}
+
+/// Bar as the session POC scan sees it, `key` placing it on the x axis
+pub struct PocBar<'a> {
+    pub key: u64,
+    pub time: u64,
+    pub low: Price,
+    pub high: Price,
+    pub footprint: &'a KlineTrades,
+}
+
+/// POC of each session that closed by the last of `bars`, keyed by its first bar, filled at
+/// the first bar after the session that traded through it. `bars` go oldest first
+pub fn session_pocs(
+    bars: &[PocBar],
+    spans: &[SessionSpan],
+    step: PriceStep,
+) -> Vec<(u64, PointOfControl)> {
+    let Some(latest) = bars.last().map(|bar| bar.time) else {
+        return vec![];
+    };
+    let mut pocs = vec![];
+
+    for span in spans.iter().filter(|span| span.end <= latest) {
+        let in_span = |bar: &PocBar| (span.start..span.end).contains(&bar.time);
+        let (Some(first), Some(last)) = (
+            bars.iter().position(in_span),
+            bars.iter().rposition(in_span),
+        ) else {
+            continue;
+        };
+
+        let mut volumes: FxHashMap<Price, f32> = FxHashMap::default();
+        for bar in &bars[first..=last] {
+            for (price, group) in &bar.footprint.trades {
+                *volumes.entry(*price).or_default() += group.total_qty();
+            }
+        }
+        let Some((price, volume)) = volumes.into_iter().max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            continue;
+        };
+
+        let status = bars[last + 1..]
+            .iter()
+            .find(|bar| {
+                bar.low.round_to_side_step(true, step) <= price
+                    && bar.high.round_to_side_step(false, step) >= price
+            })
+            .map_or(NPoc::Naked, |bar| NPoc::Filled { at: bar.key });
+
+        pocs.push((
+            bars[first].key,
+            PointOfControl {
+                price,
+                volume,
+                status,
+            },
+        ));
+    }
+
+    pocs
+}
+
+/// Rows of the stats footer drawn under each bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BarStatsRows {
+    pub delta: bool,
+    pub volume: bool,
+    pub delta_pct: bool,
+    pub trades: bool,
+    pub max_delta: bool,
+}
+
+impl BarStatsRows {
+    pub fn count(&self) -> usize {
+        [
+            self.delta,
+            self.volume,
+            self.delta_pct,
+            self.trades,
+            self.max_delta,
+        ]
+        .into_iter()
+        .filter(|shown| *shown)
+        .count()
+    }
+}
+
+impl Default for BarStatsRows {
+    fn default() -> Self {
+        Self {
+            delta: true,
+            volume: true,
+            delta_pct: true,
+            trades: true,
+            max_delta: false,
+        }
+    }
 }
 
 impl FootprintStudy {
@@ -405,17 +811,34 @@ impl FootprintStudy {
                     FootprintStudy::Imbalance { .. },
                     FootprintStudy::Imbalance { .. }
                 )
+                | (
+                    FootprintStudy::BarStats { .. },
+                    FootprintStudy::BarStats { .. }
+                )
         )
     }
 }
 
 impl FootprintStudy {
-    pub const ALL: [FootprintStudy; 2] = [
-        FootprintStudy::NPoC { lookback: 80 },
+    pub const ALL: [FootprintStudy; 3] = [
+        FootprintStudy::NPoC {
+            lookback: 80,
+            scope: PocScope::Bar,
+        },
         FootprintStudy::Imbalance {
             threshold: 200,
             color_scale: Some(400),
             ignore_zeros: true,
+            stacked: Some(DEFAULT_MIN_STACK),
+        },
+        FootprintStudy::BarStats {
+            rows: BarStatsRows {
+                delta: true,
+                volume: true,
+                delta_pct: true,
+                trades: true,
+                max_delta: false,
+            },
         },
     ];
 }
@@ -425,6 +848,7 @@ impl std::fmt::Display for FootprintStudy {
         match self {
             FootprintStudy::NPoC { .. } => write!(f, "Naked Point of Control"),
             FootprintStudy::Imbalance { .. } => write!(f, "Imbalance"),
+            FootprintStudy::BarStats { .. } => write!(f, "Bar Statistics"),
         }
     }
 }
@@ -465,3 +889,123 @@ impl NPoc {
         *self = NPoc::Naked;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn footprint(levels: &[(f32, f32, f32)], step: PriceStep) -> KlineTrades {
+        let mut footprint = KlineTrades::new();
+        for &(price, buy_qty, sell_qty) in levels {
+            for (is_sell, qty) in [(false, buy_qty), (true, sell_qty)] {
+                if qty > 0.0 {
+                    footprint.add_trade_to_nearest_bin(
+                        &Trade {
+                            time: 0,
+                            is_sell,
+                            price: Price::from_f32(price),
+                            qty,
+                            sub_ms_nanos: 0,
+                        },
+                        step,
+                    );
+                }
+            }
+        }
+        footprint
+    }
+
+    #[test]
+    fn stacks_consecutive_diagonal_imbalances() {
+        let step = PriceStep::from_f32(1.0);
+        // buys at 101..=103 dwarf the sells one level below, 104 breaks the run
+        let footprint = footprint(
+            &[
+                (100.0, 1.0, 2.0),
+                (101.0, 10.0, 2.0),
+                (102.0, 10.0, 2.0),
+                (103.0, 10.0, 2.0),
+                (104.0, 2.0, 2.0),
+            ],
+            step,
+        );
+
+        let stacks = footprint.stacked_imbalances(step, 200, true, 3);
+        assert_eq!(stacks.len(), 1);
+
+        // f32 prices only land on whole units once rounded to the step
+        let at = |price: f32| Price::from_f32(price).round_to_step(step);
+
+        let stack = stacks[0];
+        assert_eq!(stack.side, ImbalanceSide::Buy);
+        assert_eq!(stack.count, 3);
+        assert_eq!(stack.level(), at(101.0));
+        assert!(!stack.is_traded_through(at(101.0), at(105.0)));
+        assert!(stack.is_traded_through(at(100.0), at(105.0)));
+
+        assert!(footprint.stacked_imbalances(step, 200, true, 4).is_empty());
+    }
+
+    #[test]
+    fn session_poc_stays_naked_until_traded_through() {
+        let step = PriceStep::from_f32(1.0);
+        let first = footprint(&[(100.0, 5.0, 0.0), (101.0, 1.0, 0.0)], step);
+        let second = footprint(&[(101.0, 2.0, 3.0)], step);
+        let later = KlineTrades::new();
+        let at = |price: f32| Price::from_f32(price).round_to_step(step);
+
+        let bar = |key: u64, low: f32, high: f32, footprint| PocBar {
+            key,
+            time: key * 1_000,
+            low: at(low),
+            high: at(high),
+            footprint,
+        };
+        let spans = [SessionSpan {
+            kind: crate::chart::session::SessionKind::Asia,
+            start: 0,
+            end: 2_000,
+        }];
+
+        // 101 sums to 6 over the session and beats 100
+        let away = [
+            bar(0, 100.0, 101.0, &first),
+            bar(1, 101.0, 101.0, &second),
+            bar(2, 103.0, 105.0, &later),
+        ];
+        let pocs = session_pocs(&away, &spans, step);
+        assert_eq!(pocs.len(), 1);
+        assert_eq!(pocs[0].0, 0);
+        assert_eq!(pocs[0].1.price, at(101.0));
+        assert_eq!(pocs[0].1.status, NPoc::Naked);
+
+        let back = [
+            bar(0, 100.0, 101.0, &first),
+            bar(1, 101.0, 101.0, &second),
+            bar(2, 103.0, 105.0, &later),
+            bar(3, 100.0, 102.0, &later),
+        ];
+        let pocs = session_pocs(&back, &spans, step);
+        assert_eq!(pocs[0].1.status, NPoc::Filled { at: 3 });
+
+        // the session is still open at the last bar
+        assert!(session_pocs(&away[..1], &spans, step).is_empty());
+    }
+
+    #[test]
+    fn bar_stats_keep_the_highest_running_delta() {
+        let step = PriceStep::from_f32(1.0);
+        // +3 then -1 at 100, -4 at 101: delta peaks at 3 and ends at -2
+        let mut footprint = footprint(&[(100.0, 3.0, 1.0), (101.0, 0.0, 4.0)], step);
+
+        let stats = footprint.stats();
+        assert_eq!(stats.volume, 8.0);
+        assert_eq!(stats.delta, -2.0);
+        assert_eq!(stats.trades, 3);
+        assert_eq!(stats.max_delta, 3.0);
+        assert_eq!(stats.delta_pct(), -25.0);
+
+        footprint.clear();
+        assert_eq!(footprint.stats(), BarStats::default());
+    }
+}
@@ -0,0 +1,183 @@
+use iced_core::Color;
+use serde::{Deserialize, Serialize};
+
+/// Colors handed out to new averages, in order
+pub const PRESET_COLORS: [Color; 6] = [
+    Color::from_rgb(0.96, 0.78, 0.26),
+    Color::from_rgb(0.35, 0.62, 0.96),
+    Color::from_rgb(0.75, 0.45, 0.95),
+    Color::from_rgb(0.98, 0.55, 0.25),
+    Color::from_rgb(0.40, 0.85, 0.85),
+    Color::from_rgb(0.92, 0.92, 0.92),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MaKind {
+    Sma,
+    Ema,
+    Wma,
+}
+
+impl MaKind {
+    pub const ALL: [MaKind; 3] = [MaKind::Sma, MaKind::Ema, MaKind::Wma];
+}
+
+impl std::fmt::Display for MaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaKind::Sma => write!(f, "SMA"),
+            MaKind::Ema => write!(f, "EMA"),
+            MaKind::Wma => write!(f, "WMA"),
+        }
+    }
+}
+
+/// Moving average of bar closes, drawn over the price chart
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct MovingAverage {
+    pub kind: MaKind,
+    pub period: u16,
+    pub color: Color,
+}
+
+impl MovingAverage {
+    pub const MAX_COUNT: usize = PRESET_COLORS.len();
+    pub const PERIOD_RANGE: std::ops::RangeInclusive<u16> = 2..=400;
+
+    /// Next average to add next to `existing`, picking a color that isn't taken yet
+    pub fn next_after(existing: &[MovingAverage]) -> Self {
+        let color = PRESET_COLORS
+            .iter()
+            .find(|color| !existing.iter().any(|ma| ma.color == **color))
+            .copied()
+            .unwrap_or(PRESET_COLORS[0]);
+
+        let period = match existing.len() {
+            0 => 21,
+            1 => 50,
+            2 => 100,
+            _ => 200,
+        };
+
+        Self {
+            kind: MaKind::Ema,
+            period,
+            color,
+        }
+    }
+
+    /// Bars before the first drawn one needed for its value to settle
+    pub fn warmup(&self) -> usize {
+        let period = usize::from(self.period.max(1));
+        match self.kind {
+            MaKind::Ema => period * 3,
+            MaKind::Sma | MaKind::Wma => period - 1,
+        }
+    }
+
+    /// Average at each of `closes`, `None` until `period` closes are available
+    pub fn calculate(&self, closes: &[f32]) -> Vec<Option<f32>> {
+        let period = usize::from(self.period.max(1));
+        let mut values = Vec::with_capacity(closes.len());
+
+        match self.kind {
+            MaKind::Sma => {
+                let mut sum = 0.0;
+                for (idx, close) in closes.iter().enumerate() {
+                    sum += close;
+                    if idx >= period {
+                        sum -= closes[idx - period];
+                    }
+                    values.push((idx + 1 >= period).then(|| sum / period as f32));
+                }
+            }
+            MaKind::Ema => {
+                let alpha = 2.0 / (period as f32 + 1.0);
+                let mut ema: Option<f32> = None;
+                let mut seed = 0.0;
+
+                for (idx, close) in closes.iter().enumerate() {
+                    ema = match ema {
+                        Some(prev) => Some(prev + alpha * (close - prev)),
+                        None => {
+                            // seeded with the SMA of the first `period` closes
+                            seed += close;
+                            (idx + 1 == period).then(|| seed / period as f32)
+                        }
+                    };
+                    values.push(ema);
+                }
+            }
+            MaKind::Wma => {
+                let weights = (period * (period + 1) / 2) as f32;
+                for idx in 0..closes.len() {
+                    values.push((idx + 1 >= period).then(|| {
+                        closes[idx + 1 - period..=idx]
+                            .iter()
+                            .enumerate()
+                            .map(|(w, close)| close * (w + 1) as f32)
+                            .sum::<f32>()
+                            / weights
+                    }));
+                }
+            }
+        }
+
+        values
+    }
+}
+
+impl std::fmt::Display for MovingAverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind, self.period)
+    }
+}
+
+/// Bands around an SMA of closes, `multiplier` standard deviations wide
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BollingerBands {
+    pub enabled: bool,
+    pub period: u16,
+    pub multiplier: f32,
+}
+
+impl Default for BollingerBands {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            period: 20,
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl BollingerBands {
+    pub const PERIOD_RANGE: std::ops::RangeInclusive<u16> = 2..=100;
+    pub const MULTIPLIER_RANGE: std::ops::RangeInclusive<f32> = 0.5..=4.0;
+
+    /// Band of the last close in `window`, `None` unless it holds exactly `period` closes
+    pub fn band(&self, window: &[f32]) -> Option<Band> {
+        if window.is_empty() || window.len() != usize::from(self.period) {
+            return None;
+        }
+
+        let len = window.len() as f32;
+        let middle = window.iter().sum::<f32>() / len;
+        let variance = window.iter().map(|c| (c - middle).powi(2)).sum::<f32>() / len;
+        let width = variance.sqrt() * self.multiplier;
+
+        Some(Band {
+            middle,
+            upper: middle + width,
+            lower: middle - width,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub middle: f32,
+    pub upper: f32,
+    pub lower: f32,
+}
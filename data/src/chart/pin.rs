@@ -0,0 +1,64 @@
+use exchange::Ticker;
+use exchange::util::{MinTicksize, Price};
+use serde::{Deserialize, Serialize};
+
+/// Price level the user wants kept labeled on the heatmap and ladder
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct PinnedLevel {
+    pub ticker: Ticker,
+    pub price: Price,
+}
+
+/// Pinned levels of a pane, kept per ticker so switching back restores them
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct PinnedLevels {
+    pub items: Vec<PinnedLevel>,
+}
+
+impl PinnedLevels {
+    pub fn is_pinned(&self, ticker: Ticker, price: Price) -> bool {
+        self.items
+            .iter()
+            .any(|pin| pin.ticker == ticker && pin.price == price)
+    }
+
+    /// Pins `price`, or unpins it when it already is
+    pub fn toggle(&mut self, ticker: Ticker, price: Price) {
+        if self.is_pinned(ticker, price) {
+            self.items
+                .retain(|pin| !(pin.ticker == ticker && pin.price == price));
+        } else {
+            self.items.push(PinnedLevel { ticker, price });
+        }
+    }
+
+    pub fn clear(&mut self, ticker: Ticker) {
+        self.items.retain(|pin| pin.ticker != ticker);
+    }
+
+    pub fn for_ticker(&self, ticker: Ticker) -> impl Iterator<Item = Price> + '_ {
+        self.items
+            .iter()
+            .filter(move |pin| pin.ticker == ticker)
+            .map(|pin| pin.price)
+    }
+}
+
+/// Label of a pinned level, e.g. "64,250.0  +120.5 (+0.19%)"
+pub fn pin_label(price: Price, last: Option<Price>, precision: MinTicksize) -> String {
+    let text = price.to_string(precision);
+
+    let Some(last) = last.filter(|last| last.to_f32() != 0.0) else {
+        return text;
+    };
+
+    let distance = price.to_f32() - last.to_f32();
+    let pct = distance / last.to_f32() * 100.0;
+    let sign = if distance >= 0.0 { "+" } else { "-" };
+
+    format!(
+        "{text}  {sign}{} ({sign}{:.2}%)",
+        Price::from_f32(distance.abs()).to_string(precision),
+        pct.abs()
+    )
+}
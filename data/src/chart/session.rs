@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+const DAY_MS: u64 = 86_400_000;
+const MINUTE_MS: u64 = 60_000;
+
+pub const MAX_SESSIONS: usize = 4;
+
+/// Time of day in UTC, as minutes since midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct SessionTime(pub u16);
+
+impl SessionTime {
+    /// Quarter-hour steps offered when editing a session window
+    pub const STEP_MINUTES: u16 = 15;
+
+    pub const fn new(hour: u16, minute: u16) -> Self {
+        Self(hour * 60 + minute)
+    }
+
+    pub fn all_steps() -> Vec<SessionTime> {
+        (0..24 * 60)
+            .step_by(Self::STEP_MINUTES as usize)
+            .map(SessionTime)
+            .collect()
+    }
+
+    fn offset_ms(self) -> u64 {
+        u64::from(self.0) * MINUTE_MS
+    }
+}
+
+impl std::fmt::Display for SessionTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SessionKind {
+    Asia,
+    London,
+    NewYork,
+    Custom,
+}
+
+impl std::fmt::Display for SessionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionKind::Asia => write!(f, "Asia"),
+            SessionKind::London => write!(f, "London"),
+            SessionKind::NewYork => write!(f, "New York"),
+            SessionKind::Custom => write!(f, "Custom"),
+        }
+    }
+}
+
+/// A daily trading window in UTC, wraps past midnight when `end` is before `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionWindow {
+    pub kind: SessionKind,
+    pub start: SessionTime,
+    pub end: SessionTime,
+    pub enabled: bool,
+}
+
+impl SessionWindow {
+    pub const fn new(kind: SessionKind, start: SessionTime, end: SessionTime) -> Self {
+        Self {
+            kind,
+            start,
+            end,
+            enabled: true,
+        }
+    }
+
+    fn duration_ms(&self) -> u64 {
+        let (start, end) = (self.start.offset_ms(), self.end.offset_ms());
+        if end > start {
+            end - start
+        } else {
+            DAY_MS - start + end
+        }
+    }
+}
+
+/// One occurrence of a session window, timestamps are unix milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSpan {
+    pub kind: SessionKind,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Splits the trading day into named sessions so profiles and studies can be
+/// accumulated per session instead of over the whole visible history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionManager {
+    pub windows: [SessionWindow; MAX_SESSIONS],
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self {
+            windows: [
+                SessionWindow::new(
+                    SessionKind::Asia,
+                    SessionTime::new(0, 0),
+                    SessionTime::new(7, 0),
+                ),
+                SessionWindow::new(
+                    SessionKind::London,
+                    SessionTime::new(7, 0),
+                    SessionTime::new(13, 30),
+                ),
+                SessionWindow::new(
+                    SessionKind::NewYork,
+                    SessionTime::new(13, 30),
+                    SessionTime::new(20, 0),
+                ),
+                SessionWindow {
+                    enabled: false,
+                    ..SessionWindow::new(
+                        SessionKind::Custom,
+                        SessionTime::new(20, 0),
+                        SessionTime::new(0, 0),
+                    )
+                },
+            ],
+        }
+    }
+}
+
+impl SessionManager {
+    /// All session occurrences overlapping `from..=to`, ordered by start time
+    pub fn spans_between(&self, from: u64, to: u64) -> Vec<SessionSpan> {
+        if to < from {
+            return vec![];
+        }
+
+        // a window wrapping midnight may have opened the day before `from`
+        let first_day = (from / DAY_MS).saturating_sub(1);
+        let last_day = to / DAY_MS;
+
+        let mut spans: Vec<SessionSpan> = (first_day..=last_day)
+            .flat_map(|day| {
+                self.windows
+                    .iter()
+                    .filter(|window| window.enabled && window.start != window.end)
+                    .map(move |window| {
+                        let start = day * DAY_MS + window.start.offset_ms();
+                        SessionSpan {
+                            kind: window.kind,
+                            start,
+                            end: start + window.duration_ms(),
+                        }
+                    })
+            })
+            .filter(|span| span.end > from && span.start <= to)
+            .collect();
+
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+
+    /// The session that most recently opened at or before `timestamp`, even if it has since closed
+    pub fn latest_span(&self, timestamp: u64) -> Option<SessionSpan> {
+        self.spans_between(timestamp.saturating_sub(DAY_MS), timestamp)
+            .into_iter()
+            .rfind(|span| span.start <= timestamp)
+    }
+}
+
+/// Per-chart session options
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionSettings {
+    /// Shade session windows on the time axis
+    pub show_shading: bool,
+    /// Restart study accumulation at each session open
+    pub reset_studies: bool,
+    pub manager: SessionManager,
+}
+
+impl SessionSettings {
+    /// Earliest timestamp studies should accumulate from, if resetting per session
+    pub fn reset_boundary(&self, latest: u64) -> Option<u64> {
+        if !self.reset_studies {
+            return None;
+        }
+        self.manager.latest_span(latest).map(|span| span.start)
+    }
+}
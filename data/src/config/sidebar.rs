@@ -1,6 +1,6 @@
 use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::tickers_table;
+use crate::{scanner, tickers_table};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(default)]
@@ -10,6 +10,8 @@ pub struct Sidebar {
     pub active_menu: Option<Menu>,
     #[serde(default)]
     pub tickers_table: Option<tickers_table::Settings>,
+    #[serde(default)]
+    pub scanner: scanner::Settings,
 }
 
 impl Sidebar {
@@ -36,6 +38,7 @@ impl Default for Sidebar {
             position: Position::Left,
             active_menu: None,
             tickers_table: None,
+            scanner: scanner::Settings::default(),
         }
     }
 }
@@ -69,4 +72,6 @@ pub enum Menu {
     Settings,
     Audio,
     ThemeEditor,
+    Scanner,
+    RateLimits,
 }
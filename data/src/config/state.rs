@@ -18,13 +18,32 @@ pub struct State {
     pub layout_manager: Layouts,
     pub selected_theme: Theme,
     pub custom_theme: Option<Theme>,
+    /// Custom themes named and saved in the theme editor
+    pub saved_themes: Vec<Theme>,
+    pub auto_theme: super::theme::AutoTheme,
     pub main_window: Option<WindowSpec>,
     pub timezone: UserTimezone,
     pub sidebar: Sidebar,
     pub scale_factor: ScaleFactor,
     pub audio_cfg: AudioStream,
+    /// Global switch of older configs, read once to consent to Binance
+    #[serde(skip_serializing)]
     pub trade_fetch_enabled: bool,
+    /// Exchanges the user consented to fetch historical trades from
+    pub trade_fetch_venues: Vec<exchange::adapter::ExchangeInclusive>,
+    pub offline_mode: bool,
+    /// Hosts still reachable in offline mode
+    pub allowed_hosts: Vec<String>,
+    /// Lists only the simulated venue, for demoing without a network
+    pub demo_mode: bool,
+    /// REST requests each exchange may have in flight at once, `None` keeps the default
+    pub max_concurrent_requests: Option<usize>,
+    pub proxy: Option<exchange::network::Proxy>,
     pub size_in_quote_ccy: exchange::SizeUnit,
+    pub export_schedule: crate::export::ExportSchedule,
+    pub idle: crate::idle::IdleSettings,
+    pub autosave: crate::autosave::AutosaveSettings,
+    pub paper_trading: crate::paper::Config,
 }
 
 impl State {
@@ -32,24 +51,42 @@ impl State {
         layout_manager: Layouts,
         selected_theme: Theme,
         custom_theme: Option<Theme>,
+        saved_themes: Vec<Theme>,
+        auto_theme: super::theme::AutoTheme,
         main_window: Option<WindowSpec>,
         timezone: UserTimezone,
         sidebar: Sidebar,
         scale_factor: ScaleFactor,
         audio_cfg: AudioStream,
         volume_size_unit: exchange::SizeUnit,
+        export_schedule: crate::export::ExportSchedule,
+        idle: crate::idle::IdleSettings,
+        autosave: crate::autosave::AutosaveSettings,
+        paper_trading: crate::paper::Config,
     ) -> Self {
         State {
             layout_manager,
             selected_theme: Theme(selected_theme.0),
             custom_theme: custom_theme.map(|t| Theme(t.0)),
+            saved_themes,
+            auto_theme,
             main_window,
             timezone,
             sidebar,
             scale_factor,
             audio_cfg,
-            trade_fetch_enabled: exchange::fetcher::is_trade_fetch_enabled(),
+            trade_fetch_enabled: false,
+            trade_fetch_venues: exchange::fetcher::trade_fetch_venues(),
+            offline_mode: exchange::network::is_offline(),
+            allowed_hosts: exchange::network::allowed_hosts(),
+            demo_mode: exchange::network::is_demo(),
+            max_concurrent_requests: Some(exchange::limiter::max_concurrent()),
+            proxy: exchange::network::proxy(),
             size_in_quote_ccy: volume_size_unit,
+            export_schedule,
+            idle,
+            autosave,
+            paper_trading,
         }
     }
 }
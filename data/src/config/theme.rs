@@ -3,6 +3,7 @@
 /// All credits and thanks to the authors of [`Halloy`] and [`iced_core`]
 pub use professional_trading_theme as default_theme;
 
+use chrono::{NaiveTime, Timelike};
 use iced_core::{
     Color,
     theme::{Custom, Palette},
@@ -21,6 +22,9 @@ struct SerTheme {
     name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     palette: Option<Palette>,
+    /// Name given to a custom palette in the theme editor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
 }
 
 impl Default for Theme {
@@ -116,6 +120,7 @@ impl Serialize for Theme {
                 } else {
                     Some(self.0.palette())
                 },
+                label: (!is_default_theme).then(|| custom.to_string()),
             };
             ser_theme.serialize(serializer)
         } else {
@@ -202,7 +207,8 @@ impl<'de> Deserialize<'de> for Theme {
             "footprint-pro" => iced_core::Theme::Custom(footprint_optimized_theme().into()),
             "custom" => {
                 if let Some(palette) = serialized.palette {
-                    iced_core::Theme::Custom(Custom::new("Custom".to_string(), palette).into())
+                    let name = serialized.label.unwrap_or_else(|| "Custom".to_string());
+                    iced_core::Theme::Custom(Custom::new(name, palette).into())
                 } else {
                     return Err(serde::de::Error::custom(
                         "Custom theme missing palette data",
@@ -298,14 +304,71 @@ pub fn create_gradient(start: Color, end: Color, steps: usize) -> Vec<Color> {
 
 /// NEW: Check if color combination has sufficient contrast for accessibility
 pub fn has_sufficient_contrast(foreground: Color, background: Color) -> bool {
-    let l1 = luminance(foreground);
-    let l2 = luminance(background);
-    let contrast = if l1 > l2 {
+    contrast_ratio(foreground, background) >= 4.5 // WCAG AA standard
+}
+
+/// Minimum contrast for lines and markers drawn with user-picked colors,
+/// WCAG asks 3:1 for graphical objects
+pub const SERIES_MIN_CONTRAST: f32 = 3.0;
+
+/// Palette roles hard to read on its background, with their contrast ratio.
+/// Text needs the WCAG AA ratio, the other roles the one for graphical objects
+pub fn low_contrast_roles(palette: &Palette) -> Vec<(&'static str, f32)> {
+    let background = palette.background;
+    let mut low = vec![];
+
+    if !has_sufficient_contrast(palette.text, background) {
+        low.push(("Text", contrast_ratio(palette.text, background)));
+    }
+
+    for (role, color) in [
+        ("Primary", palette.primary),
+        ("Success", palette.success),
+        ("Danger", palette.danger),
+        ("Warning", palette.warning),
+    ] {
+        let ratio = contrast_ratio(color, background);
+        if ratio < SERIES_MIN_CONTRAST {
+            low.push((role, ratio));
+        }
+    }
+
+    low
+}
+
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let l1 = luminance(a);
+    let l2 = luminance(b);
+    if l1 > l2 {
         (l1 + 0.05) / (l2 + 0.05)
     } else {
         (l2 + 0.05) / (l1 + 0.05)
-    };
-    contrast >= 4.5 // WCAG AA standard
+    }
+}
+
+/// Shifts the lightness of `color` away from `background` until they reach `min_contrast`,
+/// hue and saturation are kept. `None` if the color is already readable
+pub fn remap_for_contrast(color: Color, background: Color, min_contrast: f32) -> Option<Color> {
+    if contrast_ratio(color, background) >= min_contrast {
+        return None;
+    }
+
+    let towards_light = is_dark(background);
+    let mut adjusted = color;
+
+    for _ in 0..20 {
+        adjusted = if towards_light {
+            lighten(adjusted, 0.05)
+        } else {
+            darken(adjusted, 0.05)
+        };
+
+        if contrast_ratio(adjusted, background) >= min_contrast {
+            break;
+        }
+    }
+
+    Some(adjusted)
 }
 
 /// NEW: Calculate luminance for contrast checking
@@ -536,6 +599,122 @@ pub fn from_hsv_degrees(h_deg: f32, s: f32, v: f32) -> Color {
     from_hsva(Hsva::new(hue, s, v, 1.0))
 }
 
+/// When the app switches between a light and a dark theme by itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum AutoSwitch {
+    #[default]
+    Off,
+    /// Follows the light or dark preference of the OS
+    FollowSystem,
+    /// Light theme during the day, dark one at night
+    Scheduled,
+}
+
+impl AutoSwitch {
+    pub const ALL: [AutoSwitch; 3] = [Self::Off, Self::FollowSystem, Self::Scheduled];
+}
+
+impl std::fmt::Display for AutoSwitch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AutoSwitch::Off => write!(f, "Off"),
+            AutoSwitch::FollowSystem => write!(f, "Follow system"),
+            AutoSwitch::Scheduled => write!(f, "Scheduled"),
+        }
+    }
+}
+
+/// Wall-clock time of day on this machine, unlike the UTC times of chart sessions.
+/// Stored as minutes since local midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LocalTime(pub NaiveTime);
+
+impl LocalTime {
+    /// Quarter-hour steps offered in the scheduler
+    pub const STEP_MINUTES: u32 = 15;
+
+    pub fn new(hour: u32, minute: u32) -> Self {
+        Self(NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default())
+    }
+
+    pub fn all_steps() -> Vec<LocalTime> {
+        (0..24 * 60)
+            .step_by(Self::STEP_MINUTES as usize)
+            .map(|minute| Self::new(minute / 60, minute % 60))
+            .collect()
+    }
+
+    fn minute_of_day(self) -> u16 {
+        (self.0.hour() * 60 + self.0.minute()) as u16
+    }
+}
+
+impl std::fmt::Display for LocalTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%H:%M"))
+    }
+}
+
+impl Serialize for LocalTime {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.minute_of_day())
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalTime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let minutes = u16::deserialize(deserializer)? % (24 * 60);
+        Ok(Self::new(u32::from(minutes / 60), u32::from(minutes % 60)))
+    }
+}
+
+/// Light and dark themes picked by name, and when to use which
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AutoTheme {
+    pub switch: AutoSwitch,
+    pub light: String,
+    pub dark: String,
+    /// Local time of day the light theme takes over
+    pub light_from: LocalTime,
+    /// Local time of day the dark theme takes over
+    pub dark_from: LocalTime,
+}
+
+impl Default for AutoTheme {
+    fn default() -> Self {
+        Self {
+            switch: AutoSwitch::Off,
+            light: "Light Trader".to_string(),
+            dark: "Pro Trader".to_string(),
+            light_from: LocalTime::new(7, 0),
+            dark_from: LocalTime::new(19, 0),
+        }
+    }
+}
+
+impl AutoTheme {
+    /// Name of the theme due at local time `now`, `None` while switching is off or the OS
+    /// states no preference
+    pub fn due_theme(&self, system_is_dark: Option<bool>, now: NaiveTime) -> Option<&str> {
+        let is_light = match self.switch {
+            AutoSwitch::Off => return None,
+            AutoSwitch::FollowSystem => !system_is_dark?,
+            AutoSwitch::Scheduled => {
+                let (light, dark) = (self.light_from.0, self.dark_from.0);
+                if light <= dark {
+                    (light..dark).contains(&now)
+                } else {
+                    // light window wraps past midnight
+                    now >= light || now < dark
+                }
+            }
+        };
+
+        Some(if is_light { &self.light } else { &self.dark })
+    }
+}
+
 /// NEW: Theme manager for dynamic theme switching
 pub struct ThemeManager {
     current_theme: Theme,
@@ -562,9 +741,13 @@ impl ThemeManager {
             ),
         ];
 
+        let builtin = iced_core::Theme::ALL
+            .iter()
+            .map(|theme| (theme.to_string(), Theme(theme.clone())));
+
         Self {
             current_theme: Theme::default(),
-            available_themes: themes,
+            available_themes: themes.into_iter().chain(builtin).collect(),
         }
     }
 
@@ -576,6 +759,13 @@ impl ThemeManager {
         &self.available_themes
     }
 
+    pub fn find(&self, theme_name: &str) -> Option<&Theme> {
+        self.available_themes
+            .iter()
+            .find(|(name, _)| name == theme_name)
+            .map(|(_, theme)| theme)
+    }
+
     pub fn set_theme(&mut self, theme_name: &str) -> bool {
         if let Some((_, theme)) = self
             .available_themes
@@ -589,3 +779,63 @@ impl ThemeManager {
         }
     }
 }
+
+impl Default for ThemeManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        LocalTime::new(hour, minute).0
+    }
+
+    #[test]
+    fn scheduled_switch_handles_windows_past_midnight() {
+        let mut auto = AutoTheme {
+            switch: AutoSwitch::Scheduled,
+            ..AutoTheme::default()
+        };
+
+        assert_eq!(auto.due_theme(None, at(12, 0)), Some("Light Trader"));
+        assert_eq!(auto.due_theme(None, at(19, 0)), Some("Pro Trader"));
+        assert_eq!(auto.due_theme(None, at(3, 0)), Some("Pro Trader"));
+
+        auto.light_from = LocalTime::new(22, 0);
+        auto.dark_from = LocalTime::new(6, 0);
+        assert_eq!(auto.due_theme(None, at(23, 0)), Some("Light Trader"));
+        assert_eq!(auto.due_theme(None, at(3, 0)), Some("Light Trader"));
+        assert_eq!(auto.due_theme(None, at(12, 0)), Some("Pro Trader"));
+
+        auto.switch = AutoSwitch::FollowSystem;
+        assert_eq!(auto.due_theme(None, at(0, 0)), None);
+        assert_eq!(auto.due_theme(Some(true), at(0, 0)), Some("Pro Trader"));
+    }
+
+    #[test]
+    fn due_theme_holds_through_midnight_and_flips_on_the_minute() {
+        let auto = AutoTheme {
+            switch: AutoSwitch::Scheduled,
+            light_from: LocalTime::new(6, 30),
+            dark_from: LocalTime::new(18, 0),
+            ..AutoTheme::default()
+        };
+        let last_second = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+
+        assert_eq!(auto.due_theme(None, last_second), Some("Pro Trader"));
+        assert_eq!(auto.due_theme(None, at(0, 0)), Some("Pro Trader"));
+        assert_eq!(auto.due_theme(None, at(6, 29)), Some("Pro Trader"));
+        assert_eq!(auto.due_theme(None, at(6, 30)), Some("Light Trader"));
+        assert_eq!(auto.due_theme(None, at(17, 59)), Some("Light Trader"));
+        assert_eq!(auto.due_theme(None, at(18, 0)), Some("Pro Trader"));
+
+        // saved as minutes since local midnight, as the fields were before
+        let json = serde_json::to_string(&auto).unwrap();
+        assert!(json.contains(r#""light_from":390"#));
+        assert_eq!(serde_json::from_str::<AutoTheme>(&json).unwrap(), auto);
+    }
+}
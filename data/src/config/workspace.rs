@@ -0,0 +1,216 @@
+//! Layouts and themes bundled into one versioned file, to share a setup or move it
+//! between machines
+
+use super::state::Layouts;
+use crate::{Layout, Theme};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the file layout changes, older files get migrated on import
+pub const WORKSPACE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub version: u32,
+    pub layouts: Vec<Layout>,
+    /// Name of the layout that was active when exported
+    #[serde(default)]
+    pub active_layout: Option<String>,
+    #[serde(default)]
+    pub selected_theme: Theme,
+    #[serde(default)]
+    pub custom_theme: Option<Theme>,
+    /// Named themes of the theme editor
+    #[serde(default)]
+    pub saved_themes: Vec<Theme>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WorkspaceError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("Not a valid workspace file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Made by a newer version (workspace v{0}), update to import it")]
+    TooNew(u64),
+    #[error("Workspace has no layouts")]
+    Empty,
+    #[error("Layout #{0} has no name")]
+    UnnamedLayout(usize),
+}
+
+impl Workspace {
+    pub fn new(
+        layouts: Layouts,
+        selected_theme: Theme,
+        custom_theme: Option<Theme>,
+        saved_themes: Vec<Theme>,
+    ) -> Self {
+        Self {
+            version: WORKSPACE_VERSION,
+            layouts: layouts.layouts,
+            active_layout: layouts.active_layout,
+            selected_theme,
+            custom_theme,
+            saved_themes,
+        }
+    }
+
+    /// Parses a workspace file of any version up to the current one
+    pub fn from_json(json: &str) -> Result<Self, WorkspaceError> {
+        let value: Value = serde_json::from_str(json)?;
+
+        let version = match value.get("version") {
+            Some(version) => version.as_u64().ok_or_else(|| {
+                <serde_json::Error as serde::de::Error>::custom("version is not a number")
+            })?,
+            None => 0,
+        };
+
+        if version > u64::from(WORKSPACE_VERSION) {
+            return Err(WorkspaceError::TooNew(version));
+        }
+
+        let value = if version == 0 {
+            migrate_saved_state(value)
+        } else {
+            value
+        };
+
+        let mut workspace: Workspace = serde_json::from_value(value)?;
+        workspace.validate()?;
+
+        Ok(workspace)
+    }
+
+    fn validate(&mut self) -> Result<(), WorkspaceError> {
+        if self.layouts.is_empty() {
+            return Err(WorkspaceError::Empty);
+        }
+
+        if let Some(idx) = self
+            .layouts
+            .iter()
+            .position(|layout| layout.name.trim().is_empty())
+        {
+            return Err(WorkspaceError::UnnamedLayout(idx + 1));
+        }
+
+        if self
+            .active_layout
+            .as_ref()
+            .is_some_and(|name| !self.layouts.iter().any(|layout| layout.name == *name))
+        {
+            self.active_layout = None;
+        }
+
+        self.version = WORKSPACE_VERSION;
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> Result<Self, WorkspaceError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Writes a timestamped workspace file into `dir`, returning its path
+    pub fn write(&self, dir: &Path, now_ms: u64) -> Result<PathBuf, WorkspaceError> {
+        std::fs::create_dir_all(dir)?;
+
+        let stamp = chrono::DateTime::from_timestamp_millis(now_ms as i64)
+            .map(|dt| dt.format("%Y-%m-%d_%H-%M-%S").to_string())
+            .unwrap_or_else(|| now_ms.to_string());
+        let path = dir.join(format!("workspace_{stamp}.json"));
+
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Where workspaces get exported to
+    pub fn directory() -> PathBuf {
+        crate::data_path(Some("workspaces"))
+    }
+}
+
+/// Unversioned files are saved app states, with the layouts under `layout_manager`
+fn migrate_saved_state(state: Value) -> Value {
+    let mut workspace = serde_json::Map::new();
+    workspace.insert("version".to_string(), Value::from(1));
+
+    if let Some(layout_manager) = state.get("layout_manager") {
+        for key in ["layouts", "active_layout"] {
+            if let Some(value) = layout_manager.get(key) {
+                workspace.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+
+    for key in ["selected_theme", "custom_theme", "saved_themes"] {
+        if let Some(value) = state.get(key).filter(|value| !value.is_null()) {
+            workspace.insert(key.to_string(), value.clone());
+        }
+    }
+
+    Value::Object(workspace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::state::State;
+
+    fn saved_theme(name: &str) -> Theme {
+        Theme(iced_core::Theme::custom(
+            name.to_string(),
+            iced_core::Theme::Dark.palette(),
+        ))
+    }
+
+    fn theme_names(workspace: &Workspace) -> Vec<String> {
+        workspace
+            .saved_themes
+            .iter()
+            .map(|theme| theme.0.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn migrates_saved_state_and_rejects_newer_files() {
+        let state = State {
+            layout_manager: Layouts {
+                layouts: vec![Layout::default()],
+                active_layout: Some("Gone".to_string()),
+            },
+            saved_themes: vec![saved_theme("Night desk")],
+            ..State::default()
+        };
+        let json = serde_json::to_string(&state).unwrap();
+
+        let workspace = Workspace::from_json(&json).unwrap();
+        assert_eq!(workspace.version, WORKSPACE_VERSION);
+        assert_eq!(workspace.layouts.len(), 1);
+        assert_eq!(workspace.active_layout, None);
+        assert_eq!(theme_names(&workspace), vec!["Night desk"]);
+
+        let exported = serde_json::to_string(&workspace).unwrap();
+        let imported = Workspace::from_json(&exported).unwrap();
+        assert_eq!(imported.layouts.len(), 1);
+        assert_eq!(theme_names(&imported), vec!["Night desk"]);
+
+        let newer = exported.replacen(
+            &format!("\"version\":{WORKSPACE_VERSION}"),
+            &format!("\"version\":{}", WORKSPACE_VERSION + 1),
+            1,
+        );
+        assert!(matches!(
+            Workspace::from_json(&newer),
+            Err(WorkspaceError::TooNew(_))
+        ));
+
+        assert!(matches!(
+            Workspace::from_json(r#"{"version":1,"layouts":[]}"#),
+            Err(WorkspaceError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//! Health of the live streams of each exchange, as shown by the connection indicator
+
+use exchange::adapter::Exchange;
+use exchange::clock::ClockSample;
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Span the message rate is averaged over
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+/// A connected exchange that went this long without a message is flagged
+pub const STALE_AFTER: Duration = Duration::from_secs(15);
+/// Exchanges nothing was heard from for this long are dropped, their streams are gone
+const FORGET_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkState {
+    Connected,
+    Disconnected,
+    Reconnecting { attempt: u32, retry_at: Instant },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Healthy,
+    /// Connected but quiet for longer than `STALE_AFTER`
+    Stale,
+    Down,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExchangeHealth {
+    pub state: LinkState,
+    pub last_message_at: Option<Instant>,
+    /// Latest websocket ping round trip of any of its streams
+    pub latency: Option<Duration>,
+    /// REST round trip of the latest clock sample
+    pub rest_latency: Option<Duration>,
+    /// Server clock minus local clock in ms, per the latest clock sample
+    pub clock_offset_ms: Option<i64>,
+    last_event_at: Instant,
+    /// Frame counts reported by its streams within the rate window
+    reports: VecDeque<(Instant, u32)>,
+}
+
+impl ExchangeHealth {
+    fn new(state: LinkState, now: Instant) -> Self {
+        Self {
+            state,
+            last_message_at: None,
+            latency: None,
+            rest_latency: None,
+            clock_offset_ms: None,
+            last_event_at: now,
+            reports: VecDeque::new(),
+        }
+    }
+
+    /// Frames per second across its streams
+    pub fn message_rate(&self, now: Instant) -> f32 {
+        let messages: u32 = self
+            .reports
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= RATE_WINDOW)
+            .map(|(_, count)| count)
+            .sum();
+
+        messages as f32 / RATE_WINDOW.as_secs_f32()
+    }
+
+    pub fn level(&self, now: Instant) -> Level {
+        match self.state {
+            LinkState::Connected => match self.last_message_at {
+                Some(at) if now.duration_since(at) > STALE_AFTER => Level::Stale,
+                _ => Level::Healthy,
+            },
+            LinkState::Disconnected | LinkState::Reconnecting { .. } => Level::Down,
+        }
+    }
+}
+
+/// Latest known state of every exchange streamed from, fed by stream events
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    exchanges: FxHashMap<Exchange, ExchangeHealth>,
+}
+
+impl ConnectionHealth {
+    pub fn set_state(&mut self, exchange: Exchange, state: LinkState, now: Instant) {
+        let health = self.entry(exchange, state, now);
+        health.state = state;
+        health.last_event_at = now;
+    }
+
+    /// Any data received from `exchange`
+    pub fn message(&mut self, exchange: Exchange, now: Instant) {
+        let health = self.entry(exchange, LinkState::Connected, now);
+        health.last_message_at = Some(now);
+        health.last_event_at = now;
+    }
+
+    pub fn telemetry(
+        &mut self,
+        exchange: Exchange,
+        messages: u32,
+        latency: Option<Duration>,
+        now: Instant,
+    ) {
+        let health = self.entry(exchange, LinkState::Connected, now);
+
+        if messages > 0 {
+            health.last_message_at = Some(now);
+        }
+        if latency.is_some() {
+            health.latency = latency;
+        }
+        health.last_event_at = now;
+
+        health.reports.push_back((now, messages));
+        while health
+            .reports
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > RATE_WINDOW)
+        {
+            health.reports.pop_front();
+        }
+    }
+
+    /// Clock samples only land on exchanges being streamed from
+    pub fn clock(&mut self, exchange: Exchange, sample: ClockSample) {
+        if let Some(health) = self.exchanges.get_mut(&exchange) {
+            health.rest_latency = Some(sample.rtt);
+            health.clock_offset_ms = Some(sample.offset_ms);
+        }
+    }
+
+    /// Drops exchanges that went silent, e.g. after their panes were closed
+    pub fn prune(&mut self, now: Instant) {
+        self.exchanges
+            .retain(|_, health| now.duration_since(health.last_event_at) < FORGET_AFTER);
+    }
+
+    /// Exchanges in `Exchange::ALL` order
+    pub fn iter(&self) -> impl Iterator<Item = (Exchange, &ExchangeHealth)> {
+        Exchange::ALL
+            .into_iter()
+            .filter_map(|exchange| Some((exchange, self.exchanges.get(&exchange)?)))
+    }
+
+    /// Worst level among the exchanges, `None` while nothing streams
+    pub fn level(&self, now: Instant) -> Option<Level> {
+        self.exchanges
+            .values()
+            .map(|health| health.level(now))
+            .max()
+    }
+
+    fn entry(&mut self, exchange: Exchange, state: LinkState, now: Instant) -> &mut ExchangeHealth {
+        self.exchanges
+            .entry(exchange)
+            .or_insert_with(|| ExchangeHealth::new(state, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_averages_reports_within_the_window() {
+        let mut health = ConnectionHealth::default();
+        let start = Instant::now();
+
+        health.telemetry(Exchange::BinanceLinear, 50, None, start);
+        health.telemetry(
+            Exchange::BinanceLinear,
+            50,
+            None,
+            start + Duration::from_secs(2),
+        );
+
+        let (_, binance) = health.iter().next().unwrap();
+        assert_eq!(binance.message_rate(start + Duration::from_secs(2)), 10.0);
+        // the first report aged out
+        assert_eq!(binance.message_rate(start + Duration::from_secs(11)), 5.0);
+    }
+
+    #[test]
+    fn quiet_connection_turns_stale() {
+        let mut health = ConnectionHealth::default();
+        let start = Instant::now();
+
+        health.set_state(Exchange::BybitLinear, LinkState::Connected, start);
+        health.message(Exchange::BybitLinear, start);
+
+        assert_eq!(health.level(start), Some(Level::Healthy));
+        assert_eq!(
+            health.level(start + STALE_AFTER + Duration::from_secs(1)),
+            Some(Level::Stale)
+        );
+    }
+
+    #[test]
+    fn worst_exchange_sets_the_level() {
+        let mut health = ConnectionHealth::default();
+        let now = Instant::now();
+
+        health.set_state(Exchange::BybitLinear, LinkState::Connected, now);
+        health.set_state(Exchange::OkexLinear, LinkState::Disconnected, now);
+
+        assert_eq!(health.level(now), Some(Level::Down));
+    }
+}
@@ -0,0 +1,197 @@
+//! Exchange API keys saved in the data folder, sealed with ChaCha20-Poly1305 under a random
+//! key kept next to them. Keeps them out of the saved state and off casual disclosure (backups,
+//! shared layouts, screenshots of a text editor), but anyone who can read the data folder as
+//! this user can also read the key
+
+use exchange::adapter::ExchangeInclusive;
+use exchange::auth::Credentials;
+
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+const CREDENTIALS_PATH: &str = "credentials.bin";
+const KEY_PATH: &str = "credentials.key";
+const KEY_LEN: usize = 32;
+
+pub type Store = FxHashMap<ExchangeInclusive, Credentials>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CredentialsError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("Encryption: {0}")]
+    Crypto(String),
+    #[error("Saved credentials couldn't be decrypted, they were altered or the key changed")]
+    Corrupt,
+    #[error(
+        "Saved credentials can't be opened, their key file is missing. Clear the saved API keys to start over"
+    )]
+    KeyMissing,
+    #[error("Saved credentials: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Saved credentials, empty when none were saved yet
+pub fn load() -> Result<Store, CredentialsError> {
+    let path = crate::data_path(Some(CREDENTIALS_PATH));
+    if !path.exists() {
+        return Ok(Store::default());
+    }
+
+    let sealed = std::fs::read(path)?;
+    let plaintext = open(&read_or_create_key()?, &sealed)?;
+
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+/// Replaces the saved credentials, removes the file once the last venue is cleared
+pub fn save(store: &Store) -> Result<(), CredentialsError> {
+    let path = crate::data_path(Some(CREDENTIALS_PATH));
+
+    if store.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+
+    let plaintext = serde_json::to_vec(store)?;
+    let sealed = seal(&read_or_create_key()?, &plaintext)?;
+
+    write_private(&path, &sealed)
+}
+
+fn read_or_create_key() -> Result<[u8; KEY_LEN], CredentialsError> {
+    key_at(
+        &crate::data_path(Some(KEY_PATH)),
+        &crate::data_path(Some(CREDENTIALS_PATH)),
+    )
+}
+
+/// Key at `path`, a new one only while nothing sealed under a lost key sits at `sealed`
+fn key_at(path: &Path, sealed: &Path) -> Result<[u8; KEY_LEN], CredentialsError> {
+    if path.exists() {
+        return std::fs::read(path)?
+            .try_into()
+            .map_err(|_| CredentialsError::Corrupt);
+    }
+    if sealed.exists() {
+        return Err(CredentialsError::KeyMissing);
+    }
+
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| CredentialsError::Crypto("no randomness for a new key".to_string()))?;
+
+    write_private(path, &key)?;
+    Ok(key)
+}
+
+/// Random nonce followed by the ciphertext and its tag
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, CredentialsError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| CredentialsError::Crypto("no randomness for a nonce".to_string()))?;
+
+    let mut in_out = plaintext.to_vec();
+    aead_key(key)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| CredentialsError::Crypto("sealing failed".to_string()))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(in_out);
+    Ok(sealed)
+}
+
+fn open(key: &[u8; KEY_LEN], sealed: &[u8]) -> Result<Vec<u8>, CredentialsError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(CredentialsError::Corrupt);
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| CredentialsError::Corrupt)?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = aead_key(key)?
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| CredentialsError::Corrupt)?;
+
+    Ok(plaintext.to_vec())
+}
+
+fn aead_key(key: &[u8; KEY_LEN]) -> Result<LessSafeKey, CredentialsError> {
+    UnboundKey::new(&CHACHA20_POLY1305, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| CredentialsError::Crypto("invalid key".to_string()))
+}
+
+/// Writes `bytes` readable by the current user only where the platform allows it
+fn write_private(path: &Path, bytes: &[u8]) -> Result<(), CredentialsError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    std::io::Write::write_all(&mut file, bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_credentials_open_only_unaltered() {
+        let key = [7u8; KEY_LEN];
+        let sealed = seal(&key, b"{\"Binance\":{}}").unwrap();
+
+        assert_eq!(open(&key, &sealed).unwrap(), b"{\"Binance\":{}}");
+        assert!(!sealed.windows(7).any(|w| w == b"Binance"));
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            open(&key, &tampered),
+            Err(CredentialsError::Corrupt)
+        ));
+        assert!(matches!(
+            open(&[8u8; KEY_LEN], &sealed),
+            Err(CredentialsError::Corrupt)
+        ));
+    }
+
+    #[test]
+    fn lost_key_is_not_replaced_under_sealed_credentials() {
+        let dir = std::env::temp_dir().join(format!("lux-credentials-{}", std::process::id()));
+        let (key_path, sealed_path) = (dir.join(KEY_PATH), dir.join(CREDENTIALS_PATH));
+
+        let key = key_at(&key_path, &sealed_path).unwrap();
+        assert_eq!(key_at(&key_path, &sealed_path).unwrap(), key);
+
+        write_private(&sealed_path, &seal(&key, b"{}").unwrap()).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+
+        assert!(matches!(
+            key_at(&key_path, &sealed_path),
+            Err(CredentialsError::KeyMissing)
+        ));
+        assert!(!key_path.exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,344 @@
+use crate::aggr::time::TimeSeries;
+use crate::chart::kline::KlineDataPoint;
+use crate::chart::session::SessionTime;
+
+use exchange::Kline;
+use exchange::util::Price;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+const DAY_MS: u64 = 86_400_000;
+
+const PROFILE_SVG_WIDTH: f32 = 480.0;
+const PROFILE_ROW_HEIGHT: f32 = 6.0;
+const PROFILE_LABEL_WIDTH: f32 = 80.0;
+
+/// Daily export of chart data to disk, for traders who archive their markets
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExportSchedule {
+    pub enabled: bool,
+    /// Time of day in UTC the previous 24 hours are exported at
+    pub time: SessionTime,
+    /// Target folder, `None` exports into the app's data folder
+    pub directory: Option<PathBuf>,
+    pub kline_csv: bool,
+    pub volume_profile_svg: bool,
+    pub session_stats_json: bool,
+    /// Day index (days since unix epoch) of the last completed export
+    pub last_run_day: Option<u64>,
+    /// Puts the path of an exported pane image on the clipboard
+    pub copy_image_path: bool,
+}
+
+impl Default for ExportSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time: SessionTime::new(0, 0),
+            directory: None,
+            kline_csv: true,
+            volume_profile_svg: true,
+            session_stats_json: true,
+            last_run_day: None,
+            copy_image_path: false,
+        }
+    }
+}
+
+impl ExportSchedule {
+    /// Day index to export for, once today's export time has passed and it hasn't run yet
+    pub fn due_day(&self, now_ms: u64) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+
+        let today = now_ms / DAY_MS;
+        let run_at = today * DAY_MS + u64::from(self.time.0) * 60_000;
+
+        (now_ms >= run_at && self.last_run_day != Some(today)).then_some(today)
+    }
+
+    /// `from..to` covered by the export that runs on `day`
+    pub fn window(&self, day: u64) -> (u64, u64) {
+        let to = day * DAY_MS + u64::from(self.time.0) * 60_000;
+        (to.saturating_sub(DAY_MS), to)
+    }
+
+    pub fn directory(&self) -> PathBuf {
+        self.directory
+            .clone()
+            .unwrap_or_else(|| crate::data_path(Some("exports")))
+    }
+
+    pub fn has_outputs(&self) -> bool {
+        self.kline_csv || self.volume_profile_svg || self.session_stats_json
+    }
+}
+
+/// Summary of one export window
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStats {
+    pub ticker: String,
+    pub from: u64,
+    pub to: u64,
+    pub open: f32,
+    pub high: f32,
+    pub low: f32,
+    pub close: f32,
+    pub buy_volume: f32,
+    pub sell_volume: f32,
+    pub delta: f32,
+    pub vwap: Option<f32>,
+    pub poc: Option<f32>,
+    pub bars: usize,
+}
+
+/// Chart data of one ticker over an export window
+pub struct DailySnapshot {
+    /// File-safe name of the ticker, e.g. "BinanceLinear_BTCUSDT"
+    pub label: String,
+    pub from: u64,
+    pub to: u64,
+    pub klines: Vec<Kline>,
+    /// Traded volume per price level as `(price, buy, sell)`, ascending by price
+    pub profile: Vec<(f32, f32, f32)>,
+}
+
+impl DailySnapshot {
+    pub fn from_timeseries(
+        label: String,
+        timeseries: &TimeSeries<KlineDataPoint>,
+        from: u64,
+        to: u64,
+    ) -> Option<Self> {
+        let mut klines = vec![];
+        let mut profile: BTreeMap<Price, (f32, f32)> = BTreeMap::new();
+
+        for dp in timeseries.datapoints.range(from..to).map(|(_, dp)| dp) {
+            klines.push(dp.kline);
+
+            for (price, group) in &dp.footprint.trades {
+                let level = profile.entry(*price).or_default();
+                level.0 += group.buy_qty;
+                level.1 += group.sell_qty;
+            }
+        }
+
+        if klines.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            label: label.replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+            from,
+            to,
+            klines,
+            profile: profile
+                .into_iter()
+                .map(|(price, (buy, sell))| (price.to_f32(), buy, sell))
+                .collect(),
+        })
+    }
+
+    pub fn stats(&self) -> Option<SessionStats> {
+        let first = self.klines.first()?;
+        let last = self.klines.last()?;
+
+        let (buy_volume, sell_volume) = self.klines.iter().fold((0.0, 0.0), |(buy, sell), k| {
+            (buy + k.volume.0, sell + k.volume.1)
+        });
+
+        let (notional, traded) =
+            self.profile
+                .iter()
+                .fold((0.0, 0.0), |(notional, traded), (price, buy, sell)| {
+                    (notional + price * (buy + sell), traded + buy + sell)
+                });
+
+        Some(SessionStats {
+            ticker: self.label.clone(),
+            from: self.from,
+            to: self.to,
+            open: first.open.to_f32(),
+            high: self
+                .klines
+                .iter()
+                .map(|k| k.high.to_f32())
+                .fold(f32::MIN, f32::max),
+            low: self
+                .klines
+                .iter()
+                .map(|k| k.low.to_f32())
+                .fold(f32::MAX, f32::min),
+            close: last.close.to_f32(),
+            buy_volume,
+            sell_volume,
+            delta: buy_volume - sell_volume,
+            vwap: (traded > 0.0).then(|| notional / traded),
+            poc: self.poc(),
+            bars: self.klines.len(),
+        })
+    }
+
+    fn poc(&self) -> Option<f32> {
+        self.profile
+            .iter()
+            .max_by(|a, b| (a.1 + a.2).total_cmp(&(b.1 + b.2)))
+            .map(|(price, _, _)| *price)
+    }
+
+    fn file_path(&self, dir: &Path, suffix: &str) -> PathBuf {
+        let date = chrono::DateTime::from_timestamp_millis(self.from as i64)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| self.from.to_string());
+
+        dir.join(format!("{date}_{}_{suffix}", self.label))
+    }
+
+    pub fn klines_csv(&self) -> String {
+        let mut csv = String::from("time,open,high,low,close,buy_volume,sell_volume\n");
+        for k in &self.klines {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                k.time,
+                k.open.to_f32(),
+                k.high.to_f32(),
+                k.low.to_f32(),
+                k.close.to_f32(),
+                k.volume.0,
+                k.volume.1
+            );
+        }
+        csv
+    }
+
+    /// Horizontal volume profile, buy volume stacked on sell volume per price level
+    pub fn volume_profile_svg(&self) -> Option<String> {
+        let max_qty = self
+            .profile
+            .iter()
+            .map(|(_, buy, sell)| buy + sell)
+            .fold(0.0_f32, f32::max);
+
+        if max_qty <= 0.0 {
+            return None;
+        }
+
+        let poc = self.poc();
+        let bar_area = PROFILE_SVG_WIDTH - PROFILE_LABEL_WIDTH;
+        let height = self.profile.len() as f32 * PROFILE_ROW_HEIGHT;
+        let label_every = (12.0 / PROFILE_ROW_HEIGHT).ceil() as usize * 2;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{PROFILE_SVG_WIDTH}\" height=\"{height}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#151515\"/>\n"
+        );
+
+        // highest price on top
+        for (row, (price, buy, sell)) in self.profile.iter().rev().enumerate() {
+            let y = row as f32 * PROFILE_ROW_HEIGHT;
+            let sell_w = sell / max_qty * bar_area;
+            let buy_w = buy / max_qty * bar_area;
+            let opacity = if Some(*price) == poc { 1.0 } else { 0.7 };
+
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{PROFILE_LABEL_WIDTH}\" y=\"{y}\" width=\"{sell_w}\" height=\"{}\" fill=\"#c0504d\" fill-opacity=\"{opacity}\"/>",
+                PROFILE_ROW_HEIGHT - 1.0
+            );
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{y}\" width=\"{buy_w}\" height=\"{}\" fill=\"#51cdb0\" fill-opacity=\"{opacity}\"/>",
+                PROFILE_LABEL_WIDTH + sell_w,
+                PROFILE_ROW_HEIGHT - 1.0
+            );
+
+            if row % label_every == 0 || Some(*price) == poc {
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"4\" y=\"{}\" font-size=\"10\" font-family=\"monospace\" fill=\"#d0d0d0\">{price}</text>",
+                    y + PROFILE_ROW_HEIGHT
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        Some(svg)
+    }
+
+    /// Writes the outputs enabled in `schedule` into `dir`, returning the written paths
+    pub fn write(&self, dir: &Path, schedule: &ExportSchedule) -> std::io::Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)?;
+
+        let mut written = vec![];
+
+        if schedule.kline_csv {
+            let path = self.file_path(dir, "klines.csv");
+            std::fs::write(&path, self.klines_csv())?;
+            written.push(path);
+        }
+
+        if schedule.volume_profile_svg
+            && let Some(svg) = self.volume_profile_svg()
+        {
+            let path = self.file_path(dir, "volume_profile.svg");
+            std::fs::write(&path, svg)?;
+            written.push(path);
+        }
+
+        if schedule.session_stats_json
+            && let Some(stats) = self.stats()
+        {
+            let json = serde_json::to_string_pretty(&stats)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+            let path = self.file_path(dir, "session_stats.json");
+            std::fs::write(&path, json)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+/// Pixels of a pane captured from the window, RGBA8 in sRGB
+pub struct PaneImage {
+    /// What the pane shows, e.g. "BTCUSDT 5m", made file-safe when written
+    pub label: String,
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl PaneImage {
+    /// Encodes the image as a timestamped PNG into `dir`, returning its path
+    pub fn write_png(&self, dir: &Path, now_ms: u64) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+
+        let stamp = chrono::DateTime::from_timestamp_millis(now_ms as i64)
+            .map(|dt| dt.format("%Y-%m-%d_%H-%M-%S").to_string())
+            .unwrap_or_else(|| now_ms.to_string());
+        let label = self
+            .label
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        let path = dir.join(format!("{stamp}_{label}.png"));
+
+        let to_io = |e: png::EncodingError| std::io::Error::other(e);
+
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(to_io)?;
+        writer.write_image_data(&self.rgba).map_err(to_io)?;
+        writer.finish().map_err(to_io)?;
+
+        Ok(path)
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+
+/// Levels of undo kept before the oldest edits are forgotten
+pub const DEFAULT_DEPTH: usize = 50;
+
+/// Undo and redo stacks of reversible edits
+///
+/// Entries are whatever is needed to revert an edit. Taking one off either stack and applying it
+/// yields the inverse, which goes onto the other stack through `undone` or `redone`.
+#[derive(Debug)]
+pub struct History<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    depth: usize,
+}
+
+impl<T> History<T> {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            depth: depth.max(1),
+        }
+    }
+
+    /// A new edit, whatever was undone can't be redone anymore
+    pub fn record(&mut self, entry: T) {
+        self.redo.clear();
+        self.push_undo(entry);
+    }
+
+    pub fn undo(&mut self) -> Option<T> {
+        self.undo.pop_back()
+    }
+
+    pub fn redo(&mut self) -> Option<T> {
+        self.redo.pop()
+    }
+
+    /// Inverse of an entry taken off by `undo`
+    pub fn undone(&mut self, inverse: T) {
+        self.redo.push(inverse);
+    }
+
+    /// Inverse of an entry taken off by `redo`
+    pub fn redone(&mut self, inverse: T) {
+        self.push_undo(inverse);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push_undo(&mut self, entry: T) {
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEPTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_swap_entries_until_a_new_edit() {
+        let mut history = History::new(3);
+        for value in 1..=4 {
+            history.record(value);
+        }
+
+        // oldest dropped past the depth
+        assert_eq!(history.undo(), Some(4));
+        history.undone(40);
+        assert_eq!(history.undo(), Some(3));
+        history.undone(30);
+        assert_eq!(history.undo(), Some(2));
+        history.undone(20);
+        assert_eq!(history.undo(), None);
+
+        assert_eq!(history.redo(), Some(20));
+        history.redone(2);
+        assert!(history.can_redo());
+
+        history.record(5);
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some(5));
+        assert_eq!(history.undo(), Some(2));
+    }
+}
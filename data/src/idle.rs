@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Economy mode the app drops into after a stretch without user input
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IdleSettings {
+    /// Minutes without input before going idle, `None` never does
+    pub after_mins: Option<u32>,
+    /// Silences sound alerts while idle
+    pub mute_audio: bool,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            after_mins: Some(Self::DEFAULT_AFTER_MINS),
+            mute_audio: false,
+        }
+    }
+}
+
+impl IdleSettings {
+    pub const AFTER_MINS_CHOICES: [u32; 5] = [2, 5, 10, 30, 60];
+    pub const DEFAULT_AFTER_MINS: u32 = 10;
+
+    /// Whether `since_input` without any input counts as idle
+    pub fn is_idle(&self, since_input: Duration) -> bool {
+        self.after_mins
+            .is_some_and(|mins| since_input >= Duration::from_secs(u64::from(mins) * 60))
+    }
+}
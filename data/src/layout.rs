@@ -67,3 +67,426 @@ impl From<(&iced_core::Point, &iced_core::Size)> for WindowSpec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::{
+        Autoscale, Basis, ViewConfig,
+        alert::AlertTrigger,
+        comparison,
+        drawing::{Anchor, DrawingTool},
+        heatmap::{self, HeatmapStudy, ProfileKind},
+        indicator::{HeatmapIndicator, IndicatorParams, KlineIndicator, KlineInstance},
+        kline::{self, ClusterKind, FootprintStudy, KlineChartKind},
+        moving_average::{MaKind, MovingAverage},
+    };
+    use crate::config::state::{Layouts, State};
+    use crate::panel::{heatgrid, ladder, timeandsales, watchlist};
+    use pane::{Axis, LinkGroup, Settings, VisualConfig};
+
+    use exchange::adapter::{
+        Exchange, PersistDepth, PersistKline, PersistStreamKind, StreamTicksize,
+    };
+    use exchange::util::Price;
+    use exchange::{PushFrequency, SerTicker, TickMultiplier, Ticker, TickerInfo, Timeframe};
+
+    fn btc() -> Ticker {
+        Ticker::new("BTCUSDT", Exchange::BinanceLinear)
+    }
+
+    fn eth() -> Ticker {
+        Ticker::new("ETHUSDT", Exchange::BybitLinear)
+    }
+
+    fn depth_stream(ticker: Ticker) -> PersistStreamKind {
+        PersistStreamKind::DepthAndTrades(PersistDepth {
+            ticker,
+            depth_aggr: StreamTicksize::ServerSide(TickMultiplier(10)),
+            push_freq: PushFrequency::ServerDefault,
+        })
+    }
+
+    fn kline_pane() -> Pane {
+        let mut settings = Settings {
+            tick_multiply: Some(TickMultiplier(5)),
+            visual_config: Some(VisualConfig::Kline(kline::Config::default())),
+            selected_basis: Some(Basis::Time(Timeframe::M5)),
+            moving_averages: vec![
+                MovingAverage {
+                    kind: MaKind::Ema,
+                    period: 21,
+                    color: iced_core::Color::from_rgb(0.96, 0.78, 0.26),
+                },
+                MovingAverage {
+                    kind: MaKind::Sma,
+                    period: 200,
+                    color: iced_core::Color::from_rgb(0.35, 0.62, 0.96),
+                },
+            ],
+            ..Settings::default()
+        };
+        settings
+            .alerts
+            .add(btc(), 70_000.0, AlertTrigger::CrossingUp);
+        settings
+            .pinned_levels
+            .toggle(btc(), Price::from_f32(65_000.0));
+        settings.drawings.add(
+            btc(),
+            DrawingTool::TrendLine,
+            [
+                Anchor {
+                    interval: 1_700_000_000_000,
+                    price: Price::from_f32(64_000.0),
+                },
+                Anchor {
+                    interval: 1_700_003_600_000,
+                    price: Price::from_f32(66_500.5),
+                },
+            ],
+        );
+        settings.drawings.add(
+            btc(),
+            DrawingTool::FibRetracement,
+            [
+                Anchor {
+                    interval: 1_700_000_000_000,
+                    price: Price::from_f32(60_000.0),
+                },
+                Anchor {
+                    interval: 1_700_007_200_000,
+                    price: Price::from_f32(68_000.0),
+                },
+            ],
+        );
+        settings.drawings.toggle_fib_level(1.618);
+
+        Pane::KlineChart {
+            layout: ViewConfig {
+                splits: vec![0.7, 0.85],
+                autoscale: Some(Autoscale::FitToVisible),
+                ..ViewConfig::default()
+            },
+            kind: KlineChartKind::Footprint {
+                clusters: ClusterKind::DeltaProfile,
+                scaling: kline::ClusterScaling::Hybrid { weight: 0.25 },
+                studies: vec![
+                    FootprintStudy::NPoC {
+                        lookback: 80,
+                        scope: kline::PocScope::Bar,
+                    },
+                    FootprintStudy::Imbalance {
+                        threshold: 200,
+                        color_scale: Some(400),
+                        ignore_zeros: true,
+                        stacked: Some(kline::DEFAULT_MIN_STACK),
+                    },
+                ],
+            },
+            stream_type: vec![
+                PersistStreamKind::Kline(PersistKline {
+                    ticker: btc(),
+                    timeframe: Timeframe::M5,
+                }),
+                depth_stream(btc()),
+            ],
+            settings,
+            indicators: vec![
+                KlineInstance::new(0, KlineIndicator::Volume),
+                KlineInstance {
+                    params: Some(IndicatorParams {
+                        rsi_period: 21,
+                        ..IndicatorParams::default()
+                    }),
+                    ..KlineInstance::new(1, KlineIndicator::Rsi)
+                },
+                KlineInstance::new(2, KlineIndicator::Rsi),
+            ],
+            link_group: Some(LinkGroup::A),
+        }
+    }
+
+    fn heatmap_pane() -> Pane {
+        Pane::HeatmapChart {
+            layout: ViewConfig {
+                splits: vec![0.8],
+                autoscale: Some(Autoscale::CenterLatest),
+                ..ViewConfig::default()
+            },
+            studies: vec![HeatmapStudy::VolumeProfile(ProfileKind::VisibleRange)],
+            stream_type: vec![depth_stream(btc())],
+            settings: Settings {
+                tick_multiply: Some(TickMultiplier(10)),
+                visual_config: Some(VisualConfig::Heatmap(heatmap::Config {
+                    trade_size_filter: 25_000.0,
+                    order_size_filter: 100_000.0,
+                    ..heatmap::Config::default()
+                })),
+                selected_basis: Some(Basis::Time(Timeframe::MS500)),
+                ..Settings::default()
+            },
+            indicators: vec![HeatmapIndicator::Volume],
+            link_group: Some(LinkGroup::A),
+        }
+    }
+
+    fn comparison_pane() -> Pane {
+        let btc_ser = SerTicker::from_parts(btc());
+        let eth_ser = SerTicker::from_parts(eth());
+
+        Pane::ComparisonChart {
+            stream_type: vec![
+                PersistStreamKind::Kline(PersistKline {
+                    ticker: btc(),
+                    timeframe: Timeframe::H1,
+                }),
+                PersistStreamKind::Kline(PersistKline {
+                    ticker: eth(),
+                    timeframe: Timeframe::H1,
+                }),
+            ],
+            settings: Settings {
+                visual_config: Some(VisualConfig::Comparison(comparison::Config {
+                    colors: vec![
+                        (btc_ser, iced_core::Color::from_rgb(0.9, 0.6, 0.1)),
+                        (eth_ser.clone(), iced_core::Color::from_rgb(0.3, 0.4, 0.9)),
+                    ],
+                    names: vec![(eth_ser, "Ether".to_string())],
+                    merge_stablecoin_quotes: true,
+                    locked_range: Some((-4.5, 6.0)),
+                    ..comparison::Config::default()
+                })),
+                selected_basis: Some(Basis::Time(Timeframe::H1)),
+                ..Settings::default()
+            },
+            link_group: None,
+        }
+    }
+
+    fn complex_dashboard() -> Dashboard {
+        let btc_info = TickerInfo::new(btc(), 0.1, 0.001, None);
+        let eth_info = TickerInfo::new(eth(), 0.01, 0.01, None);
+
+        let panels = Pane::Split {
+            axis: Axis::Horizontal,
+            ratio: 0.5,
+            a: Box::new(Pane::TimeAndSales {
+                stream_type: vec![depth_stream(btc())],
+                settings: Settings {
+                    visual_config: Some(VisualConfig::TimeAndSales(timeandsales::Config {
+                        trade_size_filter: 5_000.0,
+                        ..timeandsales::Config::default()
+                    })),
+                    ..Settings::default()
+                },
+                link_group: Some(LinkGroup::A),
+            }),
+            b: Box::new(Pane::Ladder {
+                stream_type: vec![depth_stream(btc())],
+                settings: Settings {
+                    tick_multiply: Some(TickMultiplier(2)),
+                    visual_config: Some(VisualConfig::Ladder(ladder::Config {
+                        show_imbalance: true,
+                        ..ladder::Config::default()
+                    })),
+                    ..Settings::default()
+                },
+                link_group: Some(LinkGroup::A),
+            }),
+        };
+
+        let charts = Pane::Split {
+            axis: Axis::Vertical,
+            ratio: 0.65,
+            a: Box::new(kline_pane()),
+            b: Box::new(heatmap_pane()),
+        };
+
+        let watchlist = Pane::Watchlist {
+            settings: Settings {
+                visual_config: Some(VisualConfig::Watchlist(watchlist::Config {
+                    tickers: vec![btc_info, eth_info],
+                    sort_desc: true,
+                    ..watchlist::Config::default()
+                })),
+                ..Settings::default()
+            },
+            link_group: Some(LinkGroup::A),
+        };
+
+        Dashboard {
+            pane: Pane::Split {
+                axis: Axis::Vertical,
+                ratio: 0.75,
+                a: Box::new(charts),
+                b: Box::new(Pane::Split {
+                    axis: Axis::Horizontal,
+                    ratio: 0.4,
+                    a: Box::new(watchlist),
+                    b: Box::new(panels),
+                }),
+            },
+            popout: vec![
+                (
+                    comparison_pane(),
+                    WindowSpec {
+                        width: 900.0,
+                        height: 600.0,
+                        pos_x: 120.0,
+                        pos_y: 80.0,
+                    },
+                ),
+                (
+                    Pane::HeatGrid {
+                        settings: Settings {
+                            visual_config: Some(VisualConfig::HeatGrid(heatgrid::Config {
+                                exchange: Exchange::BybitLinear,
+                                max_tiles: 100,
+                            })),
+                            ..Settings::default()
+                        },
+                        link_group: None,
+                    },
+                    WindowSpec::default(),
+                ),
+                (
+                    Pane::Starter {
+                        link_group: Some(LinkGroup::B),
+                    },
+                    WindowSpec::default(),
+                ),
+            ],
+            synced_view_groups: vec![LinkGroup::A],
+        }
+    }
+
+    /// Serializes, reads back and serializes again, so any field dropped or
+    /// defaulted on the way in shows up as a difference. Compared as text, since
+    /// going through `serde_json::Value` widens f32 fields to f64
+    fn round_trip<T>(value: &T) -> (String, T)
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let json = serde_json::to_string_pretty(value).expect("serializes");
+        let restored: T = serde_json::from_str(&json).expect("deserializes");
+
+        (json, restored)
+    }
+
+    #[test]
+    fn dashboard_survives_round_trip() {
+        let dashboard = complex_dashboard();
+        let (saved, restored) = round_trip(&dashboard);
+
+        assert_eq!(serde_json::to_string_pretty(&restored).unwrap(), saved);
+
+        assert_eq!(restored.popout.len(), 3);
+        assert_eq!(restored.synced_view_groups, vec![LinkGroup::A]);
+
+        let Pane::Split { a: charts, .. } = &restored.pane else {
+            panic!("root pane is not a split");
+        };
+        let Pane::Split { a: kline, .. } = charts.as_ref() else {
+            panic!("charts pane is not a split");
+        };
+        let Pane::KlineChart {
+            settings,
+            indicators,
+            kind,
+            ..
+        } = kline.as_ref()
+        else {
+            panic!("expected a kline chart");
+        };
+
+        let kinds: Vec<_> = indicators.iter().map(|instance| instance.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                KlineIndicator::Volume,
+                KlineIndicator::Rsi,
+                KlineIndicator::Rsi
+            ]
+        );
+        assert_eq!(indicators[1].params.map(|p| p.rsi_period), Some(21));
+        assert!(matches!(kind, KlineChartKind::Footprint { studies, .. } if studies.len() == 2));
+        assert_eq!(settings.drawings.items.len(), 2);
+        assert!(settings.drawings.fib_levels.contains(&1.618));
+        assert_eq!(settings.alerts.items.len(), 1);
+        assert_eq!(settings.moving_averages.len(), 2);
+        assert!(
+            settings
+                .pinned_levels
+                .is_pinned(btc(), Price::from_f32(65_000.0))
+        );
+
+        let Pane::ComparisonChart { settings, .. } = &restored.popout[0].0 else {
+            panic!("expected a comparison chart");
+        };
+        let Some(VisualConfig::Comparison(cfg)) = &settings.visual_config else {
+            panic!("comparison config lost");
+        };
+        assert_eq!(cfg.colors.len(), 2);
+        assert_eq!(cfg.names[0].1, "Ether");
+        assert_eq!(cfg.locked_range, Some((-4.5, 6.0)));
+    }
+
+    #[test]
+    fn saved_state_keeps_layouts() {
+        let state = State {
+            layout_manager: Layouts {
+                layouts: vec![
+                    Layout {
+                        name: "Scalping".to_string(),
+                        dashboard: complex_dashboard(),
+                    },
+                    Layout::default(),
+                ],
+                active_layout: Some("Scalping".to_string()),
+            },
+            ..State::default()
+        };
+        let (saved, restored) = round_trip(&state);
+
+        assert_eq!(serde_json::to_string_pretty(&restored).unwrap(), saved);
+        assert_eq!(
+            restored.layout_manager.active_layout.as_deref(),
+            Some("Scalping")
+        );
+        assert_eq!(restored.layout_manager.layouts.len(), 2);
+    }
+
+    #[test]
+    fn malformed_pane_field_falls_back_to_default() {
+        let mut saved = serde_json::to_value(complex_dashboard()).unwrap();
+        saved["pane"]["Split"]["a"]["Split"]["a"]["KlineChart"]["indicators"] =
+            serde_json::json!("not a list");
+
+        let restored: Dashboard = serde_json::from_value(saved).expect("deserializes");
+
+        let Pane::Split { a: charts, .. } = &restored.pane else {
+            panic!("root pane is not a split");
+        };
+        let Pane::Split {
+            a: kline,
+            b: heatmap,
+            ..
+        } = charts.as_ref()
+        else {
+            panic!("charts pane is not a split");
+        };
+        let Pane::KlineChart {
+            indicators,
+            settings,
+            ..
+        } = kline.as_ref()
+        else {
+            panic!("expected a kline chart");
+        };
+
+        assert!(indicators.is_empty());
+        assert_eq!(settings.drawings.items.len(), 2);
+        assert!(matches!(heatmap.as_ref(), Pane::HeatmapChart { .. }));
+    }
+}
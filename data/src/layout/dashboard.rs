@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::{WindowSpec, pane::Pane};
+use super::{
+    WindowSpec,
+    pane::{LinkGroup, Pane},
+};
 use crate::util::ok_or_default;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -9,4 +12,7 @@ pub struct Dashboard {
     pub pane: Pane,
     #[serde(deserialize_with = "ok_or_default", default)]
     pub popout: Vec<(Pane, WindowSpec)>,
+    /// Link groups whose time based charts follow each other's pan and zoom
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub synced_view_groups: Vec<LinkGroup>,
 }
@@ -1,15 +1,17 @@
-use exchange::adapter::PersistStreamKind;
-use exchange::{TickMultiplier, TickerInfo, Timeframe};
+use exchange::adapter::{Exchange, ExchangeInclusive, MarketKind, PersistStreamKind};
+use exchange::{TickMultiplier, Ticker, TickerInfo, Timeframe};
 use serde::{Deserialize, Serialize};
 
 use crate::chart::{comparison, heatmap, kline};
-use crate::panel::{ladder, timeandsales};
+use crate::panel::{
+    depth_inspector, heatgrid, ladder, timeandsales, trade_sizes, volume_profile, watchlist,
+};
 use crate::util::ok_or_default;
 
 use crate::chart::{
     Basis, ViewConfig,
     heatmap::HeatmapStudy,
-    indicator::{HeatmapIndicator, KlineIndicator},
+    indicator::{HeatmapIndicator, KlineInstance},
     kline::KlineChartKind,
 };
 
@@ -52,7 +54,7 @@ pub enum Pane {
         #[serde(deserialize_with = "ok_or_default")]
         settings: Settings,
         #[serde(deserialize_with = "ok_or_default", default)]
-        indicators: Vec<KlineIndicator>,
+        indicators: Vec<KlineInstance>,
         #[serde(deserialize_with = "ok_or_default", default)]
         link_group: Option<LinkGroup>,
     },
@@ -75,6 +77,45 @@ pub enum Pane {
         #[serde(deserialize_with = "ok_or_default", default)]
         link_group: Option<LinkGroup>,
     },
+    VolumeProfile {
+        stream_type: Vec<PersistStreamKind>,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    DepthInspector {
+        stream_type: Vec<PersistStreamKind>,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    TradeSizes {
+        stream_type: Vec<PersistStreamKind>,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    Watchlist {
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    HeatGrid {
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    Journal {
+        #[serde(deserialize_with = "ok_or_default", default)]
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
 }
 
 impl Default for Pane {
@@ -89,6 +130,58 @@ pub struct Settings {
     pub tick_multiply: Option<exchange::TickMultiplier>,
     pub visual_config: Option<VisualConfig>,
     pub selected_basis: Option<Basis>,
+    pub alerts: crate::chart::alert::Alerts,
+    pub moving_averages: Vec<crate::chart::moving_average::MovingAverage>,
+    pub pinned_levels: crate::chart::pin::PinnedLevels,
+    pub drawings: crate::chart::drawing::Drawings,
+    /// Replaces the generated title, see [`auto_title`]
+    pub custom_title: Option<String>,
+    pub failover: Option<Failover>,
+    /// Other venues whose listing of the symbol is merged into the pane's trades and depth
+    pub merge_venues: Vec<Exchange>,
+    /// Venue changes made by failover, oldest first
+    pub source_switches: Vec<SourceSwitch>,
+    pub colors: crate::chart::colors::ChartColors,
+    /// Depth updates per second the pane's streams are conflated to, `None` takes every one
+    pub depth_rate: Option<u16>,
+}
+
+/// Venue a pane moves its streams to once its own stays disconnected for too long
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Failover {
+    pub exchange: Exchange,
+    pub after_secs: u64,
+}
+
+impl Failover {
+    pub const AFTER_SECS_CHOICES: [u64; 4] = [10, 30, 60, 300];
+
+    /// Whether a venue down since `disconnected_at` (ms) has been for long enough
+    pub fn is_due(&self, disconnected_at: u64, now: u64) -> bool {
+        now.saturating_sub(disconnected_at) >= self.after_secs * 1000
+    }
+}
+
+impl Default for Failover {
+    fn default() -> Self {
+        Self {
+            exchange: Exchange::BybitLinear,
+            after_secs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct SourceSwitch {
+    pub time: u64,
+    pub from: Exchange,
+    pub to: Exchange,
+}
+
+impl SourceSwitch {
+    /// Older switches are dropped past this many
+    pub const MAX_KEPT: usize = 20;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -136,13 +229,18 @@ impl std::fmt::Display for LinkGroup {
 }
 
 /// Defines the specific configuration for different types of pane settings.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum VisualConfig {
     Heatmap(heatmap::Config),
     TimeAndSales(timeandsales::Config),
     Kline(kline::Config),
     Ladder(ladder::Config),
     Comparison(comparison::Config),
+    Watchlist(watchlist::Config),
+    HeatGrid(heatgrid::Config),
+    VolumeProfile(volume_profile::Config),
+    DepthInspector(depth_inspector::Config),
+    TradeSizes(trade_sizes::Config),
 }
 
 impl VisualConfig {
@@ -180,6 +278,41 @@ impl VisualConfig {
             _ => None,
         }
     }
+
+    pub fn watchlist(&self) -> Option<watchlist::Config> {
+        match self {
+            Self::Watchlist(cfg) => Some(cfg.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn heatgrid(&self) -> Option<heatgrid::Config> {
+        match self {
+            Self::HeatGrid(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
+
+    pub fn volume_profile(&self) -> Option<volume_profile::Config> {
+        match self {
+            Self::VolumeProfile(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
+
+    pub fn depth_inspector(&self) -> Option<depth_inspector::Config> {
+        match self {
+            Self::DepthInspector(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
+
+    pub fn trade_sizes(&self) -> Option<trade_sizes::Config> {
+        match self {
+            Self::TradeSizes(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -191,10 +324,16 @@ pub enum ContentKind {
     ComparisonChart,
     TimeAndSales,
     Ladder,
+    VolumeProfile,
+    DepthInspector,
+    TradeSizes,
+    Watchlist,
+    HeatGrid,
+    Journal,
 }
 
 impl ContentKind {
-    pub const ALL: [ContentKind; 7] = [
+    pub const ALL: [ContentKind; 13] = [
         ContentKind::Starter,
         ContentKind::HeatmapChart,
         ContentKind::FootprintChart,
@@ -202,7 +341,71 @@ impl ContentKind {
         ContentKind::ComparisonChart,
         ContentKind::TimeAndSales,
         ContentKind::Ladder,
+        ContentKind::VolumeProfile,
+        ContentKind::DepthInspector,
+        ContentKind::TradeSizes,
+        ContentKind::Watchlist,
+        ContentKind::HeatGrid,
+        ContentKind::Journal,
     ];
+
+    /// Panes listing tickers of their own, a link group doesn't switch their ticker
+    pub fn is_ticker_list(&self) -> bool {
+        matches!(
+            self,
+            ContentKind::Watchlist | ContentKind::HeatGrid | ContentKind::Journal
+        )
+    }
+
+    fn short_name(&self) -> &'static str {
+        match self {
+            ContentKind::Starter => "New Pane",
+            ContentKind::HeatmapChart => "Heatmap",
+            ContentKind::FootprintChart => "Footprint",
+            ContentKind::CandlestickChart => "Candles",
+            ContentKind::ComparisonChart => "Comparison",
+            ContentKind::TimeAndSales => "Time&Sales",
+            ContentKind::Ladder => "Ladder",
+            ContentKind::VolumeProfile => "Profile",
+            ContentKind::DepthInspector => "Depth Inspector",
+            ContentKind::TradeSizes => "Trade Sizes",
+            ContentKind::Watchlist => "Watchlist",
+            ContentKind::HeatGrid => "Heat Grid",
+            ContentKind::Journal => "Journal",
+        }
+    }
+}
+
+/// Title describing what a pane shows, e.g. "BTCUSDT · Binance Perp · 5m Footprint"
+pub fn auto_title(kind: ContentKind, ticker: Option<Ticker>, basis: Option<Basis>) -> String {
+    let view = match basis {
+        Some(basis)
+            if matches!(
+                kind,
+                ContentKind::HeatmapChart
+                    | ContentKind::FootprintChart
+                    | ContentKind::CandlestickChart
+                    | ContentKind::ComparisonChart
+            ) =>
+        {
+            format!("{basis} {}", kind.short_name())
+        }
+        _ => kind.short_name().to_string(),
+    };
+
+    let Some(ticker) = ticker else {
+        return view;
+    };
+
+    let (symbol, market) = ticker.display_symbol_and_type();
+    let market = match market {
+        MarketKind::Spot => "Spot",
+        MarketKind::LinearPerps => "Perp",
+        MarketKind::InversePerps => "Inverse Perp",
+    };
+    let venue = ExchangeInclusive::of(ticker.exchange);
+
+    format!("{symbol} · {venue} {market} · {view}")
 }
 
 impl std::fmt::Display for ContentKind {
@@ -215,6 +418,12 @@ impl std::fmt::Display for ContentKind {
             ContentKind::ComparisonChart => "Comparison Chart",
             ContentKind::TimeAndSales => "Time&Sales",
             ContentKind::Ladder => "DOM/Ladder",
+            ContentKind::VolumeProfile => "Volume Profile",
+            ContentKind::DepthInspector => "Depth Inspector",
+            ContentKind::TradeSizes => "Trade Size Histogram",
+            ContentKind::Watchlist => "Watchlist",
+            ContentKind::HeatGrid => "Heat Grid",
+            ContentKind::Journal => "Paper Journal",
         };
         write!(f, "{s}")
     }
@@ -262,7 +471,14 @@ impl PaneSetup {
             ContentKind::CandlestickChart | ContentKind::ComparisonChart => {
                 Some(current_basis.unwrap_or(Basis::Time(Timeframe::M15)))
             }
-            ContentKind::Starter | ContentKind::TimeAndSales => None,
+            ContentKind::Starter
+            | ContentKind::TimeAndSales
+            | ContentKind::VolumeProfile
+            | ContentKind::DepthInspector
+            | ContentKind::TradeSizes
+            | ContentKind::Watchlist
+            | ContentKind::HeatGrid
+            | ContentKind::Journal => None,
         };
 
         let tick_multiplier = match content_kind {
@@ -278,13 +494,19 @@ impl PaneSetup {
                 };
                 Some(tm)
             }
-            ContentKind::FootprintChart => {
+            ContentKind::FootprintChart | ContentKind::VolumeProfile => {
                 Some(current_tick_multiplier.unwrap_or(TickMultiplier(50)))
             }
+            // raw levels, as the adapter keeps them
+            ContentKind::DepthInspector => Some(TickMultiplier(1)),
             ContentKind::CandlestickChart
             | ContentKind::ComparisonChart
             | ContentKind::TimeAndSales
-            | ContentKind::Starter => current_tick_multiplier,
+            | ContentKind::TradeSizes
+            | ContentKind::Starter
+            | ContentKind::Watchlist
+            | ContentKind::HeatGrid
+            | ContentKind::Journal => current_tick_multiplier,
         };
 
         let tick_size = match tick_multiplier {
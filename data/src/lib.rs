@@ -1,10 +1,19 @@
 pub mod aggr;
 pub mod audio;
+pub mod autosave;
+pub mod backfill;
 pub mod chart;
 pub mod config;
+pub mod connection;
+pub mod credentials;
+pub mod export;
+pub mod history;
+pub mod idle;
 pub mod layout;
 pub mod log;
 pub mod panel;
+pub mod paper;
+pub mod scanner;
 pub mod tickers_table;
 pub mod util;
 
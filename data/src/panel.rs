@@ -1,2 +1,7 @@
+pub mod depth_inspector;
+pub mod heatgrid;
 pub mod ladder;
 pub mod timeandsales;
+pub mod trade_sizes;
+pub mod volume_profile;
+pub mod watchlist;
@@ -0,0 +1,170 @@
+use exchange::depth::{Depth, Levels};
+use exchange::util::Price;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Depth updates kept to step through once frozen
+    pub history: usize,
+    /// Levels listed per side of the book and in the largest changes
+    pub rows: usize,
+}
+
+impl Config {
+    pub const HISTORY_CHOICES: [usize; 4] = [100, 500, 1000, 5000];
+    pub const ROWS_CHOICES: [usize; 3] = [10, 20, 50];
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            history: 500,
+            rows: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Bid => write!(f, "Bid"),
+            Side::Ask => write!(f, "Ask"),
+        }
+    }
+}
+
+/// Quantity of one level before and after an update, zero where the level is absent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelChange {
+    pub side: Side,
+    pub price: Price,
+    pub before: f32,
+    pub after: f32,
+}
+
+impl LevelChange {
+    pub fn delta(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+/// Levels that differ between two books, bids then asks, each by price ascending
+pub fn diff(prev: &Depth, next: &Depth) -> Vec<LevelChange> {
+    let mut changes = vec![];
+    diff_levels(Side::Bid, &prev.bids, &next.bids, &mut changes);
+    diff_levels(Side::Ask, &prev.asks, &next.asks, &mut changes);
+    changes
+}
+
+fn diff_levels(side: Side, prev: &Levels, next: &Levels, out: &mut Vec<LevelChange>) {
+    let mut prev = prev.iter().peekable();
+    let mut next = next.iter().peekable();
+
+    loop {
+        let (price, before, after) = match (prev.peek(), next.peek()) {
+            (Some((p, b)), Some((n, a))) if p == n => {
+                let change = (**p, **b, **a);
+                prev.next();
+                next.next();
+                change
+            }
+            (Some((p, b)), Some((n, _))) if p < n => {
+                let change = (**p, **b, 0.0);
+                prev.next();
+                change
+            }
+            (Some((p, b)), None) => {
+                let change = (**p, **b, 0.0);
+                prev.next();
+                change
+            }
+            (_, Some((n, a))) => {
+                let change = (**n, 0.0, **a);
+                next.next();
+                change
+            }
+            (None, None) => break,
+        };
+
+        if before != after {
+            out.push(LevelChange {
+                side,
+                price,
+                before,
+                after,
+            });
+        }
+    }
+}
+
+/// Applies `changes` to `book`, or takes them back when `forward` is false
+pub fn apply(book: &mut Depth, changes: &[LevelChange], forward: bool) {
+    for change in changes {
+        let levels = match change.side {
+            Side::Bid => &mut book.bids,
+            Side::Ask => &mut book.asks,
+        };
+        let qty = if forward { change.after } else { change.before };
+
+        if qty == 0.0 {
+            levels.remove(&change.price);
+        } else {
+            levels.insert(change.price, qty);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(bids: &[(f32, f32)], asks: &[(f32, f32)]) -> Depth {
+        let side = |levels: &[(f32, f32)]| {
+            levels
+                .iter()
+                .map(|(price, qty)| (Price::from_f32(*price), *qty))
+                .collect::<Levels>()
+        };
+
+        Depth {
+            bids: side(bids),
+            asks: side(asks),
+        }
+    }
+
+    #[test]
+    fn diff_replays_both_ways() {
+        let prev = book(&[(99.0, 1.0), (100.0, 2.0)], &[(101.0, 3.0), (102.0, 1.0)]);
+        let next = book(&[(98.0, 4.0), (100.0, 2.0)], &[(101.0, 1.5), (102.0, 1.0)]);
+
+        let changes = diff(&prev, &next);
+        let summary: Vec<(Side, Price, f32, f32)> = changes
+            .iter()
+            .map(|c| (c.side, c.price, c.before, c.after))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Side::Bid, Price::from_f32(98.0), 0.0, 4.0),
+                (Side::Bid, Price::from_f32(99.0), 1.0, 0.0),
+                (Side::Ask, Price::from_f32(101.0), 3.0, 1.5),
+            ]
+        );
+
+        let mut replayed = prev.clone();
+        apply(&mut replayed, &changes, true);
+        assert!(replayed.bids.iter().eq(next.bids.iter()));
+        assert!(replayed.asks.iter().eq(next.asks.iter()));
+
+        apply(&mut replayed, &changes, false);
+        assert!(replayed.bids.iter().eq(prev.bids.iter()));
+        assert!(replayed.asks.iter().eq(prev.asks.iter()));
+    }
+}
@@ -0,0 +1,99 @@
+use exchange::{Ticker, TickerStats, adapter::Exchange};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub exchange: Exchange,
+    /// Only the most traded tickers get a tile
+    pub max_tiles: usize,
+}
+
+impl Config {
+    pub const MAX_TILES_CHOICES: [usize; 4] = [25, 50, 100, 200];
+
+    /// Tickers that get a tile, by 24h volume descending
+    pub fn top_by_volume(
+        &self,
+        stats: impl Iterator<Item = (Ticker, TickerStats)>,
+    ) -> Vec<(Ticker, TickerStats)> {
+        let mut tiles: Vec<_> = stats.filter(|(_, s)| s.daily_volume > 0.0).collect();
+
+        tiles.sort_by(|(_, a), (_, b)| b.daily_volume.total_cmp(&a.daily_volume));
+        tiles.truncate(self.max_tiles);
+        tiles
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            exchange: Exchange::BinanceLinear,
+            max_tiles: 50,
+        }
+    }
+}
+
+/// Squarified treemap of `weights`, sorted descending, filling a `width` x `height` box.
+/// Returns `[x, y, width, height]` of each weight in the same order.
+pub fn squarify(weights: &[f32], width: f32, height: f32) -> Vec<[f32; 4]> {
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 || width <= 0.0 || height <= 0.0 {
+        return vec![];
+    }
+
+    let scale = width * height / total;
+    let areas: Vec<f32> = weights.iter().map(|w| w * scale).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let (mut x, mut y, mut w, mut h) = (0.0, 0.0, width, height);
+    let mut start = 0;
+
+    while start < areas.len() {
+        let side = w.min(h);
+
+        // grow the row while it doesn't make its worst tile any thinner
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst_ratio(&areas[start..=end], side) <= worst_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let thickness = row.iter().sum::<f32>() / side;
+
+        let mut offset = 0.0;
+        for area in row {
+            let length = area / thickness;
+            if w >= h {
+                rects.push([x, y + offset, thickness, length]);
+            } else {
+                rects.push([x + offset, y, length, thickness]);
+            }
+            offset += length;
+        }
+
+        if w >= h {
+            x += thickness;
+            w -= thickness;
+        } else {
+            y += thickness;
+            h -= thickness;
+        }
+        start = end;
+    }
+
+    rects
+}
+
+/// Largest aspect ratio among the tiles of a row laid along `side`
+fn worst_ratio(row: &[f32], side: f32) -> f32 {
+    let sum: f32 = row.iter().sum();
+    let (min, max) = row.iter().fold((f32::MAX, 0.0_f32), |(min, max), a| {
+        (min.min(*a), max.max(*a))
+    });
+
+    let (side_sq, sum_sq) = (side * side, sum * sum);
+    (side_sq * max / sum_sq).max(sum_sq / (side_sq * min))
+}
@@ -2,6 +2,7 @@ use crate::chart::kline::KlineTrades;
 use crate::util::ok_or_default;
 use exchange::{
     Trade,
+    depth::Levels,
     util::{Price, PriceStep},
 };
 
@@ -14,12 +15,49 @@ use std::{
 const TRADE_RETENTION_MS: u64 = 8 * 60_000;
 const CHASE_MIN_VISIBLE_OPACITY: f32 = 0.15;
 
+const TRADE_FLOW_WINDOW_MS: u64 = 10_000;
+/// Levels whose decayed volume falls below this are dropped
+const TRADE_FLOW_MIN_QTY: f32 = 1e-6;
+
+const IMBALANCE_LEVELS: usize = 5;
+const IMBALANCE_THRESHOLD: f32 = 3.0;
+
+pub const SPREAD_HISTORY_WINDOW_MS: u64 = 5 * 60_000;
+/// Depth updates within the same bucket collapse into one sample
+const SPREAD_BUCKET_MS: u64 = 1_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub struct Config {
     pub show_spread: bool,
     #[serde(deserialize_with = "ok_or_default", default)]
     pub show_chase_tracker: bool,
     pub trade_retention: Duration,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub show_trade_flow: bool,
+    #[serde(default = "default_trade_flow_window")]
+    pub trade_flow_window: Duration,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub show_imbalance: bool,
+    /// Levels from the touch the cumulative imbalance is summed over
+    #[serde(default = "default_imbalance_levels")]
+    pub imbalance_levels: usize,
+    /// Ratio over the opposite side at which a level gets highlighted
+    #[serde(default = "default_imbalance_threshold")]
+    pub imbalance_threshold: f32,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub show_spread_history: bool,
+}
+
+fn default_trade_flow_window() -> Duration {
+    Duration::from_millis(TRADE_FLOW_WINDOW_MS)
+}
+
+fn default_imbalance_levels() -> usize {
+    IMBALANCE_LEVELS
+}
+
+fn default_imbalance_threshold() -> f32 {
+    IMBALANCE_THRESHOLD
 }
 
 impl Default for Config {
@@ -28,10 +66,51 @@ impl Default for Config {
             show_spread: false,
             show_chase_tracker: true,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
+            show_trade_flow: false,
+            trade_flow_window: default_trade_flow_window(),
+            show_imbalance: false,
+            imbalance_levels: IMBALANCE_LEVELS,
+            imbalance_threshold: IMBALANCE_THRESHOLD,
+            show_spread_history: false,
         }
     }
 }
 
+/// Size of a book level over the size of the opposite side at the same distance from the touch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Imbalance {
+    pub level: f32,
+    /// Over every level from the touch up to this one, `None` past the cumulative levels
+    pub cumulative: Option<f32>,
+}
+
+/// Imbalances of one side of the book, `own` and `opposite` hold level sizes ordered
+/// from the touch outward. Levels without a counterpart on either side get `None`.
+pub fn side_imbalances(
+    own: &[f32],
+    opposite: &[f32],
+    cumulative_levels: usize,
+) -> Vec<Option<Imbalance>> {
+    let ratio = |own: f32, opposite: f32| (own > 0.0 && opposite > 0.0).then(|| own / opposite);
+    let (mut own_sum, mut opposite_sum) = (0.0, 0.0);
+
+    own.iter()
+        .zip(opposite)
+        .enumerate()
+        .map(|(i, (&own, &opposite))| {
+            own_sum += own;
+            opposite_sum += opposite;
+
+            let cumulative = if i < cumulative_levels {
+                ratio(own_sum, opposite_sum)
+            } else {
+                None
+            };
+            ratio(own, opposite).map(|level| Imbalance { level, cumulative })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction {
     Up,
@@ -71,7 +150,7 @@ impl GroupedDepth {
         }
     }
 
-    pub fn regroup_from_raw(&mut self, levels: &BTreeMap<Price, f32>, side: Side, step: PriceStep) {
+    pub fn regroup_from_raw(&mut self, levels: &Levels, side: Side, step: PriceStep) {
         self.orders.clear();
         for (price, qty) in levels.iter() {
             let grouped_price = price.round_to_side_step(side.is_bid(), step);
@@ -183,6 +262,167 @@ impl TradeStore {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+struct FlowLevel {
+    buy_qty: f32,
+    sell_qty: f32,
+    last_update_ms: u64,
+}
+
+impl FlowLevel {
+    fn decayed(&self, now_ms: u64, window_ms: f32) -> (f32, f32) {
+        let factor = decay_factor(now_ms.saturating_sub(self.last_update_ms), window_ms);
+        (self.buy_qty * factor, self.sell_qty * factor)
+    }
+}
+
+fn decay_factor(elapsed_ms: u64, window_ms: f32) -> f32 {
+    if window_ms <= 0.0 {
+        return 0.0;
+    }
+    (-(elapsed_ms as f32) / window_ms).exp()
+}
+
+/// Recently executed volume per price level, split by aggressor side.
+///
+/// Each level decays exponentially with the window as its time constant, so a
+/// level that stops trading fades out instead of dropping off at a hard cutoff.
+#[derive(Debug, Default)]
+pub struct TradeFlow {
+    levels: BTreeMap<Price, FlowLevel>,
+    window: Duration,
+}
+
+impl TradeFlow {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            levels: BTreeMap::new(),
+            window,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    fn window_ms(&self) -> f32 {
+        self.window.as_millis() as f32
+    }
+
+    pub fn insert_trades<'a>(
+        &mut self,
+        trades: impl IntoIterator<Item = &'a Trade>,
+        step: PriceStep,
+    ) {
+        let window_ms = self.window_ms();
+
+        for trade in trades {
+            let price = trade.price.round_to_side_step(trade.is_sell, step);
+            let level = self.levels.entry(price).or_default();
+
+            let (buy_qty, sell_qty) = level.decayed(trade.time, window_ms);
+            *level = FlowLevel {
+                buy_qty: if trade.is_sell {
+                    buy_qty
+                } else {
+                    buy_qty + trade.qty
+                },
+                sell_qty: if trade.is_sell {
+                    sell_qty + trade.qty
+                } else {
+                    sell_qty
+                },
+                last_update_ms: trade.time.max(level.last_update_ms),
+            };
+        }
+    }
+
+    /// Replays retained trades, e.g. after the tick size or window changed
+    pub fn rebuild(&mut self, window: Duration, raw: &VecDeque<Trade>, step: PriceStep) {
+        self.window = window;
+        self.levels.clear();
+        self.insert_trades(raw, step);
+    }
+
+    /// Decayed (buy, sell) volume at a grouped price level
+    pub fn qty_at(&self, price: Price, now_ms: u64) -> (f32, f32) {
+        self.levels
+            .get(&price)
+            .map_or((0.0, 0.0), |level| level.decayed(now_ms, self.window_ms()))
+    }
+
+    pub fn prune(&mut self, now_ms: u64) {
+        let window_ms = self.window_ms();
+        self.levels.retain(|_, level| {
+            let (buy_qty, sell_qty) = level.decayed(now_ms, window_ms);
+            buy_qty + sell_qty > TRADE_FLOW_MIN_QTY
+        });
+    }
+}
+
+/// Bid-ask spread in ticks over one bucket of depth updates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadSample {
+    /// Start of the bucket, exchange time in milliseconds
+    pub time: u64,
+    pub min: f32,
+    pub max: f32,
+    pub last: f32,
+}
+
+/// Rolling bid-ask spread of the last few minutes, fed by every depth update
+#[derive(Debug, Default)]
+pub struct SpreadHistory {
+    samples: VecDeque<SpreadSample>,
+}
+
+impl SpreadHistory {
+    pub fn push(&mut self, time: u64, ticks: f32) {
+        let bucket = time - time % SPREAD_BUCKET_MS;
+
+        match self.samples.back_mut() {
+            Some(sample) if sample.time == bucket => {
+                sample.min = sample.min.min(ticks);
+                sample.max = sample.max.max(ticks);
+                sample.last = ticks;
+            }
+            Some(sample) if sample.time > bucket => {}
+            _ => self.samples.push_back(SpreadSample {
+                time: bucket,
+                min: ticks,
+                max: ticks,
+                last: ticks,
+            }),
+        }
+
+        let cutoff = time.saturating_sub(SPREAD_HISTORY_WINDOW_MS);
+        while self.samples.front().is_some_and(|s| s.time < cutoff) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn samples(&self) -> &VecDeque<SpreadSample> {
+        &self.samples
+    }
+
+    pub fn latest(&self) -> Option<&SpreadSample> {
+        self.samples.back()
+    }
+
+    /// Widest spread seen within the window, for scaling
+    pub fn max(&self) -> f32 {
+        self.samples.iter().map(|s| s.max).fold(0.0, f32::max)
+    }
+
+    /// Average of each bucket's closing spread
+    pub fn mean(&self) -> Option<f32> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(self.samples.iter().map(|s| s.last).sum::<f32>() / self.samples.len() as f32)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 enum ChaseProgress {
     #[default]
@@ -1,11 +1,15 @@
 use std::time::Duration;
 
+use exchange::adapter::Exchange;
 use exchange::util::Price;
 use serde::{Deserialize, Serialize};
 
 use crate::util::ok_or_default;
 
 const TRADE_RETENTION_MS: u64 = 120_000;
+/// How long prints stay available to linked charts after leaving the feed
+pub const PRINT_HISTORY_MS: u64 = 3_600_000;
+pub const MAX_PRINT_HISTORY: usize = 500_000;
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
@@ -14,6 +18,46 @@ pub struct Config {
     pub trade_retention: Duration,
     #[serde(deserialize_with = "ok_or_default", default)]
     pub stacked_bar: Option<StackedBar>,
+    #[serde(default)]
+    pub side_filter: SideFilter,
+    /// Only prints within this percent of the latest print's price, `None` for any price
+    #[serde(default)]
+    pub price_band_pct: Option<f32>,
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub size_tiers: SizeTiers,
+    /// Consecutive trades at the same price and side within this many ms of the first
+    /// are listed as one print, `None` lists every trade
+    #[serde(default)]
+    pub cluster_window_ms: Option<u64>,
+    /// Prints at or above this size in quote currency are hidden, `None` for no upper bound
+    #[serde(default)]
+    pub trade_size_cap: Option<f32>,
+}
+
+impl Config {
+    /// Size filters offered as one-click choices, in quote currency
+    pub const SIZE_FILTER_PRESETS: [f32; 5] = [0.0, 1_000.0, 10_000.0, 50_000.0, 100_000.0];
+    pub const PRICE_BAND_RANGE: std::ops::RangeInclusive<f32> = 0.05..=5.0;
+    pub const CLUSTER_WINDOW_CHOICES: [u64; 6] = [25, 50, 100, 250, 500, 1000];
+
+    /// Whether a print of `size` in quote currency is listed,
+    /// `reference` being the price of the latest print
+    pub fn shows(&self, size: f32, price: Price, is_sell: bool, reference: Option<Price>) -> bool {
+        if size < self.trade_size_filter
+            || self.trade_size_cap.is_some_and(|cap| size >= cap)
+            || !self.side_filter.allows(is_sell)
+        {
+            return false;
+        }
+
+        match (self.price_band_pct, reference) {
+            (Some(band_pct), Some(reference)) if reference.to_f32() > 0.0 => {
+                let reference = reference.to_f32();
+                (price.to_f32() - reference).abs() / reference * 100.0 <= band_pct
+            }
+            _ => true,
+        }
+    }
 }
 
 impl Default for Config {
@@ -22,6 +66,11 @@ impl Default for Config {
             trade_size_filter: 0.0,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
             stacked_bar: StackedBar::Compact(StackedBarRatio::default()).into(),
+            side_filter: SideFilter::default(),
+            price_band_pct: None,
+            size_tiers: SizeTiers::default(),
+            cluster_window_ms: None,
+            trade_size_cap: None,
         }
     }
 }
@@ -30,12 +79,88 @@ fn default_buffer_filter() -> Duration {
     Duration::from_millis(TRADE_RETENTION_MS)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SideFilter {
+    #[default]
+    Both,
+    Buys,
+    Sells,
+}
+
+impl SideFilter {
+    pub const ALL: [SideFilter; 3] = [SideFilter::Both, SideFilter::Buys, SideFilter::Sells];
+
+    pub fn allows(self, is_sell: bool) -> bool {
+        match self {
+            SideFilter::Both => true,
+            SideFilter::Buys => !is_sell,
+            SideFilter::Sells => is_sell,
+        }
+    }
+}
+
+impl std::fmt::Display for SideFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SideFilter::Both => write!(f, "Both sides"),
+            SideFilter::Buys => write!(f, "Buys only"),
+            SideFilter::Sells => write!(f, "Sells only"),
+        }
+    }
+}
+
+/// Prints at or above a threshold, in quote currency, get drawn in that tier's color
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SizeTiers {
+    pub enabled: bool,
+    /// Smallest to largest tier, 0 turns a tier off
+    pub thresholds: [f32; 3],
+}
+
+impl SizeTiers {
+    pub const THRESHOLD_CHOICES: [f32; 10] = [
+        0.0,
+        5_000.0,
+        10_000.0,
+        25_000.0,
+        50_000.0,
+        100_000.0,
+        250_000.0,
+        500_000.0,
+        1_000_000.0,
+        5_000_000.0,
+    ];
+
+    /// Index of the highest tier `size` reaches
+    pub fn tier_of(&self, size: f32) -> Option<usize> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.thresholds
+            .iter()
+            .rposition(|threshold| *threshold > 0.0 && size >= *threshold)
+    }
+}
+
+impl Default for SizeTiers {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thresholds: [10_000.0, 100_000.0, 1_000_000.0],
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TradeDisplay {
     pub time_str: String,
     pub price: Price,
     pub qty: f32,
     pub is_sell: bool,
+    /// Venue of the print when the pane merges several, `None` for a single venue
+    pub venue: Option<Exchange>,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +169,31 @@ pub struct TradeEntry {
     pub display: TradeDisplay,
 }
 
+impl TradeEntry {
+    /// Whether a trade belongs to this print when clustering within `window_ms`
+    pub fn continues(
+        &self,
+        price: Price,
+        is_sell: bool,
+        venue: Option<Exchange>,
+        ts_ms: u64,
+        window_ms: u64,
+    ) -> bool {
+        self.display.price == price
+            && self.display.is_sell == is_sell
+            && self.display.venue == venue
+            && ts_ms.saturating_sub(self.ts_ms) <= window_ms
+    }
+}
+
+/// Size and side of a print, kept longer than the feed for lookups by time
+#[derive(Debug, Clone, Copy)]
+pub struct PrintRecord {
+    pub ts_ms: u64,
+    pub qty: f32,
+    pub is_sell: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Copy)]
 pub enum StackedBar {
     Compact(StackedBarRatio),
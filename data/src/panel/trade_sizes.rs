@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Trades are counted into slices of this length, the finest the window can be cut at
+pub const SLICE_MS: u64 = 1_000;
+/// Lower bound of the second bucket in quote currency, smaller trades all go in the first
+const FIRST_BOUND: f32 = 10.0;
+pub const BUCKET_COUNT: usize = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub window: Window,
+}
+
+/// Rolling time window the histogram counts trades over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Window {
+    M1,
+    #[default]
+    M5,
+    M15,
+    H1,
+}
+
+impl Window {
+    pub const ALL: [Window; 4] = [Window::M1, Window::M5, Window::M15, Window::H1];
+
+    pub fn duration_ms(self) -> u64 {
+        match self {
+            Window::M1 => 60_000,
+            Window::M5 => 5 * 60_000,
+            Window::M15 => 15 * 60_000,
+            Window::H1 => 60 * 60_000,
+        }
+    }
+}
+
+impl std::fmt::Display for Window {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Window::M1 => write!(f, "1m"),
+            Window::M5 => write!(f, "5m"),
+            Window::M15 => write!(f, "15m"),
+            Window::H1 => write!(f, "1h"),
+        }
+    }
+}
+
+/// Upper bound of a bucket, two per tenfold: 10, 30, 100, 300...
+fn bound(bucket: usize) -> f32 {
+    let step = if bucket.is_multiple_of(2) { 1.0 } else { 3.0 };
+    FIRST_BOUND * 10f32.powi((bucket / 2) as i32) * step
+}
+
+/// Bucket a trade of `size` in quote currency falls in
+pub fn bucket_of(size: f32) -> usize {
+    (0..BUCKET_COUNT - 1)
+        .take_while(|bucket| size >= bound(*bucket))
+        .count()
+}
+
+/// Size range of a bucket in quote currency, `None` as the upper bound of the last one
+pub fn bucket_bounds(bucket: usize) -> (f32, Option<f32>) {
+    let low = if bucket == 0 { 0.0 } else { bound(bucket - 1) };
+    let high = (bucket + 1 < BUCKET_COUNT).then(|| bound(bucket));
+    (low, high)
+}
+
+/// Trades and their summed size on each side of one bucket
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Bin {
+    pub buys: u32,
+    pub sells: u32,
+    pub buy_size: f32,
+    pub sell_size: f32,
+}
+
+impl Bin {
+    pub fn count(&self) -> u32 {
+        self.buys + self.sells
+    }
+
+    fn add(&mut self, other: &Bin) {
+        self.buys += other.buys;
+        self.sells += other.sells;
+        self.buy_size += other.buy_size;
+        self.sell_size += other.sell_size;
+    }
+}
+
+/// Trade counts per size bucket, kept in one-second slices so the window can be changed
+/// without holding on to the trades
+#[derive(Debug, Default)]
+pub struct Histogram {
+    slices: BTreeMap<u64, [Bin; BUCKET_COUNT]>,
+}
+
+impl Histogram {
+    /// Adds a trade of `size` in quote currency
+    pub fn insert(&mut self, time: u64, size: f32, is_sell: bool) {
+        let slice = self
+            .slices
+            .entry(time - time % SLICE_MS)
+            .or_insert([Bin::default(); BUCKET_COUNT]);
+        let bin = &mut slice[bucket_of(size)];
+
+        if is_sell {
+            bin.sells += 1;
+            bin.sell_size += size;
+        } else {
+            bin.buys += 1;
+            bin.buy_size += size;
+        }
+    }
+
+    /// Drops slices that ended before `time`
+    pub fn prune_before(&mut self, time: u64) {
+        self.slices = self.slices.split_off(&(time - time % SLICE_MS));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+
+    /// Bins summed over the slices from `from` on
+    pub fn bins_since(&self, from: u64) -> [Bin; BUCKET_COUNT] {
+        let mut bins = [Bin::default(); BUCKET_COUNT];
+
+        for slice in self.slices.range(from - from % SLICE_MS..).map(|(_, s)| s) {
+            for (bin, slice_bin) in bins.iter_mut().zip(slice) {
+                bin.add(slice_bin);
+            }
+        }
+
+        bins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_log_scaled_and_match_their_bounds() {
+        assert_eq!(bucket_of(5.0), 0);
+        assert_eq!(bucket_of(10.0), 1);
+        assert_eq!(bucket_of(29.0), 1);
+        assert_eq!(bucket_of(30.0), 2);
+        assert_eq!(bucket_of(150_000.0), 9);
+        assert_eq!(bucket_of(1e12), BUCKET_COUNT - 1);
+
+        assert_eq!(bucket_bounds(0), (0.0, Some(10.0)));
+        assert_eq!(bucket_bounds(1), (10.0, Some(30.0)));
+        assert_eq!(bucket_bounds(2), (30.0, Some(100.0)));
+        assert_eq!(bucket_bounds(BUCKET_COUNT - 1).1, None);
+    }
+
+    #[test]
+    fn bins_sum_the_window_by_side() {
+        let mut histogram = Histogram::default();
+        histogram.insert(500, 50.0, false);
+        histogram.insert(1_200, 60.0, true);
+        histogram.insert(3_000, 5.0, false);
+
+        let bins = histogram.bins_since(1_000);
+        assert_eq!(bins[0].buys, 1);
+        assert_eq!(bins[2].count(), 1);
+        assert_eq!(bins[2].sell_size, 60.0);
+
+        histogram.prune_before(2_500);
+        assert_eq!(histogram.bins_since(0)[2], Bin::default());
+    }
+}
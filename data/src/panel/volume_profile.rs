@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use exchange::Trade;
+use exchange::util::{Price, PriceStep};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// Trades are summed into slices of this length, the finest the lookback can be cut at
+pub const SLICE_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub lookback: Lookback,
+    /// Share of the volume around the POC shaded as the value area, in `0..=1`
+    pub value_area: f32,
+}
+
+impl Config {
+    pub const VALUE_AREA_RANGE: std::ops::RangeInclusive<f32> = 0.5..=0.95;
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            lookback: Lookback::default(),
+            value_area: 0.7,
+        }
+    }
+}
+
+/// Time window the profile is built over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Lookback {
+    /// Window a chart of the pane's link group shows, everything kept until one is moved
+    VisibleRange,
+    #[default]
+    Day,
+    Week,
+}
+
+impl Lookback {
+    pub const ALL: [Lookback; 3] = [Lookback::VisibleRange, Lookback::Day, Lookback::Week];
+
+    /// Length of the window ending now, `None` while it follows a chart
+    pub fn duration_ms(self) -> Option<u64> {
+        match self {
+            Lookback::VisibleRange => None,
+            Lookback::Day => Some(86_400_000),
+            Lookback::Week => Some(7 * 86_400_000),
+        }
+    }
+}
+
+impl std::fmt::Display for Lookback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lookback::VisibleRange => write!(f, "Visible range"),
+            Lookback::Day => write!(f, "1D"),
+            Lookback::Week => write!(f, "1W"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub price: Price,
+    pub buy: f32,
+    pub sell: f32,
+}
+
+impl Level {
+    pub fn total(&self) -> f32 {
+        self.buy + self.sell
+    }
+}
+
+/// Buy and sell volume per price at the ticker's tick size, kept in one-minute footprints
+/// so any window can be summed without holding on to the trades
+#[derive(Debug, Default)]
+pub struct Profile {
+    slices: BTreeMap<u64, FxHashMap<Price, (f32, f32)>>,
+}
+
+impl Profile {
+    pub fn insert_trades(&mut self, trades: &[Trade]) {
+        for trade in trades {
+            let slice = self
+                .slices
+                .entry(trade.time - trade.time % SLICE_MS)
+                .or_default();
+            let entry = slice.entry(trade.price).or_default();
+
+            if trade.is_sell {
+                entry.1 += trade.qty;
+            } else {
+                entry.0 += trade.qty;
+            }
+        }
+    }
+
+    /// Drops slices that ended before `time`
+    pub fn prune_before(&mut self, time: u64) {
+        self.slices = self.slices.split_off(&(time - time % SLICE_MS));
+    }
+
+    pub fn clear(&mut self) {
+        self.slices.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+
+    /// Start of the oldest slice held
+    pub fn earliest(&self) -> Option<u64> {
+        self.slices.keys().next().copied()
+    }
+
+    /// Levels traded within `from..=to` grouped into rows of `step`, by price ascending
+    pub fn levels(&self, from: u64, to: u64, step: PriceStep) -> Vec<Level> {
+        if from > to {
+            return vec![];
+        }
+        let mut rows: BTreeMap<Price, (f32, f32)> = BTreeMap::new();
+
+        for (_, slice) in self.slices.range(from - from % SLICE_MS..=to) {
+            for (price, (buy, sell)) in slice {
+                let row = rows.entry(price.round_to_step(step)).or_default();
+                row.0 += buy;
+                row.1 += sell;
+            }
+        }
+
+        rows.into_iter()
+            .map(|(price, (buy, sell))| Level { price, buy, sell })
+            .collect()
+    }
+}
+
+/// Index of the POC and the inclusive index range of the value area holding `share` of
+/// the volume, grown from the POC towards the heavier neighbouring row
+pub fn value_area(levels: &[Level], share: f32) -> Option<(usize, usize, usize)> {
+    let poc = levels
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total().total_cmp(&b.total()))
+        .map(|(idx, _)| idx)?;
+
+    let target = levels.iter().map(Level::total).sum::<f32>() * share.clamp(0.0, 1.0);
+    let (mut low, mut high) = (poc, poc);
+    let mut covered = levels[poc].total();
+
+    while covered < target && (low > 0 || high + 1 < levels.len()) {
+        let below = low.checked_sub(1).map(|idx| levels[idx].total());
+        let above = levels.get(high + 1).map(Level::total);
+
+        match (below, above) {
+            (Some(below), Some(above)) if above >= below => {
+                high += 1;
+                covered += above;
+            }
+            (Some(below), _) => {
+                low -= 1;
+                covered += below;
+            }
+            (None, Some(above)) => {
+                high += 1;
+                covered += above;
+            }
+            (None, None) => break,
+        }
+    }
+
+    Some((poc, low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exact price from hundredths, f32 can't hold e.g. 100.5 in atomic units
+    fn cents(cents: i64) -> Price {
+        Price::from_units(cents * 10i64.pow(Price::PRICE_SCALE as u32 - 2))
+    }
+
+    fn trade(time: u64, price: Price, qty: f32, is_sell: bool) -> Trade {
+        Trade {
+            time,
+            is_sell,
+            price,
+            qty,
+            sub_ms_nanos: 0,
+        }
+    }
+
+    #[test]
+    fn levels_are_summed_over_the_window_into_rows() {
+        let mut profile = Profile::default();
+        profile.insert_trades(&[
+            trade(0, cents(10_000), 1.0, false),
+            trade(10_000, cents(10_050), 2.0, true),
+            trade(SLICE_MS, cents(10_100), 3.0, false),
+            trade(5 * SLICE_MS, cents(10_000), 4.0, true),
+        ]);
+
+        let step = PriceStep::from_f32(1.0);
+        let levels = profile.levels(0, 2 * SLICE_MS, step);
+        let rows: Vec<(Price, f32, f32)> =
+            levels.iter().map(|l| (l.price, l.buy, l.sell)).collect();
+        assert_eq!(
+            rows,
+            vec![(cents(10_000), 1.0, 0.0), (cents(10_100), 3.0, 2.0)]
+        );
+        assert!(profile.levels(2 * SLICE_MS, 0, step).is_empty());
+
+        profile.prune_before(SLICE_MS + 1);
+        assert_eq!(profile.earliest(), Some(SLICE_MS));
+    }
+
+    #[test]
+    fn value_area_grows_towards_the_heavier_side() {
+        let levels: Vec<Level> = [1.0, 5.0, 10.0, 2.0, 1.0]
+            .iter()
+            .enumerate()
+            .map(|(idx, total)| Level {
+                price: Price::from_f32(idx as f32),
+                buy: *total,
+                sell: 0.0,
+            })
+            .collect();
+
+        // 70% of 19 is reached once the heavier row below the POC is added
+        assert_eq!(value_area(&levels, 0.7), Some((2, 1, 2)));
+        assert_eq!(value_area(&levels, 0.9), Some((2, 1, 4)));
+        assert_eq!(value_area(&levels, 0.0), Some((2, 2, 2)));
+        assert_eq!(value_area(&[], 0.7), None);
+    }
+}
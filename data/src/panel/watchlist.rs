@@ -0,0 +1,106 @@
+use exchange::{Ticker, TickerInfo, TickerStats};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SortColumn {
+    #[default]
+    Ticker,
+    Price,
+    Change,
+    Volume,
+}
+
+impl SortColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortColumn::Ticker => "Ticker",
+            SortColumn::Price => "Price",
+            SortColumn::Change => "24h %",
+            SortColumn::Volume => "Volume",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// In the order they were added
+    pub tickers: Vec<TickerInfo>,
+    pub sort_by: SortColumn,
+    pub sort_desc: bool,
+}
+
+impl Config {
+    pub fn add(&mut self, ticker_info: TickerInfo) {
+        if !self.tickers.contains(&ticker_info) {
+            self.tickers.push(ticker_info);
+        }
+    }
+
+    pub fn remove(&mut self, ticker: Ticker) {
+        self.tickers.retain(|ti| ti.ticker != ticker);
+    }
+
+    /// Swaps `from` for `to` in place, dropping it instead when `to` is already listed
+    pub fn replace(&mut self, from: Ticker, to: TickerInfo) -> bool {
+        let Some(idx) = self.tickers.iter().position(|ti| ti.ticker == from) else {
+            return false;
+        };
+
+        if self.tickers.iter().any(|ti| ti.ticker == to.ticker) {
+            self.tickers.remove(idx);
+        } else {
+            self.tickers[idx] = to;
+        }
+        true
+    }
+
+    /// Clicking the sorted column flips its direction, another one sorts by it descending
+    pub fn sort_by(&mut self, column: SortColumn) {
+        if self.sort_by == column {
+            self.sort_desc = !self.sort_desc;
+        } else {
+            self.sort_by = column;
+            self.sort_desc = true;
+        }
+    }
+
+    /// Tickers in display order, those without stats yet stay at the bottom
+    pub fn sorted(
+        &self,
+        stats_of: impl Fn(&Ticker) -> Option<TickerStats>,
+    ) -> Vec<(TickerInfo, Option<TickerStats>)> {
+        let mut rows: Vec<_> = self
+            .tickers
+            .iter()
+            .map(|ti| (*ti, stats_of(&ti.ticker)))
+            .collect();
+
+        let key = |stats: &TickerStats| match self.sort_by {
+            SortColumn::Price => stats.mark_price,
+            SortColumn::Change => stats.daily_price_chg,
+            SortColumn::Volume => stats.daily_volume,
+            SortColumn::Ticker => 0.0,
+        };
+
+        rows.sort_by(|(a_info, a), (b_info, b)| {
+            let ordering = match (self.sort_by, a, b) {
+                (SortColumn::Ticker, _, _) => {
+                    a_info.ticker.to_string().cmp(&b_info.ticker.to_string())
+                }
+                (_, Some(a), Some(b)) => key(a).total_cmp(&key(b)),
+                (_, Some(_), None) => return std::cmp::Ordering::Less,
+                (_, None, Some(_)) => return std::cmp::Ordering::Greater,
+                (_, None, None) => std::cmp::Ordering::Equal,
+            };
+
+            if self.sort_desc {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        rows
+    }
+}
@@ -0,0 +1,693 @@
+//! Simulated trading on the live streams. Orders fill against the order book and prints of
+//! their ticker, positions keep an average entry and P&L in quote currency, nothing is sent
+//! to an exchange
+
+pub mod journal;
+
+use exchange::depth::Depth;
+use exchange::util::Price;
+use exchange::{Ticker, Trade};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// Quantities below this count as zero, float leftovers of partial fills
+const QTY_EPSILON: f32 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Size of new orders in quote currency, turned into a quantity at the order's price
+    pub order_notional: f32,
+    /// Charged on the notional of every fill, in basis points
+    pub fee_bps: f32,
+    /// Clicks on the price axis place limit orders, order lines can be dragged and cancelled
+    pub chart_trading: bool,
+}
+
+impl Config {
+    pub const FEE_BPS_CHOICES: [f32; 6] = [0.0, 1.0, 2.0, 4.0, 5.0, 10.0];
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            order_notional: 1_000.0,
+            fee_bps: 5.0,
+            chart_trading: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    pub fn sign(self) -> f32 {
+        match self {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Buy => write!(f, "Buy"),
+            Side::Sell => write!(f, "Sell"),
+        }
+    }
+}
+
+/// What order entry asks of the account for one ticker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Market order when `limit` is `None`
+    Place {
+        side: Side,
+        limit: Option<Price>,
+    },
+    /// Limit order on the passive side of the mark, a buy below it and a sell above
+    PlaceAt {
+        limit: Price,
+    },
+    Move {
+        id: u32,
+        limit: Price,
+    },
+    Cancel {
+        id: u32,
+    },
+    /// Cancels the working orders and closes the position
+    Flatten,
+    CancelAll,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    pub id: u32,
+    pub ticker: Ticker,
+    pub side: Side,
+    pub qty: f32,
+    pub filled: f32,
+    /// `None` for market orders
+    pub limit: Option<Price>,
+    pub placed_at: u64,
+}
+
+impl Order {
+    pub fn remaining(&self) -> f32 {
+        (self.qty - self.filled).max(0.0)
+    }
+
+    /// Whether the order may trade at `price`
+    fn accepts(&self, price: Price) -> bool {
+        match (self.limit, self.side) {
+            (None, _) => true,
+            (Some(limit), Side::Buy) => price <= limit,
+            (Some(limit), Side::Sell) => price >= limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    pub order_id: u32,
+    pub ticker: Ticker,
+    pub side: Side,
+    pub qty: f32,
+    /// Average price over the levels the fill took
+    pub price: Price,
+    pub time: u64,
+    pub fee: f32,
+    /// P&L the fill closed on the position, before its fee
+    pub realized: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    /// Positive when long, negative when short
+    pub qty: f32,
+    pub avg_entry: f32,
+    /// Closed P&L net of fees over the session
+    pub realized: f32,
+    /// Closed P&L net of fees since the position was last flat
+    open_realized: f32,
+}
+
+impl Position {
+    pub fn is_flat(&self) -> bool {
+        self.qty.abs() < QTY_EPSILON
+    }
+
+    pub fn unrealized(&self, mark: f32) -> f32 {
+        if self.is_flat() {
+            0.0
+        } else {
+            (mark - self.avg_entry) * self.qty
+        }
+    }
+
+    /// Exit price at which the open position nets out its fees and partial closes
+    pub fn break_even(&self) -> Option<f32> {
+        (!self.is_flat()).then(|| self.avg_entry - self.open_realized / self.qty)
+    }
+
+    /// Books a fill, returns the P&L it closed before fees
+    fn apply(&mut self, side: Side, qty: f32, price: f32, fee: f32) -> f32 {
+        if self.is_flat() {
+            self.open_realized = 0.0;
+        }
+        let signed_qty = side.sign() * qty;
+        let mut closed = 0.0;
+
+        if self.is_flat() || self.qty.signum() == signed_qty.signum() {
+            let new_qty = self.qty + signed_qty;
+            self.avg_entry =
+                (self.avg_entry * self.qty.abs() + price * qty) / new_qty.abs().max(QTY_EPSILON);
+            self.qty = new_qty;
+        } else {
+            closed = qty.min(self.qty.abs()) * (price - self.avg_entry) * self.qty.signum();
+            let new_qty = self.qty + signed_qty;
+
+            if new_qty.abs() < QTY_EPSILON {
+                self.qty = 0.0;
+            } else {
+                // flipped sides, what's left opened at this price
+                if new_qty.signum() != self.qty.signum() {
+                    self.avg_entry = price;
+                    self.open_realized = 0.0;
+                }
+                self.qty = new_qty;
+            }
+        }
+
+        self.realized += closed - fee;
+        self.open_realized += closed - fee;
+        closed
+    }
+}
+
+/// What a chart draws of the account for one ticker
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overlay {
+    /// Kept once the ticker traded, flat or not, for its realized P&L
+    pub position: Option<Position>,
+    pub mark: Option<f32>,
+    pub orders: Vec<Order>,
+    /// Whether the chart takes orders, see `Config::chart_trading`
+    pub chart_trading: bool,
+}
+
+impl Overlay {
+    pub fn is_empty(&self) -> bool {
+        self.position.is_none() && self.orders.is_empty()
+    }
+}
+
+/// Simulated orders, positions and fills across tickers
+#[derive(Debug, Default)]
+pub struct Account {
+    pub config: Config,
+    orders: Vec<Order>,
+    positions: FxHashMap<Ticker, Position>,
+    fills: Vec<Fill>,
+    /// Last traded price per ticker, the mid until the first print
+    marks: FxHashMap<Ticker, f32>,
+    /// Time of the last print seen per ticker, a ticker streamed at several depth
+    /// aggregations gets each print once per stream
+    last_prints: FxHashMap<Ticker, (u64, u32)>,
+    journal: Vec<journal::Trade>,
+    /// Bumped whenever the journal changes
+    revision: u64,
+    next_id: u32,
+}
+
+impl Account {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Places an order sized after `Config::order_notional`, `None` until the ticker has a
+    /// price to size a market order at
+    pub fn place(
+        &mut self,
+        ticker: Ticker,
+        side: Side,
+        limit: Option<Price>,
+        time: u64,
+    ) -> Option<u32> {
+        let price = limit.map(Price::to_f32).or_else(|| self.mark(ticker))?;
+        if price <= 0.0 {
+            return None;
+        }
+
+        Some(self.place_qty(
+            ticker,
+            side,
+            self.config.order_notional / price,
+            limit,
+            time,
+        ))
+    }
+
+    fn place_qty(
+        &mut self,
+        ticker: Ticker,
+        side: Side,
+        qty: f32,
+        limit: Option<Price>,
+        time: u64,
+    ) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.orders.push(Order {
+            id,
+            ticker,
+            side,
+            qty,
+            filled: 0.0,
+            limit,
+            placed_at: time,
+        });
+        id
+    }
+
+    /// Side a limit at `price` rests on, `None` at the mark or before the ticker has one
+    pub fn passive_side(&self, ticker: Ticker, price: Price) -> Option<Side> {
+        let mark = self.mark(ticker)?;
+        let price = price.to_f32();
+
+        if price < mark {
+            Some(Side::Buy)
+        } else if price > mark {
+            Some(Side::Sell)
+        } else {
+            None
+        }
+    }
+
+    /// Reprices a working limit order, keeping what it already filled
+    pub fn move_order(&mut self, id: u32, limit: Price) -> bool {
+        let Some(order) = self
+            .orders
+            .iter_mut()
+            .find(|order| order.id == id && order.limit.is_some())
+        else {
+            return false;
+        };
+
+        order.limit = Some(limit);
+        true
+    }
+
+    pub fn cancel(&mut self, id: u32) -> bool {
+        let count = self.orders.len();
+        self.orders.retain(|order| order.id != id);
+        self.orders.len() != count
+    }
+
+    pub fn cancel_all(&mut self, ticker: Ticker) -> usize {
+        let count = self.orders.len();
+        self.orders.retain(|order| order.ticker != ticker);
+        count - self.orders.len()
+    }
+
+    /// Cancels the ticker's orders and closes its position at market, `None` if it was flat
+    pub fn flatten(&mut self, ticker: Ticker, time: u64) -> Option<u32> {
+        self.cancel_all(ticker);
+
+        let position = self.position(ticker).filter(|p| !p.is_flat())?;
+        let side = if position.qty > 0.0 {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+
+        Some(self.place_qty(ticker, side, position.qty.abs(), None, time))
+    }
+
+    /// Drops every order, position, fill and journaled trade
+    pub fn reset(&mut self) {
+        let revision = self.revision + 1;
+        *self = Self::new(self.config);
+        self.revision = revision;
+    }
+
+    pub fn orders(&self, ticker: Ticker) -> impl Iterator<Item = &Order> {
+        self.orders
+            .iter()
+            .filter(move |order| order.ticker == ticker)
+    }
+
+    pub fn position(&self, ticker: Ticker) -> Option<Position> {
+        self.positions.get(&ticker).copied()
+    }
+
+    pub fn mark(&self, ticker: Ticker) -> Option<f32> {
+        self.marks.get(&ticker).copied()
+    }
+
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    /// Trades in the order they opened, the open ones included
+    pub fn journal(&self) -> &[journal::Trade] {
+        &self.journal
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    pub fn overlay(&self, ticker: Ticker) -> Overlay {
+        Overlay {
+            position: self.position(ticker),
+            mark: self.mark(ticker),
+            orders: self.orders(ticker).cloned().collect(),
+            chart_trading: self.config.chart_trading,
+        }
+    }
+
+    /// Whether the ticker has working orders or a position to show
+    pub fn is_active(&self, ticker: Ticker) -> bool {
+        self.orders(ticker).next().is_some() || self.position(ticker).is_some_and(|p| !p.is_flat())
+    }
+
+    /// Fills the ticker's orders off a depth update and the trades that came with it. Market
+    /// and marketable limit orders take the book first, resting limits then fill on prints:
+    /// through the limit for the rest of the order, at it for as much as traded there
+    pub fn on_market_data(
+        &mut self,
+        ticker: Ticker,
+        depth: &Depth,
+        trades: &[Trade],
+        time: u64,
+    ) -> Vec<Fill> {
+        self.on_feed(ticker, Some(depth), trades, time)
+    }
+
+    /// Fills resting orders off trades that came without a depth update, orders taking the
+    /// book wait for the next one
+    pub fn on_prints(&mut self, ticker: Ticker, trades: &[Trade]) -> Vec<Fill> {
+        let time = trades.last().map_or(0, |trade| trade.time);
+        self.on_feed(ticker, None, trades, time)
+    }
+
+    fn on_feed(
+        &mut self,
+        ticker: Ticker,
+        depth: Option<&Depth>,
+        trades: &[Trade],
+        time: u64,
+    ) -> Vec<Fill> {
+        let seen = |trade: &Trade| {
+            self.last_prints
+                .get(&ticker)
+                .is_some_and(|last| (trade.time, trade.sub_ms_nanos) <= *last)
+        };
+        let trades = &trades[trades.partition_point(seen)..];
+
+        if let Some(trade) = trades.last() {
+            self.last_prints
+                .insert(ticker, (trade.time, trade.sub_ms_nanos));
+            self.marks.insert(ticker, trade.price.to_f32());
+        } else if let Some(mid) = depth.and_then(Depth::mid_price) {
+            self.marks.entry(ticker).or_insert(mid.to_f32());
+        }
+
+        if let Some(position) = self.position(ticker).filter(|p| !p.is_flat()) {
+            for trade in trades {
+                if journal::track(&mut self.journal, ticker, position, trade.price.to_f32()) {
+                    self.revision += 1;
+                }
+            }
+        }
+
+        if self.orders(ticker).next().is_none() {
+            return vec![];
+        }
+
+        let mut fills = depth.map_or_else(Vec::new, |depth| self.take_book(ticker, depth, time));
+        fills.extend(self.fill_on_prints(ticker, trades));
+
+        self.orders.retain(|order| order.remaining() > QTY_EPSILON);
+        let fee_rate = self.config.fee_bps / 10_000.0;
+        for fill in &mut fills {
+            fill.fee = fill.qty * fill.price.to_f32() * fee_rate;
+            self.book_fill(fill);
+        }
+        if !fills.is_empty() {
+            self.revision += 1;
+        }
+        self.fills.extend(fills.iter().copied());
+
+        fills
+    }
+
+    fn take_book(&mut self, ticker: Ticker, depth: &Depth, time: u64) -> Vec<Fill> {
+        let asks: Vec<(Price, f32)> = depth
+            .asks
+            .iter()
+            .map(|(price, qty)| (*price, *qty))
+            .collect();
+        let bids: Vec<(Price, f32)> = depth
+            .bids
+            .iter()
+            .rev()
+            .map(|(price, qty)| (*price, *qty))
+            .collect();
+        // liquidity already taken by earlier orders of this pass, per level from the best
+        let mut taken_asks = vec![0.0; asks.len()];
+        let mut taken_bids = vec![0.0; bids.len()];
+
+        let mut fills = vec![];
+        for order in self.orders.iter_mut().filter(|o| o.ticker == ticker) {
+            let (levels, taken) = match order.side {
+                Side::Buy => (&asks, &mut taken_asks),
+                Side::Sell => (&bids, &mut taken_bids),
+            };
+
+            let mut qty = 0.0;
+            // in atomic units, so a fill on a single level keeps its exact price
+            let mut notional = 0.0_f64;
+            for ((price, level_qty), level_taken) in levels.iter().zip(taken.iter_mut()) {
+                let want = order.remaining() - qty;
+                if want <= QTY_EPSILON || !order.accepts(*price) {
+                    break;
+                }
+                let take = want.min(level_qty - *level_taken);
+                if take > 0.0 {
+                    *level_taken += take;
+                    qty += take;
+                    notional += f64::from(take) * price.units as f64;
+                }
+            }
+
+            if qty > QTY_EPSILON {
+                order.filled += qty;
+                let avg = Price::from_units((notional / f64::from(qty)).round() as i64);
+                fills.push(new_fill(order, qty, avg, time));
+            }
+        }
+        fills
+    }
+
+    fn fill_on_prints(&mut self, ticker: Ticker, trades: &[Trade]) -> Vec<Fill> {
+        let mut fills = vec![];
+
+        for trade in trades {
+            let price = trade.price;
+            let mut at_limit = trade.qty;
+
+            for order in self.orders.iter_mut().filter(|o| o.ticker == ticker) {
+                let remaining = order.remaining();
+                if remaining <= QTY_EPSILON || !order.accepts(price) {
+                    continue;
+                }
+
+                let qty = match order.limit {
+                    Some(limit) if price == limit => {
+                        // only prints hitting our side of the book reach a resting order
+                        let hits_us = trade.is_sell == (order.side == Side::Buy);
+                        if !hits_us {
+                            continue;
+                        }
+                        let qty = remaining.min(at_limit);
+                        at_limit -= qty;
+                        qty
+                    }
+                    _ => remaining,
+                };
+
+                if qty > QTY_EPSILON {
+                    order.filled += qty;
+                    fills.push(new_fill(order, qty, price, trade.time));
+                }
+            }
+        }
+        fills
+    }
+
+    fn book_fill(&mut self, fill: &mut Fill) {
+        let position = self.positions.entry(fill.ticker).or_default();
+        let before = *position;
+
+        fill.realized = position.apply(fill.side, fill.qty, fill.price.to_f32(), fill.fee);
+        journal::record(&mut self.journal, fill, before, *position);
+    }
+}
+
+fn new_fill(order: &Order, qty: f32, price: Price, time: u64) -> Fill {
+    Fill {
+        order_id: order.id,
+        ticker: order.ticker,
+        side: order.side,
+        qty,
+        price,
+        time,
+        fee: 0.0,
+        realized: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::adapter::Exchange;
+
+    fn ticker() -> Ticker {
+        Ticker::new("BTCUSDT", Exchange::BinanceLinear)
+    }
+
+    fn trade(time: u64, price: f32, qty: f32, is_sell: bool) -> Trade {
+        Trade {
+            time,
+            is_sell,
+            price: Price::from_f32(price),
+            qty,
+            sub_ms_nanos: 0,
+        }
+    }
+
+    fn no_fees() -> Account {
+        Account::new(Config {
+            order_notional: 1_000.0,
+            fee_bps: 0.0,
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn market_order_walks_the_book() {
+        let mut account = no_fees();
+        let depth = Depth {
+            bids: [(Price::from_f32(99.0), 5.0)].into_iter().collect(),
+            asks: [
+                (Price::from_f32(100.0), 4.0),
+                (Price::from_f32(101.0), 10.0),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        account.on_market_data(ticker(), &depth, &[trade(0, 100.0, 1.0, false)], 0);
+
+        account.place(ticker(), Side::Buy, None, 1).unwrap();
+        let fills = account.on_market_data(ticker(), &depth, &[], 2);
+
+        assert_eq!(fills.len(), 1);
+        assert!((fills[0].qty - 10.0).abs() < 1e-4);
+        assert!((fills[0].price.to_f32() - 100.6).abs() < 1e-4);
+        assert_eq!(account.orders(ticker()).count(), 0);
+    }
+
+    #[test]
+    fn resting_limit_fills_at_its_price_only_as_much_as_traded() {
+        let mut account = no_fees();
+        let depth = Depth::default();
+
+        account
+            .place(ticker(), Side::Buy, Some(Price::from_f32(100.0)), 0)
+            .unwrap();
+        // a buy print at the limit doesn't reach a resting bid
+        assert!(
+            account
+                .on_market_data(ticker(), &depth, &[trade(1, 100.0, 3.0, false)], 1)
+                .is_empty()
+        );
+
+        let fills = account.on_market_data(ticker(), &depth, &[trade(2, 100.0, 4.0, true)], 2);
+        assert_eq!(fills[0].qty, 4.0);
+
+        let fills = account.on_market_data(ticker(), &depth, &[trade(3, 99.5, 1.0, true)], 3);
+        assert_eq!(fills[0].qty, 6.0);
+        assert_eq!(account.position(ticker()).unwrap().qty, 10.0);
+
+        // the same print from another stream of the ticker
+        account
+            .place(ticker(), Side::Buy, Some(Price::from_f32(100.0)), 4)
+            .unwrap();
+        assert!(
+            account
+                .on_market_data(ticker(), &depth, &[trade(3, 99.5, 1.0, true)], 5)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn moved_limit_keeps_its_fills_and_rests_at_the_new_price() {
+        let mut account = no_fees();
+        let depth = Depth::default();
+        account.on_market_data(ticker(), &depth, &[trade(0, 100.0, 1.0, false)], 0);
+
+        let price = Price::from_f32;
+        assert_eq!(account.passive_side(ticker(), price(98.0)), Some(Side::Buy));
+        assert_eq!(account.passive_side(ticker(), price(100.0)), None);
+
+        let id = account
+            .place(ticker(), Side::Buy, Some(price(100.0)), 1)
+            .unwrap();
+        account.on_market_data(ticker(), &depth, &[trade(2, 100.0, 4.0, true)], 2);
+
+        assert!(account.move_order(id, price(98.0)));
+        assert!(
+            account
+                .on_market_data(ticker(), &depth, &[trade(3, 100.0, 4.0, true)], 3)
+                .is_empty()
+        );
+
+        let fills = account.on_market_data(ticker(), &depth, &[trade(4, 98.0, 10.0, true)], 4);
+        assert_eq!((fills[0].qty, fills[0].price), (6.0, price(98.0)));
+
+        let market = account.place(ticker(), Side::Sell, None, 5).unwrap();
+        assert!(!account.move_order(market, price(99.0)));
+    }
+
+    #[test]
+    fn position_tracks_entry_pnl_and_break_even() {
+        let mut position = Position::default();
+        position.apply(Side::Buy, 2.0, 100.0, 1.0);
+        position.apply(Side::Buy, 2.0, 110.0, 1.0);
+        assert_eq!(position.avg_entry, 105.0);
+        // fees of the entries are made back 0.5 above the average
+        assert_eq!(position.break_even(), Some(105.5));
+
+        let closed = position.apply(Side::Sell, 1.0, 115.0, 0.0);
+        assert_eq!(closed, 10.0);
+        assert_eq!(position.unrealized(115.0), 30.0);
+
+        position.apply(Side::Sell, 5.0, 120.0, 0.0);
+        assert_eq!(position.qty, -2.0);
+        assert_eq!(position.avg_entry, 120.0);
+        assert_eq!(position.realized, 53.0);
+    }
+}
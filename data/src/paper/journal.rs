@@ -0,0 +1,273 @@
+//! Simulated trades from opening a position to flat again, with the excursions seen while
+//! open, and statistics and CSV export over them
+
+use super::{Fill, Position, QTY_EPSILON, Side};
+use exchange::Ticker;
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub ticker: Ticker,
+    /// `Buy` for a long, `Sell` for a short
+    pub side: Side,
+    pub entry_time: u64,
+    /// `None` while the position is open
+    pub exit_time: Option<u64>,
+    pub avg_entry: f32,
+    /// Largest size held
+    pub qty: f32,
+    /// P&L closed so far, before fees
+    pub gross: f32,
+    pub fees: f32,
+    /// Worst unrealized P&L while open, zero or below
+    pub mae: f32,
+    /// Best unrealized P&L while open, zero or above
+    pub mfe: f32,
+    exit_qty: f32,
+    exit_notional: f32,
+}
+
+impl Trade {
+    pub fn is_open(&self) -> bool {
+        self.exit_time.is_none()
+    }
+
+    pub fn net(&self) -> f32 {
+        self.gross - self.fees
+    }
+
+    /// Average over the fills that closed the position, `None` before the first one
+    pub fn avg_exit(&self) -> Option<f32> {
+        (self.exit_qty > QTY_EPSILON).then(|| self.exit_notional / self.exit_qty)
+    }
+
+    /// Time held in ms, up to `now` while open
+    pub fn duration(&self, now: u64) -> u64 {
+        self.exit_time
+            .unwrap_or(now)
+            .saturating_sub(self.entry_time)
+    }
+}
+
+/// Books a fill into the open trade of its ticker, opening one when the position was flat
+/// or flipped. `before` and `after` are the ticker's position around the fill
+pub(super) fn record(trades: &mut Vec<Trade>, fill: &Fill, before: Position, after: Position) {
+    let closing = if !before.is_flat() && before.qty.signum() != fill.side.sign() {
+        fill.qty.min(before.qty.abs())
+    } else {
+        0.0
+    };
+    let opening = fill.qty - closing;
+    let closing_fee = fill.fee * closing / fill.qty.max(QTY_EPSILON);
+
+    if closing > QTY_EPSILON
+        && let Some(trade) = open_trade(trades, fill.ticker)
+    {
+        trade.exit_qty += closing;
+        trade.exit_notional += closing * fill.price.to_f32();
+        trade.gross += fill.realized;
+        trade.fees += closing_fee;
+
+        if after.is_flat() || after.qty.signum() != before.qty.signum() {
+            trade.exit_time = Some(fill.time);
+        }
+    }
+
+    if opening <= QTY_EPSILON {
+        return;
+    }
+
+    match open_trade(trades, fill.ticker) {
+        Some(trade) => {
+            trade.avg_entry = after.avg_entry;
+            trade.qty = trade.qty.max(after.qty.abs());
+            trade.fees += fill.fee - closing_fee;
+        }
+        None => trades.push(Trade {
+            ticker: fill.ticker,
+            side: fill.side,
+            entry_time: fill.time,
+            exit_time: None,
+            avg_entry: after.avg_entry,
+            qty: after.qty.abs(),
+            gross: 0.0,
+            fees: fill.fee - closing_fee,
+            mae: 0.0,
+            mfe: 0.0,
+            exit_qty: 0.0,
+            exit_notional: 0.0,
+        }),
+    }
+}
+
+/// Widens the excursions of the ticker's open trade with a print at `price`, whether they
+/// changed
+pub(super) fn track(trades: &mut [Trade], ticker: Ticker, position: Position, price: f32) -> bool {
+    let Some(trade) = open_trade(trades, ticker) else {
+        return false;
+    };
+    let unrealized = position.unrealized(price);
+
+    let (mae, mfe) = (trade.mae.min(unrealized), trade.mfe.max(unrealized));
+    let changed = (mae, mfe) != (trade.mae, trade.mfe);
+    (trade.mae, trade.mfe) = (mae, mfe);
+    changed
+}
+
+fn open_trade(trades: &mut [Trade], ticker: Ticker) -> Option<&mut Trade> {
+    trades
+        .iter_mut()
+        .rev()
+        .find(|trade| trade.ticker == ticker && trade.is_open())
+}
+
+/// Over closed trades only
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub trades: usize,
+    pub wins: usize,
+    pub net: f32,
+    pub fees: f32,
+    pub gross_profit: f32,
+    pub gross_loss: f32,
+    pub avg_duration: u64,
+}
+
+impl Stats {
+    pub fn new(trades: &[Trade]) -> Self {
+        let mut stats = Self::default();
+        let mut total_duration = 0;
+
+        for trade in trades.iter().filter(|trade| !trade.is_open()) {
+            let net = trade.net();
+
+            stats.trades += 1;
+            stats.net += net;
+            stats.fees += trade.fees;
+            total_duration += trade.duration(0);
+
+            if net > 0.0 {
+                stats.wins += 1;
+                stats.gross_profit += net;
+            } else {
+                stats.gross_loss -= net;
+            }
+        }
+
+        if stats.trades > 0 {
+            stats.avg_duration = total_duration / stats.trades as u64;
+        }
+        stats
+    }
+
+    /// Share of winners in 0..=1, `None` before any trade closed
+    pub fn win_rate(&self) -> Option<f32> {
+        (self.trades > 0).then(|| self.wins as f32 / self.trades as f32)
+    }
+
+    /// Gross profit over gross loss, `None` without a losing trade
+    pub fn profit_factor(&self) -> Option<f32> {
+        (self.gross_loss > 0.0).then(|| self.gross_profit / self.gross_loss)
+    }
+}
+
+pub fn to_csv(trades: &[Trade], now: u64) -> String {
+    let mut csv = String::from(
+        "ticker,side,entry_time,exit_time,duration_ms,qty,avg_entry,avg_exit,\
+         gross_pnl,fees,net_pnl,mae,mfe\n",
+    );
+
+    for trade in trades {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            trade.ticker,
+            trade.side,
+            trade.entry_time,
+            trade.exit_time.map(|t| t.to_string()).unwrap_or_default(),
+            trade.duration(now),
+            trade.qty,
+            trade.avg_entry,
+            trade.avg_exit().map(|p| p.to_string()).unwrap_or_default(),
+            trade.gross,
+            trade.fees,
+            trade.net(),
+            trade.mae,
+            trade.mfe,
+        );
+    }
+    csv
+}
+
+/// Writes the journal as a timestamped CSV into `dir`, returning its path
+pub fn write_csv(trades: &[Trade], dir: &Path, now: u64) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let stamp = chrono::DateTime::from_timestamp_millis(now as i64)
+        .map(|dt| dt.format("%Y-%m-%d_%H-%M-%S").to_string())
+        .unwrap_or_else(|| now.to_string());
+    let path = dir.join(format!("{stamp}_paper_journal.csv"));
+
+    std::fs::write(&path, to_csv(trades, now))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Account, Config};
+    use super::*;
+    use exchange::adapter::Exchange;
+    use exchange::depth::Depth;
+    use exchange::util::Price;
+
+    fn depth(bid: f32, ask: f32) -> Depth {
+        Depth {
+            bids: [(Price::from_f32(bid), 100.0)].into_iter().collect(),
+            asks: [(Price::from_f32(ask), 100.0)].into_iter().collect(),
+        }
+    }
+
+    fn print(time: u64, price: f32) -> exchange::Trade {
+        exchange::Trade {
+            time,
+            is_sell: false,
+            price: Price::from_f32(price),
+            qty: 1.0,
+            sub_ms_nanos: 0,
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_excursions_and_exit() {
+        let ticker = Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+        let mut account = Account::new(Config {
+            fee_bps: 0.0,
+            ..Config::default()
+        });
+
+        account.on_market_data(ticker, &depth(99.0, 100.0), &[print(0, 100.0)], 0);
+        account.place(ticker, Side::Buy, None, 1).unwrap();
+        account.on_market_data(ticker, &depth(99.0, 100.0), &[], 1);
+        assert!(account.journal()[0].is_open());
+
+        let prints = [print(2, 97.0), print(3, 104.0)];
+        account.on_market_data(ticker, &depth(99.0, 100.0), &prints, 3);
+
+        account.flatten(ticker, 4).unwrap();
+        account.on_market_data(ticker, &depth(103.0, 104.0), &[], 5);
+
+        let trade = &account.journal()[0];
+        assert_eq!(trade.exit_time, Some(5));
+        assert_eq!(trade.avg_exit(), Some(103.0));
+        assert!((trade.net() - 30.0).abs() < 1e-3);
+        assert!((trade.mae + 30.0).abs() < 1e-3);
+        assert!((trade.mfe - 40.0).abs() < 1e-3);
+
+        let stats = Stats::new(account.journal());
+        assert_eq!((stats.trades, stats.win_rate()), (1, Some(1.0)));
+        assert_eq!(stats.profit_factor(), None);
+        assert_eq!(to_csv(account.journal(), 5).lines().count(), 2);
+    }
+}
@@ -0,0 +1,156 @@
+use exchange::{Kline, Ticker, Timeframe};
+use serde::{Deserialize, Serialize};
+
+/// Closed bars the volume spike condition averages over
+pub const VOLUME_AVG_BARS: usize = 20;
+const DAY_MS: u64 = 86_400_000;
+
+/// Conditions the scanner checks on every watchlist ticker when a bar closes
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub enabled: bool,
+    pub timeframe: Timeframe,
+    /// Close-to-close move of the closed bar in percent, either direction, `None` to skip
+    pub price_change_pct: Option<f32>,
+    /// Volume of the closed bar as a multiple of the average of the bars before it, `None` to skip
+    pub volume_multiple: Option<f32>,
+    /// Closed bar made a new high of the UTC day
+    pub session_high: bool,
+}
+
+impl Settings {
+    pub const TIMEFRAME_CHOICES: [Timeframe; 4] =
+        [Timeframe::M1, Timeframe::M5, Timeframe::M15, Timeframe::H1];
+    pub const PRICE_CHANGE_CHOICES: [f32; 5] = [0.5, 1.0, 2.0, 3.0, 5.0];
+    pub const VOLUME_MULTIPLE_CHOICES: [f32; 4] = [2.0, 3.0, 5.0, 10.0];
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeframe: Timeframe::M5,
+            price_change_pct: Some(1.0),
+            volume_multiple: Some(3.0),
+            session_high: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+    /// Close-to-close change in percent
+    PriceChange(f32),
+    /// Volume as a multiple of the average
+    VolumeSpike(f32),
+    /// New high of the day
+    SessionHigh(f32),
+}
+
+impl Signal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Signal::PriceChange(_) => "Move",
+            Signal::VolumeSpike(_) => "Volume",
+            Signal::SessionHigh(_) => "Day high",
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        match self {
+            Signal::PriceChange(v) | Signal::VolumeSpike(v) | Signal::SessionHigh(v) => *v,
+        }
+    }
+}
+
+impl std::fmt::Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signal::PriceChange(pct) => write!(f, "{pct:+.2}%"),
+            Signal::VolumeSpike(multiple) => write!(f, "{multiple:.1}x"),
+            Signal::SessionHigh(price) => write!(f, "{price}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub ticker: Ticker,
+    pub signal: Signal,
+    /// Open time of the bar that triggered it
+    pub time: u64,
+}
+
+/// Open time of the last bar of `timeframe` that closed by `now_ms`
+pub fn last_closed_bar(timeframe: Timeframe, now_ms: u64) -> u64 {
+    let interval = timeframe.to_milliseconds();
+    (now_ms / interval).saturating_sub(1) * interval
+}
+
+/// Range of bars to fetch so `scan` can judge the bar opened at `bar_time`
+pub fn fetch_range(settings: &Settings, bar_time: u64) -> (u64, u64) {
+    let interval = settings.timeframe.to_milliseconds();
+    // one spare bar in case the exchange returns the range exclusive of its start
+    let lookback_start = bar_time.saturating_sub((VOLUME_AVG_BARS as u64 + 1) * interval);
+
+    let start = if settings.session_high {
+        lookback_start.min(bar_time - bar_time % DAY_MS)
+    } else {
+        lookback_start
+    };
+
+    (start, bar_time + interval - 1)
+}
+
+/// Signals of the bar opened at `bar_time`, `klines` sorted by time and holding the bars before it
+pub fn scan(settings: &Settings, klines: &[Kline], bar_time: u64) -> Vec<Signal> {
+    let Some(idx) = klines.iter().position(|k| k.time == bar_time) else {
+        return vec![];
+    };
+    let (bar, before) = (&klines[idx], &klines[..idx]);
+    let mut signals = vec![];
+
+    if let Some(threshold) = settings.price_change_pct
+        && let Some(prev) = before.last()
+        && prev.close.to_f32() > 0.0
+    {
+        let change_pct = (bar.close.to_f32() / prev.close.to_f32() - 1.0) * 100.0;
+
+        if change_pct.abs() >= threshold {
+            signals.push(Signal::PriceChange(change_pct));
+        }
+    }
+
+    if let Some(threshold) = settings.volume_multiple
+        && before.len() >= VOLUME_AVG_BARS
+    {
+        let window = &before[before.len() - VOLUME_AVG_BARS..];
+        let avg = window.iter().map(total_volume).sum::<f32>() / VOLUME_AVG_BARS as f32;
+
+        if avg > 0.0 && total_volume(bar) / avg >= threshold {
+            signals.push(Signal::VolumeSpike(total_volume(bar) / avg));
+        }
+    }
+
+    if settings.session_high {
+        let day_start = bar_time - bar_time % DAY_MS;
+        let prior_high = before
+            .iter()
+            .filter(|k| k.time >= day_start)
+            .map(|k| k.high)
+            .max();
+
+        if prior_high.is_some_and(|high| bar.high > high) {
+            signals.push(Signal::SessionHigh(bar.high.to_f32()));
+        }
+    }
+
+    signals
+}
+
+fn total_volume(kline: &Kline) -> f32 {
+    let (buy, sell) = kline.volume;
+    // bybit workaround: total volume is carried in `sell`
+    if buy == -1.0 { sell } else { buy + sell }
+}
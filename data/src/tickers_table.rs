@@ -1,8 +1,12 @@
+use crate::layout::pane::ContentKind;
 use exchange::{
     Ticker, TickerStats,
     adapter::{Exchange, ExchangeInclusive, MarketKind},
 };
+
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Settings {
@@ -11,6 +15,11 @@ pub struct Settings {
     pub selected_sort_option: SortOptions,
     pub selected_exchanges: Vec<ExchangeInclusive>,
     pub selected_markets: Vec<MarketKind>,
+    /// Show USDT/USDC/FDUSD quotes of the same base as one row
+    #[serde(default)]
+    pub merge_stablecoin_quotes: bool,
+    #[serde(default)]
+    pub mover_alert: MoverAlert,
 }
 
 impl Default for Settings {
@@ -21,7 +30,127 @@ impl Default for Settings {
             selected_sort_option: SortOptions::VolumeDesc,
             selected_exchanges: ExchangeInclusive::ALL.to_vec(),
             selected_markets: MarketKind::ALL.into_iter().collect(),
+            merge_stablecoin_quotes: false,
+            mover_alert: MoverAlert::default(),
+        }
+    }
+}
+
+/// Pane opened for a favorited ticker once it moves sharply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MoverPane {
+    Candlestick,
+    Footprint,
+    Heatmap,
+}
+
+impl MoverPane {
+    pub const ALL: [MoverPane; 3] = [
+        MoverPane::Candlestick,
+        MoverPane::Footprint,
+        MoverPane::Heatmap,
+    ];
+
+    pub fn content_kind(self) -> ContentKind {
+        match self {
+            MoverPane::Candlestick => ContentKind::CandlestickChart,
+            MoverPane::Footprint => ContentKind::FootprintChart,
+            MoverPane::Heatmap => ContentKind::HeatmapChart,
+        }
+    }
+}
+
+impl std::fmt::Display for MoverPane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.content_kind())
+    }
+}
+
+/// Opens or focuses a pane of a favorited ticker when it moves more than
+/// `threshold_pct` within `window_mins`, turning the favorites into a scanner
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MoverAlert {
+    pub enabled: bool,
+    pub threshold_pct: f32,
+    pub window_mins: u16,
+    pub pane: MoverPane,
+}
+
+impl Default for MoverAlert {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_pct: 3.0,
+            window_mins: 5,
+            pane: MoverPane::Candlestick,
+        }
+    }
+}
+
+impl MoverAlert {
+    pub const THRESHOLD_CHOICES: [f32; 6] = [1.0, 2.0, 3.0, 5.0, 10.0, 20.0];
+    pub const WINDOW_CHOICES: [u16; 5] = [1, 5, 15, 30, 60];
+
+    fn window_ms(&self) -> u64 {
+        u64::from(self.window_mins.max(1)) * 60_000
+    }
+}
+
+/// Recent mark prices of favorited tickers, sampled on each stats refresh
+#[derive(Debug, Default)]
+pub struct MoveTracker {
+    prices: FxHashMap<Ticker, VecDeque<(u64, f32)>>,
+    /// Last firing per ticker, so a sustained move only fires once per window
+    fired_at: FxHashMap<Ticker, u64>,
+}
+
+impl MoveTracker {
+    /// Records a price sample, returning the % move over the window when it crosses the threshold
+    pub fn record(
+        &mut self,
+        ticker: Ticker,
+        price: f32,
+        now_ms: u64,
+        alert: &MoverAlert,
+    ) -> Option<f32> {
+        let window_ms = alert.window_ms();
+
+        let history = self.prices.entry(ticker).or_default();
+        history.push_back((now_ms, price));
+        while history
+            .front()
+            .is_some_and(|(time, _)| now_ms.saturating_sub(*time) > window_ms)
+        {
+            history.pop_front();
+        }
+
+        let &(_, oldest) = history.front()?;
+        if oldest <= 0.0 {
+            return None;
         }
+
+        let change_pct = (price - oldest) / oldest * 100.0;
+        if change_pct.abs() < alert.threshold_pct {
+            return None;
+        }
+
+        if self
+            .fired_at
+            .get(&ticker)
+            .is_some_and(|fired| now_ms.saturating_sub(*fired) < window_ms)
+        {
+            return None;
+        }
+
+        self.fired_at.insert(ticker, now_ms);
+        Some(change_pct)
+    }
+
+    /// Drops the samples of tickers that are no longer watched
+    pub fn retain(&mut self, is_watched: impl Fn(&Ticker) -> bool) {
+        self.prices.retain(|ticker, _| is_watched(ticker));
+        self.fired_at.retain(|ticker, _| is_watched(ticker));
     }
 }
 
@@ -93,6 +222,29 @@ pub fn compute_display_data(
     }
 }
 
+/// Combined stats of stablecoin quotes of one market: summed volume,
+/// volume-weighted price and daily change
+pub fn merge_stablecoin_stats(stats: &[TickerStats]) -> Option<TickerStats> {
+    let mark_price = exchange::composite::volume_weighted_price(
+        &stats
+            .iter()
+            .map(|s| (s.mark_price, s.daily_volume))
+            .collect::<Vec<_>>(),
+    )?;
+    let daily_price_chg = exchange::composite::volume_weighted_price(
+        &stats
+            .iter()
+            .map(|s| (s.daily_price_chg, s.daily_volume))
+            .collect::<Vec<_>>(),
+    )?;
+
+    Some(TickerStats {
+        mark_price,
+        daily_price_chg,
+        daily_volume: stats.iter().map(|s| s.daily_volume).sum(),
+    })
+}
+
 fn split_price_changes(
     previous_price: f32,
     current_price: f32,
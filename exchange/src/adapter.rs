@@ -1,7 +1,7 @@
 use super::{Ticker, Timeframe};
 use crate::{
-    Kline, OpenInterest, Price, PushFrequency, TickMultiplier, TickerInfo, TickerStats, Trade,
-    depth::Depth,
+    FundingInfo, Kline, Liquidation, OpenInterest, Price, PushFrequency, TickMultiplier,
+    TickerInfo, TickerStats, Trade, depth::Depth, schedule::MarketSchedule,
 };
 
 use enum_map::{Enum, EnumMap};
@@ -11,9 +11,11 @@ use std::{collections::HashMap, str::FromStr, sync::Arc, time::Instant};
 
 pub mod binance;
 pub mod bybit;
+pub mod deribit;
 pub mod forex;
 pub mod hyperliquid;
 pub mod okex;
+pub mod simulated;
 
 /// Persisted stream resolution to avoid loop retries
 pub const RESOLVE_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
@@ -94,6 +96,17 @@ impl ResolvedStream {
         }
     }
 
+    /// Ticker of the first stream, whether resolved yet or not
+    pub fn first_ticker(&self) -> Option<Ticker> {
+        match self {
+            ResolvedStream::Waiting { streams, .. } => streams.first().map(|s| match s {
+                PersistStreamKind::Kline(k) => k.ticker,
+                PersistStreamKind::DepthAndTrades(d) => d.ticker,
+            }),
+            ResolvedStream::Ready(streams) => streams.first().map(|s| s.ticker_info().ticker),
+        }
+    }
+
     pub fn into_waiting(self) -> Vec<PersistStreamKind> {
         match self {
             ResolvedStream::Waiting { streams, .. } => streams,
@@ -132,6 +145,8 @@ pub enum AdapterError {
     WebsocketError(String),
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
+    #[error("Offline or demo mode, blocked request to {0}")]
+    Offline(String),
 }
 
 impl AdapterError {
@@ -153,6 +168,10 @@ impl AdapterError {
                 log::error!("Adapter websocket error: {err}");
                 "Realtime connection error. Trying to reconnect..."
             }
+            AdapterError::Offline(host) => {
+                log::warn!("Offline or demo mode blocked a request to {host}");
+                "Offline or demo mode is on, the request was blocked."
+            }
         }
     }
 }
@@ -430,15 +449,19 @@ pub enum ExchangeInclusive {
     Hyperliquid,
     Okex,
     Forex,
+    Deribit,
+    Simulated,
 }
 
 impl ExchangeInclusive {
-    pub const ALL: [ExchangeInclusive; 5] = [
+    pub const ALL: [ExchangeInclusive; 7] = [
         ExchangeInclusive::Bybit,
         ExchangeInclusive::Binance,
         ExchangeInclusive::Hyperliquid,
         ExchangeInclusive::Okex,
         ExchangeInclusive::Forex,
+        ExchangeInclusive::Deribit,
+        ExchangeInclusive::Simulated,
     ];
 
     pub fn of(ex: Exchange) -> Self {
@@ -450,10 +473,60 @@ impl ExchangeInclusive {
             Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => Self::Hyperliquid,
             Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => Self::Okex,
             Exchange::Forex => Self::Forex,
+            Exchange::DeribitLinear | Exchange::DeribitInverse => Self::Deribit,
+            Exchange::Simulated => Self::Simulated,
+        }
+    }
+
+    /// Whether historical trades can be fetched for footprint charts
+    pub fn supports_trade_fetch(&self) -> bool {
+        matches!(
+            self,
+            ExchangeInclusive::Binance | ExchangeInclusive::Bybit | ExchangeInclusive::Okex
+        )
+    }
+
+    /// What enabling historical trade fetching downloads, shown before asking for consent
+    pub fn trade_fetch_usage(&self) -> &'static str {
+        match self {
+            ExchangeInclusive::Binance => {
+                "Downloads daily trade archives from data.binance.vision and pages through the \
+                 aggTrades REST endpoint for the rest. A day of a busy pair can take hundreds of \
+                 MB, archives are kept in the data folder."
+            }
+            ExchangeInclusive::Bybit => {
+                "Downloads daily trade archives from public.bybit.com, the current day only \
+                 reaches back as far as the recent trades endpoint does (1000 trades, 60 on \
+                 spot). Archives are kept in the data folder."
+            }
+            ExchangeInclusive::Okex => {
+                "Pages backwards through the history-trades REST endpoint, 100 trades per \
+                 request within the rate limit, so long gaps of busy pairs fill in slowly. \
+                 Nothing is kept on disk."
+            }
+            _ => "Historical trades aren't available for this exchange.",
         }
     }
 }
 
+impl std::fmt::Display for ExchangeInclusive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExchangeInclusive::Bybit => "Bybit",
+                ExchangeInclusive::Binance => "Binance",
+                ExchangeInclusive::Hyperliquid => "Hyperliquid",
+                ExchangeInclusive::Okex => "Okex",
+                ExchangeInclusive::Forex => "Forex",
+                ExchangeInclusive::Deribit => "Deribit",
+                ExchangeInclusive::Simulated => "Simulated",
+            }
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, Enum)]
 pub enum Exchange {
     BinanceLinear,
@@ -468,6 +541,9 @@ pub enum Exchange {
     OkexInverse,
     OkexSpot,
     Forex,
+    DeribitLinear,
+    DeribitInverse,
+    Simulated,
 }
 
 impl std::fmt::Display for Exchange {
@@ -488,6 +564,9 @@ impl std::fmt::Display for Exchange {
                 Exchange::OkexInverse => "Okex Inverse",
                 Exchange::OkexSpot => "Okex Spot",
                 Exchange::Forex => "Forex",
+                Exchange::DeribitLinear => "Deribit Linear",
+                Exchange::DeribitInverse => "Deribit Inverse",
+                Exchange::Simulated => "Simulated",
             }
         )
     }
@@ -510,13 +589,16 @@ impl FromStr for Exchange {
             "Okex Inverse" => Ok(Exchange::OkexInverse),
             "Okex Spot" => Ok(Exchange::OkexSpot),
             "Forex" => Ok(Exchange::Forex),
+            "Deribit Linear" => Ok(Exchange::DeribitLinear),
+            "Deribit Inverse" => Ok(Exchange::DeribitInverse),
+            "Simulated" => Ok(Exchange::Simulated),
             _ => Err(format!("Invalid exchange: {}", s)),
         }
     }
 }
 
 impl Exchange {
-    pub const ALL: [Exchange; 12] = [
+    pub const ALL: [Exchange; 15] = [
         Exchange::BinanceLinear,
         Exchange::BinanceInverse,
         Exchange::BinanceSpot,
@@ -529,6 +611,9 @@ impl Exchange {
         Exchange::OkexInverse,
         Exchange::OkexSpot,
         Exchange::Forex,
+        Exchange::DeribitLinear,
+        Exchange::DeribitInverse,
+        Exchange::Simulated,
     ];
 
     pub fn market_type(&self) -> MarketKind {
@@ -537,14 +622,17 @@ impl Exchange {
             | Exchange::BybitLinear
             | Exchange::HyperliquidLinear
             | Exchange::OkexLinear
+            | Exchange::DeribitLinear
             | Exchange::Forex => MarketKind::LinearPerps,
-            Exchange::BinanceInverse | Exchange::BybitInverse | Exchange::OkexInverse => {
-                MarketKind::InversePerps
-            }
+            Exchange::BinanceInverse
+            | Exchange::BybitInverse
+            | Exchange::OkexInverse
+            | Exchange::DeribitInverse => MarketKind::InversePerps,
             Exchange::BinanceSpot
             | Exchange::BybitSpot
             | Exchange::HyperliquidSpot
-            | Exchange::OkexSpot => MarketKind::Spot,
+            | Exchange::OkexSpot
+            | Exchange::Simulated => MarketKind::Spot,
         }
     }
 
@@ -597,9 +685,24 @@ impl Exchange {
                 | Exchange::HyperliquidLinear
                 | Exchange::OkexLinear
                 | Exchange::OkexInverse
+                | Exchange::DeribitLinear
+                | Exchange::DeribitInverse
         )
     }
 
+    /// Whether the venue is listed, in demo mode only the simulated one is
+    pub fn is_active(&self) -> bool {
+        (*self == Exchange::Simulated) == crate::network::is_demo()
+    }
+
+    /// Trading hours of the venue, used to tell scheduled downtime apart from outages
+    pub fn schedule(&self) -> MarketSchedule {
+        match self {
+            Exchange::Forex => MarketSchedule::FOREX,
+            _ => MarketSchedule::AlwaysOpen,
+        }
+    }
+
     pub fn stream_ticksize(
         &self,
         multiplier: Option<TickMultiplier>,
@@ -617,8 +720,25 @@ impl Exchange {
 pub enum Event {
     Connected(Exchange),
     Disconnected(Exchange, String),
+    /// Connecting failed, the stream retries after `next_in`
+    Reconnecting {
+        exchange: Exchange,
+        attempt: u32,
+        next_in: std::time::Duration,
+    },
+    /// Frames a stream received since its last report, and its latest ping round trip
+    Telemetry {
+        exchange: Exchange,
+        messages: u32,
+        latency: Option<std::time::Duration>,
+    },
+    /// Scheduled downtime, carries the unix timestamp (ms) the venue reopens at
+    MarketClosed(Exchange, u64),
     DepthReceived(StreamKind, u64, Arc<Depth>, Box<[Trade]>),
+    /// Trades of a depth update dropped by [`crate::feed::set_depth_rates`]
+    TradesReceived(StreamKind, Box<[Trade]>),
     KlineReceived(StreamKind, Kline),
+    LiquidationReceived(StreamKind, Liquidation),
 }
 
 #[derive(Debug, Clone, Hash)]
@@ -665,6 +785,10 @@ pub async fn fetch_ticker_info(
             okex::fetch_ticksize(market_type).await
         }
         Exchange::Forex => forex::fetch_ticksize().await,
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            deribit::fetch_ticksize(market_type).await
+        }
+        Exchange::Simulated => simulated::fetch_ticksize().await,
     }
 }
 
@@ -687,6 +811,10 @@ pub async fn fetch_ticker_prices(
             okex::fetch_ticker_prices(market_type).await
         }
         Exchange::Forex => forex::fetch_ticker_prices().await,
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            deribit::fetch_ticker_prices(market_type).await
+        }
+        Exchange::Simulated => simulated::fetch_ticker_prices().await,
     }
 }
 
@@ -709,6 +837,33 @@ pub async fn fetch_klines(
             okex::fetch_klines(ticker_info, timeframe, range).await
         }
         Exchange::Forex => forex::fetch_klines(ticker_info, timeframe, range).await,
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            deribit::fetch_klines(ticker_info, timeframe, range).await
+        }
+        Exchange::Simulated => simulated::fetch_klines(ticker_info, timeframe, range).await,
+    }
+}
+
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    match ticker.exchange {
+        Exchange::BinanceLinear | Exchange::BinanceInverse => {
+            binance::fetch_funding_info(ticker).await
+        }
+        Exchange::BybitLinear | Exchange::BybitInverse => bybit::fetch_funding_info(ticker).await,
+        Exchange::OkexLinear | Exchange::OkexInverse => okex::fetch_funding_info(ticker).await,
+        Exchange::HyperliquidLinear => hyperliquid::fetch_funding_info(ticker).await,
+        Exchange::Forex => forex::fetch_funding_info(ticker).await,
+        Exchange::BinanceSpot
+        | Exchange::BybitSpot
+        | Exchange::OkexSpot
+        | Exchange::HyperliquidSpot
+        | Exchange::Simulated => Err(AdapterError::InvalidRequest(
+            "Funding is only available for perpetuals".to_string(),
+        )),
+        // accrues continuously, there's no settlement to count down to
+        Exchange::DeribitLinear | Exchange::DeribitInverse => Err(AdapterError::InvalidRequest(
+            "Deribit funding isn't settled periodically".to_string(),
+        )),
     }
 }
 
@@ -1,11 +1,12 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit, StreamKind,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        Exchange, FundingInfo, Kline, Liquidation, MarketKind, OpenInterest, Price, PushFrequency,
+        SizeUnit, StreamKind, Ticker, TickerInfo, TickerStats, TimeUnit, Timeframe, Trade,
         adapter::StreamTicksize,
-        connect::{State, connect_ws},
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
         de_string_to_f32,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
         is_symbol_supported,
         limiter::{self, RateLimiter},
         str_f32_parse, volume_size_unit,
@@ -56,6 +57,8 @@ impl BinanceLimiter {
 }
 
 impl RateLimiter for BinanceLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Binance;
+
     fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
         let (wait_time, _reason) = self.bucket.prepare_request(weight);
         wait_time
@@ -76,6 +79,10 @@ impl RateLimiter for BinanceLimiter {
         let status = response.status();
         status == 429 || status == 418
     }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
 }
 
 fn exchange_from_market_type(market: MarketKind) -> Exchange {
@@ -102,6 +109,14 @@ fn ws_domain_from_market_type(market: MarketKind) -> &'static str {
     }
 }
 
+/// Spot streams are requested in microseconds, futures streams only offer milliseconds
+fn stream_time_unit(market: MarketKind) -> TimeUnit {
+    match market {
+        MarketKind::Spot => TimeUnit::Micros,
+        MarketKind::LinearPerps | MarketKind::InversePerps => TimeUnit::Millis,
+    }
+}
+
 #[derive(Deserialize, Clone)]
 pub struct FetchedPerpDepth {
     #[serde(rename = "lastUpdateId")]
@@ -163,6 +178,24 @@ struct SonicTrade {
     #[serde(rename = "m")]
     is_sell: bool,
 }
+#[derive(Deserialize, Debug)]
+struct SonicForceOrder {
+    #[serde(rename = "o")]
+    order: SonicForceOrderData,
+}
+
+#[derive(Deserialize, Debug)]
+struct SonicForceOrderData {
+    #[serde(rename = "T")]
+    time: u64,
+    #[serde(rename = "S")]
+    side: String,
+    #[serde(rename = "ap", deserialize_with = "de_string_to_f32")]
+    avg_price: f32,
+    #[serde(rename = "z", deserialize_with = "de_string_to_f32")]
+    filled_qty: f32,
+}
+
 enum SonicDepth {
     Spot(SpotDepth),
     Perp(PerpDepth),
@@ -202,12 +235,14 @@ enum StreamData {
     Trade(SonicTrade),
     Depth(SonicDepth),
     Kline(Ticker, SonicKline),
+    ForceOrder(SonicForceOrderData),
 }
 
 enum StreamWrapper {
     Trade,
     Depth,
     Kline,
+    ForceOrder,
 }
 
 impl StreamWrapper {
@@ -219,6 +254,7 @@ impl StreamWrapper {
                 s if s.starts_with("de") => Some(StreamWrapper::Depth),
                 s if s.starts_with("ag") => Some(StreamWrapper::Trade),
                 s if s.starts_with("kl") => Some(StreamWrapper::Kline),
+                s if s.starts_with("fo") => Some(StreamWrapper::ForceOrder),
                 _ => None,
             })
     }
@@ -247,8 +283,9 @@ fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError>
                 }
                 Some(StreamWrapper::Depth) => match market {
                     MarketKind::Spot => {
-                        let depth: SpotDepth = sonic_rs::from_str(&v.as_raw_faststr())
+                        let mut depth: SpotDepth = sonic_rs::from_str(&v.as_raw_faststr())
                             .map_err(|e| AdapterError::ParseError(e.to_string()))?;
+                        depth.time = stream_time_unit(market).to_millis(depth.time);
 
                         return Ok(StreamData::Depth(SonicDepth::Spot(depth)));
                     }
@@ -259,6 +296,12 @@ fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError>
                         return Ok(StreamData::Depth(SonicDepth::Perp(depth)));
                     }
                 },
+                Some(StreamWrapper::ForceOrder) => {
+                    let force_order: SonicForceOrder = sonic_rs::from_str(&v.as_raw_faststr())
+                        .map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+                    return Ok(StreamData::ForceOrder(force_order.order));
+                }
                 Some(StreamWrapper::Kline) => {
                     let kline_wrap: SonicKlineWrap = sonic_rs::from_str(&v.as_raw_faststr())
                         .map_err(|e| AdapterError::ParseError(e.to_string()))?;
@@ -284,17 +327,13 @@ fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError>
 
 async fn try_resync(
     exchange: Exchange,
-    ticker_info: TickerInfo,
+    ticker: Ticker,
     contract_size: Option<f32>,
-    orderbook: &mut LocalDepthCache,
+    feed: &mut MarketFeed,
     state: &mut State,
     output: &mut mpsc::Sender<Event>,
-    already_fetching: &mut bool,
 ) {
-    let ticker = ticker_info.ticker;
-
     let (tx, rx) = tokio::sync::oneshot::channel();
-    *already_fetching = true;
 
     tokio::spawn(async move {
         let result = fetch_depth(&ticker, contract_size).await;
@@ -302,9 +341,7 @@ async fn try_resync(
     });
 
     match rx.await {
-        Ok(Ok(depth)) => {
-            orderbook.update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
-        }
+        Ok(Ok(depth)) => feed.snapshot(depth),
         Ok(Err(e)) => {
             let _ = output
                 .send(Event::Disconnected(
@@ -325,29 +362,160 @@ async fn try_resync(
                 .expect("Trying to send disconnect event...");
         }
     }
-    *already_fetching = false;
 }
 
-#[allow(unused_assignments)]
+/// Trades, liquidations and the local book of one symbol. The book starts from a REST
+/// snapshot, a first diff past it asks for a resync
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    market: MarketKind,
+    contract_size: Option<f32>,
+    size_in_quote_ccy: bool,
+    orderbook: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+    /// Final update id of the last applied diff, 0 right after a snapshot
+    prev_id: u64,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        let market = ticker_info.market_type();
+
+        Self {
+            ticker_info,
+            push_freq,
+            market,
+            contract_size: get_contract_size(&ticker_info.ticker, market),
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            orderbook: LocalDepthCache::default(),
+            trades_buffer: Vec::new(),
+            prev_id: 0,
+        }
+    }
+
+    fn stream_kind(&self) -> StreamKind {
+        StreamKind::DepthAndTrades {
+            ticker_info: self.ticker_info,
+            depth_aggr: StreamTicksize::Client,
+            push_freq: self.push_freq,
+        }
+    }
+
+    fn on_depth(&mut self, depth: SonicDepth) -> Option<Step> {
+        let last_update_id = self.orderbook.last_update_id;
+        let (first_id, final_id, time, expected_id) = match &depth {
+            SonicDepth::Perp(de) => (de.first_id, de.final_id, de.time, de.prev_final_id),
+            SonicDepth::Spot(de) => (
+                de.first_id,
+                de.final_id,
+                de.time,
+                de.first_id.saturating_sub(1),
+            ),
+        };
+
+        if final_id <= last_update_id || last_update_id == 0 {
+            return None;
+        }
+
+        if self.prev_id == 0 && first_id > last_update_id + 1 {
+            log::warn!("Out of sync at first event. Trying to resync...\n");
+            return Some(Step::Resync);
+        }
+
+        if self.prev_id != 0 && self.prev_id != expected_id {
+            return Some(Step::Reconnect(format!(
+                "Out of sync. Expected update_id: {expected_id}, got: {}",
+                self.prev_id
+            )));
+        }
+
+        self.orderbook.update(
+            DepthUpdate::Diff(new_depth_cache(&depth, self.contract_size)),
+            self.ticker_info.min_ticksize,
+        );
+        self.prev_id = final_id;
+
+        Some(Step::Emit(Event::DepthReceived(
+            self.stream_kind(),
+            time,
+            self.orderbook.depth.clone(),
+            std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+        )))
+    }
+}
+
+impl Feed for MarketFeed {
+    fn snapshot(&mut self, depth: DepthPayload) {
+        self.orderbook
+            .update(DepthUpdate::Snapshot(depth), self.ticker_info.min_ticksize);
+        self.prev_id = 0;
+    }
+
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(data) = feed_de(payload, self.market) else {
+            return vec![];
+        };
+        let min_ticksize = self.ticker_info.min_ticksize;
+
+        match data {
+            StreamData::Trade(de_trade) => {
+                let unit = stream_time_unit(self.market);
+
+                self.trades_buffer.push(Trade {
+                    time: unit.to_millis(de_trade.time),
+                    is_sell: de_trade.is_sell,
+                    price: Price::from_f32(de_trade.price).round_to_min_tick(min_ticksize),
+                    qty: calc_qty(
+                        de_trade.qty,
+                        de_trade.price,
+                        self.contract_size,
+                        self.size_in_quote_ccy,
+                    ),
+                    sub_ms_nanos: unit.sub_milli_nanos(de_trade.time),
+                });
+                vec![]
+            }
+            StreamData::ForceOrder(order) => {
+                let liquidation = Liquidation {
+                    time: order.time,
+                    is_sell: order.side == "SELL",
+                    price: Price::from_f32(order.avg_price).round_to_min_tick(min_ticksize),
+                    qty: calc_qty(
+                        order.filled_qty,
+                        order.avg_price,
+                        self.contract_size,
+                        self.size_in_quote_ccy,
+                    ),
+                };
+
+                vec![Step::Emit(Event::LiquidationReceived(
+                    self.stream_kind(),
+                    liquidation,
+                ))]
+            }
+            StreamData::Depth(depth) => self.on_depth(depth).into_iter().collect(),
+            StreamData::Kline(..) => vec![],
+        }
+    }
+}
+
 pub fn connect_market_stream(
     ticker_info: TickerInfo,
     push_freq: PushFrequency,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let ticker = ticker_info.ticker;
 
         let (symbol_str, market) = ticker.to_full_symbol_and_type();
         let exchange = exchange_from_market_type(market);
 
-        let mut orderbook: LocalDepthCache = LocalDepthCache::default();
-        let mut trades_buffer: Vec<Trade> = Vec::new();
-        let mut already_fetching: bool = false;
-        let mut prev_id: u64 = 0;
-
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
         let contract_size = get_contract_size(&ticker, market);
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
 
         loop {
             match &mut state {
@@ -356,8 +524,14 @@ pub fn connect_market_stream(
                     let stream_2 = format!("{}@depth@100ms", symbol_str.to_lowercase());
 
                     let domain = ws_domain_from_market_type(market);
-                    let streams = format!("{stream_1}/{stream_2}");
-                    let url = format!("wss://{domain}/stream?streams={streams}");
+                    let mut streams = format!("{stream_1}/{stream_2}");
+                    if market != MarketKind::Spot {
+                        streams.push_str(&format!("/{}@forceOrder", symbol_str.to_lowercase()));
+                    }
+                    let mut url = format!("wss://{domain}/stream?streams={streams}");
+                    if stream_time_unit(market) == TimeUnit::Micros {
+                        url.push_str("&timeUnit=MICROSECOND");
+                    }
 
                     if let Ok(websocket) = connect_ws(domain, &url).await {
                         let (tx, rx) = tokio::sync::oneshot::channel();
@@ -368,11 +542,10 @@ pub fn connect_market_stream(
                         });
                         match rx.await {
                             Ok(Ok(depth)) => {
-                                orderbook
-                                    .update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
-                                prev_id = 0;
+                                feed.snapshot(depth);
 
                                 state = State::Connected(websocket);
+                                backoff.reset();
 
                                 let _ = output.send(Event::Connected(exchange)).await;
                             }
@@ -383,6 +556,7 @@ pub fn connect_market_stream(
                                         format!("Depth fetch failed: {e}"),
                                     ))
                                     .await;
+                                backoff.wait(exchange, &mut output).await;
                             }
                             Err(e) => {
                                 let _ = output
@@ -391,186 +565,45 @@ pub fn connect_market_stream(
                                         format!("Channel error: {e}"),
                                     ))
                                     .await;
+                                backoff.wait(exchange, &mut output).await;
                             }
                         }
                     } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
                         let _ = output
                             .send(Event::Disconnected(
                                 exchange,
                                 "Failed to connect to websocket".to_string(),
                             ))
                             .await;
+                        backoff.wait(exchange, &mut output).await;
                     }
                 }
                 State::Connected(ws) => {
-                    match ws.read_frame().await {
+                    match telemetry.read_frame(exchange, ws, &mut output).await {
                         Ok(msg) => match msg.opcode {
                             OpCode::Text => {
-                                if let Ok(data) = feed_de(&msg.payload[..], market) {
-                                    match data {
-                                        StreamData::Trade(de_trade) => {
-                                            let price = Price::from_f32(de_trade.price)
-                                                .round_to_min_tick(ticker_info.min_ticksize);
-                                            let qty = contract_size.map_or(
-                                                if size_in_quote_ccy {
-                                                    (de_trade.qty * de_trade.price).round()
-                                                } else {
-                                                    de_trade.qty
-                                                },
-                                                |size| de_trade.qty * size,
-                                            );
-
-                                            let trade = Trade {
-                                                time: de_trade.time,
-                                                is_sell: de_trade.is_sell,
-                                                price,
-                                                qty,
-                                            };
-
-                                            trades_buffer.push(trade);
+                                for step in feed.frame(&msg.payload[..]) {
+                                    match step {
+                                        Step::Emit(event) => {
+                                            feed::emit(event, &mut output).await;
+                                        }
+                                        Step::Resync => {
+                                            try_resync(
+                                                exchange,
+                                                ticker,
+                                                contract_size,
+                                                &mut feed,
+                                                &mut state,
+                                                &mut output,
+                                            )
+                                            .await;
                                         }
-                                        StreamData::Depth(depth_type) => {
-                                            if already_fetching {
-                                                log::warn!("Already fetching...\n");
-                                                continue;
-                                            }
-
-                                            let last_update_id = orderbook.last_update_id;
-
-                                            match depth_type {
-                                                SonicDepth::Perp(ref de_depth) => {
-                                                    if (de_depth.final_id <= last_update_id)
-                                                        || last_update_id == 0
-                                                    {
-                                                        continue;
-                                                    }
-
-                                                    if prev_id == 0
-                                                        && (de_depth.first_id > last_update_id + 1)
-                                                        || (last_update_id + 1 > de_depth.final_id)
-                                                    {
-                                                        log::warn!(
-                                                            "Out of sync at first event. Trying to resync...\n"
-                                                        );
-
-                                                        try_resync(
-                                                            exchange,
-                                                            ticker_info,
-                                                            contract_size,
-                                                            &mut orderbook,
-                                                            &mut state,
-                                                            &mut output,
-                                                            &mut already_fetching,
-                                                        )
-                                                        .await;
-                                                    }
-
-                                                    if (prev_id == 0)
-                                                        || (prev_id == de_depth.prev_final_id)
-                                                    {
-                                                        orderbook.update(
-                                                            DepthUpdate::Diff(new_depth_cache(
-                                                                &depth_type,
-                                                                contract_size,
-                                                            )),
-                                                            ticker_info.min_ticksize,
-                                                        );
-
-                                                        let _ = output
-                                                            .send(Event::DepthReceived(
-                                                                StreamKind::DepthAndTrades {
-                                                                    ticker_info,
-                                                                    depth_aggr:
-                                                                        StreamTicksize::Client,
-                                                                    push_freq,
-                                                                },
-                                                                de_depth.time,
-                                                                orderbook.depth.clone(),
-                                                                std::mem::take(&mut trades_buffer)
-                                                                    .into_boxed_slice(),
-                                                            ))
-                                                            .await;
-
-                                                        prev_id = de_depth.final_id;
-                                                    } else {
-                                                        state = State::Disconnected;
-                                                        let _ = output.send(
-                                                                Event::Disconnected(
-                                                                    exchange,
-                                                                    format!("Out of sync. Expected update_id: {}, got: {}", de_depth.prev_final_id, prev_id)
-                                                                )
-                                                            ).await;
-                                                    }
-                                                }
-                                                SonicDepth::Spot(ref de_depth) => {
-                                                    if (de_depth.final_id <= last_update_id)
-                                                        || last_update_id == 0
-                                                    {
-                                                        continue;
-                                                    }
-
-                                                    if prev_id == 0
-                                                        && (de_depth.first_id > last_update_id + 1)
-                                                        || (last_update_id + 1 > de_depth.final_id)
-                                                    {
-                                                        log::warn!(
-                                                            "Out of sync at first event. Trying to resync...\n"
-                                                        );
-
-                                                        try_resync(
-                                                            exchange,
-                                                            ticker_info,
-                                                            contract_size,
-                                                            &mut orderbook,
-                                                            &mut state,
-                                                            &mut output,
-                                                            &mut already_fetching,
-                                                        )
-                                                        .await;
-                                                    }
-
-                                                    if (prev_id == 0)
-                                                        || (prev_id == de_depth.first_id - 1)
-                                                    {
-                                                        orderbook.update(
-                                                            DepthUpdate::Diff(new_depth_cache(
-                                                                &depth_type,
-                                                                contract_size,
-                                                            )),
-                                                            ticker_info.min_ticksize,
-                                                        );
-
-                                                        let _ = output
-                                                            .send(Event::DepthReceived(
-                                                                StreamKind::DepthAndTrades {
-                                                                    ticker_info,
-                                                                    depth_aggr:
-                                                                        StreamTicksize::Client,
-                                                                    push_freq,
-                                                                },
-                                                                de_depth.time,
-                                                                orderbook.depth.clone(),
-                                                                std::mem::take(&mut trades_buffer)
-                                                                    .into_boxed_slice(),
-                                                            ))
-                                                            .await;
-
-                                                        prev_id = de_depth.final_id;
-                                                    } else {
-                                                        state = State::Disconnected;
-                                                        let _ = output.send(
-                                                                Event::Disconnected(
-                                                                    exchange,
-                                                                    format!("Out of sync. Expected update_id: {}, got: {}", de_depth.final_id, prev_id)
-                                                                )
-                                                            ).await;
-                                                    }
-                                                }
-                                            }
+                                        Step::Reconnect(reason) => {
+                                            state = State::Disconnected;
+                                            let _ = output
+                                                .send(Event::Disconnected(exchange, reason))
+                                                .await;
                                         }
-                                        _ => {}
                                     }
                                 }
                             }
@@ -601,107 +634,128 @@ pub fn connect_market_stream(
     })
 }
 
+/// Klines of a set of symbols and timeframes on one market
+pub struct KlineFeed {
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: Vec<(TickerInfo, Timeframe)>, market: MarketKind) -> Self {
+        Self {
+            streams,
+            market,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(StreamData::Kline(ticker, de_kline)) = feed_de(payload, self.market) else {
+            return vec![];
+        };
+
+        let Some(&(ticker_info, timeframe)) = self
+            .streams
+            .iter()
+            .find(|(info, tf)| info.ticker == ticker && tf.to_string() == de_kline.interval)
+        else {
+            log::error!("Ticker info not found for ticker: {}", ticker);
+            return vec![];
+        };
+
+        let (buy_volume, sell_volume) = {
+            let buy_volume = de_kline.taker_buy_base_asset_volume;
+            let sell_volume = de_kline.volume - buy_volume;
+
+            if let Some(c_size) = get_contract_size(&ticker, self.market) {
+                (buy_volume * c_size, sell_volume * c_size)
+            } else if self.size_in_quote_ccy {
+                (
+                    (buy_volume * de_kline.close).round(),
+                    (sell_volume * de_kline.close).round(),
+                )
+            } else {
+                (buy_volume, sell_volume)
+            }
+        };
+
+        let kline = Kline::new(
+            de_kline.time,
+            de_kline.open,
+            de_kline.high,
+            de_kline.low,
+            de_kline.close,
+            (buy_volume, sell_volume),
+            ticker_info.min_ticksize,
+        );
+
+        vec![Step::Emit(Event::KlineReceived(
+            StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            },
+            kline,
+        ))]
+    }
+}
+
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
     market: MarketKind,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
         let exchange = exchange_from_market_type(market);
 
-        let ticker_info_map = streams
+        let stream_str = streams
             .iter()
-            .map(|(ticker_info, _)| (ticker_info.ticker, *ticker_info))
-            .collect::<HashMap<Ticker, TickerInfo>>();
+            .map(|(ticker_info, timeframe)| {
+                let ticker = ticker_info.ticker;
+                format!(
+                    "{}@kline_{}",
+                    ticker.to_full_symbol_and_type().0.to_lowercase(),
+                    timeframe
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("/");
 
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut feed = KlineFeed::new(streams, market);
 
         loop {
             match &mut state {
                 State::Disconnected => {
-                    let stream_str = streams
-                        .iter()
-                        .map(|(ticker_info, timeframe)| {
-                            let ticker = ticker_info.ticker;
-                            format!(
-                                "{}@kline_{}",
-                                ticker.to_full_symbol_and_type().0.to_lowercase(),
-                                timeframe
-                            )
-                        })
-                        .collect::<Vec<String>>()
-                        .join("/");
-
                     let domain = ws_domain_from_market_type(market);
                     let url = format!("wss://{domain}/stream?streams={stream_str}");
 
                     if let Ok(websocket) = connect_ws(domain, &url).await {
                         state = State::Connected(websocket);
+                        backoff.reset();
                         let _ = output.send(Event::Connected(exchange)).await;
                     } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
                         let _ = output
                             .send(Event::Disconnected(
                                 exchange,
                                 "Failed to connect to websocket".to_string(),
                             ))
                             .await;
+                        backoff.wait(exchange, &mut output).await;
                     }
                 }
-                State::Connected(ws) => match ws.read_frame().await {
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
                     Ok(msg) => match msg.opcode {
                         OpCode::Text => {
-                            if let Ok(StreamData::Kline(ticker, de_kline)) =
-                                feed_de(&msg.payload[..], market)
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
                             {
-                                let (buy_volume, sell_volume) = {
-                                    let buy_volume = de_kline.taker_buy_base_asset_volume;
-                                    let sell_volume = de_kline.volume - buy_volume;
-
-                                    if let Some(c_size) = get_contract_size(&ticker, market) {
-                                        (buy_volume * c_size, sell_volume * c_size)
-                                    } else if size_in_quote_ccy {
-                                        (
-                                            (buy_volume * de_kline.close).round(),
-                                            (sell_volume * de_kline.close).round(),
-                                        )
-                                    } else {
-                                        (buy_volume, sell_volume)
-                                    }
-                                };
-
-                                if let Some((_, tf)) = streams
-                                    .iter()
-                                    .find(|(_, tf)| tf.to_string() == de_kline.interval)
-                                {
-                                    if let Some(info) = ticker_info_map.get(&ticker) {
-                                        let ticker_info = *info;
-                                        let timeframe = *tf;
-
-                                        let kline = Kline::new(
-                                            de_kline.time,
-                                            de_kline.open,
-                                            de_kline.high,
-                                            de_kline.low,
-                                            de_kline.close,
-                                            (buy_volume, sell_volume),
-                                            info.min_ticksize,
-                                        );
-
-                                        let _ = output
-                                            .send(Event::KlineReceived(
-                                                StreamKind::Kline {
-                                                    ticker_info,
-                                                    timeframe,
-                                                },
-                                                kline,
-                                            ))
-                                            .await;
-                                    } else {
-                                        log::error!("Ticker info not found for ticker: {}", ticker);
-                                    }
-                                }
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
                             }
                         }
                         OpCode::Close => {
@@ -1028,7 +1082,9 @@ pub async fn fetch_ticksize(
         MarketKind::InversePerps => (INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/exchangeInfo", 1),
     };
 
-    let response_text = crate::limiter::HTTP_CLIENT
+    crate::network::check_url(&url)?;
+
+    let response_text = crate::limiter::http_client()
         .get(&url)
         .send()
         .await
@@ -1191,6 +1247,52 @@ pub async fn fetch_ticker_prices(
     Ok(ticker_price_map)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DePremiumIndex {
+    #[serde(deserialize_with = "de_string_to_f32")]
+    pub last_funding_rate: f32,
+    pub next_funding_time: u64,
+}
+
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    let (ticker_str, market) = ticker.to_full_symbol_and_type();
+
+    let url = match market {
+        MarketKind::LinearPerps => {
+            format!("{LINEAR_PERP_DOMAIN}/fapi/v1/premiumIndex?symbol={ticker_str}")
+        }
+        MarketKind::InversePerps => {
+            format!("{INVERSE_PERP_DOMAIN}/dapi/v1/premiumIndex?symbol={ticker_str}")
+        }
+        MarketKind::Spot => {
+            return Err(AdapterError::InvalidRequest(
+                "Funding is only available for perpetuals".to_string(),
+            ));
+        }
+    };
+
+    let limiter = limiter_from_market_type(market);
+    let text = crate::limiter::http_request_with_limiter(&url, limiter, 1, None, None).await?;
+
+    // dapi answers with a list even when a single symbol is requested
+    let premium_index = match market {
+        MarketKind::InversePerps => serde_json::from_str::<Vec<DePremiumIndex>>(&text)
+            .map_err(|e| AdapterError::ParseError(format!("Failed to parse premium index: {e}")))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AdapterError::ParseError("Empty premium index".to_string()))?,
+        _ => serde_json::from_str::<DePremiumIndex>(&text)
+            .map_err(|e| AdapterError::ParseError(format!("Failed to parse premium index: {e}")))?,
+    };
+
+    Ok(FundingInfo {
+        rate: premium_index.last_funding_rate,
+        predicted_rate: None,
+        next_funding_time: premium_index.next_funding_time,
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct DeOpenInterest {
@@ -1369,6 +1471,7 @@ pub async fn fetch_intraday_trades(
                 } else {
                     de_trade.qty
                 },
+                sub_ms_nanos: 0,
             })
             .collect()
     };
@@ -1415,6 +1518,7 @@ pub async fn get_hist_trades(
 
         log::info!("Downloading from {}", url);
 
+        crate::network::check_url(&url)?;
         let resp = reqwest::get(&url).await.map_err(AdapterError::FetchError)?;
 
         if !resp.status().is_success() {
@@ -1451,7 +1555,9 @@ pub async fn get_hist_trades(
 
                 trades.extend(csv_reader.records().filter_map(|record| {
                     record.ok().and_then(|record| {
-                        let time = record[5].parse::<u64>().ok()?;
+                        // archives switched from milliseconds to microseconds in 2025
+                        let raw_time = record[5].parse::<u64>().ok()?;
+                        let unit = TimeUnit::infer(raw_time);
                         let is_sell = record[6].parse::<bool>().ok()?;
                         let price_f32 = str_f32_parse(&record[1]);
 
@@ -1467,10 +1573,11 @@ pub async fn get_hist_trades(
                         };
 
                         Some(Trade {
-                            time,
+                            time: unit.to_millis(raw_time),
                             is_sell,
                             price,
                             qty,
+                            sub_ms_nanos: unit.sub_milli_nanos(raw_time),
                         })
                     })
                 }));
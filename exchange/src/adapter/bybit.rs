@@ -1,11 +1,12 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit, StreamKind,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        Exchange, FundingInfo, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit,
+        StreamKind, Ticker, TickerInfo, TickerStats, TimeUnit, Timeframe, Trade,
         adapter::StreamTicksize,
-        connect::{State, connect_ws},
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
         de_string_to_f32, de_string_to_u64,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
         is_symbol_supported,
         limiter::{self, http_request_with_limiter},
         volume_size_unit,
@@ -22,7 +23,7 @@ use serde_json::{Value, json};
 use sonic_rs::{Deserialize, JsonValueTrait, to_object_iter_unchecked};
 use tokio::sync::Mutex;
 
-use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use std::{collections::HashMap, path::PathBuf, sync::LazyLock, time::Duration};
 
 const WS_DOMAIN: &str = "stream.bybit.com";
 const FETCH_DOMAIN: &str = "https://api.bybit.com";
@@ -49,6 +50,8 @@ impl BybitLimiter {
 }
 
 impl limiter::RateLimiter for BybitLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Bybit;
+
     fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
         self.bucket.calculate_wait_time(weight)
     }
@@ -60,6 +63,10 @@ impl limiter::RateLimiter for BybitLimiter {
     fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
         response.status() == 403
     }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
 }
 
 fn exchange_from_market_type(market: MarketKind) -> Exchange {
@@ -251,6 +258,7 @@ async fn try_connect(
     streams: &Value,
     market_type: MarketKind,
     output: &mut mpsc::Sender<Event>,
+    backoff: &mut Backoff,
 ) -> State {
     let exchange = match market_type {
         MarketKind::Spot => Exchange::BybitSpot,
@@ -281,43 +289,144 @@ async fn try_connect(
                         format!("Failed subscribing: {e}"),
                     ))
                     .await;
+                backoff.wait(exchange, output).await;
                 return State::Disconnected;
             }
 
+            backoff.reset();
             let _ = output.send(Event::Connected(exchange)).await;
             State::Connected(websocket)
         }
         Err(err) => {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
             let _ = output
                 .send(Event::Disconnected(
                     exchange,
                     format!("Failed to connect: {err}"),
                 ))
                 .await;
+            backoff.wait(exchange, output).await;
             State::Disconnected
         }
     }
 }
 
+/// Trades and the local book of one symbol. The book restarts from every snapshot the
+/// stream sends, on subscribing and after a service restart (`u` of 1)
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    size_in_quote_ccy: bool,
+    orderbook: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote
+                && ticker_info.market_type() != MarketKind::InversePerps,
+            orderbook: LocalDepthCache::default(),
+            trades_buffer: Vec::new(),
+        }
+    }
+
+    fn qty(&self, qty: f32, price: f32) -> f32 {
+        if self.size_in_quote_ccy {
+            (qty * price).round()
+        } else {
+            qty
+        }
+    }
+}
+
+impl Feed for MarketFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let ticker = self.ticker_info.ticker;
+        let min_ticksize = self.ticker_info.min_ticksize;
+
+        let Ok(data) = feed_de(payload, Some(ticker), ticker.market_type()) else {
+            return vec![];
+        };
+
+        match data {
+            StreamData::Trade(de_trade_vec) => {
+                for de_trade in &de_trade_vec {
+                    let trade = Trade {
+                        time: de_trade.time,
+                        is_sell: de_trade.is_sell == "Sell",
+                        price: Price::from_f32(de_trade.price).round_to_min_tick(min_ticksize),
+                        qty: self.qty(de_trade.qty, de_trade.price),
+                        sub_ms_nanos: 0,
+                    };
+                    self.trades_buffer.push(trade);
+                }
+                vec![]
+            }
+            StreamData::Depth(de_depth, data_type, time) => {
+                let to_orders = |levels: &[DeOrder]| {
+                    levels
+                        .iter()
+                        .map(|x| DeOrder {
+                            price: x.price,
+                            qty: self.qty(x.qty, x.price),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                let depth = DepthPayload {
+                    last_update_id: de_depth.update_id,
+                    time,
+                    bids: to_orders(&de_depth.bids),
+                    asks: to_orders(&de_depth.asks),
+                };
+
+                if (data_type == "snapshot") || (depth.last_update_id == 1) {
+                    self.orderbook
+                        .update(DepthUpdate::Snapshot(depth), min_ticksize);
+                    return vec![];
+                }
+                if data_type != "delta" {
+                    return vec![];
+                }
+
+                self.orderbook
+                    .update(DepthUpdate::Diff(depth), min_ticksize);
+
+                vec![Step::Emit(Event::DepthReceived(
+                    StreamKind::DepthAndTrades {
+                        ticker_info: self.ticker_info,
+                        depth_aggr: StreamTicksize::Client,
+                        push_freq: self.push_freq,
+                    },
+                    time,
+                    self.orderbook.depth.clone(),
+                    std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+                ))]
+            }
+            StreamData::Kline(..) => {
+                log::warn!("Unknown data received");
+                vec![]
+            }
+        }
+    }
+}
+
 pub fn connect_market_stream(
     ticker_info: TickerInfo,
     push_freq: PushFrequency,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state: State = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let ticker = ticker_info.ticker;
 
         let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
         let exchange = exchange_from_market_type(market_type);
 
-        let mut trades_buffer: Vec<Trade> = Vec::new();
-        let mut orderbook = LocalDepthCache::default();
-
-        let size_in_quote_ccy =
-            volume_size_unit() == SizeUnit::Quote && market_type != MarketKind::InversePerps;
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
 
         loop {
             match &mut state {
@@ -346,239 +455,198 @@ pub fn connect_market_stream(
                         "op": "subscribe",
                         "args": [stream_1, stream_2]
                     });
-                    state = try_connect(&subscribe_message, market_type, &mut output).await;
+                    state = try_connect(&subscribe_message, market_type, &mut output, &mut backoff)
+                        .await;
                 }
-                State::Connected(websocket) => match websocket.read_frame().await {
-                    Ok(msg) => match msg.opcode {
-                        OpCode::Text => {
-                            if let Ok(data) = feed_de(&msg.payload[..], Some(ticker), market_type) {
-                                match data {
-                                    StreamData::Trade(de_trade_vec) => {
-                                        for de_trade in &de_trade_vec {
-                                            let price = Price::from_f32(de_trade.price)
-                                                .round_to_min_tick(ticker_info.min_ticksize);
-                                            let qty = if size_in_quote_ccy {
-                                                (de_trade.qty * de_trade.price).round()
-                                            } else {
-                                                de_trade.qty
-                                            };
-
-                                            let trade = Trade {
-                                                time: de_trade.time,
-                                                is_sell: de_trade.is_sell == "Sell",
-                                                price,
-                                                qty,
-                                            };
-
-                                            trades_buffer.push(trade);
-                                        }
-                                    }
-                                    StreamData::Depth(de_depth, data_type, time) => {
-                                        let depth = DepthPayload {
-                                            last_update_id: de_depth.update_id,
-                                            time,
-                                            bids: de_depth
-                                                .bids
-                                                .iter()
-                                                .map(|x| DeOrder {
-                                                    price: x.price,
-                                                    qty: if size_in_quote_ccy {
-                                                        (x.qty * x.price).round()
-                                                    } else {
-                                                        x.qty
-                                                    },
-                                                })
-                                                .collect(),
-                                            asks: de_depth
-                                                .asks
-                                                .iter()
-                                                .map(|x| DeOrder {
-                                                    price: x.price,
-                                                    qty: if size_in_quote_ccy {
-                                                        (x.qty * x.price).round()
-                                                    } else {
-                                                        x.qty
-                                                    },
-                                                })
-                                                .collect(),
-                                        };
-
-                                        if (data_type == "snapshot") || (depth.last_update_id == 1)
-                                        {
-                                            orderbook.update(
-                                                DepthUpdate::Snapshot(depth),
-                                                ticker_info.min_ticksize,
-                                            );
-                                        } else if data_type == "delta" {
-                                            orderbook.update(
-                                                DepthUpdate::Diff(depth),
-                                                ticker_info.min_ticksize,
-                                            );
-
-                                            let _ = output
-                                                .send(Event::DepthReceived(
-                                                    StreamKind::DepthAndTrades {
-                                                        ticker_info,
-                                                        depth_aggr: StreamTicksize::Client,
-                                                        push_freq,
-                                                    },
-                                                    time,
-                                                    orderbook.depth.clone(),
-                                                    std::mem::take(&mut trades_buffer)
-                                                        .into_boxed_slice(),
-                                                ))
-                                                .await;
-                                        }
-                                    }
-                                    _ => {
-                                        log::warn!("Unknown data received");
-                                    }
+                State::Connected(websocket) => {
+                    match telemetry.read_frame(exchange, websocket, &mut output).await {
+                        Ok(msg) => match msg.opcode {
+                            OpCode::Text => {
+                                if let Some(reason) =
+                                    feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                                {
+                                    state = State::Disconnected;
+                                    let _ =
+                                        output.send(Event::Disconnected(exchange, reason)).await;
                                 }
                             }
-                        }
-                        OpCode::Close => {
+                            OpCode::Close => {
+                                state = State::Disconnected;
+                                let _ = output
+                                    .send(Event::Disconnected(
+                                        exchange,
+                                        "Connection closed".to_string(),
+                                    ))
+                                    .await;
+                            }
+                            _ => {}
+                        },
+                        Err(e) => {
                             state = State::Disconnected;
                             let _ = output
                                 .send(Event::Disconnected(
                                     exchange,
-                                    "Connection closed".to_string(),
+                                    "Error reading frame: ".to_string() + &e.to_string(),
                                 ))
                                 .await;
                         }
-                        _ => {}
-                    },
-                    Err(e) => {
-                        state = State::Disconnected;
-                        let _ = output
-                            .send(Event::Disconnected(
-                                exchange,
-                                "Error reading frame: ".to_string() + &e.to_string(),
-                            ))
-                            .await;
                     }
-                },
+                }
             }
         }
     })
 }
 
+/// Klines of a set of symbols and timeframes on one market
+pub struct KlineFeed {
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: Vec<(TickerInfo, Timeframe)>, market_type: MarketKind) -> Self {
+        Self {
+            streams,
+            market_type,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote
+                && market_type != MarketKind::InversePerps,
+        }
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(StreamData::Kline(ticker, de_kline_vec)) = feed_de(payload, None, self.market_type)
+        else {
+            return vec![];
+        };
+
+        let Some(ticker_info) = self
+            .streams
+            .iter()
+            .map(|(info, _)| *info)
+            .find(|info| info.ticker == ticker)
+        else {
+            log::error!("Ticker info not found for ticker: {}", ticker);
+            return vec![];
+        };
+
+        de_kline_vec
+            .iter()
+            .filter_map(|de_kline| {
+                let Some(timeframe) = string_to_timeframe(&de_kline.interval) else {
+                    log::error!(
+                        "Failed to find timeframe: {}, {:?}",
+                        &de_kline.interval,
+                        self.streams
+                    );
+                    return None;
+                };
+
+                let volume = if self.size_in_quote_ccy {
+                    (de_kline.volume * de_kline.close).round()
+                } else {
+                    de_kline.volume
+                };
+
+                let kline = Kline::new(
+                    de_kline.time,
+                    de_kline.open,
+                    de_kline.high,
+                    de_kline.low,
+                    de_kline.close,
+                    (-1.0, volume),
+                    ticker_info.min_ticksize,
+                );
+
+                Some(Step::Emit(Event::KlineReceived(
+                    StreamKind::Kline {
+                        ticker_info,
+                        timeframe,
+                    },
+                    kline,
+                )))
+            })
+            .collect()
+    }
+}
+
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
     market_type: MarketKind,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let exchange = exchange_from_market_type(market_type);
-        let size_in_quote_ccy =
-            volume_size_unit() == SizeUnit::Quote && market_type != MarketKind::InversePerps;
 
-        let ticker_info_map = streams
+        let stream_str = streams
             .iter()
-            .map(|(ticker_info, _)| (ticker_info.ticker, *ticker_info))
-            .collect::<HashMap<Ticker, TickerInfo>>();
+            .map(|(ticker_info, timeframe)| {
+                let ticker = ticker_info.ticker;
+                let timeframe_str = {
+                    if Timeframe::D1 == *timeframe {
+                        "D".to_string()
+                    } else {
+                        timeframe.to_minutes().to_string()
+                    }
+                };
+                format!(
+                    "kline.{timeframe_str}.{}",
+                    ticker.to_full_symbol_and_type().0
+                )
+            })
+            .collect::<Vec<String>>();
+        let subscribe_message = serde_json::json!({
+            "op": "subscribe",
+            "args": stream_str
+        });
+
+        let mut feed = KlineFeed::new(streams, market_type);
 
         loop {
             match &mut state {
                 State::Disconnected => {
-                    let stream_str = streams
-                        .iter()
-                        .map(|(ticker_info, timeframe)| {
-                            let ticker = ticker_info.ticker;
-                            let timeframe_str = {
-                                if Timeframe::D1 == *timeframe {
-                                    "D".to_string()
-                                } else {
-                                    timeframe.to_minutes().to_string()
-                                }
-                            };
-                            format!(
-                                "kline.{timeframe_str}.{}",
-                                ticker.to_full_symbol_and_type().0
-                            )
-                        })
-                        .collect::<Vec<String>>();
-                    let subscribe_message = serde_json::json!({
-                        "op": "subscribe",
-                        "args": stream_str
-                    });
-
-                    state = try_connect(&subscribe_message, market_type, &mut output).await;
+                    state = try_connect(&subscribe_message, market_type, &mut output, &mut backoff)
+                        .await;
                 }
-                State::Connected(websocket) => match websocket.read_frame().await {
-                    Ok(msg) => match msg.opcode {
-                        OpCode::Text => {
-                            if let Ok(StreamData::Kline(ticker, de_kline_vec)) =
-                                feed_de(&msg.payload[..], None, market_type)
-                            {
-                                for de_kline in &de_kline_vec {
-                                    let volume = if size_in_quote_ccy {
-                                        (de_kline.volume * de_kline.close).round()
-                                    } else {
-                                        de_kline.volume
-                                    };
-
-                                    if let Some(timeframe) = string_to_timeframe(&de_kline.interval)
-                                    {
-                                        if let Some(info) = ticker_info_map.get(&ticker) {
-                                            let ticker_info = *info;
-
-                                            let kline = Kline::new(
-                                                de_kline.time,
-                                                de_kline.open,
-                                                de_kline.high,
-                                                de_kline.low,
-                                                de_kline.close,
-                                                (-1.0, volume),
-                                                ticker_info.min_ticksize,
-                                            );
-
-                                            let _ = output
-                                                .send(Event::KlineReceived(
-                                                    StreamKind::Kline {
-                                                        ticker_info,
-                                                        timeframe,
-                                                    },
-                                                    kline,
-                                                ))
-                                                .await;
-                                        } else {
-                                            log::error!(
-                                                "Ticker info not found for ticker: {}",
-                                                ticker
-                                            );
-                                        }
-                                    } else {
-                                        log::error!(
-                                            "Failed to find timeframe: {}, {:?}",
-                                            &de_kline.interval,
-                                            streams
-                                        );
-                                    }
+                State::Connected(websocket) => {
+                    match telemetry.read_frame(exchange, websocket, &mut output).await {
+                        Ok(msg) => match msg.opcode {
+                            OpCode::Text => {
+                                if let Some(reason) =
+                                    feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                                {
+                                    state = State::Disconnected;
+                                    let _ =
+                                        output.send(Event::Disconnected(exchange, reason)).await;
                                 }
                             }
-                        }
-                        OpCode::Close => {
+                            OpCode::Close => {
+                                state = State::Disconnected;
+                                let _ = output
+                                    .send(Event::Disconnected(
+                                        exchange,
+                                        "Connection closed".to_string(),
+                                    ))
+                                    .await;
+                            }
+                            _ => {}
+                        },
+                        Err(e) => {
                             state = State::Disconnected;
                             let _ = output
                                 .send(Event::Disconnected(
                                     exchange,
-                                    "Connection closed".to_string(),
+                                    "Error reading frame: ".to_string() + &e.to_string(),
                                 ))
                                 .await;
                         }
-                        _ => {}
-                    },
-                    Err(e) => {
-                        state = State::Disconnected;
-                        let _ = output
-                            .send(Event::Disconnected(
-                                exchange,
-                                "Error reading frame: ".to_string() + &e.to_string(),
-                            ))
-                            .await;
                     }
-                },
+                }
             }
         }
     })
@@ -793,6 +861,226 @@ pub async fn fetch_klines(
     klines
 }
 
+const ARCHIVE_DOMAIN: &str = "https://public.bybit.com";
+
+#[derive(Deserialize)]
+struct RecentTradesResponse {
+    result: RecentTradesResult,
+}
+
+#[derive(Deserialize)]
+struct RecentTradesResult {
+    list: Vec<RecentTrade>,
+}
+
+#[derive(Deserialize)]
+struct RecentTrade {
+    #[serde(deserialize_with = "de_string_to_u64")]
+    time: u64,
+    #[serde(deserialize_with = "de_string_to_f32")]
+    price: f32,
+    #[serde(deserialize_with = "de_string_to_f32")]
+    size: f32,
+    side: String,
+}
+
+/// Trades at or after `from_time`, up to the end of the first UTC day past it that has any.
+/// Past days come from the daily archives on public.bybit.com, the current one from the
+/// recent trades endpoint, which only reaches back 1000 trades (60 for spot)
+pub async fn fetch_trades(
+    ticker_info: TickerInfo,
+    from_time: u64,
+    data_path: PathBuf,
+) -> Result<Vec<Trade>, AdapterError> {
+    let today = chrono::Utc::now().date_naive();
+    let mut date = chrono::DateTime::from_timestamp_millis(from_time as i64)
+        .ok_or_else(|| AdapterError::ParseError("Invalid timestamp".into()))?
+        .date_naive();
+
+    while date < today {
+        let trades = match get_hist_trades(ticker_info, date, data_path.clone()).await {
+            Ok(trades) => trades,
+            Err(e) => {
+                log::warn!(
+                    "Historical trades fetch failed: {}, falling back to recent trades",
+                    e
+                );
+                break;
+            }
+        };
+
+        if trades.last().is_some_and(|trade| trade.time > from_time) {
+            return Ok(trades
+                .into_iter()
+                .filter(|trade| trade.time >= from_time)
+                .collect());
+        }
+        date = date.succ_opt().unwrap_or(today);
+    }
+
+    let mut trades = fetch_recent_trades(ticker_info).await?;
+    trades.retain(|trade| trade.time >= from_time);
+    Ok(trades)
+}
+
+async fn fetch_recent_trades(ticker_info: TickerInfo) -> Result<Vec<Trade>, AdapterError> {
+    let (symbol_str, market_type) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let (category, limit) = match market_type {
+        MarketKind::Spot => ("spot", 60),
+        MarketKind::LinearPerps => ("linear", 1000),
+        MarketKind::InversePerps => ("inverse", 1000),
+    };
+    let url = format!(
+        "{FETCH_DOMAIN}/v5/market/recent-trade?category={category}&symbol={}&limit={limit}",
+        symbol_str.to_uppercase(),
+    );
+
+    let response: RecentTradesResponse =
+        limiter::http_parse_with_limiter(&url, &BYBIT_LIMITER, 1, None, None).await?;
+
+    let size_in_quote_ccy =
+        volume_size_unit() == SizeUnit::Quote && market_type != MarketKind::InversePerps;
+
+    let mut trades: Vec<Trade> = response
+        .result
+        .list
+        .into_iter()
+        .map(|de_trade| Trade {
+            time: de_trade.time,
+            is_sell: de_trade.side == "Sell",
+            price: Price::from_f32(de_trade.price).round_to_min_tick(ticker_info.min_ticksize),
+            qty: if size_in_quote_ccy {
+                (de_trade.size * de_trade.price).round()
+            } else {
+                de_trade.size
+            },
+            sub_ms_nanos: 0,
+        })
+        .collect();
+
+    // newest first from the endpoint
+    trades.sort_by_key(Trade::sort_key);
+    Ok(trades)
+}
+
+/// Trades of one UTC day from the daily archive, downloaded once into `base_path`
+pub async fn get_hist_trades(
+    ticker_info: TickerInfo,
+    date: chrono::NaiveDate,
+    base_path: PathBuf,
+) -> Result<Vec<Trade>, AdapterError> {
+    let (symbol, market_type) = ticker_info.ticker.to_full_symbol_and_type();
+    let symbol = symbol.to_uppercase();
+    let day = date.format("%Y-%m-%d");
+
+    let (market_subpath, file_name) = match market_type {
+        MarketKind::Spot => (format!("spot/{symbol}"), format!("{symbol}_{day}.csv.gz")),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            (format!("trading/{symbol}"), format!("{symbol}{day}.csv.gz"))
+        }
+    };
+
+    let base_path = base_path.join(&market_subpath);
+    std::fs::create_dir_all(&base_path)
+        .map_err(|e| AdapterError::ParseError(format!("Failed to create directories: {e}")))?;
+
+    let archive_path = base_path.join(&file_name);
+
+    if std::fs::metadata(&archive_path).is_ok() {
+        log::info!("Using cached {market_subpath}/{file_name}");
+    } else {
+        let url = format!("{ARCHIVE_DOMAIN}/{market_subpath}/{file_name}");
+
+        log::info!("Downloading from {}", url);
+
+        crate::network::check_url(&url)?;
+        let resp = reqwest::get(&url).await.map_err(AdapterError::FetchError)?;
+
+        if !resp.status().is_success() {
+            return Err(AdapterError::InvalidRequest(format!(
+                "Failed to fetch from {}: {}",
+                url,
+                resp.status()
+            )));
+        }
+
+        let body = resp.bytes().await.map_err(AdapterError::FetchError)?;
+
+        std::fs::write(&archive_path, &body).map_err(|e| {
+            AdapterError::ParseError(format!("Failed to write archive: {e}, {archive_path:?}"))
+        })?;
+    }
+
+    let file = std::fs::File::open(&archive_path)
+        .map_err(|e| AdapterError::ParseError(format!("Failed to open archive: {e}")))?;
+
+    let size_in_quote_ccy =
+        volume_size_unit() == SizeUnit::Quote && market_type != MarketKind::InversePerps;
+
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(std::io::BufReader::new(flate2::read::GzDecoder::new(file)));
+
+    let mut trades: Vec<Trade> = csv_reader
+        .records()
+        .filter_map(|record| {
+            let record = record.ok()?;
+            parse_archive_trade(&record, market_type, size_in_quote_ccy, &ticker_info)
+        })
+        .collect();
+
+    // older derivative archives run newest first
+    trades.sort_by_key(Trade::sort_key);
+    Ok(trades)
+}
+
+/// Spot rows are `id,timestamp,price,volume,side` in ms, derivative rows start with
+/// `timestamp,symbol,side,size,price` in seconds with a fraction down to 100µs
+fn parse_archive_trade(
+    record: &csv::StringRecord,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+    ticker_info: &TickerInfo,
+) -> Option<Trade> {
+    let (raw_micros, price, qty, side) = match market_type {
+        MarketKind::Spot => (
+            record.get(1)?.parse::<u64>().ok()?.checked_mul(1000)?,
+            record.get(2)?.parse::<f32>().ok()?,
+            record.get(3)?.parse::<f32>().ok()?,
+            record.get(4)?,
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            let (secs, fraction) = record
+                .get(0)?
+                .split_once('.')
+                .unwrap_or((record.get(0)?, ""));
+            let fraction = fraction.get(..fraction.len().min(6))?;
+            let micros = secs.parse::<u64>().ok()? * 1_000_000
+                + format!("{fraction:0<6}").parse::<u64>().ok()?;
+
+            (
+                micros,
+                record.get(4)?.parse::<f32>().ok()?,
+                record.get(3)?.parse::<f32>().ok()?,
+                record.get(2)?,
+            )
+        }
+    };
+
+    Some(Trade {
+        time: TimeUnit::Micros.to_millis(raw_micros),
+        is_sell: side.eq_ignore_ascii_case("sell"),
+        price: Price::from_f32(price).round_to_min_tick(ticker_info.min_ticksize),
+        qty: if size_in_quote_ccy {
+            (qty * price).round()
+        } else {
+            qty
+        },
+        sub_ms_nanos: TimeUnit::Micros.sub_milli_nanos(raw_micros),
+    })
+}
+
 pub async fn fetch_ticksize(
     market_type: MarketKind,
 ) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
@@ -806,7 +1094,9 @@ pub async fn fetch_ticksize(
 
     let url = format!("{FETCH_DOMAIN}/v5/market/instruments-info?category={market}&limit=1000",);
 
-    let response_text = crate::limiter::HTTP_CLIENT
+    crate::network::check_url(&url)?;
+
+    let response_text = crate::limiter::http_client()
         .get(&url)
         .send()
         .await
@@ -876,6 +1166,42 @@ pub async fn fetch_ticksize(
     Ok(ticker_info_map)
 }
 
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    let (ticker_str, market_type) = ticker.to_full_symbol_and_type();
+
+    let category = match market_type {
+        MarketKind::LinearPerps => "linear",
+        MarketKind::InversePerps => "inverse",
+        MarketKind::Spot => {
+            return Err(AdapterError::InvalidRequest(
+                "Funding is only available for perpetuals".to_string(),
+            ));
+        }
+    };
+
+    let url = format!("{FETCH_DOMAIN}/v5/market/tickers?category={category}&symbol={ticker_str}");
+
+    let parsed_response: Value =
+        limiter::http_parse_with_limiter(&url, &BYBIT_LIMITER, 1, None, None).await?;
+
+    let item = &parsed_response["result"]["list"][0];
+
+    let rate = item["fundingRate"]
+        .as_str()
+        .and_then(|s| s.parse::<f32>().ok())
+        .ok_or_else(|| AdapterError::ParseError("Funding rate not found".to_string()))?;
+    let next_funding_time = item["nextFundingTime"]
+        .as_str()
+        .and_then(|s| s.parse::<u64>().ok())
+        .ok_or_else(|| AdapterError::ParseError("Next funding time not found".to_string()))?;
+
+    Ok(FundingInfo {
+        rate,
+        predicted_rate: None,
+        next_funding_time,
+    })
+}
+
 pub async fn fetch_ticker_prices(
     market_type: MarketKind,
 ) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
@@ -944,3 +1270,45 @@ pub async fn fetch_ticker_prices(
 
     Ok(ticker_prices_map)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_archive_rows_of_both_layouts() {
+        let linear = TickerInfo::new(
+            Ticker::new("BTCUSDT", Exchange::BybitLinear),
+            0.1,
+            0.001,
+            None,
+        );
+        let row = csv::StringRecord::from(vec![
+            "1704067199.9936",
+            "BTCUSDT",
+            "Sell",
+            "0.25",
+            "42283.6",
+            "ZeroMinusTick",
+        ]);
+        let trade = parse_archive_trade(&row, MarketKind::LinearPerps, false, &linear).unwrap();
+
+        assert_eq!(trade.time, 1_704_067_199_993);
+        assert_eq!(trade.sub_ms_nanos, 600_000);
+        assert!(trade.is_sell);
+        assert_eq!(trade.qty, 0.25);
+
+        let spot = TickerInfo::new(
+            Ticker::new("BTCUSDT", Exchange::BybitSpot),
+            0.01,
+            0.0001,
+            None,
+        );
+        let row = csv::StringRecord::from(vec!["1", "1704067199993", "42283.61", "0.5", "buy"]);
+        let trade = parse_archive_trade(&row, MarketKind::Spot, false, &spot).unwrap();
+
+        assert_eq!(trade.time, 1_704_067_199_993);
+        assert_eq!(trade.sub_ms_nanos, 0);
+        assert!(!trade.is_sell);
+    }
+}
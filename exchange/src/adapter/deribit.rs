@@ -0,0 +1,778 @@
+use super::{
+    super::{
+        Exchange, Kline, MarketKind, Price, PushFrequency, SizeUnit, StreamKind, Ticker,
+        TickerInfo, TickerStats, Timeframe, Trade,
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
+        depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
+        is_symbol_supported,
+        limiter::{self, RateLimiter},
+        volume_size_unit,
+    },
+    AdapterError, Event, StreamTicksize,
+};
+
+use fastwebsockets::{Frame, OpCode};
+use iced_futures::{
+    futures::{SinkExt, Stream, channel::mpsc},
+    stream,
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use tokio::sync::Mutex;
+
+const API_DOMAIN: &str = "https://www.deribit.com/api/v2";
+const WS_DOMAIN: &str = "www.deribit.com";
+
+/// Underlyings listed, inverse contracts settle in these, linear ones in USDC
+const CURRENCIES: [&str; 2] = ["BTC", "ETH"];
+const LINEAR_SETTLEMENT: &str = "USDC";
+
+// non-matching engine requests refill at 20 credits per second
+const LIMIT: usize = 20;
+
+const REFILL_RATE: Duration = Duration::from_secs(1);
+const LIMITER_BUFFER_PCT: f32 = 0.05;
+
+static DERIBIT_LIMITER: LazyLock<Mutex<DeribitLimiter>> =
+    LazyLock::new(|| Mutex::new(DeribitLimiter::new(LIMIT, REFILL_RATE)));
+
+pub struct DeribitLimiter {
+    bucket: limiter::FixedWindowBucket,
+}
+
+impl DeribitLimiter {
+    pub fn new(limit: usize, refill_rate: Duration) -> Self {
+        let effective_limit = (limit as f32 * (1.0 - LIMITER_BUFFER_PCT)) as usize;
+        Self {
+            bucket: limiter::FixedWindowBucket::new(effective_limit, refill_rate),
+        }
+    }
+}
+
+impl RateLimiter for DeribitLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Deribit;
+
+    fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
+        self.bucket.calculate_wait_time(weight)
+    }
+
+    fn update_from_response(&mut self, _response: &reqwest::Response, weight: usize) {
+        self.bucket.consume_tokens(weight);
+    }
+
+    fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
+        response.status() == 429
+    }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct DeInstrument {
+    instrument_name: String,
+    base_currency: String,
+    /// `reversed` for inverse contracts, `linear` otherwise
+    instrument_type: String,
+    tick_size: f32,
+    min_trade_amount: f32,
+    contract_size: f32,
+    is_active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeBookSummary {
+    instrument_name: String,
+    last: Option<f32>,
+    mark_price: f32,
+    /// 24h change in percent
+    price_change: Option<f32>,
+    volume_usd: Option<f32>,
+    volume: f32,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeChartData {
+    status: String,
+    #[serde(default)]
+    ticks: Vec<u64>,
+    #[serde(default)]
+    open: Vec<f32>,
+    #[serde(default)]
+    high: Vec<f32>,
+    #[serde(default)]
+    low: Vec<f32>,
+    #[serde(default)]
+    close: Vec<f32>,
+    #[serde(default)]
+    volume: Vec<f32>,
+    #[serde(default)]
+    cost: Vec<f32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeTrade {
+    timestamp: u64,
+    price: f32,
+    amount: f32,
+    direction: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeBook {
+    #[serde(rename = "type")]
+    kind: String,
+    timestamp: u64,
+    change_id: u64,
+    prev_change_id: Option<u64>,
+    /// `[action, price, amount]`, deleted levels come with a zero amount
+    bids: Vec<(String, f32, f32)>,
+    asks: Vec<(String, f32, f32)>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeCandle {
+    tick: u64,
+    open: f32,
+    high: f32,
+    low: f32,
+    close: f32,
+    volume: f32,
+    cost: f32,
+}
+
+#[derive(Deserialize)]
+struct Notification {
+    method: String,
+    params: NotificationParams,
+}
+
+#[derive(Deserialize)]
+struct NotificationParams {
+    channel: String,
+    data: Value,
+}
+
+enum StreamData {
+    Trades(Vec<DeTrade>),
+    Book(DeBook),
+    Candle(String, DeCandle),
+}
+
+fn parse_websocket_message(payload: &[u8]) -> Result<StreamData, AdapterError> {
+    let notification: Notification =
+        serde_json::from_slice(payload).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+    if notification.method != "subscription" {
+        return Err(AdapterError::ParseError(format!(
+            "Unexpected method: {}",
+            notification.method
+        )));
+    }
+
+    let NotificationParams { channel, data } = notification.params;
+    let parse_err = |e: serde_json::Error| AdapterError::ParseError(e.to_string());
+
+    if channel.starts_with("chart.trades.") {
+        let candle = serde_json::from_value(data).map_err(parse_err)?;
+        Ok(StreamData::Candle(channel, candle))
+    } else if channel.starts_with("trades.") {
+        Ok(StreamData::Trades(
+            serde_json::from_value(data).map_err(parse_err)?,
+        ))
+    } else if channel.starts_with("book.") {
+        Ok(StreamData::Book(
+            serde_json::from_value(data).map_err(parse_err)?,
+        ))
+    } else {
+        Err(AdapterError::ParseError(format!(
+            "Unknown channel: {channel}"
+        )))
+    }
+}
+
+/// Inverse amounts are in USD already, linear ones in the base coin
+fn calc_qty(amount: f32, price: f32, size_in_quote_ccy: bool, market: MarketKind) -> f32 {
+    match market {
+        MarketKind::InversePerps => amount,
+        _ if size_in_quote_ccy => (amount * price).round(),
+        _ => amount,
+    }
+}
+
+fn exchange_for(market: MarketKind) -> Exchange {
+    match market {
+        MarketKind::InversePerps => Exchange::DeribitInverse,
+        _ => Exchange::DeribitLinear,
+    }
+}
+
+/// Currencies to query instruments by, linear contracts of every underlying settle in USDC
+fn settlement_currencies(market: MarketKind) -> &'static [&'static str] {
+    match market {
+        MarketKind::InversePerps => &CURRENCIES,
+        _ => &[LINEAR_SETTLEMENT],
+    }
+}
+
+/// "BTC" of both "BTC-PERPETUAL" and "BTC_USDC-PERPETUAL"
+fn underlying(instrument: &str) -> &str {
+    instrument.split(['-', '_']).next().unwrap_or(instrument)
+}
+
+/// Largest power of ten that divides into `tick`, so a 0.5 tick becomes 0.1 rather than
+/// being rounded up to 1.0 and skipping every other price level
+fn decade_tick(tick: f32) -> f32 {
+    if tick <= 0.0 {
+        return tick;
+    }
+    // nudge past f32 noise, log10(0.01) can land just under -2
+    10f32.powi((tick.log10() + 1e-4).floor() as i32)
+}
+
+fn timeframe_to_resolution(tf: Timeframe) -> Option<&'static str> {
+    Some(match tf {
+        Timeframe::M1 => "1",
+        Timeframe::M3 => "3",
+        Timeframe::M5 => "5",
+        Timeframe::M15 => "15",
+        Timeframe::M30 => "30",
+        Timeframe::H1 => "60",
+        Timeframe::H2 => "120",
+        Timeframe::H12 => "720",
+        Timeframe::D1 => "1D",
+        _ => return None,
+    })
+}
+
+/// GET on the public API, unwrapping the JSON-RPC envelope
+async fn get_public<T: DeserializeOwned>(method: &str, query: &str) -> Result<T, AdapterError> {
+    let url = format!("{API_DOMAIN}/public/{method}?{query}");
+
+    let mut response: Value =
+        limiter::http_parse_with_limiter(&url, &DERIBIT_LIMITER, 1, None, None).await?;
+
+    if let Some(error) = response.get("error") {
+        return Err(AdapterError::InvalidRequest(format!(
+            "Deribit {method}: {}",
+            error["message"].as_str().unwrap_or("rejected")
+        )));
+    }
+
+    serde_json::from_value(response["result"].take())
+        .map_err(|e| AdapterError::ParseError(format!("Deribit {method}: {e}")))
+}
+
+pub async fn fetch_ticksize(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    let exchange = exchange_for(market_type);
+    let is_inverse = market_type == MarketKind::InversePerps;
+
+    let mut map = HashMap::new();
+
+    for currency in settlement_currencies(market_type) {
+        let instruments: Vec<DeInstrument> = get_public(
+            "get_instruments",
+            &format!("currency={currency}&kind=future&expired=false"),
+        )
+        .await?;
+
+        for instrument in instruments {
+            if !instrument.is_active
+                || (instrument.instrument_type == "reversed") != is_inverse
+                || !CURRENCIES.contains(&instrument.base_currency.as_str())
+                || !is_symbol_supported(&instrument.instrument_name, exchange, true)
+            {
+                continue;
+            }
+
+            let ticker = Ticker::new(&instrument.instrument_name, exchange);
+            let contract_size = is_inverse.then_some(instrument.contract_size);
+            let info = TickerInfo::new(
+                ticker,
+                decade_tick(instrument.tick_size),
+                instrument.min_trade_amount,
+                contract_size,
+            );
+
+            map.insert(ticker, Some(info));
+        }
+    }
+
+    Ok(map)
+}
+
+pub async fn fetch_ticker_prices(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let exchange = exchange_for(market_type);
+
+    let mut map = HashMap::new();
+
+    for currency in settlement_currencies(market_type) {
+        let summaries: Vec<DeBookSummary> = get_public(
+            "get_book_summary_by_currency",
+            &format!("currency={currency}&kind=future"),
+        )
+        .await?;
+
+        for summary in summaries {
+            if !CURRENCIES.contains(&underlying(&summary.instrument_name))
+                || !is_symbol_supported(&summary.instrument_name, exchange, false)
+            {
+                continue;
+            }
+
+            let price = summary.last.unwrap_or(summary.mark_price);
+
+            map.insert(
+                Ticker::new(&summary.instrument_name, exchange),
+                TickerStats {
+                    mark_price: price,
+                    daily_price_chg: summary.price_change.unwrap_or(0.0),
+                    daily_volume: summary.volume_usd.unwrap_or(summary.volume * price),
+                },
+            );
+        }
+    }
+
+    Ok(map)
+}
+
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<Kline>, AdapterError> {
+    let (symbol_str, market) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let resolution = timeframe_to_resolution(timeframe).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Unsupported timeframe: {timeframe}"))
+    })?;
+
+    let (start, end) = range.unwrap_or_else(|| {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        (now.saturating_sub(timeframe.to_milliseconds() * 500), now)
+    });
+
+    let chart: DeChartData = get_public(
+        "get_tradingview_chart_data",
+        &format!(
+            "instrument_name={symbol_str}&start_timestamp={start}&end_timestamp={end}&resolution={resolution}"
+        ),
+    )
+    .await?;
+
+    if chart.status == "no_data" {
+        return Ok(vec![]);
+    }
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let klines = (0..chart.ticks.len())
+        .filter_map(|i| {
+            let close = *chart.close.get(i)?;
+            let volume = if market == MarketKind::InversePerps || size_in_quote_ccy {
+                *chart.cost.get(i)?
+            } else {
+                *chart.volume.get(i)?
+            };
+
+            Some(Kline::new(
+                chart.ticks[i],
+                *chart.open.get(i)?,
+                *chart.high.get(i)?,
+                *chart.low.get(i)?,
+                close,
+                (-1.0, volume),
+                ticker_info.min_ticksize,
+            ))
+        })
+        .collect();
+
+    Ok(klines)
+}
+
+async fn try_connect(
+    channels: &[String],
+    exchange: Exchange,
+    output: &mut mpsc::Sender<Event>,
+    backoff: &mut Backoff,
+) -> State {
+    let url = format!("wss://{WS_DOMAIN}/ws/api/v2");
+
+    match connect_ws(WS_DOMAIN, &url).await {
+        Ok(mut websocket) => {
+            let subscribe_message = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "public/subscribe",
+                "params": { "channels": channels },
+            });
+
+            if let Err(e) = websocket
+                .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                    subscribe_message.to_string().as_bytes(),
+                )))
+                .await
+            {
+                let _ = output
+                    .send(Event::Disconnected(
+                        exchange,
+                        format!("Failed subscribing: {e}"),
+                    ))
+                    .await;
+                backoff.wait(exchange, output).await;
+                return State::Disconnected;
+            }
+
+            backoff.reset();
+            let _ = output.send(Event::Connected(exchange)).await;
+            State::Connected(websocket)
+        }
+        Err(err) => {
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Failed to connect: {err}"),
+                ))
+                .await;
+            backoff.wait(exchange, output).await;
+            State::Disconnected
+        }
+    }
+}
+
+/// Trades and the book of one instrument. The book comes as a snapshot on subscribing and
+/// changes chained by `prev_change_id` after it
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+    orderbook: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+    // a change that doesn't follow the last one means a lost message, resubscribing
+    // starts over from a fresh snapshot
+    last_change_id: Option<u64>,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            market_type: ticker_info.market_type(),
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            orderbook: LocalDepthCache::default(),
+            trades_buffer: vec![],
+            last_change_id: None,
+        }
+    }
+
+    fn qty(&self, amount: f32, price: f32) -> f32 {
+        calc_qty(amount, price, self.size_in_quote_ccy, self.market_type)
+    }
+
+    fn on_book(&mut self, book: DeBook) -> Step {
+        let min_ticksize = self.ticker_info.min_ticksize;
+        let to_orders = |levels: &[(String, f32, f32)]| {
+            levels
+                .iter()
+                .map(|(_, price, amount)| DeOrder {
+                    price: *price,
+                    qty: self.qty(*amount, *price),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let depth = DepthPayload {
+            last_update_id: book.change_id,
+            time: book.timestamp,
+            bids: to_orders(&book.bids),
+            asks: to_orders(&book.asks),
+        };
+
+        if book.kind == "snapshot" {
+            self.orderbook
+                .update(DepthUpdate::Snapshot(depth), min_ticksize);
+        } else if self.last_change_id.is_some() && book.prev_change_id == self.last_change_id {
+            self.orderbook
+                .update(DepthUpdate::Diff(depth), min_ticksize);
+        } else {
+            self.last_change_id = None;
+            return Step::Resync;
+        }
+        self.last_change_id = Some(book.change_id);
+
+        Step::Emit(Event::DepthReceived(
+            StreamKind::DepthAndTrades {
+                ticker_info: self.ticker_info,
+                depth_aggr: StreamTicksize::Client,
+                push_freq: self.push_freq,
+            },
+            book.timestamp,
+            self.orderbook.depth.clone(),
+            std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+        ))
+    }
+}
+
+impl Feed for MarketFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        match parse_websocket_message(payload) {
+            Ok(StreamData::Trades(de_trades)) => {
+                for de_trade in de_trades {
+                    let trade = Trade {
+                        time: de_trade.timestamp,
+                        is_sell: de_trade.direction == "sell",
+                        price: Price::from_f32(de_trade.price)
+                            .round_to_min_tick(self.ticker_info.min_ticksize),
+                        qty: self.qty(de_trade.amount, de_trade.price),
+                        sub_ms_nanos: 0,
+                    };
+                    self.trades_buffer.push(trade);
+                }
+                vec![]
+            }
+            Ok(StreamData::Book(book)) => vec![self.on_book(book)],
+            Ok(StreamData::Candle(..)) | Err(_) => vec![],
+        }
+    }
+}
+
+pub fn connect_market_stream(
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state: State = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
+
+        let ticker = ticker_info.ticker;
+        let (symbol_str, _) = ticker.to_full_symbol_and_type();
+        let exchange = ticker.exchange;
+
+        let channels = [
+            format!("trades.{symbol_str}.100ms"),
+            format!("book.{symbol_str}.100ms"),
+        ];
+
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    feed.last_change_id = None;
+                    state = try_connect(&channels, exchange, &mut output, &mut backoff).await;
+                }
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                            {
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
+                            }
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// Candles of a set of instruments and resolutions, keyed by their channel
+pub struct KlineFeed {
+    lookup: HashMap<String, (TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: &[(TickerInfo, Timeframe)], market_type: MarketKind) -> Self {
+        let mut lookup = HashMap::new();
+        for (ticker_info, timeframe) in streams {
+            if let Some(resolution) = timeframe_to_resolution(*timeframe) {
+                let (symbol, _) = ticker_info.ticker.to_full_symbol_and_type();
+                lookup.insert(
+                    format!("chart.trades.{symbol}.{resolution}"),
+                    (*ticker_info, *timeframe),
+                );
+            }
+        }
+
+        Self {
+            lookup,
+            market_type,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+
+    fn channels(&self) -> Vec<String> {
+        self.lookup.keys().cloned().collect()
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(StreamData::Candle(channel, candle)) = parse_websocket_message(payload) else {
+            return vec![];
+        };
+        let Some(&(ticker_info, timeframe)) = self.lookup.get(&channel) else {
+            return vec![];
+        };
+
+        let volume = if self.market_type == MarketKind::InversePerps || self.size_in_quote_ccy {
+            candle.cost
+        } else {
+            candle.volume
+        };
+
+        let kline = Kline::new(
+            candle.tick,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            (-1.0, volume),
+            ticker_info.min_ticksize,
+        );
+
+        vec![Step::Emit(Event::KlineReceived(
+            StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            },
+            kline,
+        ))]
+    }
+}
+
+pub fn connect_kline_stream(
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
+
+        let mut feed = KlineFeed::new(&streams, market_type);
+        let channels = feed.channels();
+
+        let exchange = streams
+            .first()
+            .map(|(t, _)| t.exchange())
+            .unwrap_or(exchange_for(market_type));
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&channels, exchange, &mut output, &mut backoff).await;
+                }
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                            {
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
+                            }
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_subscription_notifications() {
+        let book = br#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"book.BTC-PERPETUAL.100ms","data":{"type":"change","timestamp":1700000000123,"prev_change_id":41,"instrument_name":"BTC-PERPETUAL","change_id":42,"bids":[["delete",36999.5,0.0]],"asks":[["new",37001.0,2500.0]]}}}"#;
+        let Ok(StreamData::Book(book)) = parse_websocket_message(book) else {
+            panic!("book change not parsed");
+        };
+        assert_eq!((book.prev_change_id, book.change_id), (Some(41), 42));
+        assert_eq!(book.asks[0].1, 37001.0);
+
+        let trades = br#"{"jsonrpc":"2.0","method":"subscription","params":{"channel":"trades.BTC-PERPETUAL.100ms","data":[{"trade_seq":1,"trade_id":"1","timestamp":1700000000123,"tick_direction":0,"price":37000.5,"mark_price":37000.1,"instrument_name":"BTC-PERPETUAL","index_price":36990.2,"direction":"sell","amount":120.0}]}}"#;
+        let Ok(StreamData::Trades(trades)) = parse_websocket_message(trades) else {
+            panic!("trades not parsed");
+        };
+        assert_eq!(trades[0].direction, "sell");
+
+        let ack = br#"{"jsonrpc":"2.0","id":1,"result":["book.BTC-PERPETUAL.100ms"]}"#;
+        assert!(parse_websocket_message(ack).is_err());
+    }
+
+    #[test]
+    fn underlying_of_inverse_and_linear_names() {
+        assert_eq!(underlying("BTC-PERPETUAL"), "BTC");
+        assert_eq!(underlying("ETH-27DEC24"), "ETH");
+        assert_eq!(underlying("BTC_USDC-PERPETUAL"), "BTC");
+    }
+
+    #[test]
+    fn non_decade_ticks_round_down_to_a_power_of_ten() {
+        let power = |tick: f32| crate::util::MinTicksize::from(decade_tick(tick)).power;
+
+        assert_eq!(power(0.5), -1);
+        assert_eq!(power(0.05), -2);
+        assert_eq!(power(2.5), 0);
+        assert_eq!(power(0.01), -2);
+        assert_eq!(power(1.0), 0);
+        assert_eq!(power(0.0001), -4);
+    }
+}
@@ -1,13 +1,15 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit, StreamKind,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        Exchange, FundingInfo, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit,
+        StreamKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
         adapter::StreamTicksize,
-        connect::{State, connect_ws},
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
         de_string_to_f32,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
         is_symbol_supported,
         limiter::{self, RateLimiter},
+        schedule::MarketStatus,
         volume_size_unit,
     },
     AdapterError, Event,
@@ -35,6 +37,9 @@ const LIMIT: usize = 2400; // Futures API limit is 2400/min
 const REFILL_RATE: Duration = Duration::from_secs(60);
 const LIMITER_BUFFER_PCT: f32 = 0.03;
 
+/// Upper bound on a single sleep during scheduled downtime, so the status shown stays fresh
+const MARKET_CLOSED_RECHECK: Duration = Duration::from_secs(300);
+
 pub struct ForexLimiter {
     bucket: limiter::DynamicBucket,
 }
@@ -49,6 +54,8 @@ impl ForexLimiter {
 }
 
 impl RateLimiter for ForexLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Forex;
+
     fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
         let (wait_time, _reason) = self.bucket.prepare_request(weight);
         wait_time
@@ -69,6 +76,10 @@ impl RateLimiter for ForexLimiter {
         let status = response.status();
         status == 429 || status == 418
     }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
 }
 
 fn exchange_from_market_type(_market: MarketKind) -> Exchange {
@@ -83,6 +94,23 @@ fn ws_domain_from_market_type(_market: MarketKind) -> &'static str {
     WS_DOMAIN
 }
 
+/// Sleeps instead of dialing the websocket while the market is closed.
+///
+/// Returns `true` if it waited, so the caller re-checks before reconnecting.
+async fn wait_out_market_close(exchange: Exchange, output: &mut mpsc::Sender<Event>) -> bool {
+    let MarketStatus::Closed { reopens_at } = exchange.schedule().status_now() else {
+        return false;
+    };
+
+    let _ = output.send(Event::MarketClosed(exchange, reopens_at)).await;
+
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let remaining = Duration::from_millis(reopens_at.saturating_sub(now));
+    tokio::time::sleep(remaining.min(MARKET_CLOSED_RECHECK)).await;
+
+    true
+}
+
 #[derive(Deserialize, Clone)]
 pub struct FetchedPerpDepth {
     #[serde(rename = "lastUpdateId")]
@@ -233,27 +261,20 @@ fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError>
 
 async fn try_resync(
     exchange: Exchange,
-    ticker_info: TickerInfo,
-    contract_size: Option<f32>,
-    orderbook: &mut LocalDepthCache,
+    ticker: Ticker,
+    feed: &mut MarketFeed,
     state: &mut State,
     output: &mut mpsc::Sender<Event>,
-    already_fetching: &mut bool,
 ) {
-    let ticker = ticker_info.ticker;
-
     let (tx, rx) = tokio::sync::oneshot::channel();
-    *already_fetching = true;
 
     tokio::spawn(async move {
-        let result = fetch_depth(&ticker, contract_size).await;
+        let result = fetch_depth(&ticker, None).await;
         let _ = tx.send(result);
     });
 
     match rx.await {
-        Ok(Ok(depth)) => {
-            orderbook.update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
-        }
+        Ok(Ok(depth)) => feed.snapshot(depth),
         Ok(Err(e)) => {
             let _ = output
                 .send(Event::Disconnected(
@@ -274,33 +295,124 @@ async fn try_resync(
                 .expect("Trying to send disconnect event...");
         }
     }
-    *already_fetching = false;
 }
 
-#[allow(unused_assignments)]
+/// Trades and the local book of one pair. The book starts from a REST snapshot, a first
+/// diff past it asks for a resync
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    market: MarketKind,
+    size_in_quote_ccy: bool,
+    orderbook: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+    /// Final update id of the last applied diff, 0 right after a snapshot
+    prev_id: u64,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            market: ticker_info.market_type(),
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            orderbook: LocalDepthCache::default(),
+            trades_buffer: Vec::new(),
+            prev_id: 0,
+        }
+    }
+
+    fn on_depth(&mut self, depth: SonicDepth) -> Option<Step> {
+        let last_update_id = self.orderbook.last_update_id;
+        let SonicDepth::Perp(de_depth) = &depth;
+
+        if de_depth.final_id <= last_update_id || last_update_id == 0 {
+            return None;
+        }
+
+        if self.prev_id == 0 && de_depth.first_id > last_update_id + 1 {
+            log::warn!("Out of sync at first event. Trying to resync...\n");
+            return Some(Step::Resync);
+        }
+
+        if self.prev_id != 0 && self.prev_id != de_depth.prev_final_id {
+            return Some(Step::Reconnect(format!(
+                "Out of sync. Expected update_id: {}, got: {}",
+                de_depth.prev_final_id, self.prev_id
+            )));
+        }
+
+        let (time, final_id) = (de_depth.time, de_depth.final_id);
+        self.orderbook.update(
+            DepthUpdate::Diff(new_depth_cache(&depth, None)),
+            self.ticker_info.min_ticksize,
+        );
+        self.prev_id = final_id;
+
+        Some(Step::Emit(Event::DepthReceived(
+            StreamKind::DepthAndTrades {
+                ticker_info: self.ticker_info,
+                depth_aggr: StreamTicksize::Client,
+                push_freq: self.push_freq,
+            },
+            time,
+            self.orderbook.depth.clone(),
+            std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+        )))
+    }
+}
+
+impl Feed for MarketFeed {
+    fn snapshot(&mut self, depth: DepthPayload) {
+        self.orderbook
+            .update(DepthUpdate::Snapshot(depth), self.ticker_info.min_ticksize);
+        self.prev_id = 0;
+    }
+
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        match feed_de(payload, self.market) {
+            Ok(StreamData::Trade(de_trade)) => {
+                self.trades_buffer.push(Trade {
+                    time: de_trade.time,
+                    is_sell: de_trade.is_sell,
+                    price: Price::from_f32(de_trade.price)
+                        .round_to_min_tick(self.ticker_info.min_ticksize),
+                    qty: calc_qty(de_trade.qty, de_trade.price, None, self.size_in_quote_ccy),
+                    sub_ms_nanos: 0,
+                });
+                vec![]
+            }
+            Ok(StreamData::Depth(depth)) => self.on_depth(depth).into_iter().collect(),
+            Ok(StreamData::Kline(..)) | Err(_) => vec![],
+        }
+    }
+}
+
 pub fn connect_market_stream(
     ticker_info: TickerInfo,
     push_freq: PushFrequency,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let ticker = ticker_info.ticker;
 
         let (symbol_str, market) = ticker.to_full_symbol_and_type();
         let exchange = exchange_from_market_type(market);
 
-        let mut orderbook: LocalDepthCache = LocalDepthCache::default();
-        let mut trades_buffer: Vec<Trade> = Vec::new();
-        let mut already_fetching: bool = false;
-        let mut prev_id: u64 = 0;
-
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
         let contract_size = None;
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
 
         loop {
             match &mut state {
                 State::Disconnected => {
+                    if wait_out_market_close(exchange, &mut output).await {
+                        continue;
+                    }
+
                     let stream_1 = format!("{}@aggTrade", symbol_str.to_lowercase());
                     let stream_2 = format!("{}@depth@100ms", symbol_str.to_lowercase());
 
@@ -317,11 +429,10 @@ pub fn connect_market_stream(
                         });
                         match rx.await {
                             Ok(Ok(depth)) => {
-                                orderbook
-                                    .update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
-                                prev_id = 0;
+                                feed.snapshot(depth);
 
                                 state = State::Connected(websocket);
+                                backoff.reset();
 
                                 let _ = output.send(Event::Connected(exchange)).await;
                             }
@@ -332,6 +443,7 @@ pub fn connect_market_stream(
                                         format!("Depth fetch failed: {e}"),
                                     ))
                                     .await;
+                                backoff.wait(exchange, &mut output).await;
                             }
                             Err(e) => {
                                 let _ = output
@@ -340,121 +452,44 @@ pub fn connect_market_stream(
                                         format!("Channel error: {e}"),
                                     ))
                                     .await;
+                                backoff.wait(exchange, &mut output).await;
                             }
                         }
                     } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
                         let _ = output
                             .send(Event::Disconnected(
                                 exchange,
                                 "Failed to connect to websocket".to_string(),
                             ))
                             .await;
+                        backoff.wait(exchange, &mut output).await;
                     }
                 }
                 State::Connected(ws) => {
-                    match ws.read_frame().await {
+                    match telemetry.read_frame(exchange, ws, &mut output).await {
                         Ok(msg) => match msg.opcode {
                             OpCode::Text => {
-                                if let Ok(data) = feed_de(&msg.payload[..], market) {
-                                    match data {
-                                        StreamData::Trade(de_trade) => {
-                                            let price = Price::from_f32(de_trade.price)
-                                                .round_to_min_tick(ticker_info.min_ticksize);
-                                            // For Perp volume, Qty is usually in base?
-                                            // Binance Linear Futures: qty is in base asset (e.g. BTC)
-                                            // So logic is similar.
-                                            let qty = if size_in_quote_ccy {
-                                                (de_trade.qty * de_trade.price).round()
-                                            } else {
-                                                de_trade.qty
-                                            };
-
-                                            let trade = Trade {
-                                                time: de_trade.time,
-                                                is_sell: de_trade.is_sell,
-                                                price,
-                                                qty,
-                                            };
-
-                                            trades_buffer.push(trade);
+                                for step in feed.frame(&msg.payload[..]) {
+                                    match step {
+                                        Step::Emit(event) => {
+                                            feed::emit(event, &mut output).await;
                                         }
-                                        StreamData::Depth(depth_type) => {
-                                            if already_fetching {
-                                                continue;
-                                            }
-
-                                            let last_update_id = orderbook.last_update_id;
-
-                                            match depth_type {
-                                                SonicDepth::Perp(ref de_depth) => {
-                                                    if (de_depth.final_id <= last_update_id)
-                                                        || last_update_id == 0
-                                                    {
-                                                        continue;
-                                                    }
-
-                                                    if prev_id == 0
-                                                        && (de_depth.first_id > last_update_id + 1)
-                                                        || (last_update_id + 1 > de_depth.final_id)
-                                                    {
-                                                        log::warn!(
-                                                            "Out of sync at first event. Trying to resync...\n"
-                                                        );
-
-                                                        try_resync(
-                                                            exchange,
-                                                            ticker_info,
-                                                            contract_size,
-                                                            &mut orderbook,
-                                                            &mut state,
-                                                            &mut output,
-                                                            &mut already_fetching,
-                                                        )
-                                                        .await;
-                                                    }
-
-                                                    if (prev_id == 0)
-                                                        || (prev_id == de_depth.prev_final_id)
-                                                    {
-                                                        orderbook.update(
-                                                            DepthUpdate::Diff(new_depth_cache(
-                                                                &depth_type,
-                                                                contract_size,
-                                                            )),
-                                                            ticker_info.min_ticksize,
-                                                        );
-
-                                                        let _ = output
-                                                            .send(Event::DepthReceived(
-                                                                StreamKind::DepthAndTrades {
-                                                                    ticker_info,
-                                                                    depth_aggr:
-                                                                        StreamTicksize::Client,
-                                                                    push_freq,
-                                                                },
-                                                                de_depth.time,
-                                                                orderbook.depth.clone(),
-                                                                std::mem::take(&mut trades_buffer)
-                                                                    .into_boxed_slice(),
-                                                            ))
-                                                            .await;
-
-                                                        prev_id = de_depth.final_id;
-                                                    } else {
-                                                        state = State::Disconnected;
-                                                        let _ = output.send(
-                                                                Event::Disconnected(
-                                                                    exchange,
-                                                                    format!("Out of sync. Expected update_id: {}, got: {}", de_depth.prev_final_id, prev_id)
-                                                                )
-                                                            ).await;
-                                                    }
-                                                }
-                                            }
+                                        Step::Resync => {
+                                            try_resync(
+                                                exchange,
+                                                ticker,
+                                                &mut feed,
+                                                &mut state,
+                                                &mut output,
+                                            )
+                                            .await;
+                                        }
+                                        Step::Reconnect(reason) => {
+                                            state = State::Disconnected;
+                                            let _ = output
+                                                .send(Event::Disconnected(exchange, reason))
+                                                .await;
                                         }
-                                        _ => {}
                                     }
                                 }
                             }
@@ -577,24 +612,91 @@ fn calc_qty(qty: f32, price: f32, contract_size: Option<f32>, size_in_quote_ccy:
     }
 }
 
+/// Klines of a set of pairs and timeframes
+pub struct KlineFeed {
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: Vec<(TickerInfo, Timeframe)>, market: MarketKind) -> Self {
+        Self {
+            streams,
+            market,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(StreamData::Kline(ticker, de_kline)) = feed_de(payload, self.market) else {
+            return vec![];
+        };
+
+        let Some(&(ticker_info, timeframe)) = self
+            .streams
+            .iter()
+            .find(|(info, tf)| info.ticker == ticker && tf.to_string() == de_kline.interval)
+        else {
+            log::error!("Ticker info not found for ticker: {}", ticker);
+            return vec![];
+        };
+
+        let (buy_volume, sell_volume) = {
+            let buy_volume = de_kline.taker_buy_base_asset_volume;
+            let sell_volume = de_kline.volume - buy_volume;
+
+            if self.size_in_quote_ccy {
+                (
+                    (buy_volume * de_kline.close).round(),
+                    (sell_volume * de_kline.close).round(),
+                )
+            } else {
+                (buy_volume, sell_volume)
+            }
+        };
+
+        let kline = Kline::new(
+            de_kline.time,
+            de_kline.open,
+            de_kline.high,
+            de_kline.low,
+            de_kline.close,
+            (buy_volume, sell_volume),
+            ticker_info.min_ticksize,
+        );
+
+        vec![Step::Emit(Event::KlineReceived(
+            StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            },
+            kline,
+        ))]
+    }
+}
+
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
     market: MarketKind,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
         let exchange = exchange_from_market_type(market);
 
-        let ticker_info_map = streams
-            .iter()
-            .map(|(ticker_info, _)| (ticker_info.ticker, *ticker_info))
-            .collect::<HashMap<Ticker, TickerInfo>>();
-
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut feed = KlineFeed::new(streams.clone(), market);
 
         loop {
             match &mut state {
                 State::Disconnected => {
+                    if wait_out_market_close(exchange, &mut output).await {
+                        continue;
+                    }
+
                     let stream_str = streams
                         .iter()
                         .map(|(ticker_info, timeframe)| {
@@ -613,69 +715,27 @@ pub fn connect_kline_stream(
 
                     if let Ok(websocket) = connect_ws(domain, &url).await {
                         state = State::Connected(websocket);
+                        backoff.reset();
                         let _ = output.send(Event::Connected(exchange)).await;
                     } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
                         let _ = output
                             .send(Event::Disconnected(
                                 exchange,
                                 "Failed to connect to websocket".to_string(),
                             ))
                             .await;
+                        backoff.wait(exchange, &mut output).await;
                     }
                 }
-                State::Connected(ws) => match ws.read_frame().await {
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
                     Ok(msg) => match msg.opcode {
                         OpCode::Text => {
-                            if let Ok(StreamData::Kline(ticker, de_kline)) =
-                                feed_de(&msg.payload[..], market)
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
                             {
-                                let (buy_volume, sell_volume) = {
-                                    let buy_volume = de_kline.taker_buy_base_asset_volume;
-                                    let sell_volume = de_kline.volume - buy_volume;
-
-                                    if size_in_quote_ccy {
-                                        (
-                                            (buy_volume * de_kline.close).round(),
-                                            (sell_volume * de_kline.close).round(),
-                                        )
-                                    } else {
-                                        (buy_volume, sell_volume)
-                                    }
-                                };
-
-                                if let Some((_, tf)) = streams
-                                    .iter()
-                                    .find(|(_, tf)| tf.to_string() == de_kline.interval)
-                                {
-                                    if let Some(info) = ticker_info_map.get(&ticker) {
-                                        let ticker_info = *info;
-                                        let timeframe = *tf;
-
-                                        let kline = Kline::new(
-                                            de_kline.time,
-                                            de_kline.open,
-                                            de_kline.high,
-                                            de_kline.low,
-                                            de_kline.close,
-                                            (buy_volume, sell_volume),
-                                            info.min_ticksize,
-                                        );
-
-                                        let _ = output
-                                            .send(Event::KlineReceived(
-                                                StreamKind::Kline {
-                                                    ticker_info,
-                                                    timeframe,
-                                                },
-                                                kline,
-                                            ))
-                                            .await;
-                                    } else {
-                                        log::error!("Ticker info not found for ticker: {}", ticker);
-                                    }
-                                }
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
                             }
                         }
                         OpCode::Close => {
@@ -812,7 +872,9 @@ pub async fn fetch_ticksize() -> Result<HashMap<Ticker, Option<TickerInfo>>, Ada
     let url = LINEAR_PERP_Domain.to_string() + "/fapi/v1/exchangeInfo";
     let weight = 20;
 
-    let response_text = crate::limiter::HTTP_CLIENT
+    crate::network::check_url(&url)?;
+
+    let response_text = crate::limiter::http_client()
         .get(&url)
         .send()
         .await
@@ -984,6 +1046,31 @@ pub async fn fetch_ticker_prices() -> Result<HashMap<Ticker, TickerStats>, Adapt
     Ok(ticker_price_map)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DePremiumIndex {
+    #[serde(deserialize_with = "de_string_to_f32")]
+    pub last_funding_rate: f32,
+    pub next_funding_time: u64,
+}
+
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    let (ticker_str, market) = ticker.to_full_symbol_and_type();
+    let url = format!("{LINEAR_PERP_Domain}/fapi/v1/premiumIndex?symbol={ticker_str}");
+
+    let limiter = limiter_from_market_type(market);
+    let text = crate::limiter::http_request_with_limiter(&url, limiter, 1, None, None).await?;
+
+    let premium_index: DePremiumIndex = serde_json::from_str(&text)
+        .map_err(|e| AdapterError::ParseError(format!("Failed to parse premium index: {e}")))?;
+
+    Ok(FundingInfo {
+        rate: premium_index.last_funding_rate,
+        predicted_rate: None,
+        next_funding_time: premium_index.next_funding_time,
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct DeOpenInterest {
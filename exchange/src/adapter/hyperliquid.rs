@@ -1,10 +1,11 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, Price, PushFrequency, SizeUnit, StreamKind, TickMultiplier,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
-        connect::{State, connect_ws},
+        Exchange, FundingInfo, Kline, MarketKind, Price, PushFrequency, SizeUnit, StreamKind,
+        TickMultiplier, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
         de_string_to_f32,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
         limiter::{self, RateLimiter},
         volume_size_unit,
     },
@@ -28,7 +29,7 @@ use tokio::sync::Mutex;
 const API_DOMAIN: &str = "https://api.hyperliquid.xyz";
 const WS_DOMAIN: &str = "api.hyperliquid.xyz";
 
-const _MAX_DECIMALS_SPOT: u8 = 8;
+const MAX_DECIMALS_SPOT: u8 = 8;
 const MAX_DECIMALS_PERP: u8 = 6;
 
 const ALLOWED_MANTISSA: [i32; 3] = [1, 2, 5];
@@ -93,6 +94,8 @@ impl HyperliquidLimiter {
 }
 
 impl RateLimiter for HyperliquidLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Hyperliquid;
+
     fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
         self.bucket.calculate_wait_time(weight)
     }
@@ -104,6 +107,10 @@ impl RateLimiter for HyperliquidLimiter {
     fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
         response.status() == 429
     }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
 }
 
 // Unified structure for both perp and spot asset info
@@ -242,6 +249,43 @@ pub async fn fetch_ticker_prices(
     Ok(ticker_stats_map)
 }
 
+/// Hyperliquid settles funding every hour, on the hour
+const FUNDING_INTERVAL_MS: u64 = 3_600_000;
+
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    let (symbol, _) = ticker.to_full_symbol_and_type();
+
+    // HIP-3 listings are namespaced as "dex:COIN"
+    let body = match symbol.split_once(':') {
+        Some((dex, _)) => json!({ "type": "metaAndAssetCtxs", "dex": dex }),
+        None => json!({ "type": "metaAndAssetCtxs" }),
+    };
+
+    let response_json: Value = post_info(&body).await?;
+
+    let asset_idx = response_json[0]["universe"]
+        .as_array()
+        .and_then(|universe| {
+            universe
+                .iter()
+                .position(|asset| asset["name"].as_str() == Some(symbol.as_str()))
+        })
+        .ok_or_else(|| AdapterError::InvalidRequest(format!("Unknown asset: {symbol}")))?;
+
+    let rate = response_json[1][asset_idx]["funding"]
+        .as_str()
+        .and_then(|s| s.parse::<f32>().ok())
+        .ok_or_else(|| AdapterError::ParseError("Funding rate not found".to_string()))?;
+
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+
+    Ok(FundingInfo {
+        rate,
+        predicted_rate: None,
+        next_funding_time: (now / FUNDING_INTERVAL_MS + 1) * FUNDING_INTERVAL_MS,
+    })
+}
+
 async fn post_info<T: DeserializeOwned>(body: &Value) -> Result<T, AdapterError> {
     let url = format!("{}/info", API_DOMAIN);
     let response_text = limiter::http_request_with_limiter(
@@ -635,8 +679,9 @@ fn compute_tick_size(price: f32, sz_decimals: u32, market: MarketKind) -> f32 {
         return 0.001;
     }
 
+    // prices carry at most `MAX_DECIMALS - szDecimals` decimals on top of the sig-fig rule
     let max_system_decimals = match market {
-        MarketKind::LinearPerps => MAX_DECIMALS_PERP as i32,
+        MarketKind::Spot => MAX_DECIMALS_SPOT as i32,
         _ => MAX_DECIMALS_PERP as i32,
     };
     let decimal_cap = (max_system_decimals - sz_decimals as i32).max(0);
@@ -781,6 +826,100 @@ fn parse_websocket_message(payload: &[u8]) -> Result<StreamData, AdapterError> {
     }
 }
 
+/// Trades and the book of one coin. Every `l2Book` message is a whole book, already
+/// aggregated by the server
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    user_multiplier: u16,
+    size_in_quote_ccy: bool,
+    local_depth_cache: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+}
+
+impl MarketFeed {
+    pub fn new(
+        ticker_info: TickerInfo,
+        tick_multiplier: Option<TickMultiplier>,
+        push_freq: PushFrequency,
+    ) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            user_multiplier: tick_multiplier.unwrap_or(TickMultiplier(1)).0,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            local_depth_cache: LocalDepthCache::default(),
+            trades_buffer: Vec::new(),
+        }
+    }
+
+    fn qty(&self, sz: f32, px: f32) -> f32 {
+        if self.size_in_quote_ccy {
+            (sz * px).round()
+        } else {
+            sz
+        }
+    }
+}
+
+impl Feed for MarketFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let min_ticksize = self.ticker_info.min_ticksize;
+
+        match parse_websocket_message(payload) {
+            Ok(StreamData::Trade(trades)) => {
+                for hl_trade in trades {
+                    let trade = Trade {
+                        time: hl_trade.time,
+                        is_sell: hl_trade.side == "A", // A for Ask/Sell, B for Bid/Buy
+                        price: Price::from_f32(hl_trade.px).round_to_min_tick(min_ticksize),
+                        qty: self.qty(hl_trade.sz, hl_trade.px),
+                        sub_ms_nanos: 0,
+                    };
+                    self.trades_buffer.push(trade);
+                }
+                vec![]
+            }
+            Ok(StreamData::Depth(depth)) => {
+                let to_orders = |levels: &[HyperliquidLevel]| {
+                    levels
+                        .iter()
+                        .map(|level| DeOrder {
+                            price: level.px,
+                            qty: self.qty(level.sz, level.px),
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                let depth_payload = DepthPayload {
+                    last_update_id: depth.time,
+                    time: depth.time,
+                    bids: to_orders(&depth.levels[0]),
+                    asks: to_orders(&depth.levels[1]),
+                };
+                self.local_depth_cache
+                    .update(DepthUpdate::Snapshot(depth_payload), min_ticksize);
+
+                let stream_kind = StreamKind::DepthAndTrades {
+                    ticker_info: self.ticker_info,
+                    depth_aggr: super::StreamTicksize::ServerSide(TickMultiplier(
+                        self.user_multiplier,
+                    )),
+                    push_freq: self.push_freq,
+                };
+
+                vec![Step::Emit(Event::DepthReceived(
+                    stream_kind,
+                    depth.time,
+                    self.local_depth_cache.depth.clone(),
+                    std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+                ))]
+            }
+            Ok(StreamData::Kline(_)) | Err(_) => vec![],
+        }
+    }
+}
+
 pub fn connect_market_stream(
     ticker_info: TickerInfo,
     tick_multiplier: Option<TickMultiplier>,
@@ -788,14 +927,13 @@ pub fn connect_market_stream(
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let ticker = ticker_info.ticker;
         let exchange = ticker.exchange;
 
-        let mut local_depth_cache = LocalDepthCache::default();
-        let mut trades_buffer = Vec::new();
-
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut feed = MarketFeed::new(ticker_info, tick_multiplier, push_freq);
         let user_multiplier = tick_multiplier.unwrap_or(TickMultiplier(1)).0;
 
         let (symbol_str, _) = ticker.to_full_symbol_and_type();
@@ -816,7 +954,7 @@ pub fn connect_market_stream(
                         }
                     };
                     if price.is_none() {
-                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        backoff.wait(exchange, &mut output).await;
                         continue;
                     }
                     let price = price.unwrap();
@@ -858,7 +996,7 @@ pub fn connect_market_stream(
                                 .await
                                 .is_err()
                             {
-                                tokio::time::sleep(Duration::from_secs(1)).await;
+                                backoff.wait(exchange, &mut output).await;
                                 continue;
                             }
 
@@ -883,108 +1021,35 @@ pub fn connect_market_stream(
                                 .await
                                 .is_err()
                             {
-                                tokio::time::sleep(Duration::from_secs(1)).await;
+                                backoff.wait(exchange, &mut output).await;
                                 continue;
                             }
 
                             state = State::Connected(websocket);
+                            backoff.reset();
                             let _ = output.send(Event::Connected(exchange)).await;
                         }
                         Err(_) => {
-                            tokio::time::sleep(Duration::from_secs(1)).await;
                             let _ = output
                                 .send(Event::Disconnected(
                                     exchange,
                                     "Failed to connect to websocket".to_string(),
                                 ))
                                 .await;
+                            backoff.wait(exchange, &mut output).await;
                         }
                     }
                 }
                 State::Connected(websocket) => {
-                    match websocket.read_frame().await {
+                    match telemetry.read_frame(exchange, websocket, &mut output).await {
                         Ok(msg) => match msg.opcode {
                             OpCode::Text => {
-                                if let Ok(stream_data) = parse_websocket_message(&msg.payload) {
-                                    match stream_data {
-                                        StreamData::Trade(trades) => {
-                                            for hl_trade in trades {
-                                                let price = Price::from_f32(hl_trade.px)
-                                                    .round_to_min_tick(ticker_info.min_ticksize);
-                                                let qty = if size_in_quote_ccy {
-                                                    (hl_trade.sz * hl_trade.px).round()
-                                                } else {
-                                                    hl_trade.sz
-                                                };
-
-                                                let trade = Trade {
-                                                    time: hl_trade.time,
-                                                    is_sell: hl_trade.side == "A", // A for Ask/Sell, B for Bid/Buy
-                                                    price,
-                                                    qty,
-                                                };
-                                                trades_buffer.push(trade);
-                                            }
-                                        }
-                                        StreamData::Depth(depth) => {
-                                            let bids = depth.levels[0]
-                                                .iter()
-                                                .map(|level| DeOrder {
-                                                    price: level.px,
-                                                    qty: if size_in_quote_ccy {
-                                                        (level.sz * level.px).round()
-                                                    } else {
-                                                        level.sz
-                                                    },
-                                                })
-                                                .collect();
-                                            let asks = depth.levels[1]
-                                                .iter()
-                                                .map(|level| DeOrder {
-                                                    price: level.px,
-                                                    qty: if size_in_quote_ccy {
-                                                        (level.sz * level.px).round()
-                                                    } else {
-                                                        level.sz
-                                                    },
-                                                })
-                                                .collect();
-
-                                            let depth_payload = DepthPayload {
-                                                last_update_id: depth.time,
-                                                time: depth.time,
-                                                bids,
-                                                asks,
-                                            };
-                                            local_depth_cache.update(
-                                                DepthUpdate::Snapshot(depth_payload),
-                                                ticker_info.min_ticksize,
-                                            );
-
-                                            let stream_kind = StreamKind::DepthAndTrades {
-                                                ticker_info,
-                                                depth_aggr: super::StreamTicksize::ServerSide(
-                                                    TickMultiplier(user_multiplier),
-                                                ),
-                                                push_freq,
-                                            };
-                                            let current_depth = local_depth_cache.depth.clone();
-                                            let trades = std::mem::take(&mut trades_buffer)
-                                                .into_boxed_slice();
-
-                                            let _ = output
-                                                .send(Event::DepthReceived(
-                                                    stream_kind,
-                                                    depth.time,
-                                                    current_depth,
-                                                    trades,
-                                                ))
-                                                .await;
-                                        }
-                                        StreamData::Kline(_) => {
-                                            // Handle kline data if needed for depth stream
-                                        }
-                                    }
+                                if let Some(reason) =
+                                    feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                                {
+                                    state = State::Disconnected;
+                                    let _ =
+                                        output.send(Event::Disconnected(exchange, reason)).await;
                                 }
                             }
                             OpCode::Close => {
@@ -1017,19 +1082,73 @@ pub fn connect_market_stream(
     })
 }
 
+/// Candles of a set of coins and intervals
+pub struct KlineFeed {
+    streams: Vec<(TickerInfo, Timeframe)>,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: Vec<(TickerInfo, Timeframe)>) -> Self {
+        Self {
+            streams,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(StreamData::Kline(hl_kline)) = parse_websocket_message(payload) else {
+            return vec![];
+        };
+        let Some(&(ticker_info, timeframe)) = self.streams.iter().find(|(t, tf)| {
+            t.ticker.as_str() == hl_kline.symbol && tf.to_string() == hl_kline.interval.as_str()
+        }) else {
+            return vec![];
+        };
+
+        let volume = if self.size_in_quote_ccy {
+            (hl_kline.volume * hl_kline.close).round()
+        } else {
+            hl_kline.volume
+        };
+
+        let kline = Kline::new(
+            hl_kline.time,
+            hl_kline.open,
+            hl_kline.high,
+            hl_kline.low,
+            hl_kline.close,
+            (-1.0, volume),
+            ticker_info.min_ticksize,
+        );
+
+        vec![Step::Emit(Event::KlineReceived(
+            StreamKind::Kline {
+                ticker_info,
+                timeframe,
+            },
+            kline,
+        ))]
+    }
+}
+
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
     _market: MarketKind,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let exchange = streams
             .first()
             .map(|(t, _)| t.exchange())
             .unwrap_or(Exchange::HyperliquidLinear);
 
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut feed = KlineFeed::new(streams.clone());
 
         loop {
             match &mut state {
@@ -1061,76 +1180,56 @@ pub fn connect_kline_stream(
                         }
 
                         state = State::Connected(websocket);
+                        backoff.reset();
                         let _ = output.send(Event::Connected(exchange)).await;
                     }
                     Err(_) => {
-                        tokio::time::sleep(Duration::from_secs(1)).await;
                         let _ = output
                             .send(Event::Disconnected(
                                 exchange,
                                 "Failed to connect to websocket".to_string(),
                             ))
                             .await;
+                        backoff.wait(exchange, &mut output).await;
                     }
                 },
-                State::Connected(websocket) => match websocket.read_frame().await {
-                    Ok(msg) => match msg.opcode {
-                        OpCode::Text => {
-                            if let Ok(StreamData::Kline(hl_kline)) =
-                                parse_websocket_message(&msg.payload)
-                                && let Some((ticker_info, timeframe)) =
-                                    streams.iter().find(|(t, tf)| {
-                                        t.ticker.as_str() == hl_kline.symbol
-                                            && tf.to_string() == hl_kline.interval.as_str()
-                                    })
-                            {
-                                let volume = if size_in_quote_ccy {
-                                    (hl_kline.volume * hl_kline.close).round()
-                                } else {
-                                    hl_kline.volume
-                                };
-
-                                let kline = Kline::new(
-                                    hl_kline.time,
-                                    hl_kline.open,
-                                    hl_kline.high,
-                                    hl_kline.low,
-                                    hl_kline.close,
-                                    (-1.0, volume),
-                                    ticker_info.min_ticksize,
-                                );
-
-                                let stream_kind = StreamKind::Kline {
-                                    ticker_info: *ticker_info,
-                                    timeframe: *timeframe,
-                                };
-                                let _ = output.send(Event::KlineReceived(stream_kind, kline)).await;
+                State::Connected(websocket) => {
+                    match telemetry.read_frame(exchange, websocket, &mut output).await {
+                        Ok(msg) => match msg.opcode {
+                            OpCode::Text => {
+                                if let Some(reason) =
+                                    feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                                {
+                                    state = State::Disconnected;
+                                    let _ =
+                                        output.send(Event::Disconnected(exchange, reason)).await;
+                                }
                             }
-                        }
-                        OpCode::Close => {
+                            OpCode::Close => {
+                                state = State::Disconnected;
+                                let _ = output
+                                    .send(Event::Disconnected(
+                                        exchange,
+                                        "WebSocket closed".to_string(),
+                                    ))
+                                    .await;
+                            }
+                            OpCode::Ping => {
+                                let _ = websocket.write_frame(Frame::pong(msg.payload)).await;
+                            }
+                            _ => {}
+                        },
+                        Err(e) => {
                             state = State::Disconnected;
                             let _ = output
                                 .send(Event::Disconnected(
                                     exchange,
-                                    "WebSocket closed".to_string(),
+                                    format!("WebSocket error: {}", e),
                                 ))
                                 .await;
                         }
-                        OpCode::Ping => {
-                            let _ = websocket.write_frame(Frame::pong(msg.payload)).await;
-                        }
-                        _ => {}
-                    },
-                    Err(e) => {
-                        state = State::Disconnected;
-                        let _ = output
-                            .send(Event::Disconnected(
-                                exchange,
-                                format!("WebSocket error: {}", e),
-                            ))
-                            .await;
                     }
-                },
+                }
             }
         }
     })
@@ -1229,6 +1328,22 @@ mod tests {
         best
     }
 
+    #[test]
+    fn tick_size_follows_sig_figs_and_decimal_caps() {
+        // 5 sig figs, integer prices above that
+        assert_eq!(
+            compute_tick_size(123_456.0, 5, MarketKind::LinearPerps),
+            1.0
+        );
+        assert!((compute_tick_size(2_345.6, 4, MarketKind::LinearPerps) - 0.1).abs() < 1e-6);
+
+        // perps cap decimals at 6 - szDecimals, spot at 8 - szDecimals
+        let perp = compute_tick_size(0.012_345, 2, MarketKind::LinearPerps);
+        let spot = compute_tick_size(0.012_345, 2, MarketKind::Spot);
+        assert!((perp - 1e-4).abs() < 1e-9);
+        assert!((spot - 1e-6).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn manual_depth_cfg() {
         let symbol = "BTC";
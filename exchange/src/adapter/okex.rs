@@ -1,6 +1,7 @@
 use crate::{
-    OpenInterest, Price, PushFrequency, SizeUnit,
+    FundingInfo, OpenInterest, Price, PushFrequency, SizeUnit,
     adapter::{StreamKind, StreamTicksize},
+    feed::{self, Feed, Step},
     limiter::{self, RateLimiter},
     volume_size_unit,
 };
@@ -8,9 +9,9 @@ use crate::{
 use super::{
     super::{
         Exchange, Kline, MarketKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
-        connect::{State, connect_ws},
+        connect::{Backoff, ReconnectPolicy, State, Telemetry, connect_ws},
         de_string_to_f32, de_string_to_u64, is_symbol_supported,
-        limiter::HTTP_CLIENT,
+        limiter::http_client,
     },
     AdapterError, Event,
 };
@@ -51,6 +52,8 @@ impl OkexLimiter {
 }
 
 impl RateLimiter for OkexLimiter {
+    const VENUE: super::ExchangeInclusive = super::ExchangeInclusive::Okex;
+
     fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
         self.bucket.calculate_wait_time(weight)
     }
@@ -62,6 +65,10 @@ impl RateLimiter for OkexLimiter {
     fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
         response.status() == 429
     }
+
+    fn usage(&self) -> limiter::Usage {
+        self.bucket.usage()
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -76,6 +83,14 @@ struct SonicTrade {
     pub is_sell: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct DeHistTrade {
+    #[serde(rename = "tradeId", deserialize_with = "de_string_to_u64")]
+    trade_id: u64,
+    #[serde(flatten)]
+    trade: SonicTrade,
+}
+
 struct SonicDepth {
     pub update_id: u64,
     pub bids: Vec<DeOrder>,
@@ -163,6 +178,7 @@ async fn try_connect(
     exchange: Exchange,
     output: &mut mpsc::Sender<Event>,
     topic: &str,
+    backoff: &mut Backoff,
 ) -> State {
     let url = format!("wss://{WS_DOMAIN}/ws/v5/{topic}");
 
@@ -180,35 +196,139 @@ async fn try_connect(
                         format!("Failed subscribing: {e}"),
                     ))
                     .await;
+                backoff.wait(exchange, output).await;
                 return State::Disconnected;
             }
 
+            backoff.reset();
             let _ = output.send(Event::Connected(exchange)).await;
             State::Connected(websocket)
         }
         Err(err) => {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
             let _ = output
                 .send(Event::Disconnected(
                     exchange,
                     format!("Failed to connect: {err}"),
                 ))
                 .await;
+            backoff.wait(exchange, output).await;
             State::Disconnected
         }
     }
 }
 
+/// Trades and the local book of one instrument, restarting from every snapshot the
+/// `books` channel sends
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    size_in_quote_ccy: bool,
+    contract_size: Option<f32>,
+    orderbook: LocalDepthCache,
+    trades_buffer: Vec<Trade>,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            contract_size: ticker_info.contract_size.map(f32::from),
+            orderbook: LocalDepthCache::default(),
+            trades_buffer: vec![],
+        }
+    }
+
+    fn qty(&self, qty: f32, price: f32) -> f32 {
+        calc_qty(
+            qty,
+            price,
+            self.size_in_quote_ccy,
+            self.contract_size,
+            self.ticker_info.market_type(),
+        )
+    }
+}
+
+impl Feed for MarketFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let min_ticksize = self.ticker_info.min_ticksize;
+
+        let Ok(data) = feed_de(payload, self.ticker_info.ticker) else {
+            return vec![];
+        };
+
+        match data {
+            StreamData::Trade(de_trade_vec) => {
+                for de_trade in &de_trade_vec {
+                    let trade = Trade {
+                        time: de_trade.time,
+                        is_sell: de_trade.is_sell == "sell" || de_trade.is_sell == "SELL",
+                        price: Price::from_f32(de_trade.price).round_to_min_tick(min_ticksize),
+                        qty: self.qty(de_trade.qty, de_trade.price),
+                        sub_ms_nanos: 0,
+                    };
+                    self.trades_buffer.push(trade);
+                }
+                vec![]
+            }
+            StreamData::Depth(de_depth, data_type, time) => {
+                let to_orders = |levels: &[DeOrder]| {
+                    levels
+                        .iter()
+                        .map(|x| DeOrder {
+                            price: x.price,
+                            qty: self.qty(x.qty, x.price),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                let depth = DepthPayload {
+                    last_update_id: de_depth.update_id,
+                    time,
+                    bids: to_orders(&de_depth.bids),
+                    asks: to_orders(&de_depth.asks),
+                };
+
+                if (data_type == "snapshot") || (depth.last_update_id == 1) {
+                    self.orderbook
+                        .update(DepthUpdate::Snapshot(depth), min_ticksize);
+                    return vec![];
+                }
+                if data_type != "delta" {
+                    return vec![];
+                }
+
+                self.orderbook
+                    .update(DepthUpdate::Diff(depth), min_ticksize);
+
+                vec![Step::Emit(Event::DepthReceived(
+                    StreamKind::DepthAndTrades {
+                        ticker_info: self.ticker_info,
+                        depth_aggr: StreamTicksize::Client,
+                        push_freq: self.push_freq,
+                    },
+                    time,
+                    self.orderbook.depth.clone(),
+                    std::mem::take(&mut self.trades_buffer).into_boxed_slice(),
+                ))]
+            }
+        }
+    }
+}
+
 pub fn connect_market_stream(
     ticker_info: TickerInfo,
     push_freq: PushFrequency,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state: State = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
         let ticker = ticker_info.ticker;
 
-        let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
+        let (symbol_str, _) = ticker.to_full_symbol_and_type();
         let exchange = ticker.exchange;
 
         let subscribe_message = serde_json::json!({
@@ -219,106 +339,29 @@ pub fn connect_market_stream(
             ],
         });
 
-        let mut trades_buffer: Vec<Trade> = vec![];
-        let mut orderbook = LocalDepthCache::default();
-
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
-        let contract_size = ticker_info.contract_size.map(f32::from);
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
 
         loop {
             match &mut state {
                 State::Disconnected => {
-                    state = try_connect(&subscribe_message, exchange, &mut output, "public").await;
+                    state = try_connect(
+                        &subscribe_message,
+                        exchange,
+                        &mut output,
+                        "public",
+                        &mut backoff,
+                    )
+                    .await;
                 }
-                State::Connected(ws) => match ws.read_frame().await {
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
                     Ok(msg) => match msg.opcode {
                         OpCode::Text => {
-                            if let Ok(data) = feed_de(&msg.payload[..], ticker) {
-                                match data {
-                                    StreamData::Trade(de_trade_vec) => {
-                                        for de_trade in &de_trade_vec {
-                                            let price = Price::from_f32(de_trade.price)
-                                                .round_to_min_tick(ticker_info.min_ticksize);
-                                            let qty = calc_qty(
-                                                de_trade.qty,
-                                                de_trade.price,
-                                                size_in_quote_ccy,
-                                                contract_size,
-                                                market_type,
-                                            );
-
-                                            let trade = Trade {
-                                                time: de_trade.time,
-                                                is_sell: de_trade.is_sell == "sell"
-                                                    || de_trade.is_sell == "SELL",
-                                                price,
-                                                qty,
-                                            };
-                                            trades_buffer.push(trade);
-                                        }
-                                    }
-                                    StreamData::Depth(de_depth, data_type, time) => {
-                                        let depth = DepthPayload {
-                                            last_update_id: de_depth.update_id,
-                                            time,
-                                            bids: de_depth
-                                                .bids
-                                                .iter()
-                                                .map(|x| DeOrder {
-                                                    price: x.price,
-                                                    qty: calc_qty(
-                                                        x.qty,
-                                                        x.price,
-                                                        size_in_quote_ccy,
-                                                        contract_size,
-                                                        market_type,
-                                                    ),
-                                                })
-                                                .collect(),
-                                            asks: de_depth
-                                                .asks
-                                                .iter()
-                                                .map(|x| DeOrder {
-                                                    price: x.price,
-                                                    qty: calc_qty(
-                                                        x.qty,
-                                                        x.price,
-                                                        size_in_quote_ccy,
-                                                        contract_size,
-                                                        market_type,
-                                                    ),
-                                                })
-                                                .collect(),
-                                        };
-
-                                        if (data_type == "snapshot") || (depth.last_update_id == 1)
-                                        {
-                                            orderbook.update(
-                                                DepthUpdate::Snapshot(depth),
-                                                ticker_info.min_ticksize,
-                                            );
-                                        } else if data_type == "delta" {
-                                            orderbook.update(
-                                                DepthUpdate::Diff(depth),
-                                                ticker_info.min_ticksize,
-                                            );
-
-                                            let _ = output
-                                                .send(Event::DepthReceived(
-                                                    StreamKind::DepthAndTrades {
-                                                        ticker_info,
-                                                        depth_aggr: StreamTicksize::Client,
-                                                        push_freq,
-                                                    },
-                                                    time,
-                                                    orderbook.depth.clone(),
-                                                    std::mem::take(&mut trades_buffer)
-                                                        .into_boxed_slice(),
-                                                ))
-                                                .await;
-                                        }
-                                    }
-                                }
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                            {
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
                             }
                         }
                         OpCode::Close => {
@@ -347,28 +390,116 @@ pub fn connect_market_stream(
     })
 }
 
+/// Candles of a set of instruments and bars, keyed by their channel and instrument id
+pub struct KlineFeed {
+    lookup: HashMap<(String, String), (TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: &[(TickerInfo, Timeframe)], market_type: MarketKind) -> Self {
+        let lookup = streams
+            .iter()
+            .filter_map(|(ticker_info, timeframe)| {
+                let bar = timeframe_to_okx_bar(*timeframe)?;
+                let (symbol, _) = ticker_info.ticker.to_full_symbol_and_type();
+                Some(((format!("candle{bar}"), symbol), (*ticker_info, *timeframe)))
+            })
+            .collect();
+
+        Self {
+            lookup,
+            market_type,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+
+    /// Subscription arguments of every stream with a matching bar
+    fn args(&self) -> Vec<Value> {
+        self.lookup
+            .keys()
+            .map(|(channel, symbol)| serde_json::json!({ "channel": channel, "instId": symbol }))
+            .collect()
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Ok(v) = serde_json::from_slice::<Value>(payload) else {
+            return vec![];
+        };
+
+        let channel = v["arg"]["channel"].as_str().unwrap_or("");
+        if !channel.starts_with("candle") {
+            return vec![];
+        }
+        let Some(inst) = v["arg"]["instId"].as_str() else {
+            return vec![];
+        };
+        let Some(&(ticker_info, timeframe)) =
+            self.lookup.get(&(channel.to_string(), inst.to_string()))
+        else {
+            return vec![];
+        };
+        let Some(data) = v.get("data").and_then(|d| d.as_array()) else {
+            return vec![];
+        };
+
+        let contract_size = ticker_info.contract_size.map(f32::from);
+
+        data.iter()
+            .filter_map(|row| {
+                let field = |idx: usize| row.get(idx).and_then(|x| x.as_str());
+
+                let ts = field(0).and_then(|s| s.parse::<u64>().ok())?;
+                let [open, high, low, close] =
+                    [1, 2, 3, 4].map(|idx| field(idx).and_then(|s| s.parse::<f32>().ok()));
+                let (open, high, low, close) = (open?, high?, low?, close?);
+                let volume = field(5).and_then(|s| s.parse::<f32>().ok());
+
+                let volume_in_display = volume.map_or(0.0, |vq| {
+                    calc_qty(
+                        vq,
+                        close,
+                        self.size_in_quote_ccy,
+                        contract_size,
+                        self.market_type,
+                    )
+                });
+
+                let kline = Kline::new(
+                    ts,
+                    open,
+                    high,
+                    low,
+                    close,
+                    (-1.0, volume_in_display),
+                    ticker_info.min_ticksize,
+                );
+
+                Some(Step::Emit(Event::KlineReceived(
+                    StreamKind::Kline {
+                        ticker_info,
+                        timeframe,
+                    },
+                    kline,
+                )))
+            })
+            .collect()
+    }
+}
+
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
     market_type: MarketKind,
 ) -> impl Stream<Item = Event> {
     stream::channel(100, async move |mut output| {
         let mut state = State::Disconnected;
+        let mut backoff = Backoff::new(ReconnectPolicy::default());
+        let mut telemetry = Telemetry::new();
 
-        let mut args = Vec::with_capacity(streams.len());
-        let mut lookup = HashMap::new();
-        for (ticker_info, timeframe) in &streams {
-            let ticker = ticker_info.ticker;
-
-            if let Some(bar) = timeframe_to_okx_bar(*timeframe) {
-                let (symbol, _mt) = ticker.to_full_symbol_and_type();
-                let channel = format!("candle{bar}");
-                args.push(serde_json::json!({
-                    "channel": channel,
-                    "instId": symbol,
-                }));
-                lookup.insert((channel, symbol), (*ticker_info, *timeframe));
-            }
-        }
+        let mut feed = KlineFeed::new(&streams, market_type);
 
         let exchange = streams
             .first()
@@ -377,109 +508,30 @@ pub fn connect_kline_stream(
 
         let subscribe_message = serde_json::json!({
             "op": "subscribe",
-            "args": args,
+            "args": feed.args(),
         });
 
-        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
-
         loop {
             match &mut state {
                 State::Disconnected => {
-                    state =
-                        try_connect(&subscribe_message, exchange, &mut output, "business").await;
+                    state = try_connect(
+                        &subscribe_message,
+                        exchange,
+                        &mut output,
+                        "business",
+                        &mut backoff,
+                    )
+                    .await;
                 }
-                State::Connected(ws) => match ws.read_frame().await {
+                State::Connected(ws) => match telemetry.read_frame(exchange, ws, &mut output).await
+                {
                     Ok(msg) => match msg.opcode {
                         OpCode::Text => {
-                            if let Ok(v) = serde_json::from_slice::<Value>(&msg.payload[..]) {
-                                let channel = v["arg"]["channel"].as_str().unwrap_or("");
-                                if !channel.starts_with("candle") {
-                                    continue;
-                                }
-
-                                let inst = match v["arg"]["instId"].as_str() {
-                                    Some(s) => s,
-                                    None => continue,
-                                };
-                                let (ticker_info, timeframe) =
-                                    match lookup.get(&(channel.to_string(), inst.to_string())) {
-                                        Some(t) => *t,
-                                        None => continue,
-                                    };
-
-                                let contract_size = ticker_info.contract_size.map(f32::from);
-
-                                if let Some(data) = v.get("data").and_then(|d| d.as_array()) {
-                                    for row in data {
-                                        let time = row
-                                            .get(0)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<u64>().ok());
-                                        let open = row
-                                            .get(1)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<f32>().ok());
-                                        let high = row
-                                            .get(2)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<f32>().ok());
-                                        let low = row
-                                            .get(3)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<f32>().ok());
-                                        let close = row
-                                            .get(4)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<f32>().ok());
-                                        let volume = row
-                                            .get(5)
-                                            .and_then(|x| x.as_str())
-                                            .and_then(|s| s.parse::<f32>().ok());
-
-                                        let (ts, open, high, low, close) =
-                                            match (time, open, high, low, close) {
-                                                (
-                                                    Some(ts),
-                                                    Some(open),
-                                                    Some(high),
-                                                    Some(low),
-                                                    Some(close),
-                                                ) => (ts, open, high, low, close),
-                                                _ => continue,
-                                            };
-
-                                        let volume_in_display = if let Some(vq) = volume {
-                                            calc_qty(
-                                                vq,
-                                                close,
-                                                size_in_quote_ccy,
-                                                contract_size,
-                                                market_type,
-                                            )
-                                        } else {
-                                            0.0
-                                        };
-
-                                        let kline = Kline::new(
-                                            ts,
-                                            open,
-                                            high,
-                                            low,
-                                            close,
-                                            (-1.0, volume_in_display),
-                                            ticker_info.min_ticksize,
-                                        );
-                                        let _ = output
-                                            .send(Event::KlineReceived(
-                                                StreamKind::Kline {
-                                                    ticker_info,
-                                                    timeframe,
-                                                },
-                                                kline,
-                                            ))
-                                            .await;
-                                    }
-                                }
+                            if let Some(reason) =
+                                feed::forward(feed.frame(&msg.payload[..]), &mut output).await
+                            {
+                                state = State::Disconnected;
+                                let _ = output.send(Event::Disconnected(exchange, reason)).await;
                             }
                         }
                         OpCode::Close => {
@@ -569,7 +621,9 @@ pub async fn fetch_ticksize(
         inst_type
     );
 
-    let response_text = HTTP_CLIENT
+    crate::network::check_url(&url)?;
+
+    let response_text = http_client()
         .get(&url)
         .send()
         .await
@@ -642,6 +696,31 @@ pub async fn fetch_ticksize(
     Ok(map)
 }
 
+pub async fn fetch_funding_info(ticker: Ticker) -> Result<FundingInfo, AdapterError> {
+    let (ticker_str, _market) = ticker.to_full_symbol_and_type();
+    let url = format!("https://www.okx.com/api/v5/public/funding-rate?instId={ticker_str}");
+
+    let parsed_response: Value =
+        limiter::http_parse_with_limiter(&url, &OKEX_LIMITER, 1, None, None).await?;
+
+    let item = &parsed_response["data"][0];
+    let parse_field = |key: &str| item[key].as_str().and_then(|s| s.parse::<f32>().ok());
+
+    let rate = parse_field("fundingRate")
+        .ok_or_else(|| AdapterError::ParseError("Funding rate not found".to_string()))?;
+    // `fundingTime` is the upcoming settlement, `nextFundingTime` the one after it
+    let next_funding_time = item["fundingTime"]
+        .as_str()
+        .and_then(|s| s.parse::<u64>().ok())
+        .ok_or_else(|| AdapterError::ParseError("Funding time not found".to_string()))?;
+
+    Ok(FundingInfo {
+        rate,
+        predicted_rate: parse_field("nextFundingRate"),
+        next_funding_time,
+    })
+}
+
 pub async fn fetch_ticker_prices(
     market_type: MarketKind,
 ) -> Result<std::collections::HashMap<Ticker, TickerStats>, AdapterError> {
@@ -811,6 +890,60 @@ pub async fn fetch_klines(
     Ok(klines)
 }
 
+/// Page of up to 100 trades, oldest first, older than trade `after_id`,
+/// or at or before `until` ms for the first page. Also returns the id to continue before.
+pub async fn fetch_trades_before(
+    ticker_info: TickerInfo,
+    until: u64,
+    after_id: Option<u64>,
+) -> Result<(Vec<Trade>, Option<u64>), AdapterError> {
+    let (symbol_str, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let contract_size = ticker_info.contract_size.map(f32::from);
+
+    // `after` is exclusive, paging by id keeps trades sharing a millisecond across pages
+    let url = match after_id {
+        Some(id) => format!(
+            "https://www.okx.com/api/v5/market/history-trades?instId={symbol_str}&type=1&after={id}&limit=100"
+        ),
+        None => format!(
+            "https://www.okx.com/api/v5/market/history-trades?instId={symbol_str}&type=2&after={}&limit=100",
+            until.saturating_add(1)
+        ),
+    };
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &OKEX_LIMITER, 1, None, None).await?;
+
+    let de_trades: Vec<DeHistTrade> = serde_json::from_value(doc["data"].clone())
+        .map_err(|e| AdapterError::ParseError(format!("Failed to parse trades: {e}")))?;
+
+    let oldest_id = de_trades.iter().map(|t| t.trade_id).min();
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let mut trades: Vec<Trade> = de_trades
+        .into_iter()
+        .map(
+            |DeHistTrade {
+                 trade: de_trade, ..
+             }| Trade {
+                time: de_trade.time,
+                is_sell: de_trade.is_sell == "sell" || de_trade.is_sell == "SELL",
+                price: Price::from_f32(de_trade.price).round_to_min_tick(ticker_info.min_ticksize),
+                qty: calc_qty(
+                    de_trade.qty,
+                    de_trade.price,
+                    size_in_quote_ccy,
+                    contract_size,
+                    market,
+                ),
+                sub_ms_nanos: 0,
+            },
+        )
+        .collect();
+
+    trades.sort_by_key(Trade::sort_key);
+    Ok((trades, oldest_id))
+}
+
 const TRADING_STATS_DOMAIN: &str = "https://www.okx.com/api/v5/rubik/stat";
 
 pub async fn fetch_historical_oi(
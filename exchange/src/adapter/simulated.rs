@@ -0,0 +1,539 @@
+//! Made up venue for demoing without a network. Prices are a deterministic function of
+//! time, so fetched history lines up with what the streams print afterwards, and every run
+//! shows the same market
+
+use super::{
+    super::{
+        Exchange, Kline, Price, PushFrequency, SizeUnit, StreamKind, Ticker, TickerInfo,
+        TickerStats, Timeframe, Trade,
+        adapter::StreamTicksize,
+        depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        feed::{self, Feed, Step},
+        volume_size_unit,
+    },
+    AdapterError, Event,
+};
+
+use iced_futures::{
+    futures::{SinkExt, Stream},
+    stream,
+};
+
+use std::{collections::HashMap, time::Duration};
+
+/// Interval trades are generated at, also the default push rate of the book
+const TRADE_SLOT_MS: u64 = 100;
+const KLINE_PUSH: Duration = Duration::from_secs(1);
+/// Slots generated at most per frame, so a stalled stream doesn't catch up in one burst
+const MAX_SLOTS_PER_FRAME: u64 = 50;
+const DEPTH_LEVELS: i64 = 100;
+const KLINE_LIMIT: usize = 1000;
+/// Intervals a candle's range is sampled over for its high and low
+const KLINE_SAMPLES: u64 = 16;
+
+/// `(period in ms, amplitude)` of the layers of noise making up the log price, amplitudes
+/// shrink about with the square root of the period like a random walk's
+const OCTAVES: [(f64, f64); 6] = [
+    (259_200_000.0, 0.08),
+    (28_800_000.0, 0.03),
+    (3_600_000.0, 0.012),
+    (600_000.0, 0.005),
+    (60_000.0, 0.002),
+    (5_000.0, 0.0006),
+];
+
+struct Instrument {
+    symbol: &'static str,
+    price: f64,
+    ticksize: f32,
+    min_qty: f32,
+    /// Base asset traded over a day
+    daily_volume: f32,
+}
+
+const INSTRUMENTS: [Instrument; 3] = [
+    Instrument {
+        symbol: "DEMOBTCUSD",
+        price: 60_000.0,
+        ticksize: 0.1,
+        min_qty: 0.001,
+        daily_volume: 20_000.0,
+    },
+    Instrument {
+        symbol: "DEMOETHUSD",
+        price: 3_000.0,
+        ticksize: 0.01,
+        min_qty: 0.01,
+        daily_volume: 300_000.0,
+    },
+    Instrument {
+        symbol: "DEMOSOLUSD",
+        price: 150.0,
+        ticksize: 0.001,
+        min_qty: 0.1,
+        daily_volume: 4_000_000.0,
+    },
+];
+
+fn instrument(ticker: &Ticker) -> Option<(u64, &'static Instrument)> {
+    let symbol = ticker.to_full_symbol_and_type().0;
+
+    INSTRUMENTS
+        .iter()
+        .enumerate()
+        .find(|(_, instrument)| instrument.symbol == symbol)
+        .map(|(idx, instrument)| (idx as u64 + 1, instrument))
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+fn hash(seed: u64, n: u64) -> u64 {
+    splitmix64(seed.wrapping_mul(0x2545_F491_4F6C_DD1D) ^ splitmix64(n))
+}
+
+/// Uniform in 0..1
+fn unit(seed: u64, n: u64) -> f64 {
+    (hash(seed, n) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Smoothly interpolated noise in -1..1, one random value per integer `x`
+fn value_noise(seed: u64, x: f64) -> f64 {
+    let i = x.floor();
+    let f = x - i;
+    let s = f * f * (3.0 - 2.0 * f);
+
+    let a = unit(seed, i as u64) * 2.0 - 1.0;
+    let b = unit(seed, i as u64 + 1) * 2.0 - 1.0;
+    a + (b - a) * s
+}
+
+fn price_at(seed: u64, instrument: &Instrument, time: u64) -> f64 {
+    let log_offset: f64 = OCTAVES
+        .iter()
+        .enumerate()
+        .map(|(octave, &(period, amplitude))| {
+            amplitude * value_noise(seed * 16 + octave as u64, time as f64 / period)
+        })
+        .sum();
+
+    instrument.price * log_offset.exp()
+}
+
+/// Average size of a print, about two per trade slot
+fn mean_trade_qty(instrument: &Instrument) -> f32 {
+    instrument.daily_volume / (86_400.0 * 20.0)
+}
+
+fn to_size_unit(qty: f32, price: f32, size_in_quote_ccy: bool) -> f32 {
+    if size_in_quote_ccy {
+        (qty * price).round()
+    } else {
+        qty
+    }
+}
+
+/// Best bid a tick below the best ask, both around the price at `time`
+fn touch(seed: u64, instrument: &Instrument, time: u64) -> (f64, f64) {
+    let tick = f64::from(instrument.ticksize);
+    let bid = (price_at(seed, instrument, time) / tick).floor() * tick;
+    (bid, bid + tick)
+}
+
+fn slot_trades(
+    seed: u64,
+    instrument: &Instrument,
+    slot: u64,
+    size_in_quote_ccy: bool,
+) -> Vec<Trade> {
+    let slot_seed = hash(seed, slot);
+    let count = hash(slot_seed, 0) % 5;
+    let mean_qty = f64::from(mean_trade_qty(instrument));
+    let min_qty = f64::from(instrument.min_qty);
+
+    let mut trades: Vec<Trade> = (0..count)
+        .map(|n| {
+            let time = slot * TRADE_SLOT_MS + hash(slot_seed, n * 3 + 1) % TRADE_SLOT_MS;
+            let is_sell = unit(slot_seed, n * 3 + 2) < 0.5;
+
+            // exponential sizes, rounded to the lot size
+            let qty =
+                (-(1.0 - unit(slot_seed, n * 3 + 3)).ln() * mean_qty / min_qty).ceil() * min_qty;
+
+            let (bid, ask) = touch(seed, instrument, time);
+            let price = if is_sell { bid as f32 } else { ask as f32 };
+
+            Trade {
+                time,
+                is_sell,
+                price: Price::from_f32(price),
+                qty: to_size_unit(qty as f32, price, size_in_quote_ccy),
+                sub_ms_nanos: 0,
+            }
+        })
+        .collect();
+
+    trades.sort_by_key(Trade::sort_key);
+    trades
+}
+
+/// Full book around the touch at `time`, sizes change every couple of seconds and grow
+/// away from the spread
+fn book(seed: u64, instrument: &Instrument, time: u64, size_in_quote_ccy: bool) -> DepthPayload {
+    let tick = f64::from(instrument.ticksize);
+    let (bid, ask) = touch(seed, instrument, time);
+    let refresh = time / 2_000;
+    let mean_qty = mean_trade_qty(instrument);
+
+    let level = |price: f64, distance: i64| {
+        let level_seed = hash(seed ^ refresh, (price / tick).round() as u64);
+        let wall = if level_seed.is_multiple_of(23) {
+            8.0
+        } else {
+            1.0
+        };
+        let qty = mean_qty
+            * 4.0
+            * (0.2 + unit(level_seed, 0) as f32)
+            * (1.0 + distance as f32 * 0.05)
+            * wall;
+
+        DeOrder {
+            price: price as f32,
+            qty: to_size_unit(qty, price as f32, size_in_quote_ccy),
+        }
+    };
+
+    DepthPayload {
+        last_update_id: time,
+        time,
+        bids: (0..DEPTH_LEVELS)
+            .map(|i| level(bid - i as f64 * tick, i))
+            .collect(),
+        asks: (0..DEPTH_LEVELS)
+            .map(|i| level(ask + i as f64 * tick, i))
+            .collect(),
+    }
+}
+
+/// Candle over `start..end`, sampled rather than built from trades
+fn kline(
+    seed: u64,
+    ticker_info: &TickerInfo,
+    instrument: &Instrument,
+    start: u64,
+    end: u64,
+    size_in_quote_ccy: bool,
+) -> Kline {
+    let end = end.max(start);
+    let samples = (0..=KLINE_SAMPLES)
+        .map(|i| price_at(seed, instrument, start + (end - start) * i / KLINE_SAMPLES) as f32);
+
+    let (mut high, mut low) = (f32::MIN, f32::MAX);
+    for price in samples {
+        high = high.max(price);
+        low = low.min(price);
+    }
+
+    let open = price_at(seed, instrument, start) as f32;
+    let close = price_at(seed, instrument, end) as f32;
+
+    let activity = 0.5 + unit(seed, start / 60_000) as f32;
+    let volume = instrument.daily_volume * (end - start) as f32 / 86_400_000.0 * activity;
+    let buy_share = (0.5 + (close - open) / open * 50.0).clamp(0.2, 0.8);
+
+    Kline::new(
+        start,
+        open,
+        high,
+        low,
+        close,
+        (
+            to_size_unit(volume * buy_share, close, size_in_quote_ccy),
+            to_size_unit(volume * (1.0 - buy_share), close, size_in_quote_ccy),
+        ),
+        ticker_info.min_ticksize,
+    )
+}
+
+/// Frames of the simulated venue are the clock ticks driving it, as decimal unix ms
+fn parse_tick(payload: &[u8]) -> Option<u64> {
+    std::str::from_utf8(payload).ok()?.trim().parse().ok()
+}
+
+/// Trades and book of one made up pair, generated up to the time of each frame
+pub struct MarketFeed {
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+    size_in_quote_ccy: bool,
+    orderbook: LocalDepthCache,
+    /// Last trade slot generated, `None` before the first frame
+    last_slot: Option<u64>,
+}
+
+impl MarketFeed {
+    pub fn new(ticker_info: TickerInfo, push_freq: PushFrequency) -> Self {
+        Self {
+            ticker_info,
+            push_freq,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+            orderbook: LocalDepthCache::default(),
+            last_slot: None,
+        }
+    }
+}
+
+impl Feed for MarketFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Some(time) = parse_tick(payload) else {
+            return vec![];
+        };
+        let Some((seed, instrument)) = instrument(&self.ticker_info.ticker) else {
+            return vec![];
+        };
+
+        let slot = time / TRADE_SLOT_MS;
+        let first = match self.last_slot {
+            Some(last) if last >= slot => return vec![],
+            Some(last) => (last + 1).max(slot.saturating_sub(MAX_SLOTS_PER_FRAME - 1)),
+            None => slot,
+        };
+        self.last_slot = Some(slot);
+
+        let min_ticksize = self.ticker_info.min_ticksize;
+        let trades: Vec<Trade> = (first..=slot)
+            .flat_map(|slot| slot_trades(seed, instrument, slot, self.size_in_quote_ccy))
+            .map(|trade| Trade {
+                price: trade.price.round_to_min_tick(min_ticksize),
+                ..trade
+            })
+            .collect();
+
+        self.orderbook.update(
+            DepthUpdate::Snapshot(book(seed, instrument, time, self.size_in_quote_ccy)),
+            min_ticksize,
+        );
+
+        vec![Step::Emit(Event::DepthReceived(
+            StreamKind::DepthAndTrades {
+                ticker_info: self.ticker_info,
+                depth_aggr: StreamTicksize::Client,
+                push_freq: self.push_freq,
+            },
+            time,
+            self.orderbook.depth.clone(),
+            trades.into_boxed_slice(),
+        ))]
+    }
+}
+
+/// Forming candles of a set of made up pairs and timeframes
+pub struct KlineFeed {
+    streams: Vec<(TickerInfo, Timeframe)>,
+    size_in_quote_ccy: bool,
+}
+
+impl KlineFeed {
+    pub fn new(streams: Vec<(TickerInfo, Timeframe)>) -> Self {
+        Self {
+            streams,
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+        }
+    }
+}
+
+impl Feed for KlineFeed {
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step> {
+        let Some(time) = parse_tick(payload) else {
+            return vec![];
+        };
+
+        self.streams
+            .iter()
+            .filter_map(|&(ticker_info, timeframe)| {
+                let (seed, instrument) = instrument(&ticker_info.ticker)?;
+                let interval = timeframe.to_milliseconds();
+                let start = time - time % interval;
+
+                Some(Step::Emit(Event::KlineReceived(
+                    StreamKind::Kline {
+                        ticker_info,
+                        timeframe,
+                    },
+                    kline(
+                        seed,
+                        &ticker_info,
+                        instrument,
+                        start,
+                        time,
+                        self.size_in_quote_ccy,
+                    ),
+                )))
+            })
+            .collect()
+    }
+}
+
+pub fn connect_market_stream(
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut feed = MarketFeed::new(ticker_info, push_freq);
+
+        let interval = match push_freq {
+            PushFrequency::ServerDefault => Duration::from_millis(TRADE_SLOT_MS),
+            PushFrequency::Custom(tf) => Duration::from_millis(tf.to_milliseconds()),
+        };
+
+        let _ = output.send(Event::Connected(Exchange::Simulated)).await;
+
+        loop {
+            let tick = now_ms().to_string();
+            let _ = feed::forward(feed.frame(tick.as_bytes()), &mut output).await;
+
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+pub fn connect_kline_stream(streams: Vec<(TickerInfo, Timeframe)>) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut feed = KlineFeed::new(streams);
+
+        let _ = output.send(Event::Connected(Exchange::Simulated)).await;
+
+        loop {
+            let tick = now_ms().to_string();
+            let _ = feed::forward(feed.frame(tick.as_bytes()), &mut output).await;
+
+            tokio::time::sleep(KLINE_PUSH).await;
+        }
+    })
+}
+
+pub async fn fetch_ticksize() -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    Ok(INSTRUMENTS
+        .iter()
+        .map(|instrument| {
+            let ticker = Ticker::new(instrument.symbol, Exchange::Simulated);
+            let info = TickerInfo::new(ticker, instrument.ticksize, instrument.min_qty, None);
+            (ticker, Some(info))
+        })
+        .collect())
+}
+
+pub async fn fetch_ticker_prices() -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let now = now_ms();
+
+    Ok(INSTRUMENTS
+        .iter()
+        .enumerate()
+        .map(|(idx, instrument)| {
+            let seed = idx as u64 + 1;
+            let price = price_at(seed, instrument, now) as f32;
+            let day_ago = price_at(seed, instrument, now.saturating_sub(86_400_000)) as f32;
+
+            (
+                Ticker::new(instrument.symbol, Exchange::Simulated),
+                TickerStats {
+                    mark_price: price,
+                    daily_price_chg: (price - day_ago) / day_ago * 100.0,
+                    daily_volume: instrument.daily_volume * price,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<Kline>, AdapterError> {
+    let (seed, instrument) = instrument(&ticker_info.ticker).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Unknown ticker: {}", ticker_info.ticker))
+    })?;
+
+    let interval = timeframe.to_milliseconds();
+    let now = now_ms();
+    let (start, end) = range.unwrap_or_else(|| (now.saturating_sub(interval * 500), now));
+    let end = end.min(now);
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    Ok((start - start % interval..=end)
+        .step_by(interval as usize)
+        .take(KLINE_LIMIT)
+        .map(|open_time| {
+            let close_time = (open_time + interval).min(now);
+            kline(
+                seed,
+                &ticker_info,
+                instrument,
+                open_time,
+                close_time,
+                size_in_quote_ccy,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_and_stream_agree() {
+        let ticker_info = TickerInfo::new(
+            Ticker::new("DEMOETHUSD", Exchange::Simulated),
+            0.01,
+            0.01,
+            None,
+        );
+        let (seed, instrument) = instrument(&ticker_info.ticker).unwrap();
+        let time = 1_699_999_980_000;
+
+        let fetched = kline(seed, &ticker_info, instrument, time, time + 60_000, false);
+        let streamed = KlineFeed::new(vec![(ticker_info, Timeframe::M1)])
+            .frame((time + 60_000 - 1).to_string().as_bytes());
+
+        let Some(Step::Emit(Event::KlineReceived(_, live))) = streamed.first() else {
+            panic!("expected a kline");
+        };
+        assert_eq!(live.time, fetched.time);
+        assert_eq!(live.open, fetched.open);
+        assert!(fetched.low <= fetched.open && fetched.open <= fetched.high);
+    }
+
+    #[test]
+    fn book_straddles_trades() {
+        let ticker_info = TickerInfo::new(
+            Ticker::new("DEMOBTCUSD", Exchange::Simulated),
+            0.1,
+            0.001,
+            None,
+        );
+        let mut feed = MarketFeed::new(ticker_info, PushFrequency::ServerDefault);
+
+        let _ = feed.frame(b"1700000000000");
+        let steps = feed.frame(b"1700000001000");
+
+        let Some(Step::Emit(Event::DepthReceived(_, _, depth, trades))) = steps.first() else {
+            panic!("expected depth");
+        };
+        let (best_bid, _) = depth.bids.last_key_value().unwrap();
+        let (best_ask, _) = depth.asks.first_key_value().unwrap();
+        assert!(best_bid < best_ask);
+        assert!(!trades.is_empty());
+        assert!(trades.windows(2).all(|w| w[0].time <= w[1].time));
+    }
+}
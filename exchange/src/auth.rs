@@ -0,0 +1,294 @@
+//! API keys per exchange and request signing for their authenticated REST endpoints,
+//! keys are kept in memory only, storing them is up to the caller
+
+use crate::adapter::{AdapterError, Exchange, ExchangeInclusive};
+
+use base64::Engine;
+use reqwest::RequestBuilder;
+use ring::hmac;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{LazyLock, RwLock};
+
+/// How long a signed request stays valid after its timestamp, in ms
+const RECV_WINDOW_MS: u64 = 5_000;
+
+static CREDENTIALS: LazyLock<RwLock<FxHashMap<ExchangeInclusive, Credentials>>> =
+    LazyLock::new(|| RwLock::new(FxHashMap::default()));
+
+#[derive(Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Credentials {
+    pub key: String,
+    pub secret: String,
+    /// Set when the key was created, only OKX asks for one
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+impl Credentials {
+    /// Whether every field `venue` signs with is filled in
+    pub fn is_complete(&self, venue: ExchangeInclusive) -> bool {
+        let has_passphrase = self
+            .passphrase
+            .as_deref()
+            .is_some_and(|passphrase| !passphrase.is_empty());
+
+        !self.key.trim().is_empty()
+            && !self.secret.trim().is_empty()
+            && (!needs_passphrase(venue) || has_passphrase)
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("key", &self.key)
+            .field("secret", &"<redacted>")
+            .field(
+                "passphrase",
+                &self.passphrase.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+/// Venues whose authenticated endpoints can be called with an API key
+pub const VENUES: [ExchangeInclusive; 3] = [
+    ExchangeInclusive::Binance,
+    ExchangeInclusive::Bybit,
+    ExchangeInclusive::Okex,
+];
+
+pub fn supports(venue: ExchangeInclusive) -> bool {
+    VENUES.contains(&venue)
+}
+
+pub fn needs_passphrase(venue: ExchangeInclusive) -> bool {
+    venue == ExchangeInclusive::Okex
+}
+
+/// Signs authenticated requests to `venue` with `credentials`, or stops when `None`
+pub fn set_credentials(venue: ExchangeInclusive, credentials: Option<Credentials>) {
+    if let Ok(mut stored) = CREDENTIALS.write() {
+        match credentials {
+            Some(credentials) => stored.insert(venue, credentials),
+            None => stored.remove(&venue),
+        };
+    }
+}
+
+pub fn credentials(venue: ExchangeInclusive) -> Option<Credentials> {
+    CREDENTIALS
+        .read()
+        .ok()
+        .and_then(|stored| stored.get(&venue).cloned())
+}
+
+/// Signed GET to `path` of the venue REST API with the stored credentials, `query`
+/// without the leading `?`
+pub async fn signed_get(
+    venue: ExchangeInclusive,
+    path: &str,
+    query: &str,
+) -> Result<Value, AdapterError> {
+    let credentials = credentials(venue)
+        .ok_or_else(|| AdapterError::InvalidRequest(format!("No API key set for {venue}")))?;
+
+    signed_get_with(venue, &credentials, path, query).await
+}
+
+/// Calls an endpoint that only answers to a valid key, without any side effects
+pub async fn validate(
+    venue: ExchangeInclusive,
+    credentials: &Credentials,
+) -> Result<(), AdapterError> {
+    let path = match venue {
+        ExchangeInclusive::Binance => "/sapi/v1/account/apiRestrictions",
+        ExchangeInclusive::Bybit => "/v5/user/query-api",
+        ExchangeInclusive::Okex => "/api/v5/account/config",
+        _ => {
+            return Err(AdapterError::InvalidRequest(format!(
+                "{venue} doesn't take API keys"
+            )));
+        }
+    };
+
+    signed_get_with(venue, credentials, path, "")
+        .await
+        .map(|_| ())
+}
+
+async fn signed_get_with(
+    venue: ExchangeInclusive,
+    credentials: &Credentials,
+    path: &str,
+    query: &str,
+) -> Result<Value, AdapterError> {
+    if !credentials.is_complete(venue) {
+        return Err(AdapterError::InvalidRequest(format!(
+            "Incomplete API key for {venue}"
+        )));
+    }
+
+    let client = crate::limiter::http_client();
+    let key = credentials.key.trim();
+    let secret = credentials.secret.trim();
+
+    let request = match venue {
+        ExchangeInclusive::Binance => {
+            let timestamp = crate::clock::now_ms(Exchange::BinanceSpot);
+            let query = join_query(
+                query,
+                &format!("recvWindow={RECV_WINDOW_MS}&timestamp={timestamp}"),
+            );
+            let signature = hex(&sign(secret, &query));
+            let url = format!("https://api.binance.com{path}?{query}&signature={signature}");
+
+            crate::network::check_url(&url)?;
+            client.get(url).header("X-MBX-APIKEY", key)
+        }
+        ExchangeInclusive::Bybit => {
+            let timestamp = crate::clock::now_ms(Exchange::BybitLinear).to_string();
+            let recv_window = RECV_WINDOW_MS.to_string();
+            let signature = hex(&sign(
+                secret,
+                &format!("{timestamp}{key}{recv_window}{query}"),
+            ));
+            let url = with_query(&format!("https://api.bybit.com{path}"), query);
+
+            crate::network::check_url(&url)?;
+            client
+                .get(url)
+                .header("X-BAPI-API-KEY", key)
+                .header("X-BAPI-TIMESTAMP", timestamp)
+                .header("X-BAPI-RECV-WINDOW", recv_window)
+                .header("X-BAPI-SIGN", signature)
+        }
+        ExchangeInclusive::Okex => {
+            let timestamp = chrono::DateTime::from_timestamp_millis(crate::clock::now_ms(
+                Exchange::OkexLinear,
+            ) as i64)
+            .unwrap_or_default()
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string();
+            let request_path = with_query(path, query);
+            let signature = base64::engine::general_purpose::STANDARD
+                .encode(sign(secret, &format!("{timestamp}GET{request_path}")));
+            let url = format!("https://www.okx.com{request_path}");
+
+            crate::network::check_url(&url)?;
+            client
+                .get(url)
+                .header("OK-ACCESS-KEY", key)
+                .header("OK-ACCESS-SIGN", signature)
+                .header("OK-ACCESS-TIMESTAMP", timestamp)
+                .header(
+                    "OK-ACCESS-PASSPHRASE",
+                    credentials.passphrase.as_deref().unwrap_or_default(),
+                )
+        }
+        _ => {
+            return Err(AdapterError::InvalidRequest(format!(
+                "{venue} doesn't take API keys"
+            )));
+        }
+    };
+
+    send(venue, request).await
+}
+
+/// Sends the request and turns an error the venue reports in the body into an `Err`
+async fn send(venue: ExchangeInclusive, request: RequestBuilder) -> Result<Value, AdapterError> {
+    let body = request
+        .send()
+        .await
+        .map_err(AdapterError::FetchError)?
+        .text()
+        .await
+        .map_err(AdapterError::FetchError)?;
+
+    let value: Value = serde_json::from_str(&body)
+        .map_err(|err| AdapterError::ParseError(format!("{venue} response: {err}")))?;
+
+    if let Some(message) = venue_error(venue, &value) {
+        return Err(AdapterError::InvalidRequest(format!("{venue}: {message}")));
+    }
+    Ok(value)
+}
+
+fn venue_error(venue: ExchangeInclusive, value: &Value) -> Option<String> {
+    let message = |field: &str| value[field].as_str().unwrap_or("rejected").to_string();
+
+    match venue {
+        // errors come as {"code": -2015, "msg": ...}, successful responses have no code
+        ExchangeInclusive::Binance => value["code"]
+            .as_i64()
+            .filter(|code| *code < 0)
+            .map(|_| message("msg")),
+        ExchangeInclusive::Bybit => value["retCode"]
+            .as_i64()
+            .filter(|code| *code != 0)
+            .map(|_| message("retMsg")),
+        ExchangeInclusive::Okex => value["code"]
+            .as_str()
+            .filter(|code| *code != "0")
+            .map(|_| message("msg")),
+        _ => None,
+    }
+}
+
+fn sign(secret: &str, payload: &str) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::sign(&key, payload.as_bytes()).as_ref().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn join_query(query: &str, rest: &str) -> String {
+    if query.is_empty() {
+        rest.to_string()
+    } else {
+        format!("{query}&{rest}")
+    }
+}
+
+fn with_query(path: &str, query: &str) -> String {
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        format!("{path}?{query}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_like_the_binance_docs_example() {
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1\
+                     &recvWindow=5000&timestamp=1499827319559";
+
+        assert_eq!(
+            hex(&sign(secret, query)),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[test]
+    fn okx_keys_need_a_passphrase() {
+        let credentials = Credentials {
+            key: "key".to_string(),
+            secret: "hunter2".to_string(),
+            passphrase: None,
+        };
+
+        assert!(credentials.is_complete(ExchangeInclusive::Binance));
+        assert!(!credentials.is_complete(ExchangeInclusive::Okex));
+        assert!(!format!("{credentials:?}").contains("hunter2"));
+    }
+}
@@ -0,0 +1,200 @@
+//! REST round trip and server clock offset per exchange, sampled periodically so
+//! timestamps compared against the local clock can be corrected for skew
+
+use crate::adapter::{AdapterError, Exchange};
+
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// Weight of a new offset against the running one, smooths out jittery round trips
+const OFFSET_SMOOTHING: f64 = 0.3;
+
+static SAMPLES: LazyLock<RwLock<FxHashMap<Exchange, ClockSample>>> =
+    LazyLock::new(|| RwLock::new(FxHashMap::default()));
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockSample {
+    pub rtt: Duration,
+    /// Server clock minus local clock, in ms
+    pub offset_ms: i64,
+}
+
+impl ClockSample {
+    fn blend(previous: Option<ClockSample>, sample: ClockSample) -> ClockSample {
+        let Some(previous) = previous else {
+            return sample;
+        };
+
+        let offset = previous.offset_ms as f64
+            + (sample.offset_ms - previous.offset_ms) as f64 * OFFSET_SMOOTHING;
+
+        ClockSample {
+            rtt: sample.rtt,
+            offset_ms: offset.round() as i64,
+        }
+    }
+}
+
+/// Latest measurement of `exchange`, `None` until one succeeded
+pub fn sample(exchange: Exchange) -> Option<ClockSample> {
+    SAMPLES
+        .read()
+        .ok()
+        .and_then(|samples| samples.get(&exchange).copied())
+}
+
+/// Current time on the clock of `exchange` in unix ms, the local one until it was measured
+pub fn now_ms(exchange: Exchange) -> u64 {
+    let local = chrono::Utc::now().timestamp_millis();
+    let offset = sample(exchange).map_or(0, |sample| sample.offset_ms);
+
+    local.saturating_add(offset).max(0) as u64
+}
+
+/// Whether the venue exposes its server time
+pub fn is_supported(exchange: Exchange) -> bool {
+    time_url(exchange).is_some()
+}
+
+/// Times a server time request to `exchange` and records the result
+pub async fn measure(exchange: Exchange) -> Result<ClockSample, AdapterError> {
+    let url = time_url(exchange).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("{exchange} doesn't expose its server time"))
+    })?;
+    crate::network::check_url(url)?;
+
+    let sent_at = chrono::Utc::now().timestamp_millis();
+    let started = Instant::now();
+
+    let body = crate::limiter::http_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(AdapterError::FetchError)?
+        .text()
+        .await
+        .map_err(AdapterError::FetchError)?;
+
+    let rtt = started.elapsed();
+    let received_at = chrono::Utc::now().timestamp_millis();
+
+    let server_time = parse_server_time(exchange, &body)
+        .ok_or_else(|| AdapterError::ParseError(format!("{exchange} server time: {body}")))?;
+
+    let measured = ClockSample {
+        rtt,
+        offset_ms: offset_ms(sent_at, received_at, server_time),
+    };
+
+    let blended = ClockSample::blend(sample(exchange), measured);
+    if let Ok(mut samples) = SAMPLES.write() {
+        samples.insert(exchange, blended);
+    }
+
+    Ok(blended)
+}
+
+/// Offset assuming the server stamped its response halfway through the round trip
+fn offset_ms(sent_at: i64, received_at: i64, server_time: i64) -> i64 {
+    server_time - (sent_at + received_at) / 2
+}
+
+fn time_url(exchange: Exchange) -> Option<&'static str> {
+    match exchange {
+        Exchange::BinanceLinear => Some("https://fapi.binance.com/fapi/v1/time"),
+        Exchange::BinanceInverse => Some("https://dapi.binance.com/dapi/v1/time"),
+        Exchange::BinanceSpot => Some("https://api.binance.com/api/v3/time"),
+        Exchange::BybitLinear | Exchange::BybitInverse | Exchange::BybitSpot => {
+            Some("https://api.bybit.com/v5/market/time")
+        }
+        Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
+            Some("https://www.okx.com/api/v5/public/time")
+        }
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            Some("https://www.deribit.com/api/v2/public/get_time")
+        }
+        Exchange::HyperliquidLinear
+        | Exchange::HyperliquidSpot
+        | Exchange::Forex
+        | Exchange::Simulated => None,
+    }
+}
+
+fn parse_server_time(exchange: Exchange, body: &str) -> Option<i64> {
+    let value: Value = serde_json::from_str(body).ok()?;
+
+    let time = match exchange {
+        Exchange::BinanceLinear | Exchange::BinanceInverse | Exchange::BinanceSpot => {
+            &value["serverTime"]
+        }
+        Exchange::BybitLinear | Exchange::BybitInverse | Exchange::BybitSpot => &value["time"],
+        Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
+            &value["data"][0]["ts"]
+        }
+        Exchange::DeribitLinear | Exchange::DeribitInverse => &value["result"],
+        Exchange::HyperliquidLinear
+        | Exchange::HyperliquidSpot
+        | Exchange::Forex
+        | Exchange::Simulated => {
+            return None;
+        }
+    };
+
+    time.as_i64()
+        .or_else(|| time.as_str().and_then(|s| s.parse().ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_server_time_of_each_venue() {
+        let cases = [
+            (Exchange::BinanceLinear, r#"{"serverTime":1700000000123}"#),
+            (
+                Exchange::BybitSpot,
+                r#"{"retCode":0,"result":{"timeSecond":"1700000000"},"time":1700000000123}"#,
+            ),
+            (
+                Exchange::OkexLinear,
+                r#"{"code":"0","data":[{"ts":"1700000000123"}],"msg":""}"#,
+            ),
+            (
+                Exchange::DeribitInverse,
+                r#"{"jsonrpc":"2.0","result":1700000000123,"usIn":1,"usOut":2}"#,
+            ),
+        ];
+
+        for (exchange, body) in cases {
+            assert_eq!(parse_server_time(exchange, body), Some(1_700_000_000_123));
+        }
+    }
+
+    #[test]
+    fn offset_is_taken_at_the_middle_of_the_round_trip() {
+        // server ahead by 500ms, 100ms round trip
+        assert_eq!(offset_ms(1_000, 1_100, 1_550), 500);
+        assert_eq!(offset_ms(1_000, 1_100, 850), -200);
+    }
+
+    #[test]
+    fn offset_is_smoothed_across_samples() {
+        let first = ClockSample {
+            rtt: Duration::from_millis(80),
+            offset_ms: 100,
+        };
+        let jittery = ClockSample {
+            rtt: Duration::from_millis(400),
+            offset_ms: 200,
+        };
+
+        assert_eq!(ClockSample::blend(None, first), first);
+
+        let blended = ClockSample::blend(Some(first), jittery);
+        assert_eq!(blended.offset_ms, 130);
+        assert_eq!(blended.rtt, jittery.rtt);
+    }
+}
@@ -0,0 +1,425 @@
+//! Groups listings of the same underlying across venues and quote assets, so series
+//! like open interest or prices can be combined into a single market-wide view.
+
+use crate::adapter::{self, AdapterError, Exchange, MarketKind};
+use crate::depth::{Depth, Levels};
+use crate::{MinTicksize, OpenInterest, Price, Ticker, Timeframe};
+
+use iced_futures::futures::future::join_all;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Venues whose linear perps report historical OI in units of the base asset,
+/// which makes their series directly summable
+const LINEAR_OI_VENUES: [Exchange; 3] = [
+    Exchange::BinanceLinear,
+    Exchange::BybitLinear,
+    Exchange::OkexLinear,
+];
+
+const QUOTE_ASSETS: [&str; 3] = ["USDT", "USDC", "USD"];
+
+/// USD stablecoin quotes that can be treated as one logical market per base
+pub const STABLECOIN_QUOTES: [&str; 3] = ["USDT", "USDC", "FDUSD"];
+
+/// Listings of the same base on the same venue and market, quoted in different stablecoins
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StablecoinMarket {
+    pub exchange: Exchange,
+    pub base: String,
+}
+
+impl StablecoinMarket {
+    /// Label shared by every quote of the market, e.g. "BTCUSD"
+    pub fn label(&self) -> String {
+        format!("{}USD", self.base)
+    }
+}
+
+/// Open interest summed across venues at a single timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatedOpenInterest {
+    pub time: u64,
+    pub total: f32,
+    pub venues: Vec<(Exchange, f32)>,
+}
+
+/// Base asset of a perpetual listing, e.g. "BTC" for "BTCUSDT" or "BTC-USDT-SWAP"
+pub fn underlying_base(ticker: &Ticker) -> Option<String> {
+    base_and_quote(ticker).map(|(base, _)| base)
+}
+
+/// Listing of the same base on `exchange`, preferring the one with the same quote asset
+pub fn equivalent_listing<'a>(
+    ticker: &Ticker,
+    exchange: Exchange,
+    listings: impl IntoIterator<Item = &'a Ticker>,
+) -> Option<Ticker> {
+    let (base, quote) = base_and_quote(ticker)?;
+    let mut other_quote = None;
+
+    for listing in listings {
+        if listing.exchange != exchange {
+            continue;
+        }
+        if let Some((listing_base, listing_quote)) = base_and_quote(listing)
+            && listing_base == base
+        {
+            if listing_quote == quote {
+                return Some(*listing);
+            }
+            other_quote.get_or_insert(*listing);
+        }
+    }
+
+    other_quote
+}
+
+/// Listings of the same asset as `ticker` on each of `venues`, the symbol mapping a
+/// merged feed subscribes to. Venues not listing it are left out
+pub fn merged_listings<'a>(
+    ticker: &Ticker,
+    venues: &[Exchange],
+    listings: impl IntoIterator<Item = &'a Ticker> + Clone,
+) -> Vec<Ticker> {
+    venues
+        .iter()
+        .filter(|venue| **venue != ticker.exchange)
+        .filter_map(|venue| equivalent_listing(ticker, *venue, listings.clone()))
+        .collect()
+}
+
+/// Latest book of each venue of a merged feed, summed into one
+#[derive(Default)]
+pub struct MergedDepth {
+    venues: FxHashMap<Exchange, Arc<Depth>>,
+}
+
+impl MergedDepth {
+    /// Replaces the book of `exchange` and sums every venue's, with prices rounded to
+    /// the tick of the merged feed
+    pub fn update(
+        &mut self,
+        exchange: Exchange,
+        depth: &Arc<Depth>,
+        min_ticksize: MinTicksize,
+    ) -> Depth {
+        self.venues.insert(exchange, Arc::clone(depth));
+
+        Depth {
+            bids: self.sum_side(|depth| &depth.bids, min_ticksize),
+            asks: self.sum_side(|depth| &depth.asks, min_ticksize),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.venues.clear();
+    }
+
+    fn sum_side(&self, side: fn(&Depth) -> &Levels, min_ticksize: MinTicksize) -> Levels {
+        let mut levels: Vec<(Price, f32)> = self
+            .venues
+            .values()
+            .flat_map(|depth| side(depth).iter())
+            .map(|(price, qty)| (price.round_to_min_tick(min_ticksize), *qty))
+            .collect();
+
+        levels.sort_by_key(|(price, _)| *price);
+        levels.dedup_by(|later, earlier| {
+            if later.0 == earlier.0 {
+                earlier.1 += later.1;
+                true
+            } else {
+                false
+            }
+        });
+
+        levels.into_iter().collect()
+    }
+}
+
+fn base_and_quote(ticker: &Ticker) -> Option<(String, &'static str)> {
+    let (symbol, _) = ticker.to_full_symbol_and_type();
+
+    let normalized = symbol
+        .trim_end_matches("-SWAP")
+        .trim_end_matches("_PERP")
+        .replace('-', "");
+
+    QUOTE_ASSETS.iter().find_map(|quote| {
+        normalized
+            .strip_suffix(quote)
+            .filter(|base| !base.is_empty())
+            .map(|base| (base.to_owned(), *quote))
+    })
+}
+
+/// Logical market of a stablecoin-quoted listing, `None` for any other quote
+pub fn stablecoin_market(ticker: &Ticker) -> Option<StablecoinMarket> {
+    let (symbol, _) = ticker.to_full_symbol_and_type();
+    let normalized = symbol.replace(['-', '_', '/'], "");
+
+    STABLECOIN_QUOTES.iter().find_map(|quote| {
+        normalized
+            .strip_suffix(quote)
+            .filter(|base| !base.is_empty())
+            .map(|base| StablecoinMarket {
+                exchange: ticker.exchange,
+                base: base.to_owned(),
+            })
+    })
+}
+
+/// Volume-weighted average of `(price, volume)` pairs, falls back to the plain
+/// mean when none of them carry volume
+pub fn volume_weighted_price(quotes: &[(f32, f32)]) -> Option<f32> {
+    if quotes.is_empty() {
+        return None;
+    }
+
+    let total_volume: f32 = quotes.iter().map(|(_, volume)| volume.max(0.0)).sum();
+
+    if total_volume > 0.0 {
+        let notional: f32 = quotes
+            .iter()
+            .map(|(price, volume)| price * volume.max(0.0))
+            .sum();
+        Some(notional / total_volume)
+    } else {
+        Some(quotes.iter().map(|(price, _)| price).sum::<f32>() / quotes.len() as f32)
+    }
+}
+
+/// Whether aggregated OI can be built for this ticker
+pub fn supports_aggregated_oi(ticker: &Ticker) -> bool {
+    LINEAR_OI_VENUES.contains(&ticker.exchange) && underlying_base(ticker).is_some()
+}
+
+/// USDT-margined perp listings of the same underlying on every OI-capable venue.
+///
+/// Listings are derived from venue naming conventions, a venue that doesn't list
+/// the pair simply fails its fetch and is left out of the aggregate.
+pub fn linear_perp_listings(ticker: &Ticker) -> Vec<Ticker> {
+    let Some(base) = underlying_base(ticker) else {
+        return vec![];
+    };
+
+    LINEAR_OI_VENUES
+        .iter()
+        .map(|&exchange| match exchange {
+            Exchange::OkexLinear => Ticker::new(&format!("{base}-USDT-SWAP"), exchange),
+            _ => Ticker::new(&format!("{base}USDT"), exchange),
+        })
+        .collect()
+}
+
+pub async fn fetch_aggregated_oi(
+    ticker: Ticker,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<AggregatedOpenInterest>, AdapterError> {
+    if ticker.market_type() != MarketKind::LinearPerps {
+        return Err(AdapterError::InvalidRequest(
+            "Aggregated open interest is only available for linear perpetuals".to_string(),
+        ));
+    }
+
+    let listings = linear_perp_listings(&ticker);
+    let fetches = listings
+        .iter()
+        .map(|listing| adapter::fetch_open_interest(*listing, timeframe, range));
+
+    let mut per_venue = vec![];
+    let mut last_err = None;
+
+    for (listing, result) in listings.iter().zip(join_all(fetches).await) {
+        match result {
+            Ok(data) => per_venue.push((listing.exchange, data)),
+            Err(err) => {
+                log::debug!("Skipping {listing} in aggregated OI: {err}");
+                last_err = Some(err);
+            }
+        }
+    }
+
+    if per_venue.is_empty() {
+        return Err(last_err.unwrap_or_else(|| {
+            AdapterError::InvalidRequest("No venue lists this underlying".to_string())
+        }));
+    }
+
+    Ok(aggregate(per_venue))
+}
+
+/// Sums per-venue series, keeping only timestamps every responding venue reported
+/// so a venue lagging behind doesn't show up as a drop in the total
+fn aggregate(per_venue: Vec<(Exchange, Vec<OpenInterest>)>) -> Vec<AggregatedOpenInterest> {
+    let venue_count = per_venue.len();
+    let mut by_time: BTreeMap<u64, Vec<(Exchange, f32)>> = BTreeMap::new();
+
+    for (exchange, series) in per_venue {
+        for oi in series {
+            by_time
+                .entry(oi.time)
+                .or_default()
+                .push((exchange, oi.value));
+        }
+    }
+
+    by_time
+        .into_iter()
+        .filter(|(_, venues)| venues.len() == venue_count)
+        .map(|(time, venues)| AggregatedOpenInterest {
+            time,
+            total: venues.iter().map(|(_, value)| value).sum(),
+            venues,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_underlying_across_venues() {
+        let cases = [
+            ("BTCUSDT", Exchange::BinanceLinear),
+            ("BTCUSDT", Exchange::BybitLinear),
+            ("BTC-USDT-SWAP", Exchange::OkexLinear),
+        ];
+
+        for (symbol, exchange) in cases {
+            let ticker = Ticker::new(symbol, exchange);
+            assert_eq!(underlying_base(&ticker).as_deref(), Some("BTC"));
+        }
+    }
+
+    #[test]
+    fn finds_same_listing_on_another_venue() {
+        let listings = [
+            Ticker::new("BTC-USDC-SWAP", Exchange::OkexLinear),
+            Ticker::new("BTC-USDT-SWAP", Exchange::OkexLinear),
+            Ticker::new("ETH-USDT-SWAP", Exchange::OkexLinear),
+            Ticker::new("BTCUSDT", Exchange::BybitLinear),
+        ];
+        let binance = Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+
+        assert_eq!(
+            equivalent_listing(&binance, Exchange::OkexLinear, &listings),
+            Some(Ticker::new("BTC-USDT-SWAP", Exchange::OkexLinear))
+        );
+        assert_eq!(
+            equivalent_listing(&binance, Exchange::HyperliquidLinear, &listings),
+            None
+        );
+
+        let binance_usdc = Ticker::new("BTCUSDC", Exchange::BinanceLinear);
+        assert_eq!(
+            equivalent_listing(&binance_usdc, Exchange::BybitLinear, &listings),
+            Some(Ticker::new("BTCUSDT", Exchange::BybitLinear))
+        );
+    }
+
+    #[test]
+    fn maps_listings_of_other_venues() {
+        let listings = [
+            Ticker::new("BTC-USDT-SWAP", Exchange::OkexLinear),
+            Ticker::new("BTCUSDT", Exchange::BybitLinear),
+            Ticker::new("BTCUSDT", Exchange::BinanceLinear),
+        ];
+        let binance = Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+
+        let venues = [
+            Exchange::BinanceLinear,
+            Exchange::BybitLinear,
+            Exchange::OkexLinear,
+            Exchange::HyperliquidLinear,
+        ];
+        assert_eq!(
+            merged_listings(&binance, &venues, &listings),
+            vec![listings[1], listings[0]]
+        );
+    }
+
+    #[test]
+    fn sums_books_of_every_venue() {
+        let min_tick = MinTicksize::from(1.0);
+        // merged books key on the tick, f32 prices only land on it once rounded
+        let at = |price: f32| Price::from_f32(price).round_to_min_tick(min_tick);
+        let level = |price: f32, qty: f32| (at(price), qty);
+        let book = |bids: Vec<(Price, f32)>| {
+            Arc::new(Depth {
+                bids: bids.into_iter().collect(),
+                asks: Levels::default(),
+            })
+        };
+
+        let mut merged = MergedDepth::default();
+        merged.update(
+            Exchange::BinanceLinear,
+            &book(vec![level(100.0, 2.0), level(99.0, 1.0)]),
+            min_tick,
+        );
+        let depth = merged.update(
+            Exchange::BybitLinear,
+            &book(vec![level(100.0, 3.0)]),
+            min_tick,
+        );
+        assert_eq!(depth.bids.get(&at(100.0)), Some(&5.0));
+        assert_eq!(depth.bids.get(&at(99.0)), Some(&1.0));
+
+        // a venue's newer book replaces its older one
+        let depth = merged.update(
+            Exchange::BinanceLinear,
+            &book(vec![level(100.0, 1.0)]),
+            min_tick,
+        );
+        assert_eq!(depth.bids.get(&at(100.0)), Some(&4.0));
+        assert_eq!(depth.bids.len(), 1);
+    }
+
+    #[test]
+    fn groups_stablecoin_quotes_of_same_base() {
+        let usdt = stablecoin_market(&Ticker::new("BTCUSDT", Exchange::BinanceSpot));
+        let fdusd = stablecoin_market(&Ticker::new("BTCFDUSD", Exchange::BinanceSpot));
+        let other_venue = stablecoin_market(&Ticker::new("BTCUSDC", Exchange::BybitSpot));
+
+        assert!(usdt.is_some());
+        assert_eq!(usdt, fdusd);
+        assert_ne!(usdt, other_venue);
+        assert_eq!(
+            stablecoin_market(&Ticker::new("ETHBTC", Exchange::BinanceSpot)),
+            None
+        );
+    }
+
+    #[test]
+    fn weights_price_by_volume() {
+        assert_eq!(
+            volume_weighted_price(&[(100.0, 3.0), (104.0, 1.0)]),
+            Some(101.0)
+        );
+        assert_eq!(
+            volume_weighted_price(&[(100.0, 0.0), (102.0, 0.0)]),
+            Some(101.0)
+        );
+        assert_eq!(volume_weighted_price(&[]), None);
+    }
+
+    #[test]
+    fn aggregates_only_shared_timestamps() {
+        let oi = |time, value| OpenInterest { time, value };
+
+        let aggregated = aggregate(vec![
+            (Exchange::BinanceLinear, vec![oi(1, 10.0), oi(2, 11.0)]),
+            (Exchange::BybitLinear, vec![oi(1, 5.0)]),
+        ]);
+
+        assert_eq!(aggregated.len(), 1);
+        assert_eq!(aggregated[0].time, 1);
+        assert_eq!(aggregated[0].total, 15.0);
+        assert_eq!(aggregated[0].venues.len(), 2);
+    }
+}
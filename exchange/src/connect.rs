@@ -1,6 +1,8 @@
-use crate::adapter::AdapterError;
+use crate::adapter::{AdapterError, Event, Exchange};
+use crate::network::{Proxy, ProxyKind};
+use base64::Engine;
 use bytes::Bytes;
-use fastwebsockets::FragmentCollector;
+use fastwebsockets::{FragmentCollector, Frame, OpCode, Payload, WebSocketError};
 use http_body_util::Empty;
 use hyper::{
     Request,
@@ -8,6 +10,10 @@ use hyper::{
     upgrade::Upgraded,
 };
 use hyper_util::rt::TokioIo;
+use iced_futures::futures::{SinkExt, channel::mpsc};
+use std::hash::BuildHasher;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio_rustls::{
     TlsConnector,
@@ -20,6 +26,177 @@ pub enum State {
     Connected(FragmentCollector<TokioIo<Upgraded>>),
 }
 
+/// How a websocket stream paces its reconnect attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Delay before the first retry, doubled on each failure after it
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Share of each delay that's randomized, so streams dropped together don't retry in lockstep
+    pub jitter: f32,
+    /// Failures in a row before backing off for `cooldown`, after which the count starts over
+    pub max_retries: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.25,
+            max_retries: 10,
+            cooldown: Duration::from_secs(120),
+        }
+    }
+}
+
+/// Failed attempts so far under a `ReconnectPolicy`, reset once a stream connects
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    policy: ReconnectPolicy,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self { policy, attempt: 0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Counts a failure, returns the number of the upcoming attempt and how long to wait for it
+    pub fn next_delay(&mut self) -> (u32, Duration) {
+        let policy = self.policy;
+
+        if self.attempt >= policy.max_retries {
+            self.attempt = 0;
+            return (policy.max_retries + 1, policy.cooldown);
+        }
+        self.attempt += 1;
+
+        let exp = policy
+            .base_delay
+            .saturating_mul(1 << (self.attempt - 1).min(16))
+            .min(policy.max_delay);
+
+        let jitter = policy.jitter.clamp(0.0, 1.0);
+        // uniform in `1 - jitter..=1`, never waits longer than the cap
+        let factor = 1.0 - jitter * random_unit();
+
+        (self.attempt, exp.mul_f32(factor))
+    }
+
+    /// Reports the upcoming attempt of `exchange` and sleeps until it's due
+    pub async fn wait(&mut self, exchange: Exchange, output: &mut mpsc::Sender<Event>) {
+        let (attempt, next_in) = self.next_delay();
+
+        let _ = output
+            .send(Event::Reconnecting {
+                exchange,
+                attempt,
+                next_in,
+            })
+            .await;
+
+        tokio::time::sleep(next_in).await;
+    }
+}
+
+/// How often a stream reports its message count
+const TELEMETRY_INTERVAL: Duration = Duration::from_secs(2);
+/// How often a stream pings the venue to time the round trip
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Frames received and ping round trip of one stream connection, reported as `Event::Telemetry`
+pub struct Telemetry {
+    window_start: Instant,
+    messages: u32,
+    last_ping: Option<Instant>,
+    /// Sent time of the ping still waiting on its pong
+    pending_ping: Option<Instant>,
+    latency: Option<Duration>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            messages: 0,
+            last_ping: None,
+            pending_ping: None,
+            latency: None,
+        }
+    }
+
+    /// `read_frame` that counts what arrives, pings the venue when due
+    /// and reports to `output` once every `TELEMETRY_INTERVAL`
+    pub async fn read_frame(
+        &mut self,
+        exchange: Exchange,
+        ws: &mut FragmentCollector<TokioIo<Upgraded>>,
+        output: &mut mpsc::Sender<Event>,
+    ) -> Result<Frame<'static>, WebSocketError> {
+        let now = Instant::now();
+
+        // a pong that never came is written off with the next ping
+        if self
+            .last_ping
+            .is_none_or(|at| now.duration_since(at) >= PING_INTERVAL)
+        {
+            self.last_ping = Some(now);
+            self.pending_ping = ws
+                .write_frame(Frame::new(true, OpCode::Ping, None, Payload::Borrowed(&[])))
+                .await
+                .is_ok()
+                .then_some(now);
+        }
+
+        let frame = ws.read_frame().await?;
+        let now = Instant::now();
+
+        match frame.opcode {
+            OpCode::Pong => {
+                if let Some(sent_at) = self.pending_ping.take() {
+                    self.latency = Some(now.duration_since(sent_at));
+                }
+            }
+            _ => self.messages += 1,
+        }
+
+        if now.duration_since(self.window_start) >= TELEMETRY_INTERVAL {
+            let _ = output
+                .send(Event::Telemetry {
+                    exchange,
+                    messages: std::mem::take(&mut self.messages),
+                    latency: self.latency,
+                })
+                .await;
+            self.window_start = now;
+        }
+
+        Ok(frame)
+    }
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pseudo-random value in `0.0..1.0`, std's hasher keys are seeded per process
+fn random_unit() -> f32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let hash = std::collections::hash_map::RandomState::new().hash_one(nanos);
+
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
+
 pub async fn connect_ws(
     domain: &str,
     url: &str,
@@ -27,6 +204,8 @@ pub async fn connect_ws(
     fastwebsockets::FragmentCollector<hyper_util::rt::TokioIo<hyper::upgrade::Upgraded>>,
     AdapterError,
 > {
+    crate::network::check_host(domain)?;
+
     let tcp_stream = setup_tcp(domain).await?;
     let tls_stream = upgrade_to_tls(domain, tcp_stream).await?;
 
@@ -46,10 +225,24 @@ where
 }
 
 async fn setup_tcp(domain: &str) -> Result<TcpStream, AdapterError> {
-    let addr = format!("{domain}:443");
-    let stream = TcpStream::connect(&addr)
-        .await
-        .map_err(|e| AdapterError::WebsocketError(e.to_string()))?;
+    let stream = match crate::network::proxy() {
+        Some(proxy) => {
+            let mut stream = TcpStream::connect((proxy.host.as_str(), proxy.port))
+                .await
+                .map_err(|e| proxy_error(&proxy, e))?;
+
+            match proxy.kind {
+                ProxyKind::Http => http_connect(&mut stream, &proxy, domain, 443).await,
+                ProxyKind::Socks5 => socks5_connect(&mut stream, &proxy, domain, 443).await,
+            }
+            .map_err(|e| proxy_error(&proxy, e))?;
+
+            stream
+        }
+        None => TcpStream::connect(format!("{domain}:443"))
+            .await
+            .map_err(|e| AdapterError::WebsocketError(e.to_string()))?,
+    };
 
     // High quality connection settings
     stream
@@ -69,6 +262,148 @@ async fn setup_tcp(domain: &str) -> Result<TcpStream, AdapterError> {
     Ok(stream)
 }
 
+fn proxy_error(proxy: &Proxy, err: impl std::fmt::Display) -> AdapterError {
+    AdapterError::WebsocketError(format!(
+        "{} proxy {}:{}: {err}",
+        proxy.kind, proxy.host, proxy.port
+    ))
+}
+
+/// Largest proxy response header read before giving up on it
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+/// Opens a tunnel to `host:port` through an HTTP proxy
+async fn http_connect(
+    stream: &mut TcpStream,
+    proxy: &Proxy,
+    host: &str,
+    port: u16,
+) -> std::io::Result<()> {
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some((username, password)) = proxy.auth() {
+        let credentials = base64::engine::general_purpose::STANDARD
+            .encode(format!("{username}:{password}").as_bytes());
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // read byte by byte, the tunneled TLS handshake follows right after the header
+    let mut response = Vec::with_capacity(256);
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_CONNECT_RESPONSE {
+            return Err(std::io::Error::other("response header too long"));
+        }
+        response.push(stream.read_u8().await?);
+    }
+
+    parse_connect_status(&response).map_err(std::io::Error::other)
+}
+
+fn parse_connect_status(response: &[u8]) -> Result<(), String> {
+    let status_line = response
+        .split(|b| *b == b'\n')
+        .next()
+        .map(|line| String::from_utf8_lossy(line).trim().to_string())
+        .unwrap_or_default();
+
+    match status_line.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(format!("tunnel refused: {status_line}")),
+    }
+}
+
+/// Opens a tunnel to `host:port` through a SOCKS5 proxy, letting it resolve the host
+async fn socks5_connect(
+    stream: &mut TcpStream,
+    proxy: &Proxy,
+    host: &str,
+    port: u16,
+) -> std::io::Result<()> {
+    const NO_AUTH: u8 = 0x00;
+    const USER_PASS: u8 = 0x02;
+
+    let auth = proxy.auth();
+    let greeting: &[u8] = if auth.is_some() {
+        &[0x05, 2, NO_AUTH, USER_PASS]
+    } else {
+        &[0x05, 1, NO_AUTH]
+    };
+    stream.write_all(greeting).await?;
+
+    let mut choice = [0u8; 2];
+    stream.read_exact(&mut choice).await?;
+    match (choice[1], auth) {
+        (NO_AUTH, _) => {}
+        (USER_PASS, Some((username, password))) => {
+            stream
+                .write_all(&socks5_auth_request(username, password)?)
+                .await?;
+
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status).await?;
+            if status[1] != 0 {
+                return Err(std::io::Error::other("credentials rejected"));
+            }
+        }
+        _ => return Err(std::io::Error::other("no acceptable auth method")),
+    }
+
+    stream
+        .write_all(&socks5_connect_request(host, port)?)
+        .await?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0 {
+        return Err(std::io::Error::other(format!(
+            "connect failed with reply {}",
+            reply[1]
+        )));
+    }
+
+    // the bound address isn't needed, only consumed
+    let addr_len = match reply[3] {
+        0x01 => 4,
+        0x03 => stream.read_u8().await? as usize,
+        0x04 => 16,
+        other => {
+            return Err(std::io::Error::other(format!(
+                "unknown address type {other}"
+            )));
+        }
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound).await?;
+
+    Ok(())
+}
+
+fn socks5_auth_request(username: &str, password: &str) -> std::io::Result<Vec<u8>> {
+    let (username, password) = (username.as_bytes(), password.as_bytes());
+    if username.len() > 255 || password.len() > 255 {
+        return Err(std::io::Error::other("credentials longer than 255 bytes"));
+    }
+
+    let mut request = vec![0x01, username.len() as u8];
+    request.extend_from_slice(username);
+    request.push(password.len() as u8);
+    request.extend_from_slice(password);
+    Ok(request)
+}
+
+fn socks5_connect_request(host: &str, port: u16) -> std::io::Result<Vec<u8>> {
+    let host = host.as_bytes();
+    if host.len() > 255 {
+        return Err(std::io::Error::other("host longer than 255 bytes"));
+    }
+
+    let mut request = vec![0x05, 0x01, 0x00, 0x03, host.len() as u8];
+    request.extend_from_slice(host);
+    request.extend_from_slice(&port.to_be_bytes());
+    Ok(request)
+}
+
 fn tls_connector() -> Result<TlsConnector, AdapterError> {
     let mut root_store = tokio_rustls::rustls::RootCertStore::empty();
 
@@ -127,3 +462,90 @@ async fn upgrade_to_websocket(
 
     Ok(FragmentCollector::new(ws))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(8),
+            jitter: 0.0,
+            max_retries: 5,
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn delays_double_up_to_the_cap() {
+        let mut backoff = Backoff::new(policy());
+
+        let delays: Vec<_> = (0..5).map(|_| backoff.next_delay()).collect();
+
+        assert_eq!(
+            delays,
+            vec![
+                (1, Duration::from_secs(1)),
+                (2, Duration::from_secs(2)),
+                (3, Duration::from_secs(4)),
+                (4, Duration::from_secs(8)),
+                (5, Duration::from_secs(8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn cools_down_after_max_retries_then_starts_over() {
+        let mut backoff = Backoff::new(policy());
+        for _ in 0..5 {
+            backoff.next_delay();
+        }
+
+        assert_eq!(backoff.next_delay(), (6, Duration::from_secs(60)));
+        assert_eq!(backoff.next_delay(), (1, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn jitter_only_shortens_the_delay() {
+        let mut backoff = Backoff::new(ReconnectPolicy {
+            jitter: 0.5,
+            ..policy()
+        });
+
+        for _ in 0..5 {
+            let (attempt, delay) = backoff.next_delay();
+            let full = Duration::from_secs(1 << (attempt - 1)).min(Duration::from_secs(8));
+
+            assert!(delay <= full && delay >= full / 2);
+        }
+    }
+
+    #[test]
+    fn reset_starts_from_the_base_delay() {
+        let mut backoff = Backoff::new(policy());
+        backoff.next_delay();
+        backoff.next_delay();
+
+        backoff.reset();
+
+        assert_eq!(backoff.next_delay(), (1, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn accepts_only_successful_tunnels() {
+        assert!(parse_connect_status(b"HTTP/1.1 200 Connection established\r\n\r\n").is_ok());
+        assert!(
+            parse_connect_status(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").is_err()
+        );
+    }
+
+    #[test]
+    fn socks5_request_carries_domain_and_port() {
+        let request = socks5_connect_request("fstream.binance.com", 443).unwrap();
+
+        assert_eq!(&request[..5], &[0x05, 0x01, 0x00, 0x03, 19]);
+        assert_eq!(&request[5..24], b"fstream.binance.com");
+        assert_eq!(&request[24..], &[0x01, 0xBB]);
+    }
+}
@@ -4,7 +4,104 @@ use serde::Deserializer;
 use serde::de::Error as SerdeError;
 use serde_json::Value;
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::sync::Arc;
+
+/// Price levels of one side of the book, ascending by price
+#[cfg(not(feature = "btree-depth"))]
+pub type Levels = FlatLevels;
+#[cfg(feature = "btree-depth")]
+pub type Levels = std::collections::BTreeMap<Price, f32>;
+
+type LevelRef<'a> = (&'a Price, &'a f32);
+
+/// Price levels kept in one contiguous vec sorted by price, cheaper to walk than a
+/// `BTreeMap` for the full-book passes of the ladder and heatmap. Mirrors the subset
+/// of the `BTreeMap` API the book is read and updated through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlatLevels {
+    levels: Vec<(Price, f32)>,
+}
+
+impl FlatLevels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    pub fn get(&self, price: &Price) -> Option<&f32> {
+        self.levels
+            .binary_search_by_key(price, |(p, _)| *p)
+            .ok()
+            .map(|idx| &self.levels[idx].1)
+    }
+
+    pub fn insert(&mut self, price: Price, qty: f32) -> Option<f32> {
+        match self.levels.binary_search_by_key(&price, |(p, _)| *p) {
+            Ok(idx) => Some(std::mem::replace(&mut self.levels[idx].1, qty)),
+            Err(idx) => {
+                self.levels.insert(idx, (price, qty));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, price: &Price) -> Option<f32> {
+        self.levels
+            .binary_search_by_key(price, |(p, _)| *p)
+            .ok()
+            .map(|idx| self.levels.remove(idx).1)
+    }
+
+    pub fn first_key_value(&self) -> Option<LevelRef<'_>> {
+        self.levels.first().map(|(p, q)| (p, q))
+    }
+
+    pub fn last_key_value(&self) -> Option<LevelRef<'_>> {
+        self.levels.last().map(|(p, q)| (p, q))
+    }
+
+    pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
+        self.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a FlatLevels {
+    type Item = LevelRef<'a>;
+    type IntoIter =
+        std::iter::Map<std::slice::Iter<'a, (Price, f32)>, fn(&'a (Price, f32)) -> LevelRef<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let as_ref: fn(&'a (Price, f32)) -> LevelRef<'a> = |(p, q)| (p, q);
+        self.levels.iter().map(as_ref)
+    }
+}
+
+impl FromIterator<(Price, f32)> for FlatLevels {
+    /// Like collecting into a `BTreeMap`, the last of duplicate prices wins
+    fn from_iter<I: IntoIterator<Item = (Price, f32)>>(iter: I) -> Self {
+        let mut levels: Vec<(Price, f32)> = iter.into_iter().collect();
+
+        // stable, so duplicates stay in arrival order
+        levels.sort_by_key(|(p, _)| *p);
+        levels.dedup_by(|later, earlier| {
+            if later.0 == earlier.0 {
+                earlier.1 = later.1;
+                true
+            } else {
+                false
+            }
+        });
+
+        Self { levels }
+    }
+}
 
 #[derive(Clone, Copy)]
 pub struct DeOrder {
@@ -65,8 +162,8 @@ pub enum DepthUpdate {
 
 #[derive(Clone, Default)]
 pub struct Depth {
-    pub bids: BTreeMap<Price, f32>,
-    pub asks: BTreeMap<Price, f32>,
+    pub bids: Levels,
+    pub asks: Levels,
 }
 
 impl std::fmt::Debug for Depth {
@@ -84,11 +181,7 @@ impl Depth {
         Self::diff_price_levels(&mut self.asks, &diff.asks, min_ticksize);
     }
 
-    fn diff_price_levels(
-        price_map: &mut BTreeMap<Price, f32>,
-        orders: &[DeOrder],
-        min_ticksize: MinTicksize,
-    ) {
+    fn diff_price_levels(price_map: &mut Levels, orders: &[DeOrder], min_ticksize: MinTicksize) {
         orders.iter().for_each(|order| {
             let order = Order {
                 price: Price::from_f32(order.price).round_to_min_tick(min_ticksize),
@@ -113,7 +206,7 @@ impl Depth {
                     de_order.qty,
                 )
             })
-            .collect::<BTreeMap<Price, f32>>();
+            .collect::<Levels>();
         self.asks = snapshot
             .asks
             .iter()
@@ -123,7 +216,7 @@ impl Depth {
                     de_order.qty,
                 )
             })
-            .collect::<BTreeMap<Price, f32>>();
+            .collect::<Levels>();
     }
 
     pub fn mid_price(&self) -> Option<Price> {
@@ -161,3 +254,114 @@ impl LocalDepthCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::time::Instant;
+
+    /// Deterministic stream of diffs around a mid price, most of them near the top of the book
+    fn diffs(count: usize, levels: i64) -> Vec<(Price, f32)> {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        (0..count)
+            .map(|_| {
+                let r = next();
+                let offset = if r % 4 == 0 {
+                    (r >> 8) as i64 % levels
+                } else {
+                    (r >> 8) as i64 % 50
+                };
+                let qty = if r % 5 == 0 { 0.0 } else { (r % 1000) as f32 };
+                (Price::from_units(1_000_000 + offset * 100), qty)
+            })
+            .collect()
+    }
+
+    fn apply_btree(book: &mut BTreeMap<Price, f32>, diffs: &[(Price, f32)]) {
+        for (price, qty) in diffs {
+            if *qty == 0.0 {
+                book.remove(price);
+            } else {
+                book.insert(*price, *qty);
+            }
+        }
+    }
+
+    fn apply_flat(book: &mut FlatLevels, diffs: &[(Price, f32)]) {
+        for (price, qty) in diffs {
+            if *qty == 0.0 {
+                book.remove(price);
+            } else {
+                book.insert(*price, *qty);
+            }
+        }
+    }
+
+    #[test]
+    fn flat_levels_match_btree() {
+        let snapshot = diffs(8_000, 6_000);
+        let updates = diffs(50_000, 6_000);
+
+        let mut btree: BTreeMap<Price, f32> = snapshot.iter().copied().collect();
+        let mut flat: FlatLevels = snapshot.iter().copied().collect();
+        assert!(btree.iter().eq(flat.iter()));
+
+        apply_btree(&mut btree, &updates);
+        apply_flat(&mut flat, &updates);
+
+        assert!(btree.iter().eq(flat.iter()));
+        assert_eq!(btree.first_key_value(), flat.first_key_value());
+        assert_eq!(btree.last_key_value(), flat.last_key_value());
+        assert_eq!(btree.len(), flat.len());
+    }
+
+    /// `cargo test -p lux-chart-exchange --release -- --ignored --nocapture depth_bench`
+    #[test]
+    #[ignore]
+    fn depth_bench() {
+        const LEVELS: i64 = 10_000;
+        const PASSES: usize = 500;
+
+        let snapshot = diffs(20_000, LEVELS);
+        let updates = diffs(200_000, LEVELS);
+
+        let mut btree: BTreeMap<Price, f32> = snapshot.iter().copied().collect();
+        let mut flat: FlatLevels = snapshot.iter().copied().collect();
+
+        let start = Instant::now();
+        apply_btree(&mut btree, &updates);
+        let btree_diff = start.elapsed();
+
+        let start = Instant::now();
+        apply_flat(&mut flat, &updates);
+        let flat_diff = start.elapsed();
+
+        let start = Instant::now();
+        let btree_sum: f32 = (0..PASSES).map(|_| btree.values().sum::<f32>()).sum();
+        let btree_iter = start.elapsed();
+
+        let start = Instant::now();
+        let flat_sum: f32 = (0..PASSES)
+            .map(|_| flat.iter().map(|(_, q)| q).sum::<f32>())
+            .sum();
+        let flat_iter = start.elapsed();
+
+        assert_eq!(btree_sum, flat_sum);
+
+        println!(
+            "{} levels, {} diffs, {PASSES} full passes",
+            flat.len(),
+            updates.len()
+        );
+        println!("diffs:  btree {btree_diff:?}, flat {flat_diff:?}");
+        println!("passes: btree {btree_iter:?}, flat {flat_iter:?}");
+    }
+}
@@ -0,0 +1,166 @@
+//! Venue handling of websocket frames, kept apart from the connection so the same code
+//! turns live frames and recorded payloads into [`Event`]s
+
+use crate::TickerInfo;
+use crate::adapter::Event;
+use crate::depth::DepthPayload;
+
+use iced_futures::futures::{SinkExt, channel::mpsc};
+use rustc_hash::FxHashMap;
+
+use std::sync::{LazyLock, Mutex};
+
+/// Depth updates per second a listing is conflated to
+#[derive(Debug, Clone, Copy)]
+struct Conflation {
+    rate: u16,
+    /// Exchange time (ms) of the last update let through
+    last_ms: Option<u64>,
+}
+
+type ConflationMap = FxHashMap<TickerInfo, Conflation>;
+
+static CONFLATION: LazyLock<Mutex<ConflationMap>> =
+    LazyLock::new(|| Mutex::new(FxHashMap::default()));
+
+/// What a stream does after a frame
+#[derive(Debug)]
+pub enum Step {
+    Emit(Event),
+    /// The local book can't continue from the frame, a fresh snapshot has to be fed in
+    /// before diffs apply again
+    Resync,
+    /// The stream can't recover in place and has to reconnect
+    Reconnect(String),
+}
+
+pub trait Feed {
+    /// Replaces the local book with one fetched out of band, for venues that don't send
+    /// snapshots in the stream
+    fn snapshot(&mut self, _depth: DepthPayload) {}
+
+    /// Steps of one text frame, in order. Frames carrying no data yield none
+    fn frame(&mut self, payload: &[u8]) -> Vec<Step>;
+}
+
+/// Sends the events of a frame in order, conflated by [`set_depth_rates`], returning why the
+/// stream has to reconnect if it does. For venues that send a snapshot on subscribing,
+/// resyncing means reconnecting
+pub async fn forward(steps: Vec<Step>, output: &mut mpsc::Sender<Event>) -> Option<String> {
+    for step in steps {
+        match step {
+            Step::Emit(event) => emit(event, output).await,
+            Step::Resync => return Some("Out of sync orderbook, resubscribing".to_string()),
+            Step::Reconnect(reason) => return Some(reason),
+        }
+    }
+    None
+}
+
+/// Sends one event, conflated by [`set_depth_rates`]. For venues that resync in place
+/// rather than through [`forward`]
+pub async fn emit(event: Event, output: &mut mpsc::Sender<Event>) {
+    if let Some(event) = conflate(event) {
+        let _ = output.send(event).await;
+    }
+}
+
+/// Caps the depth updates of each listing to as many per second, listings left out get
+/// every update the venue sends
+pub fn set_depth_rates(rates: impl IntoIterator<Item = (TickerInfo, u16)>) {
+    let Ok(mut conflation) = CONFLATION.lock() else {
+        return;
+    };
+    let previous = std::mem::take(&mut *conflation);
+
+    for (ticker_info, rate) in rates {
+        let last_ms = previous.get(&ticker_info).and_then(|prev| prev.last_ms);
+        conflation.insert(
+            ticker_info,
+            Conflation {
+                rate: rate.max(1),
+                last_ms,
+            },
+        );
+    }
+}
+
+/// Lets a depth update through if its listing's rate allows, otherwise only its trades.
+/// Books are sent whole, so the next update let through carries what the dropped ones changed
+fn conflate(event: Event) -> Option<Event> {
+    let Event::DepthReceived(stream, time, depth, trades) = event else {
+        return Some(event);
+    };
+
+    if let Ok(mut conflation) = CONFLATION.lock()
+        && let Some(entry) = conflation.get_mut(&stream.ticker_info())
+    {
+        let interval = 1000 / u64::from(entry.rate);
+        // an earlier time means the venue's clock or the book restarted
+        if entry
+            .last_ms
+            .is_some_and(|last| time >= last && time - last < interval)
+        {
+            return (!trades.is_empty()).then_some(Event::TradesReceived(stream, trades));
+        }
+        entry.last_ms = Some(time);
+    }
+
+    Some(Event::DepthReceived(stream, time, depth, trades))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::{Exchange, StreamKind, StreamTicksize};
+    use crate::depth::Depth;
+    use crate::util::Price;
+    use crate::{PushFrequency, Ticker, Trade};
+    use std::sync::Arc;
+
+    #[test]
+    fn conflated_updates_keep_their_trades() {
+        let ticker_info = TickerInfo::new(
+            Ticker::new("CONFLATEUSD", Exchange::Simulated),
+            0.1,
+            0.001,
+            None,
+        );
+        let stream = StreamKind::DepthAndTrades {
+            ticker_info,
+            depth_aggr: StreamTicksize::Client,
+            push_freq: PushFrequency::ServerDefault,
+        };
+        set_depth_rates([(ticker_info, 2)]);
+
+        let update = |time: u64, trades: usize| {
+            let trade = Trade {
+                time,
+                is_sell: false,
+                price: Price::from_f32(100.0),
+                qty: 1.0,
+                sub_ms_nanos: 0,
+            };
+            Event::DepthReceived(
+                stream,
+                time,
+                Arc::new(Depth::default()),
+                vec![trade; trades].into_boxed_slice(),
+            )
+        };
+
+        let kinds: Vec<_> = [(0, 1), (100, 2), (200, 0), (500, 1), (400, 0)]
+            .into_iter()
+            .map(|(time, trades)| match conflate(update(time, trades)) {
+                Some(Event::DepthReceived(_, time, ..)) => format!("depth {time}"),
+                Some(Event::TradesReceived(_, trades)) => format!("{} trades", trades.len()),
+                _ => "none".to_string(),
+            })
+            .collect();
+
+        assert_eq!(
+            kinds,
+            ["depth 0", "2 trades", "none", "depth 500", "depth 400"]
+        );
+    }
+}
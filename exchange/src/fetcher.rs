@@ -1,26 +1,48 @@
-use crate::adapter::StreamKind;
+use crate::adapter::{ExchangeInclusive, StreamKind};
+use crate::composite::AggregatedOpenInterest;
 use crate::{Kline, OpenInterest, Trade};
 
 use smallvec::SmallVec;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU8, Ordering};
 use uuid::Uuid;
 
-static TRADE_FETCH_ENABLED: AtomicBool = AtomicBool::new(false);
+/// Venues the user consented to fetch historical trades from, one bit per `ExchangeInclusive::ALL` index
+static TRADE_FETCH_VENUES: AtomicU8 = AtomicU8::new(0);
 
-pub fn toggle_trade_fetch(value: bool) {
-    TRADE_FETCH_ENABLED.store(value, Ordering::Relaxed);
+fn venue_bit(venue: ExchangeInclusive) -> u8 {
+    let idx = ExchangeInclusive::ALL
+        .iter()
+        .position(|v| *v == venue)
+        .unwrap_or_default();
+    1 << idx
 }
 
-pub fn is_trade_fetch_enabled() -> bool {
-    TRADE_FETCH_ENABLED.load(Ordering::Relaxed)
+pub fn toggle_trade_fetch(venue: ExchangeInclusive, value: bool) {
+    if value {
+        TRADE_FETCH_VENUES.fetch_or(venue_bit(venue), Ordering::Relaxed);
+    } else {
+        TRADE_FETCH_VENUES.fetch_and(!venue_bit(venue), Ordering::Relaxed);
+    }
+}
+
+pub fn is_trade_fetch_enabled(venue: ExchangeInclusive) -> bool {
+    venue.supports_trade_fetch()
+        && TRADE_FETCH_VENUES.load(Ordering::Relaxed) & venue_bit(venue) != 0
+}
+
+pub fn trade_fetch_venues() -> Vec<ExchangeInclusive> {
+    ExchangeInclusive::ALL
+        .into_iter()
+        .filter(|venue| is_trade_fetch_enabled(*venue))
+        .collect()
 }
 
 #[derive(Debug, Clone)]
 pub enum FetchedData {
     Trades {
         batch: Vec<Trade>,
-        until_time: u64,
+        is_complete: bool,
     },
     Klines {
         data: Vec<Kline>,
@@ -30,6 +52,10 @@ pub enum FetchedData {
         data: Vec<OpenInterest>,
         req_id: Option<uuid::Uuid>,
     },
+    AggregatedOI {
+        data: Vec<AggregatedOpenInterest>,
+        req_id: Option<uuid::Uuid>,
+    },
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -118,6 +144,7 @@ impl Default for RequestHandler {
 pub enum FetchRange {
     Kline(u64, u64),
     OpenInterest(u64, u64),
+    AggregatedOpenInterest(u64, u64),
     Trades(u64, u64),
 }
 
@@ -138,9 +165,11 @@ impl FetchRequest {
     fn same_with(&self, other: &FetchRequest) -> bool {
         match (&self.fetch_type, &other.fetch_type) {
             (FetchRange::Kline(s1, e1), FetchRange::Kline(s2, e2)) => e1 == e2 && s1 == s2,
-            (FetchRange::OpenInterest(s1, e1), FetchRange::OpenInterest(s2, e2)) => {
-                e1 == e2 && s1 == s2
-            }
+            (FetchRange::OpenInterest(s1, e1), FetchRange::OpenInterest(s2, e2))
+            | (
+                FetchRange::AggregatedOpenInterest(s1, e1),
+                FetchRange::AggregatedOpenInterest(s2, e2),
+            ) => e1 == e2 && s1 == s2,
             _ => false,
         }
     }
@@ -0,0 +1,346 @@
+//! Closed klines persisted to disk per venue, ticker and timeframe, so reopening a pane
+//! or scrolling back over already fetched history doesn't hit the exchange again.
+
+use crate::adapter::{self, AdapterError};
+use crate::{Kline, SizeUnit, TickerInfo, Timeframe, volume_size_unit};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Files older than this are dropped and fetched again, picking up corrections from the venue
+const TTL_MS: u64 = 7 * 86_400_000;
+/// Bars kept per file, the oldest go first
+const MAX_BARS: usize = 50_000;
+/// A latest fetch is served from disk only when the cached bars end within this many bars of now
+const MAX_TAIL_BARS: u64 = 200;
+/// Bars served from disk in place of a latest fetch
+const LATEST_BARS: u64 = 1_000;
+/// Beyond this many holes the missing range is refetched as a single span
+const MAX_GAP_FETCHES: usize = 4;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    created_at: u64,
+    /// Sorted, disjoint and inclusive ranges known to hold every bar the venue has
+    covered: Vec<(u64, u64)>,
+    /// Closed bars by open time
+    klines: BTreeMap<u64, Kline>,
+}
+
+impl CacheFile {
+    /// Parts of `start..=end` not covered yet
+    fn missing(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        let mut gaps = vec![];
+        let mut cursor = start;
+
+        for &(from, to) in &self.covered {
+            if to < cursor {
+                continue;
+            }
+            if from > end {
+                break;
+            }
+            if from > cursor {
+                gaps.push((cursor, from - 1));
+            }
+            cursor = to.saturating_add(1);
+            if cursor > end {
+                return gaps;
+            }
+        }
+
+        gaps.push((cursor, end));
+        gaps
+    }
+
+    /// Stores the closed bars of a fetch over `requested`, `None` for a latest fetch,
+    /// and marks the span they prove complete as covered
+    fn insert(
+        &mut self,
+        klines: &[Kline],
+        requested: Option<(u64, u64)>,
+        interval: u64,
+        closed_until: u64,
+    ) {
+        let first = klines.iter().map(|k| k.time).min();
+        let last = klines.iter().map(|k| k.time).max();
+
+        let span = match (requested, first, last) {
+            (Some((start, end)), Some(first), Some(last)) => {
+                // venues cap bars per request, only trust the ends the response reached
+                let from = if first <= start.saturating_add(interval) {
+                    start
+                } else {
+                    first
+                };
+                let to = if last.saturating_add(interval) > end {
+                    end
+                } else {
+                    last + interval - 1
+                };
+                Some((from, to))
+            }
+            // nothing traded in a past range, e.g. before the listing
+            (Some(range), None, None) => Some(range),
+            (None, Some(first), Some(last)) => Some((first, last + interval - 1)),
+            _ => None,
+        };
+
+        for kline in klines {
+            if kline.time.saturating_add(interval - 1) <= closed_until {
+                self.klines.insert(kline.time, *kline);
+            }
+        }
+
+        if let Some((from, to)) = span {
+            let to = to.min(closed_until);
+            if from <= to {
+                self.covered.push((from, to));
+                self.merge_covered();
+            }
+        }
+
+        if self.klines.len() > MAX_BARS {
+            let keep_from = *self
+                .klines
+                .keys()
+                .nth(self.klines.len() - MAX_BARS)
+                .expect("more bars than the cap");
+            self.klines = self.klines.split_off(&keep_from);
+            self.covered.retain_mut(|(from, to)| {
+                *from = (*from).max(keep_from);
+                from <= to
+            });
+        }
+    }
+
+    fn merge_covered(&mut self) {
+        self.covered.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.covered.len());
+        for (from, to) in self.covered.drain(..) {
+            match merged.last_mut() {
+                Some(prev) if from <= prev.1.saturating_add(1) => prev.1 = prev.1.max(to),
+                _ => merged.push((from, to)),
+            }
+        }
+        self.covered = merged;
+    }
+}
+
+/// `adapter::fetch_klines` that serves closed bars from `cache_dir` and only
+/// asks the venue for the parts of the range the cache doesn't cover
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+    cache_dir: PathBuf,
+) -> Result<Vec<Kline>, AdapterError> {
+    let interval = timeframe.to_milliseconds();
+    // sub-minute bars are built from trades, venues don't serve them
+    if interval < 60_000 {
+        return adapter::fetch_klines(ticker_info, timeframe, range).await;
+    }
+
+    let path = file_path(&cache_dir, ticker_info, timeframe);
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    // the forming bar keeps changing, only bars closed by now get stored
+    let closed_until = (now - now % interval).saturating_sub(1);
+
+    let mut cache = load(&path, now);
+
+    let (start, end) = match range {
+        Some(range) => range,
+        None => match cache.covered.last() {
+            Some(&(from, to)) if to.saturating_add(MAX_TAIL_BARS * interval) >= closed_until => {
+                let start = (to + 1).saturating_sub(LATEST_BARS * interval).max(from);
+                (start, now)
+            }
+            _ => {
+                let klines = adapter::fetch_klines(ticker_info, timeframe, None).await?;
+                cache.insert(&klines, None, interval, closed_until);
+                save(&path, &cache);
+                return Ok(klines);
+            }
+        },
+    };
+
+    let mut gaps = cache.missing(start, end);
+    if gaps.len() > MAX_GAP_FETCHES
+        && let (Some(first), Some(last)) = (gaps.first(), gaps.last())
+    {
+        gaps = vec![(first.0, last.1)];
+    }
+
+    let mut fetched = vec![];
+    for gap in &gaps {
+        let klines = adapter::fetch_klines(ticker_info, timeframe, Some(*gap)).await?;
+        cache.insert(&klines, Some(*gap), interval, closed_until);
+        fetched.extend(klines);
+    }
+
+    if !gaps.is_empty() {
+        save(&path, &cache);
+    }
+
+    let mut klines: BTreeMap<u64, Kline> = cache
+        .klines
+        .range(start..=end)
+        .map(|(time, kline)| (*time, *kline))
+        .collect();
+    // the forming bar only comes from the venue
+    klines.extend(fetched.into_iter().map(|kline| (kline.time, kline)));
+
+    Ok(klines.into_values().collect())
+}
+
+fn file_path(cache_dir: &Path, ticker_info: TickerInfo, timeframe: Timeframe) -> PathBuf {
+    let (symbol, _) = ticker_info.ticker.to_full_symbol_and_type();
+    let symbol: String = symbol
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    // volumes are stored in whichever unit was preferred when fetched
+    let unit = match volume_size_unit() {
+        SizeUnit::Base => "base",
+        SizeUnit::Quote => "quote",
+    };
+
+    cache_dir
+        .join(format!("{:?}", ticker_info.ticker.exchange))
+        .join(format!("{symbol}_{timeframe}_{unit}.json"))
+}
+
+fn load(path: &Path, now: u64) -> CacheFile {
+    let cache = std::fs::read(path).ok().and_then(|bytes| {
+        match serde_json::from_slice::<CacheFile>(&bytes) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                log::warn!("Discarding unreadable kline cache {path:?}: {e}");
+                None
+            }
+        }
+    });
+
+    match cache {
+        Some(cache) if now.saturating_sub(cache.created_at) < TTL_MS => cache,
+        _ => CacheFile {
+            created_at: now,
+            ..CacheFile::default()
+        },
+    }
+}
+
+fn save(path: &Path, cache: &CacheFile) {
+    let write = || -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // write aside and swap, so a pane reading concurrently never sees half a file
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(cache)?)?;
+        std::fs::rename(&tmp_path, path)
+    };
+
+    if let Err(e) = write() {
+        log::warn!("Failed to write kline cache {path:?}: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Price;
+
+    const MIN: u64 = 60_000;
+
+    fn kline(time: u64) -> Kline {
+        let price = Price::from_f32(1.0);
+        Kline {
+            time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: (1.0, 1.0),
+        }
+    }
+
+    fn bars(from: u64, to: u64) -> Vec<Kline> {
+        (from..=to).map(|i| kline(i * MIN)).collect()
+    }
+
+    #[test]
+    fn missing_reports_holes_between_covered_ranges() {
+        let cache = CacheFile {
+            covered: vec![(10 * MIN, 20 * MIN - 1), (30 * MIN, 40 * MIN - 1)],
+            ..CacheFile::default()
+        };
+
+        assert_eq!(
+            cache.missing(0, 50 * MIN - 1),
+            vec![
+                (0, 10 * MIN - 1),
+                (20 * MIN, 30 * MIN - 1),
+                (40 * MIN, 50 * MIN - 1)
+            ]
+        );
+        assert!(cache.missing(12 * MIN, 18 * MIN).is_empty());
+        assert_eq!(
+            cache.missing(15 * MIN, 35 * MIN),
+            vec![(20 * MIN, 30 * MIN - 1)]
+        );
+    }
+
+    #[test]
+    fn insert_merges_adjacent_coverage() {
+        let mut cache = CacheFile::default();
+        let closed_until = 100 * MIN - 1;
+
+        cache.insert(&bars(0, 9), Some((0, 10 * MIN - 1)), MIN, closed_until);
+        cache.insert(
+            &bars(10, 19),
+            Some((10 * MIN, 20 * MIN - 1)),
+            MIN,
+            closed_until,
+        );
+
+        assert_eq!(cache.covered, vec![(0, 20 * MIN - 1)]);
+        assert_eq!(cache.klines.len(), 20);
+    }
+
+    #[test]
+    fn capped_response_only_covers_what_it_reached() {
+        let mut cache = CacheFile::default();
+
+        // asked for 50 bars, the venue returned the first 20
+        cache.insert(&bars(0, 19), Some((0, 50 * MIN - 1)), MIN, u64::MAX);
+
+        assert_eq!(cache.covered, vec![(0, 20 * MIN - 1)]);
+        assert_eq!(
+            cache.missing(0, 50 * MIN - 1),
+            vec![(20 * MIN, 50 * MIN - 1)]
+        );
+    }
+
+    #[test]
+    fn forming_bar_is_never_stored() {
+        let mut cache = CacheFile::default();
+        let closed_until = 10 * MIN - 1;
+
+        cache.insert(&bars(0, 10), None, MIN, closed_until);
+
+        assert!(!cache.klines.contains_key(&(10 * MIN)));
+        assert_eq!(cache.covered, vec![(0, closed_until)]);
+    }
+
+    #[test]
+    fn empty_past_range_is_covered() {
+        let mut cache = CacheFile::default();
+
+        cache.insert(&[], Some((0, 10 * MIN - 1)), MIN, u64::MAX);
+
+        assert!(cache.missing(0, 10 * MIN - 1).is_empty());
+    }
+}
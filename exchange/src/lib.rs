@@ -1,8 +1,16 @@
 pub mod adapter;
+pub mod auth;
+pub mod clock;
+pub mod composite;
 pub mod connect;
 pub mod depth;
+pub mod feed;
 pub mod fetcher;
-mod limiter;
+pub mod kline_cache;
+pub mod limiter;
+pub mod network;
+pub mod schedule;
+pub mod scheduler;
 pub mod util;
 
 use crate::util::{ContractSize, MinQtySize, MinTicksize, Price};
@@ -223,6 +231,9 @@ impl SerTicker {
             Exchange::OkexInverse => "OkexInverse",
             Exchange::OkexSpot => "OkexSpot",
             Exchange::Forex => "Forex",
+            Exchange::DeribitLinear => "DeribitLinear",
+            Exchange::DeribitInverse => "DeribitInverse",
+            Exchange::Simulated => "Simulated",
         }
     }
 
@@ -240,6 +251,9 @@ impl SerTicker {
             "OkexInverse" => Ok(Exchange::OkexInverse),
             "OkexSpot" => Ok(Exchange::OkexSpot),
             "Forex" => Ok(Exchange::Forex),
+            "DeribitLinear" => Ok(Exchange::DeribitLinear),
+            "DeribitInverse" => Ok(Exchange::DeribitInverse),
+            "Simulated" => Ok(Exchange::Simulated),
             _ => Err(format!("Unknown exchange: {}", s)),
         }
     }
@@ -582,9 +596,66 @@ pub struct Trade {
     pub is_sell: bool,
     pub price: Price,
     pub qty: f32,
+    /// Nanoseconds past `time`'s millisecond, 0 from millisecond sources.
+    /// Keeps prints of the same millisecond in order when sources get merged
+    #[serde(default)]
+    pub sub_ms_nanos: u32,
 }
 
+impl Trade {
+    pub fn sort_key(&self) -> (u64, u32) {
+        (self.time, self.sub_ms_nanos)
+    }
+}
+
+/// Resolution of a source's raw timestamps, everything past the adapters works in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimeUnit {
+    /// Unit of an epoch timestamp judged by its magnitude, for sources that don't state it.
+    /// Milliseconds only reach 1e14 in the year 5138
+    pub fn infer(raw: u64) -> Self {
+        match raw {
+            t if t >= 100_000_000_000_000_000 => TimeUnit::Nanos,
+            t if t >= 100_000_000_000_000 => TimeUnit::Micros,
+            _ => TimeUnit::Millis,
+        }
+    }
+
+    fn per_milli(self) -> u64 {
+        match self {
+            TimeUnit::Millis => 1,
+            TimeUnit::Micros => 1_000,
+            TimeUnit::Nanos => 1_000_000,
+        }
+    }
+
+    pub fn to_millis(self, raw: u64) -> u64 {
+        raw / self.per_milli()
+    }
+
+    /// Part of `raw` below a millisecond, in nanoseconds
+    pub fn sub_milli_nanos(self, raw: u64) -> u32 {
+        let per_milli = self.per_milli();
+        ((raw % per_milli) * (1_000_000 / per_milli)) as u32
+    }
+}
+
+/// Forced close of a leveraged position, `is_sell` when a long got liquidated
 #[derive(Debug, Clone, Copy)]
+pub struct Liquidation {
+    pub time: u64,
+    pub is_sell: bool,
+    pub price: Price,
+    pub qty: f32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct Kline {
     pub time: u64,
     pub open: Price,
@@ -622,6 +693,16 @@ pub struct TickerStats {
     pub daily_volume: f32,
 }
 
+/// Funding state of a perpetual, rates are fractions per funding interval (0.0001 = 0.01%)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingInfo {
+    pub rate: f32,
+    /// Estimate for the following interval, only some venues publish one
+    pub predicted_rate: Option<f32>,
+    /// Unix timestamp in milliseconds of the next settlement
+    pub next_funding_time: u64,
+}
+
 pub fn is_symbol_supported(symbol: &str, exchange: Exchange, log: bool) -> bool {
     let valid_symbol = symbol
         .chars()
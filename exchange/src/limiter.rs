@@ -1,13 +1,223 @@
-use crate::adapter::AdapterError;
+use crate::adapter::{AdapterError, ExchangeInclusive};
+use crate::network::{Proxy, ProxyKind};
 
-use reqwest::{Client, Method, Response};
+use reqwest::{Client, Method, Response, StatusCode};
+use rustc_hash::FxHashMap;
 use serde_json::Value;
-use std::sync::LazyLock;
+use std::collections::VecDeque;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-pub static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+/// Swapped out whenever the proxy changes, see [`rebuild_http_client`]
+static HTTP_CLIENT: LazyLock<RwLock<Client>> = LazyLock::new(|| RwLock::new(Client::new()));
+
+pub fn http_client() -> Client {
+    HTTP_CLIENT
+        .read()
+        .map(|client| client.clone())
+        .unwrap_or_default()
+}
+
+pub(crate) fn rebuild_http_client(proxy: Option<&Proxy>) -> Result<(), AdapterError> {
+    let mut builder = Client::builder();
+
+    if let Some(proxy) = proxy {
+        let scheme = match proxy.kind {
+            ProxyKind::Http => "http",
+            // resolve hosts on the proxy side, the local resolver may be what's blocked
+            ProxyKind::Socks5 => "socks5h",
+        };
+
+        let mut reqwest_proxy =
+            reqwest::Proxy::all(format!("{scheme}://{}:{}", proxy.host, proxy.port))
+                .map_err(AdapterError::FetchError)?;
+        if let Some((username, password)) = proxy.auth() {
+            reqwest_proxy = reqwest_proxy.basic_auth(username, password);
+        }
+        builder = builder.proxy(reqwest_proxy);
+    }
+
+    let client = builder.build().map_err(AdapterError::FetchError)?;
+    if let Ok(mut current) = HTTP_CLIENT.write() {
+        *current = client;
+    }
+    Ok(())
+}
+
+pub const DEFAULT_MAX_CONCURRENT: usize = 4;
+pub const MAX_CONCURRENT_CHOICES: [usize; 4] = [1, 2, 4, 8];
+
+/// REST requests a venue may have in flight at once, on top of its weight budget
+static CONCURRENCY: LazyLock<RwLock<Concurrency>> = LazyLock::new(|| {
+    RwLock::new(Concurrency {
+        max: DEFAULT_MAX_CONCURRENT,
+        permits: FxHashMap::default(),
+    })
+});
+
+struct Concurrency {
+    max: usize,
+    permits: FxHashMap<ExchangeInclusive, Arc<Semaphore>>,
+}
+
+/// Requests already in flight keep counting against the previous cap until they finish
+pub fn set_max_concurrent(max: usize) {
+    let max = max.max(1);
+
+    if let Ok(mut concurrency) = CONCURRENCY.write()
+        && concurrency.max != max
+    {
+        concurrency.max = max;
+        concurrency.permits.clear();
+    }
+}
+
+pub fn max_concurrent() -> usize {
+    CONCURRENCY
+        .read()
+        .map(|concurrency| concurrency.max)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT)
+}
+
+async fn acquire_permit(venue: ExchangeInclusive) -> Option<OwnedSemaphorePermit> {
+    let semaphore = {
+        let mut concurrency = CONCURRENCY.write().ok()?;
+        let max = concurrency.max;
+
+        concurrency
+            .permits
+            .entry(venue)
+            .or_insert_with(|| Arc::new(Semaphore::new(max)))
+            .clone()
+    };
+
+    semaphore.acquire_owned().await.ok()
+}
+
+/// Waits a limiter forces beyond this are reported through [`take_throttles`]
+pub const THROTTLE_NOTICE_AFTER: Duration = Duration::from_secs(5);
+/// How far back rejected requests are counted
+pub const REJECTION_WINDOW: Duration = Duration::from_secs(600);
+
+/// Weight budget of a limiter at one point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    pub used: usize,
+    pub limit: usize,
+    /// Until the budget refills
+    pub refill_in: Duration,
+}
+
+/// A request held back by its venue's limiter for longer than [`THROTTLE_NOTICE_AFTER`]
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    pub venue: ExchangeInclusive,
+    pub wait: Duration,
+}
+
+/// What the dashboard shows of a venue's limiter
+#[derive(Debug, Clone)]
+pub struct LimiterStatus {
+    pub venue: ExchangeInclusive,
+    /// `None` before the first request, the refill countdown is as of now
+    pub usage: Option<Usage>,
+    /// Responses with status 429 within [`REJECTION_WINDOW`]
+    pub rejections: usize,
+    /// Latest wait the limiter forced, and when it started
+    pub last_wait: Option<(Duration, Instant)>,
+}
+
+#[derive(Default)]
+struct VenueStats {
+    usage: Option<(Usage, Instant)>,
+    rejections: VecDeque<Instant>,
+    last_wait: Option<(Duration, Instant)>,
+}
+
+static STATS: LazyLock<RwLock<FxHashMap<ExchangeInclusive, VenueStats>>> =
+    LazyLock::new(|| RwLock::new(FxHashMap::default()));
+static THROTTLES: Mutex<Vec<Throttle>> = Mutex::new(Vec::new());
+
+fn record(venue: ExchangeInclusive, f: impl FnOnce(&mut VenueStats)) {
+    if let Ok(mut stats) = STATS.write() {
+        f(stats.entry(venue).or_default());
+    }
+}
+
+fn record_usage(venue: ExchangeInclusive, usage: Usage) {
+    record(venue, |stats| stats.usage = Some((usage, Instant::now())));
+}
+
+fn record_wait(venue: ExchangeInclusive, wait: Duration) {
+    let now = Instant::now();
+    record(venue, |stats| stats.last_wait = Some((wait, now)));
+
+    if wait > THROTTLE_NOTICE_AFTER
+        && let Ok(mut throttles) = THROTTLES.lock()
+    {
+        throttles.push(Throttle { venue, wait });
+    }
+}
+
+fn record_rejection(venue: ExchangeInclusive) {
+    let now = Instant::now();
+    record(venue, |stats| {
+        stats.rejections.push_back(now);
+        while stats
+            .rejections
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > REJECTION_WINDOW)
+        {
+            stats.rejections.pop_front();
+        }
+    });
+}
+
+/// Limiters of every venue that made a request, in `ExchangeInclusive::ALL` order
+pub fn limiter_status() -> Vec<LimiterStatus> {
+    let now = Instant::now();
+    let Ok(stats) = STATS.read() else {
+        return vec![];
+    };
+
+    ExchangeInclusive::ALL
+        .into_iter()
+        .filter_map(|venue| {
+            let stats = stats.get(&venue)?;
+
+            let usage = stats.usage.map(|(usage, at)| Usage {
+                refill_in: usage.refill_in.saturating_sub(now.duration_since(at)),
+                ..usage
+            });
+            let rejections = stats
+                .rejections
+                .iter()
+                .filter(|at| now.duration_since(**at) <= REJECTION_WINDOW)
+                .count();
+
+            Some(LimiterStatus {
+                venue,
+                usage,
+                rejections,
+                last_wait: stats.last_wait,
+            })
+        })
+        .collect()
+}
+
+/// Long waits since the last call, oldest first
+pub fn take_throttles() -> Vec<Throttle> {
+    THROTTLES
+        .lock()
+        .map(|mut throttles| std::mem::take(&mut *throttles))
+        .unwrap_or_default()
+}
 
 pub trait RateLimiter: Send + Sync {
+    /// Venue whose concurrency cap the requests count against
+    const VENUE: ExchangeInclusive;
+
     /// Prepare for a request with given weight. Returns wait time if needed.
     fn prepare_request(&mut self, weight: usize) -> Option<Duration>;
 
@@ -16,6 +226,9 @@ pub trait RateLimiter: Send + Sync {
 
     /// Check if response indicates rate limiting and should exit
     fn should_exit_on_response(&self, response: &Response) -> bool;
+
+    /// Current state of the weight budget
+    fn usage(&self) -> Usage;
 }
 
 pub async fn http_request_with_limiter<L: RateLimiter>(
@@ -27,14 +240,24 @@ pub async fn http_request_with_limiter<L: RateLimiter>(
 ) -> Result<String, AdapterError> {
     let method = method.unwrap_or(Method::GET);
 
-    let mut limiter_guard = limiter.lock().await;
+    crate::network::check_url(url)?;
+
+    let _permit = acquire_permit(L::VENUE).await;
 
-    if let Some(wait_time) = limiter_guard.prepare_request(weight) {
-        log::warn!("Rate limit hit for: {url}. Waiting for {:?}", wait_time);
-        tokio::time::sleep(wait_time).await;
+    {
+        let mut limiter_guard = limiter.lock().await;
+
+        let wait = limiter_guard.prepare_request(weight);
+        record_usage(L::VENUE, limiter_guard.usage());
+
+        if let Some(wait_time) = wait {
+            log::warn!("Rate limit hit for: {url}. Waiting for {:?}", wait_time);
+            record_wait(L::VENUE, wait_time);
+            tokio::time::sleep(wait_time).await;
+        }
     }
 
-    let mut request_builder = HTTP_CLIENT.request(method.clone(), url);
+    let mut request_builder = http_client().request(method.clone(), url);
 
     if let Some(body) = json_body {
         request_builder = request_builder.json(body);
@@ -45,6 +268,12 @@ pub async fn http_request_with_limiter<L: RateLimiter>(
         .await
         .map_err(AdapterError::FetchError)?;
 
+    let mut limiter_guard = limiter.lock().await;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        record_rejection(L::VENUE);
+    }
+
     if limiter_guard.should_exit_on_response(&response) {
         let status = response.status();
         log::error!(
@@ -56,6 +285,8 @@ pub async fn http_request_with_limiter<L: RateLimiter>(
     }
 
     limiter_guard.update_from_response(&response, weight);
+    record_usage(L::VENUE, limiter_guard.usage());
+    drop(limiter_guard);
 
     response.text().await.map_err(AdapterError::FetchError)
 }
@@ -165,6 +396,25 @@ impl FixedWindowBucket {
         self.refill();
         self.available_tokens -= tokens.min(self.available_tokens);
     }
+
+    pub fn usage(&self) -> Usage {
+        Usage {
+            used: self.max_tokens - self.available_tokens,
+            limit: self.max_tokens,
+            refill_in: self.refill_rate.saturating_sub(self.last_refill.elapsed()),
+        }
+    }
+}
+
+/// Time left in the wall clock window of length `period`, venues reporting used weight
+/// reset it on window boundaries
+fn window_remaining(period: Duration) -> Duration {
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let period_seconds = period.as_secs().max(1);
+    Duration::from_secs(period_seconds - current_time.as_secs() % period_seconds)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -226,18 +476,24 @@ impl DynamicBucket {
             return (None, None);
         }
 
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-
-        let period_seconds = self.refill_rate.as_secs();
-        let seconds_in_period = current_time.as_secs() % period_seconds;
-        let wait_time = Duration::from_secs(period_seconds - seconds_in_period)
-            .saturating_add(Duration::from_millis(500));
+        let wait_time =
+            window_remaining(self.refill_rate).saturating_add(Duration::from_millis(500));
 
         (Some(wait_time), Some(DynamicLimitReason::HeaderRate))
     }
 
+    pub fn usage(&self) -> Usage {
+        if self.last_updated.elapsed() <= self.refill_rate && self.current_used_weight > 0 {
+            Usage {
+                used: self.current_used_weight,
+                limit: self.max_weight,
+                refill_in: window_remaining(self.refill_rate),
+            }
+        } else {
+            self.fallback_bucket.usage()
+        }
+    }
+
     fn prepare_with_fallback(
         &mut self,
         weight: usize,
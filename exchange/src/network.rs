@@ -0,0 +1,155 @@
+//! Offline mode, blocks outbound requests and stream connections to hosts the user hasn't allowed,
+//! demo mode, which blocks all of them, and the proxy both go through
+
+use crate::adapter::AdapterError;
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static DEMO: AtomicBool = AtomicBool::new(false);
+static ALLOWED_HOSTS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+static PROXY: RwLock<Option<Proxy>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum ProxyKind {
+    /// HTTP proxy tunneling through `CONNECT`
+    #[default]
+    Http,
+    Socks5,
+}
+
+impl ProxyKind {
+    pub const ALL: [ProxyKind; 2] = [ProxyKind::Http, ProxyKind::Socks5];
+}
+
+impl std::fmt::Display for ProxyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProxyKind::Http => write!(f, "HTTP"),
+            ProxyKind::Socks5 => write!(f, "SOCKS5"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Proxy {
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Proxy {
+    /// Credentials, only when a username is set
+    pub fn auth(&self) -> Option<(&str, &str)> {
+        self.username
+            .as_deref()
+            .filter(|username| !username.is_empty())
+            .map(|username| (username, self.password.as_deref().unwrap_or_default()))
+    }
+}
+
+/// Routes REST requests and new stream connections through `proxy`, open streams keep
+/// their connection until they reconnect
+pub fn set_proxy(proxy: Option<Proxy>) -> Result<(), AdapterError> {
+    crate::limiter::rebuild_http_client(proxy.as_ref())?;
+
+    if let Ok(mut current) = PROXY.write() {
+        *current = proxy;
+    }
+    Ok(())
+}
+
+pub fn proxy() -> Option<Proxy> {
+    PROXY.read().ok().and_then(|proxy| proxy.clone())
+}
+
+pub fn set_offline(value: bool) {
+    OFFLINE.store(value, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Only the simulated venue is listed and nothing is fetched, applied at startup
+pub fn set_demo(value: bool) {
+    DEMO.store(value, Ordering::Relaxed);
+}
+
+pub fn is_demo() -> bool {
+    DEMO.load(Ordering::Relaxed)
+}
+
+/// Hosts still reachable in offline mode, a host also allows its subdomains
+pub fn set_allowed_hosts(hosts: Vec<String>) {
+    let hosts = hosts
+        .into_iter()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect();
+
+    if let Ok(mut allowed) = ALLOWED_HOSTS.write() {
+        *allowed = hosts;
+    }
+}
+
+pub fn allowed_hosts() -> Vec<String> {
+    ALLOWED_HOSTS
+        .read()
+        .map(|allowed| allowed.clone())
+        .unwrap_or_default()
+}
+
+fn is_host_allowed(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+
+    ALLOWED_HOSTS.read().is_ok_and(|allowed| {
+        allowed.iter().any(|entry| {
+            host == *entry
+                || host
+                    .strip_suffix(entry.as_str())
+                    .is_some_and(|rest| rest.ends_with('.'))
+        })
+    })
+}
+
+/// Errors in demo mode, or if offline mode is on and the host of `url` isn't allowed
+pub fn check_url(url: &str) -> Result<(), AdapterError> {
+    if !is_offline() && !is_demo() {
+        return Ok(());
+    }
+
+    let host = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string());
+
+    check_host(&host)
+}
+
+/// Errors in demo mode, or if offline mode is on and `host` isn't allowed
+pub fn check_host(host: &str) -> Result<(), AdapterError> {
+    if is_demo() || (is_offline() && !is_host_allowed(host)) {
+        return Err(AdapterError::Offline(host.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist_matches_host_and_subdomains() {
+        set_allowed_hosts(vec![" Binance.com ".to_string()]);
+
+        assert!(is_host_allowed("binance.com"));
+        assert!(is_host_allowed("fapi.binance.com"));
+        assert!(!is_host_allowed("notbinance.com"));
+        assert!(!is_host_allowed("bybit.com"));
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Datelike, Timelike, Weekday};
+
+const DAY_MS: u64 = 86_400_000;
+const WEEK_MS: u64 = 7 * DAY_MS;
+
+/// A point in the trading week, in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeeklyTime {
+    pub weekday: Weekday,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl WeeklyTime {
+    pub const fn new(weekday: Weekday, hour: u32, minute: u32) -> Self {
+        Self {
+            weekday,
+            hour,
+            minute,
+        }
+    }
+
+    /// Milliseconds elapsed since Monday 00:00 UTC
+    fn offset_ms(self) -> u64 {
+        u64::from(self.weekday.num_days_from_monday()) * DAY_MS
+            + u64::from(self.hour) * 3_600_000
+            + u64::from(self.minute) * 60_000
+    }
+}
+
+/// When a venue accepts connections and publishes data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketSchedule {
+    /// Trades around the clock, e.g. crypto venues
+    AlwaysOpen,
+    /// Closed once a week from `closes` until `reopens`
+    WeeklyBreak {
+        closes: WeeklyTime,
+        reopens: WeeklyTime,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketStatus {
+    Open,
+    /// Scheduled downtime, `reopens_at` is a unix timestamp in milliseconds
+    Closed {
+        reopens_at: u64,
+    },
+}
+
+impl MarketSchedule {
+    /// Spot FX: closes Friday 22:00 UTC, reopens Sunday 22:00 UTC
+    pub const FOREX: Self = Self::WeeklyBreak {
+        closes: WeeklyTime::new(Weekday::Fri, 22, 0),
+        reopens: WeeklyTime::new(Weekday::Sun, 22, 0),
+    };
+
+    pub fn status_at(&self, now_ms: u64) -> MarketStatus {
+        let Self::WeeklyBreak { closes, reopens } = self else {
+            return MarketStatus::Open;
+        };
+
+        let Some(now) = DateTime::from_timestamp_millis(now_ms as i64) else {
+            return MarketStatus::Open;
+        };
+
+        let week_offset = u64::from(now.weekday().num_days_from_monday()) * DAY_MS
+            + u64::from(now.num_seconds_from_midnight()) * 1000
+            + u64::from(now.timestamp_subsec_millis());
+
+        let since_close = (week_offset + WEEK_MS - closes.offset_ms()) % WEEK_MS;
+        let break_len = (reopens.offset_ms() + WEEK_MS - closes.offset_ms()) % WEEK_MS;
+
+        if since_close < break_len {
+            MarketStatus::Closed {
+                reopens_at: now_ms + (break_len - since_close),
+            }
+        } else {
+            MarketStatus::Open
+        }
+    }
+
+    pub fn status_now(&self) -> MarketStatus {
+        self.status_at(chrono::Utc::now().timestamp_millis() as u64)
+    }
+}
+
+/// Formats a remaining duration as "2d 3h", "5h 12m" or "12m"
+pub fn format_countdown(remaining_ms: u64) -> String {
+    let total_minutes = remaining_ms.div_ceil(60_000);
+    let (days, hours, minutes) = (
+        total_minutes / 1440,
+        (total_minutes % 1440) / 60,
+        total_minutes % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> u64 {
+        DateTime::parse_from_rfc3339(s).unwrap().timestamp_millis() as u64
+    }
+
+    #[test]
+    fn forex_weekend_break() {
+        let schedule = MarketSchedule::FOREX;
+
+        assert_eq!(
+            schedule.status_at(ts("2024-06-05T12:00:00Z")),
+            MarketStatus::Open
+        );
+        assert_eq!(
+            schedule.status_at(ts("2024-06-07T21:59:59Z")),
+            MarketStatus::Open
+        );
+        assert_eq!(
+            schedule.status_at(ts("2024-06-08T10:00:00Z")),
+            MarketStatus::Closed {
+                reopens_at: ts("2024-06-09T22:00:00Z")
+            }
+        );
+        assert_eq!(
+            schedule.status_at(ts("2024-06-09T22:00:00Z")),
+            MarketStatus::Open
+        );
+    }
+
+    #[test]
+    fn countdown_formatting() {
+        assert_eq!(format_countdown((5 * 60 + 12) * 60_000), "5h 12m");
+        assert_eq!(format_countdown(30_000), "1m");
+        assert_eq!(format_countdown((26 * 60) * 60_000), "1d 2h");
+    }
+}
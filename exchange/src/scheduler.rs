@@ -0,0 +1,407 @@
+//! Queues historical REST fetches per venue so the ones a visible pane waits on go first,
+//! and lets identical fetches in flight share one request. Each venue runs as many at once
+//! as [`limiter::max_concurrent`] allows
+
+use crate::adapter::{self, AdapterError, ExchangeInclusive};
+use crate::{Kline, OpenInterest, Ticker, TickerInfo, Timeframe, composite, kline_cache, limiter};
+
+use iced_futures::futures::future::{BoxFuture, FutureExt, WeakShared};
+use rustc_hash::FxHashMap;
+use tokio::sync::oneshot;
+
+use std::any::Any;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Higher goes first, requests of the same priority in arrival order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Data nobody looks at yet, e.g. scanner rows
+    Prefetch,
+    /// Panes that are open but covered, or catching up after a reconnect
+    Background,
+    Visible,
+}
+
+impl Priority {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Priority::Prefetch,
+            1 => Priority::Background,
+            _ => Priority::Visible,
+        }
+    }
+}
+
+/// What a fetch asks for, identical keys in flight share one request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RequestKey {
+    Klines {
+        ticker_info: TickerInfo,
+        timeframe: Timeframe,
+        range: Option<(u64, u64)>,
+    },
+    OpenInterest {
+        ticker: Ticker,
+        timeframe: Timeframe,
+        range: Option<(u64, u64)>,
+    },
+    AggregatedOpenInterest {
+        ticker: Ticker,
+        timeframe: Timeframe,
+        range: Option<(u64, u64)>,
+    },
+}
+
+impl RequestKey {
+    /// Queue the request waits in, aggregates wait in the one of the listing they're for
+    fn venue(&self) -> ExchangeInclusive {
+        match self {
+            RequestKey::Klines { ticker_info, .. } => ExchangeInclusive::of(ticker_info.exchange()),
+            RequestKey::OpenInterest { ticker, .. }
+            | RequestKey::AggregatedOpenInterest { ticker, .. } => {
+                ExchangeInclusive::of(ticker.exchange)
+            }
+        }
+    }
+}
+
+/// Requests of a venue running and waiting, by priority
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueDepth {
+    pub running: usize,
+    pub visible: usize,
+    pub background: usize,
+    pub prefetch: usize,
+}
+
+impl QueueDepth {
+    pub fn queued(&self) -> usize {
+        self.visible + self.background + self.prefetch
+    }
+}
+
+pub type Outcome<T> = Result<T, Arc<AdapterError>>;
+
+struct Waiter {
+    /// Raised when a more urgent caller joins the request
+    priority: Arc<AtomicU8>,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct Queue {
+    running: usize,
+    waiting: Vec<Waiter>,
+}
+
+struct InFlight {
+    id: u64,
+    priority: Arc<AtomicU8>,
+    /// `WeakShared` of the request's future, so it's dropped once nobody awaits it
+    fetch: Box<dyn Any + Send>,
+}
+
+static QUEUES: LazyLock<Mutex<FxHashMap<ExchangeInclusive, Queue>>> =
+    LazyLock::new(|| Mutex::new(FxHashMap::default()));
+static IN_FLIGHT: LazyLock<Mutex<FxHashMap<RequestKey, InFlight>>> =
+    LazyLock::new(|| Mutex::new(FxHashMap::default()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Runs `fetch` once a slot of its venue frees up, or joins the identical request
+/// already in flight and drops `fetch`
+pub async fn run<T, F>(key: RequestKey, priority: Priority, fetch: F) -> Outcome<T>
+where
+    T: Clone + Send + Sync + 'static,
+    F: Future<Output = Result<T, AdapterError>> + Send + 'static,
+{
+    let shared = {
+        let Ok(mut in_flight) = IN_FLIGHT.lock() else {
+            return fetch.await.map_err(Arc::new);
+        };
+
+        let joined = in_flight.get(&key).and_then(|entry| {
+            let shared = entry
+                .fetch
+                .downcast_ref::<WeakShared<BoxFuture<'static, Outcome<T>>>>()?
+                .upgrade()?;
+            entry.priority.fetch_max(priority as u8, Ordering::Relaxed);
+            Some(shared)
+        });
+
+        match joined {
+            Some(shared) => shared,
+            None => {
+                let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+                let priority = Arc::new(AtomicU8::new(priority as u8));
+
+                let entry = EntryGuard {
+                    key: key.clone(),
+                    id,
+                };
+                let venue = key.venue();
+                let slot_priority = Arc::clone(&priority);
+
+                let shared = async move {
+                    let _entry = entry;
+                    let _slot = acquire(venue, slot_priority).await;
+                    fetch.await.map_err(Arc::new)
+                }
+                .boxed()
+                .shared();
+
+                if let Some(weak) = shared.downgrade() {
+                    in_flight.insert(
+                        key,
+                        InFlight {
+                            id,
+                            priority,
+                            fetch: Box::new(weak),
+                        },
+                    );
+                }
+                shared
+            }
+        }
+    };
+
+    shared.await
+}
+
+/// `kline_cache::fetch_klines` through the queue
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+    cache_dir: PathBuf,
+    priority: Priority,
+) -> Outcome<Vec<Kline>> {
+    let key = RequestKey::Klines {
+        ticker_info,
+        timeframe,
+        range,
+    };
+    run(
+        key,
+        priority,
+        kline_cache::fetch_klines(ticker_info, timeframe, range, cache_dir),
+    )
+    .await
+}
+
+/// `adapter::fetch_open_interest` through the queue
+pub async fn fetch_open_interest(
+    ticker: Ticker,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+    priority: Priority,
+) -> Outcome<Vec<OpenInterest>> {
+    let key = RequestKey::OpenInterest {
+        ticker,
+        timeframe,
+        range,
+    };
+    run(
+        key,
+        priority,
+        adapter::fetch_open_interest(ticker, timeframe, range),
+    )
+    .await
+}
+
+/// `composite::fetch_aggregated_oi` through the queue
+pub async fn fetch_aggregated_oi(
+    ticker: Ticker,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+    priority: Priority,
+) -> Outcome<Vec<composite::AggregatedOpenInterest>> {
+    let key = RequestKey::AggregatedOpenInterest {
+        ticker,
+        timeframe,
+        range,
+    };
+    run(
+        key,
+        priority,
+        composite::fetch_aggregated_oi(ticker, timeframe, range),
+    )
+    .await
+}
+
+/// Venues with requests running or waiting, in `ExchangeInclusive::ALL` order
+pub fn queue_depth() -> Vec<(ExchangeInclusive, QueueDepth)> {
+    let Ok(queues) = QUEUES.lock() else {
+        return vec![];
+    };
+
+    ExchangeInclusive::ALL
+        .into_iter()
+        .filter_map(|venue| {
+            let queue = queues.get(&venue)?;
+            let mut depth = QueueDepth {
+                running: queue.running,
+                ..QueueDepth::default()
+            };
+
+            for waiter in queue.waiting.iter().filter(|w| !w.wake.is_closed()) {
+                match Priority::from_u8(waiter.priority.load(Ordering::Relaxed)) {
+                    Priority::Visible => depth.visible += 1,
+                    Priority::Background => depth.background += 1,
+                    Priority::Prefetch => depth.prefetch += 1,
+                }
+            }
+
+            (depth != QueueDepth::default()).then_some((venue, depth))
+        })
+        .collect()
+}
+
+/// Forgets the request once it finished or nobody awaits it anymore
+struct EntryGuard {
+    key: RequestKey,
+    id: u64,
+}
+
+impl Drop for EntryGuard {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = IN_FLIGHT.lock()
+            && in_flight
+                .get(&self.key)
+                .is_some_and(|entry| entry.id == self.id)
+        {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+/// A running request of the venue, the slot passes on to the next waiter when dropped
+struct Slot {
+    venue: ExchangeInclusive,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        release(self.venue);
+    }
+}
+
+/// Waiting for a slot, a slot handed over after the wait was given up is passed on
+struct Pending {
+    venue: ExchangeInclusive,
+    wake: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if let Some(mut wake) = self.wake.take()
+            && wake.try_recv().is_ok()
+        {
+            release(self.venue);
+        }
+    }
+}
+
+async fn acquire(venue: ExchangeInclusive, priority: Arc<AtomicU8>) -> Slot {
+    let wake = {
+        let Ok(mut queues) = QUEUES.lock() else {
+            return Slot { venue };
+        };
+        let queue = queues.entry(venue).or_default();
+
+        if queue.running < limiter::max_concurrent() && queue.waiting.is_empty() {
+            queue.running += 1;
+            None
+        } else {
+            let (tx, rx) = oneshot::channel();
+            queue.waiting.push(Waiter {
+                priority,
+                seq: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                wake: tx,
+            });
+            Some(rx)
+        }
+    };
+
+    if let Some(wake) = wake {
+        let mut pending = Pending {
+            venue,
+            wake: Some(wake),
+        };
+        if let Some(wake) = pending.wake.as_mut() {
+            let _ = wake.await;
+        }
+        pending.wake = None;
+    }
+
+    Slot { venue }
+}
+
+/// Hands the slot to the most urgent waiter still around, or frees it
+fn release(venue: ExchangeInclusive) {
+    let Ok(mut queues) = QUEUES.lock() else {
+        return;
+    };
+    let Some(queue) = queues.get_mut(&venue) else {
+        return;
+    };
+
+    loop {
+        let next = queue
+            .waiting
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, waiter)| {
+                (
+                    waiter.priority.load(Ordering::Relaxed),
+                    std::cmp::Reverse(waiter.seq),
+                )
+            })
+            .map(|(idx, _)| idx);
+
+        let Some(idx) = next else {
+            queue.running = queue.running.saturating_sub(1);
+            return;
+        };
+
+        if queue.waiting.swap_remove(idx).wake.send(()).is_ok() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapter::Exchange;
+    use std::sync::atomic::AtomicUsize;
+
+    #[tokio::test]
+    async fn identical_fetches_share_one_request() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let key = RequestKey::OpenInterest {
+            ticker: Ticker::new("DEMOBTCUSD", Exchange::Simulated),
+            timeframe: Timeframe::M5,
+            range: Some((0, 1)),
+        };
+
+        let fetch = |calls: Arc<AtomicUsize>| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok::<_, AdapterError>(7u32)
+        };
+
+        let (first, second) = tokio::join!(
+            run(key.clone(), Priority::Visible, fetch(Arc::clone(&calls))),
+            run(key, Priority::Prefetch, fetch(Arc::clone(&calls))),
+        );
+
+        assert_eq!(first.unwrap(), 7);
+        assert_eq!(second.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(queue_depth().is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+//! Runs each adapter's feed over recorded payloads in `tests/fixtures` and checks the events
+//! it emits, so an adapter can be validated without reaching its venue.
+//!
+//! A fixture is read line by line:
+//! - `@ {..}` sets up the feed under test, always the first line
+//! - `= {..}` feeds a REST depth snapshot, as fetched on connecting or resyncing
+//! - `> ..` feeds a websocket text frame, verbatim
+//! - `< ..` is the next step the last frame has to produce: an event, `"resync"` or
+//!   `{"reconnect": reason}`. A frame without any has to produce none
+//! - blank lines and `#` comments are skipped
+
+use lux_chart_exchange::adapter::{
+    Event, Exchange, ExchangeInclusive, StreamKind, binance, bybit, deribit, forex, hyperliquid,
+    okex, simulated,
+};
+use lux_chart_exchange::depth::{DeOrder, DepthPayload, Levels};
+use lux_chart_exchange::feed::{Feed, Step};
+use lux_chart_exchange::util::Price;
+use lux_chart_exchange::{PushFrequency, TickMultiplier, Ticker, TickerInfo, Timeframe};
+
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+#[derive(Deserialize)]
+#[serde(tag = "feed", rename_all = "snake_case")]
+enum Setup {
+    Market {
+        exchange: Exchange,
+        symbol: String,
+        min_ticksize: f32,
+        #[serde(default)]
+        contract_size: Option<f32>,
+        #[serde(default)]
+        tick_multiplier: Option<u16>,
+    },
+    Kline {
+        exchange: Exchange,
+        min_ticksize: f32,
+        #[serde(default)]
+        contract_size: Option<f32>,
+        streams: Vec<(String, Timeframe)>,
+    },
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    last_update_id: u64,
+    time: u64,
+    bids: Vec<DeOrder>,
+    asks: Vec<DeOrder>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum Expect {
+    Depth {
+        time: u64,
+        /// `[price, qty]` by ascending price
+        bids: Vec<(f32, f32)>,
+        asks: Vec<(f32, f32)>,
+        #[serde(default)]
+        trades: Vec<ExpectTrade>,
+    },
+    Liquidation {
+        time: u64,
+        is_sell: bool,
+        price: f32,
+        qty: f32,
+    },
+    Kline {
+        symbol: String,
+        timeframe: Timeframe,
+        time: u64,
+        open: f32,
+        high: f32,
+        low: f32,
+        close: f32,
+        volume: (f32, f32),
+    },
+    Resync,
+    Reconnect(String),
+}
+
+#[derive(Deserialize, Debug)]
+struct ExpectTrade {
+    time: u64,
+    is_sell: bool,
+    price: f32,
+    qty: f32,
+}
+
+fn market_feed(ticker_info: TickerInfo, tick_multiplier: Option<u16>) -> Box<dyn Feed> {
+    let push_freq = PushFrequency::ServerDefault;
+
+    match ExchangeInclusive::of(ticker_info.exchange()) {
+        ExchangeInclusive::Binance => Box::new(binance::MarketFeed::new(ticker_info, push_freq)),
+        ExchangeInclusive::Bybit => Box::new(bybit::MarketFeed::new(ticker_info, push_freq)),
+        ExchangeInclusive::Okex => Box::new(okex::MarketFeed::new(ticker_info, push_freq)),
+        ExchangeInclusive::Hyperliquid => Box::new(hyperliquid::MarketFeed::new(
+            ticker_info,
+            tick_multiplier.map(TickMultiplier),
+            push_freq,
+        )),
+        ExchangeInclusive::Forex => Box::new(forex::MarketFeed::new(ticker_info, push_freq)),
+        ExchangeInclusive::Deribit => Box::new(deribit::MarketFeed::new(ticker_info, push_freq)),
+        ExchangeInclusive::Simulated => {
+            Box::new(simulated::MarketFeed::new(ticker_info, push_freq))
+        }
+    }
+}
+
+fn kline_feed(exchange: Exchange, streams: Vec<(TickerInfo, Timeframe)>) -> Box<dyn Feed> {
+    let market = exchange.market_type();
+
+    match ExchangeInclusive::of(exchange) {
+        ExchangeInclusive::Binance => Box::new(binance::KlineFeed::new(streams, market)),
+        ExchangeInclusive::Bybit => Box::new(bybit::KlineFeed::new(streams, market)),
+        ExchangeInclusive::Okex => Box::new(okex::KlineFeed::new(&streams, market)),
+        ExchangeInclusive::Hyperliquid => Box::new(hyperliquid::KlineFeed::new(streams)),
+        ExchangeInclusive::Forex => Box::new(forex::KlineFeed::new(streams, market)),
+        ExchangeInclusive::Deribit => Box::new(deribit::KlineFeed::new(&streams, market)),
+        ExchangeInclusive::Simulated => Box::new(simulated::KlineFeed::new(streams)),
+    }
+}
+
+/// Feed under test, with the tick size and symbols its events are checked against
+struct Harness {
+    feed: Box<dyn Feed>,
+    ticker_info: TickerInfo,
+    exchange: Exchange,
+}
+
+impl Harness {
+    fn new(setup: Setup) -> Self {
+        match setup {
+            Setup::Market {
+                exchange,
+                symbol,
+                min_ticksize,
+                contract_size,
+                tick_multiplier,
+            } => {
+                let ticker_info = TickerInfo::new(
+                    Ticker::new(&symbol, exchange),
+                    min_ticksize,
+                    0.0,
+                    contract_size,
+                );
+                Self {
+                    feed: market_feed(ticker_info, tick_multiplier),
+                    ticker_info,
+                    exchange,
+                }
+            }
+            Setup::Kline {
+                exchange,
+                min_ticksize,
+                contract_size,
+                streams,
+            } => {
+                let streams = streams
+                    .iter()
+                    .map(|(symbol, timeframe)| {
+                        let ticker = Ticker::new(symbol, exchange);
+                        let info = TickerInfo::new(ticker, min_ticksize, 0.0, contract_size);
+                        (info, *timeframe)
+                    })
+                    .collect::<Vec<_>>();
+                Self {
+                    ticker_info: streams[0].0,
+                    feed: kline_feed(exchange, streams),
+                    exchange,
+                }
+            }
+        }
+    }
+
+    fn price(&self, price: f32) -> Price {
+        Price::from_f32(price).round_to_min_tick(self.ticker_info.min_ticksize)
+    }
+
+    fn check(&self, step: Step, expect: &Expect) -> Result<(), String> {
+        match (step, expect) {
+            (
+                Step::Emit(Event::DepthReceived(_, time, depth, trades)),
+                Expect::Depth {
+                    time: e_time,
+                    bids,
+                    asks,
+                    trades: e_trades,
+                },
+            ) => {
+                ensure(time == *e_time, || format!("depth time {time}"))?;
+                self.check_side("bids", &depth.bids, bids)?;
+                self.check_side("asks", &depth.asks, asks)?;
+
+                ensure(trades.len() == e_trades.len(), || {
+                    format!("{} trades: {trades:?}", trades.len())
+                })?;
+                for (trade, e) in trades.iter().zip(e_trades) {
+                    ensure(
+                        trade.time == e.time
+                            && trade.is_sell == e.is_sell
+                            && trade.price == self.price(e.price)
+                            && approx(trade.qty, e.qty),
+                        || format!("trade {trade:?}"),
+                    )?;
+                }
+                Ok(())
+            }
+            (
+                Step::Emit(Event::LiquidationReceived(_, liquidation)),
+                Expect::Liquidation {
+                    time,
+                    is_sell,
+                    price,
+                    qty,
+                },
+            ) => ensure(
+                liquidation.time == *time
+                    && liquidation.is_sell == *is_sell
+                    && liquidation.price == self.price(*price)
+                    && approx(liquidation.qty, *qty),
+                || format!("liquidation {liquidation:?}"),
+            ),
+            (
+                Step::Emit(Event::KlineReceived(stream, kline)),
+                Expect::Kline {
+                    symbol,
+                    timeframe,
+                    time,
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                },
+            ) => {
+                let StreamKind::Kline {
+                    ticker_info,
+                    timeframe: stream_tf,
+                } = stream
+                else {
+                    return Err(format!("kline on {stream:?}"));
+                };
+                ensure(
+                    ticker_info.ticker == Ticker::new(symbol, self.exchange)
+                        && stream_tf == *timeframe,
+                    || format!("kline of {} {stream_tf}", ticker_info.ticker),
+                )?;
+                ensure(
+                    kline.time == *time
+                        && kline.open == self.price(*open)
+                        && kline.high == self.price(*high)
+                        && kline.low == self.price(*low)
+                        && kline.close == self.price(*close)
+                        && approx(kline.volume.0, volume.0)
+                        && approx(kline.volume.1, volume.1),
+                    || format!("kline {kline:?}"),
+                )
+            }
+            (Step::Resync, Expect::Resync) => Ok(()),
+            (Step::Reconnect(reason), Expect::Reconnect(e_reason)) => {
+                ensure(reason == *e_reason, || format!("reconnect: {reason}"))
+            }
+            (step, _) => Err(format!("{step:?}")),
+        }
+    }
+
+    fn check_side(&self, side: &str, levels: &Levels, expect: &[(f32, f32)]) -> Result<(), String> {
+        let got = levels
+            .iter()
+            .map(|(price, qty)| (*price, *qty))
+            .collect::<Vec<_>>();
+
+        let matches = got.len() == expect.len()
+            && got
+                .iter()
+                .zip(expect)
+                .all(|((price, qty), (e_price, e_qty))| {
+                    *price == self.price(*e_price) && approx(*qty, *e_qty)
+                });
+        ensure(matches, || {
+            let got = got
+                .iter()
+                .map(|(price, qty)| (price.to_f32(), *qty))
+                .collect::<Vec<_>>();
+            format!("{side} {got:?}")
+        })
+    }
+}
+
+fn approx(got: f32, expect: f32) -> bool {
+    (got - expect).abs() <= 1e-5 * expect.abs().max(1.0)
+}
+
+fn ensure(ok: bool, got: impl FnOnce() -> String) -> Result<(), String> {
+    if ok { Ok(()) } else { Err(got()) }
+}
+
+fn run(name: &str) {
+    let path = format!(
+        "{}/tests/fixtures/{name}.frames",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+
+    let mut harness: Option<Harness> = None;
+    // steps of the last frame not matched yet, with the frame's line
+    let mut pending: VecDeque<Step> = VecDeque::new();
+    let mut frame_line = 0;
+
+    let leftover = |pending: &VecDeque<Step>, frame_line: usize| {
+        assert!(
+            pending.is_empty(),
+            "{name}:{frame_line}: unexpected {:?}",
+            pending.front()
+        );
+    };
+
+    for (idx, line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (tag, body) = line.split_at(1);
+        let body = body.trim_start();
+
+        if tag == "@" {
+            let setup: Setup = serde_json::from_str(body)
+                .unwrap_or_else(|e| panic!("{name}:{line_no}: bad setup: {e}"));
+            harness = Some(Harness::new(setup));
+            continue;
+        }
+        let harness = harness
+            .as_mut()
+            .unwrap_or_else(|| panic!("{name}:{line_no}: no `@` setup before"));
+
+        match tag {
+            "=" => {
+                leftover(&pending, frame_line);
+
+                let snapshot: Snapshot = serde_json::from_str(body)
+                    .unwrap_or_else(|e| panic!("{name}:{line_no}: bad snapshot: {e}"));
+                harness.feed.snapshot(DepthPayload {
+                    last_update_id: snapshot.last_update_id,
+                    time: snapshot.time,
+                    bids: snapshot.bids,
+                    asks: snapshot.asks,
+                });
+            }
+            ">" => {
+                leftover(&pending, frame_line);
+
+                pending = harness.feed.frame(body.as_bytes()).into();
+                frame_line = line_no;
+            }
+            "<" => {
+                let expect: Expect = serde_json::from_str(body)
+                    .unwrap_or_else(|e| panic!("{name}:{line_no}: bad expectation: {e}"));
+                let Some(step) = pending.pop_front() else {
+                    panic!(
+                        "{name}:{line_no}: frame at {frame_line} produced nothing for {expect:?}"
+                    );
+                };
+
+                if let Err(got) = harness.check(step, &expect) {
+                    panic!("{name}:{line_no}: expected {expect:?}, got {got}");
+                }
+            }
+            _ => panic!("{name}:{line_no}: unknown line `{line}`"),
+        }
+    }
+    leftover(&pending, frame_line);
+}
+
+#[test]
+fn binance_perp_resyncs_and_reconnects() {
+    run("binance_perp");
+}
+
+#[test]
+fn binance_spot_chains_on_first_id() {
+    run("binance_spot");
+}
+
+#[test]
+fn bybit_restarts_from_snapshots() {
+    run("bybit_linear");
+}
+
+#[test]
+fn okex_scales_contracts() {
+    run("okex_swap");
+}
+
+#[test]
+fn hyperliquid_replaces_whole_book() {
+    run("hyperliquid_perp");
+}
+
+#[test]
+fn deribit_resyncs_on_change_gap() {
+    run("deribit_inverse");
+}
+
+#[test]
+fn forex_resyncs_and_reconnects() {
+    run("forex");
+}
+
+#[test]
+fn klines_of_every_venue() {
+    for name in [
+        "binance_kline",
+        "bybit_kline",
+        "okex_kline",
+        "hyperliquid_kline",
+        "deribit_kline",
+        "forex_kline",
+    ] {
+        run(name);
+    }
+}
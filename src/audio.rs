@@ -10,6 +10,9 @@ pub const BUY_SOUND: &str = "hard-typewriter-click.wav";
 pub const HARD_BUY_SOUND: &str = "dry-pop-up.wav";
 pub const SELL_SOUND: &str = "hard-typewriter-hit.wav";
 pub const HARD_SELL_SOUND: &str = "fall-on-foam-splash.wav";
+/// Liquidations reuse the hard buy sample, pitched down so they stand apart from trades
+pub const LIQUIDATION_SOUND: &str = "dry-pop-up.wav";
+const LIQUIDATION_PITCH: (u32, u32) = (2, 3);
 
 const OVERLAP_THRESHOLD: Duration = Duration::from_millis(10);
 
@@ -19,6 +22,7 @@ pub enum SoundType {
     HardBuy = 1,
     Sell = 2,
     HardSell = 3,
+    Liquidation = 4,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -61,6 +65,7 @@ impl std::fmt::Display for SoundType {
                 Self::HardBuy => HARD_BUY_SOUND,
                 Self::Sell => SELL_SOUND,
                 Self::HardSell => HARD_SELL_SOUND,
+                Self::Liquidation => LIQUIDATION_SOUND,
             }
         )
     }
@@ -76,8 +81,8 @@ pub struct SoundCache {
     _stream: OutputStream,
     stream_handle: OutputStreamHandle,
     volume: Option<f32>,
-    sample_buffers: [Option<rodio::buffer::SamplesBuffer<i16>>; 4],
-    last_played: [(Option<Instant>, usize); 4],
+    sample_buffers: [Option<rodio::buffer::SamplesBuffer<i16>>; 5],
+    last_played: [(Option<Instant>, usize); 5],
 }
 
 impl SoundCache {
@@ -88,8 +93,8 @@ impl SoundCache {
             _stream: stream,
             stream_handle,
             volume,
-            sample_buffers: [None, None, None, None],
-            last_played: [(None, 0), (None, 0), (None, 0), (None, 0)],
+            sample_buffers: [None, None, None, None, None],
+            last_played: [(None, 0); 5],
         })
     }
 
@@ -101,6 +106,7 @@ impl SoundCache {
             SoundType::HardBuy,
             SoundType::Sell,
             SoundType::HardSell,
+            SoundType::Liquidation,
         ];
 
         for sound_type in &sound_types {
@@ -109,6 +115,7 @@ impl SoundCache {
                 SoundType::HardBuy => (HARD_BUY_SOUND, HARD_BUY_SOUND_DATA),
                 SoundType::Sell => (SELL_SOUND, SELL_SOUND_DATA),
                 SoundType::HardSell => (HARD_SELL_SOUND, HARD_SELL_SOUND_DATA),
+                SoundType::Liquidation => (LIQUIDATION_SOUND, HARD_BUY_SOUND_DATA),
             };
 
             cache
@@ -136,9 +143,16 @@ impl SoundCache {
         let cursor = std::io::Cursor::new(data.to_vec());
         let decoder = Decoder::new(cursor)?;
 
+        let sample_rate = match sound_type {
+            SoundType::Liquidation => {
+                decoder.sample_rate() * LIQUIDATION_PITCH.0 / LIQUIDATION_PITCH.1
+            }
+            _ => decoder.sample_rate(),
+        };
+
         let sample_buffer = rodio::buffer::SamplesBuffer::new(
             decoder.channels(),
-            decoder.sample_rate(),
+            sample_rate,
             decoder.collect::<Vec<i16>>(),
         );
 
@@ -1,16 +1,24 @@
 pub mod comparison;
+mod drawing;
 pub mod heatmap;
 pub mod indicator;
 pub mod kline;
 mod scale;
+pub mod text_cache;
 
 use crate::style;
 use crate::widget::multi_split::{DRAG_SIZE, MultiSplit};
 use crate::widget::tooltip;
-use data::chart::{Autoscale, Basis, PlotData, ViewConfig, indicator::Indicator};
-use exchange::TickerInfo;
+use data::chart::{
+    Autoscale, Basis, LiquidationMarkers, Navigation, PlotData, ViewConfig, Watermark,
+    drawing::{Anchor, Drawing, DrawingTool},
+    kline::FormingBar,
+    session::SessionSettings,
+};
 use exchange::fetcher::{FetchRange, FetchRequests, FetchSpec, RequestHandler};
 use exchange::util::{Price, PriceStep};
+use exchange::{Liquidation, TickerInfo};
+use indicator::plot::ExtentsIndex;
 use scale::linear::PriceInfoLabel;
 use scale::{AxisLabelsX, AxisLabelsY};
 
@@ -18,11 +26,22 @@ use iced::theme::palette::Extended;
 use iced::widget::canvas::{self, Cache, Canvas, Event, Frame, LineDash, Path, Stroke};
 use iced::{
     Alignment, Element, Length, Point, Rectangle, Size, Theme, Vector, keyboard, mouse, padding,
-    widget::{button, center, column, container, mouse_area, row, rule, text},
+    widget::{button, center, column, container, mouse_area, row, rule, stack, text},
 };
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 const ZOOM_SENSITIVITY: f32 = 30.0;
+/// Chart-space units per second below which a release or a coast comes to rest
+const MIN_COAST_SPEED: f32 = 20.0;
+/// A release only flings if the cursor was still moving this recently
+const FLING_WINDOW: Duration = Duration::from_millis(60);
 const TEXT_SIZE: f32 = 12.0;
+/// Pixels off a simulated order line that still grab it
+const ORDER_GRAB_DISTANCE: f32 = 4.0;
+
+const MAX_LIQUIDATIONS: usize = 500;
+const MAX_LIQUIDATION_RADIUS: f32 = 16.0;
 
 #[derive(Default, Debug, Clone, Copy)]
 pub enum Interaction {
@@ -34,10 +53,47 @@ pub enum Interaction {
     Panning {
         translation: Vector,
         start: Point,
+        /// Chart-space speed of the drag, carried on as momentum on a kinetic release
+        velocity: Vector,
+        last_move: Option<(Point, Instant)>,
+    },
+    /// Still moving after a kinetic pan was released
+    Coasting {
+        velocity: Vector,
+        last_frame: Instant,
     },
     Ruler {
         start: Option<Point>,
     },
+    /// Dragging out a drawing from its first anchor
+    Drawing {
+        tool: DrawingTool,
+        start: Anchor,
+    },
+    MovingAnchor {
+        id: u32,
+        idx: usize,
+    },
+    /// Lens key held, magnifying the cells under the cursor
+    Lens,
+    /// Dragging a simulated limit order to a new price
+    MovingOrder {
+        id: u32,
+        price: Price,
+    },
+    /// Pressed on the price axis, places a limit there unless dragged into a zoom
+    PlacingOrder {
+        start: Point,
+    },
+}
+
+/// Part of a simulated order drawn on the chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderHandle {
+    /// The order line at its limit, dragged to reprice it
+    Line(u32, Price),
+    /// Cancel box at the end of the order label
+    Cancel(u32),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -46,21 +102,75 @@ pub enum AxisScaleClicked {
     Y,
 }
 
+/// Actions of the price axis context menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceScaleAction {
+    ResetAutoscale,
+    ToggleLock,
+    ToggleInvert,
+    ToggleLog,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Message {
     Translated(Vector),
     Scaled(f32, Vector),
     AutoscaleToggled,
     CrosshairMoved,
+    /// Bar under the cursor as its open time or its index from the latest bar,
+    /// `None` while the cursor is off the chart
+    BarHovered(Option<u64>),
+    /// Right click at a position on the chart, with the price level under it
+    PriceMenu(Point, Price),
+    /// Right click on the price axis, at a height of the axis
+    PriceScaleMenu(f32),
+    PriceScale(PriceScaleAction),
+    DrawingPlaced(DrawingTool, Anchor, Anchor),
+    /// Anchor `idx` of a drawing was dragged to a new point
+    AnchorDragged(u32, usize, Anchor),
+    DrawingRemoved(u32),
+    /// Click on the price axis while chart trading, at the price under it
+    OrderPlaced(Price),
+    /// Simulated order `id` was dragged to a new limit
+    OrderMoved(u32, Price),
+    OrderCancelled(u32),
     YScaling(f32, f32, bool),
     XScaling(f32, f32, bool),
     BoundsChanged(Rectangle),
     SplitDragged(usize, f32),
     DoubleClick(AxisScaleClicked),
+    NavigationChanged(Navigation),
+    WatermarkChanged(Watermark),
+}
+
+impl Message {
+    /// Whether the message pans or zooms the time axis
+    pub fn moves_time_axis(&self) -> bool {
+        matches!(
+            self,
+            Message::Translated(_)
+                | Message::Scaled(..)
+                | Message::XScaling(..)
+                | Message::DoubleClick(AxisScaleClicked::X)
+        )
+    }
+
+    /// Simulated order the message asks for when it comes from chart trading
+    pub fn paper_command(&self) -> Option<data::paper::Command> {
+        use data::paper::Command;
+
+        match *self {
+            Message::OrderPlaced(price) => Some(Command::PlaceAt { limit: price }),
+            Message::OrderMoved(id, price) => Some(Command::Move { id, limit: price }),
+            Message::OrderCancelled(id) => Some(Command::Cancel { id }),
+            _ => None,
+        }
+    }
 }
 
 pub trait Chart: PlotConstants + canvas::Program<Message> {
-    type IndicatorKind: Indicator;
+    /// Entries of the pane's indicator list, drawn below the chart in order
+    type IndicatorKind;
 
     fn state(&self) -> &ViewState;
 
@@ -72,6 +182,11 @@ pub trait Chart: PlotConstants + canvas::Program<Message> {
 
     fn view_indicators(&'_ self, enabled: &[Self::IndicatorKind]) -> Vec<Element<'_, Message>>;
 
+    /// Layer drawn below the chart's canvas, for content drawn outside of it
+    fn underlay(&self) -> Option<Element<'_, Message>> {
+        None
+    }
+
     fn visible_timerange(&self) -> Option<(u64, u64)>;
 
     fn interval_keys(&self) -> Option<Vec<u64>>;
@@ -81,6 +196,21 @@ pub trait Chart: PlotConstants + canvas::Program<Message> {
     fn supports_fit_autoscaling(&self) -> bool;
 
     fn is_empty(&self) -> bool;
+
+    /// Whether holding the lens key magnifies the cells under the cursor
+    fn has_zoom_lens(&self) -> bool {
+        false
+    }
+
+    /// Working simulated limits as id, side and price, `None` unless the chart trades
+    fn paper_orders(&self) -> Option<Vec<(u32, data::paper::Side, f32)>> {
+        None
+    }
+
+    /// Simulated order drawn under a position of the chart bounds
+    fn order_handle_at(&self, _position: Point, _bounds: Size) -> Option<OrderHandle> {
+        None
+    }
 }
 
 fn canvas_interaction<T: Chart>(
@@ -98,9 +228,42 @@ fn canvas_interaction<T: Chart>(
     let cursor_position = cursor.position_in(shrunken_bounds);
 
     if let Event::Mouse(mouse::Event::ButtonReleased(_)) = event {
-        match interaction {
-            Interaction::Panning { .. } | Interaction::Zoomin { .. } => {
+        match *interaction {
+            Interaction::Panning {
+                velocity,
+                last_move,
+                ..
+            } => {
+                let is_flung = last_move.is_some_and(|(_, at)| at.elapsed() < FLING_WINDOW)
+                    && speed(velocity) > MIN_COAST_SPEED;
+
+                if chart.state().layout.navigation.kinetic && is_flung {
+                    *interaction = Interaction::Coasting {
+                        velocity,
+                        last_frame: Instant::now(),
+                    };
+                    return Some(canvas::Action::request_redraw());
+                }
+                *interaction = Interaction::None;
+            }
+            Interaction::Zoomin { .. } | Interaction::MovingAnchor { .. } => {
+                *interaction = Interaction::None;
+            }
+            Interaction::MovingOrder { id, price } => {
                 *interaction = Interaction::None;
+                return Some(canvas::Action::publish(Message::OrderMoved(id, price)));
+            }
+            Interaction::Drawing { tool, start } => {
+                *interaction = Interaction::None;
+
+                let end = cursor
+                    .position_in(bounds)
+                    .map(|position| chart.state().anchor_at(position, bounds.size()))
+                    .filter(|end| *end != start)?;
+
+                return Some(canvas::Action::publish(Message::DrawingPlaced(
+                    tool, start, end,
+                )));
             }
             _ => {}
         }
@@ -120,14 +283,73 @@ fn canvas_interaction<T: Chart>(
                 mouse::Event::ButtonPressed(button) => {
                     let cursor_in_bounds = cursor_position?;
 
+                    if let mouse::Button::Right = button
+                        && let Some(position) = cursor.position_in(bounds)
+                    {
+                        let msg = match state.drawing_handle_at(position, bounds.size()) {
+                            Some((id, _)) => Message::DrawingRemoved(id),
+                            None => Message::PriceMenu(
+                                position,
+                                state.price_at(position.y, bounds.size()),
+                            ),
+                        };
+                        return Some(canvas::Action::publish(msg).and_capture());
+                    }
+
+                    if let mouse::Button::Left = button
+                        && matches!(
+                            interaction,
+                            Interaction::None | Interaction::Coasting { .. }
+                        )
+                        && let Some(position) = cursor.position_in(bounds)
+                    {
+                        if let Some(tool) = state.drawing_tool {
+                            let anchor = state.anchor_at(position, bounds.size());
+
+                            if tool.is_single_anchor() {
+                                *interaction = Interaction::None;
+                                let msg = Message::DrawingPlaced(tool, anchor, anchor);
+                                return Some(canvas::Action::publish(msg).and_capture());
+                            }
+
+                            *interaction = Interaction::Drawing {
+                                tool,
+                                start: anchor,
+                            };
+                            return Some(canvas::Action::request_redraw().and_capture());
+                        }
+
+                        match chart.order_handle_at(position, bounds.size()) {
+                            Some(OrderHandle::Cancel(id)) => {
+                                *interaction = Interaction::None;
+                                let msg = Message::OrderCancelled(id);
+                                return Some(canvas::Action::publish(msg).and_capture());
+                            }
+                            Some(OrderHandle::Line(id, price)) => {
+                                *interaction = Interaction::MovingOrder { id, price };
+                                return Some(canvas::Action::request_redraw().and_capture());
+                            }
+                            None => {}
+                        }
+
+                        if let Some((id, idx)) = state.drawing_handle_at(position, bounds.size()) {
+                            *interaction = Interaction::MovingAnchor { id, idx };
+                            return Some(canvas::Action::request_redraw().and_capture());
+                        }
+                    }
+
                     if let mouse::Button::Left = button {
                         match interaction {
                             Interaction::None
                             | Interaction::Panning { .. }
-                            | Interaction::Zoomin { .. } => {
+                            | Interaction::Coasting { .. }
+                            | Interaction::Zoomin { .. }
+                            | Interaction::Lens => {
                                 *interaction = Interaction::Panning {
                                     translation: state.translation,
                                     start: cursor_in_bounds,
+                                    velocity: Vector::default(),
+                                    last_move: None,
                                 };
                             }
                             Interaction::Ruler { start } if start.is_none() => {
@@ -135,7 +357,11 @@ fn canvas_interaction<T: Chart>(
                                     start: Some(cursor_in_bounds),
                                 };
                             }
-                            Interaction::Ruler { .. } => {
+                            Interaction::Ruler { .. }
+                            | Interaction::Drawing { .. }
+                            | Interaction::MovingAnchor { .. }
+                            | Interaction::MovingOrder { .. }
+                            | Interaction::PlacingOrder { .. } => {
                                 *interaction = Interaction::None;
                             }
                         }
@@ -143,21 +369,76 @@ fn canvas_interaction<T: Chart>(
                     Some(canvas::Action::request_redraw().and_capture())
                 }
                 mouse::Event::CursorMoved { .. } => match *interaction {
-                    Interaction::Panning { translation, start } => {
+                    Interaction::Panning {
+                        translation,
+                        start,
+                        velocity,
+                        last_move,
+                    } => {
                         let cursor_in_bounds = cursor_position?;
-                        let msg = Message::Translated(
+                        let now = Instant::now();
+
+                        let velocity = match last_move {
+                            Some((last_position, at)) => {
+                                let dt = now.duration_since(at).as_secs_f32();
+                                if dt > 0.0 {
+                                    let current = (cursor_in_bounds - last_position)
+                                        * (1.0 / (state.scaling * dt));
+                                    velocity * 0.2 + current * 0.8
+                                } else {
+                                    velocity
+                                }
+                            }
+                            None => Vector::default(),
+                        };
+
+                        *interaction = Interaction::Panning {
+                            translation,
+                            start,
+                            velocity,
+                            last_move: Some((cursor_in_bounds, now)),
+                        };
+
+                        let msg = Message::Translated(state.snap_translation(
                             translation + (cursor_in_bounds - start) * (1.0 / state.scaling),
-                        );
+                        ));
                         Some(canvas::Action::publish(msg).and_capture())
                     }
-                    Interaction::None | Interaction::Ruler { .. } => {
-                        Some(canvas::Action::publish(Message::CrosshairMoved))
+                    Interaction::MovingAnchor { id, idx } => {
+                        let position = cursor.position_in(bounds)?;
+                        let anchor = state.anchor_at(position, bounds.size());
+                        Some(
+                            canvas::Action::publish(Message::AnchorDragged(id, idx, anchor))
+                                .and_capture(),
+                        )
+                    }
+                    Interaction::MovingOrder { id, .. } => {
+                        let position = cursor.position_in(bounds)?;
+                        *interaction = Interaction::MovingOrder {
+                            id,
+                            price: state.price_at(position.y, bounds.size()),
+                        };
+                        Some(canvas::Action::publish(Message::CrosshairMoved).and_capture())
+                    }
+                    Interaction::None
+                    | Interaction::Ruler { .. }
+                    | Interaction::Coasting { .. }
+                    | Interaction::Drawing { .. }
+                    | Interaction::Lens => {
+                        let hovered = cursor
+                            .position_in(bounds)
+                            .map(|position| state.bar_at(position.x, bounds.size()));
+                        Some(canvas::Action::publish(Message::BarHovered(hovered)))
                     }
                     _ => None,
                 },
                 mouse::Event::WheelScrolled { delta } => {
                     cursor_position?;
 
+                    if let Interaction::Coasting { .. } = interaction {
+                        *interaction = Interaction::None;
+                    }
+
                     let default_cell_width = T::default_cell_width(chart);
                     let min_cell_width = T::min_cell_width(chart);
                     let max_cell_width = T::max_cell_width(chart);
@@ -252,6 +533,51 @@ fn canvas_interaction<T: Chart>(
                 _ => None,
             }
         }
+        Event::Window(iced::window::Event::RedrawRequested(now)) => {
+            let Interaction::Coasting {
+                velocity,
+                last_frame,
+            } = *interaction
+            else {
+                return None;
+            };
+            let state = chart.state();
+
+            let dt = now
+                .saturating_duration_since(last_frame)
+                .as_secs_f32()
+                .min(0.1);
+            let decay = (1.0 - state.layout.navigation.friction).powf(dt * 60.0);
+            let velocity = velocity * decay;
+
+            if speed(velocity) < MIN_COAST_SPEED {
+                *interaction = Interaction::None;
+
+                let resting = state.snap_translation(state.translation);
+                return (resting != state.translation)
+                    .then(|| canvas::Action::publish(Message::Translated(resting)));
+            }
+
+            *interaction = Interaction::Coasting {
+                velocity,
+                last_frame: *now,
+            };
+            Some(canvas::Action::publish(Message::Translated(
+                state.translation + velocity * dt,
+            )))
+        }
+        Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => match interaction {
+            Interaction::None if modifiers.alt() && chart.has_zoom_lens() => {
+                cursor_position?;
+                *interaction = Interaction::Lens;
+                Some(canvas::Action::publish(Message::CrosshairMoved))
+            }
+            Interaction::Lens if !modifiers.alt() => {
+                *interaction = Interaction::None;
+                Some(canvas::Action::publish(Message::CrosshairMoved))
+            }
+            _ => None,
+        },
         Event::Keyboard(keyboard_event) => {
             cursor_position?;
             match keyboard_event {
@@ -273,6 +599,21 @@ fn canvas_interaction<T: Chart>(
     }
 }
 
+fn speed(velocity: Vector) -> f32 {
+    velocity.x.hypot(velocity.y)
+}
+
+/// Theme palette with the pane's color overrides, the theme's own when it has none
+pub fn overridden_palette<'a>(
+    theme: &'a Theme,
+    colors: &data::chart::colors::ChartColors,
+) -> std::borrow::Cow<'a, Extended> {
+    match colors.palette(theme.palette()) {
+        Some(palette) => std::borrow::Cow::Owned(Extended::generate(palette)),
+        None => std::borrow::Cow::Borrowed(theme.extended_palette()),
+    }
+}
+
 pub enum Action {
     ErrorOccurred(data::InternalError),
     RequestFetch(FetchRequests),
@@ -292,14 +633,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                     state.cell_width = default_chart_width;
                     state.translation = autoscaled_coords;
                 }
-                AxisScaleClicked::Y => {
-                    if supports_fit_autoscaling {
-                        state.layout.autoscale = Some(Autoscale::FitToVisible);
-                        state.scaling = 1.0;
-                    } else {
-                        state.layout.autoscale = Some(Autoscale::CenterLatest);
-                    }
-                }
+                AxisScaleClicked::Y => state.reset_y_autoscale(supports_fit_autoscaling),
             }
         }
         Message::Translated(translation) => {
@@ -312,6 +646,13 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                 state.layout.autoscale = None;
             }
         }
+        Message::NavigationChanged(navigation) => {
+            chart.mut_state().layout.navigation = *navigation;
+        }
+        Message::WatermarkChanged(watermark) => {
+            chart.mut_state().layout.watermark = *watermark;
+            chart.invalidate_all();
+        }
         Message::Scaled(scaling, translation) => {
             let state = chart.mut_state();
             state.scaling = *scaling;
@@ -341,6 +682,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
 
             if state.layout.autoscale.is_some() {
                 state.scaling = 1.0;
+                state.layout.price_scale.locked = false;
             }
         }
         Message::XScaling(delta, cursor_to_center_x, is_wheel_scroll) => {
@@ -409,13 +751,12 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
 
                         state.interval_to_x(cursor_time)
                     }
-                    Basis::Tick(_) => {
+                    Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                         let tick_index = cursor_chart_x / state.cell_width;
                         state.cell_width = new_width;
 
                         tick_index * state.cell_width
                     }
-                    Basis::Volume(_) | Basis::Range(_) => unimplemented!(),
                 };
 
                 if *is_wheel_scroll || !is_interval_x_visible {
@@ -433,6 +774,10 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
 
             let state = chart.mut_state();
 
+            if state.layout.price_scale.locked {
+                return;
+            }
+
             if state.layout.autoscale == Some(Autoscale::FitToVisible) {
                 state.layout.autoscale = None;
             }
@@ -487,7 +832,45 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                 *split = (size * 100.0).round() / 100.0;
             }
         }
-        Message::CrosshairMoved => return chart.invalidate_crosshair(),
+        Message::PriceScale(action) => {
+            let supports_fit_autoscaling = chart.supports_fit_autoscaling();
+            let state = chart.mut_state();
+            let price_scale = &mut state.layout.price_scale;
+
+            match action {
+                PriceScaleAction::ResetAutoscale => {
+                    state.reset_y_autoscale(supports_fit_autoscaling);
+                }
+                PriceScaleAction::ToggleLock => {
+                    price_scale.locked = !price_scale.locked;
+
+                    if price_scale.locked {
+                        state.layout.autoscale = None;
+                    }
+                }
+                PriceScaleAction::ToggleInvert => {
+                    price_scale.inverted = !price_scale.inverted;
+                    // keep the same prices in view, mirrored around the base price
+                    state.translation.y = -state.translation.y;
+                }
+                PriceScaleAction::ToggleLog => {
+                    let center_y = -state.translation.y;
+                    let center_price = state.y_to_price(center_y);
+
+                    state.layout.price_scale.log = !state.layout.price_scale.log;
+                    state.translation.y = -state.price_to_y(center_price);
+                }
+            }
+        }
+        Message::CrosshairMoved | Message::BarHovered(_) => return chart.invalidate_crosshair(),
+        Message::PriceMenu(..)
+        | Message::PriceScaleMenu(_)
+        | Message::DrawingPlaced(..)
+        | Message::AnchorDragged(..)
+        | Message::DrawingRemoved(_)
+        | Message::OrderPlaced(_)
+        | Message::OrderMoved(..)
+        | Message::OrderCancelled(_) => return,
     }
     chart.invalidate_all();
 }
@@ -505,6 +888,7 @@ pub fn view<'a, T: Chart>(
 
     let axis_labels_x = Canvas::new(AxisLabelsX {
         labels_cache: &state.cache.x_labels,
+        crosshair_cache: &state.cache.x_crosshair_label,
         scaling: state.scaling,
         translation_x: state.translation.x,
         max: state.latest_x,
@@ -512,8 +896,13 @@ pub fn view<'a, T: Chart>(
         cell_width: state.cell_width,
         timezone,
         chart_bounds: state.bounds,
+        synced_crosshair_x: state.synced_crosshair_x(state.bounds.size()),
         interval_keys: chart.interval_keys(),
         autoscaling: state.layout.autoscale,
+        sessions: state
+            .sessions
+            .show_shading
+            .then_some(state.sessions.manager),
     })
     .width(Length::Fill)
     .height(Length::Fill);
@@ -553,6 +942,7 @@ pub fn view<'a, T: Chart>(
     let content = {
         let axis_labels_y = Canvas::new(AxisLabelsY {
             labels_cache: &state.cache.y_labels,
+            crosshair_cache: &state.cache.y_crosshair_label,
             translation_y: state.translation.y,
             scaling: state.scaling,
             decimals: state.decimals,
@@ -562,12 +952,22 @@ pub fn view<'a, T: Chart>(
             cell_height: state.cell_height,
             basis: state.basis,
             chart_bounds: state.bounds,
+            drawing_levels: state.fib_level_prices(),
+            price_scale: state.layout.price_scale,
+            exchange: state.ticker_info.exchange(),
+            paper_orders: chart.paper_orders(),
         })
         .width(Length::Fill)
         .height(Length::Fill);
 
+        let plot = Canvas::new(chart).width(Length::Fill).height(Length::Fill);
+        let plot: Element<_> = match chart.underlay() {
+            Some(underlay) => stack![underlay, plot].into(),
+            None => plot.into(),
+        };
+
         let main_chart: Element<_> = row![
-            container(Canvas::new(chart).width(Length::Fill).height(Length::Fill))
+            container(plot)
                 .width(Length::FillPortion(10))
                 .height(Length::FillPortion(120)),
             rule::vertical(1).style(style::split_ruler),
@@ -629,7 +1029,13 @@ pub struct Caches {
     main: Cache,
     x_labels: Cache,
     y_labels: Cache,
+    /// Crosshair labels are kept apart so moving the cursor leaves the
+    /// shaped tick labels of the axes untouched
+    x_crosshair_label: Cache,
+    y_crosshair_label: Cache,
     crosshair: Cache,
+    /// Extents of an indicator's data, kept across redraws until the data itself changes
+    extents: ExtentsIndex,
 }
 
 impl Caches {
@@ -637,13 +1043,15 @@ impl Caches {
         self.main.clear();
         self.x_labels.clear();
         self.y_labels.clear();
+        self.x_crosshair_label.clear();
+        self.y_crosshair_label.clear();
         self.crosshair.clear();
     }
 
     fn clear_crosshair(&self) {
         self.crosshair.clear();
-        self.y_labels.clear();
-        self.x_labels.clear();
+        self.x_crosshair_label.clear();
+        self.y_crosshair_label.clear();
     }
 }
 
@@ -662,6 +1070,17 @@ pub struct ViewState {
     decimals: usize,
     ticker_info: TickerInfo,
     layout: ViewConfig,
+    forming_bar: FormingBar,
+    sessions: SessionSettings,
+    liquidation_markers: LiquidationMarkers,
+    /// Streamed liquidations, oldest first, capped at `MAX_LIQUIDATIONS`
+    liquidations: VecDeque<Liquidation>,
+    /// Tool placed by the next left click, `None` while panning as usual
+    drawing_tool: Option<DrawingTool>,
+    drawings: Vec<Drawing>,
+    fib_levels: Vec<f32>,
+    /// Bar under the cursor of a linked pane, in this chart's x units
+    synced_crosshair: Option<u64>,
 }
 
 impl ViewState {
@@ -689,6 +1108,81 @@ impl ViewState {
             decimals,
             ticker_info,
             layout,
+            forming_bar: FormingBar::default(),
+            sessions: SessionSettings::default(),
+            liquidation_markers: LiquidationMarkers::default(),
+            liquidations: VecDeque::new(),
+            drawing_tool: None,
+            drawings: vec![],
+            fib_levels: vec![],
+            synced_crosshair: None,
+        }
+    }
+
+    fn insert_liquidation(&mut self, liquidation: Liquidation) {
+        if self.liquidations.len() >= MAX_LIQUIDATIONS {
+            self.liquidations.pop_front();
+        }
+        self.liquidations.push_back(liquidation);
+    }
+
+    /// Circles sized by notional at each liquidation passing the size filter,
+    /// red for longs getting liquidated and green for shorts
+    fn draw_liquidations(
+        &self,
+        frame: &mut canvas::Frame,
+        palette: &Extended,
+        earliest: u64,
+        latest: u64,
+        time_to_x: impl Fn(u64) -> f32,
+    ) {
+        let markers = self.liquidation_markers;
+        if !markers.enabled || self.liquidations.is_empty() {
+            return;
+        }
+
+        let market_type = self.ticker_info.market_type();
+        let size_in_quote_ccy = exchange::volume_size_unit() == exchange::SizeUnit::Quote;
+
+        for liquidation in self
+            .liquidations
+            .iter()
+            .filter(|liq| liq.time >= earliest && liq.time <= latest)
+        {
+            let size = market_type.qty_in_quote_value(
+                liquidation.qty,
+                liquidation.price,
+                size_in_quote_ccy,
+            );
+            if size < markers.min_size {
+                continue;
+            }
+
+            let color = if liquidation.is_sell {
+                palette.danger.strong.color
+            } else {
+                palette.success.strong.color
+            };
+
+            let ratio = (size / markers.min_size.max(1.0)).max(1.0);
+            let radius = (4.0 + ratio.log2() * 2.0).min(MAX_LIQUIDATION_RADIUS) / self.scaling;
+
+            let center = Point::new(
+                time_to_x(liquidation.time),
+                self.price_to_y(liquidation.price),
+            );
+
+            frame.fill(&Path::circle(center, radius), color.scale_alpha(0.35));
+            frame.stroke(
+                &Path::circle(center, radius),
+                Stroke::with_color(
+                    Stroke {
+                        width: 1.5 / self.scaling,
+                        ..Default::default()
+                    },
+                    color,
+                ),
+            );
         }
     }
 
@@ -717,7 +1211,7 @@ impl ViewState {
 
     fn interval_range(&self, region: &Rectangle) -> (u64, u64) {
         match self.basis {
-            Basis::Tick(_) => (
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => (
                 self.x_to_interval(region.x + region.width),
                 self.x_to_interval(region.x),
             ),
@@ -729,7 +1223,6 @@ impl ViewState {
                         .saturating_add(interval / 2),
                 )
             }
-            Basis::Volume(_) | Basis::Range(_) => unimplemented!(),
         }
     }
 
@@ -749,8 +1242,9 @@ impl ViewState {
                 let diff = value as f64 - self.latest_x as f64;
                 (diff / interval * cell_width) as f32
             }
-            Basis::Tick(_) => -((value as f32) * self.cell_width),
-            Basis::Volume(_) | Basis::Range(_) => unimplemented!(),
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
+                -((value as f32) * self.cell_width)
+            }
         }
     }
 
@@ -767,27 +1261,197 @@ impl ViewState {
                     self.latest_x.saturating_add(diff)
                 }
             }
-            Basis::Tick(_) => {
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                 let tick = -(x / self.cell_width);
                 tick.round() as u64
             }
-            Basis::Volume(_) | Basis::Range(_) => unimplemented!(),
+        }
+    }
+
+    /// Bar under `x` of a canvas sized `bounds`, in the units of `interval_to_x`
+    fn bar_at(&self, x: f32, bounds: Size) -> u64 {
+        let region = self.visible_region(bounds);
+        let chart_x = region.x + x / bounds.width * region.width;
+
+        match self.basis {
+            Basis::Time(timeframe) => {
+                let interval = timeframe.to_milliseconds();
+                (self.x_to_interval(chart_x) + interval / 2) / interval * interval
+            }
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => self.x_to_interval(chart_x),
         }
     }
 
     fn price_to_y(&self, price: Price) -> f32 {
-        if self.tick_size.units == 0 {
+        let price_scale = self.layout.price_scale;
+
+        if price_scale.log {
+            let ticks = price_scale.ticks_below(
+                self.base_price_y.to_f32_lossy(),
+                price.to_f32_lossy(),
+                self.tick_size.to_f32_lossy(),
+            );
+            return ticks * self.cell_height;
+        }
+
+        let y = if self.tick_size.units == 0 {
             let one = Self::price_unit() as f32;
             let delta_units = (self.base_price_y.units - price.units) as f32;
-            return (delta_units / one) * self.cell_height;
+            (delta_units / one) * self.cell_height
+        } else {
+            let delta_units = self.base_price_y.units - price.units;
+            let ticks = (delta_units as f32) / (self.tick_size.units as f32);
+            ticks * self.cell_height
+        };
+
+        if price_scale.inverted { -y } else { y }
+    }
+
+    /// Chart point under a position of the chart bounds, snapped to the bar and tick grid
+    fn anchor_at(&self, position: Point, bounds: Size) -> Anchor {
+        Anchor {
+            interval: self.bar_at(position.x, bounds),
+            price: self.price_at(position.y, bounds),
+        }
+    }
+
+    /// Position of an anchor within the chart bounds
+    fn anchor_to_screen(&self, anchor: Anchor, bounds: Size) -> Point {
+        let region = self.visible_region(bounds);
+
+        Point::new(
+            (self.interval_to_x(anchor.interval) - region.x) / region.width * bounds.width,
+            (self.price_to_y(anchor.price) - region.y) / region.height * bounds.height,
+        )
+    }
+
+    /// Visible time span of a time based chart
+    fn time_window(&self) -> Option<(u64, u64)> {
+        let Basis::Time(_) = self.basis else {
+            return None;
+        };
+        let region = self.visible_region(self.bounds.size());
+
+        (region.width > 0.0).then(|| {
+            (
+                self.x_to_interval(region.x),
+                self.x_to_interval(region.x + region.width),
+            )
+        })
+    }
+
+    /// Pans and zooms the time axis to show `earliest..latest`, leaving the price axis as is.
+    /// Returns whether the chart is time based and took the window
+    fn show_time_window(
+        &mut self,
+        (earliest, latest): (u64, u64),
+        (min_cell_width, max_cell_width): (f32, f32),
+    ) -> bool {
+        let Basis::Time(timeframe) = self.basis else {
+            return false;
+        };
+        if latest <= earliest || self.bounds.width <= 0.0 {
+            return false;
+        }
+
+        let bars = (latest - earliest) as f32 / timeframe.to_milliseconds() as f32;
+        self.cell_width =
+            (self.bounds.width / self.scaling / bars).clamp(min_cell_width, max_cell_width);
+        self.translation.x = -self.interval_to_x(earliest + (latest - earliest) / 2);
+
+        if self.layout.autoscale == Some(Autoscale::CenterLatest) {
+            self.layout.autoscale = None;
         }
+        true
+    }
+
+    fn set_synced_crosshair(&mut self, interval: Option<u64>) {
+        if self.synced_crosshair != interval {
+            self.synced_crosshair = interval;
+            self.cache.clear_crosshair();
+        }
+    }
+
+    /// Position of the synced crosshair within the chart bounds, `None` while off screen
+    fn synced_crosshair_x(&self, bounds: Size) -> Option<f32> {
+        let interval = self.synced_crosshair?;
+        let region = self.visible_region(bounds);
+        let x = (self.interval_to_x(interval) - region.x) / region.width * bounds.width;
+
+        (0.0..=bounds.width).contains(&x).then_some(x)
+    }
+
+    /// Vertical line at the bar hovered on a linked pane
+    fn draw_synced_crosshair(&self, frame: &mut Frame, theme: &Theme, bounds: Size) {
+        if let Some(x) = self.synced_crosshair_x(bounds) {
+            frame.stroke(
+                &Path::line(Point::new(x, 0.0), Point::new(x, bounds.height)),
+                style::dashed_line(theme),
+            );
+        }
+    }
+
+    /// Price of every level of every fib retracement, for the price axis
+    fn fib_level_prices(&self) -> Vec<f32> {
+        self.drawings
+            .iter()
+            .filter(|drawing| drawing.tool == DrawingTool::FibRetracement)
+            .flat_map(|drawing| {
+                let [start, end] = drawing.anchors.map(|anchor| anchor.price.to_f32());
+                self.fib_levels
+                    .iter()
+                    .map(move |ratio| end + (start - end) * ratio)
+            })
+            .collect()
+    }
+
+    /// Drawing id and anchor index of the handle under a position, latest drawing first
+    fn drawing_handle_at(&self, position: Point, bounds: Size) -> Option<(u32, usize)> {
+        self.drawings.iter().rev().find_map(|drawing| {
+            drawing
+                .handles()
+                .iter()
+                .position(|anchor| {
+                    self.anchor_to_screen(*anchor, bounds).distance(position)
+                        <= drawing::HANDLE_GRAB_RADIUS
+                })
+                .map(|idx| (drawing.id, idx))
+        })
+    }
+
+    /// Price level under a y position of the chart bounds, rounded to the tick size
+    fn price_at(&self, y: f32, bounds: Size) -> Price {
+        let region = self.visible_region(bounds);
+        let chart_y = region.y + y / bounds.height * region.height;
+
+        self.y_to_price(chart_y).round_to_step(self.tick_size)
+    }
 
-        let delta_units = self.base_price_y.units - price.units;
-        let ticks = (delta_units as f32) / (self.tick_size.units as f32);
-        ticks * self.cell_height
+    /// Unlocks the price axis and fits it back to the chart
+    fn reset_y_autoscale(&mut self, supports_fit_autoscaling: bool) {
+        self.layout.price_scale.locked = false;
+
+        if supports_fit_autoscaling {
+            self.layout.autoscale = Some(Autoscale::FitToVisible);
+            self.scaling = 1.0;
+        } else {
+            self.layout.autoscale = Some(Autoscale::CenterLatest);
+        }
     }
 
     fn y_to_price(&self, y: f32) -> Price {
+        let price_scale = self.layout.price_scale;
+
+        if price_scale.log {
+            return Price::from_f32_lossy(price_scale.price_at(
+                self.base_price_y.to_f32_lossy(),
+                y / self.cell_height,
+                self.tick_size.to_f32_lossy(),
+            ));
+        }
+
+        let y = if price_scale.inverted { -y } else { y };
+
         if self.tick_size.units == 0 {
             let one = Self::price_unit() as f32;
             let delta_units = ((y / self.cell_height) * one).round() as i64;
@@ -873,7 +1537,14 @@ impl ViewState {
                     let tick_diff = tick1.abs_diff(tick2);
                     format!("{} ticks", tick_diff)
                 }
-                Basis::Volume(_) | Basis::Range(_) => todo!(),
+                Basis::Volume(volume) => {
+                    let (bar1, _) = self.snap_x_to_index(p1.x, bounds, region);
+                    let (bar2, _) = self.snap_x_to_index(p2.x, bounds, region);
+
+                    let traded = bar1.abs_diff(bar2) as f32 * volume as f32;
+                    format!("≈{} vol", data::util::abbr_large_numbers(traded))
+                }
+                Basis::Range(range) => format!("{range} range"),
             };
 
             let rect_x = snapped_p1_x.min(snapped_p2_x);
@@ -933,12 +1604,17 @@ impl ViewState {
                     let datapoints = (tick_diff / u64::from(aggregation.0)).max(1);
                     format!("{} bars", datapoints)
                 }
-                Basis::Volume(_) | Basis::Range(_) => todo!(),
+                Basis::Volume(_) | Basis::Range(_) => {
+                    let (bar1, _) = self.snap_x_to_index(p1.x, bounds, region);
+                    let (bar2, _) = self.snap_x_to_index(p2.x, bounds, region);
+
+                    format!("{} bars", bar1.abs_diff(bar2).max(1))
+                }
             };
 
             let label_text = format!("{}, {} | {}", datapoints_text, interval_diff, pct_text);
 
-            let text_width = (label_text.len() as f32) * TEXT_SIZE * 0.6;
+            let text_width = text_cache::run_size(&label_text, 11.0, iced::Font::DEFAULT).width;
             let text_height = TEXT_SIZE * 1.2;
             let rect_padding = 4.0;
 
@@ -1021,7 +1697,7 @@ impl ViewState {
                 );
                 (rounded_price, rounded_timestamp)
             }
-            Basis::Tick(aggregation) => {
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                 let (chart_x_min, chart_x_max) = (region.x, region.x + region.width);
                 let crosshair_pos = chart_x_min + (cursor_position.x / bounds.width) * region.width;
 
@@ -1030,7 +1706,7 @@ impl ViewState {
                 let snapped_crosshair = cell_index * self.cell_width;
                 let snap_ratio = (snapped_crosshair - chart_x_min) / (chart_x_max - chart_x_min);
 
-                let rounded_tick = (-cell_index as u64) * (u64::from(aggregation.0));
+                let rounded_tick = (-cell_index as u64) * self.trades_per_bar();
 
                 frame.stroke(
                     &Path::line(
@@ -1041,7 +1717,6 @@ impl ViewState {
                 );
                 (rounded_price, rounded_tick)
             }
-            Basis::Volume(_) | Basis::Range(_) => todo!(),
         }
     }
 
@@ -1077,6 +1752,53 @@ impl ViewState {
         }
     }
 
+    /// Rounds the x offset to whole bars when the layout snaps panning
+    fn snap_translation(&self, translation: Vector) -> Vector {
+        if !self.layout.navigation.snap_to_bars || self.cell_width <= 0.0 {
+            return translation;
+        }
+
+        Vector::new(
+            (translation.x / self.cell_width).round() * self.cell_width,
+            translation.y,
+        )
+    }
+
+    /// Draws the watermark centered on `frame`, before any chart translation is applied
+    fn draw_watermark(&self, frame: &mut canvas::Frame, palette: &Extended) {
+        let watermark = self.layout.watermark;
+        if !watermark.enabled {
+            return;
+        }
+
+        let center = frame.center();
+        let symbol_size = (frame.width() / 8.0).clamp(20.0, 72.0);
+        let color = palette.background.base.text.scale_alpha(watermark.opacity);
+
+        let ticker = self.ticker_info.ticker;
+        let (symbol, _) = ticker.display_symbol_and_type();
+
+        frame.fill_text(canvas::Text {
+            content: symbol,
+            position: center,
+            color,
+            size: iced::Pixels(symbol_size),
+            align_x: Alignment::Center.into(),
+            align_y: Alignment::End.into(),
+            ..Default::default()
+        });
+
+        frame.fill_text(canvas::Text {
+            content: format!("{} · {}", ticker.exchange, self.basis),
+            position: Point::new(center.x, center.y + symbol_size * 0.1),
+            color,
+            size: iced::Pixels(symbol_size * 0.35),
+            align_x: Alignment::Center.into(),
+            align_y: Alignment::Start.into(),
+            ..Default::default()
+        });
+    }
+
     fn layout(&self) -> ViewConfig {
         let layout = &self.layout;
         ViewConfig {
@@ -1086,6 +1808,9 @@ impl ViewState {
             show_volume_histogram: layout.show_volume_histogram,
             show_price_levels: layout.show_price_levels,
             show_market_depth: layout.show_market_depth,
+            navigation: layout.navigation,
+            watermark: layout.watermark,
+            price_scale: layout.price_scale,
         }
     }
 
@@ -1093,7 +1818,7 @@ impl ViewState {
         let precision = self.ticker_info.min_ticksize;
 
         let value = self.base_price_y.to_string(precision);
-        let width = (value.len() as f32 * TEXT_SIZE * 0.8).max(72.0);
+        let width = (text_cache::label_width(&value) + 16.0).max(72.0);
 
         Length::Fixed(width.ceil())
     }
@@ -1119,7 +1844,7 @@ impl ViewState {
 
                 (rounded_timestamp, snap_ratio)
             }
-            Basis::Tick(aggregation) => {
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                 let (chart_x_min, chart_x_max) = (region.x, region.x + region.width);
                 let chart_x = chart_x_min + x_ratio * (chart_x_max - chart_x_min);
 
@@ -1132,11 +1857,32 @@ impl ViewState {
                     0.5
                 };
 
-                let rounded_tick = (-cell_index as u64) * u64::from(aggregation.0);
+                let rounded_tick = (-cell_index as u64) * self.trades_per_bar();
 
                 (rounded_tick, snap_ratio)
             }
-            Basis::Volume(_) | Basis::Range(_) => todo!(),
+        }
+    }
+
+    /// Scale of the x-values returned for trade-driven bars: tick charts count trades,
+    /// volume and range bars are addressed by bar index
+    fn trades_per_bar(&self) -> u64 {
+        match self.basis {
+            Basis::Tick(aggregation) => u64::from(aggregation.0),
+            Basis::Time(_) | Basis::Volume(_) | Basis::Range(_) => 1,
+        }
+    }
+
+    /// Interval of the bar that is still accumulating, if any.
+    ///
+    /// Trade-driven bars always have one at index 0, a time bar only until its timeframe elapses
+    fn forming_interval(&self) -> Option<u64> {
+        match self.basis {
+            Basis::Time(timeframe) => {
+                let now = exchange::clock::now_ms(self.ticker_info.exchange());
+                (now < self.latest_x + timeframe.to_milliseconds()).then_some(self.latest_x)
+            }
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => Some(0),
         }
     }
 }
@@ -1,9 +1,11 @@
+use super::PriceScaleAction;
 use crate::widget::chart::comparison::{DEFAULT_ZOOM_POINTS, LineComparison, LineComparisonEvent};
 use crate::widget::chart::{Series, Zoom, domain};
 
 use data::chart::Basis;
 use data::chart::comparison::Config;
 use exchange::adapter::StreamKind;
+use exchange::composite::{self, StablecoinMarket};
 use exchange::fetcher::{FetchRange, FetchSpec, RequestHandler};
 use exchange::{Kline, SerTicker, TickerInfo, Timeframe};
 
@@ -18,6 +20,8 @@ pub enum Action {
     SeriesNameChanged(TickerInfo, String),
     RemoveSeries(TickerInfo),
     OpenSeriesEditor,
+    /// Right click on the y-axis, at a height of the axis
+    OpenPriceScaleMenu(f32),
 }
 
 pub struct ComparisonChart {
@@ -32,6 +36,12 @@ pub struct ComparisonChart {
     pub config: data::chart::comparison::Config,
     pub series_editor: series_editor::TickerSeriesEditor,
     cache_rev: u64,
+    /// Per-bar volume of each series, used to weight merged stablecoin quotes
+    volumes: FxHashMap<TickerInfo, FxHashMap<u64, f32>>,
+    /// Series as drawn when stablecoin quotes are merged
+    merged_series: Vec<Series>,
+    /// Series as drawn followed by the spreads and ratios, while any is configured
+    with_derived: Vec<Series>,
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +96,9 @@ impl ComparisonChart {
             config: cfg,
             series_editor: series_editor::TickerSeriesEditor::default(),
             cache_rev: 0,
+            volumes: FxHashMap::default(),
+            merged_series: Vec::new(),
+            with_derived: Vec::new(),
         }
     }
 
@@ -111,23 +124,91 @@ impl ComparisonChart {
                     self.pan = DEFAULT_PAN_POINTS;
                     None
                 }
+                LineComparisonEvent::YAxisMenu(y) => Some(Action::OpenPriceScaleMenu(y)),
+                LineComparisonEvent::AnchorChanged(anchor) => {
+                    self.set_rebase_anchor(anchor);
+                    None
+                }
             },
             Message::Editor(msg) => self.series_editor.update(msg),
             Message::OpenEditorFor(ticker_info) => self.open_editor_for_ticker(ticker_info),
         }
     }
 
+    pub fn update_price_scale(&mut self, action: PriceScaleAction) {
+        let price_scale = &mut self.config.price_scale;
+
+        match action {
+            PriceScaleAction::ResetAutoscale => {
+                price_scale.locked = false;
+                self.config.locked_range = None;
+            }
+            PriceScaleAction::ToggleLock => {
+                self.config.locked_range = if self.config.locked_range.is_some() {
+                    None
+                } else {
+                    self.visible_pct_range()
+                };
+                self.config.price_scale.locked = self.config.locked_range.is_some();
+            }
+            PriceScaleAction::ToggleInvert => price_scale.inverted = !price_scale.inverted,
+            PriceScaleAction::ToggleLog => price_scale.log = !price_scale.log,
+        }
+
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+    }
+
+    /// Pins the 0% baseline to `anchor`, the locked percent range no longer applies once it moves
+    pub fn set_rebase_anchor(&mut self, anchor: Option<u64>) {
+        if self.config.rebase_anchor == anchor {
+            return;
+        }
+
+        self.config.rebase_anchor = anchor;
+        self.config.locked_range = None;
+        self.config.price_scale.locked = false;
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+    }
+
+    /// Percent range the y-axis currently fits
+    fn visible_pct_range(&self) -> Option<(f32, f32)> {
+        let points: Vec<&[(u64, f32)]> = self
+            .drawn_series()
+            .iter()
+            .map(|s| s.points.as_slice())
+            .collect();
+        let dt = self.timeframe.to_milliseconds().max(1);
+
+        let (min_x, max_x) = domain::window(&points, self.zoom, self.pan, dt)?;
+        let base_x = self.config.rebase_anchor.unwrap_or(min_x);
+        domain::pct_domain(&points, base_x, min_x, max_x)
+    }
+
+    fn drawn_series(&self) -> &[Series] {
+        if !self.config.derived.is_empty() {
+            &self.with_derived
+        } else if self.config.merge_stablecoin_quotes {
+            &self.merged_series
+        } else {
+            &self.series
+        }
+    }
+
     pub fn view(&self, timezone: data::UserTimezone) -> iced::Element<'_, Message> {
         if self.series.iter().all(|s| s.points.is_empty()) {
             return iced::widget::center(iced::widget::text("Waiting for data...").size(16)).into();
         }
 
-        let chart: iced::Element<_> = LineComparison::<Series>::new(&self.series, self.timeframe)
-            .with_timezone(timezone)
-            .with_zoom(self.zoom)
-            .with_pan(self.pan)
-            .version(self.cache_rev)
-            .into();
+        let chart: iced::Element<_> =
+            LineComparison::<Series>::new(self.drawn_series(), self.timeframe)
+                .with_timezone(timezone)
+                .with_zoom(self.zoom)
+                .with_pan(self.pan)
+                .with_price_scale(self.config.price_scale, self.config.locked_range)
+                .with_anchor(self.config.rebase_anchor)
+                .with_correlation(self.config.show_correlation)
+                .version(self.cache_rev)
+                .into();
 
         iced::widget::container(chart.map(Message::Chart))
             .padding(1)
@@ -141,7 +222,6 @@ impl ComparisonChart {
         klines: &[Kline],
     ) {
         let idx = self.get_or_create_series_idx(&ticker_info);
-        let dst = &mut self.series[idx].points;
 
         let dt = self.timeframe.to_milliseconds().max(1);
         let align = |t: u64| (t / dt) * dt;
@@ -151,6 +231,11 @@ impl ComparisonChart {
             .map(|k| (align(k.time), k.close.to_f32()))
             .collect();
 
+        let volumes = self.volumes.entry(ticker_info).or_default();
+        for kline in klines {
+            volumes.insert(align(kline.time), kline_volume(kline));
+        }
+
         incoming.sort_by_key(|(x, _)| *x);
         incoming.dedup_by_key(|(x, _)| *x);
 
@@ -161,6 +246,8 @@ impl ComparisonChart {
             return;
         }
 
+        let dst = &mut self.series[idx].points;
+
         if dst.is_empty() {
             *dst = incoming;
         } else {
@@ -208,13 +295,19 @@ impl ComparisonChart {
 
     pub fn update_latest_kline(&mut self, ticker_info: &TickerInfo, kline: &Kline) {
         let idx = self.get_or_create_series_idx(ticker_info);
-        let series = &mut self.series[idx];
 
         // Align to timeframe grid
         let dt = self.timeframe.to_milliseconds().max(1);
         let t = (kline.time / dt) * dt;
         let new_point = (t, kline.close.to_f32());
 
+        self.volumes
+            .entry(*ticker_info)
+            .or_default()
+            .insert(t, kline_volume(kline));
+
+        let series = &mut self.series[idx];
+
         if let Some((last_x, last_y)) = series.points.last_mut() {
             if *last_x == new_point.0 {
                 *last_y = new_point.1;
@@ -229,6 +322,12 @@ impl ComparisonChart {
         if series.points.len() > SERIES_MAX_POINTS {
             let drop = series.points.len() - SERIES_MAX_POINTS;
             series.points.drain(0..drop);
+
+            if let Some(&(first_x, _)) = series.points.first()
+                && let Some(volumes) = self.volumes.get_mut(ticker_info)
+            {
+                volumes.retain(|x, _| *x >= first_x);
+            }
         }
     }
 
@@ -242,6 +341,7 @@ impl ComparisonChart {
                 name: None,
                 points: Vec::new(),
                 color: self.color_for_or_default(ticker_info),
+                derived: false,
             });
             self.series_index.insert(*ticker_info, i);
             i
@@ -271,6 +371,14 @@ impl ComparisonChart {
             }
         }
         self.selected_tickers.retain(|t| t != ticker_info);
+        self.volumes.remove(ticker_info);
+
+        let ser = SerTicker::from_parts(ticker_info.ticker);
+        self.config
+            .derived
+            .retain(|derived| derived.a != ser && derived.b != ser);
+        self.rebuild_merged_series();
+        self.rebuild_derived_series();
 
         if self
             .series_editor
@@ -340,6 +448,8 @@ impl ComparisonChart {
         if let Some(t) = now {
             self.last_tick = t;
             self.cache_rev = self.cache_rev.wrapping_add(1);
+            self.rebuild_merged_series();
+            self.rebuild_derived_series();
         }
 
         let reqs = self.collect_fetch_reqs(self.desired_fetch_batches(self.pan));
@@ -364,6 +474,7 @@ impl ComparisonChart {
 
                 self.series.clear();
                 self.series_index.clear();
+                self.volumes.clear();
 
                 for (i, &t) in self.selected_tickers.iter().enumerate() {
                     let color = prev_colors
@@ -430,6 +541,42 @@ impl ComparisonChart {
         }
     }
 
+    /// Series whose color falls below the minimum contrast against `background`,
+    /// paired with the remapped color
+    pub fn low_contrast_series(&self, background: iced::Color) -> Vec<(TickerInfo, iced::Color)> {
+        self.series
+            .iter()
+            .filter_map(|s| {
+                data::config::theme::remap_for_contrast(
+                    s.color,
+                    background,
+                    data::config::theme::SERIES_MIN_CONTRAST,
+                )
+                .map(|color| (s.ticker_info, color))
+            })
+            .collect()
+    }
+
+    pub fn apply_config(&mut self, config: data::chart::comparison::Config) {
+        for s in &mut self.series {
+            let ser = SerTicker::from_parts(s.ticker_info.ticker);
+
+            if let Some((_, color)) = config.colors.iter().find(|(t, _)| *t == ser) {
+                s.color = *color;
+            }
+            s.name = config
+                .names
+                .iter()
+                .find(|(t, _)| *t == ser)
+                .map(|(_, name)| name.clone());
+        }
+
+        self.config = config;
+        self.cache_rev = self.cache_rev.wrapping_add(1);
+        self.rebuild_merged_series();
+        self.rebuild_derived_series();
+    }
+
     pub fn serializable_config(&self) -> data::chart::comparison::Config {
         let mut colors = vec![];
         let mut names = vec![];
@@ -442,7 +589,122 @@ impl ComparisonChart {
                 names.push((ser_ticker, name.clone()));
             }
         }
-        data::chart::comparison::Config { colors, names }
+        data::chart::comparison::Config {
+            colors,
+            names,
+            merge_stablecoin_quotes: self.config.merge_stablecoin_quotes,
+            price_scale: self.config.price_scale,
+            locked_range: self.config.locked_range,
+            rebase_anchor: self.config.rebase_anchor,
+            show_correlation: self.config.show_correlation,
+            derived: self.config.derived.clone(),
+        }
+    }
+
+    /// Recomputes the spreads and ratios from the latest points of their legs
+    fn rebuild_derived_series(&mut self) {
+        self.with_derived.clear();
+
+        if self.config.derived.is_empty() {
+            return;
+        }
+
+        let leg = |ser: &SerTicker| {
+            self.series
+                .iter()
+                .find(|s| SerTicker::from_parts(s.ticker_info.ticker) == *ser)
+        };
+
+        let mut drawn = if self.config.merge_stablecoin_quotes {
+            self.merged_series.clone()
+        } else {
+            self.series.clone()
+        };
+
+        for derived in &self.config.derived {
+            let (Some(a), Some(b)) = (leg(&derived.a), leg(&derived.b)) else {
+                continue;
+            };
+
+            drawn.push(Series {
+                ticker_info: a.ticker_info,
+                name: Some(derived.label()),
+                points: derived.points(&a.points, &b.points),
+                color: derived.color,
+                derived: true,
+            });
+        }
+
+        self.with_derived = drawn;
+    }
+
+    /// Collapses series quoted in different stablecoins of the same base into one,
+    /// priced by the volume-weighted close of every quote trading at that bar
+    fn rebuild_merged_series(&mut self) {
+        self.merged_series.clear();
+
+        if !self.config.merge_stablecoin_quotes {
+            return;
+        }
+
+        let mut groups: Vec<(Option<StablecoinMarket>, Vec<&Series>)> = vec![];
+        for s in &self.series {
+            let market = composite::stablecoin_market(&s.ticker_info.ticker);
+            match groups
+                .iter_mut()
+                .find(|(m, _)| market.is_some() && *m == market)
+            {
+                Some((_, members)) => members.push(s),
+                None => groups.push((market, vec![s])),
+            }
+        }
+
+        for (market, members) in groups {
+            let [lead, rest @ ..] = members.as_slice() else {
+                continue;
+            };
+
+            if rest.is_empty() {
+                self.merged_series.push((*lead).clone());
+                continue;
+            }
+
+            let volume_at = |s: &Series, x: u64| {
+                self.volumes
+                    .get(&s.ticker_info)
+                    .and_then(|v| v.get(&x))
+                    .copied()
+                    .unwrap_or(0.0)
+            };
+
+            let points = lead
+                .points
+                .iter()
+                .filter_map(|&(x, _)| {
+                    let quotes: Vec<(f32, f32)> = members
+                        .iter()
+                        .filter_map(|s| {
+                            s.points
+                                .binary_search_by_key(&x, |(px, _)| *px)
+                                .ok()
+                                .map(|i| (s.points[i].1, volume_at(s, x)))
+                        })
+                        .collect();
+                    composite::volume_weighted_price(&quotes).map(|y| (x, y))
+                })
+                .collect();
+
+            self.merged_series.push(Series {
+                ticker_info: lead.ticker_info,
+                name: lead
+                    .name
+                    .clone()
+                    .or_else(|| market.as_ref().map(StablecoinMarket::label)),
+                points,
+                color: lead.color,
+                derived: false,
+            });
+        }
     }
 
     fn color_for_or_default(&self, ticker_info: &TickerInfo) -> iced::Color {
@@ -555,6 +817,12 @@ impl ComparisonChart {
     }
 }
 
+fn kline_volume(kline: &Kline) -> f32 {
+    let (buy, sell) = kline.volume;
+    // bybit workaround: total volume is carried in `sell`
+    if buy == -1.0 { sell } else { buy + sell }
+}
+
 fn default_color_for(ticker: &TickerInfo) -> iced::Color {
     use std::hash::{DefaultHasher, Hash, Hasher};
 
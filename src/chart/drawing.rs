@@ -0,0 +1,144 @@
+use super::ViewState;
+use crate::style;
+
+use data::chart::drawing::{Anchor, DrawingTool};
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Frame, LineDash, Path, Stroke};
+use iced::{Alignment, Color, Point, Rectangle, Size};
+
+/// Distance in pixels within which a click grabs an anchor
+pub const HANDLE_GRAB_RADIUS: f32 = 6.0;
+const HANDLE_RADIUS: f32 = 3.0;
+
+/// Draws every drawing of the chart in chart coordinates, with a handle on each anchor
+pub fn draw_drawings(frame: &mut Frame, chart: &ViewState, region: Rectangle, palette: &Extended) {
+    if chart.drawings.is_empty() {
+        return;
+    }
+
+    let color = palette.primary.strong.color;
+    let unit = 1.0 / chart.scaling;
+    let to_point = |anchor: Anchor| {
+        Point::new(
+            chart.interval_to_x(anchor.interval),
+            chart.price_to_y(anchor.price),
+        )
+    };
+
+    for drawing in &chart.drawings {
+        let [start, end] = drawing.anchors.map(to_point);
+        draw_shape(
+            frame,
+            drawing.tool,
+            start,
+            end,
+            &chart.fib_levels,
+            (region.x, region.x + region.width),
+            unit,
+            color,
+        );
+
+        for anchor in drawing.handles() {
+            frame.fill(
+                &Path::circle(to_point(*anchor), HANDLE_RADIUS * unit),
+                color,
+            );
+        }
+    }
+}
+
+/// Drawing being dragged out from `start` to the cursor, in screen coordinates
+pub fn draw_preview(
+    frame: &mut Frame,
+    chart: &ViewState,
+    tool: DrawingTool,
+    start: Anchor,
+    cursor: Point,
+    bounds: Size,
+    palette: &Extended,
+) {
+    let start = chart.anchor_to_screen(start, bounds);
+    let color = palette.primary.strong.color.scale_alpha(0.6);
+
+    draw_shape(
+        frame,
+        tool,
+        start,
+        cursor,
+        &chart.fib_levels,
+        (0.0, bounds.width),
+        1.0,
+        color,
+    );
+}
+
+/// `unit` is the size of a screen pixel in the frame's coordinates,
+/// `(left, right)` the horizontal extent lines are stretched to
+fn draw_shape(
+    frame: &mut Frame,
+    tool: DrawingTool,
+    a: Point,
+    b: Point,
+    fib_levels: &[f32],
+    (left, right): (f32, f32),
+    unit: f32,
+    color: Color,
+) {
+    let stroke = Stroke::default().with_color(color).with_width(unit);
+
+    match tool {
+        DrawingTool::TrendLine => frame.stroke(&Path::line(a, b), stroke),
+        DrawingTool::Ray => {
+            let end = if (b.x - a.x).abs() > f32::EPSILON {
+                let edge = if b.x >= a.x { right } else { left };
+                Point::new(edge, a.y + (edge - a.x) * (b.y - a.y) / (b.x - a.x))
+            } else {
+                b
+            };
+            frame.stroke(&Path::line(a, end), stroke);
+        }
+        DrawingTool::HorizontalLine => frame.stroke(
+            &Path::line(Point::new(left, a.y), Point::new(right, a.y)),
+            stroke,
+        ),
+        DrawingTool::Rectangle => {
+            let top_left = Point::new(a.x.min(b.x), a.y.min(b.y));
+            let size = Size::new((a.x - b.x).abs(), (a.y - b.y).abs());
+
+            frame.fill_rectangle(top_left, size, color.scale_alpha(0.1));
+            frame.stroke(&Path::rectangle(top_left, size), stroke);
+        }
+        DrawingTool::FibRetracement => {
+            let start_x = a.x.min(b.x);
+
+            frame.stroke(
+                &Path::line(a, b),
+                Stroke {
+                    line_dash: LineDash {
+                        segments: &[4.0 * unit, 4.0 * unit],
+                        offset: 0,
+                    },
+                    ..stroke
+                },
+            );
+
+            for &ratio in fib_levels {
+                let y = b.y + (a.y - b.y) * ratio;
+
+                frame.stroke(
+                    &Path::line(Point::new(start_x, y), Point::new(right, y)),
+                    stroke,
+                );
+                frame.fill_text(canvas::Text {
+                    content: format!("{ratio}"),
+                    position: Point::new(start_x + 2.0 * unit, y - 2.0 * unit),
+                    size: (10.0 * unit).into(),
+                    color,
+                    font: style::AZERET_MONO,
+                    align_y: Alignment::End.into(),
+                    ..canvas::Text::default()
+                });
+            }
+        }
+    }
+}
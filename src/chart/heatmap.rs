@@ -1,5 +1,12 @@
+mod mesh;
+
 use super::{
-    Chart, Interaction, Message, PlotConstants, TEXT_SIZE, ViewState, scale::linear::PriceInfoLabel,
+    Caches, Chart, Interaction, Message, PlotConstants, TEXT_SIZE, ViewState,
+    indicator::{
+        indicator_row,
+        plot::{PlotTooltip, line::LinePlot},
+    },
+    scale::linear::PriceInfoLabel,
 };
 use crate::{
     modal::pane::settings::study::{self, Study},
@@ -7,9 +14,10 @@ use crate::{
 };
 use data::chart::{
     Basis, ViewConfig,
+    colors::{ChartColors, gradient_at},
     heatmap::{
-        CLEANUP_THRESHOLD, Config, HeatmapDataPoint, HeatmapStudy, HistoricalDepth, ProfileKind,
-        QtyScale,
+        BookTop, CLEANUP_THRESHOLD, Config, DepthHistory, HeatmapDataPoint, HeatmapStudy,
+        HistoricalDepth, ProfileKind, QtyScale,
     },
     indicator::HeatmapIndicator,
 };
@@ -33,6 +41,7 @@ use iced::{
 
 use enum_map::EnumMap;
 use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 const MIN_SCALING: f32 = 0.6;
@@ -65,14 +74,33 @@ impl Chart for HeatmapChart {
 
     fn invalidate_crosshair(&mut self) {
         self.chart.cache.clear_crosshair();
+        self.indicators
+            .values()
+            .flatten()
+            .for_each(IndicatorData::clear_crosshair_caches);
     }
 
     fn invalidate_all(&mut self) {
         self.invalidate(None);
     }
 
-    fn view_indicators(&'_ self, _indicators: &[Self::IndicatorKind]) -> Vec<Element<'_, Message>> {
-        vec![]
+    fn view_indicators(&'_ self, enabled: &[Self::IndicatorKind]) -> Vec<Element<'_, Message>> {
+        let chart_state = self.state();
+        let visible_region = chart_state.visible_region(chart_state.bounds.size());
+        let (earliest, latest) = chart_state.interval_range(&visible_region);
+        if earliest > latest {
+            return vec![];
+        }
+
+        enabled
+            .iter()
+            .filter_map(|indicator| self.indicators[*indicator].as_ref())
+            .filter_map(|data| data.element(chart_state, earliest..=latest))
+            .collect()
+    }
+
+    fn underlay(&self) -> Option<Element<'_, Message>> {
+        Some(mesh::DepthLayer::new(self).into())
     }
 
     fn visible_timerange(&self) -> Option<(u64, u64)> {
@@ -140,10 +168,64 @@ impl PlotConstants for HeatmapChart {
     }
 }
 
-#[derive(Default)]
 enum IndicatorData {
-    #[default]
     Volume,
+    /// Top of the book at each column, as of the last update within it
+    Spread {
+        tops: BTreeMap<u64, BookTop>,
+        cache: Box<Caches>,
+    },
+}
+
+impl IndicatorData {
+    fn new(indicator: HeatmapIndicator) -> Self {
+        match indicator {
+            HeatmapIndicator::Volume => IndicatorData::Volume,
+            HeatmapIndicator::Spread => IndicatorData::Spread {
+                tops: BTreeMap::new(),
+                cache: Box::default(),
+            },
+        }
+    }
+
+    fn clear_all_caches(&self) {
+        if let IndicatorData::Spread { cache, .. } = self {
+            cache.clear_all();
+        }
+    }
+
+    fn clear_crosshair_caches(&self) {
+        if let IndicatorData::Spread { cache, .. } = self {
+            cache.clear_crosshair();
+        }
+    }
+
+    /// Panel below the chart, for the indicators drawn in one
+    fn element<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        visible_range: std::ops::RangeInclusive<u64>,
+    ) -> Option<Element<'a, Message>> {
+        let IndicatorData::Spread { tops, cache } = self else {
+            return None;
+        };
+
+        let decimals = main_chart.decimals;
+        let tooltip = move |top: &BookTop, _next: Option<&BookTop>| {
+            PlotTooltip::new(format!(
+                "Spread: {:.1} ticks, {:.2} bps\nMicroprice: {:.decimals$}",
+                top.spread_ticks, top.spread_bps, top.microprice,
+            ))
+        };
+
+        let plot = LinePlot::new(|top: &BookTop| top.spread_ticks)
+            .stroke_width(1.0)
+            .show_points(false)
+            .padding(0.08)
+            .with_tooltip(tooltip);
+
+        Some(indicator_row(main_chart, cache, plot, tops, visible_range))
+    }
 }
 
 pub struct HeatmapChart {
@@ -151,11 +233,22 @@ pub struct HeatmapChart {
     trades: TimeSeries<HeatmapDataPoint>,
     indicators: EnumMap<HeatmapIndicator, Option<IndicatorData>>,
     pause_buffer: Vec<(u64, Box<[Trade]>, Depth)>,
+    /// Trades whose depth update was conflated away, drawn with the next one
+    held_trades: Vec<Trade>,
     heatmap: HistoricalDepth,
     visual_config: Config,
     study_configurator: study::Configurator<HeatmapStudy>,
     last_tick: Instant,
     pub studies: Vec<HeatmapStudy>,
+    pinned_levels: Vec<Price>,
+    depth_mesh: mesh::DepthMesh,
+    depth_history: DepthHistory,
+    /// Time of the book being inspected, live data is held back meanwhile
+    scrub: Option<u64>,
+    /// Pane colors drawn instead of the theme's, owned by the pane settings
+    colors: ChartColors,
+    /// Shades resting depth is drawn with instead of the bid and ask colors
+    depth_gradient: Option<Vec<Color>>,
 }
 
 impl HeatmapChart {
@@ -172,56 +265,92 @@ impl HeatmapChart {
 
         let mut indicators = EnumMap::default();
         for &indicator in enabled_indicators {
-            indicators[indicator] = Some(match indicator {
-                HeatmapIndicator::Volume => IndicatorData::Volume,
-            });
+            indicators[indicator] = Some(IndicatorData::new(indicator));
         }
 
         let heatmap = HistoricalDepth::new(ticker_info.min_qty.into(), step, basis);
 
-        let view_state = ViewState::new(
+        let visual_config = config.unwrap_or_default();
+
+        let mut view_state = ViewState::new(
             basis,
             step,
             count_decimals(tick_size),
             ticker_info,
             ViewConfig {
-                splits: layout.splits,
+                splits: panel_splits(&layout.splits, enabled_indicators),
                 autoscale: Some(Autoscale::CenterLatest),
                 trading_config: None,
                 show_volume_histogram: true,
                 show_price_levels: true,
                 show_market_depth: false,
+                navigation: layout.navigation,
+                watermark: layout.watermark,
+                price_scale: layout.price_scale,
             },
             DEFAULT_CELL_WIDTH,
             4.0,
         );
+        view_state.sessions = visual_config.sessions;
+        view_state.liquidation_markers = visual_config.liquidations;
 
         HeatmapChart {
             chart: view_state,
             indicators,
             pause_buffer: vec![],
+            held_trades: vec![],
             heatmap,
             trades: TimeSeries::<HeatmapDataPoint>::new(basis, step),
-            visual_config: config.unwrap_or_default(),
+            visual_config,
             study_configurator: study::Configurator::new(),
             studies,
             last_tick: Instant::now(),
+            pinned_levels: vec![],
+            depth_mesh: mesh::DepthMesh::default(),
+            depth_history: DepthHistory::new(visual_config.scrub_memory_mb),
+            scrub: None,
+            colors: ChartColors::default(),
+            depth_gradient: None,
         }
     }
 
+    pub fn set_colors(&mut self, colors: ChartColors) {
+        self.colors = colors;
+        self.depth_gradient = colors.heatmap_gradient();
+        self.invalidate(Some(Instant::now()));
+    }
+
+    pub fn set_pinned_levels(&mut self, levels: Vec<Price>) {
+        self.pinned_levels = levels;
+        self.invalidate(Some(Instant::now()));
+    }
+
     pub fn insert_datapoint(
         &mut self,
         trades_buffer: &[Trade],
         depth_update_t: u64,
         depth: &Depth,
     ) {
+        let held;
+        let trades_buffer = if self.held_trades.is_empty() {
+            trades_buffer
+        } else {
+            held = [
+                std::mem::take(&mut self.held_trades).as_slice(),
+                trades_buffer,
+            ]
+            .concat();
+            held.as_slice()
+        };
+
         let chart = &mut self.chart;
 
         let mid_price = depth.mid_price().unwrap_or(chart.base_price_y);
         chart.last_price = Some(PriceInfoLabel::Neutral(mid_price));
 
         // if current orderbook not visible, pause the data insertion and buffer them instead
-        let is_paused = { chart.translation.x * chart.scaling > chart.bounds.width / 2.0 };
+        let is_paused =
+            self.scrub.is_some() || chart.translation.x * chart.scaling > chart.bounds.width / 2.0;
 
         if is_paused {
             self.pause_buffer.push((
@@ -244,6 +373,15 @@ impl HeatmapChart {
         self.process_datapoint(trades_buffer, depth_update_t, depth);
     }
 
+    /// Columns are cut by depth updates, so trades without one wait for the next
+    pub fn insert_trades(&mut self, trades_buffer: &[Trade]) {
+        self.held_trades.extend_from_slice(trades_buffer);
+    }
+
+    pub fn insert_liquidation(&mut self, liquidation: exchange::Liquidation) {
+        self.chart.insert_liquidation(liquidation);
+    }
+
     fn cleanup_old_data(&mut self) {
         if self.trades.datapoints.len() > CLEANUP_THRESHOLD {
             let keys_to_remove = self
@@ -260,6 +398,13 @@ impl HeatmapChart {
 
             if let Some(oldest_time) = self.trades.datapoints.keys().next().copied() {
                 self.heatmap.cleanup_old_price_levels(oldest_time);
+
+                if let Some(IndicatorData::Spread { tops, cache }) =
+                    &mut self.indicators[HeatmapIndicator::Spread]
+                {
+                    *tops = tops.split_off(&oldest_time);
+                    cache.extents.invalidate();
+                }
             }
         }
     }
@@ -289,8 +434,18 @@ impl HeatmapChart {
             }
         }
 
+        if let Some(IndicatorData::Spread { tops, cache }) =
+            &mut self.indicators[HeatmapIndicator::Spread]
+            && let Some(top) = BookTop::of(depth, chart.ticker_info.min_ticksize.into())
+        {
+            tops.insert(rounded_depth_update, top);
+            cache.extents.invalidate_from(rounded_depth_update);
+        }
+
         self.heatmap
             .insert_latest_depth(depth, rounded_depth_update);
+        self.depth_history
+            .push(rounded_depth_update, depth, chart.tick_size);
 
         {
             let mid_price = depth.mid_price().unwrap_or(chart.base_price_y);
@@ -306,9 +461,85 @@ impl HeatmapChart {
 
     pub fn set_visual_config(&mut self, visual_config: Config) {
         self.visual_config = visual_config;
+        self.chart.sessions = visual_config.sessions;
+        self.chart.liquidation_markers = visual_config.liquidations;
+        self.depth_history.set_budget(visual_config.scrub_memory_mb);
         self.invalidate(Some(Instant::now()));
     }
 
+    /// Shows the book recorded at `time` with the live edge held there, `None` resumes live data
+    pub fn scrub_to(&mut self, time: Option<u64>) {
+        let time = time.and_then(|time| {
+            let (oldest, latest) = self.depth_history.time_range()?;
+            Some(time.clamp(oldest, latest))
+        });
+        self.scrub = time;
+
+        let chart = &mut self.chart;
+        let live_x = 0.5 * (chart.bounds.width / chart.scaling) - (90.0 / chart.scaling);
+        chart.translation.x = match time {
+            Some(time) => live_x - chart.interval_to_x(time),
+            None => live_x,
+        };
+
+        self.invalidate(None);
+    }
+
+    pub fn is_scrubbing(&self) -> bool {
+        self.scrub.is_some()
+    }
+
+    /// Timeline under the chart to scrub back through the recorded books, `None` until there are some
+    pub fn scrubber(&self) -> Option<Element<'_, Option<u64>>> {
+        let (oldest, latest) = self.depth_history.time_range()?;
+        if latest <= oldest {
+            return None;
+        }
+
+        let span_secs = (latest - oldest) as f64 / 1000.0;
+        let behind_ms = self.scrub.map_or(0, |time| latest.saturating_sub(time));
+
+        let toggle = iced::widget::button(
+            iced::widget::text(if self.scrub.is_some() {
+                "Live"
+            } else {
+                "Pause"
+            })
+            .size(11),
+        )
+        .padding([2, 6])
+        .on_press(if self.scrub.is_some() {
+            None
+        } else {
+            Some(latest)
+        });
+
+        let timeline = iced::widget::slider(
+            -span_secs..=0.0,
+            -(behind_ms as f64) / 1000.0,
+            move |secs: f64| Some(latest.saturating_sub((-secs * 1000.0) as u64)),
+        )
+        .step(0.1);
+
+        let label = if self.scrub.is_some() {
+            format!("-{}", exchange::schedule::format_countdown(behind_ms))
+        } else {
+            "Live".to_string()
+        };
+
+        Some(
+            iced::widget::row![
+                toggle,
+                timeline,
+                iced::widget::text(label).size(11).width(56)
+            ]
+            .spacing(8)
+            .padding([2, 8])
+            .align_y(Alignment::Center)
+            .into(),
+        )
+    }
+
     pub fn set_basis(&mut self, basis: Basis) {
         self.chart.basis = basis;
 
@@ -318,6 +549,14 @@ impl HeatmapChart {
             self.chart.tick_size,
             basis,
         );
+        self.depth_history.clear();
+        self.scrub = None;
+        if let Some(IndicatorData::Spread { tops, cache }) =
+            &mut self.indicators[HeatmapIndicator::Spread]
+        {
+            tops.clear();
+            cache.extents.invalidate();
+        }
 
         let chart = &mut self.chart;
         chart.translation = Vector::new(
@@ -364,6 +603,33 @@ impl HeatmapChart {
         }
     }
 
+    /// Time span of `bar`, as the bars are keyed on the x axis
+    pub fn bar_time_range(&self, bar: u64) -> Option<(u64, u64)> {
+        self.basis_interval().map(|interval| (bar, bar + interval))
+    }
+
+    pub fn time_window(&self) -> Option<(u64, u64)> {
+        self.chart.time_window()
+    }
+
+    /// Follows the time window panned or zoomed to on a linked pane
+    pub fn sync_time_window(&mut self, window: (u64, u64)) {
+        let cell_widths = (self.min_cell_width(), self.max_cell_width());
+
+        if self.chart.show_time_window(window, cell_widths) {
+            self.invalidate(None);
+        }
+    }
+
+    /// Shows a crosshair at the bar holding `time`, hovered on a linked pane
+    pub fn sync_crosshair(&mut self, time: Option<u64>) {
+        let interval = time
+            .zip(self.basis_interval())
+            .map(|(time, interval)| time - time % interval);
+
+        self.chart.set_synced_crosshair(interval);
+    }
+
     pub fn chart_layout(&self) -> ViewConfig {
         self.chart.layout()
     }
@@ -380,6 +646,8 @@ impl HeatmapChart {
 
         self.trades.datapoints.clear();
         self.heatmap = HistoricalDepth::new(self.chart.ticker_info.min_qty.into(), step, basis);
+        self.depth_history.clear();
+        self.scrub = None;
     }
 
     pub fn tick_size(&self) -> f32 {
@@ -390,17 +658,21 @@ impl HeatmapChart {
         if self.indicators[indicator].is_some() {
             self.indicators[indicator] = None;
         } else {
-            let data = match indicator {
-                HeatmapIndicator::Volume => IndicatorData::Volume,
-            };
-            self.indicators[indicator] = Some(data);
+            self.indicators[indicator] = Some(IndicatorData::new(indicator));
         }
+
+        let enabled: Vec<HeatmapIndicator> = self
+            .indicators
+            .iter()
+            .filter_map(|(indicator, data)| data.as_ref().map(|_| indicator))
+            .collect();
+        self.chart.layout.splits = panel_splits(&self.chart.layout.splits, &enabled);
     }
 
     pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
         let chart = &mut self.chart;
 
-        if chart.layout.autoscale.is_some() {
+        if chart.layout.autoscale.is_some() && self.scrub.is_none() {
             chart.translation = Vector::new(
                 0.5 * (chart.bounds.width / chart.scaling) - (90.0 / chart.scaling),
                 0.0,
@@ -408,6 +680,11 @@ impl HeatmapChart {
         }
 
         chart.cache.clear_all();
+        self.depth_mesh.clear();
+        self.indicators
+            .values()
+            .flatten()
+            .for_each(IndicatorData::clear_all_caches);
 
         if let Some(t) = now {
             self.last_tick = t;
@@ -416,6 +693,119 @@ impl HeatmapChart {
         None
     }
 
+    /// Depth runs of the visible region as quads, drawn below the canvas by [`mesh::DepthLayer`]
+    fn depth_quads(&self, bounds: Rectangle, palette: &Extended) -> mesh::QuadBatch {
+        let chart = self.state();
+        let market_type = chart.ticker_info.market_type();
+        let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
+
+        let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
+        let mut quads = mesh::QuadBatch::new(
+            Vector::new(bounds.x, bounds.y) + center,
+            chart.translation,
+            chart.scaling,
+        );
+
+        let region = chart.visible_region(bounds.size());
+
+        let (earliest, latest) = chart.interval_range(&region);
+        let (highest, lowest) = chart.price_range(&region);
+
+        if latest < earliest {
+            return quads;
+        }
+
+        let cell_height = chart.cell_height;
+        let normalization = self.visual_config.depth_normalization;
+        let reference = self.heatmap.depth_reference(
+            normalization,
+            earliest,
+            latest,
+            highest,
+            lowest,
+            market_type,
+            self.visual_config.order_size_filter,
+        );
+
+        // per column normalization splits runs at column boundaries, the others draw them whole
+        let mut push_run = |price: Price, start: u64, until: u64, qty: f32, is_bid: bool| {
+            let y_position = chart.price_to_y(price);
+            let until = until.min(latest);
+            let mut segment_start = start.max(earliest);
+
+            while segment_start < until {
+                let segment_until = reference.segment_end(segment_start, until);
+
+                let start_x = chart.interval_to_x(segment_start);
+                let end_x = chart.interval_to_x(segment_until).min(0.0);
+                let width = end_x - start_x;
+
+                if width > 0.001 {
+                    let color_alpha = normalization.alpha(qty, reference.at(segment_start));
+
+                    let color = match &self.depth_gradient {
+                        Some(gradient) => gradient_at(gradient, color_alpha),
+                        None => depth_color(palette, is_bid, color_alpha),
+                    };
+
+                    quads.push(
+                        Point::new(start_x, y_position - (cell_height / 2.0)),
+                        Size::new(width, cell_height),
+                        color,
+                    );
+                }
+                segment_start = segment_until;
+            }
+        };
+
+        if let Some(merge_strat) = self.visual_config().coalescing {
+            let coalesced_visual_runs = self.heatmap.coalesced_runs(
+                earliest,
+                latest,
+                highest,
+                lowest,
+                market_type,
+                self.visual_config.order_size_filter,
+                merge_strat,
+            );
+
+            for (price_of_run, visual_run) in coalesced_visual_runs {
+                push_run(
+                    price_of_run,
+                    visual_run.start_time,
+                    visual_run.until_time,
+                    visual_run.qty(),
+                    visual_run.is_bid,
+                );
+            }
+        } else {
+            self.heatmap
+                .iter_time_filtered(earliest, latest, highest, lowest)
+                .for_each(|(price, runs)| {
+                    runs.iter()
+                        .filter(|run| {
+                            let order_size = market_type.qty_in_quote_value(
+                                run.qty(),
+                                *price,
+                                size_in_quote_ccy,
+                            );
+                            order_size > self.visual_config.order_size_filter
+                        })
+                        .for_each(|run| {
+                            push_run(
+                                *price,
+                                run.start_time,
+                                run.until_time,
+                                run.qty(),
+                                run.is_bid,
+                            );
+                        });
+                });
+        }
+
+        quads
+    }
+
     pub fn last_update(&self) -> Instant {
         self.last_tick
     }
@@ -479,9 +869,12 @@ impl canvas::Program<Message> for HeatmapChart {
         let market_type = chart.ticker_info.market_type();
 
         let bounds_size = bounds.size();
-        let palette = theme.extended_palette();
+        let palette = super::overridden_palette(theme, &self.colors);
+        let palette = palette.as_ref();
 
         let heatmap = chart.cache.main.draw(renderer, bounds_size, |frame| {
+            chart.draw_watermark(frame, palette);
+
             let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
 
             frame.translate(center);
@@ -500,7 +893,6 @@ impl canvas::Program<Message> for HeatmapChart {
             let cell_height = chart.cell_height;
             let qty_scales = self.calc_qty_scales(earliest, latest, highest, lowest);
 
-            let max_depth_qty = qty_scales.max_depth_qty;
             let (max_aggr_volume, max_trade_qty) =
                 (qty_scales.max_aggr_volume, qty_scales.max_trade_qty);
 
@@ -508,76 +900,44 @@ impl canvas::Program<Message> for HeatmapChart {
 
             let volume_indicator = self.indicators[HeatmapIndicator::Volume].is_some();
 
-            if let Some(merge_strat) = self.visual_config().coalescing {
-                let coalesced_visual_runs = self.heatmap.coalesced_runs(
-                    earliest,
-                    latest,
-                    highest,
-                    lowest,
-                    market_type,
-                    self.visual_config.order_size_filter,
-                    merge_strat,
-                );
-
-                for (price_of_run, visual_run) in coalesced_visual_runs {
-                    let y_position = chart.price_to_y(price_of_run);
-
-                    let run_start_time_clipped = visual_run.start_time.max(earliest);
-                    let run_until_time_clipped = visual_run.until_time.min(latest);
-
-                    if run_start_time_clipped >= run_until_time_clipped {
-                        continue;
-                    }
-
-                    let start_x = chart.interval_to_x(run_start_time_clipped);
-                    let end_x = chart.interval_to_x(run_until_time_clipped).min(0.0);
+            if let Some(snapshot) = self.scrub.and_then(|time| self.depth_history.at(time)) {
+                let scrub_x = chart.interval_to_x(snapshot.time);
 
-                    let width = end_x - start_x;
+                let max_qty = snapshot
+                    .levels_in(highest, lowest)
+                    .map(|(_, qty, _)| qty)
+                    .fold(f32::MIN, f32::max);
 
-                    if width > 0.001 {
-                        let color_alpha = (visual_run.qty() / max_depth_qty).min(1.0);
+                if max_qty > 0.0 {
+                    for (price, qty, is_bid) in snapshot.levels_in(highest, lowest) {
+                        let y_position = chart.price_to_y(price);
 
                         frame.fill_rectangle(
-                            Point::new(start_x, y_position - (cell_height / 2.0)),
-                            Size::new(width, cell_height),
-                            depth_color(palette, visual_run.is_bid, color_alpha),
+                            Point::new(scrub_x, y_position - (cell_height / 2.0)),
+                            Size::new((qty / max_qty) * 50.0, cell_height),
+                            depth_color(palette, is_bid, 0.5),
                         );
                     }
                 }
-            } else {
-                self.heatmap
-                    .iter_time_filtered(earliest, latest, highest, lowest)
-                    .for_each(|(price, runs)| {
-                        let y_position = chart.price_to_y(*price);
-
-                        runs.iter()
-                            .filter(|run| {
-                                let order_size = market_type.qty_in_quote_value(
-                                    run.qty(),
-                                    *price,
-                                    size_in_quote_ccy,
-                                );
-                                order_size > self.visual_config.order_size_filter
-                            })
-                            .for_each(|run| {
-                                let start_x = chart.interval_to_x(run.start_time.max(earliest));
-                                let end_x =
-                                    chart.interval_to_x(run.until_time.min(latest)).min(0.0);
-
-                                let width = end_x - start_x;
-
-                                let color_alpha = (run.qty() / max_depth_qty).min(1.0);
-
-                                frame.fill_rectangle(
-                                    Point::new(start_x, y_position - (cell_height / 2.0)),
-                                    Size::new(width, cell_height),
-                                    depth_color(palette, run.is_bid, color_alpha),
-                                );
-                            });
-                    });
-            }
 
-            if let Some(latest_timestamp) = self.trades.latest_timestamp() {
+                frame.stroke(
+                    &Path::line(
+                        Point::new(scrub_x, region.y),
+                        Point::new(scrub_x, region.y + region.height),
+                    ),
+                    canvas::Stroke::with_color(
+                        canvas::Stroke {
+                            width: 1.0 / chart.scaling,
+                            line_dash: canvas::LineDash {
+                                segments: &[4.0, 4.0],
+                                offset: 0,
+                            },
+                            ..canvas::Stroke::default()
+                        },
+                        palette.background.base.text.scale_alpha(0.5),
+                    ),
+                );
+            } else if let Some(latest_timestamp) = self.trades.latest_timestamp() {
                 let max_qty = self
                     .heatmap
                     .latest_order_runs(highest, lowest, latest_timestamp)
@@ -681,6 +1041,39 @@ impl canvas::Program<Message> for HeatmapChart {
                     }
                 });
 
+            if let Some(IndicatorData::Spread { tops, .. }) =
+                &self.indicators[HeatmapIndicator::Spread]
+            {
+                let microprice = Path::new(|builder| {
+                    for (idx, (time, top)) in tops.range(earliest..=latest).enumerate() {
+                        let point = Point::new(
+                            chart.interval_to_x(*time),
+                            chart.price_to_y(Price::from_f32(top.microprice)),
+                        );
+                        if idx == 0 {
+                            builder.move_to(point);
+                        } else {
+                            builder.line_to(point);
+                        }
+                    }
+                });
+
+                frame.stroke(
+                    &microprice,
+                    canvas::Stroke::with_color(
+                        canvas::Stroke {
+                            width: 1.0 / chart.scaling,
+                            ..canvas::Stroke::default()
+                        },
+                        palette.primary.strong.color,
+                    ),
+                );
+            }
+
+            chart.draw_liquidations(frame, palette, earliest, latest, |time| {
+                chart.interval_to_x(time)
+            });
+
             if volume_indicator && max_aggr_volume > 0.0 {
                 let text_size = 9.0 / chart.scaling;
                 let text_content = abbr_large_numbers(max_aggr_volume);
@@ -739,6 +1132,8 @@ impl canvas::Program<Message> for HeatmapChart {
                 );
             }
 
+            draw_pinned_levels(frame, chart, &self.pinned_levels, &region, palette);
+
             let is_paused = chart.translation.x * chart.scaling > chart.bounds.width / 2.0;
             if is_paused {
                 let bar_width = 8.0 / chart.scaling;
@@ -770,6 +1165,10 @@ impl canvas::Program<Message> for HeatmapChart {
 
         if !self.is_empty() {
             let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
+                if cursor.position_in(bounds).is_none() {
+                    chart.draw_synced_crosshair(frame, theme, bounds_size);
+                }
+
                 if let Some(cursor_position) = cursor.position_in(bounds) {
                     let (cursor_at_price, cursor_at_time) = chart.draw_crosshair(
                         frame,
@@ -852,7 +1251,6 @@ impl canvas::Program<Message> for HeatmapChart {
                     let cell_width_overlay = TOOLTIP_WIDTH / 4.0;
                     let cell_height_overlay = TOOLTIP_HEIGHT / 3.0;
 
-                    let palette = theme.extended_palette();
                     for (display_row_idx, &data_price_val) in
                         prices_for_display_lookup.iter().enumerate()
                     {
@@ -907,9 +1305,16 @@ impl canvas::Program<Message> for HeatmapChart {
         cursor: mouse::Cursor,
     ) -> mouse::Interaction {
         match interaction {
-            Interaction::Panning { .. } => mouse::Interaction::Grabbing,
+            Interaction::Panning { .. }
+            | Interaction::MovingAnchor { .. }
+            | Interaction::MovingOrder { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
+            Interaction::None
+            | Interaction::Ruler { .. }
+            | Interaction::Coasting { .. }
+            | Interaction::Drawing { .. }
+            | Interaction::Lens
+            | Interaction::PlacingOrder { .. } => {
                 if cursor.is_over(bounds) {
                     return mouse::Interaction::Crosshair;
                 }
@@ -919,6 +1324,23 @@ impl canvas::Program<Message> for HeatmapChart {
     }
 }
 
+/// Splits between the chart and the panels of `enabled`, keeping the chart's share of `splits`
+fn panel_splits(splits: &[f32], enabled: &[HeatmapIndicator]) -> Vec<f32> {
+    let panels = enabled
+        .iter()
+        .filter(|indicator| indicator.has_panel())
+        .count();
+
+    if panels == 0 {
+        vec![]
+    } else if splits.len() == panels {
+        splits.to_vec()
+    } else {
+        let main_split = splits.first().copied().unwrap_or(0.8);
+        data::util::calc_panel_splits(main_split, panels, Some(splits.len()))
+    }
+}
+
 fn depth_color(palette: &Extended, is_bid: bool, alpha: f32) -> Color {
     if is_bid {
         palette.success.strong.color.scale_alpha(alpha)
@@ -927,6 +1349,72 @@ fn depth_color(palette: &Extended, is_bid: bool, alpha: f32) -> Color {
     }
 }
 
+/// Dashed line per pinned level, its label stays at the top or bottom edge while the level is out of view
+fn draw_pinned_levels(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    pins: &[Price],
+    region: &Rectangle,
+    palette: &Extended,
+) {
+    if pins.is_empty() {
+        return;
+    }
+
+    let color = palette.primary.base.color;
+    let text_size = 10.0 / chart.scaling;
+    let last_price = chart
+        .last_price
+        .map(|label| label.get_with_color(palette).0);
+
+    let line_stroke = canvas::Stroke::with_color(
+        canvas::Stroke {
+            width: 1.0 / chart.scaling,
+            line_dash: canvas::LineDash {
+                segments: &[6.0, 4.0],
+                offset: 0,
+            },
+            ..Default::default()
+        },
+        color.scale_alpha(0.8),
+    );
+
+    for &price in pins {
+        let y = chart.price_to_y(price);
+        let top = region.y + text_size;
+        let bottom = region.y + region.height - text_size;
+
+        if y >= region.y && y <= region.y + region.height {
+            frame.stroke(
+                &Path::line(
+                    Point::new(region.x, y),
+                    Point::new(region.x + region.width, y),
+                ),
+                line_stroke,
+            );
+        }
+
+        let label = data::chart::pin::pin_label(price, last_price, chart.ticker_info.min_ticksize);
+        let content = if y < top {
+            format!("\u{25B2} {label}")
+        } else if y > bottom {
+            format!("\u{25BC} {label}")
+        } else {
+            label
+        };
+
+        frame.fill_text(canvas::Text {
+            content,
+            position: Point::new(region.x + 4.0 / chart.scaling, y.clamp(top, bottom)),
+            size: iced::Pixels(text_size),
+            color,
+            font: style::AZERET_MONO,
+            align_y: Alignment::Center.into(),
+            ..canvas::Text::default()
+        });
+    }
+}
+
 fn draw_volume_profile(
     frame: &mut canvas::Frame,
     region: &Rectangle,
@@ -957,6 +1445,17 @@ fn draw_volume_profile(
 
             earliest..=latest
         }
+        ProfileKind::Session => {
+            let latest = chart
+                .latest_x
+                .min(chart.x_to_interval(region.x + region.width));
+
+            let Some(session) = chart.sessions.manager.latest_span(latest) else {
+                return;
+            };
+
+            session.start..=latest
+        }
     };
 
     let step = chart.tick_size;
@@ -0,0 +1,176 @@
+use super::HeatmapChart;
+
+use iced::advanced::graphics::color;
+use iced::advanced::graphics::mesh::{self, Indexed, Mesh, SolidVertex2D};
+use iced::advanced::widget::tree::Tree;
+use iced::advanced::{Layout, Widget, layout, renderer};
+use iced::{
+    Color, Element, Length, Point, Rectangle, Renderer, Size, Theme, Transformation, Vector, mouse,
+};
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Depth cells of a frame, batched as quads so they go out in one draw call
+/// instead of a tessellated fill per cell
+pub struct QuadBatch {
+    /// Screen position of the chart's origin
+    origin: Vector,
+    translation: Vector,
+    scaling: f32,
+    vertices: Vec<SolidVertex2D>,
+    indices: Vec<u32>,
+}
+
+impl QuadBatch {
+    pub fn new(origin: Vector, translation: Vector, scaling: f32) -> Self {
+        Self {
+            origin,
+            translation,
+            scaling,
+            vertices: vec![],
+            indices: vec![],
+        }
+    }
+
+    /// Adds a rectangle given in chart coordinates
+    pub fn push(&mut self, top_left: Point, size: Size, color: Color) {
+        let Point { x, y } = self.to_screen(top_left);
+        let (width, height) = (size.width * self.scaling, size.height * self.scaling);
+
+        let color = color::pack(color);
+        let base = self.vertices.len() as u32;
+
+        self.vertices.extend(
+            [
+                [x, y],
+                [x + width, y],
+                [x + width, y + height],
+                [x, y + height],
+            ]
+            .map(|position| SolidVertex2D { position, color }),
+        );
+        self.indices
+            .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    fn to_screen(&self, point: Point) -> Point {
+        Point::new(
+            (point.x + self.translation.x) * self.scaling + self.origin.x,
+            (point.y + self.translation.y) * self.scaling + self.origin.y,
+        )
+    }
+
+    fn into_mesh(self, clip_bounds: Rectangle) -> Option<Mesh> {
+        if self.indices.is_empty() {
+            return None;
+        }
+
+        Some(Mesh::Solid {
+            buffers: Indexed {
+                vertices: self.vertices,
+                indices: self.indices,
+            },
+            transformation: Transformation::IDENTITY,
+            clip_bounds,
+        })
+    }
+}
+
+/// Uploaded depth cells, kept until the chart is invalidated or moved on screen
+#[derive(Default)]
+pub struct DepthMesh {
+    cache: RefCell<Option<mesh::Cache>>,
+    /// Bounds and theme colors the mesh was built for
+    built_for: RefCell<Option<(Rectangle, [Color; 2])>>,
+}
+
+impl DepthMesh {
+    pub fn clear(&self) {
+        self.built_for.replace(None);
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        key: (Rectangle, [Color; 2]),
+        build: impl FnOnce() -> Option<Mesh>,
+    ) {
+        use iced::advanced::graphics::mesh::Renderer as _;
+
+        if *self.built_for.borrow() != Some(key) {
+            let meshes: Arc<[Mesh]> = build().into_iter().collect();
+            let mut cache = self.cache.borrow_mut();
+
+            match cache.as_mut() {
+                Some(cache) => cache.update(meshes),
+                None => *cache = Some(mesh::Cache::new(meshes)),
+            }
+            self.built_for.replace(Some(key));
+        }
+
+        if let Some(cache) = self.cache.borrow().as_ref()
+            && !cache.is_empty()
+        {
+            renderer.draw_mesh_cache(cache.clone());
+        }
+    }
+}
+
+/// Draws the depth cells of the heatmap below its canvas
+pub struct DepthLayer<'a> {
+    chart: &'a HeatmapChart,
+}
+
+impl<'a> DepthLayer<'a> {
+    pub fn new(chart: &'a HeatmapChart) -> Self {
+        Self { chart }
+    }
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for DepthLayer<'_> {
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: Length::Fill,
+            height: Length::Fill,
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        if bounds.width == 0.0 || bounds.height == 0.0 {
+            return;
+        }
+
+        let palette = crate::chart::overridden_palette(theme, &self.chart.colors);
+        let colors = [palette.success.strong.color, palette.danger.strong.color];
+
+        self.chart.depth_mesh.draw(renderer, (bounds, colors), || {
+            self.chart.depth_quads(bounds, &palette).into_mesh(bounds)
+        });
+    }
+}
+
+impl<'a, Message: 'a> From<DepthLayer<'a>> for Element<'a, Message, Theme, Renderer> {
+    fn from(layer: DepthLayer<'a>) -> Self {
+        Element::new(layer)
+    }
+}
@@ -5,7 +5,7 @@ use super::scale::linear;
 use super::{Interaction, Message};
 use crate::chart::{
     Caches, TEXT_SIZE, ViewState,
-    indicator::plot::{AnySeries, ChartCanvas, Plot},
+    indicator::plot::{ChartCanvas, IndexedSeries, Plot},
     scale::{AxisLabel, LabelContent, calc_label_rect},
 };
 use data::util::{abbr_large_numbers, round_to_tick};
@@ -29,16 +29,16 @@ pub fn indicator_row<'a, P, Y>(
     visible_range: RangeInclusive<u64>,
 ) -> Element<'a, Message>
 where
-    P: Plot<AnySeries<'a, Y>> + 'a,
+    P: Plot<IndexedSeries<'a, Y>> + 'a,
 {
-    let series = AnySeries::for_basis(main_chart.basis, datapoints);
+    let series = IndexedSeries::new(main_chart.basis, datapoints, &cache.extents);
 
     let (min, max) = plot
         .y_extents(&series, visible_range)
         .map(|(min, max)| plot.adjust_extents(min, max))
         .unwrap_or((0.0, 0.0));
 
-    let canvas = Canvas::new(ChartCanvas::<P, AnySeries<'a, Y>> {
+    let canvas = Canvas::new(ChartCanvas::<P, IndexedSeries<'a, Y>> {
         indicator_cache: &cache.main,
         crosshair_cache: &cache.crosshair,
         ctx: main_chart,
@@ -52,6 +52,7 @@ where
 
     let labels = Canvas::new(IndicatorLabel {
         label_cache: &cache.y_labels,
+        crosshair_cache: &cache.y_crosshair_label,
         max,
         min,
         chart_bounds: main_chart.bounds,
@@ -69,6 +70,7 @@ where
 
 pub struct IndicatorLabel<'a> {
     pub label_cache: &'a Cache,
+    pub crosshair_cache: &'a Cache,
     pub max: f32,
     pub min: f32,
     pub chart_bounds: Rectangle,
@@ -103,7 +105,7 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
         let tick_size = data::util::guesstimate_ticks(range);
 
         let labels = self.label_cache.draw(renderer, bounds.size(), |frame| {
-            let mut all_labels = linear::generate_labels(
+            let all_labels = linear::generate_labels(
                 bounds,
                 self.min,
                 self.max,
@@ -112,6 +114,10 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
                 None,
             );
 
+            AxisLabel::filter_and_draw(&all_labels, frame);
+        });
+
+        let crosshair = self.crosshair_cache.draw(renderer, bounds.size(), |frame| {
             let common_bounds = Rectangle {
                 x: self.chart_bounds.x,
                 y: bounds.y,
@@ -134,17 +140,16 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
 
                 let y_position = bounds.height - ((rounded_value - lowest) / range * bounds.height);
 
-                all_labels.push(AxisLabel::Y {
+                AxisLabel::Y {
                     bounds: calc_label_rect(y_position, 1, TEXT_SIZE, bounds),
                     value_label: label,
                     timer_label: None,
-                });
+                }
+                .draw(frame);
             }
-
-            AxisLabel::filter_and_draw(&all_labels, frame);
         });
 
-        vec![labels]
+        vec![labels, crosshair]
     }
 
     fn mouse_interaction(
@@ -1,12 +1,15 @@
 use crate::chart::{Message, ViewState};
 
 use data::chart::PlotData;
-use data::chart::indicator::KlineIndicator;
+use data::chart::indicator::{IndicatorParams, KlineIndicator};
 use data::chart::kline::KlineDataPoint;
 use exchange::fetcher::FetchRange;
 use exchange::{Kline, Timeframe, Trade};
 
+pub mod aggregated_oi;
+pub mod atr;
 pub mod open_interest;
+pub mod rsi;
 pub mod volume;
 
 pub trait KlineIndicatorImpl {
@@ -47,6 +50,20 @@ pub trait KlineIndicatorImpl {
     fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
 
     fn on_open_interest(&mut self, _pairs: &[exchange::OpenInterest]) {}
+
+    fn on_aggregated_open_interest(
+        &mut self,
+        _data: &[exchange::composite::AggregatedOpenInterest],
+    ) {
+    }
+
+    /// Indicator inputs were changed in the chart settings
+    fn on_params_change(&mut self, _params: &IndicatorParams, _source: &PlotData<KlineDataPoint>) {}
+
+    /// Last `count` values of the plotted series, oldest first
+    fn recent_values(&self, _count: usize) -> Vec<f32> {
+        vec![]
+    }
 }
 
 pub struct FetchCtx<'a> {
@@ -57,11 +74,16 @@ pub struct FetchCtx<'a> {
     pub prefetch_earliest: u64,
 }
 
-pub fn make_empty(which: KlineIndicator) -> Box<dyn KlineIndicatorImpl> {
+pub fn make_empty(which: KlineIndicator, params: &IndicatorParams) -> Box<dyn KlineIndicatorImpl> {
     match which {
         KlineIndicator::Volume => Box::new(super::kline::volume::VolumeIndicator::new()),
         KlineIndicator::OpenInterest => {
             Box::new(super::kline::open_interest::OpenInterestIndicator::new())
         }
+        KlineIndicator::AggregatedOpenInterest => {
+            Box::new(super::kline::aggregated_oi::AggregatedOpenInterestIndicator::new())
+        }
+        KlineIndicator::Rsi => Box::new(super::kline::rsi::RsiIndicator::new(params)),
+        KlineIndicator::Atr => Box::new(super::kline::atr::AtrIndicator::new(params)),
     }
 }
@@ -0,0 +1,187 @@
+use crate::chart::{
+    Basis, Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::{FetchCtx, KlineIndicatorImpl, open_interest::OpenInterestIndicator},
+        plot::{PlotTooltip, line::LinePlot},
+    },
+};
+
+use data::chart::{PlotData, kline::KlineDataPoint};
+use data::util::format_with_commas;
+use exchange::composite::{self, AggregatedOpenInterest};
+use exchange::fetcher::FetchRange;
+use exchange::{Kline, Trade};
+
+use iced::widget::{center, row, text};
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// Open interest of the chart's underlying summed over every venue listing it as a linear perp
+pub struct AggregatedOpenInterestIndicator {
+    cache: Caches,
+    pub data: BTreeMap<u64, AggregatedOpenInterest>,
+}
+
+impl AggregatedOpenInterestIndicator {
+    pub fn new() -> Self {
+        Self {
+            cache: Caches::default(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        match main_chart.basis {
+            Basis::Time(timeframe) => {
+                let ticker = main_chart.ticker_info.ticker;
+                if !composite::supports_aggregated_oi(&ticker) {
+                    return center(text(format!(
+                        "Aggregated Open Interest is not available for {ticker}"
+                    )))
+                    .into();
+                }
+
+                if !OpenInterestIndicator::is_supported_timeframe(timeframe) {
+                    return center(text(format!(
+                        "WIP: Open Interest is not available on {timeframe} timeframe"
+                    )))
+                    .into();
+                }
+
+                let (earliest, latest) = visible_range.clone().into_inner();
+                if latest < earliest {
+                    return row![].into();
+                }
+            }
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
+                return center(text(
+                    "WIP: Open Interest is not available for non-time-based charts.",
+                ))
+                .into();
+            }
+        }
+
+        let tooltip = |value: &AggregatedOpenInterest, next: Option<&AggregatedOpenInterest>| {
+            let mut lines = vec![format!(
+                "Aggregated OI: {}",
+                format_with_commas(value.total)
+            )];
+
+            if let Some(next) = next {
+                let delta = next.total - value.total;
+                let sign = if delta >= 0.0 { "+" } else { "" };
+                lines.push(format!("Change: {}{}", sign, format_with_commas(delta)));
+            }
+
+            lines.extend(value.venues.iter().map(|(exchange, venue_oi)| {
+                let share = if value.total > 0.0 {
+                    venue_oi / value.total * 100.0
+                } else {
+                    0.0
+                };
+                format!(
+                    "{exchange}: {} ({share:.1}%)",
+                    format_with_commas(*venue_oi)
+                )
+            }));
+
+            PlotTooltip::new(lines.join("\n"))
+        };
+
+        let plot = LinePlot::new(|v: &AggregatedOpenInterest| v.total)
+            .stroke_width(1.0)
+            .show_points(true)
+            .point_radius_factor(0.2)
+            .padding(0.08)
+            .with_tooltip(tooltip);
+
+        indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
+    }
+
+    fn oi_timerange(&self, latest_kline: u64) -> (u64, u64) {
+        let earliest = self
+            .data
+            .keys()
+            .next()
+            .map_or(latest_kline, |t| (*t).min(latest_kline));
+        let latest = self.data.keys().next_back().copied().unwrap_or(u64::MIN);
+
+        (earliest, latest)
+    }
+}
+
+impl KlineIndicatorImpl for AggregatedOpenInterestIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, visible_range)
+    }
+
+    fn fetch_range(&mut self, ctx: &FetchCtx) -> Option<FetchRange> {
+        let is_supported = composite::supports_aggregated_oi(&ctx.main_chart.ticker_info.ticker)
+            && OpenInterestIndicator::is_supported_timeframe(ctx.timeframe);
+
+        if !is_supported {
+            return None;
+        }
+
+        let (oi_earliest, oi_latest) = self.oi_timerange(ctx.kline_latest);
+
+        if ctx.visible_earliest < oi_earliest {
+            return Some(FetchRange::AggregatedOpenInterest(
+                ctx.prefetch_earliest,
+                oi_earliest,
+            ));
+        }
+
+        if oi_latest < ctx.kline_latest {
+            return Some(FetchRange::AggregatedOpenInterest(
+                oi_latest.max(ctx.prefetch_earliest),
+                ctx.kline_latest,
+            ));
+        }
+
+        None
+    }
+
+    fn rebuild_from_source(&mut self, _source: &PlotData<KlineDataPoint>) {
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, _klines: &[Kline]) {}
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        _old_dp_len: usize,
+        _source: &PlotData<KlineDataPoint>,
+    ) {
+    }
+
+    fn on_ticksize_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
+
+    fn on_aggregated_open_interest(&mut self, data: &[AggregatedOpenInterest]) {
+        self.data
+            .extend(data.iter().map(|oi| (oi.time, oi.clone())));
+        if let Some(earliest) = data.iter().map(|oi| oi.time).min() {
+            self.cache.extents.invalidate_from(earliest);
+        }
+        self.clear_all_caches();
+    }
+}
@@ -0,0 +1,184 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::KlineIndicatorImpl,
+        plot::{PlotTooltip, line::LinePlot},
+    },
+};
+
+use data::aggr::{range::RangeAggr, ticks::TickAggr, volume::VolumeAggr};
+use data::chart::indicator::IndicatorParams;
+use data::chart::{PlotData, kline::KlineDataPoint};
+use data::util::format_with_commas;
+use exchange::{Kline, Trade};
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// High, low and close of a bar
+type Bar = (f32, f32, f32);
+
+/// Average true range of bars, using Wilder's smoothing
+pub struct AtrIndicator {
+    cache: Caches,
+    period: u16,
+    color: Option<iced::Color>,
+    bars: BTreeMap<u64, Bar>,
+    data: BTreeMap<u64, f32>,
+}
+
+impl AtrIndicator {
+    pub fn new(params: &IndicatorParams) -> Self {
+        Self {
+            cache: Caches::default(),
+            period: params.atr_period,
+            color: params.atr_color,
+            bars: BTreeMap::new(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        let period = self.period;
+        let tooltip = move |value: &f32, _next: Option<&f32>| {
+            PlotTooltip::new(format!("ATR({period}): {}", format_with_commas(*value)))
+        };
+
+        let plot = LinePlot::new(|value: &f32| *value)
+            .stroke_width(1.0)
+            .show_points(false)
+            .padding(0.08)
+            .color(self.color)
+            .with_tooltip(tooltip);
+
+        indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
+    }
+
+    fn recalculate(&mut self) {
+        self.data = wilder_atr(&self.bars, usize::from(self.period.max(1)));
+        self.cache.extents.invalidate();
+        self.clear_all_caches();
+    }
+}
+
+fn bar_of(kline: &Kline) -> Bar {
+    (
+        kline.high.to_f32(),
+        kline.low.to_f32(),
+        kline.close.to_f32(),
+    )
+}
+
+/// ATR of each bar from the `period`th one onward, keyed like `bars`
+fn wilder_atr(bars: &BTreeMap<u64, Bar>, period: usize) -> BTreeMap<u64, f32> {
+    let mut atr = BTreeMap::new();
+
+    let mut prev_close: Option<f32> = None;
+    let mut avg = 0.0;
+
+    for (idx, (key, (high, low, close))) in bars.iter().enumerate() {
+        let range = high - low;
+        let true_range = match prev_close.replace(*close) {
+            Some(prev) => range.max((high - prev).abs()).max((low - prev).abs()),
+            None => range,
+        };
+
+        if idx < period {
+            // seed with the plain average of the first `period` ranges
+            avg += true_range / period as f32;
+
+            if idx + 1 < period {
+                continue;
+            }
+        } else {
+            avg = (avg * (period - 1) as f32 + true_range) / period as f32;
+        }
+
+        atr.insert(*key, avg);
+    }
+
+    atr
+}
+
+impl KlineIndicatorImpl for AtrIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.bars = match source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .iter()
+                .map(|(time, dp)| (*time, bar_of(&dp.kline)))
+                .collect(),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+                .iter()
+                .enumerate()
+                .map(|(idx, dp)| (idx as u64, bar_of(&dp.kline)))
+                .collect(),
+        };
+        self.recalculate();
+    }
+
+    fn on_insert_klines(&mut self, klines: &[Kline]) {
+        for kline in klines {
+            self.bars.insert(kline.time, bar_of(kline));
+        }
+        self.recalculate();
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        match source {
+            PlotData::TimeBased(_) => return,
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let start_idx = old_dp_len.saturating_sub(1);
+                for (idx, dp) in datapoints.iter().enumerate().skip(start_idx) {
+                    self.bars.insert(idx as u64, bar_of(&dp.kline));
+                }
+            }
+        }
+        self.recalculate();
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_params_change(&mut self, params: &IndicatorParams, source: &PlotData<KlineDataPoint>) {
+        self.period = params.atr_period;
+        self.color = params.atr_color;
+
+        self.rebuild_from_source(source);
+    }
+}
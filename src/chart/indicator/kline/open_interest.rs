@@ -3,7 +3,11 @@ use crate::chart::{
     indicator::{
         indicator_row,
         kline::{FetchCtx, KlineIndicatorImpl},
-        plot::{PlotTooltip, line::LinePlot},
+        plot::{
+            PlotTooltip,
+            bar::{BarClass, BarPlot},
+            line::LinePlot,
+        },
     },
 };
 
@@ -12,19 +16,25 @@ use data::util::format_with_commas;
 use exchange::{Kline, Timeframe, Trade};
 use exchange::{adapter::Exchange, fetcher::FetchRange};
 
-use iced::widget::{center, row, text};
+use iced::Length;
+use iced::widget::{center, column, container, row, rule, text};
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
 pub struct OpenInterestIndicator {
     cache: Caches,
+    delta_cache: Caches,
     pub data: BTreeMap<u64, f32>,
+    /// OI change from the previous datapoint, keyed like `data`
+    deltas: BTreeMap<u64, f32>,
 }
 
 impl OpenInterestIndicator {
     pub fn new() -> Self {
         Self {
             cache: Caches::default(),
+            delta_cache: Caches::default(),
             data: BTreeMap::new(),
+            deltas: BTreeMap::new(),
         }
     }
 
@@ -84,7 +94,47 @@ impl OpenInterestIndicator {
             .padding(0.08)
             .with_tooltip(tooltip);
 
-        indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
+        let delta_tooltip = |delta: &f32, _next: Option<&f32>| {
+            let sign = if *delta >= 0.0 { "+" } else { "" };
+            PlotTooltip::new(format!("OI Delta: {}{}", sign, format_with_commas(*delta)))
+        };
+
+        let delta_plot = BarPlot::new(
+            |delta: &f32| delta.abs(),
+            |delta: &f32| BarClass::Overlay { overlay: *delta },
+        )
+        .bar_width_factor(0.9)
+        .with_tooltip(delta_tooltip);
+
+        column![
+            container(indicator_row(
+                main_chart,
+                &self.cache,
+                plot,
+                &self.data,
+                visible_range.clone()
+            ))
+            .height(Length::FillPortion(3)),
+            rule::horizontal(1).style(crate::style::split_ruler),
+            container(indicator_row(
+                main_chart,
+                &self.delta_cache,
+                delta_plot,
+                &self.deltas,
+                visible_range
+            ))
+            .height(Length::FillPortion(1)),
+        ]
+        .into()
+    }
+
+    fn rebuild_deltas(&mut self) {
+        self.deltas = self
+            .data
+            .values()
+            .zip(self.data.iter().skip(1))
+            .map(|(prev, (time, value))| (*time, value - prev))
+            .collect();
     }
 
     // helper to compute (earliest, latest) present OI keys
@@ -111,10 +161,12 @@ impl OpenInterestIndicator {
 impl KlineIndicatorImpl for OpenInterestIndicator {
     fn clear_all_caches(&mut self) {
         self.cache.clear_all();
+        self.delta_cache.clear_all();
     }
 
     fn clear_crosshair_caches(&mut self) {
         self.cache.clear_crosshair();
+        self.delta_cache.clear_crosshair();
     }
 
     fn element<'a>(
@@ -140,6 +192,7 @@ impl KlineIndicatorImpl for OpenInterestIndicator {
             return Some(FetchRange::OpenInterest(ctx.prefetch_earliest, oi_earliest));
         }
 
+        // none of the venues stream OI, so the tail is polled as new klines arrive
         if oi_latest < ctx.kline_latest {
             return Some(FetchRange::OpenInterest(
                 oi_latest.max(ctx.prefetch_earliest),
@@ -171,6 +224,18 @@ impl KlineIndicatorImpl for OpenInterestIndicator {
 
     fn on_open_interest(&mut self, data: &[exchange::OpenInterest]) {
         self.data.extend(data.iter().map(|oi| (oi.time, oi.value)));
+        self.rebuild_deltas();
+        if let Some(earliest) = data.iter().map(|oi| oi.time).min() {
+            // a delta also moves when the value before it does, and that's at `earliest` or later
+            self.cache.extents.invalidate_from(earliest);
+            self.delta_cache.extents.invalidate_from(earliest);
+        }
         self.clear_all_caches();
     }
+
+    fn recent_values(&self, count: usize) -> Vec<f32> {
+        let mut values: Vec<f32> = self.data.values().rev().take(count).copied().collect();
+        values.reverse();
+        values
+    }
 }
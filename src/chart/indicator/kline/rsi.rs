@@ -0,0 +1,187 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::KlineIndicatorImpl,
+        plot::{PlotTooltip, line::LinePlot},
+    },
+};
+
+use data::aggr::{range::RangeAggr, ticks::TickAggr, volume::VolumeAggr};
+use data::chart::indicator::IndicatorParams;
+use data::chart::{PlotData, kline::KlineDataPoint};
+use exchange::{Kline, Trade};
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Relative strength index of bar closes, using Wilder's smoothing
+pub struct RsiIndicator {
+    cache: Caches,
+    period: u16,
+    overbought: f32,
+    oversold: f32,
+    color: Option<iced::Color>,
+    closes: BTreeMap<u64, f32>,
+    data: BTreeMap<u64, f32>,
+}
+
+impl RsiIndicator {
+    pub fn new(params: &IndicatorParams) -> Self {
+        Self {
+            cache: Caches::default(),
+            period: params.rsi_period,
+            overbought: params.rsi_overbought,
+            oversold: params.rsi_oversold,
+            color: params.rsi_color,
+            closes: BTreeMap::new(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        let period = self.period;
+        let tooltip = move |value: &f32, _next: Option<&f32>| {
+            PlotTooltip::new(format!("RSI({period}): {value:.2}"))
+        };
+
+        let plot = LinePlot::new(|value: &f32| *value)
+            .stroke_width(1.0)
+            .show_points(false)
+            .fixed_range(0.0, 100.0)
+            .guides(&[self.oversold, self.overbought])
+            .color(self.color)
+            .with_tooltip(tooltip);
+
+        indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
+    }
+
+    fn recalculate(&mut self) {
+        self.data = wilder_rsi(&self.closes, usize::from(self.period.max(1)));
+        self.cache.extents.invalidate();
+        self.clear_all_caches();
+    }
+}
+
+/// RSI of each close from the `period`th change onward, keyed like `closes`
+fn wilder_rsi(closes: &BTreeMap<u64, f32>, period: usize) -> BTreeMap<u64, f32> {
+    let mut rsi = BTreeMap::new();
+
+    let mut prev_close: Option<f32> = None;
+    let (mut avg_gain, mut avg_loss) = (0.0, 0.0);
+
+    for (idx, (key, close)) in closes.iter().enumerate() {
+        let Some(prev) = prev_close.replace(*close) else {
+            continue;
+        };
+
+        let change = close - prev;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+
+        if idx <= period {
+            // seed with the plain average of the first `period` changes
+            avg_gain += gain / period as f32;
+            avg_loss += loss / period as f32;
+
+            if idx < period {
+                continue;
+            }
+        } else {
+            avg_gain = (avg_gain * (period - 1) as f32 + gain) / period as f32;
+            avg_loss = (avg_loss * (period - 1) as f32 + loss) / period as f32;
+        }
+
+        let value = if avg_loss == 0.0 {
+            if avg_gain == 0.0 { 50.0 } else { 100.0 }
+        } else {
+            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+        };
+        rsi.insert(*key, value);
+    }
+
+    rsi
+}
+
+impl KlineIndicatorImpl for RsiIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.closes = match source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .iter()
+                .map(|(time, dp)| (*time, dp.kline.close.to_f32()))
+                .collect(),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+                .iter()
+                .enumerate()
+                .map(|(idx, dp)| (idx as u64, dp.kline.close.to_f32()))
+                .collect(),
+        };
+        self.recalculate();
+    }
+
+    fn on_insert_klines(&mut self, klines: &[Kline]) {
+        for kline in klines {
+            self.closes.insert(kline.time, kline.close.to_f32());
+        }
+        self.recalculate();
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        _trades: &[Trade],
+        old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        match source {
+            PlotData::TimeBased(_) => return,
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let start_idx = old_dp_len.saturating_sub(1);
+                for (idx, dp) in datapoints.iter().enumerate().skip(start_idx) {
+                    self.closes.insert(idx as u64, dp.kline.close.to_f32());
+                }
+            }
+        }
+        self.recalculate();
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_params_change(&mut self, params: &IndicatorParams, source: &PlotData<KlineDataPoint>) {
+        self.period = params.rsi_period;
+        self.overbought = params.rsi_overbought;
+        self.oversold = params.rsi_oversold;
+        self.color = params.rsi_color;
+
+        self.rebuild_from_source(source);
+    }
+}
@@ -10,6 +10,7 @@ use crate::chart::{
     },
 };
 
+use data::aggr::{range::RangeAggr, ticks::TickAggr, volume::VolumeAggr};
 use data::chart::{PlotData, kline::KlineDataPoint};
 use data::util::format_with_commas;
 use exchange::{Kline, Trade};
@@ -92,7 +93,14 @@ impl KlineIndicatorImpl for VolumeIndicator {
             PlotData::TickBased(tickseries) => {
                 self.data = tickseries.volume_data();
             }
+            PlotData::VolumeBased(volumeseries) => {
+                self.data = volumeseries.volume_data();
+            }
+            PlotData::RangeBased(rangeseries) => {
+                self.data = rangeseries.volume_data();
+            }
         }
+        self.cache.extents.invalidate();
         self.clear_all_caches();
     }
 
@@ -101,6 +109,9 @@ impl KlineIndicatorImpl for VolumeIndicator {
             self.data
                 .insert(kline.time, (kline.volume.0, kline.volume.1));
         }
+        if let Some(earliest) = klines.iter().map(|kline| kline.time).min() {
+            self.cache.extents.invalidate_from(earliest);
+        }
         self.clear_all_caches();
     }
 
@@ -112,12 +123,15 @@ impl KlineIndicatorImpl for VolumeIndicator {
     ) {
         match source {
             PlotData::TimeBased(_) => return,
-            PlotData::TickBased(tickseries) => {
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
                 let start_idx = old_dp_len.saturating_sub(1);
-                for (idx, dp) in tickseries.datapoints.iter().enumerate().skip(start_idx) {
+                for (idx, dp) in datapoints.iter().enumerate().skip(start_idx) {
                     self.data
                         .insert(idx as u64, (dp.kline.volume.0, dp.kline.volume.1));
                 }
+                self.cache.extents.invalidate_from(start_idx as u64);
             }
         }
         self.clear_all_caches();
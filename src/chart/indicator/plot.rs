@@ -5,8 +5,9 @@ use data::util::{guesstimate_ticks, round_to_tick};
 use iced::widget::canvas::{self, Cache, Geometry, Path};
 use iced::{Alignment, Point, Rectangle, Renderer, Size, Theme, Vector, mouse};
 
+use std::cell::RefCell;
 use std::collections::BTreeMap;
-use std::ops::RangeInclusive;
+use std::ops::{Bound, RangeInclusive};
 
 pub mod bar;
 pub mod line;
@@ -21,6 +22,20 @@ pub trait Series {
     fn next_after<'a>(&'a self, x: u64) -> Option<(u64, &'a Self::Y)>
     where
         Self: 'a;
+
+    /// Lowest and highest `value` over `range`, `None` if no datapoint falls in it
+    fn min_max_in(
+        &self,
+        range: RangeInclusive<u64>,
+        value: impl Fn(&Self::Y) -> f32,
+    ) -> Option<(f32, f32)> {
+        let mut extents: Option<(f32, f32)> = None;
+        self.for_each_in(range, |_, y| {
+            let v = value(y);
+            extents = Some(extents.map_or((v, v), |(min, max)| (min.min(v), max.max(v))));
+        });
+        extents
+    }
 }
 
 impl<Y> Series for &BTreeMap<u64, Y> {
@@ -99,6 +114,16 @@ impl<'a, Y> AnySeries<'a, Y> {
             Basis::Time(_) => Self::Forward(data),
         }
     }
+
+    /// Keys of the underlying map that `range` covers
+    fn key_range(&self, range: RangeInclusive<u64>) -> RangeInclusive<u64> {
+        match self {
+            AnySeries::Forward(_) => range,
+            AnySeries::Reversed(rv) => {
+                rv.offset.saturating_sub(*range.end())..=rv.offset.saturating_sub(*range.start())
+            }
+        }
+    }
 }
 
 impl<'a, Y> Series for AnySeries<'a, Y> {
@@ -133,6 +158,163 @@ impl<'a, Y> Series for AnySeries<'a, Y> {
     }
 }
 
+/// [`AnySeries`] whose extents are read from an [`ExtentsIndex`] of its data
+pub struct IndexedSeries<'a, Y> {
+    series: AnySeries<'a, Y>,
+    data: &'a BTreeMap<u64, Y>,
+    index: &'a ExtentsIndex,
+}
+
+impl<'a, Y> IndexedSeries<'a, Y> {
+    pub fn new(basis: Basis, data: &'a BTreeMap<u64, Y>, index: &'a ExtentsIndex) -> Self {
+        Self {
+            series: AnySeries::for_basis(basis, data),
+            data,
+            index,
+        }
+    }
+}
+
+impl<Y> Series for IndexedSeries<'_, Y> {
+    type Y = Y;
+
+    fn for_each_in<F: FnMut(u64, &Self::Y)>(&self, range: RangeInclusive<u64>, f: F) {
+        self.series.for_each_in(range, f);
+    }
+
+    fn at(&self, x: u64) -> Option<&Self::Y> {
+        self.series.at(x)
+    }
+
+    fn next_after<'b>(&'b self, x: u64) -> Option<(u64, &'b Self::Y)>
+    where
+        Self: 'b,
+    {
+        self.series.next_after(x)
+    }
+
+    fn min_max_in(
+        &self,
+        range: RangeInclusive<u64>,
+        value: impl Fn(&Self::Y) -> f32,
+    ) -> Option<(f32, f32)> {
+        self.index
+            .min_max(self.data, self.series.key_range(range), value)
+    }
+}
+
+/// Datapoints per chunk of an [`ExtentsIndex`]
+const EXTENTS_CHUNK: usize = 256;
+
+struct ExtentsChunk {
+    first: u64,
+    last: u64,
+    len: usize,
+    min: f32,
+    max: f32,
+}
+
+/// Min and max of a plotted value per chunk of consecutive keys, so the extents of a wide
+/// visible range fold whole chunks and only rescan the two its ends cut through.
+///
+/// Chunks are built on the first query after the data grows or is invalidated, from where it
+/// changed; in-place changes must be reported with [`Self::invalidate_from`]
+#[derive(Default)]
+pub struct ExtentsIndex {
+    chunks: RefCell<Vec<ExtentsChunk>>,
+}
+
+impl ExtentsIndex {
+    /// The data was replaced or lost keys
+    pub fn invalidate(&self) {
+        self.chunks.borrow_mut().clear();
+    }
+
+    /// The data changed at `key` or after it
+    pub fn invalidate_from(&self, key: u64) {
+        let mut chunks = self.chunks.borrow_mut();
+        let keep = chunks.partition_point(|chunk| chunk.last < key);
+        chunks.truncate(keep);
+    }
+
+    fn min_max<Y>(
+        &self,
+        data: &BTreeMap<u64, Y>,
+        keys: RangeInclusive<u64>,
+        value: impl Fn(&Y) -> f32,
+    ) -> Option<(f32, f32)> {
+        let (lo, hi) = keys.into_inner();
+        if lo > hi {
+            return None;
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        Self::extend(&mut chunks, data, &value);
+
+        let mut extents: Option<(f32, f32)> = None;
+        let mut fold = |min: f32, max: f32| {
+            extents = Some(extents.map_or((min, max), |(low, high)| (low.min(min), high.max(max))));
+        };
+
+        let start = chunks.partition_point(|chunk| chunk.last < lo);
+        for chunk in chunks[start..].iter().take_while(|chunk| chunk.first <= hi) {
+            if lo <= chunk.first && chunk.last <= hi {
+                fold(chunk.min, chunk.max);
+            } else {
+                for (_, y) in data.range(lo.max(chunk.first)..=hi.min(chunk.last)) {
+                    let v = value(y);
+                    fold(v, v);
+                }
+            }
+        }
+
+        extents
+    }
+
+    /// Chunks the keys past the last full chunk
+    fn extend<Y>(
+        chunks: &mut Vec<ExtentsChunk>,
+        data: &BTreeMap<u64, Y>,
+        value: impl Fn(&Y) -> f32,
+    ) {
+        let Some(&latest) = data.keys().next_back() else {
+            chunks.clear();
+            return;
+        };
+        if chunks.last().is_some_and(|chunk| chunk.last >= latest) {
+            return;
+        }
+
+        // a partly filled chunk is redone, so appending one key at a time doesn't leave a
+        // trail of tiny chunks behind
+        if chunks.last().is_some_and(|chunk| chunk.len < EXTENTS_CHUNK) {
+            chunks.pop();
+        }
+        let from = chunks
+            .last()
+            .map_or(Bound::Unbounded, |chunk| Bound::Excluded(chunk.last));
+
+        for (&key, y) in data.range((from, Bound::Unbounded)) {
+            let v = value(y);
+            match chunks.last_mut() {
+                Some(chunk) if chunk.len < EXTENTS_CHUNK => {
+                    chunk.last = key;
+                    chunk.len += 1;
+                    chunk.min = chunk.min.min(v);
+                    chunk.max = chunk.max.max(v);
+                }
+                _ => chunks.push(ExtentsChunk {
+                    first: key,
+                    last: key,
+                    len: 1,
+                    min: v,
+                    max: v,
+                }),
+            }
+        }
+    }
+}
+
 pub struct YScale {
     pub min: f32,
     pub max: f32,
@@ -277,12 +459,8 @@ where
 
                 let crosshair_ratio = f64::from(cursor_position.x / bounds.width);
                 let (rounded_x, snap_ratio) = match ctx.basis {
-                    Basis::Time(_) | Basis::Volume(_) | Basis::Range(_) => {
-                        let step = if let Basis::Time(tf) = ctx.basis {
-                            tf.to_milliseconds() as f64
-                        } else {
-                            60000.0 // fallback
-                        };
+                    Basis::Time(tf) => {
+                        let step = tf.to_milliseconds() as f64;
                         let rx = ((earliest + crosshair_ratio * (latest - earliest)) / step).round()
                             as u64
                             * step as u64;
@@ -294,7 +472,7 @@ where
                         };
                         (rx, sr)
                     }
-                    Basis::Tick(_) => {
+                    Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                         let world_x = region.x + (cursor_position.x / bounds.width) * region.width;
                         let snapped_world_x = (world_x / ctx.cell_width).round() * ctx.cell_width;
 
@@ -438,3 +616,46 @@ impl PlotTooltip {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data::aggr::TickCount;
+    use exchange::Timeframe;
+
+    #[test]
+    fn indexed_extents_match_a_full_scan() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut data: BTreeMap<u64, f32> = (0..2_000u64)
+            .map(|idx| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                (idx * 3, (seed % 10_000) as f32 - 5_000.0)
+            })
+            .collect();
+        let ranges = [0..=5_999, 10..=20, 700..=4_100, 767..=768, 5_990..=9_000];
+
+        for basis in [Basis::Time(Timeframe::M1), Basis::Tick(TickCount(100))] {
+            let index = ExtentsIndex::default();
+
+            for round in 0..2 {
+                let indexed = IndexedSeries::new(basis, &data, &index);
+                let scanned = AnySeries::for_basis(basis, &data);
+
+                for range in ranges.clone() {
+                    assert_eq!(
+                        indexed.min_max_in(range.clone(), |v| *v),
+                        scanned.min_max_in(range, |v| *v),
+                    );
+                }
+
+                if round == 0 {
+                    data.insert(4_000, 9_999.0);
+                    data.insert(7_000, -9_999.0);
+                    index.invalidate_from(4_000);
+                }
+            }
+        }
+    }
+}
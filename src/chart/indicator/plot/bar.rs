@@ -1,5 +1,6 @@
 use std::ops::RangeInclusive;
 
+use data::chart::kline::FormingBar;
 use iced::{Point, Size, Theme, widget::canvas};
 
 use crate::chart::{
@@ -85,22 +86,9 @@ where
     CL: Fn(&S::Y) -> BarClass,
 {
     fn y_extents(&self, datapoints: &S, range: RangeInclusive<u64>) -> Option<(f32, f32)> {
-        let mut min_v = f32::MAX;
-        let mut max_v = f32::MIN;
-        let mut n = 0u32;
-
-        datapoints.for_each_in(range, |_, y| {
-            let v = (self.value)(y);
-            if v < min_v {
-                min_v = v;
-            }
-            if v > max_v {
-                max_v = v;
-            }
-            n += 1;
-        });
+        let (min_v, max_v) = datapoints.min_max_in(range, &self.value)?;
 
-        if n == 0 || (max_v <= 0.0 && matches!(self.baseline, Baseline::Zero)) {
+        if max_v <= 0.0 && matches!(self.baseline, Baseline::Zero) {
             return None;
         }
 
@@ -128,7 +116,6 @@ where
         range: RangeInclusive<u64>,
         scale: &YScale,
     ) {
-        let palette = theme.extended_palette();
         let bar_width = ctx.cell_width * self.bar_width_factor;
 
         let baseline_value = match self.baseline {
@@ -136,65 +123,98 @@ where
             Baseline::Min => scale.min, // extents min
             Baseline::Fixed(v) => v,
         };
-        let y_base = scale.to_y(baseline_value);
+
+        let forming = ctx.forming_interval();
 
         datapoints.for_each_in(range, |x, y| {
+            let alpha = match ctx.forming_bar {
+                _ if forming != Some(x) => 1.0,
+                FormingBar::Normal => 1.0,
+                FormingBar::Faded => 0.4,
+                FormingBar::Hidden => return,
+            };
+
             let center_x = ctx.interval_to_x(x);
             let left = center_x - (bar_width / 2.0);
 
-            let total = (self.value)(y);
-            let rel = total - baseline_value;
+            fill_bar(
+                frame,
+                theme,
+                (self.classify)(y),
+                (left, bar_width),
+                scale,
+                (baseline_value, (self.value)(y)),
+                alpha,
+            );
+        });
+    }
 
-            let (top_y, h_total) = if rel > 0.0 {
-                let y_total = scale.to_y(total);
-                let h = (y_base - y_total).max(0.0);
-                (y_total, h)
-            } else {
-                (y_base, 0.0)
-            };
-            if h_total <= 0.0 {
-                return;
-            }
+    fn tooltip_fn(&self) -> Option<&TooltipFn<S::Y>> {
+        self.tooltip.as_ref()
+    }
+}
 
-            match (self.classify)(y) {
-                BarClass::Single => {
-                    frame.fill_rectangle(
-                        Point::new(left, top_y),
-                        Size::new(bar_width, h_total),
-                        palette.secondary.strong.color,
-                    );
-                }
-                BarClass::Overlay { overlay } => {
-                    let base_color = if overlay >= 0.0 {
-                        palette.success.base.color
-                    } else {
-                        palette.danger.base.color
-                    };
+/// Draws one bar rising from `baseline_value` to `total`, `bar_width` wide from `left`,
+/// colored by its class
+pub fn fill_bar(
+    frame: &mut canvas::Frame,
+    theme: &Theme,
+    class: BarClass,
+    (left, bar_width): (f32, f32),
+    scale: &YScale,
+    (baseline_value, total): (f32, f32),
+    alpha: f32,
+) {
+    let palette = theme.extended_palette();
+    let y_base = scale.to_y(baseline_value);
+
+    let rel = total - baseline_value;
+
+    let (top_y, h_total) = if rel > 0.0 {
+        let y_total = scale.to_y(total);
+        let h = (y_base - y_total).max(0.0);
+        (y_total, h)
+    } else {
+        (y_base, 0.0)
+    };
+    if h_total <= 0.0 {
+        return;
+    }
 
+    match class {
+        BarClass::Single => {
+            frame.fill_rectangle(
+                Point::new(left, top_y),
+                Size::new(bar_width, h_total),
+                palette.secondary.strong.color.scale_alpha(alpha),
+            );
+        }
+        BarClass::Overlay { overlay } => {
+            let base_color = if overlay >= 0.0 {
+                palette.success.base.color
+            } else {
+                palette.danger.base.color
+            }
+            .scale_alpha(alpha);
+
+            frame.fill_rectangle(
+                Point::new(left, top_y),
+                Size::new(bar_width, h_total),
+                base_color.scale_alpha(0.3),
+            );
+
+            let ov_abs = overlay.abs().max(0.0);
+            if ov_abs > 0.0 {
+                let y_overlay = scale.to_y(baseline_value + ov_abs);
+                let h_overlay = (y_base - y_overlay).max(0.0);
+                if h_overlay > 0.0 {
                     frame.fill_rectangle(
-                        Point::new(left, top_y),
-                        Size::new(bar_width, h_total),
-                        base_color.scale_alpha(0.3),
+                        Point::new(left, y_overlay),
+                        Size::new(bar_width, h_overlay),
+                        base_color,
                     );
-
-                    let ov_abs = overlay.abs().max(0.0);
-                    if ov_abs > 0.0 {
-                        let y_overlay = scale.to_y(baseline_value + ov_abs);
-                        let h_overlay = (y_base - y_overlay).max(0.0);
-                        if h_overlay > 0.0 {
-                            frame.fill_rectangle(
-                                Point::new(left, y_overlay),
-                                Size::new(bar_width, h_overlay),
-                                base_color,
-                            );
-                        }
-                    }
                 }
             }
-        });
-    }
-
-    fn tooltip_fn(&self) -> Option<&TooltipFn<S::Y>> {
-        self.tooltip.as_ref()
+        }
     }
 }
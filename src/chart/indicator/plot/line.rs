@@ -18,6 +18,12 @@ pub struct LinePlot<V, T> {
     pub stroke_width: f32,
     pub show_points: bool,
     pub point_radius_factor: f32,
+    /// Value range to plot in instead of fitting the visible data, e.g. 0..100 for oscillators
+    pub fixed_range: Option<(f32, f32)>,
+    /// Values marked with dashed horizontal lines
+    pub guides: Vec<f32>,
+    /// Line color, the theme's unless set
+    pub color: Option<iced::Color>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -32,6 +38,9 @@ impl<V, T> LinePlot<V, T> {
             stroke_width: 1.0,
             show_points: true,
             point_radius_factor: 0.2,
+            fixed_range: None,
+            guides: vec![],
+            color: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -59,6 +68,21 @@ impl<V, T> LinePlot<V, T> {
         self
     }
 
+    pub fn fixed_range(mut self, min: f32, max: f32) -> Self {
+        self.fixed_range = Some((min, max));
+        self
+    }
+
+    pub fn guides(mut self, levels: &[f32]) -> Self {
+        self.guides = levels.to_vec();
+        self
+    }
+
+    pub fn color(mut self, color: Option<iced::Color>) -> Self {
+        self.color = color;
+        self
+    }
+
     pub fn with_tooltip<F>(mut self, tooltip: F) -> Self
     where
         F: Fn(&T, Option<&T>) -> PlotTooltip + 'static,
@@ -74,28 +98,13 @@ where
     V: Fn(&S::Y) -> f32,
 {
     fn y_extents(&self, datapoints: &S, range: RangeInclusive<u64>) -> Option<(f32, f32)> {
-        let mut min_v = f32::MAX;
-        let mut max_v = f32::MIN;
-
-        datapoints.for_each_in(range, |_, y| {
-            let v = (self.value)(y);
-            if v < min_v {
-                min_v = v;
-            }
-            if v > max_v {
-                max_v = v;
-            }
-        });
+        let extents = datapoints.min_max_in(range, &self.value)?;
 
-        if min_v == f32::MAX {
-            None
-        } else {
-            Some((min_v, max_v))
-        }
+        Some(self.fixed_range.unwrap_or(extents))
     }
 
     fn adjust_extents(&self, min: f32, max: f32) -> (f32, f32) {
-        if self.padding > 0.0 && max > min {
+        if self.padding > 0.0 && max > min && self.fixed_range.is_none() {
             let range = max - min;
             let pad = range * self.padding;
             (min - pad, max + pad)
@@ -114,7 +123,7 @@ where
         scale: &YScale,
     ) {
         let palette = theme.extended_palette();
-        let color = palette.secondary.strong.color;
+        let color = self.color.unwrap_or(palette.secondary.strong.color);
 
         let stroke = Stroke::with_color(
             Stroke {
@@ -124,6 +133,32 @@ where
             color,
         );
 
+        if !self.guides.is_empty() {
+            let half_cell = ctx.cell_width / 2.0;
+            let from_x = ctx.interval_to_x(*range.start()) - half_cell;
+            let to_x = ctx.interval_to_x(*range.end()) + half_cell;
+
+            let guide_stroke = Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    line_dash: canvas::LineDash {
+                        segments: &[4.0, 4.0],
+                        offset: 0,
+                    },
+                    ..Stroke::default()
+                },
+                palette.background.strong.color,
+            );
+
+            for level in &self.guides {
+                let y = scale.to_y(*level);
+                frame.stroke(
+                    &Path::line(iced::Point::new(from_x, y), iced::Point::new(to_x, y)),
+                    guide_stroke,
+                );
+            }
+        }
+
         // Polyline
         let mut prev: Option<(f32, f32)> = None;
         datapoints.for_each_in(range.clone(), |x, y| {
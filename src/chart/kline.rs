@@ -1,31 +1,53 @@
+mod bollinger;
+mod divergence;
+
 use super::{
-    Action, Basis, Chart, Interaction, Message, PlotConstants, PlotData, TEXT_SIZE, ViewState,
-    indicator, request_fetch, scale::linear::PriceInfoLabel,
+    Action, Basis, Chart, Interaction, Message, ORDER_GRAB_DISTANCE, OrderHandle, PlotConstants,
+    PlotData, TEXT_SIZE, ViewState, indicator, request_fetch, scale::linear::PriceInfoLabel,
 };
 use crate::chart::indicator::kline::KlineIndicatorImpl;
 use crate::{modal::pane::settings::study, style};
+use bollinger::BollingerOverlay;
+use data::aggr::range::RangeAggr;
 use data::aggr::ticks::TickAggr;
 use data::aggr::time::TimeSeries;
+use data::aggr::volume::VolumeAggr;
 use data::chart::Autoscale;
+use data::chart::alert::{MarketState, PriceAlert};
+use data::chart::colors::ChartColors;
+use data::chart::drawing::{Anchor, Drawing, DrawingTool};
 use data::chart::kline::ClusterScaling;
+use data::chart::kline::FormingBar;
+use data::chart::kline::LargeOrder;
+use data::chart::kline::ZoomLens;
+use data::chart::moving_average::MovingAverage;
+use data::chart::session::SessionSettings;
 use data::chart::{
-    KlineChartKind, ViewConfig,
-    indicator::{Indicator, KlineIndicator},
-    kline::{ClusterKind, FootprintStudy, KlineDataPoint, KlineTrades, NPoc, PointOfControl},
+    KlineChartKind, LevelType, SupportResistanceLevel, TradingConfig, ViewConfig,
+    indicator::{Indicator, IndicatorParams, KlineIndicator, KlineInstance},
+    kline::{
+        BarStatsRows, ClusterKind, FootprintStudy, ImbalanceSide, KlineDataPoint, KlineTrades,
+        NPoc, PocBar, PocScope, PointOfControl, session_pocs,
+    },
 };
+use data::config::theme::get_large_order_color;
+use data::export::DailySnapshot;
+use data::layout::pane::SourceSwitch;
+use data::paper::{Order, Overlay, Side};
 use data::util::{abbr_large_numbers, count_decimals};
-use exchange::util::{Price, PriceStep};
+use divergence::{DivergenceOverlay, draw_divergence_tooltip};
+use exchange::util::{MinTicksize, Price, PriceStep};
 use exchange::{
     Kline, OpenInterest as OIData, TickerInfo, Trade,
+    adapter::ExchangeInclusive,
     fetcher::{FetchRange, RequestHandler},
 };
 
 use iced::task::Handle;
-use iced::theme::palette::Extended;
+use iced::theme::{Palette, palette::Extended};
 use iced::widget::canvas::{self, Event, Geometry, Path, Stroke};
 use iced::{Alignment, Element, Point, Rectangle, Renderer, Size, Theme, Vector, mouse};
 
-use enum_map::EnumMap;
 use std::time::Instant;
 
 // NEW: Advanced rejection detection structure
@@ -50,8 +72,6 @@ pub enum RejectionType {
 pub struct FootprintConfig {
     pub show_rejection_zones: bool,
     pub rejection_threshold: f32, // Minimum strength to show
-    pub highlight_large_orders: bool,
-    pub large_order_threshold: f32,
     pub show_market_depth: bool,
     pub animate_transitions: bool,
 }
@@ -61,8 +81,6 @@ impl Default for FootprintConfig {
         Self {
             show_rejection_zones: true,
             rejection_threshold: 0.7,
-            highlight_large_orders: true,
-            large_order_threshold: 1000.0, // Minimum volume to consider as large order
             show_market_depth: false,
             animate_transitions: true,
         }
@@ -70,7 +88,7 @@ impl Default for FootprintConfig {
 }
 
 impl Chart for KlineChart {
-    type IndicatorKind = KlineIndicator;
+    type IndicatorKind = KlineInstance;
 
     fn state(&self) -> &ViewState {
         &self.chart
@@ -83,8 +101,8 @@ impl Chart for KlineChart {
     fn invalidate_crosshair(&mut self) {
         self.chart.cache.clear_crosshair();
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
+            .iter_mut()
+            .map(|slot| &mut slot.indicator)
             .for_each(|indi| indi.clear_crosshair_caches());
     }
 
@@ -103,12 +121,12 @@ impl Chart for KlineChart {
         let market = chart_state.ticker_info.market_type();
         let mut elements = vec![];
 
-        for selected_indicator in enabled {
-            if !KlineIndicator::for_market(market).contains(selected_indicator) {
+        for instance in enabled {
+            if !KlineIndicator::for_market(market).contains(&instance.kind) {
                 continue;
             }
-            if let Some(indi) = self.indicators[*selected_indicator].as_ref() {
-                elements.push(indi.element(chart_state, earliest..=latest));
+            if let Some(slot) = self.indicators.iter().find(|slot| slot.id == instance.id) {
+                elements.push(slot.indicator.element(chart_state, earliest..=latest));
             }
         }
         elements
@@ -134,7 +152,7 @@ impl Chart for KlineChart {
                 Some((earliest, latest))
             }
             Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
-                unimplemented!()
+                Some(chart.interval_range(&region))
             }
         }
     }
@@ -149,6 +167,20 @@ impl Chart for KlineChart {
                     .map(|dp| dp.kline.time)
                     .collect(),
             ),
+            PlotData::VolumeBased(volume_aggr) => Some(
+                volume_aggr
+                    .datapoints
+                    .iter()
+                    .map(|dp| dp.kline.time)
+                    .collect(),
+            ),
+            PlotData::RangeBased(range_aggr) => Some(
+                range_aggr
+                    .datapoints
+                    .iter()
+                    .map(|dp| dp.kline.time)
+                    .collect(),
+            ),
         }
     }
 
@@ -174,7 +206,43 @@ impl Chart for KlineChart {
         match &self.data_source {
             PlotData::TimeBased(timeseries) => timeseries.datapoints.is_empty(),
             PlotData::TickBased(tick_aggr) => tick_aggr.datapoints.is_empty(),
+            PlotData::VolumeBased(volume_aggr) => volume_aggr.datapoints.is_empty(),
+            PlotData::RangeBased(range_aggr) => range_aggr.datapoints.is_empty(),
+        }
+    }
+
+    fn has_zoom_lens(&self) -> bool {
+        self.zoom_lens.enabled && matches!(self.kind, KlineChartKind::Footprint { .. })
+    }
+
+    fn paper_orders(&self) -> Option<Vec<(u32, Side, f32)>> {
+        self.paper.chart_trading.then(|| {
+            self.paper
+                .orders
+                .iter()
+                .filter_map(|order| Some((order.id, order.side, order.limit?.to_f32())))
+                .collect()
+        })
+    }
+
+    fn order_handle_at(&self, position: Point, bounds: Size) -> Option<OrderHandle> {
+        if !self.paper.chart_trading {
+            return None;
         }
+        let region = self.chart.visible_region(bounds);
+
+        self.paper.orders.iter().find_map(|order| {
+            let price = order.limit?;
+            let y = (self.chart.price_to_y(price) - region.y) * self.chart.scaling;
+
+            if cancel_box(&order_label(order), y).contains(position) {
+                Some(OrderHandle::Cancel(order.id))
+            } else if (y - position.y).abs() <= ORDER_GRAB_DISTANCE {
+                Some(OrderHandle::Line(order.id, price))
+            } else {
+                None
+            }
+        })
     }
 }
 
@@ -208,11 +276,49 @@ impl PlotConstants for KlineChart {
     }
 }
 
+const MAX_LARGE_ORDERS: usize = 200;
+/// Screen distance within which fib anchors snap onto a bar's OHLC prices
+const OHLC_SNAP_PX: f32 = 12.0;
+/// Gap between the cursor and the zoom lens, in px
+const LENS_OFFSET: f32 = 24.0;
+/// Largest share of the chart's width or height the zoom lens covers
+const LENS_MAX_SHARE: f32 = 0.6;
+
+/// Indicator of the pane, keyed by its instance id
+struct IndicatorSlot {
+    id: u16,
+    kind: KlineIndicator,
+    /// Inputs of this instance, the chart's defaults unless set
+    params: Option<IndicatorParams>,
+    indicator: Box<dyn KlineIndicatorImpl>,
+}
+
+impl IndicatorSlot {
+    fn new(
+        instance: KlineInstance,
+        defaults: &IndicatorParams,
+        source: &PlotData<KlineDataPoint>,
+    ) -> Self {
+        let mut indicator = indicator::kline::make_empty(
+            instance.kind,
+            instance.params.as_ref().unwrap_or(defaults),
+        );
+        indicator.rebuild_from_source(source);
+
+        Self {
+            id: instance.id,
+            kind: instance.kind,
+            params: instance.params,
+            indicator,
+        }
+    }
+}
+
 pub struct KlineChart {
     chart: ViewState,
     data_source: PlotData<KlineDataPoint>,
     raw_trades: Vec<Trade>,
-    indicators: EnumMap<KlineIndicator, Option<Box<dyn KlineIndicatorImpl>>>,
+    indicators: Vec<IndicatorSlot>,
     fetching_trades: (bool, Option<Handle>),
     pub(crate) kind: KlineChartKind,
     request_handler: RequestHandler,
@@ -221,7 +327,22 @@ pub struct KlineChart {
     // NEW: Enhanced features
     pub footprint_config: FootprintConfig,
     rejection_zones: Vec<RejectionZone>,
-    large_orders: Vec<(Price, f32, bool)>, // (price, volume, is_buy)
+    large_orders: Vec<LargeOrder>,
+    support_resistance: Vec<SupportResistanceLevel>,
+    /// Alerts of the pane for the charted ticker, owned by the pane settings
+    alerts: Vec<PriceAlert>,
+    /// Simulated orders and position of the charted ticker
+    paper: Overlay,
+    indicator_params: IndicatorParams,
+    /// Overlays of the pane, owned by the pane settings
+    moving_averages: Vec<MovingAverage>,
+    bollinger: BollingerOverlay,
+    divergence: DivergenceOverlay,
+    zoom_lens: ZoomLens,
+    /// Failover venue changes of the pane, owned by the pane settings
+    source_switches: Vec<SourceSwitch>,
+    /// Pane colors drawn instead of the theme's, owned by the pane settings
+    colors: ChartColors,
 }
 
 impl KlineChart {
@@ -231,10 +352,13 @@ impl KlineChart {
         tick_size: f32,
         klines_raw: &[Kline],
         raw_trades: Vec<Trade>,
-        enabled_indicators: &[KlineIndicator],
+        enabled_indicators: &[KlineInstance],
         ticker_info: TickerInfo,
         kind: &KlineChartKind,
+        config: Option<data::chart::kline::Config>,
     ) -> Self {
+        let config = config.unwrap_or_default();
+
         match basis {
             Basis::Time(interval) => {
                 let step = PriceStep::from_f32(tick_size);
@@ -276,14 +400,20 @@ impl KlineChart {
                     ViewConfig {
                         splits: layout.splits,
                         autoscale: Some(Autoscale::FitToVisible),
-                        trading_config: None,
+                        trading_config: layout.trading_config,
                         show_volume_histogram: true,
                         show_price_levels: true,
                         show_market_depth: false,
+                        navigation: layout.navigation,
+                        watermark: layout.watermark,
+                        price_scale: layout.price_scale,
                     },
                     cell_width,
                     cell_height,
                 );
+                chart.forming_bar = config.forming_bar;
+                chart.sessions = config.sessions;
+                chart.liquidation_markers = config.liquidations;
                 chart.base_price_y = base_price_y;
                 chart.latest_x = latest_x;
 
@@ -301,12 +431,15 @@ impl KlineChart {
 
                 let data_source = PlotData::TimeBased(timeseries);
 
-                let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
-                    let mut indi = indicator::kline::make_empty(i);
-                    indi.rebuild_from_source(&data_source);
-                    indicators[i] = Some(indi);
-                }
+                let indicators = enabled_indicators
+                    .iter()
+                    .map(|instance| {
+                        IndicatorSlot::new(*instance, &config.indicator_params, &data_source)
+                    })
+                    .collect();
+
+                let bollinger = BollingerOverlay::new(config.bollinger, &data_source);
+                let divergence = DivergenceOverlay::new(config.delta_divergence, &data_source);
 
                 KlineChart {
                     chart,
@@ -322,9 +455,19 @@ impl KlineChart {
                     footprint_config: FootprintConfig::default(),
                     rejection_zones: Vec::new(),
                     large_orders: Vec::new(),
+                    support_resistance: Vec::new(),
+                    alerts: Vec::new(),
+                    paper: Overlay::default(),
+                    indicator_params: config.indicator_params,
+                    moving_averages: Vec::new(),
+                    bollinger,
+                    divergence,
+                    zoom_lens: config.zoom_lens,
+                    source_switches: Vec::new(),
+                    colors: ChartColors::default(),
                 }
             }
-            Basis::Tick(interval) => {
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                 let step = PriceStep::from_f32(tick_size);
 
                 let cell_width = match kind {
@@ -344,14 +487,20 @@ impl KlineChart {
                     ViewConfig {
                         splits: layout.splits,
                         autoscale: Some(Autoscale::FitToVisible),
-                        trading_config: None,
+                        trading_config: layout.trading_config,
                         show_volume_histogram: true,
                         show_price_levels: true,
                         show_market_depth: false,
+                        navigation: layout.navigation,
+                        watermark: layout.watermark,
+                        price_scale: layout.price_scale,
                     },
                     cell_width,
                     cell_height,
                 );
+                chart.forming_bar = config.forming_bar;
+                chart.sessions = config.sessions;
+                chart.liquidation_markers = config.liquidations;
 
                 let x_translation = match &kind {
                     KlineChartKind::Footprint { .. } => {
@@ -365,14 +514,28 @@ impl KlineChart {
                 };
                 chart.translation.x = x_translation;
 
-                let data_source = PlotData::TickBased(TickAggr::new(interval, step, &raw_trades));
+                let data_source = match basis {
+                    Basis::Volume(volume) => {
+                        PlotData::VolumeBased(VolumeAggr::new(volume, step, &raw_trades))
+                    }
+                    Basis::Range(range) => {
+                        PlotData::RangeBased(RangeAggr::new(range, step, &raw_trades))
+                    }
+                    Basis::Tick(interval) => {
+                        PlotData::TickBased(TickAggr::new(interval, step, &raw_trades))
+                    }
+                    Basis::Time(_) => unreachable!(),
+                };
 
-                let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
-                    let mut indi = indicator::kline::make_empty(i);
-                    indi.rebuild_from_source(&data_source);
-                    indicators[i] = Some(indi);
-                }
+                let indicators = enabled_indicators
+                    .iter()
+                    .map(|instance| {
+                        IndicatorSlot::new(*instance, &config.indicator_params, &data_source)
+                    })
+                    .collect();
+
+                let bollinger = BollingerOverlay::new(config.bollinger, &data_source);
+                let divergence = DivergenceOverlay::new(config.delta_divergence, &data_source);
 
                 KlineChart {
                     chart,
@@ -388,9 +551,18 @@ impl KlineChart {
                     footprint_config: FootprintConfig::default(),
                     rejection_zones: Vec::new(),
                     large_orders: Vec::new(),
+                    support_resistance: Vec::new(),
+                    alerts: Vec::new(),
+                    paper: Overlay::default(),
+                    indicator_params: config.indicator_params,
+                    moving_averages: Vec::new(),
+                    bollinger,
+                    divergence,
+                    zoom_lens: config.zoom_lens,
+                    source_switches: Vec::new(),
+                    colors: ChartColors::default(),
                 }
             }
-            _ => unimplemented!(),
         }
     }
 
@@ -417,16 +589,17 @@ impl KlineChart {
                     );
                 }
             }
-            PlotData::TickBased(tick_aggr) => {
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
                 let earliest = visible_earliest as usize;
                 let latest = visible_latest as usize;
 
-                for (index, dp) in tick_aggr
-                    .datapoints
-                    .iter()
-                    .enumerate()
-                    .filter(|(index, _)| *index >= earliest && *index <= latest)
-                {
+                // trade-based bars are addressed from the latest one backwards
+                for (index, dp) in datapoints.iter().rev().enumerate() {
+                    if index < earliest || index > latest {
+                        continue;
+                    }
                     self.analyze_datapoint_for_rejection(
                         index as u64,
                         &dp.kline,
@@ -481,13 +654,6 @@ impl KlineChart {
                 });
             }
         }
-
-        // Detect large orders
-        if self.footprint_config.highlight_large_orders {
-            // This requires mutable access which we can't do in this immutable loop
-            // We'll handle large orders in a separate pass or change architecture later
-            // For now, let's just note we'd need to collect them here
-        }
     }
 
     // NEW: Calculate rejection strength with multiple factors
@@ -530,6 +696,89 @@ impl KlineChart {
         strength.min(1.0)
     }
 
+    fn detect_overlays(&mut self, visible_earliest: u64, visible_latest: u64) {
+        let Some(config) = self
+            .chart
+            .layout
+            .trading_config
+            .as_ref()
+            .filter(|cfg| cfg.auto_detect_support_resistance || cfg.show_large_orders)
+        else {
+            self.support_resistance.clear();
+            self.large_orders.clear();
+            return;
+        };
+
+        // only levels and large orders are drawn, skip the cluster pass
+        let config = TradingConfig {
+            show_volume_profile: false,
+            show_delta_profile: false,
+            ..config.clone()
+        };
+
+        let analysis =
+            self.data_source
+                .analyze_market_structure(visible_earliest, visible_latest, &config);
+
+        let mut large_orders = analysis.large_orders;
+        large_orders.sort_by(|a, b| b.volume.total_cmp(&a.volume));
+        large_orders.truncate(MAX_LARGE_ORDERS);
+
+        self.support_resistance = analysis.support_resistance;
+        self.large_orders = large_orders;
+    }
+
+    pub fn trading_config(&self) -> Option<&TradingConfig> {
+        self.chart.layout.trading_config.as_ref()
+    }
+
+    pub fn set_trading_config(&mut self, config: Option<TradingConfig>) {
+        self.chart.layout.trading_config = config;
+        self.invalidate(None);
+    }
+
+    /// Overlays stay off when a layout never had a trading config
+    fn trading_config_mut(&mut self) -> &mut TradingConfig {
+        self.chart
+            .layout
+            .trading_config
+            .get_or_insert_with(|| TradingConfig {
+                auto_detect_support_resistance: false,
+                show_large_orders: false,
+                ..TradingConfig::default()
+            })
+    }
+
+    pub fn shows_large_orders(&self) -> bool {
+        self.trading_config()
+            .is_some_and(|cfg| cfg.show_large_orders)
+    }
+
+    pub fn toggle_large_orders(&mut self) {
+        let enabled = !self.shows_large_orders();
+        self.trading_config_mut().show_large_orders = enabled;
+
+        self.invalidate(None);
+    }
+
+    pub fn set_large_order_threshold(&mut self, threshold: f32) {
+        self.trading_config_mut().volume_threshold = threshold;
+
+        self.invalidate(None);
+    }
+
+    pub fn shows_support_resistance(&self) -> bool {
+        self.trading_config()
+            .is_some_and(|cfg| cfg.auto_detect_support_resistance)
+    }
+
+    pub fn toggle_support_resistance(&mut self) {
+        let enabled = !self.shows_support_resistance();
+        self.trading_config_mut().auto_detect_support_resistance = enabled;
+
+        self.invalidate(None);
+    }
+
     // NEW: Update footprint configuration
     pub fn update_footprint_config(&mut self, config: FootprintConfig) {
         self.footprint_config = config;
@@ -542,10 +791,13 @@ impl KlineChart {
                 timeseries.insert_klines(&[*kline]);
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
+                    .iter_mut()
+                    .map(|slot| &mut slot.indicator)
                     .for_each(|indi| indi.on_insert_klines(&[*kline]));
 
+                self.bollinger.update_from(&self.data_source, kline.time);
+                self.divergence.update_from(&self.data_source, kline.time);
+
                 let chart = self.mut_state();
 
                 if (kline.time) > chart.latest_x {
@@ -554,7 +806,7 @@ impl KlineChart {
 
                 chart.last_price = Some(PriceInfoLabel::new(kline.close, kline.open));
             }
-            PlotData::TickBased(_) => {}
+            PlotData::TickBased(_) | PlotData::VolumeBased(_) | PlotData::RangeBased(_) => {}
         }
     }
 
@@ -562,13 +814,36 @@ impl KlineChart {
         &self.kind
     }
 
+    /// Requests bars missed while the stream was down since `disconnected_at`, stopping
+    /// short of the forming bar so the live stream keeps owning it
+    pub fn backfill_since(&mut self, disconnected_at: u64) -> Option<Action> {
+        let PlotData::TimeBased(timeseries) = &self.data_source else {
+            return None;
+        };
+        if timeseries.datapoints.is_empty() {
+            return None;
+        }
+
+        let interval = timeseries.interval.to_milliseconds();
+        let now = exchange::clock::now_ms(self.chart.ticker_info.exchange());
+        let forming_bar = now - now % interval;
+        // the bar the outage started in only got part of its trades
+        let first_bar = disconnected_at - disconnected_at % interval;
+
+        let missing = timeseries.check_kline_integrity(first_bar, forming_bar, interval)?;
+        let last_missing = *missing.iter().max()?;
+
+        let range = FetchRange::Kline(first_bar, last_missing + interval - 1);
+        request_fetch(&mut self.request_handler, range)
+    }
+
     fn missing_data_task(&mut self) -> Option<Action> {
         match &self.data_source {
             PlotData::TimeBased(timeseries) => {
                 let timeframe_ms = timeseries.interval.to_milliseconds();
 
                 if timeseries.datapoints.is_empty() {
-                    let latest = chrono::Utc::now().timestamp_millis() as u64;
+                    let latest = exchange::clock::now_ms(self.chart.ticker_info.exchange());
                     let earliest = latest.saturating_sub(450 * timeframe_ms);
 
                     let range = FetchRange::Kline(earliest, latest);
@@ -592,7 +867,9 @@ impl KlineChart {
 
                 // priority 2, trades fetch
                 if !self.fetching_trades.0
-                    && exchange::fetcher::is_trade_fetch_enabled()
+                    && exchange::fetcher::is_trade_fetch_enabled(ExchangeInclusive::of(
+                        self.chart.ticker_info.exchange(),
+                    ))
                     && let Some((fetch_from, fetch_to)) =
                         timeseries.suggest_trade_fetch_range(visible_earliest, visible_latest)
                 {
@@ -611,7 +888,7 @@ impl KlineChart {
                     kline_latest,
                     prefetch_earliest: earliest,
                 };
-                for indi in self.indicators.values_mut().filter_map(Option::as_mut) {
+                for indi in self.indicators.iter_mut().map(|slot| &mut slot.indicator) {
                     if let Some(range) = indi.fetch_range(&ctx)
                         && let Some(action) = request_fetch(&mut self.request_handler, range)
                     {
@@ -634,7 +911,7 @@ impl KlineChart {
                     }
                 }
             }
-            PlotData::TickBased(_) => {
+            PlotData::TickBased(_) | PlotData::VolumeBased(_) | PlotData::RangeBased(_) => {
                 // TODO: implement trade fetch
             }
         }
@@ -697,6 +974,296 @@ impl KlineChart {
         self.chart.layout()
     }
 
+    pub fn visual_config(&self) -> data::chart::kline::Config {
+        data::chart::kline::Config {
+            forming_bar: self.chart.forming_bar,
+            sessions: self.chart.sessions,
+            liquidations: self.chart.liquidation_markers,
+            indicator_params: self.indicator_params,
+            bollinger: self.bollinger.config(),
+            zoom_lens: self.zoom_lens,
+            delta_divergence: self.divergence.config(),
+        }
+    }
+
+    /// Liquidations are only placed on time-based footprints, other bases have no time axis
+    pub fn insert_liquidation(&mut self, liquidation: exchange::Liquidation) {
+        if matches!(self.kind, KlineChartKind::Footprint { .. }) && self.chart.basis.is_time() {
+            self.chart.insert_liquidation(liquidation);
+            self.invalidate(None);
+        }
+    }
+
+    pub fn set_source_switches(&mut self, switches: &[SourceSwitch]) {
+        self.source_switches = switches.to_vec();
+        self.invalidate(None);
+    }
+
+    pub fn set_alerts(&mut self, alerts: &data::chart::alert::Alerts) {
+        self.alerts = alerts
+            .for_ticker(self.chart.ticker_info.ticker)
+            .cloned()
+            .collect();
+        self.invalidate(None);
+    }
+
+    pub fn set_paper_overlay(&mut self, overlay: Overlay) {
+        if self.paper != overlay {
+            self.paper = overlay;
+            self.invalidate(None);
+        }
+    }
+
+    pub fn set_moving_averages(&mut self, moving_averages: &[MovingAverage]) {
+        self.moving_averages = moving_averages.to_vec();
+        self.invalidate(None);
+    }
+
+    pub fn set_colors(&mut self, colors: ChartColors) {
+        self.colors = colors;
+        self.invalidate(None);
+    }
+
+    pub fn set_drawings(&mut self, drawings: Vec<Drawing>, fib_levels: &[f32]) {
+        self.chart.drawings = drawings;
+        self.chart.fib_levels = fib_levels.to_vec();
+        self.invalidate(None);
+    }
+
+    /// Moves `anchor` onto the closest OHLC price of its bar, if one is within `OHLC_SNAP_PX`
+    pub fn snap_to_ohlc(&self, anchor: Anchor) -> Anchor {
+        let Some(kline) = self.kline_at(anchor.interval) else {
+            return anchor;
+        };
+
+        let chart = &self.chart;
+        let y = chart.price_to_y(anchor.price);
+
+        [kline.open, kline.high, kline.low, kline.close]
+            .into_iter()
+            .map(|price| (price, (chart.price_to_y(price) - y).abs() * chart.scaling))
+            .filter(|(_, distance)| *distance <= OHLC_SNAP_PX)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(anchor, |(price, _)| Anchor { price, ..anchor })
+    }
+
+    fn kline_at(&self, bar: u64) -> Option<&Kline> {
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => timeseries.datapoints.get(&bar).map(|dp| &dp.kline),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let idx = datapoints.len().checked_sub(1 + bar as usize)?;
+                datapoints.get(idx).map(|dp| &dp.kline)
+            }
+        }
+    }
+
+    pub fn drawing_tool(&self) -> Option<DrawingTool> {
+        self.chart.drawing_tool
+    }
+
+    pub fn set_drawing_tool(&mut self, tool: Option<DrawingTool>) {
+        self.chart.drawing_tool = tool;
+    }
+
+    /// Scrolls the price axis so `price` sits in the middle of the chart
+    pub fn center_on_price(&mut self, price: f32) {
+        let chart = self.mut_state();
+
+        chart.translation.y = -chart.price_to_y(Price::from_f32(price));
+        chart.layout.autoscale = None;
+
+        self.invalidate(None);
+    }
+
+    /// Scrolls the time axis so the bar holding `time` sits in the middle of the chart
+    pub fn center_on_time(&mut self, time: u64) {
+        let bar = match &self.data_source {
+            PlotData::TimeBased(timeseries) => {
+                let interval = timeseries.interval.to_milliseconds();
+                Some(time / interval * interval)
+            }
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+                .iter()
+                .rposition(|dp| dp.kline.time <= time)
+                .map(|idx| (datapoints.len() - 1 - idx) as u64),
+        };
+
+        if let Some(bar) = bar {
+            let chart = self.mut_state();
+            chart.translation.x = -chart.interval_to_x(bar);
+
+            if chart.layout.autoscale == Some(Autoscale::CenterLatest) {
+                chart.layout.autoscale = None;
+            }
+
+            self.invalidate(None);
+        }
+    }
+
+    pub fn time_window(&self) -> Option<(u64, u64)> {
+        self.chart.time_window()
+    }
+
+    /// Follows the time window panned or zoomed to on a linked pane
+    pub fn sync_time_window(&mut self, window: (u64, u64)) {
+        let cell_widths = (self.min_cell_width(), self.max_cell_width());
+
+        if self.chart.show_time_window(window, cell_widths) {
+            self.invalidate(None);
+        }
+    }
+
+    /// Shows a crosshair at the bar holding `time`, hovered on a linked pane
+    pub fn sync_crosshair(&mut self, time: Option<u64>) {
+        let interval = time.and_then(|time| match &self.data_source {
+            PlotData::TimeBased(timeseries) => {
+                let interval = timeseries.interval.to_milliseconds();
+                Some(time - time % interval)
+            }
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let idx = datapoints.iter().rposition(|dp| dp.kline.time <= time)?;
+                Some((datapoints.len() - 1 - idx) as u64)
+            }
+        });
+
+        self.chart.set_synced_crosshair(interval);
+    }
+
+    /// Time span of the trades that make up `bar`, as the bars are keyed on the x axis
+    pub fn bar_time_range(&self, bar: u64) -> Option<(u64, u64)> {
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .contains_key(&bar)
+                .then(|| (bar, bar + timeseries.interval.to_milliseconds())),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let idx = datapoints.len().checked_sub(1 + bar as usize)?;
+                let end = datapoints
+                    .get(idx + 1)
+                    .map_or(u64::MAX, |next| next.kline.time);
+
+                Some((datapoints[idx].kline.time, end))
+            }
+        }
+    }
+
+    pub fn last_price(&self) -> Option<f32> {
+        self.chart.last_price.map(|label| match label {
+            PriceInfoLabel::Up(p) | PriceInfoLabel::Down(p) | PriceInfoLabel::Neutral(p) => {
+                p.to_f32()
+            }
+        })
+    }
+
+    /// Last `bars` closes, cumulative delta and open interest, for composite alerts
+    pub fn market_state(&self, bars: usize) -> MarketState {
+        let klines: Vec<&Kline> = match &self.data_source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .values()
+                .rev()
+                .take(bars)
+                .map(|dp| &dp.kline)
+                .collect(),
+            PlotData::TickBased(tick_aggr) => tick_aggr
+                .datapoints
+                .iter()
+                .rev()
+                .take(bars)
+                .map(|dp| &dp.kline)
+                .collect(),
+            PlotData::VolumeBased(volume_aggr) => volume_aggr
+                .datapoints
+                .iter()
+                .rev()
+                .take(bars)
+                .map(|dp| &dp.kline)
+                .collect(),
+            PlotData::RangeBased(range_aggr) => range_aggr
+                .datapoints
+                .iter()
+                .rev()
+                .take(bars)
+                .map(|dp| &dp.kline)
+                .collect(),
+        };
+
+        let mut cvd = 0.0;
+        let (closes, cvd) = klines
+            .iter()
+            .rev()
+            .map(|k| {
+                cvd += k.volume.0 - k.volume.1;
+                (k.close.to_f32(), cvd)
+            })
+            .unzip();
+
+        let open_interest = self
+            .indicators
+            .iter()
+            .find(|slot| slot.kind == KlineIndicator::OpenInterest)
+            .map(|slot| slot.indicator.recent_values(bars))
+            .unwrap_or_default();
+
+        MarketState {
+            closes,
+            cvd,
+            open_interest,
+        }
+    }
+
+    /// Bars and traded volume between `from..to`, only time-based charts are exported
+    pub fn daily_snapshot(&self, from: u64, to: u64) -> Option<DailySnapshot> {
+        let PlotData::TimeBased(timeseries) = &self.data_source else {
+            return None;
+        };
+
+        let ticker = self.chart.ticker_info.ticker;
+        let (symbol, _) = ticker.to_full_symbol_and_type();
+
+        DailySnapshot::from_timeseries(
+            format!("{}_{symbol}", ticker.exchange),
+            timeseries,
+            from,
+            to,
+        )
+    }
+
+    pub fn set_visual_config(&mut self, config: data::chart::kline::Config) {
+        self.chart.forming_bar = config.forming_bar;
+        self.chart.sessions = config.sessions;
+        self.chart.liquidation_markers = config.liquidations;
+
+        if self.indicator_params != config.indicator_params {
+            self.indicator_params = config.indicator_params;
+
+            for slot in self
+                .indicators
+                .iter_mut()
+                .filter(|slot| slot.params.is_none())
+            {
+                slot.indicator
+                    .on_params_change(&self.indicator_params, &self.data_source);
+            }
+        }
+
+        self.bollinger
+            .set_config(config.bollinger, &self.data_source);
+        self.divergence
+            .set_config(config.delta_divergence, &self.data_source);
+        self.zoom_lens = config.zoom_lens;
+
+        self.invalidate(None);
+    }
+
     pub fn set_cluster_kind(&mut self, new_kind: ClusterKind) {
         if let KlineChartKind::Footprint {
             ref mut clusters, ..
@@ -735,16 +1302,25 @@ impl KlineChart {
             PlotData::TickBased(ref mut tick_aggr) => {
                 tick_aggr.change_tick_size(new_tick_size, &self.raw_trades);
             }
+            PlotData::VolumeBased(ref mut volume_aggr) => {
+                volume_aggr.change_tick_size(new_tick_size, &self.raw_trades);
+            }
+            PlotData::RangeBased(ref mut range_aggr) => {
+                range_aggr.change_tick_size(new_tick_size, &self.raw_trades);
+            }
             PlotData::TimeBased(ref mut timeseries) => {
                 timeseries.change_tick_size(new_tick_size, &self.raw_trades);
             }
         }
 
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
+            .iter_mut()
+            .map(|slot| &mut slot.indicator)
             .for_each(|indi| indi.on_ticksize_change(&self.data_source));
 
+        self.bollinger.rebuild(&self.data_source);
+        self.divergence.rebuild(&self.data_source);
+
         self.invalidate(None);
     }
 
@@ -763,14 +1339,26 @@ impl KlineChart {
                 let tick_aggr = TickAggr::new(tick_count, step, &self.raw_trades);
                 self.data_source = PlotData::TickBased(tick_aggr);
             }
-            Basis::Volume(_) | Basis::Range(_) => todo!(),
+            Basis::Volume(volume) => {
+                let step = self.chart.tick_size;
+                let volume_aggr = VolumeAggr::new(volume, step, &self.raw_trades);
+                self.data_source = PlotData::VolumeBased(volume_aggr);
+            }
+            Basis::Range(range) => {
+                let step = self.chart.tick_size;
+                let range_aggr = RangeAggr::new(range, step, &self.raw_trades);
+                self.data_source = PlotData::RangeBased(range_aggr);
+            }
         }
 
         self.indicators
-            .values_mut()
-            .filter_map(Option::as_mut)
+            .iter_mut()
+            .map(|slot| &mut slot.indicator)
             .for_each(|indi| indi.on_basis_change(&self.data_source));
 
+        self.bollinger.rebuild(&self.data_source);
+        self.divergence.rebuild(&self.data_source);
+
         self.reset_request_handler();
         self.invalidate(Some(Instant::now()))
     }
@@ -797,24 +1385,41 @@ impl KlineChart {
         self.raw_trades.extend_from_slice(trades_buffer);
 
         match self.data_source {
-            PlotData::TickBased(ref mut tick_aggr) => {
-                let old_dp_len = tick_aggr.datapoints.len();
-                tick_aggr.insert_trades(trades_buffer);
+            PlotData::TickBased(_) | PlotData::VolumeBased(_) | PlotData::RangeBased(_) => {
+                let (old_dp_len, last_kline) = match self.data_source {
+                    PlotData::TickBased(ref mut tick_aggr) => {
+                        let old_dp_len = tick_aggr.datapoints.len();
+                        tick_aggr.insert_trades(trades_buffer);
+                        (old_dp_len, tick_aggr.datapoints.last().map(|dp| dp.kline))
+                    }
+                    PlotData::VolumeBased(ref mut volume_aggr) => {
+                        let old_dp_len = volume_aggr.datapoints.len();
+                        volume_aggr.insert_trades(trades_buffer);
+                        (old_dp_len, volume_aggr.datapoints.last().map(|dp| dp.kline))
+                    }
+                    PlotData::RangeBased(ref mut range_aggr) => {
+                        let old_dp_len = range_aggr.datapoints.len();
+                        range_aggr.insert_trades(trades_buffer);
+                        (old_dp_len, range_aggr.datapoints.last().map(|dp| dp.kline))
+                    }
+                    PlotData::TimeBased(_) => unreachable!(),
+                };
 
-                if let Some(last_dp) = tick_aggr.datapoints.last() {
-                    self.chart.last_price =
-                        Some(PriceInfoLabel::new(last_dp.kline.close, last_dp.kline.open));
-                } else {
-                    self.chart.last_price = None;
-                }
+                self.chart.last_price =
+                    last_kline.map(|kline| PriceInfoLabel::new(kline.close, kline.open));
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
+                    .iter_mut()
+                    .map(|slot| &mut slot.indicator)
                     .for_each(|indi| {
                         indi.on_insert_trades(trades_buffer, old_dp_len, &self.data_source)
                     });
 
+                self.bollinger
+                    .update_from(&self.data_source, old_dp_len.saturating_sub(1) as u64);
+                self.divergence
+                    .update_from(&self.data_source, old_dp_len.saturating_sub(1) as u64);
+
                 self.invalidate(None);
             }
             PlotData::TimeBased(ref mut timeseries) => {
@@ -828,12 +1433,20 @@ impl KlineChart {
             PlotData::TickBased(ref mut tick_aggr) => {
                 tick_aggr.insert_trades(&raw_trades);
             }
+            PlotData::VolumeBased(ref mut volume_aggr) => {
+                volume_aggr.insert_trades(&raw_trades);
+            }
+            PlotData::RangeBased(ref mut range_aggr) => {
+                range_aggr.insert_trades(&raw_trades);
+            }
             PlotData::TimeBased(ref mut timeseries) => {
                 timeseries.insert_trades_existing_buckets(&raw_trades);
             }
         }
 
         self.raw_trades.extend(raw_trades);
+        self.bollinger.rebuild(&self.data_source);
+        self.divergence.rebuild(&self.data_source);
 
         if is_batches_done {
             self.fetching_trades = (false, None);
@@ -847,10 +1460,13 @@ impl KlineChart {
                 timeseries.insert_trades_existing_buckets(&self.raw_trades);
 
                 self.indicators
-                    .values_mut()
-                    .filter_map(Option::as_mut)
+                    .iter_mut()
+                    .map(|slot| &mut slot.indicator)
                     .for_each(|indi| indi.on_insert_klines(klines_raw));
 
+                self.bollinger.rebuild(&self.data_source);
+                self.divergence.rebuild(&self.data_source);
+
                 if klines_raw.is_empty() {
                     self.request_handler
                         .mark_failed(req_id, "No data received".to_string());
@@ -859,7 +1475,7 @@ impl KlineChart {
                 }
                 self.invalidate(None);
             }
-            PlotData::TickBased(_) => {}
+            PlotData::TickBased(_) | PlotData::VolumeBased(_) | PlotData::RangeBased(_) => {}
         }
     }
 
@@ -873,23 +1489,170 @@ impl KlineChart {
             }
         }
 
-        if let Some(indi) = self.indicators[KlineIndicator::OpenInterest].as_mut() {
-            indi.on_open_interest(oi_data);
+        self.indicators
+            .iter_mut()
+            .filter(|slot| slot.kind == KlineIndicator::OpenInterest)
+            .for_each(|slot| slot.indicator.on_open_interest(oi_data));
+    }
+
+    pub fn insert_aggregated_oi(
+        &mut self,
+        req_id: Option<uuid::Uuid>,
+        oi_data: &[exchange::composite::AggregatedOpenInterest],
+    ) {
+        if let Some(req_id) = req_id {
+            if oi_data.is_empty() {
+                self.request_handler
+                    .mark_failed(req_id, "No data received".to_string());
+            } else {
+                self.request_handler.mark_completed(req_id);
+            }
         }
+
+        self.indicators
+            .iter_mut()
+            .filter(|slot| slot.kind == KlineIndicator::AggregatedOpenInterest)
+            .for_each(|slot| slot.indicator.on_aggregated_open_interest(oi_data));
     }
 
-    fn calc_qty_scales(
-        &self,
-        earliest: u64,
-        latest: u64,
-        highest: Price,
-        lowest: Price,
-        step: PriceStep,
-        cluster_kind: ClusterKind,
-    ) -> f32 {
-        let rounded_highest = highest.round_to_side_step(false, step).add_steps(1, step);
+    /// Intervals to draw within `region`, leaving out a hidden forming bar
+    fn drawn_interval_range(&self, region: &Rectangle) -> (u64, u64) {
+        let chart = self.state();
+        let (earliest, latest) = chart.interval_range(region);
 
-        let rounded_lowest = lowest.round_to_side_step(true, step).add_steps(-1, step);
+        match chart
+            .forming_interval()
+            .filter(|_| chart.forming_bar == FormingBar::Hidden)
+        {
+            Some(forming) if chart.basis.is_time() => {
+                (earliest, latest.min(forming.saturating_sub(1)))
+            }
+            Some(forming) => (earliest.max(forming + 1), latest),
+            None => (earliest, latest),
+        }
+    }
+
+    /// Footprint cells around `cursor` redrawn magnified in an inset beside it
+    fn draw_zoom_lens(
+        &self,
+        frame: &mut canvas::Frame,
+        palette: &Extended,
+        bounds: Size,
+        cursor: Point,
+    ) {
+        let KlineChartKind::Footprint {
+            clusters,
+            scaling,
+            studies,
+        } = &self.kind
+        else {
+            return;
+        };
+        let chart = self.state();
+
+        let lens_scaling = chart.scaling * self.zoom_lens.magnification;
+        let width =
+            (ZoomLens::BARS * chart.cell_width * lens_scaling).min(bounds.width * LENS_MAX_SHARE);
+        let height = (width * 0.75).min(bounds.height * LENS_MAX_SHARE);
+        if width < 1.0 || height < 1.0 {
+            return;
+        }
+
+        // beside the cursor so the cells it magnifies stay in sight
+        let x = if cursor.x + LENS_OFFSET + width <= bounds.width {
+            cursor.x + LENS_OFFSET
+        } else {
+            (cursor.x - LENS_OFFSET - width).max(0.0)
+        };
+        let y = (cursor.y - height / 2.0).clamp(0.0, (bounds.height - height).max(0.0));
+        let lens = Rectangle {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        let region = chart.visible_region(bounds);
+        let focus = Point::new(
+            region.x + cursor.x / bounds.width * region.width,
+            region.y + cursor.y / bounds.height * region.height,
+        );
+        let lens_region = Rectangle {
+            x: focus.x - width / lens_scaling / 2.0,
+            y: focus.y - height / lens_scaling / 2.0,
+            width: width / lens_scaling,
+            height: height / lens_scaling,
+        };
+
+        let (earliest, latest) = self.drawn_interval_range(&lens_region);
+        let (highest, lowest) = chart.price_range(&lens_region);
+        let max_cluster_qty = self.calc_qty_scales(
+            earliest,
+            latest,
+            highest,
+            lowest,
+            chart.tick_size,
+            *clusters,
+        );
+        let cells = CellLayout::new(chart, lens_scaling, *clusters, studies);
+
+        frame.fill_rectangle(lens.position(), lens.size(), palette.background.base.color);
+
+        frame.with_clip(lens, |frame| {
+            frame.translate(Vector::new(width / 2.0, height / 2.0));
+            frame.scale(lens_scaling);
+            frame.translate(Vector::new(-focus.x, -focus.y));
+
+            render_data_source(
+                &self.data_source,
+                frame,
+                earliest,
+                latest,
+                |interval| chart.interval_to_x(interval),
+                |frame, x_position, kline, trades| {
+                    draw_clusters(
+                        frame,
+                        |price| chart.price_to_y(price),
+                        x_position,
+                        chart.cell_width,
+                        chart.cell_height,
+                        cells.candle_width,
+                        effective_cluster_qty(*scaling, max_cluster_qty, trades, *clusters),
+                        palette,
+                        cells.text_size,
+                        self.tick_size(),
+                        cells.show_text,
+                        cells.imbalance,
+                        kline,
+                        trades,
+                        *clusters,
+                        cells.content_spacing,
+                        lens_scaling,
+                    );
+                },
+            );
+        });
+
+        frame.stroke(
+            &Path::rectangle(lens.position(), lens.size()),
+            Stroke::default()
+                .with_color(palette.background.strong.color)
+                .with_width(1.0),
+        );
+    }
+
+    fn calc_qty_scales(
+        &self,
+        earliest: u64,
+        latest: u64,
+        highest: Price,
+        lowest: Price,
+        step: PriceStep,
+        cluster_kind: ClusterKind,
+    ) -> f32 {
+        let rounded_highest = highest.round_to_side_step(false, step).add_steps(1, step);
+
+        let rounded_lowest = lowest.round_to_side_step(true, step).add_steps(-1, step);
 
         match &self.data_source {
             PlotData::TimeBased(timeseries) => timeseries.max_qty_ts_range(
@@ -911,6 +1674,20 @@ impl KlineChart {
                     rounded_lowest,
                 )
             }
+            PlotData::VolumeBased(volume_aggr) => volume_aggr.max_qty_idx_range(
+                cluster_kind,
+                earliest as usize,
+                latest as usize,
+                rounded_highest,
+                rounded_lowest,
+            ),
+            PlotData::RangeBased(range_aggr) => range_aggr.max_qty_idx_range(
+                cluster_kind,
+                earliest as usize,
+                latest as usize,
+                rounded_highest,
+                rounded_lowest,
+            ),
         }
     }
 
@@ -922,6 +1699,7 @@ impl KlineChart {
         // NEW: Detect rejection zones before rendering
         if let Some((visible_earliest, visible_latest)) = self.visible_timerange() {
             self.detect_rejection_zones(visible_earliest, visible_latest);
+            self.detect_overlays(visible_earliest, visible_latest);
         }
 
         let chart = &mut self.chart;
@@ -981,13 +1759,21 @@ impl KlineChart {
                         let price_span = (highest - lowest) + (2.0 * padding);
 
                         if price_span > 0.0 && chart.bounds.height > f32::EPSILON {
-                            let padded_highest = highest + padding;
+                            let price_scale = chart.layout.price_scale;
                             let chart_height = chart.bounds.height;
                             let tick_size = chart.tick_size.to_f32_lossy();
 
-                            if tick_size > 0.0 {
-                                chart.cell_height = (chart_height * tick_size) / price_span;
-                                chart.base_price_y = Price::from_f32(padded_highest);
+                            // price at the top edge becomes the base, at y = 0
+                            let (top, bottom) = if price_scale.inverted {
+                                (lowest - padding, highest + padding)
+                            } else {
+                                (highest + padding, lowest - padding)
+                            };
+                            let span_ticks = price_scale.ticks_below(top, bottom, tick_size).abs();
+
+                            if tick_size > 0.0 && span_ticks > 0.0 {
+                                chart.cell_height = chart_height / span_ticks;
+                                chart.base_price_y = Price::from_f32(top);
                                 chart.translation.y = -chart_height / 2.0;
                             }
                         }
@@ -998,8 +1784,8 @@ impl KlineChart {
         }
 
         chart.cache.clear_all();
-        for indi in self.indicators.values_mut().filter_map(Option::as_mut) {
-            indi.clear_all_caches();
+        for slot in &mut self.indicators {
+            slot.indicator.clear_all_caches();
         }
 
         if let Some(t) = now {
@@ -1010,22 +1796,47 @@ impl KlineChart {
         }
     }
 
-    pub fn toggle_indicator(&mut self, indicator: KlineIndicator) {
-        let prev_indi_count = self.indicators.values().filter(|v| v.is_some()).count();
+    pub fn add_indicator(&mut self, instance: KlineInstance) {
+        let prev_indi_count = self.indicators.len();
 
-        if self.indicators[indicator].is_some() {
-            self.indicators[indicator] = None;
-        } else {
-            let mut box_indi = indicator::kline::make_empty(indicator);
-            box_indi.rebuild_from_source(&self.data_source);
-            self.indicators[indicator] = Some(box_indi);
+        self.indicators.push(IndicatorSlot::new(
+            instance,
+            &self.indicator_params,
+            &self.data_source,
+        ));
+        self.resplit_panels(prev_indi_count);
+    }
+
+    pub fn remove_indicator(&mut self, id: u16) {
+        let prev_indi_count = self.indicators.len();
+
+        self.indicators.retain(|slot| slot.id != id);
+        if self.indicators.len() != prev_indi_count {
+            self.resplit_panels(prev_indi_count);
         }
+    }
 
+    /// Own inputs of one instance, `None` follows the chart's defaults again
+    pub fn set_indicator_params(&mut self, id: u16, params: Option<IndicatorParams>) {
+        let Some(slot) = self.indicators.iter_mut().find(|slot| slot.id == id) else {
+            return;
+        };
+        if slot.params == params {
+            return;
+        }
+
+        slot.params = params;
+        slot.indicator.on_params_change(
+            params.as_ref().unwrap_or(&self.indicator_params),
+            &self.data_source,
+        );
+    }
+
+    fn resplit_panels(&mut self, prev_indi_count: usize) {
         if let Some(main_split) = self.chart.layout.splits.first() {
-            let current_indi_count = self.indicators.values().filter(|v| v.is_some()).count();
             self.chart.layout.splits = data::util::calc_panel_splits(
                 *main_split,
-                current_indi_count,
+                self.indicators.len(),
                 Some(prev_indi_count),
             );
         }
@@ -1060,17 +1871,24 @@ impl canvas::Program<Message> for KlineChart {
         }
 
         let bounds_size = bounds.size();
-        let palette = theme.extended_palette();
+        let palette = super::overridden_palette(theme, &self.colors);
+        let palette = palette.as_ref();
+        let wick_color = self.colors.wick;
 
         let klines = chart.cache.main.draw(renderer, bounds_size, |frame| {
+            chart.draw_watermark(frame, palette);
+
             let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
 
+            frame.push_transform();
             frame.translate(center);
             frame.scale(chart.scaling);
             frame.translate(chart.translation);
 
             let region = chart.visible_region(frame.size());
-            let (earliest, latest) = chart.interval_range(&region);
+            let forming = chart.forming_interval();
+
+            let (earliest, latest) = self.drawn_interval_range(&region);
 
             let price_to_y = |price| chart.price_to_y(price);
             let interval_to_x = |interval| chart.interval_to_x(interval);
@@ -1086,16 +1904,27 @@ impl canvas::Program<Message> for KlineChart {
                 );
             }
 
-            if self.footprint_config.highlight_large_orders {
+            if !self.large_orders.is_empty() {
                 draw_large_orders(
                     frame,
                     price_to_y,
+                    interval_to_x,
                     &self.large_orders,
-                    palette,
+                    &theme.palette(),
                     chart.cell_width,
                 );
             }
 
+            if !self.support_resistance.is_empty() {
+                draw_support_resistance(
+                    frame,
+                    price_to_y,
+                    &self.support_resistance,
+                    palette,
+                    region,
+                );
+            }
+
             match &self.kind {
                 KlineChartKind::Footprint {
                     clusters,
@@ -1113,40 +1942,13 @@ impl canvas::Program<Message> for KlineChart {
                         *clusters,
                     );
 
-                    let cell_height_unscaled = chart.cell_height * chart.scaling;
-                    let cell_width_unscaled = chart.cell_width * chart.scaling;
-
-                    let text_size = {
-                        let text_size_from_height = cell_height_unscaled.round().min(16.0) - 3.0;
-                        let text_size_from_width =
-                            (cell_width_unscaled * 0.1).round().min(16.0) - 3.0;
-
-                        text_size_from_height.min(text_size_from_width)
-                    };
-
-                    let candle_width = 0.1 * chart.cell_width;
-                    let content_spacing = ContentGaps::from_view(candle_width, chart.scaling);
-
-                    let imbalance = studies.iter().find_map(|study| {
-                        if let FootprintStudy::Imbalance {
-                            threshold,
-                            color_scale,
-                            ignore_zeros,
-                        } = study
-                        {
-                            Some((*threshold, *color_scale, *ignore_zeros))
-                        } else {
-                            None
-                        }
-                    });
-
-                    let show_text = {
-                        let min_w = match clusters {
-                            ClusterKind::VolumeProfile | ClusterKind::DeltaProfile => 80.0,
-                            ClusterKind::BidAsk => 120.0,
-                        };
-                        should_show_text(cell_height_unscaled, cell_width_unscaled, min_w)
-                    };
+                    let CellLayout {
+                        text_size,
+                        candle_width,
+                        content_spacing,
+                        imbalance,
+                        show_text,
+                    } = CellLayout::new(chart, chart.scaling, *clusters, studies);
 
                     draw_all_npocs(
                         &self.data_source,
@@ -1163,6 +1965,22 @@ impl canvas::Program<Message> for KlineChart {
                         *clusters,
                         content_spacing,
                         imbalance.is_some(),
+                        &chart.sessions,
+                        chart.tick_size,
+                    );
+
+                    draw_stacked_imbalances(
+                        &self.data_source,
+                        frame,
+                        price_to_y,
+                        interval_to_x,
+                        chart.cell_width,
+                        chart.cell_height,
+                        palette,
+                        studies,
+                        self.tick_size(),
+                        earliest,
+                        latest,
                     );
 
                     render_data_source(
@@ -1212,6 +2030,7 @@ impl canvas::Program<Message> for KlineChart {
                                 price_to_y,
                                 candle_width,
                                 palette,
+                                wick_color,
                                 x_position,
                                 kline,
                                 chart.scaling,
@@ -1221,14 +2040,173 @@ impl canvas::Program<Message> for KlineChart {
                 }
             }
 
+            if chart.forming_bar == FormingBar::Faded
+                && let Some(forming) = forming
+                && let Some(kline) = kline_at(&self.data_source, forming)
+            {
+                draw_forming_bar_veil(
+                    frame,
+                    price_to_y,
+                    interval_to_x(forming),
+                    chart.cell_width,
+                    chart.cell_height,
+                    kline,
+                    palette,
+                );
+            }
+
+            self.bollinger.draw(
+                frame,
+                &self.data_source,
+                earliest,
+                latest,
+                price_to_y,
+                interval_to_x,
+                palette.secondary.strong.color,
+            );
+
+            self.divergence.draw(
+                frame,
+                &self.data_source,
+                earliest,
+                latest,
+                price_to_y,
+                interval_to_x,
+                chart.cell_width,
+                palette,
+            );
+
+            if !self.moving_averages.is_empty() {
+                draw_moving_averages(
+                    frame,
+                    &self.data_source,
+                    &self.moving_averages,
+                    earliest,
+                    latest,
+                    price_to_y,
+                    interval_to_x,
+                );
+            }
+
+            if let Basis::Time(timeframe) = chart.basis {
+                let interval = timeframe.to_milliseconds();
+                chart.draw_liquidations(
+                    frame,
+                    palette,
+                    earliest,
+                    latest.saturating_add(interval),
+                    |time| interval_to_x(time - time % interval),
+                );
+
+                let visible = self.source_switches.iter().filter(|switch| {
+                    (earliest..=latest.saturating_add(interval)).contains(&switch.time)
+                });
+                for switch in visible {
+                    let x = interval_to_x(switch.time - switch.time % interval);
+                    draw_source_switch(frame, switch, x, region, palette, chart.scaling);
+                }
+            }
+
+            if !self.alerts.is_empty() {
+                draw_alert_lines(frame, price_to_y, &self.alerts, palette, region);
+            }
+
+            super::drawing::draw_drawings(frame, chart, region, palette);
+
             chart.draw_last_price_line(frame, palette, region);
+
+            frame.pop_transform();
+
+            if let KlineChartKind::Footprint { studies, .. } = &self.kind
+                && let Some(rows) = studies.iter().find_map(|study| match study {
+                    FootprintStudy::BarStats { rows } => Some(*rows),
+                    _ => None,
+                })
+            {
+                draw_bar_stats(
+                    &self.data_source,
+                    frame,
+                    rows,
+                    earliest,
+                    latest,
+                    |interval| (interval_to_x(interval) - region.x) * chart.scaling,
+                    chart.cell_width * chart.scaling,
+                    palette,
+                );
+            }
+
+            if !self.paper.is_empty() {
+                draw_paper_overlay(
+                    frame,
+                    &self.paper,
+                    |price| (price_to_y(Price::from_f32(price)) - region.y) * chart.scaling,
+                    chart.ticker_info.min_ticksize,
+                    palette,
+                );
+            }
         });
 
         let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
+            if cursor.position_in(bounds).is_none() {
+                chart.draw_synced_crosshair(frame, theme, bounds_size);
+            }
+
+            if let Interaction::MovingOrder { price, .. } = interaction {
+                let region = chart.visible_region(bounds_size);
+                let y = (chart.price_to_y(*price) - region.y) * chart.scaling;
+
+                frame.stroke(
+                    &Path::line(Point::new(0.0, y), Point::new(bounds_size.width, y)),
+                    Stroke::with_color(
+                        Stroke {
+                            width: 1.0,
+                            line_dash: canvas::LineDash {
+                                segments: &[4.0, 3.0],
+                                offset: 0,
+                            },
+                            ..Default::default()
+                        },
+                        palette.primary.base.color,
+                    ),
+                );
+            }
+
             if let Some(cursor_position) = cursor.position_in(bounds) {
-                let (_, rounded_aggregation) =
+                if let Interaction::Drawing { tool, start } = interaction {
+                    super::drawing::draw_preview(
+                        frame,
+                        chart,
+                        *tool,
+                        *start,
+                        cursor_position,
+                        bounds_size,
+                        palette,
+                    );
+                }
+
+                let (rounded_price, rounded_aggregation) =
                     chart.draw_crosshair(frame, theme, bounds_size, cursor_position, interaction);
 
+                if let Interaction::Lens = interaction
+                    && self.has_zoom_lens()
+                {
+                    self.draw_zoom_lens(frame, palette, bounds_size, cursor_position);
+                }
+
+                let half_tick = chart.tick_size.to_f32_lossy() / 2.0;
+                if let Some(level) = self
+                    .support_resistance
+                    .iter()
+                    .find(|level| (level.price_level.to_f32() - rounded_price).abs() <= half_tick)
+                {
+                    draw_sr_level_tooltip(frame, palette, cursor_position, level);
+                }
+
+                if let Some(divergence) = self.divergence.at(&self.data_source, rounded_aggregation)
+                {
+                    draw_divergence_tooltip(frame, palette, cursor_position, divergence);
+                }
+
                 draw_crosshair_tooltip(
                     &self.data_source,
                     &chart.ticker_info,
@@ -1238,6 +2216,20 @@ impl canvas::Program<Message> for KlineChart {
                     &self.rejection_zones,
                 );
             }
+
+            if let Some(threshold) = self
+                .trading_config()
+                .filter(|_| !self.large_orders.is_empty())
+                .map(|cfg| cfg.volume_threshold)
+            {
+                draw_large_order_legend(
+                    frame,
+                    palette,
+                    &self.large_orders,
+                    threshold,
+                    chart.cell_width * chart.scaling,
+                );
+            }
         });
 
         vec![klines, crosshair]
@@ -1250,15 +2242,23 @@ impl canvas::Program<Message> for KlineChart {
         cursor: mouse::Cursor,
     ) -> mouse::Interaction {
         match interaction {
-            Interaction::Panning { .. } => mouse::Interaction::Grabbing,
+            Interaction::Panning { .. }
+            | Interaction::MovingAnchor { .. }
+            | Interaction::MovingOrder { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
-                if cursor.is_over(bounds) {
-                    mouse::Interaction::Crosshair
-                } else {
-                    mouse::Interaction::default()
-                }
-            }
+            Interaction::None
+            | Interaction::Ruler { .. }
+            | Interaction::Coasting { .. }
+            | Interaction::Drawing { .. }
+            | Interaction::Lens
+            | Interaction::PlacingOrder { .. } => match cursor.position_in(bounds) {
+                Some(position) => match self.order_handle_at(position, bounds.size()) {
+                    Some(OrderHandle::Line(..)) => mouse::Interaction::ResizingVertically,
+                    Some(OrderHandle::Cancel(_)) => mouse::Interaction::Pointer,
+                    None => mouse::Interaction::Crosshair,
+                },
+                None => mouse::Interaction::default(),
+            },
         }
     }
 }
@@ -1318,6 +2318,7 @@ fn draw_candle_dp(
     price_to_y: impl Fn(Price) -> f32,
     candle_width: f32,
     palette: &Extended,
+    wick_color: Option<iced::Color>,
     x_position: f32,
     kline: &Kline,
     scaling: f32,
@@ -1338,11 +2339,7 @@ fn draw_candle_dp(
         body_color,
     );
 
-    let wick_color = if kline.close >= kline.open {
-        palette.success.base.color
-    } else {
-        palette.danger.base.color
-    };
+    let wick_color = wick_color.unwrap_or(body_color);
 
     let px_width = 1.0 / scaling;
     let wick_width = (candle_width / 6.0).max(px_width);
@@ -1375,20 +2372,21 @@ fn render_data_source<F>(
     F: Fn(&mut canvas::Frame, f32, &Kline, &KlineTrades),
 {
     match data_source {
-        PlotData::TickBased(tick_aggr) => {
+        PlotData::TickBased(TickAggr { datapoints, .. })
+        | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
             let earliest = earliest as usize;
             let latest = latest as usize;
 
-            tick_aggr
-                .datapoints
+            datapoints
                 .iter()
                 .rev()
                 .enumerate()
                 .filter(|(index, _)| *index <= latest && *index >= earliest)
-                .for_each(|(index, tick_aggr)| {
+                .for_each(|(index, dp)| {
                     let x_position = interval_to_x(index as u64);
 
-                    draw_fn(frame, x_position, &tick_aggr.kline, &tick_aggr.footprint);
+                    draw_fn(frame, x_position, &dp.kline, &dp.footprint);
                 });
         }
         PlotData::TimeBased(timeseries) => {
@@ -1408,6 +2406,130 @@ fn render_data_source<F>(
     }
 }
 
+/// Closes of the visible bars plus `warmup` bars before them, oldest first, keyed by interval
+fn closes_with_warmup(
+    data_source: &PlotData<KlineDataPoint>,
+    earliest: u64,
+    latest: u64,
+    warmup: usize,
+) -> Vec<(u64, f32)> {
+    let mut closes: Vec<(u64, f32)> = match data_source {
+        PlotData::TickBased(TickAggr { datapoints, .. })
+        | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+            let visible = latest.saturating_sub(earliest) as usize + 1;
+
+            datapoints
+                .iter()
+                .rev()
+                .enumerate()
+                .skip(earliest as usize)
+                .take(visible + warmup)
+                .map(|(index, dp)| (index as u64, dp.kline.close.to_f32()))
+                .collect()
+        }
+        PlotData::TimeBased(timeseries) => {
+            if latest < earliest {
+                return vec![];
+            }
+            let visible = timeseries.datapoints.range(earliest..=latest).count();
+
+            timeseries
+                .datapoints
+                .range(..=latest)
+                .rev()
+                .take(visible + warmup)
+                .map(|(timestamp, dp)| (*timestamp, dp.kline.close.to_f32()))
+                .collect()
+        }
+    };
+
+    closes.reverse();
+    closes
+}
+
+fn draw_moving_averages(
+    frame: &mut canvas::Frame,
+    data_source: &PlotData<KlineDataPoint>,
+    moving_averages: &[MovingAverage],
+    earliest: u64,
+    latest: u64,
+    price_to_y: impl Fn(Price) -> f32,
+    interval_to_x: impl Fn(u64) -> f32,
+) {
+    let warmup = moving_averages
+        .iter()
+        .map(MovingAverage::warmup)
+        .max()
+        .unwrap_or(0);
+
+    let closes = closes_with_warmup(data_source, earliest, latest, warmup);
+    let prices: Vec<f32> = closes.iter().map(|(_, close)| *close).collect();
+
+    for ma in moving_averages {
+        let mut builder = canvas::path::Builder::new();
+        let mut started = false;
+
+        for ((interval, _), value) in closes.iter().zip(ma.calculate(&prices)) {
+            let Some(value) = value else {
+                continue;
+            };
+            let point = Point::new(interval_to_x(*interval), price_to_y(Price::from_f32(value)));
+
+            if started {
+                builder.line_to(point);
+            } else {
+                builder.move_to(point);
+                started = true;
+            }
+        }
+
+        frame.stroke(
+            &builder.build(),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.5,
+                    ..Default::default()
+                },
+                ma.color,
+            ),
+        );
+    }
+}
+
+fn kline_at(data_source: &PlotData<KlineDataPoint>, interval: u64) -> Option<&Kline> {
+    match data_source {
+        PlotData::TickBased(TickAggr { datapoints, .. })
+        | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+            .iter()
+            .rev()
+            .nth(interval as usize)
+            .map(|dp| &dp.kline),
+        PlotData::TimeBased(timeseries) => timeseries.datapoints.get(&interval).map(|dp| &dp.kline),
+    }
+}
+
+/// Washes out the bar that hasn't closed yet by covering it with the chart background
+fn draw_forming_bar_veil(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    x_position: f32,
+    cell_width: f32,
+    cell_height: f32,
+    kline: &Kline,
+    palette: &Extended,
+) {
+    let top = price_to_y(kline.high) - cell_height / 2.0;
+    let bottom = price_to_y(kline.low) + cell_height / 2.0;
+
+    frame.fill_rectangle(
+        Point::new(x_position - cell_width / 2.0, top),
+        Size::new(cell_width, bottom - top),
+        palette.background.base.color.scale_alpha(0.6),
+    );
+}
+
 fn draw_all_npocs(
     data_source: &PlotData<KlineDataPoint>,
     frame: &mut canvas::Frame,
@@ -1423,10 +2545,12 @@ fn draw_all_npocs(
     cluster_kind: ClusterKind,
     spacing: ContentGaps,
     imb_study_on: bool,
+    sessions: &SessionSettings,
+    step: PriceStep,
 ) {
-    let Some(lookback) = studies.iter().find_map(|study| {
-        if let FootprintStudy::NPoC { lookback } = study {
-            Some(*lookback)
+    let Some((lookback, scope)) = studies.iter().find_map(|study| {
+        if let FootprintStudy::NPoC { lookback, scope } = study {
+            Some((*lookback, *scope))
         } else {
             None
         }
@@ -1486,74 +2610,530 @@ fn draw_all_npocs(
         }
     };
 
-    let end_x_for = |cell_center_x: f32| -> f32 {
-        match cluster_kind {
-            ClusterKind::BidAsk => cell_center_x - (candle_width / 2.0) - spacing.candle_to_cluster,
-            ClusterKind::VolumeProfile | ClusterKind::DeltaProfile => wick_x_for(cell_center_x),
-        }
-    };
+    let end_x_for = |cell_center_x: f32| -> f32 {
+        match cluster_kind {
+            ClusterKind::BidAsk => cell_center_x - (candle_width / 2.0) - spacing.candle_to_cluster,
+            ClusterKind::VolumeProfile | ClusterKind::DeltaProfile => wick_x_for(cell_center_x),
+        }
+    };
+
+    let rightmost_cell_center_x = {
+        let earliest_x = interval_to_x(visible_earliest);
+        let latest_x = interval_to_x(visible_latest);
+        if earliest_x > latest_x {
+            earliest_x
+        } else {
+            latest_x
+        }
+    };
+
+    let mut draw_the_line = |interval: u64, poc: &PointOfControl| {
+        let start_x = start_x_for(interval_to_x(interval));
+
+        let (line_width, color) = match poc.status {
+            NPoc::Naked => {
+                let end_x = end_x_for(rightmost_cell_center_x);
+                let line_width = end_x - start_x;
+                if line_width.abs() <= cell_width {
+                    return;
+                }
+                (line_width, naked_color)
+            }
+            NPoc::Filled { at } => {
+                let end_x = end_x_for(interval_to_x(at));
+                let line_width = end_x - start_x;
+                if line_width.abs() <= cell_width {
+                    return;
+                }
+                (line_width, filled_color)
+            }
+            _ => return,
+        };
+
+        frame.fill_rectangle(
+            Point::new(start_x, price_to_y(poc.price) - line_height / 2.0),
+            Size::new(line_width, line_height),
+            color,
+        );
+    };
+
+    if scope == PocScope::Session {
+        let mut bars: Vec<PocBar> = match data_source {
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+                .iter()
+                .rev()
+                .enumerate()
+                .take(lookback)
+                .map(|(index, dp)| PocBar {
+                    key: index as u64,
+                    time: dp.kline.time,
+                    low: dp.kline.low,
+                    high: dp.kline.high,
+                    footprint: &dp.footprint,
+                })
+                .collect(),
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .iter()
+                .rev()
+                .take(lookback)
+                .map(|(timestamp, dp)| PocBar {
+                    key: *timestamp,
+                    time: *timestamp,
+                    low: dp.kline.low,
+                    high: dp.kline.high,
+                    footprint: &dp.footprint,
+                })
+                .collect(),
+        };
+        bars.reverse();
+
+        let (Some(first), Some(last)) = (bars.first(), bars.last()) else {
+            return;
+        };
+        let spans = sessions.manager.spans_between(first.time, last.time);
+
+        for (interval, poc) in session_pocs(&bars, &spans, step) {
+            draw_the_line(interval, &poc);
+        }
+        return;
+    }
+
+    match data_source {
+        PlotData::TickBased(TickAggr { datapoints, .. })
+        | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+            let session_start = datapoints
+                .last()
+                .and_then(|dp| sessions.reset_boundary(dp.kline.time));
+
+            datapoints
+                .iter()
+                .rev()
+                .enumerate()
+                .take(lookback)
+                .take_while(|(_, dp)| session_start.is_none_or(|start| dp.kline.time >= start))
+                .filter_map(|(index, dp)| dp.footprint.poc.as_ref().map(|poc| (index as u64, poc)))
+                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+        }
+        PlotData::TimeBased(timeseries) => {
+            let session_start = timeseries
+                .latest_timestamp()
+                .and_then(|latest| sessions.reset_boundary(latest));
+
+            timeseries
+                .datapoints
+                .iter()
+                .rev()
+                .take(lookback)
+                .take_while(|(timestamp, _)| session_start.is_none_or(|start| **timestamp >= start))
+                .filter_map(|(timestamp, dp)| {
+                    dp.footprint.poc.as_ref().map(|poc| (*timestamp, poc))
+                })
+                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+        }
+    }
+}
+
+const BAR_STATS_ROW_HEIGHT: f32 = 12.0;
+const BAR_STATS_LABEL_WIDTH: f32 = 56.0;
+/// Narrower bars leave the footer blank, the numbers wouldn't fit
+const BAR_STATS_MIN_BAR_PX: f32 = 28.0;
+
+/// Footer along the bottom of the chart with the chosen stats under each bar,
+/// `interval_to_x` and `bar_width` being in screen pixels
+/// Working orders, entry and break-even of the simulated position as lines across the chart,
+/// with its P&L in the top left corner
+fn draw_paper_overlay(
+    frame: &mut canvas::Frame,
+    overlay: &Overlay,
+    price_to_y: impl Fn(f32) -> f32,
+    precision: MinTicksize,
+    palette: &Extended,
+) {
+    let width = frame.size().width;
+    let side_color = |side: Side| match side {
+        Side::Buy => palette.success.base.color,
+        Side::Sell => palette.danger.base.color,
+    };
+
+    let mut level = |price: f32, label: String, color: iced::Color, dashed: bool, cancel: bool| {
+        let y = price_to_y(price);
+        frame.stroke(
+            &Path::line(Point::new(0.0, y), Point::new(width, y)),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    line_dash: canvas::LineDash {
+                        segments: if dashed { &[4.0, 3.0] } else { &[] },
+                        offset: 0,
+                    },
+                    ..Default::default()
+                },
+                color,
+            ),
+        );
+        if cancel {
+            draw_cancel_box(frame, cancel_box(&label, y), color, palette);
+        }
+        draw_paper_label(frame, label, Point::new(4.0, y), color, palette);
+    };
+
+    for order in &overlay.orders {
+        if let Some(limit) = order.limit {
+            level(
+                limit.to_f32(),
+                order_label(order),
+                side_color(order.side).scale_alpha(0.7),
+                true,
+                overlay.chart_trading,
+            );
+        }
+    }
+
+    let Some(position) = overlay.position else {
+        return;
+    };
+
+    if !position.is_flat() {
+        let (side, name) = if position.qty > 0.0 {
+            (Side::Buy, "Long")
+        } else {
+            (Side::Sell, "Short")
+        };
+        let price_label = |price: f32| Price::from_f32(price).to_string(precision);
+
+        if let Some(break_even) = position.break_even() {
+            level(
+                break_even,
+                format!("BE {}", price_label(break_even)),
+                palette.warning.base.color,
+                true,
+                false,
+            );
+        }
+        level(
+            position.avg_entry,
+            format!(
+                "{name} {:.4} @ {}",
+                position.qty.abs(),
+                price_label(position.avg_entry)
+            ),
+            side_color(side),
+            false,
+            false,
+        );
+    }
+
+    let unrealized = overlay.mark.map_or(0.0, |mark| position.unrealized(mark));
+    let total = position.realized + unrealized;
+    draw_paper_label(
+        frame,
+        format!(
+            "Paper uPnL {unrealized:+.2}  rPnL {:+.2}",
+            position.realized
+        ),
+        Point::new(4.0, 10.0),
+        if total >= 0.0 {
+            palette.success.base.color
+        } else {
+            palette.danger.base.color
+        },
+        palette,
+    );
+}
+
+fn order_label(order: &Order) -> String {
+    format!("{} {:.4} LMT", order.side, order.remaining())
+}
+
+fn paper_label_width(content: &str) -> f32 {
+    content.chars().count() as f32 * TEXT_SIZE * 0.6 + 8.0
+}
+
+/// Box right of an order label drawn at `y`, a click in it cancels the order
+fn cancel_box(label: &str, y: f32) -> Rectangle {
+    let size = TEXT_SIZE * 1.4;
+
+    Rectangle {
+        x: 4.0 + paper_label_width(label) + 2.0,
+        y: y - size / 2.0,
+        width: size,
+        height: size,
+    }
+}
+
+fn draw_cancel_box(
+    frame: &mut canvas::Frame,
+    rect: Rectangle,
+    color: iced::Color,
+    palette: &Extended,
+) {
+    frame.fill_rectangle(
+        rect.position(),
+        rect.size(),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.stroke_rectangle(
+        rect.position(),
+        rect.size(),
+        Stroke::with_color(
+            Stroke {
+                width: 1.0,
+                ..Default::default()
+            },
+            color,
+        ),
+    );
+    frame.fill_text(canvas::Text {
+        content: "×".to_string(),
+        position: rect.center(),
+        size: iced::Pixels(TEXT_SIZE),
+        color,
+        font: style::AZERET_MONO,
+        align_x: Alignment::Center.into(),
+        align_y: Alignment::Center.into(),
+        ..canvas::Text::default()
+    });
+}
+
+fn draw_paper_label(
+    frame: &mut canvas::Frame,
+    content: String,
+    left_center: Point,
+    color: iced::Color,
+    palette: &Extended,
+) {
+    frame.fill_rectangle(
+        Point::new(left_center.x, left_center.y - TEXT_SIZE * 0.7),
+        Size::new(paper_label_width(&content), TEXT_SIZE * 1.4),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content,
+        position: Point::new(left_center.x + 4.0, left_center.y),
+        size: iced::Pixels(TEXT_SIZE),
+        color,
+        font: style::AZERET_MONO,
+        align_y: Alignment::Center.into(),
+        ..canvas::Text::default()
+    });
+}
+
+fn draw_bar_stats(
+    data_source: &PlotData<KlineDataPoint>,
+    frame: &mut canvas::Frame,
+    rows: BarStatsRows,
+    earliest: u64,
+    latest: u64,
+    interval_to_x: impl Fn(u64) -> f32,
+    bar_width: f32,
+    palette: &Extended,
+) {
+    let labels: Vec<&str> = [
+        (rows.delta, "Delta"),
+        (rows.volume, "Volume"),
+        (rows.delta_pct, "Delta%"),
+        (rows.trades, "Trades"),
+        (rows.max_delta, "MaxDelta"),
+    ]
+    .into_iter()
+    .filter_map(|(shown, label)| shown.then_some(label))
+    .collect();
+    if labels.is_empty() {
+        return;
+    }
+
+    let size = frame.size();
+    let top = size.height - labels.len() as f32 * BAR_STATS_ROW_HEIGHT;
 
-    let rightmost_cell_center_x = {
-        let earliest_x = interval_to_x(visible_earliest);
-        let latest_x = interval_to_x(visible_latest);
-        if earliest_x > latest_x {
-            earliest_x
+    frame.fill_rectangle(
+        Point::new(0.0, top),
+        Size::new(size.width, size.height - top),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+
+    let row_y = |idx: usize| top + (idx as f32 + 0.5) * BAR_STATS_ROW_HEIGHT;
+    let signed_color = |value: f32| {
+        if value >= 0.0 {
+            palette.success.base.color
         } else {
-            latest_x
+            palette.danger.base.color
         }
     };
+    let stat_text = |content: String, position: Point, color, align_x: Alignment| canvas::Text {
+        content,
+        position,
+        size: iced::Pixels(10.0),
+        color,
+        font: style::AZERET_MONO,
+        align_x: align_x.into(),
+        align_y: Alignment::Center.into(),
+        ..canvas::Text::default()
+    };
 
-    let mut draw_the_line = |interval: u64, poc: &PointOfControl| {
-        let start_x = start_x_for(interval_to_x(interval));
-
-        let (line_width, color) = match poc.status {
-            NPoc::Naked => {
-                let end_x = end_x_for(rightmost_cell_center_x);
-                let line_width = end_x - start_x;
-                if line_width.abs() <= cell_width {
+    if bar_width >= BAR_STATS_MIN_BAR_PX {
+        render_data_source(
+            data_source,
+            frame,
+            earliest,
+            latest,
+            &interval_to_x,
+            |frame, x, _, trades| {
+                if x < BAR_STATS_LABEL_WIDTH || x > size.width {
                     return;
                 }
-                (line_width, naked_color)
-            }
-            NPoc::Filled { at } => {
-                let end_x = end_x_for(interval_to_x(at));
-                let line_width = end_x - start_x;
-                if line_width.abs() <= cell_width {
-                    return;
+                let stats = trades.stats();
+
+                let cells = [
+                    (
+                        rows.delta,
+                        abbr_large_numbers(stats.delta),
+                        Some(stats.delta),
+                    ),
+                    (rows.volume, abbr_large_numbers(stats.volume), None),
+                    (
+                        rows.delta_pct,
+                        format!("{:+.1}", stats.delta_pct()),
+                        Some(stats.delta),
+                    ),
+                    (rows.trades, stats.trades.to_string(), None),
+                    (
+                        rows.max_delta,
+                        abbr_large_numbers(stats.max_delta),
+                        Some(stats.max_delta),
+                    ),
+                ];
+
+                for (idx, (_, content, signed)) in
+                    cells.into_iter().filter(|(shown, ..)| *shown).enumerate()
+                {
+                    let color = signed.map_or(palette.background.base.text, signed_color);
+                    frame.fill_text(stat_text(
+                        content,
+                        Point::new(x, row_y(idx)),
+                        color,
+                        Alignment::Center,
+                    ));
                 }
-                (line_width, filled_color)
-            }
-            _ => return,
-        };
-
-        frame.fill_rectangle(
-            Point::new(start_x, price_to_y(poc.price) - line_height / 2.0),
-            Size::new(line_width, line_height),
-            color,
+            },
         );
-    };
+    }
 
-    match data_source {
-        PlotData::TickBased(tick_aggr) => {
-            tick_aggr
-                .datapoints
-                .iter()
-                .rev()
-                .enumerate()
-                .take(lookback)
-                .filter_map(|(index, dp)| dp.footprint.poc.as_ref().map(|poc| (index as u64, poc)))
-                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+    frame.fill_rectangle(
+        Point::new(0.0, top),
+        Size::new(BAR_STATS_LABEL_WIDTH, size.height - top),
+        palette.background.weakest.color,
+    );
+    for (idx, label) in labels.into_iter().enumerate() {
+        frame.fill_text(stat_text(
+            label.to_string(),
+            Point::new(4.0, row_y(idx)),
+            palette.background.weak.text,
+            Alignment::Start,
+        ));
+    }
+}
+
+/// Bars back from the latest scanned for stacked imbalances still holding
+const STACKED_IMBALANCE_LOOKBACK: usize = 200;
+
+fn draw_stacked_imbalances(
+    data_source: &PlotData<KlineDataPoint>,
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    interval_to_x: impl Fn(u64) -> f32,
+    cell_width: f32,
+    cell_height: f32,
+    palette: &Extended,
+    studies: &[FootprintStudy],
+    tick_size: f32,
+    visible_earliest: u64,
+    visible_latest: u64,
+) {
+    let Some((threshold, ignore_zeros, min_stack)) = studies.iter().find_map(|study| {
+        if let FootprintStudy::Imbalance {
+            threshold,
+            ignore_zeros,
+            stacked: Some(min_stack),
+            ..
+        } = study
+        {
+            Some((*threshold, *ignore_zeros, *min_stack))
+        } else {
+            None
         }
-        PlotData::TimeBased(timeseries) => {
-            timeseries
-                .datapoints
+    }) else {
+        return;
+    };
+
+    // oldest first, keyed by what `interval_to_x` expects
+    let bars: Vec<(u64, &Kline, &KlineTrades)> = match data_source {
+        PlotData::TickBased(TickAggr { datapoints, .. })
+        | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+            .iter()
+            .rev()
+            .enumerate()
+            .take(STACKED_IMBALANCE_LOOKBACK)
+            .map(|(index, dp)| (index as u64, &dp.kline, &dp.footprint))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect(),
+        PlotData::TimeBased(timeseries) => timeseries
+            .datapoints
+            .iter()
+            .rev()
+            .take(STACKED_IMBALANCE_LOOKBACK)
+            .map(|(timestamp, dp)| (*timestamp, &dp.kline, &dp.footprint))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect(),
+    };
+
+    let step = PriceStep::from_f32(tick_size);
+    let rightmost_x = interval_to_x(visible_earliest).max(interval_to_x(visible_latest));
+    let line_height = (cell_height / 4.0).clamp(1.0, 2.0);
+
+    for (index, (interval, _, footprint)) in bars.iter().enumerate() {
+        for stack in footprint.stacked_imbalances(step, threshold, ignore_zeros, min_stack) {
+            let color = match stack.side {
+                ImbalanceSide::Buy => palette.success.weak.color,
+                ImbalanceSide::Sell => palette.danger.weak.color,
+            };
+            let x = interval_to_x(*interval);
+
+            let zone_top = price_to_y(stack.high) - cell_height / 2.0;
+            let zone_bottom = price_to_y(stack.low) + cell_height / 2.0;
+            frame.fill_rectangle(
+                Point::new(x - cell_width / 2.0, zone_top),
+                Size::new(cell_width, zone_bottom - zone_top),
+                color.scale_alpha(0.15),
+            );
+
+            let end_x = bars[index + 1..]
                 .iter()
-                .rev()
-                .take(lookback)
-                .filter_map(|(timestamp, dp)| {
-                    dp.footprint.poc.as_ref().map(|poc| (*timestamp, poc))
+                .find(|(_, kline, _)| {
+                    let low = kline.low.round_to_side_step(true, step);
+                    let high = kline.high.round_to_side_step(false, step);
+                    stack.is_traded_through(low, high)
                 })
-                .for_each(|(interval, poc)| draw_the_line(interval, poc));
+                .map_or(rightmost_x, |(at, _, _)| interval_to_x(*at));
+
+            let start_x = x + cell_width / 2.0;
+            if end_x - start_x <= 0.0 {
+                continue;
+            }
+
+            frame.fill_rectangle(
+                Point::new(start_x, price_to_y(stack.level()) - line_height / 2.0),
+                Size::new(end_x - start_x, line_height),
+                color.scale_alpha(0.8),
+            );
         }
     }
 }
@@ -1698,8 +3278,6 @@ fn draw_clusters(
 
                 if let Some((threshold, color_scale, ignore_zeros)) = imbalance {
                     let step = PriceStep::from_f32(tick_size);
-                    let higher_price =
-                        Price::from_f32(price.to_f32() + tick_size).round_to_step(step);
 
                     let rect_w = ((area.imb_marker_width - 1.0) / 2.0).max(1.0);
                     let buyside_x = area.imb_marker_left + area.imb_marker_width - rect_w;
@@ -1711,8 +3289,7 @@ fn draw_clusters(
                         &price_to_y,
                         footprint,
                         *price,
-                        group.sell_qty,
-                        higher_price,
+                        step,
                         threshold,
                         color_scale,
                         ignore_zeros,
@@ -1812,8 +3389,6 @@ fn draw_clusters(
                     && area.imb_marker_width > 0.0
                 {
                     let step = PriceStep::from_f32(tick_size);
-                    let higher_price =
-                        Price::from_f32(price.to_f32() + tick_size).round_to_step(step);
 
                     let rect_width = ((area.imb_marker_width - 1.0) / 2.0).max(1.0);
 
@@ -1825,8 +3400,7 @@ fn draw_clusters(
                         &price_to_y,
                         footprint,
                         *price,
-                        group.sell_qty,
-                        higher_price,
+                        step,
                         threshold,
                         color_scale,
                         ignore_zeros,
@@ -1857,8 +3431,7 @@ fn draw_imbalance_markers(
     price_to_y: &impl Fn(Price) -> f32,
     footprint: &KlineTrades,
     price: Price,
-    sell_qty: f32,
-    higher_price: Price,
+    step: PriceStep,
     threshold: usize,
     color_scale: Option<usize>,
     ignore_zeros: bool,
@@ -1868,56 +3441,30 @@ fn draw_imbalance_markers(
     sellside_x: f32,
     rect_width: f32,
 ) {
-    if ignore_zeros && sell_qty <= 0.0 {
+    let Some(imbalance) = footprint.diagonal_imbalance(price, step, threshold, ignore_zeros) else {
         return;
-    }
-
-    if let Some(group) = footprint.trades.get(&higher_price) {
-        let diagonal_buy_qty = group.buy_qty;
+    };
 
-        if ignore_zeros && diagonal_buy_qty <= 0.0 {
-            return;
-        }
+    let alpha = if let Some(scale) = color_scale {
+        let divisor = (scale as f32 / 10.0) - 1.0;
+        (0.2 + 0.8 * ((imbalance.ratio - 1.0) / divisor).min(1.0)).min(1.0)
+    } else {
+        1.0
+    };
 
-        let rect_height = cell_height / 2.0;
+    let (x, color) = match imbalance.side {
+        ImbalanceSide::Buy => (buyside_x, palette.success.weak.color),
+        ImbalanceSide::Sell => (sellside_x, palette.danger.weak.color),
+    };
 
-        let alpha_from_ratio = |ratio: f32| -> f32 {
-            if let Some(scale) = color_scale {
-                let divisor = (scale as f32 / 10.0) - 1.0;
-                (0.2 + 0.8 * ((ratio - 1.0) / divisor).min(1.0)).min(1.0)
-            } else {
-                1.0
-            }
-        };
+    let rect_height = cell_height / 2.0;
+    let y = price_to_y(imbalance.price);
 
-        if diagonal_buy_qty >= sell_qty {
-            let required_qty = sell_qty * (100 + threshold) as f32 / 100.0;
-            if diagonal_buy_qty > required_qty {
-                let ratio = diagonal_buy_qty / required_qty;
-                let alpha = alpha_from_ratio(ratio);
-
-                let y = price_to_y(higher_price);
-                frame.fill_rectangle(
-                    Point::new(buyside_x, y - (rect_height / 2.0)),
-                    Size::new(rect_width, rect_height),
-                    palette.success.weak.color.scale_alpha(alpha),
-                );
-            }
-        } else {
-            let required_qty = diagonal_buy_qty * (100 + threshold) as f32 / 100.0;
-            if sell_qty > required_qty {
-                let ratio = sell_qty / required_qty;
-                let alpha = alpha_from_ratio(ratio);
-
-                let y = price_to_y(price);
-                frame.fill_rectangle(
-                    Point::new(sellside_x, y - (rect_height / 2.0)),
-                    Size::new(rect_width, rect_height),
-                    palette.danger.weak.color.scale_alpha(alpha),
-                );
-            }
-        }
-    }
+    frame.fill_rectangle(
+        Point::new(x, y - (rect_height / 2.0)),
+        Size::new(rect_width, rect_height),
+        color.scale_alpha(alpha),
+    );
 }
 
 impl ContentGaps {
@@ -1932,6 +3479,66 @@ impl ContentGaps {
     }
 }
 
+/// Sizes of the footprint cell contents at a given zoom
+struct CellLayout {
+    text_size: f32,
+    candle_width: f32,
+    content_spacing: ContentGaps,
+    imbalance: Option<(usize, Option<usize>, bool)>,
+    show_text: bool,
+}
+
+impl CellLayout {
+    fn new(
+        chart: &ViewState,
+        scaling: f32,
+        clusters: ClusterKind,
+        studies: &[FootprintStudy],
+    ) -> Self {
+        let cell_height_unscaled = chart.cell_height * scaling;
+        let cell_width_unscaled = chart.cell_width * scaling;
+
+        let text_size = {
+            let text_size_from_height = cell_height_unscaled.round().min(16.0) - 3.0;
+            let text_size_from_width = (cell_width_unscaled * 0.1).round().min(16.0) - 3.0;
+
+            text_size_from_height.min(text_size_from_width)
+        };
+
+        let candle_width = 0.1 * chart.cell_width;
+
+        let imbalance = studies.iter().find_map(|study| {
+            if let FootprintStudy::Imbalance {
+                threshold,
+                color_scale,
+                ignore_zeros,
+                ..
+            } = study
+            {
+                Some((*threshold, *color_scale, *ignore_zeros))
+            } else {
+                None
+            }
+        });
+
+        let show_text = {
+            let min_w = match clusters {
+                ClusterKind::VolumeProfile | ClusterKind::DeltaProfile => 80.0,
+                ClusterKind::BidAsk => 120.0,
+            };
+            should_show_text(cell_height_unscaled, cell_width_unscaled, min_w)
+        };
+
+        Self {
+            text_size,
+            candle_width,
+            content_spacing: ContentGaps::from_view(candle_width, scaling),
+            imbalance,
+            show_text,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct ContentGaps {
     /// Space between imb. markers candle body
@@ -1998,6 +3605,12 @@ fn draw_crosshair_tooltip(
                 None
             }
         }
+        PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+        | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+            .iter()
+            .rev()
+            .nth(at_interval as usize)
+            .map(|dp| &dp.kline),
     };
 
     if let Some(kline) = kline_opt {
@@ -2257,40 +3870,266 @@ fn draw_rejection_zones(
     }
 }
 
-// NEW: Enhanced large orders visualization
+fn large_order_radius(volume: f32, max_volume: f32, cell_width: f32) -> f32 {
+    let max_radius = (cell_width * 0.8).clamp(4.0, 24.0);
+    let min_radius = max_radius * 0.25;
+
+    // area grows with volume, so the radius follows its square root
+    let ratio = (volume / max_volume.max(f32::EPSILON)).clamp(0.0, 1.0);
+    min_radius + (max_radius - min_radius) * ratio.sqrt()
+}
+
 fn draw_large_orders(
     frame: &mut canvas::Frame,
     price_to_y: impl Fn(Price) -> f32,
-    large_orders: &[(Price, f32, bool)],
+    interval_to_x: impl Fn(u64) -> f32,
+    large_orders: &[LargeOrder],
+    palette: &Palette,
+    cell_width: f32,
+) {
+    let max_volume = large_orders
+        .iter()
+        .map(|order| order.volume)
+        .fold(0.0, f32::max);
+
+    // smallest bubbles last, so they stay visible on top of larger ones
+    for order in large_orders.iter() {
+        let center = Point::new(interval_to_x(order.timestamp), price_to_y(order.price));
+        let radius = large_order_radius(order.volume, max_volume, cell_width);
+        let color = get_large_order_color(order.is_buy, order.volume, max_volume, palette);
+
+        let circle = Path::circle(center, radius);
+        frame.fill(&circle, color.scale_alpha(0.35));
+        frame.stroke(
+            &circle,
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    ..Stroke::default()
+                },
+                color,
+            ),
+        );
+    }
+}
+
+/// Reference bubbles for the threshold, midpoint and largest visible order,
+/// drawn in screen space at the top-left corner
+fn draw_large_order_legend(
+    frame: &mut canvas::Frame,
     palette: &Extended,
+    large_orders: &[LargeOrder],
+    threshold: f32,
     cell_width: f32,
 ) {
-    for (price, volume, is_buy) in large_orders {
-        let y = price_to_y(*price);
-        let color = if *is_buy {
-            palette.success.base.color
-        } else {
-            palette.danger.base.color
-        };
+    let max_volume = large_orders
+        .iter()
+        .map(|order| order.volume)
+        .fold(threshold, f32::max);
+
+    let samples = [threshold, (threshold + max_volume) / 2.0, max_volume];
+    let radii = samples.map(|volume| large_order_radius(volume, max_volume, cell_width));
+    let max_radius = radii.iter().copied().fold(0.0, f32::max);
+
+    let padding = 6.0;
+    let row_height = (max_radius * 2.0).max(TEXT_SIZE) + 4.0;
+    let label_x = padding * 2.0 + max_radius * 2.0;
+
+    let labels = samples.map(|volume| format!("≥ {}", abbr_large_numbers(volume)));
+    let label_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0) as f32
+        * TEXT_SIZE
+        * 0.6;
+
+    let title = "Large orders";
+    let width = (label_x + label_width).max(title.len() as f32 * TEXT_SIZE * 0.6) + padding * 2.0;
+    let height = TEXT_SIZE + padding * 3.0 + row_height * samples.len() as f32;
+
+    let origin = Point::new(8.0, 8.0);
+    frame.fill_rectangle(
+        origin,
+        Size::new(width, height),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content: title.to_string(),
+        position: Point::new(origin.x + padding, origin.y + padding),
+        size: iced::Pixels(TEXT_SIZE),
+        color: palette.background.base.text,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+
+    let stroke_color = palette.background.base.text.scale_alpha(0.7);
 
-        let order_size = (volume / 1000.0).min(10.0).max(2.0); // Scale circle size
+    for (idx, (radius, label)) in radii.iter().zip(labels).enumerate() {
+        let row_center_y =
+            origin.y + TEXT_SIZE + padding * 2.0 + row_height * idx as f32 + row_height / 2.0;
 
-        frame.fill(
-            &Path::circle(Point::new(cell_width * 12.0, y), order_size),
-            color.scale_alpha(0.7),
+        frame.stroke(
+            &Path::circle(
+                Point::new(origin.x + padding + max_radius, row_center_y),
+                *radius,
+            ),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    ..Stroke::default()
+                },
+                stroke_color,
+            ),
         );
 
-        // Draw volume text for very large orders
-        if *volume > 5000.0 {
-            draw_cluster_text(
-                frame,
-                &abbr_large_numbers(*volume),
-                Point::new(cell_width * 12.0 + order_size + 2.0, y),
-                10.0,
-                color,
-                Alignment::Start,
-                Alignment::Center,
-            );
-        }
+        draw_cluster_text(
+            frame,
+            &label,
+            Point::new(origin.x + label_x, row_center_y),
+            TEXT_SIZE,
+            palette.background.base.text,
+            Alignment::Start,
+            Alignment::Center,
+        );
+    }
+}
+
+fn sr_level_color(level_type: &LevelType, palette: &Extended) -> iced::Color {
+    match level_type {
+        LevelType::Support => palette.success.base.color,
+        LevelType::Resistance => palette.danger.base.color,
+        LevelType::Breakout => palette.warning.base.color,
+    }
+}
+
+fn draw_support_resistance(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    levels: &[SupportResistanceLevel],
+    palette: &Extended,
+    region: Rectangle,
+) {
+    for level in levels {
+        let y = price_to_y(level.price_level);
+        let color = sr_level_color(&level.level_type, palette);
+
+        frame.stroke(
+            &Path::line(
+                Point::new(region.x, y),
+                Point::new(region.x + region.width, y),
+            ),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0 + level.strength,
+                    ..Default::default()
+                },
+                color.scale_alpha(0.2 + level.strength * 0.6),
+            ),
+        );
+    }
+}
+
+fn draw_source_switch(
+    frame: &mut canvas::Frame,
+    switch: &SourceSwitch,
+    x: f32,
+    region: Rectangle,
+    palette: &Extended,
+    scaling: f32,
+) {
+    let color = palette.secondary.base.color;
+
+    frame.stroke(
+        &Path::line(
+            Point::new(x, region.y),
+            Point::new(x, region.y + region.height),
+        ),
+        Stroke::with_color(
+            Stroke {
+                width: 1.0 / scaling,
+                line_dash: canvas::LineDash {
+                    segments: &[2.0, 4.0],
+                    offset: 0,
+                },
+                ..Default::default()
+            },
+            color.scale_alpha(0.8),
+        ),
+    );
+
+    frame.fill_text(canvas::Text {
+        content: format!("{} \u{2192} {}", switch.from, switch.to),
+        position: Point::new(x + 4.0 / scaling, region.y + 4.0 / scaling),
+        size: iced::Pixels(TEXT_SIZE / scaling),
+        color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
+
+fn draw_alert_lines(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    alerts: &[PriceAlert],
+    palette: &Extended,
+    region: Rectangle,
+) {
+    for alert in alerts {
+        let y = price_to_y(Price::from_f32(alert.price));
+        let color = if alert.is_armed() {
+            palette.warning.base.color
+        } else {
+            palette.background.strong.color
+        };
+
+        frame.stroke(
+            &Path::line(
+                Point::new(region.x, y),
+                Point::new(region.x + region.width, y),
+            ),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    line_dash: canvas::LineDash {
+                        segments: &[6.0, 4.0],
+                        offset: 0,
+                    },
+                    ..Default::default()
+                },
+                color.scale_alpha(if alert.muted { 0.4 } else { 0.9 }),
+            ),
+        );
     }
 }
+
+fn draw_sr_level_tooltip(
+    frame: &mut canvas::Frame,
+    palette: &Extended,
+    cursor_position: Point,
+    level: &SupportResistanceLevel,
+) {
+    let kind = match level.level_type {
+        LevelType::Support => "Support",
+        LevelType::Resistance => "Resistance",
+        LevelType::Breakout => "Breakout",
+    };
+    let label = format!("{kind} · {} touches", level.touches);
+
+    let text_width = label.chars().count() as f32 * TEXT_SIZE * 0.6;
+    let position = Point::new(cursor_position.x + 12.0, cursor_position.y - 24.0);
+
+    frame.fill_rectangle(
+        Point::new(position.x - 4.0, position.y - 2.0),
+        Size::new(text_width + 8.0, TEXT_SIZE + 6.0),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content: label,
+        position,
+        size: iced::Pixels(11.0),
+        color: sr_level_color(&level.level_type, palette),
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
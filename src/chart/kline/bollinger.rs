@@ -0,0 +1,176 @@
+use data::aggr::{range::RangeAggr, ticks::TickAggr, volume::VolumeAggr};
+use data::chart::PlotData;
+use data::chart::kline::KlineDataPoint;
+use data::chart::moving_average::{Band, BollingerBands};
+use exchange::util::Price;
+
+use iced::widget::canvas::{self, Path, Stroke};
+use iced::{Color, Point};
+use std::collections::BTreeMap;
+
+/// Bollinger bands of the chart's bars, keyed by bar time or by bar index counted from the oldest.
+///
+/// A band only depends on the closes of its own window, so streamed updates recompute
+/// just the bands of the bars that changed.
+pub struct BollingerOverlay {
+    config: BollingerBands,
+    bands: BTreeMap<u64, Band>,
+}
+
+impl BollingerOverlay {
+    pub fn new(config: BollingerBands, source: &PlotData<KlineDataPoint>) -> Self {
+        let mut overlay = Self {
+            config,
+            bands: BTreeMap::new(),
+        };
+        overlay.rebuild(source);
+        overlay
+    }
+
+    pub fn config(&self) -> BollingerBands {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: BollingerBands, source: &PlotData<KlineDataPoint>) {
+        if self.config != config {
+            self.config = config;
+            self.rebuild(source);
+        }
+    }
+
+    pub fn rebuild(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.bands.clear();
+        self.update_from(source, 0);
+    }
+
+    /// Recomputes the bands of bars keyed `from` onward
+    pub fn update_from(&mut self, source: &PlotData<KlineDataPoint>, from: u64) {
+        if !self.config.enabled {
+            self.bands.clear();
+            return;
+        }
+
+        let period = usize::from(self.config.period);
+
+        match source {
+            PlotData::TimeBased(timeseries) => {
+                for time in timeseries.datapoints.range(from..).map(|(time, _)| *time) {
+                    let mut window: Vec<f32> = timeseries
+                        .datapoints
+                        .range(..=time)
+                        .rev()
+                        .take(period)
+                        .map(|(_, dp)| dp.kline.close.to_f32())
+                        .collect();
+                    window.reverse();
+
+                    self.insert(time, &window);
+                }
+            }
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                for idx in (from as usize)..datapoints.len() {
+                    let window: Vec<f32> = datapoints[(idx + 1).saturating_sub(period)..=idx]
+                        .iter()
+                        .map(|dp| dp.kline.close.to_f32())
+                        .collect();
+
+                    self.insert(idx as u64, &window);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, key: u64, window: &[f32]) {
+        match self.config.band(window) {
+            Some(band) => self.bands.insert(key, band),
+            None => self.bands.remove(&key),
+        };
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        source: &PlotData<KlineDataPoint>,
+        earliest: u64,
+        latest: u64,
+        price_to_y: impl Fn(Price) -> f32,
+        interval_to_x: impl Fn(u64) -> f32,
+        color: Color,
+    ) {
+        if self.bands.is_empty() || latest < earliest {
+            return;
+        }
+
+        // index-based bars are drawn newest first
+        let points: Vec<(f32, Band)> = match source {
+            PlotData::TimeBased(_) => self
+                .bands
+                .range(earliest..=latest)
+                .map(|(time, band)| (interval_to_x(*time), *band))
+                .collect(),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let Some(last) = (datapoints.len() as u64).checked_sub(1) else {
+                    return;
+                };
+
+                self.bands
+                    .range(last.saturating_sub(latest)..=last.saturating_sub(earliest))
+                    .map(|(idx, band)| (interval_to_x(last - idx), *band))
+                    .collect()
+            }
+        };
+
+        if points.len() < 2 {
+            return;
+        }
+
+        let y = |price: f32| price_to_y(Price::from_f32(price));
+
+        let fill = Path::new(|builder| {
+            for (i, (x, band)) in points.iter().enumerate() {
+                let point = Point::new(*x, y(band.upper));
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+            for (x, band) in points.iter().rev() {
+                builder.line_to(Point::new(*x, y(band.lower)));
+            }
+            builder.close();
+        });
+        frame.fill(&fill, color.scale_alpha(0.08));
+
+        let line = |value: fn(&Band) -> f32| {
+            Path::new(|builder| {
+                for (i, (x, band)) in points.iter().enumerate() {
+                    let point = Point::new(*x, y(value(band)));
+                    if i == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            })
+        };
+
+        let stroke = |alpha: f32| {
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    ..Default::default()
+                },
+                color.scale_alpha(alpha),
+            )
+        };
+
+        frame.stroke(&line(|band| band.upper), stroke(0.6));
+        frame.stroke(&line(|band| band.lower), stroke(0.6));
+        frame.stroke(&line(|band| band.middle), stroke(0.9));
+    }
+}
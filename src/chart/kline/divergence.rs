@@ -0,0 +1,211 @@
+use data::aggr::{range::RangeAggr, ticks::TickAggr, volume::VolumeAggr};
+use data::chart::PlotData;
+use data::chart::divergence::{BarDelta, DeltaDivergence, Divergence};
+use data::chart::kline::KlineDataPoint;
+use exchange::Kline;
+use exchange::util::Price;
+
+use iced::theme::palette::Extended;
+use iced::widget::canvas::{self, Path};
+use iced::{Point, Size};
+use std::collections::BTreeMap;
+
+use crate::chart::TEXT_SIZE;
+use crate::style;
+
+/// Delta divergences of the chart's bars, keyed by bar time or by bar index counted from the oldest.
+///
+/// Cumulative delta runs from the oldest loaded bar, so only its changes between bars matter
+/// and a bar's verdict only depends on the window before it.
+pub struct DivergenceOverlay {
+    config: DeltaDivergence,
+    marks: BTreeMap<u64, Divergence>,
+}
+
+impl DivergenceOverlay {
+    pub fn new(config: DeltaDivergence, source: &PlotData<KlineDataPoint>) -> Self {
+        let mut overlay = Self {
+            config,
+            marks: BTreeMap::new(),
+        };
+        overlay.rebuild(source);
+        overlay
+    }
+
+    pub fn config(&self) -> DeltaDivergence {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: DeltaDivergence, source: &PlotData<KlineDataPoint>) {
+        if self.config != config {
+            self.config = config;
+            self.rebuild(source);
+        }
+    }
+
+    pub fn rebuild(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.marks.clear();
+        self.update_from(source, 0);
+    }
+
+    /// Re-evaluates the bars keyed `from` onward
+    pub fn update_from(&mut self, source: &PlotData<KlineDataPoint>, from: u64) {
+        if !self.config.enabled {
+            self.marks.clear();
+            return;
+        }
+
+        let keyed: Vec<(u64, &Kline)> = match source {
+            PlotData::TimeBased(timeseries) => timeseries
+                .datapoints
+                .iter()
+                .map(|(time, dp)| (*time, &dp.kline))
+                .collect(),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => datapoints
+                .iter()
+                .enumerate()
+                .map(|(idx, dp)| (idx as u64, &dp.kline))
+                .collect(),
+        };
+
+        let mut cvd = 0.0;
+        let bars: Vec<BarDelta> = keyed
+            .iter()
+            .map(|(_, kline)| {
+                cvd += kline.volume.0 - kline.volume.1;
+                BarDelta {
+                    high: kline.high.to_f32(),
+                    low: kline.low.to_f32(),
+                    cvd,
+                }
+            })
+            .collect();
+
+        let window_len = usize::from(self.config.lookback) + 1;
+        let start = keyed.partition_point(|(key, _)| *key < from);
+
+        for (idx, (key, _)) in keyed.iter().enumerate().skip(start) {
+            let window = &bars[(idx + 1).saturating_sub(window_len)..=idx];
+
+            match self.config.detect(window) {
+                Some(divergence) => self.marks.insert(*key, divergence),
+                None => self.marks.remove(key),
+            };
+        }
+    }
+
+    /// Divergence of the bar under the crosshair, `at` keyed like the crosshair tooltip
+    pub fn at(&self, source: &PlotData<KlineDataPoint>, at: u64) -> Option<Divergence> {
+        let key = match source {
+            PlotData::TimeBased(_) => at,
+            PlotData::TickBased(tick_aggr) => {
+                let reversed = at / u64::from(tick_aggr.interval.0);
+                (tick_aggr.datapoints.len() as u64).checked_sub(reversed + 1)?
+            }
+            PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                (datapoints.len() as u64).checked_sub(at + 1)?
+            }
+        };
+
+        self.marks.get(&key).copied()
+    }
+
+    pub fn draw(
+        &self,
+        frame: &mut canvas::Frame,
+        source: &PlotData<KlineDataPoint>,
+        earliest: u64,
+        latest: u64,
+        price_to_y: impl Fn(Price) -> f32,
+        interval_to_x: impl Fn(u64) -> f32,
+        cell_width: f32,
+        palette: &Extended,
+    ) {
+        if self.marks.is_empty() || latest < earliest {
+            return;
+        }
+
+        // index-based bars are drawn newest first
+        let marks: Vec<(f32, &Kline, Divergence)> = match source {
+            PlotData::TimeBased(timeseries) => self
+                .marks
+                .range(earliest..=latest)
+                .filter_map(|(time, divergence)| {
+                    let dp = timeseries.datapoints.get(time)?;
+                    Some((interval_to_x(*time), &dp.kline, *divergence))
+                })
+                .collect(),
+            PlotData::TickBased(TickAggr { datapoints, .. })
+            | PlotData::VolumeBased(VolumeAggr { datapoints, .. })
+            | PlotData::RangeBased(RangeAggr { datapoints, .. }) => {
+                let Some(last) = (datapoints.len() as u64).checked_sub(1) else {
+                    return;
+                };
+
+                self.marks
+                    .range(last.saturating_sub(latest)..=last.saturating_sub(earliest))
+                    .filter_map(|(idx, divergence)| {
+                        let dp = datapoints.get(*idx as usize)?;
+                        Some((interval_to_x(last - idx), &dp.kline, *divergence))
+                    })
+                    .collect()
+            }
+        };
+
+        let size = (cell_width * 0.5).clamp(4.0, 10.0);
+        let gap = size * 0.5;
+
+        for (x, kline, divergence) in marks {
+            // arrow points away from the bar, down above it and up below it
+            let (tip_y, base_y, color) = if divergence.is_bearish() {
+                let base_y = price_to_y(kline.high) - gap - size;
+                (base_y + size, base_y, palette.danger.base.color)
+            } else {
+                let base_y = price_to_y(kline.low) + gap + size;
+                (base_y - size, base_y, palette.success.base.color)
+            };
+
+            let arrow = Path::new(|builder| {
+                builder.move_to(Point::new(x - size / 2.0, base_y));
+                builder.line_to(Point::new(x + size / 2.0, base_y));
+                builder.line_to(Point::new(x, tip_y));
+                builder.close();
+            });
+            frame.fill(&arrow, color);
+        }
+    }
+}
+
+pub fn draw_divergence_tooltip(
+    frame: &mut canvas::Frame,
+    palette: &Extended,
+    cursor_position: Point,
+    divergence: Divergence,
+) {
+    let label = divergence.to_string();
+    let color = if divergence.is_bearish() {
+        palette.danger.base.color
+    } else {
+        palette.success.base.color
+    };
+
+    let text_width = label.chars().count() as f32 * TEXT_SIZE * 0.6;
+    let position = Point::new(cursor_position.x + 12.0, cursor_position.y + 12.0);
+
+    frame.fill_rectangle(
+        Point::new(position.x - 4.0, position.y - 2.0),
+        Size::new(text_width + 8.0, TEXT_SIZE + 6.0),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content: label,
+        position,
+        size: iced::Pixels(11.0),
+        color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
@@ -1,10 +1,19 @@
 pub mod linear;
 pub mod timeseries;
 
-use crate::{chart::TEXT_SIZE, style::AZERET_MONO};
+use crate::{
+    chart::{TEXT_SIZE, text_cache},
+    style::AZERET_MONO,
+};
 
-use super::{Basis, Interaction, Message};
-use data::{chart::Autoscale, util::round_to_tick};
+use super::{Basis, Interaction, Message, ORDER_GRAB_DISTANCE};
+use data::chart::session::{SessionKind, SessionManager};
+use data::{
+    chart::{Autoscale, PriceScale},
+    paper::Side,
+    util::round_to_tick,
+};
+use exchange::util::Price;
 use iced::{
     Alignment, Color, Event, Point, Rectangle, Renderer, Size, Theme, mouse,
     theme::palette::Extended,
@@ -12,6 +21,8 @@ use iced::{
 };
 
 const REGULAR_LABEL_WIDTH: f32 = TEXT_SIZE * 6.0;
+/// Pixels a press on the price axis moves before it zooms instead of placing an order
+const ORDER_CLICK_SLOP: f32 = 3.0;
 
 /// calculates `Rectangle` from given content, clamps it within bounds if needed
 pub fn calc_label_rect(
@@ -70,7 +81,7 @@ impl AxisLabel {
         is_crosshair: bool,
         palette: &Extended,
     ) -> Self {
-        let content_width = text_content.len() as f32 * (TEXT_SIZE / 2.6);
+        let content_width = text_cache::label_width(&text_content) / 2.0 + 4.0;
 
         let rect = Rectangle {
             x: center_x_position - content_width,
@@ -134,7 +145,7 @@ impl AxisLabel {
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    pub fn draw(&self, frame: &mut Frame) {
         match self {
             AxisLabel::X { bounds, label } => {
                 let frame_bounds = frame.size();
@@ -218,6 +229,7 @@ impl AxisLabel {
 // X-AXIS LABELS
 pub struct AxisLabelsX<'a> {
     pub labels_cache: &'a Cache,
+    pub crosshair_cache: &'a Cache,
     pub max: u64,
     pub scaling: f32,
     pub translation_x: f32,
@@ -225,8 +237,11 @@ pub struct AxisLabelsX<'a> {
     pub cell_width: f32,
     pub timezone: data::UserTimezone,
     pub chart_bounds: Rectangle,
+    /// Crosshair of a linked pane, labeled while the cursor is off this chart
+    pub synced_crosshair_x: Option<f32>,
     pub interval_keys: Option<Vec<u64>>,
     pub autoscaling: Option<Autoscale>,
+    pub sessions: Option<SessionManager>,
 }
 
 impl AxisLabelsX<'_> {
@@ -247,7 +262,7 @@ impl AxisLabelsX<'_> {
         palette: &Extended,
     ) -> Option<AxisLabel> {
         match self.basis {
-            Basis::Tick(interval) => {
+            Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                 let Some(interval_keys) = &self.interval_keys else {
                     return None;
                 };
@@ -274,9 +289,10 @@ impl AxisLabelsX<'_> {
                 let array_index = last_index - offset;
 
                 if let Some(timestamp) = interval_keys.get(array_index) {
-                    let text_content = self
-                        .timezone
-                        .format_crosshair_timestamp(*timestamp as i64, interval.0.into());
+                    let text_content = self.timezone.format_crosshair_timestamp(
+                        *timestamp as i64,
+                        exchange::Timeframe::MS100.to_milliseconds(),
+                    );
 
                     return Some(AxisLabel::new_x(
                         snap_x,
@@ -324,7 +340,6 @@ impl AxisLabelsX<'_> {
                     palette,
                 ));
             }
-            Basis::Volume(_) | Basis::Range(_) => return None,
         }
 
         None
@@ -452,10 +467,10 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
 
             let label_count = target_count.max(2);
 
-            let mut labels: Vec<AxisLabel> = Vec::with_capacity(label_count + 1); // +1 for crosshair
+            let mut labels: Vec<AxisLabel> = Vec::with_capacity(label_count);
 
             match self.basis {
-                Basis::Tick(_) => {
+                Basis::Tick(_) | Basis::Volume(_) | Basis::Range(_) => {
                     if let Some(interval_keys) = &self.interval_keys {
                         let last_idx = interval_keys.len() - 1;
                         let mut last_x: Option<f32> = None;
@@ -489,6 +504,17 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
                     let x_min_region = self.x_to_interval(region.x);
                     let x_max_region = self.x_to_interval(region.x + region.width);
 
+                    if let Some(sessions) = &self.sessions {
+                        draw_session_shading(
+                            frame,
+                            sessions,
+                            x_min_region,
+                            x_max_region,
+                            bounds,
+                            palette,
+                        );
+                    }
+
                     let generated_labels = timeseries::generate_time_labels(
                         timeframe,
                         self.timezone,
@@ -501,19 +527,26 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
 
                     labels.extend(generated_labels);
                 }
-                Basis::Volume(_) | Basis::Range(_) => {}
             }
 
-            if let Some(cursor_pos) = cursor.position_in(self.chart_bounds)
+            AxisLabel::filter_and_draw(&labels, frame);
+        });
+
+        let crosshair = self.crosshair_cache.draw(renderer, bounds.size(), |frame| {
+            let region = self.visible_region(frame.size());
+
+            let crosshair_pos = cursor
+                .position_in(self.chart_bounds)
+                .or(self.synced_crosshair_x.map(|x| Point::new(x, 0.0)));
+
+            if let Some(cursor_pos) = crosshair_pos
                 && let Some(label) = self.generate_crosshair(cursor_pos, region, bounds, palette)
             {
-                labels.push(label);
+                label.draw(frame);
             }
-
-            AxisLabel::filter_and_draw(&labels, frame);
         });
 
-        vec![labels]
+        vec![labels, crosshair]
     }
 
     fn mouse_interaction(
@@ -531,9 +564,51 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
     }
 }
 
+fn draw_session_shading(
+    frame: &mut Frame,
+    sessions: &SessionManager,
+    x_min: u64,
+    x_max: u64,
+    bounds: Rectangle,
+    palette: &Extended,
+) {
+    if x_max <= x_min {
+        return;
+    }
+
+    let to_x = |timestamp: u64| {
+        let ratio = (timestamp as f64 - x_min as f64) / (x_max as f64 - x_min as f64);
+        (ratio * f64::from(bounds.width)) as f32
+    };
+
+    for span in sessions.spans_between(x_min, x_max) {
+        let color = match span.kind {
+            SessionKind::Asia => palette.secondary.base.color,
+            SessionKind::London => palette.primary.base.color,
+            SessionKind::NewYork => palette.success.base.color,
+            SessionKind::Custom => palette.warning.base.color,
+        };
+
+        let start_x = to_x(span.start).max(0.0);
+        let end_x = to_x(span.end).min(bounds.width);
+
+        frame.fill_rectangle(
+            Point::new(start_x, 0.0),
+            Size::new(end_x - start_x, bounds.height),
+            color.scale_alpha(0.12),
+        );
+        frame.fill_rectangle(
+            Point::new(start_x, 0.0),
+            Size::new(end_x - start_x, 2.0),
+            color.scale_alpha(0.6),
+        );
+    }
+}
+
 // Y-AXIS LABELS
 pub struct AxisLabelsY<'a> {
     pub labels_cache: &'a Cache,
+    pub crosshair_cache: &'a Cache,
     pub translation_y: f32,
     pub scaling: f32,
     pub min: f32,
@@ -543,6 +618,13 @@ pub struct AxisLabelsY<'a> {
     pub cell_height: f32,
     pub basis: Basis,
     pub chart_bounds: Rectangle,
+    /// Prices of drawing levels labeled on the axis, e.g. fib retracements
+    pub drawing_levels: Vec<f32>,
+    pub price_scale: PriceScale,
+    /// Venue whose clock the candle close countdown runs on
+    pub exchange: exchange::adapter::Exchange,
+    /// Simulated limits as id, side and price, `Some` when clicks on the axis place orders
+    pub paper_orders: Option<Vec<(u32, Side, f32)>>,
 }
 
 impl AxisLabelsY<'_> {
@@ -559,7 +641,32 @@ impl AxisLabelsY<'_> {
     }
 
     fn y_to_price(&self, y: f32) -> f32 {
-        self.min - (y / self.cell_height) * self.tick_size
+        self.price_scale
+            .price_at(self.min, y / self.cell_height, self.tick_size)
+    }
+
+    fn price_to_y(&self, price: f32) -> f32 {
+        self.price_scale
+            .ticks_below(self.min, price, self.tick_size)
+            * self.cell_height
+    }
+
+    /// Price under a height of the axis, rounded to the tick size
+    fn price_at(&self, y: f32, size: Size) -> f32 {
+        let region = self.visible_region(size);
+        round_to_tick(self.y_to_price(region.y + y / self.scaling), self.tick_size)
+    }
+
+    fn order_line_at(&self, y: f32, size: Size) -> Option<u32> {
+        let region = self.visible_region(size);
+
+        self.paper_orders
+            .as_ref()?
+            .iter()
+            .find_map(|(id, _, price)| {
+                let line_y = (self.price_to_y(*price) - region.y) * self.scaling;
+                ((line_y - y).abs() <= ORDER_GRAB_DISTANCE).then_some(*id)
+            })
     }
 }
 
@@ -574,23 +681,48 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
         cursor: mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
         if let Event::Mouse(mouse::Event::ButtonReleased(_)) = event {
-            *interaction = Interaction::None;
+            match std::mem::take(interaction) {
+                Interaction::MovingOrder { id, price } => {
+                    return Some(canvas::Action::publish(Message::OrderMoved(id, price)));
+                }
+                Interaction::PlacingOrder { start } => {
+                    let price = Price::from_f32(self.price_at(start.y, bounds.size()));
+                    return Some(canvas::Action::publish(Message::OrderPlaced(price)));
+                }
+                _ => {}
+            }
         }
 
         let cursor_position = cursor.position_in(bounds)?;
 
         if let Event::Mouse(mouse_event) = event {
             match mouse_event {
-                mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                    let message = Message::PriceScaleMenu(cursor_position.y);
+
+                    return Some(canvas::Action::publish(message).and_capture());
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) if self.paper_orders.is_some() => {
+                    *interaction = match self.order_line_at(cursor_position.y, bounds.size()) {
+                        Some(id) => Interaction::MovingOrder {
+                            id,
+                            price: Price::from_f32(self.price_at(cursor_position.y, bounds.size())),
+                        },
+                        None => Interaction::PlacingOrder {
+                            start: cursor_position,
+                        },
+                    };
+                    return Some(canvas::Action::request_redraw().and_capture());
+                }
+                mouse::Event::ButtonPressed(mouse::Button::Left) if !self.price_scale.locked => {
                     *interaction = Interaction::Zoomin {
                         last_position: cursor_position,
                     };
                 }
-                mouse::Event::CursorMoved { .. } => {
-                    if let Interaction::Zoomin {
+                mouse::Event::CursorMoved { .. } => match *interaction {
+                    Interaction::Zoomin {
                         ref mut last_position,
-                    } = *interaction
-                    {
+                    } => {
                         let difference_y = last_position.y - cursor_position.y;
 
                         if difference_y.abs() > 1.0 {
@@ -601,7 +733,25 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                             return Some(canvas::Action::publish(message).and_capture());
                         }
                     }
-                }
+                    Interaction::PlacingOrder { start }
+                        if !self.price_scale.locked
+                            && (start.y - cursor_position.y).abs() > ORDER_CLICK_SLOP =>
+                    {
+                        *interaction = Interaction::Zoomin {
+                            last_position: start,
+                        };
+                    }
+                    Interaction::MovingOrder { id, .. } => {
+                        *interaction = Interaction::MovingOrder {
+                            id,
+                            price: Price::from_f32(self.price_at(cursor_position.y, bounds.size())),
+                        };
+                        return Some(
+                            canvas::Action::publish(Message::CrosshairMoved).and_capture(),
+                        );
+                    }
+                    _ => {}
+                },
                 mouse::Event::WheelScrolled { delta } => match delta {
                     mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
                         let message = Message::YScaling(
@@ -630,7 +780,7 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
 
     fn draw(
         &self,
-        _state: &Self::State,
+        interaction: &Self::State,
         renderer: &Renderer,
         theme: &Theme,
         bounds: Rectangle,
@@ -641,28 +791,69 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
 
         let labels = self.labels_cache.draw(renderer, bounds.size(), |frame| {
             let region = self.visible_region(frame.size());
+            let to_screen_y = |price: f32| (self.price_to_y(price) - region.y) * self.scaling;
 
-            let highest = self.y_to_price(region.y);
-            let lowest = self.y_to_price(region.y + region.height);
+            let (highest, lowest) = {
+                let top = self.y_to_price(region.y);
+                let bottom = self.y_to_price(region.y + region.height);
+                (top.max(bottom), top.min(bottom))
+            };
 
-            let range = highest - lowest;
-
-            let mut all_labels = linear::generate_labels(
+            let mut all_labels = linear::generate_labels_with(
                 bounds,
                 lowest,
                 highest,
                 text_size,
                 palette.background.base.text,
                 Some(self.decimals),
+                to_screen_y,
             );
 
+            for &price in &self.drawing_levels {
+                if price < lowest || price > highest {
+                    continue;
+                }
+
+                all_labels.push(AxisLabel::Y {
+                    bounds: calc_label_rect(to_screen_y(price), 1, text_size, bounds),
+                    value_label: LabelContent {
+                        content: format!("{:.*}", self.decimals, price),
+                        background_color: Some(palette.primary.weak.color),
+                        text_color: palette.primary.weak.text,
+                        text_size: 11.0,
+                    },
+                    timer_label: None,
+                });
+            }
+
+            for &(_, side, price) in self.paper_orders.iter().flatten() {
+                if price < lowest || price > highest {
+                    continue;
+                }
+                let pair = match side {
+                    Side::Buy => palette.success.base,
+                    Side::Sell => palette.danger.base,
+                };
+
+                all_labels.push(AxisLabel::Y {
+                    bounds: calc_label_rect(to_screen_y(price), 1, text_size, bounds),
+                    value_label: LabelContent {
+                        content: format!("{:.*}", self.decimals, price),
+                        background_color: Some(pair.color),
+                        text_color: pair.text,
+                        text_size: 11.0,
+                    },
+                    timer_label: None,
+                });
+            }
+
             // Last price (priority 2)
             if let Some(label) = self.last_price {
                 let candle_close_label = match self.basis {
                     Basis::Time(timeframe) => {
                         let interval = timeframe.to_milliseconds();
 
-                        let current_time = chrono::Utc::now().timestamp_millis() as u64;
+                        let current_time = exchange::clock::now_ms(self.exchange);
                         let next_kline_open = (current_time / interval + 1) * interval;
 
                         let remaining_seconds = (next_kline_open - current_time) / 1000;
@@ -715,7 +906,7 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                     text_size: 12.0,
                 };
 
-                let y_pos = bounds.height - ((price - lowest) / range * bounds.height);
+                let y_pos = to_screen_y(price);
                 let content_amt = if candle_close_label.is_some() { 2 } else { 1 };
 
                 all_labels.push(AxisLabel::Y {
@@ -725,13 +916,35 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                 });
             }
 
-            // Crosshair price (priority 3)
+            AxisLabel::filter_and_draw(&all_labels, frame);
+        });
+
+        let crosshair = self.crosshair_cache.draw(renderer, bounds.size(), |frame| {
+            let region = self.visible_region(frame.size());
+            let to_screen_y = |price: f32| (self.price_to_y(price) - region.y) * self.scaling;
+
+            if let Interaction::MovingOrder { price, .. } = interaction {
+                let price = price.to_f32();
+
+                AxisLabel::Y {
+                    bounds: calc_label_rect(to_screen_y(price), 1, text_size, bounds),
+                    value_label: LabelContent {
+                        content: format!("{:.*}", self.decimals, price),
+                        background_color: Some(palette.primary.base.color),
+                        text_color: palette.primary.base.text,
+                        text_size: 12.0,
+                    },
+                    timer_label: None,
+                }
+                .draw(frame);
+            }
+
             if let Some(crosshair_pos) = cursor.position_in(self.chart_bounds) {
                 let rounded_price = round_to_tick(
-                    lowest + (range * (bounds.height - crosshair_pos.y) / bounds.height),
+                    self.y_to_price(region.y + crosshair_pos.y / self.scaling),
                     self.tick_size,
                 );
-                let y_position = bounds.height - ((rounded_price - lowest) / range * bounds.height);
+                let y_position = to_screen_y(rounded_price);
 
                 let label = LabelContent {
                     content: format!("{:.*}", self.decimals, rounded_price),
@@ -740,17 +953,16 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                     text_size: 12.0,
                 };
 
-                all_labels.push(AxisLabel::Y {
+                AxisLabel::Y {
                     bounds: calc_label_rect(y_position, 1, text_size, bounds),
                     value_label: label,
                     timer_label: None,
-                });
+                }
+                .draw(frame);
             }
-
-            AxisLabel::filter_and_draw(&all_labels, frame);
         });
 
-        vec![labels]
+        vec![labels, crosshair]
     }
 
     fn mouse_interaction(
@@ -762,7 +974,19 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
         match interaction {
             Interaction::Zoomin { .. } => mouse::Interaction::ResizingVertically,
             Interaction::Panning { .. } => mouse::Interaction::None,
-            Interaction::None if cursor.is_over(bounds) => mouse::Interaction::ResizingVertically,
+            Interaction::MovingOrder { .. } => mouse::Interaction::Grabbing,
+            Interaction::None | Interaction::PlacingOrder { .. } if self.paper_orders.is_some() => {
+                match cursor.position_in(bounds) {
+                    Some(position) if self.order_line_at(position.y, bounds.size()).is_some() => {
+                        mouse::Interaction::Grab
+                    }
+                    Some(_) => mouse::Interaction::Pointer,
+                    None => mouse::Interaction::default(),
+                }
+            }
+            Interaction::None if cursor.is_over(bounds) && !self.price_scale.locked => {
+                mouse::Interaction::ResizingVertically
+            }
             _ => mouse::Interaction::default(),
         }
     }
@@ -30,6 +30,27 @@ pub fn generate_labels(
     text_size: f32,
     text_color: iced::Color,
     decimals: Option<usize>,
+) -> Vec<AxisLabel> {
+    generate_labels_with(
+        bounds,
+        lowest,
+        highest,
+        text_size,
+        text_color,
+        decimals,
+        |value| bounds.height - ((value - lowest) / (highest - lowest) * bounds.height),
+    )
+}
+
+/// Like `generate_labels`, placing each value at `to_y(value)` for axes that aren't linear
+pub fn generate_labels_with(
+    bounds: iced::Rectangle,
+    lowest: f32,
+    highest: f32,
+    text_size: f32,
+    text_color: iced::Color,
+    decimals: Option<usize>,
+    to_y: impl Fn(f32) -> f32,
 ) -> Vec<AxisLabel> {
     if !lowest.is_finite() || !highest.is_finite() {
         return Vec::new();
@@ -85,9 +106,7 @@ pub fn generate_labels(
                 text_size,
             };
 
-            let clamped_value = value.max(lowest).min(highest);
-            let label_pos =
-                bounds.height - ((clamped_value - lowest) / (highest - lowest) * bounds.height);
+            let label_pos = to_y(value.max(lowest).min(highest));
 
             labels.push(AxisLabel::Y {
                 bounds: calc_label_rect(label_pos, 1, text_size, bounds),
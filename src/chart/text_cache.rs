@@ -0,0 +1,67 @@
+//! Shaped extents of chart label text, shared by every pane. Prices and times repeat across
+//! axes, crosshairs and panes, so each distinct run is shaped once per content, size and font
+//! rather than on every cache rebuild or cursor move
+
+use crate::{chart::TEXT_SIZE, style::AZERET_MONO};
+
+use iced::advanced::text::{self, Paragraph as _};
+use iced::{Font, Renderer, Size};
+use rustc_hash::FxHashMap;
+use std::sync::{LazyLock, Mutex};
+
+type RunParagraph = <Renderer as text::Renderer>::Paragraph;
+
+/// Distinct runs kept before starting over, far above what a few panes show at once
+const CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RunKey {
+    content: Box<str>,
+    size_bits: u32,
+    font: Font,
+}
+
+static RUNS: LazyLock<Mutex<FxHashMap<RunKey, Size>>> = LazyLock::new(Mutex::default);
+
+/// Laid out size of `content` on a single line, in logical pixels so it holds at any scale factor
+pub fn run_size(content: &str, size: f32, font: Font) -> Size {
+    let key = RunKey {
+        content: content.into(),
+        size_bits: size.to_bits(),
+        font,
+    };
+
+    let mut runs = RUNS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(&run) = runs.get(&key) {
+        return run;
+    }
+
+    let run = shape(content, size, font);
+    if runs.len() >= CAPACITY {
+        runs.clear();
+    }
+    runs.insert(key, run);
+    run
+}
+
+/// Width of `content` in the chart's label font and size
+pub fn label_width(content: &str) -> f32 {
+    run_size(content, TEXT_SIZE, AZERET_MONO).width
+}
+
+fn shape(content: &str, size: f32, font: Font) -> Size {
+    RunParagraph::with_text(text::Text {
+        content,
+        bounds: Size::INFINITE,
+        size: size.into(),
+        line_height: text::LineHeight::default(),
+        font,
+        align_x: text::Alignment::Default,
+        align_y: iced::alignment::Vertical::Top,
+        shaping: text::Shaping::Basic,
+        wrapping: text::Wrapping::None,
+    })
+    .min_bounds()
+}
@@ -1,5 +1,9 @@
 use crate::modal::layout_manager::LayoutManager;
-use crate::screen::dashboard::{Dashboard, pane};
+use crate::screen::dashboard::{
+    Dashboard, pane,
+    panel::{heatgrid::HeatGrid, journal::Journal, watchlist::Watchlist},
+};
+use crate::window;
 use data::{
     UserTimezone,
     layout::{WindowSpec, pane::Axis},
@@ -28,8 +32,14 @@ pub struct SavedState {
     pub sidebar: data::Sidebar,
     pub theme: data::Theme,
     pub custom_theme: Option<data::Theme>,
+    pub saved_themes: Vec<data::Theme>,
+    pub auto_theme: data::config::theme::AutoTheme,
     pub audio_cfg: data::AudioStream,
     pub volume_size_unit: exchange::SizeUnit,
+    pub export_schedule: data::export::ExportSchedule,
+    pub idle: data::idle::IdleSettings,
+    pub autosave: data::autosave::AutosaveSettings,
+    pub paper_trading: data::paper::Config,
 }
 
 impl SavedState {
@@ -56,51 +66,74 @@ impl Default for SavedState {
             sidebar: data::Sidebar::default(),
             theme: data::Theme::default(),
             custom_theme: None,
+            saved_themes: vec![],
+            auto_theme: data::config::theme::AutoTheme::default(),
             audio_cfg: data::AudioStream::default(),
             volume_size_unit: exchange::SizeUnit::Base,
+            export_schedule: data::export::ExportSchedule::default(),
+            idle: data::idle::IdleSettings::default(),
+            autosave: data::autosave::AutosaveSettings::default(),
+            paper_trading: data::paper::Config::default(),
         }
     }
 }
 
-impl From<&Dashboard> for data::Dashboard {
-    fn from(dashboard: &Dashboard) -> Self {
-        use pane_grid::Node;
-
-        fn from_layout(panes: &pane_grid::State<pane::State>, node: pane_grid::Node) -> data::Pane {
-            match node {
-                Node::Split {
-                    axis, ratio, a, b, ..
-                } => data::Pane::Split {
-                    axis: match axis {
-                        pane_grid::Axis::Horizontal => Axis::Horizontal,
-                        pane_grid::Axis::Vertical => Axis::Vertical,
-                    },
-                    ratio,
-                    a: Box::new(from_layout(panes, *a)),
-                    b: Box::new(from_layout(panes, *b)),
+/// Destructive edit that can be undone, holding what it replaced
+pub enum Edit {
+    /// Pane trees of each window of a layout, before panes were closed, swapped or cleared
+    Panes {
+        layout: Uuid,
+        grids: Vec<(window::Id, data::Pane)>,
+    },
+    /// Layout taken out of the manager, with its position in the list
+    LayoutRemoved { index: usize, layout: Box<Layout> },
+    /// Removed layout put back, redoing removes it again
+    LayoutRestored(Uuid),
+}
+
+/// Serialized form of the pane tree of one window
+pub fn pane_tree(panes: &pane_grid::State<pane::State>) -> data::Pane {
+    use pane_grid::Node;
+
+    fn from_layout(panes: &pane_grid::State<pane::State>, node: pane_grid::Node) -> data::Pane {
+        match node {
+            Node::Split {
+                axis, ratio, a, b, ..
+            } => data::Pane::Split {
+                axis: match axis {
+                    pane_grid::Axis::Horizontal => Axis::Horizontal,
+                    pane_grid::Axis::Vertical => Axis::Vertical,
                 },
-                Node::Pane(pane) => panes
-                    .get(pane)
-                    .map_or(data::Pane::default(), data::Pane::from),
-            }
+                ratio,
+                a: Box::new(from_layout(panes, *a)),
+                b: Box::new(from_layout(panes, *b)),
+            },
+            Node::Pane(pane) => panes
+                .get(pane)
+                .map_or(data::Pane::default(), data::Pane::from),
         }
+    }
 
-        let main_window_layout = dashboard.panes.layout().clone();
+    from_layout(panes, panes.layout().clone())
+}
 
+impl From<&Dashboard> for data::Dashboard {
+    fn from(dashboard: &Dashboard) -> Self {
         let popouts_layout: Vec<(data::Pane, WindowSpec)> = dashboard
             .popout
             .iter()
-            .map(|(_, (pane, spec))| (from_layout(pane, pane.layout().clone()), *spec))
+            .map(|(_, (pane, spec))| (pane_tree(pane), *spec))
             .collect();
 
         data::Dashboard {
-            pane: from_layout(&dashboard.panes, main_window_layout),
+            pane: pane_tree(&dashboard.panes),
             popout: {
                 popouts_layout
                     .iter()
                     .map(|(pane, window_spec)| (pane.clone(), *window_spec))
                     .collect()
             },
+            synced_view_groups: dashboard.synced_view_groups.clone(),
         }
     }
 }
@@ -153,6 +186,21 @@ impl From<&pane::State> for data::Pane {
                 settings: pane.settings.clone(),
                 link_group: pane.link_group,
             },
+            pane::Content::VolumeProfile(_) => data::Pane::VolumeProfile {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
+            pane::Content::DepthInspector(_) => data::Pane::DepthInspector {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
+            pane::Content::TradeSizes(_) => data::Pane::TradeSizes {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
             pane::Content::Comparison(chart) => {
                 let settings = data::layout::pane::Settings {
                     visual_config: chart.as_ref().map(|c| {
@@ -167,6 +215,26 @@ impl From<&pane::State> for data::Pane {
                     link_group: pane.link_group,
                 }
             }
+            pane::Content::Watchlist(watchlist) => data::Pane::Watchlist {
+                settings: data::layout::pane::Settings {
+                    visual_config: Some(data::layout::pane::VisualConfig::Watchlist(
+                        watchlist.config.clone(),
+                    )),
+                    ..pane.settings.clone()
+                },
+                link_group: pane.link_group,
+            },
+            pane::Content::HeatGrid(grid) => data::Pane::HeatGrid {
+                settings: data::layout::pane::Settings {
+                    visual_config: Some(data::layout::pane::VisualConfig::HeatGrid(grid.config)),
+                    ..pane.settings.clone()
+                },
+                link_group: pane.link_group,
+            },
+            pane::Content::Journal(_) => data::Pane::Journal {
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
         }
     }
 }
@@ -274,29 +342,118 @@ pub fn configuration(pane: data::Pane) -> Configuration<pane::State> {
                 link_group,
             ))
         }
+        data::Pane::VolumeProfile {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::VolumeProfile(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        data::Pane::DepthInspector {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::DepthInspector(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        data::Pane::TradeSizes {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::TradeSizes(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        data::Pane::Watchlist {
+            settings,
+            link_group,
+        } => {
+            let config = settings
+                .visual_config
+                .as_ref()
+                .and_then(|cfg| cfg.watchlist());
+            let content = pane::Content::Watchlist(Watchlist::new(config));
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                vec![],
+                settings,
+                link_group,
+            ))
+        }
+        data::Pane::HeatGrid {
+            settings,
+            link_group,
+        } => {
+            let config = settings
+                .visual_config
+                .as_ref()
+                .and_then(|cfg| cfg.heatgrid());
+            let content = pane::Content::HeatGrid(HeatGrid::new(config));
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                vec![],
+                settings,
+                link_group,
+            ))
+        }
+        data::Pane::Journal {
+            settings,
+            link_group,
+        } => Configuration::Pane(pane::State::from_config(
+            pane::Content::Journal(Journal::new()),
+            vec![],
+            settings,
+            link_group,
+        )),
     }
 }
 
+/// Rebuilds a dashboard, popouts included, from its serialized form
+pub fn dashboard_from_config(dashboard: &data::Dashboard, layout_id: Uuid) -> Dashboard {
+    let popout_windows = dashboard
+        .popout
+        .iter()
+        .map(|(pane, window_spec)| (configuration(pane.clone()), *window_spec))
+        .collect();
+
+    Dashboard::from_config(
+        configuration(dashboard.pane.clone()),
+        popout_windows,
+        layout_id,
+        dashboard.synced_view_groups.clone(),
+    )
+}
+
 pub fn load_saved_state() -> SavedState {
     match data::read_from_file(data::SAVED_STATE_PATH) {
         Ok(state) => {
             let mut de_layouts = vec![];
 
             for layout in &state.layout_manager.layouts {
-                let mut popout_windows = Vec::new();
-
-                for (pane, window_spec) in &layout.dashboard.popout {
-                    let configuration = configuration(pane.clone());
-                    popout_windows.push((configuration, *window_spec));
-                }
-
                 let layout_id = Uuid::new_v4();
-
-                let dashboard = Dashboard::from_config(
-                    configuration(layout.dashboard.pane.clone()),
-                    popout_windows,
-                    layout_id,
-                );
+                let dashboard = dashboard_from_config(&layout.dashboard, layout_id);
 
                 de_layouts.push((layout.name.clone(), layout_id, dashboard));
             }
@@ -327,12 +484,37 @@ pub fn load_saved_state() -> SavedState {
                 LayoutManager::from_config(layouts, active_layout)
             };
 
-            exchange::fetcher::toggle_trade_fetch(state.trade_fetch_enabled);
+            if state.trade_fetch_enabled {
+                exchange::fetcher::toggle_trade_fetch(
+                    exchange::adapter::ExchangeInclusive::Binance,
+                    true,
+                );
+            }
+            for venue in state.trade_fetch_venues {
+                exchange::fetcher::toggle_trade_fetch(venue, true);
+            }
+            exchange::network::set_offline(state.offline_mode);
+            exchange::network::set_allowed_hosts(state.allowed_hosts);
+            exchange::network::set_demo(state.demo_mode);
+            if let Some(max) = state.max_concurrent_requests {
+                exchange::limiter::set_max_concurrent(max);
+            }
+            if let Err(err) = exchange::network::set_proxy(state.proxy) {
+                log::error!("Failed to set up the saved proxy: {err}");
+            }
+            match data::credentials::load() {
+                Ok(store) => store.into_iter().for_each(|(venue, credentials)| {
+                    exchange::auth::set_credentials(venue, Some(credentials));
+                }),
+                Err(err) => log::error!("Failed to load saved API keys: {err}"),
+            }
             exchange::set_preferred_currency(state.size_in_quote_ccy);
 
             SavedState {
                 theme: state.selected_theme,
                 custom_theme: state.custom_theme,
+                saved_themes: state.saved_themes,
+                auto_theme: state.auto_theme,
                 layout_manager,
                 main_window: state.main_window,
                 timezone: state.timezone,
@@ -340,6 +522,10 @@ pub fn load_saved_state() -> SavedState {
                 scale_factor: state.scale_factor,
                 audio_cfg: state.audio_cfg,
                 volume_size_unit: state.size_in_quote_ccy,
+                export_schedule: state.export_schedule,
+                idle: state.idle,
+                autosave: state.autosave,
+                paper_trading: state.paper_trading,
             }
         }
         Err(e) => {
@@ -10,10 +10,14 @@ mod style;
 mod widget;
 mod window;
 
-use data::config::theme::default_theme;
+use data::config::theme::{AutoSwitch, AutoTheme, ThemeManager};
 use data::{layout::WindowSpec, sidebar};
-use layout::{LayoutId, configuration};
-use modal::{LayoutManager, ThemeEditor, audio::AudioStream};
+use layout::{Edit, LayoutId, dashboard_from_config};
+use modal::{
+    CommandPalette, LayoutManager, ReplaceTicker, ThemeEditor,
+    audio::AudioStream,
+    command_palette::{self, Category, Command},
+};
 use modal::{dashboard_modal, main_dialog_modal};
 use screen::dashboard::{self, Dashboard};
 use widget::{
@@ -31,6 +35,20 @@ use iced::{
 };
 use std::{borrow::Cow, collections::HashMap, vec};
 
+/// Stream events held back while live updates are paused, past this the view resumes on its own
+const MAX_PAUSED_EVENTS: usize = 200_000;
+const CLOCK_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+fn measure_clock(exchange: exchange::adapter::Exchange) -> Task<Message> {
+    if !exchange::clock::is_supported(exchange) {
+        return Task::none();
+    }
+
+    Task::perform(exchange::clock::measure(exchange), move |result| {
+        Message::ClockMeasured(exchange, result.map_err(|err| err.to_string()))
+    })
+}
+
 fn main() {
     logger::setup(cfg!(debug_assertions)).expect("Failed to initialize logger");
 
@@ -60,11 +78,40 @@ struct LuxChart {
     theme_editor: ThemeEditor,
     audio_stream: AudioStream,
     confirm_dialog: Option<screen::ConfirmDialog<Message>>,
+    command_palette: Option<CommandPalette>,
+    replace_ticker: ReplaceTicker,
     volume_size_unit: exchange::SizeUnit,
+    export_schedule: data::export::ExportSchedule,
+    idle_settings: data::idle::IdleSettings,
+    /// Last mouse or keyboard input, to tell when the user stepped away
+    last_input: std::time::Instant,
+    is_idle: bool,
+    autosave_settings: data::autosave::AutosaveSettings,
+    autosave: data::autosave::Autosave,
+    /// Closed panes, swapped streams, deleted drawings and removed layouts to bring back
+    history: data::history::History<Edit>,
     ui_scale_factor: data::ScaleFactor,
     timezone: data::UserTimezone,
     theme: data::Theme,
+    theme_manager: ThemeManager,
+    auto_theme: AutoTheme,
+    /// Tone the OS prefers, `None` until reported or if it has no preference
+    system_is_dark: Option<bool>,
     notifications: Vec<Toast>,
+    /// Offline mode allowlist as typed, comma separated
+    allowed_hosts_input: String,
+    /// Proxy settings being edited, applied on demand
+    proxy_draft: exchange::network::Proxy,
+    proxy_port_input: String,
+    /// Venue the API key fields edit
+    credentials_venue: exchange::adapter::ExchangeInclusive,
+    credentials_draft: exchange::auth::Credentials,
+    /// Simulated orders and positions, filled off the streams of the active layout
+    paper: data::paper::Account,
+    paper_notional_input: String,
+    /// Stream data received since live updates were paused, `None` while they're live
+    paused_events: Option<Vec<exchange::Event>>,
+    connection_health: data::connection::ConnectionHealth,
 }
 
 #[derive(Debug, Clone)]
@@ -77,21 +124,70 @@ enum Message {
         event: dashboard::Message,
     },
     Tick(std::time::Instant),
+    UserActivity,
+    IdleSettingsChanged(data::idle::IdleSettings),
+    AutosaveSettingsChanged(data::autosave::AutosaveSettings),
+    AutosaveRequested(HashMap<window::Id, WindowSpec>),
     WindowEvent(window::Event),
     ExitRequested(HashMap<window::Id, WindowSpec>),
     RestartRequested(HashMap<window::Id, WindowSpec>),
     GoBack,
     DataFolderRequested,
     ThemeSelected(data::Theme),
+    AutoThemeChanged(AutoTheme),
+    SystemThemeChanged(iced::theme::Mode),
+    RemapSeriesColors,
+    /// Rebuilds panes with the tick sizes exchanges switched these tickers to
+    ApplyTickSizes(Vec<exchange::TickerInfo>),
     ScaleFactorChanged(data::ScaleFactor),
     SetTimezone(data::UserTimezone),
-    ToggleTradeFetch(bool),
+    ToggleTradeFetch(exchange::adapter::ExchangeInclusive, bool),
+    ToggleOfflineMode(bool),
+    AllowedHostsChanged(String),
+    ProxyDraftChanged(exchange::network::Proxy),
+    ProxyPortChanged(String),
+    /// Applies the drafted proxy, or turns it off
+    ApplyProxy(bool),
+    CredentialsVenueChanged(exchange::adapter::ExchangeInclusive),
+    CredentialsDraftChanged(exchange::auth::Credentials),
+    /// Pings an authenticated endpoint with the drafted key
+    TestCredentials,
+    CredentialsTested(exchange::adapter::ExchangeInclusive, Result<(), String>),
+    /// Saves the drafted key of the venue, or removes it
+    SaveCredentials(bool),
+    /// Order entry hotkey, for the ticker of the focused pane
+    PaperTrade(data::paper::Command),
+    PaperNotionalChanged(String),
+    PaperFeeChanged(f32),
+    ChartTradingToggled(bool),
+    ResetPaperAccount,
+    /// Samples REST latency and clock offset of the venues being streamed from
+    MeasureClocks,
+    ClockMeasured(
+        exchange::adapter::Exchange,
+        Result<exchange::clock::ClockSample, String>,
+    ),
+    MaxConcurrentRequestsChanged(usize),
     ApplyVolumeSizeUnit(exchange::SizeUnit),
+    ApplyDemoMode(bool),
+    ExportScheduleChanged(data::export::ExportSchedule),
+    ExportNow,
+    SnapshotsExported(Result<usize, String>),
+    PaneImageExported(Result<std::path::PathBuf, String>),
+    JournalExported(Result<std::path::PathBuf, String>),
+    WorkspaceExported(Result<std::path::PathBuf, String>),
+    WorkspaceLoaded(Result<data::config::workspace::Workspace, String>),
     RemoveNotification(usize),
     ToggleDialogModal(Option<screen::ConfirmDialog<Message>>),
     ThemeEditor(modal::theme_editor::Message),
     Layouts(modal::layout_manager::Message),
+    ReplaceTicker(modal::replace_ticker::Message),
     AudioStream(modal::audio::Message),
+    ToggleCommandPalette,
+    Undo,
+    Redo,
+    CommandPalette(command_palette::Message),
+    ToggleLiveUpdates,
 }
 
 impl LuxChart {
@@ -116,15 +212,39 @@ impl LuxChart {
         let mut state = Self {
             main_window: window::Window::new(main_window_id),
             layout_manager: saved_state.layout_manager,
-            theme_editor: ThemeEditor::new(saved_state.custom_theme),
+            theme_editor: ThemeEditor::new(saved_state.custom_theme, saved_state.saved_themes),
             audio_stream,
             sidebar,
             confirm_dialog: None,
+            command_palette: None,
+            replace_ticker: ReplaceTicker::new(),
             timezone: saved_state.timezone,
             ui_scale_factor: saved_state.scale_factor,
             volume_size_unit: saved_state.volume_size_unit,
+            export_schedule: saved_state.export_schedule,
+            idle_settings: saved_state.idle,
+            last_input: std::time::Instant::now(),
+            is_idle: false,
+            autosave_settings: saved_state.autosave,
+            autosave: data::autosave::Autosave::new(std::time::Instant::now()),
+            history: data::history::History::default(),
             theme: saved_state.theme,
+            theme_manager: ThemeManager::new(),
+            auto_theme: saved_state.auto_theme,
+            system_is_dark: None,
             notifications: vec![],
+            allowed_hosts_input: exchange::network::allowed_hosts().join(", "),
+            proxy_draft: exchange::network::proxy().unwrap_or_default(),
+            proxy_port_input: exchange::network::proxy()
+                .map(|proxy| proxy.port.to_string())
+                .unwrap_or_default(),
+            credentials_venue: exchange::auth::VENUES[0],
+            credentials_draft: exchange::auth::credentials(exchange::auth::VENUES[0])
+                .unwrap_or_default(),
+            paper: data::paper::Account::new(saved_state.paper_trading),
+            paper_notional_input: saved_state.paper_trading.order_notional.to_string(),
+            paused_events: None,
+            connection_health: data::connection::ConnectionHealth::default(),
         };
 
         if let Some(err) = audio_init_err {
@@ -148,69 +268,140 @@ impl LuxChart {
             open_main_window
                 .discard()
                 .chain(load_layout)
-                .chain(launch_sidebar.map(Message::Sidebar)),
+                .chain(launch_sidebar.map(Message::Sidebar))
+                .chain(iced::system::theme().map(Message::SystemThemeChanged)),
         )
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::MarketWsEvent(event) => {
+                self.track_connection_health(&event);
+
                 let main_window_id = self.main_window.id;
                 let dashboard = self.active_dashboard_mut();
 
                 match event {
                     exchange::Event::Connected(exchange) => {
                         log::info!("a stream connected to {exchange} WS");
-                    }
-                    exchange::Event::Disconnected(exchange, reason) => {
-                        log::info!("a stream disconnected from {exchange} WS: {reason:?}");
-                    }
-                    exchange::Event::DepthReceived(
-                        stream,
-                        depth_update_t,
-                        depth,
-                        trades_buffer,
-                    ) => {
-                        let task = dashboard
-                            .update_depth_and_trades(
-                                &stream,
-                                depth_update_t,
-                                &depth,
-                                &trades_buffer,
-                                main_window_id,
-                            )
+                        dashboard.set_market_closed(exchange, None, main_window_id);
+                        dashboard.set_reconnecting(exchange, None, main_window_id);
+
+                        let backfill = dashboard
+                            .backfill_after_reconnect(exchange, main_window_id)
                             .map(move |msg| Message::Dashboard {
                                 layout_id: None,
                                 event: msg,
                             });
 
-                        if let Some(msg) = self.audio_stream.try_play_sound(&stream, &trades_buffer)
-                        {
-                            self.notifications.push(Toast::error(msg));
+                        if exchange::clock::sample(exchange).is_none() {
+                            return Task::batch([backfill, measure_clock(exchange)]);
                         }
-
-                        return task;
+                        return backfill;
                     }
-                    exchange::Event::KlineReceived(stream, kline) => {
-                        return dashboard
-                            .update_latest_klines(&stream, &kline, main_window_id)
-                            .map(move |msg| Message::Dashboard {
-                                layout_id: None,
-                                event: msg,
-                            });
+                    exchange::Event::Disconnected(exchange, reason) => {
+                        log::info!("a stream disconnected from {exchange} WS: {reason:?}");
+                        dashboard.mark_disconnected(exchange);
+                    }
+                    exchange::Event::Reconnecting {
+                        exchange,
+                        attempt,
+                        next_in,
+                    } => {
+                        log::info!(
+                            "retrying {exchange} WS in {:.1}s, attempt {attempt}",
+                            next_in.as_secs_f32()
+                        );
+                        let retry_at = chrono::Utc::now().timestamp_millis() as u64
+                            + next_in.as_millis() as u64;
+                        dashboard.set_reconnecting(
+                            exchange,
+                            Some((attempt, retry_at)),
+                            main_window_id,
+                        );
+                    }
+                    exchange::Event::Telemetry { .. } => {}
+                    exchange::Event::MarketClosed(exchange, reopens_at) => {
+                        log::info!(
+                            "{exchange} is closed until {reopens_at}, holding off reconnects"
+                        );
+                        dashboard.set_market_closed(exchange, Some(reopens_at), main_window_id);
+                    }
+                    event => {
+                        self.play_stream_alerts(&event);
+
+                        let Some(buffer) = &mut self.paused_events else {
+                            return self.apply_market_data(event);
+                        };
+                        if buffer.len() < MAX_PAUSED_EVENTS {
+                            buffer.push(event);
+                            return Task::none();
+                        }
+
+                        self.notifications.push(Toast::warn(
+                            "Too much data buffered while paused, resuming live updates",
+                        ));
+                        let resume = self.resume_live_updates();
+                        return Task::batch([resume, self.apply_market_data(event)]);
                     }
                 }
             }
             Message::Tick(now) => {
                 let main_window_id = self.main_window.id;
+                self.connection_health.prune(now);
+
+                for throttle in exchange::limiter::take_throttles() {
+                    self.notifications.push(Toast::warn(format!(
+                        "{} rate limit reached, a request waits {:.0}s",
+                        throttle.venue,
+                        throttle.wait.as_secs_f32()
+                    )));
+                }
+
+                if !self.is_idle
+                    && self
+                        .idle_settings
+                        .is_idle(now.saturating_duration_since(self.last_input))
+                {
+                    log::info!("No input for a while, switching to economy mode");
+                    self.is_idle = true;
+                }
+                let is_idle = self.is_idle;
+
+                let export = match self
+                    .export_schedule
+                    .due_day(chrono::Utc::now().timestamp_millis() as u64)
+                {
+                    Some(day) => {
+                        self.export_schedule.last_run_day = Some(day);
+                        let (from, to) = self.export_schedule.window(day);
+                        self.export_snapshots(from, to)
+                    }
+                    None => Task::none(),
+                };
+
+                let autosave = if self.autosave.is_due(&self.autosave_settings, now) {
+                    self.autosave.saved(now);
+                    self.autosave_state()
+                } else {
+                    Task::none()
+                };
 
-                return self
+                let tick = self
                     .active_dashboard_mut()
-                    .tick(now, main_window_id)
+                    .tick(now, main_window_id, is_idle)
                     .map(move |msg| Message::Dashboard {
                         layout_id: None,
                         event: msg,
                     });
+
+                let auto_theme = if self.auto_theme.switch == AutoSwitch::Scheduled {
+                    self.apply_auto_theme()
+                } else {
+                    Task::none()
+                };
+
+                return Task::batch([tick, export, autosave, auto_theme, self.run_failovers()]);
             }
             Message::WindowEvent(event) => match event {
                 window::Event::CloseRequested(window) => {
@@ -245,6 +436,8 @@ impl LuxChart {
 
                 if self.confirm_dialog.is_some() {
                     self.confirm_dialog = None;
+                } else if self.command_palette.is_some() {
+                    self.command_palette = None;
                 } else if self.sidebar.active_menu().is_some() {
                     self.sidebar.set_menu(None);
                 } else {
@@ -259,8 +452,78 @@ impl LuxChart {
                     }
                 }
             }
+            Message::AutoThemeChanged(auto_theme) => {
+                self.auto_theme = auto_theme;
+                return self.apply_auto_theme();
+            }
+            Message::SystemThemeChanged(mode) => {
+                self.system_is_dark = match mode {
+                    iced::theme::Mode::Dark => Some(true),
+                    iced::theme::Mode::Light => Some(false),
+                    iced::theme::Mode::None => None,
+                };
+                return self.apply_auto_theme();
+            }
             Message::ThemeSelected(theme) => {
+                let was_dark = data::config::theme::is_dark(self.theme.0.palette().background);
                 self.theme = theme.clone();
+
+                let background = self.theme.0.palette().background;
+                if data::config::theme::is_dark(background) != was_dark {
+                    let main_window = self.main_window.id;
+                    let low_contrast: usize = self
+                        .layout_manager
+                        .layouts
+                        .iter()
+                        .map(|layout| {
+                            layout
+                                .dashboard
+                                .low_contrast_colors(main_window, background)
+                        })
+                        .sum();
+
+                    if low_contrast > 0 {
+                        let confirm_dialog = screen::ConfirmDialog::new(
+                            format!(
+                                "{low_contrast} custom series color(s) may be hard to read on this theme. Adjust their lightness?"
+                            ),
+                            Box::new(Message::RemapSeriesColors),
+                        )
+                        .with_confirm_btn_text("Adjust colors".to_string());
+
+                        self.confirm_dialog = Some(confirm_dialog);
+                    }
+                }
+            }
+            Message::RemapSeriesColors => {
+                let main_window = self.main_window.id;
+                let background = self.theme.0.palette().background;
+
+                self.layout_manager
+                    .iter_dashboards_mut()
+                    .for_each(|dashboard| {
+                        dashboard.remap_low_contrast_colors(main_window, background);
+                    });
+
+                self.confirm_dialog = None;
+            }
+            Message::ApplyTickSizes(fresh) => {
+                let main_window = self.main_window.id;
+                self.confirm_dialog = None;
+
+                let tasks = self.layout_manager.layouts.iter_mut().map(|layout| {
+                    let layout_id = layout.id.unique;
+
+                    layout
+                        .dashboard
+                        .apply_ticker_info(main_window, &fresh)
+                        .map(move |msg| Message::Dashboard {
+                            layout_id: Some(layout_id),
+                            event: msg,
+                        })
+                });
+
+                return Task::batch(tasks.collect::<Vec<_>>());
             }
             Message::Dashboard {
                 layout_id: id,
@@ -274,7 +537,18 @@ impl LuxChart {
                 let main_window = self.main_window;
                 let layout_id = id.unwrap_or(active_layout.unique);
 
+                if msg.is_structural() {
+                    self.autosave.mark_changed(std::time::Instant::now());
+                }
+
                 if let Some(dashboard) = self.layout_manager.mut_dashboard(layout_id) {
+                    if msg.is_undoable() {
+                        self.history.record(Edit::Panes {
+                            layout: layout_id,
+                            grids: dashboard.grids(main_window.id),
+                        });
+                    }
+
                     let (main_task, event) = dashboard.update(msg, &main_window, &layout_id);
 
                     let additional_task = match event {
@@ -342,6 +616,40 @@ impl LuxChart {
                                 }
                             }
                         }
+                        Some(dashboard::Event::ResolveMergedFeed {
+                            pane_id,
+                            ticker,
+                            venues,
+                        }) => {
+                            let tickers_info = self.sidebar.tickers_info();
+                            if !tickers_info.values().any(Option::is_some) {
+                                return Task::none();
+                            }
+
+                            let listings = exchange::composite::merged_listings(
+                                &ticker,
+                                &venues,
+                                tickers_info.keys(),
+                            )
+                            .into_iter()
+                            .filter_map(|listing| tickers_info.get(&listing).copied().flatten())
+                            .collect();
+
+                            dashboard
+                                .set_merged_feed(main_window.id, pane_id, ticker, venues, listings)
+                                .map(move |msg| Message::Dashboard {
+                                    layout_id: None,
+                                    event: msg,
+                                })
+                        }
+                        Some(dashboard::Event::ImageCaptured(title, screenshot)) => {
+                            self.export_pane_image(title, screenshot)
+                        }
+                        Some(dashboard::Event::PaperTrade(ticker_info, command)) => {
+                            self.run_paper_command(ticker_info, command);
+                            Task::none()
+                        }
+                        Some(dashboard::Event::ExportJournal) => self.export_journal(),
                         None => Task::none(),
                     };
 
@@ -364,20 +672,211 @@ impl LuxChart {
             Message::ScaleFactorChanged(value) => {
                 self.ui_scale_factor = value;
             }
-            Message::ToggleTradeFetch(checked) => {
+            Message::ToggleTradeFetch(venue, checked) => {
                 self.layout_manager
                     .iter_dashboards_mut()
                     .for_each(|dashboard| {
-                        dashboard.toggle_trade_fetch(checked, &self.main_window);
+                        dashboard.toggle_trade_fetch(venue, checked, &self.main_window);
                     });
 
                 if checked {
                     self.confirm_dialog = None;
                 }
             }
+            Message::ToggleOfflineMode(checked) => {
+                exchange::network::set_offline(checked);
+
+                if checked {
+                    self.notifications.push(Toast::info(
+                        "Offline mode on, open streams close on their next reconnect".to_string(),
+                    ));
+                }
+            }
+            Message::AllowedHostsChanged(input) => {
+                exchange::network::set_allowed_hosts(
+                    input.split(',').map(str::to_string).collect(),
+                );
+                self.allowed_hosts_input = input;
+            }
+            Message::ProxyDraftChanged(draft) => {
+                self.proxy_draft = draft;
+            }
+            Message::ProxyPortChanged(input) => {
+                if input.chars().all(|c| c.is_ascii_digit()) {
+                    self.proxy_port_input = input;
+                }
+            }
+            Message::ApplyProxy(enabled) => {
+                let proxy = if enabled {
+                    let host = self.proxy_draft.host.trim().to_string();
+                    let port = self
+                        .proxy_port_input
+                        .parse::<u16>()
+                        .ok()
+                        .filter(|port| *port > 0);
+
+                    let Some(port) = port.filter(|_| !host.is_empty()) else {
+                        self.notifications.push(Toast::warn(
+                            "Proxy needs a host and a port between 1 and 65535".to_string(),
+                        ));
+                        return Task::none();
+                    };
+
+                    Some(exchange::network::Proxy {
+                        host,
+                        port,
+                        ..self.proxy_draft.clone()
+                    })
+                } else {
+                    None
+                };
+
+                match exchange::network::set_proxy(proxy) {
+                    Ok(()) => self.notifications.push(Toast::info(
+                        "Proxy updated, open streams pick it up on their next reconnect"
+                            .to_string(),
+                    )),
+                    Err(err) => self
+                        .notifications
+                        .push(Toast::error(format!("Couldn't set up the proxy: {err}"))),
+                }
+            }
+            Message::CredentialsVenueChanged(venue) => {
+                self.credentials_venue = venue;
+                self.credentials_draft = exchange::auth::credentials(venue).unwrap_or_default();
+            }
+            Message::CredentialsDraftChanged(draft) => {
+                self.credentials_draft = draft;
+            }
+            Message::TestCredentials => {
+                let venue = self.credentials_venue;
+                let credentials = self.credentials_draft.clone();
+
+                return Task::perform(
+                    async move { exchange::auth::validate(venue, &credentials).await },
+                    move |result| {
+                        Message::CredentialsTested(venue, result.map_err(|err| err.to_string()))
+                    },
+                );
+            }
+            Message::CredentialsTested(venue, result) => match result {
+                Ok(()) => self
+                    .notifications
+                    .push(Toast::info(format!("{venue} accepted the API key"))),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("{venue} API key check failed: {err}"))),
+            },
+            Message::SaveCredentials(keep) => {
+                let venue = self.credentials_venue;
+
+                if keep && !self.credentials_draft.is_complete(venue) {
+                    self.notifications.push(Toast::warn(format!(
+                        "{venue} needs a key and a secret{}",
+                        if exchange::auth::needs_passphrase(venue) {
+                            " and a passphrase"
+                        } else {
+                            ""
+                        }
+                    )));
+                    return Task::none();
+                }
+
+                exchange::auth::set_credentials(
+                    venue,
+                    keep.then(|| self.credentials_draft.clone()),
+                );
+                if !keep {
+                    self.credentials_draft = exchange::auth::Credentials::default();
+                }
+
+                let store = exchange::auth::VENUES
+                    .into_iter()
+                    .filter_map(|venue| {
+                        exchange::auth::credentials(venue).map(|credentials| (venue, credentials))
+                    })
+                    .collect();
+
+                match data::credentials::save(&store) {
+                    Ok(()) if keep => self
+                        .notifications
+                        .push(Toast::info(format!("{venue} API key saved"))),
+                    Ok(()) => self
+                        .notifications
+                        .push(Toast::info(format!("{venue} API key removed"))),
+                    Err(err) => self
+                        .notifications
+                        .push(Toast::error(format!("Couldn't save API keys: {err}"))),
+                }
+            }
+            Message::PaperTrade(command) => {
+                let main_window = self.main_window.id;
+
+                match self.active_dashboard().focused_ticker(main_window) {
+                    Some(ticker_info) => self.run_paper_command(ticker_info, command),
+                    None => self.notifications.push(Toast::warn(
+                        "Focus a pane streaming a ticker to trade it".to_string(),
+                    )),
+                }
+            }
+            Message::PaperNotionalChanged(input) => {
+                if let Ok(notional) = input.trim().parse::<f32>()
+                    && notional > 0.0
+                {
+                    self.paper.config.order_notional = notional;
+                }
+                self.paper_notional_input = input;
+            }
+            Message::PaperFeeChanged(fee_bps) => {
+                self.paper.config.fee_bps = fee_bps;
+            }
+            Message::ChartTradingToggled(enabled) => {
+                self.paper.config.chart_trading = enabled;
+
+                // charts pick the flag up with their next market data
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .clear_paper_overlays(main_window);
+            }
+            Message::ResetPaperAccount => {
+                self.paper.reset();
+
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .clear_paper_overlays(main_window);
+                self.sync_paper_journals();
+            }
+            Message::MeasureClocks => {
+                let tasks = self
+                    .connection_health
+                    .iter()
+                    .map(|(exchange, _)| measure_clock(exchange))
+                    .collect::<Vec<_>>();
+
+                return Task::batch(tasks);
+            }
+            Message::ClockMeasured(exchange, result) => match result {
+                Ok(sample) => self.connection_health.clock(exchange, sample),
+                Err(err) => log::warn!("Failed to measure {exchange} clock: {err}"),
+            },
+            Message::MaxConcurrentRequestsChanged(max) => {
+                exchange::limiter::set_max_concurrent(max);
+            }
             Message::ToggleDialogModal(dialog) => {
                 self.confirm_dialog = dialog;
             }
+            Message::ReplaceTicker(message) => {
+                let Some(modal::replace_ticker::Action::Replace {
+                    from,
+                    to,
+                    all_layouts,
+                }) = self.replace_ticker.update(message)
+                else {
+                    return Task::none();
+                };
+
+                return self.replace_ticker_everywhere(from, to, all_layouts);
+            }
             Message::Layouts(message) => {
                 let action = self.layout_manager.update(message);
 
@@ -433,22 +932,26 @@ impl LuxChart {
                                 name: manager.ensure_unique_name(&name, new_uid),
                             };
 
-                            let mut popout_windows = Vec::new();
-
-                            for (pane, window_spec) in &ser_dashboard.popout {
-                                let configuration = configuration(pane.clone());
-                                popout_windows.push((configuration, *window_spec));
-                            }
-
-                            let dashboard = Dashboard::from_config(
-                                configuration(ser_dashboard.pane.clone()),
-                                popout_windows,
-                                old_id,
-                            );
+                            let dashboard = dashboard_from_config(&ser_dashboard, old_id);
 
                             manager.insert_layout(new_layout.clone(), dashboard);
                         }
                     }
+                    Some(modal::layout_manager::Action::Removed(index, layout)) => {
+                        self.history.record(Edit::LayoutRemoved { index, layout });
+                    }
+                    Some(modal::layout_manager::Action::ExportWorkspace) => {
+                        return self.export_workspace();
+                    }
+                    Some(modal::layout_manager::Action::ImportWorkspace(path)) => {
+                        return Task::perform(
+                            async move {
+                                data::config::workspace::Workspace::read(&path)
+                                    .map_err(|err| err.to_string())
+                            },
+                            Message::WorkspaceLoaded,
+                        );
+                    }
                     None => {}
                 }
             }
@@ -467,6 +970,83 @@ impl LuxChart {
                     }
                 }
             }
+            Message::ExportScheduleChanged(schedule) => {
+                self.export_schedule = schedule;
+            }
+            Message::UserActivity => {
+                self.last_input = std::time::Instant::now();
+
+                if self.is_idle {
+                    self.is_idle = false;
+
+                    let main_window = self.main_window.id;
+                    self.active_dashboard_mut()
+                        .invalidate_all_panes(main_window);
+                }
+            }
+            Message::IdleSettingsChanged(settings) => {
+                self.idle_settings = settings;
+            }
+            Message::AutosaveSettingsChanged(settings) => {
+                self.autosave_settings = settings;
+            }
+            Message::AutosaveRequested(windows) => {
+                self.save_state_to_disk(&windows);
+            }
+            Message::ExportNow => {
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                return self.export_snapshots(now.saturating_sub(86_400_000), now);
+            }
+            Message::WorkspaceExported(result) => match result {
+                Ok(path) => self.notifications.push(Toast::info(format!(
+                    "Exported workspace to {}",
+                    path.to_string_lossy()
+                ))),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Workspace export failed: {err}"))),
+            },
+            Message::WorkspaceLoaded(result) => match result {
+                Ok(workspace) => return self.import_workspace(workspace),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Workspace import failed: {err}"))),
+            },
+            Message::JournalExported(result) => match result {
+                Ok(path) => self.notifications.push(Toast::info(format!(
+                    "Saved journal to {}",
+                    path.to_string_lossy()
+                ))),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Journal export failed: {err}"))),
+            },
+            Message::PaneImageExported(result) => match result {
+                Ok(path) => {
+                    self.notifications.push(Toast::info(format!(
+                        "Saved image to {}",
+                        path.to_string_lossy()
+                    )));
+
+                    if self.export_schedule.copy_image_path {
+                        return iced::clipboard::write(path.to_string_lossy().into_owned());
+                    }
+                }
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Image export failed: {err}"))),
+            },
+            Message::SnapshotsExported(result) => match result {
+                Ok(0) => self
+                    .notifications
+                    .push(Toast::warn("No time-based kline charts to export")),
+                Ok(count) => self
+                    .notifications
+                    .push(Toast::info(format!("Exported {count} snapshot file(s)"))),
+                Err(err) => self
+                    .notifications
+                    .push(Toast::error(format!("Snapshot export failed: {err}"))),
+            },
             Message::DataFolderRequested => {
                 if let Err(err) = data::open_data_folder() {
                     self.notifications
@@ -492,11 +1072,21 @@ impl LuxChart {
                 }
             }
             Message::Sidebar(message) => {
+                let opens_layout_menu = matches!(
+                    message,
+                    dashboard::sidebar::Message::ToggleSidebarMenu(Some(sidebar::Menu::Layout))
+                );
                 let (task, action) = self.sidebar.update(message);
 
+                if opens_layout_menu && self.sidebar.is_menu_active(sidebar::Menu::Layout) {
+                    self.refresh_replace_ticker_options();
+                }
+
                 match action {
                     Some(dashboard::sidebar::Action::TickerSelected(ticker_info, content)) => {
                         let main_window_id = self.main_window.id;
+                        self.autosave.mark_changed(std::time::Instant::now());
+                        self.record_panes();
 
                         let task = {
                             if let Some(kind) = content {
@@ -519,6 +1109,46 @@ impl LuxChart {
                     Some(dashboard::sidebar::Action::ErrorOccurred(err)) => {
                         self.notifications.push(Toast::error(err.to_string()));
                     }
+                    Some(dashboard::sidebar::Action::OpenMovers(movers, kind)) => {
+                        let main_window_id = self.main_window.id;
+                        let mut tasks = vec![task.map(Message::Sidebar)];
+
+                        for (ticker_info, change_pct) in movers {
+                            self.notifications.push(Toast::info(format!(
+                                "{} moved {change_pct:+.2}%",
+                                ticker_info.ticker
+                            )));
+
+                            let task = self.active_dashboard_mut().open_or_focus_ticker(
+                                main_window_id,
+                                ticker_info,
+                                kind,
+                            );
+                            tasks.push(task.map(move |msg| Message::Dashboard {
+                                layout_id: None,
+                                event: msg,
+                            }));
+                        }
+
+                        return Task::batch(tasks);
+                    }
+                    Some(dashboard::sidebar::Action::OpenChart(ticker_info, kind)) => {
+                        let main_window_id = self.main_window.id;
+
+                        return self
+                            .active_dashboard_mut()
+                            .open_or_focus_ticker(main_window_id, ticker_info, kind)
+                            .map(move |msg| Message::Dashboard {
+                                layout_id: None,
+                                event: msg,
+                            });
+                    }
+                    Some(dashboard::sidebar::Action::ToggleLiveUpdates) => {
+                        return self.update(Message::ToggleLiveUpdates);
+                    }
+                    Some(dashboard::sidebar::Action::TickSizeChanged(changed)) => {
+                        self.prompt_tick_size_change(&changed);
+                    }
                     None => {}
                 }
 
@@ -534,38 +1164,734 @@ impl LuxChart {
 
                 return window::collect_window_specs(active_windows, Message::RestartRequested);
             }
+            Message::ApplyDemoMode(enabled) => {
+                exchange::network::set_demo(enabled);
+                self.confirm_dialog = None;
+
+                let mut active_windows: Vec<window::Id> =
+                    self.active_dashboard().popout.keys().copied().collect();
+                active_windows.push(self.main_window.id);
+
+                return window::collect_window_specs(active_windows, Message::RestartRequested);
+            }
+            Message::ToggleLiveUpdates => {
+                if self.paused_events.is_some() {
+                    return self.resume_live_updates();
+                }
+                self.paused_events = Some(vec![]);
+            }
+            Message::ToggleCommandPalette => {
+                if self.command_palette.take().is_none() {
+                    let palette = CommandPalette::new();
+                    let input_id = palette.input_id.clone();
+
+                    self.command_palette = Some(palette);
+                    return iced::widget::operation::focus(input_id);
+                }
+            }
+            Message::Undo => {
+                let Some(edit) = self.history.undo() else {
+                    return Task::none();
+                };
+                let (inverse, task) = self.revert(edit);
+                if let Some(inverse) = inverse {
+                    self.history.undone(inverse);
+                }
+                return task;
+            }
+            Message::Redo => {
+                let Some(edit) = self.history.redo() else {
+                    return Task::none();
+                };
+                let (inverse, task) = self.revert(edit);
+                if let Some(inverse) = inverse {
+                    self.history.redone(inverse);
+                }
+                return task;
+            }
+            Message::CommandPalette(message) => {
+                let entries = self.palette_entries();
+
+                if let Some(palette) = &mut self.command_palette
+                    && let Some(command_palette::Action::Run(command)) =
+                        palette.update(message, &entries)
+                {
+                    self.command_palette = None;
+                    return self.run_command(command);
+                }
+            }
         }
         Task::none()
     }
 
-    fn view(&self, id: window::Id) -> Element<'_, Message> {
-        let dashboard = self.active_dashboard();
-        let sidebar_pos = self.sidebar.position();
+    fn run_command(&mut self, command: Command) -> Task<Message> {
+        match command {
+            Command::SwitchTicker(ticker_info) => {
+                let main_window_id = self.main_window.id;
+                self.autosave.mark_changed(std::time::Instant::now());
+                self.record_panes();
 
-        let tickers_table = &self.sidebar.tickers_table;
+                self.active_dashboard_mut()
+                    .switch_tickers_in_group(main_window_id, ticker_info)
+                    .map(move |msg| Message::Dashboard {
+                        layout_id: None,
+                        event: msg,
+                    })
+            }
+            Command::SelectLayout(id) => self.update(Message::Layouts(
+                modal::layout_manager::Message::SelectActive(id),
+            )),
+            Command::AddLayout => {
+                self.update(Message::Layouts(modal::layout_manager::Message::AddLayout))
+            }
+            Command::OpenMenu(menu) => {
+                self.sidebar.set_menu(Some(menu));
+                if menu == sidebar::Menu::Layout {
+                    self.refresh_replace_ticker_options();
+                }
+                Task::none()
+            }
+            Command::SetTheme(theme) => self.update(Message::ThemeSelected(theme)),
+            Command::SetTimezone(tz) => self.update(Message::SetTimezone(tz)),
+            Command::SetSidebarPosition(pos) => self.update(Message::Sidebar(
+                dashboard::sidebar::Message::SetSidebarPosition(pos),
+            )),
+            Command::OpenDataFolder => self.update(Message::DataFolderRequested),
+            Command::ToggleLiveUpdates => self.update(Message::ToggleLiveUpdates),
+        }
+    }
 
-        let content = if id == self.main_window.id {
-            let sidebar_view = self
-                .sidebar
-                .view(self.audio_stream.volume())
-                .map(Message::Sidebar);
+    /// Feeds stream data to the active dashboard's panes
+    fn apply_market_data(&mut self, event: exchange::Event) -> Task<Message> {
+        let main_window_id = self.main_window.id;
+
+        let task = match event {
+            exchange::Event::DepthReceived(stream, depth_update_t, depth, trades_buffer) => {
+                let ticker_info = stream.ticker_info();
+                let fills = self.paper.on_market_data(
+                    ticker_info.ticker,
+                    &depth,
+                    &trades_buffer,
+                    depth_update_t,
+                );
+                self.show_paper_fills(ticker_info, &fills);
 
-            let dashboard_view = dashboard
-                .view(&self.main_window, tickers_table, self.timezone)
-                .map(move |msg| Message::Dashboard {
-                    layout_id: None,
-                    event: msg,
-                });
+                self.active_dashboard_mut().update_depth_and_trades(
+                    &stream,
+                    Some((depth_update_t, &depth)),
+                    &trades_buffer,
+                    main_window_id,
+                )
+            }
+            exchange::Event::TradesReceived(stream, trades_buffer) => {
+                let ticker_info = stream.ticker_info();
+                let fills = self.paper.on_prints(ticker_info.ticker, &trades_buffer);
+                self.show_paper_fills(ticker_info, &fills);
+
+                self.active_dashboard_mut().update_depth_and_trades(
+                    &stream,
+                    None,
+                    &trades_buffer,
+                    main_window_id,
+                )
+            }
+            exchange::Event::LiquidationReceived(stream, liquidation) => {
+                self.active_dashboard_mut().insert_liquidation(
+                    &stream,
+                    liquidation,
+                    main_window_id,
+                );
+                Task::none()
+            }
+            exchange::Event::KlineReceived(stream, kline) => self
+                .active_dashboard_mut()
+                .update_latest_klines(&stream, &kline, main_window_id),
+            _ => Task::none(),
+        };
 
-            let header_title = {
-                let logo = image(image::Handle::from_bytes(style::LOGO_BYTES))
-                    .width(18)
-                    .height(18);
+        task.map(move |msg| Message::Dashboard {
+            layout_id: None,
+            event: msg,
+        })
+    }
 
-                iced::widget::center(
-                    row![
-                        logo,
-                        text("LUX CHART")
+    fn run_paper_command(
+        &mut self,
+        ticker_info: exchange::TickerInfo,
+        command: data::paper::Command,
+    ) {
+        let ticker = ticker_info.ticker;
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        match command {
+            data::paper::Command::Place { side, limit } => {
+                if self.paper.place(ticker, side, limit, now).is_none() {
+                    self.notifications.push(Toast::warn(format!(
+                        "No price for {ticker} yet, it needs a pane streaming its trades"
+                    )));
+                }
+            }
+            data::paper::Command::PlaceAt { limit } => {
+                match self.paper.passive_side(ticker, limit) {
+                    Some(side) => {
+                        self.paper.place(ticker, side, Some(limit), now);
+                    }
+                    None => self.notifications.push(Toast::warn(format!(
+                        "No price for {ticker} to place a limit against yet"
+                    ))),
+                }
+            }
+            data::paper::Command::Move { id, limit } => {
+                if !self.paper.move_order(id, limit) {
+                    self.notifications.push(Toast::info(format!(
+                        "Paper order on {ticker} is no longer working"
+                    )));
+                }
+            }
+            data::paper::Command::Cancel { id } => {
+                self.paper.cancel(id);
+            }
+            data::paper::Command::Flatten => {
+                if self.paper.flatten(ticker, now).is_none() {
+                    self.notifications
+                        .push(Toast::info(format!("No paper position on {ticker}")));
+                }
+            }
+            data::paper::Command::CancelAll => {
+                let cancelled = self.paper.cancel_all(ticker);
+                self.notifications.push(Toast::info(format!(
+                    "Cancelled {cancelled} paper orders on {ticker}"
+                )));
+            }
+        }
+
+        let overlay = self.paper.overlay(ticker);
+        let main_window = self.main_window.id;
+        self.active_dashboard_mut()
+            .set_paper_overlay(main_window, ticker, &overlay);
+        self.sync_paper_journals();
+    }
+
+    fn sync_paper_journals(&mut self) {
+        let main_window = self.main_window.id;
+        let Some(layout_id) = self.layout_manager.active_layout_id().map(|id| id.unique) else {
+            return;
+        };
+        if let Some(layout) = self.layout_manager.get_mut(layout_id) {
+            layout.dashboard.sync_journals(main_window, &self.paper);
+        }
+    }
+
+    /// Toasts the fills and redraws the ticker's orders and position where they're shown
+    fn show_paper_fills(&mut self, ticker_info: exchange::TickerInfo, fills: &[data::paper::Fill]) {
+        self.notify_paper_fills(ticker_info, fills);
+
+        let overlay = (!fills.is_empty()
+            || self.paper.is_active(ticker_info.ticker)
+            || self.paper.config.chart_trading)
+            .then(|| self.paper.overlay(ticker_info.ticker));
+
+        self.sync_paper_journals();
+
+        if let Some(overlay) = overlay {
+            let main_window_id = self.main_window.id;
+            self.active_dashboard_mut().set_paper_overlay(
+                main_window_id,
+                ticker_info.ticker,
+                &overlay,
+            );
+        }
+    }
+
+    fn notify_paper_fills(
+        &mut self,
+        ticker_info: exchange::TickerInfo,
+        fills: &[data::paper::Fill],
+    ) {
+        for fill in fills {
+            let price = fill.price.to_string(ticker_info.min_ticksize);
+
+            self.notifications.push(Toast::info(format!(
+                "Paper {} {:.4} {} @ {price}",
+                fill.side.to_string().to_lowercase(),
+                fill.qty,
+                ticker_info.ticker,
+            )));
+        }
+    }
+
+    /// Moves panes off venues that stayed down past their failover delay
+    fn run_failovers(&mut self) -> Task<Message> {
+        let main_window = self.main_window.id;
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        let due = self.active_dashboard_mut().due_failovers(main_window, now);
+        if due.is_empty() {
+            return Task::none();
+        }
+
+        let tickers_info = self.sidebar.tickers_info();
+        let resolved: Vec<_> = due
+            .into_iter()
+            .map(|(pane_id, ticker, venue)| {
+                let listing =
+                    exchange::composite::equivalent_listing(&ticker, venue, tickers_info.keys())
+                        .and_then(|listing| tickers_info.get(&listing).copied().flatten());
+                (pane_id, ticker, venue, listing)
+            })
+            .collect();
+
+        let mut tasks = vec![];
+        for (pane_id, ticker, venue, listing) in resolved {
+            let Some(ticker_info) = listing else {
+                self.notifications.push(Toast::warn(format!(
+                    "{ticker} is down but isn't listed on {venue} to fail over to"
+                )));
+                continue;
+            };
+
+            self.notifications.push(Toast::info(format!(
+                "{} down, switched {ticker} to {}",
+                ticker.exchange, ticker_info.ticker
+            )));
+
+            let task = self
+                .active_dashboard_mut()
+                .fail_over(main_window, pane_id, ticker_info);
+            tasks.push(task.map(move |msg| Message::Dashboard {
+                layout_id: None,
+                event: msg,
+            }));
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Swaps `from` for `to` across the active layout or all of them, favorites included
+    fn replace_ticker_everywhere(
+        &mut self,
+        from: exchange::Ticker,
+        to: exchange::TickerInfo,
+        all_layouts: bool,
+    ) -> Task<Message> {
+        let main_window = self.main_window.id;
+        let active_layout = self.layout_manager.active_layout_id().map(|id| id.unique);
+
+        let mut touched = 0;
+        let mut tasks = vec![];
+
+        for layout in &mut self.layout_manager.layouts {
+            let layout_id = layout.id.unique;
+            if !all_layouts && Some(layout_id) != active_layout {
+                continue;
+            }
+
+            let (task, panes) = layout.dashboard.replace_ticker(main_window, from, to);
+            touched += panes;
+            tasks.push(task.map(move |msg| Message::Dashboard {
+                layout_id: Some(layout_id),
+                event: msg,
+            }));
+        }
+
+        let favorite = self.sidebar.replace_favorite(from, to.ticker);
+
+        if touched == 0 && !favorite {
+            self.notifications
+                .push(Toast::warn(format!("{from} isn't used anywhere")));
+        } else {
+            self.notifications
+                .push(Toast::info(modal::replace_ticker::summary(
+                    from, to, touched, favorite,
+                )));
+        }
+        self.refresh_replace_ticker_options();
+
+        Task::batch(tasks)
+    }
+
+    fn refresh_replace_ticker_options(&mut self) {
+        let main_window = self.main_window.id;
+
+        let mut referenced: Vec<exchange::TickerInfo> = vec![];
+        for layout in &self.layout_manager.layouts {
+            for ticker_info in layout.dashboard.referenced_tickers(main_window) {
+                if !referenced.iter().any(|ti| ti.ticker == ticker_info.ticker) {
+                    referenced.push(ticker_info);
+                }
+            }
+        }
+
+        let listings = self
+            .sidebar
+            .tickers_info()
+            .values()
+            .filter_map(|info| *info)
+            .collect();
+
+        self.replace_ticker.set_options(referenced, listings);
+    }
+
+    fn prompt_tick_size_change(
+        &mut self,
+        changed: &[(exchange::TickerInfo, exchange::TickerInfo)],
+    ) {
+        for (previous, fresh) in changed {
+            log::info!(
+                "{} tick size changed from {} to {}",
+                fresh.ticker,
+                f32::from(previous.min_ticksize),
+                f32::from(fresh.min_ticksize)
+            );
+        }
+
+        let fresh: Vec<exchange::TickerInfo> = changed.iter().map(|(_, fresh)| *fresh).collect();
+        let main_window = self.main_window.id;
+
+        let affected: usize = self
+            .layout_manager
+            .layouts
+            .iter()
+            .map(|layout| {
+                layout
+                    .dashboard
+                    .panes_with_stale_ticksize(main_window, &fresh)
+            })
+            .sum();
+        if affected == 0 {
+            return;
+        }
+
+        let tickers = changed
+            .iter()
+            .map(|(previous, fresh)| {
+                format!(
+                    "{} ({} -> {})",
+                    fresh.ticker,
+                    f32::from(previous.min_ticksize),
+                    f32::from(fresh.min_ticksize)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let confirm_dialog = screen::ConfirmDialog::new(
+            format!(
+                "Exchange changed the tick size of {tickers}. Rebuild {affected} pane(s) with the new precision?"
+            ),
+            Box::new(Message::ApplyTickSizes(fresh)),
+        )
+        .with_confirm_btn_text("Rebuild".to_string());
+
+        self.confirm_dialog = Some(confirm_dialog);
+    }
+
+    fn track_connection_health(&mut self, event: &exchange::Event) {
+        use data::connection::LinkState;
+
+        let now = std::time::Instant::now();
+        let health = &mut self.connection_health;
+
+        match event {
+            exchange::Event::Connected(exchange) => {
+                health.set_state(*exchange, LinkState::Connected, now);
+            }
+            exchange::Event::Disconnected(exchange, _) => {
+                health.set_state(*exchange, LinkState::Disconnected, now);
+            }
+            exchange::Event::Reconnecting {
+                exchange,
+                attempt,
+                next_in,
+            } => {
+                let state = LinkState::Reconnecting {
+                    attempt: *attempt,
+                    retry_at: now + *next_in,
+                };
+                health.set_state(*exchange, state, now);
+            }
+            exchange::Event::Telemetry {
+                exchange,
+                messages,
+                latency,
+            } => health.telemetry(*exchange, *messages, *latency, now),
+            exchange::Event::DepthReceived(stream, ..)
+            | exchange::Event::TradesReceived(stream, _)
+            | exchange::Event::KlineReceived(stream, _)
+            | exchange::Event::LiquidationReceived(stream, _) => {
+                health.message(stream.ticker_info().exchange(), now);
+            }
+            exchange::Event::MarketClosed(..) => {}
+        }
+    }
+
+    /// Sound alerts go off as data arrives, paused or not, unless muted while idle
+    fn play_stream_alerts(&mut self, event: &exchange::Event) {
+        if self.is_idle && self.idle_settings.mute_audio {
+            return;
+        }
+
+        let err = match event {
+            exchange::Event::DepthReceived(stream, _, _, trades_buffer)
+            | exchange::Event::TradesReceived(stream, trades_buffer) => {
+                self.audio_stream.try_play_sound(stream, trades_buffer)
+            }
+            exchange::Event::LiquidationReceived(stream, liquidation) => {
+                self.audio_stream.try_play_liquidation(stream, liquidation)
+            }
+            _ => None,
+        };
+
+        if let Some(msg) = err {
+            self.notifications.push(Toast::error(msg));
+        }
+    }
+
+    /// Replays what was buffered while paused, in arrival order
+    fn resume_live_updates(&mut self) -> Task<Message> {
+        let Some(buffered) = self.paused_events.take() else {
+            return Task::none();
+        };
+
+        let tasks: Vec<_> = buffered
+            .into_iter()
+            .map(|event| self.apply_market_data(event))
+            .collect();
+
+        Task::batch(tasks)
+    }
+
+    /// Writes `from..to` of every kline chart in the active layout to the export folder
+    fn export_snapshots(&self, from: u64, to: u64) -> Task<Message> {
+        let schedule = self.export_schedule.clone();
+        if !schedule.has_outputs() {
+            return Task::none();
+        }
+
+        let mut snapshots = self
+            .active_dashboard()
+            .daily_snapshots(self.main_window.id, from, to);
+        // panes showing the same ticker would write the same files
+        snapshots.sort_by(|a, b| a.label.cmp(&b.label));
+        snapshots.dedup_by(|a, b| a.label == b.label);
+
+        let dir = schedule.directory();
+
+        Task::perform(
+            async move {
+                let mut written = 0;
+                for snapshot in &snapshots {
+                    written += snapshot
+                        .write(&dir, &schedule)
+                        .map_err(|err| err.to_string())?
+                        .len();
+                }
+                Ok(written)
+            },
+            Message::SnapshotsExported,
+        )
+    }
+
+    /// Encodes a captured pane into a PNG in the export folder
+    fn export_pane_image(
+        &self,
+        title: String,
+        screenshot: Result<iced::window::Screenshot, String>,
+    ) -> Task<Message> {
+        let screenshot = match screenshot {
+            Ok(screenshot) => screenshot,
+            Err(err) => return Task::done(Message::PaneImageExported(Err(err))),
+        };
+
+        let image = data::export::PaneImage {
+            label: title,
+            width: screenshot.size.width,
+            height: screenshot.size.height,
+            rgba: screenshot.rgba.to_vec(),
+        };
+        let dir = self.export_schedule.directory();
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        Task::perform(
+            async move { image.write_png(&dir, now).map_err(|err| err.to_string()) },
+            Message::PaneImageExported,
+        )
+    }
+
+    /// Writes the paper trading journal as CSV into the export folder
+    fn export_journal(&self) -> Task<Message> {
+        let trades = self.paper.journal().to_vec();
+        if trades.is_empty() {
+            return Task::done(Message::JournalExported(Err(
+                "no paper trades yet".to_string()
+            )));
+        }
+
+        let dir = self.export_schedule.directory();
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        Task::perform(
+            async move {
+                data::paper::journal::write_csv(&trades, &dir, now).map_err(|err| err.to_string())
+            },
+            Message::JournalExported,
+        )
+    }
+
+    /// Everything reachable from the command palette, in category order
+    fn palette_entries(&self) -> Vec<command_palette::Entry> {
+        use command_palette::Entry;
+
+        let mut entries = vec![
+            Entry::new(Category::Action, "New layout", Command::AddLayout),
+            Entry::new(
+                Category::Action,
+                "Manage layouts",
+                Command::OpenMenu(sidebar::Menu::Layout),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open settings",
+                Command::OpenMenu(sidebar::Menu::Settings),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open audio settings",
+                Command::OpenMenu(sidebar::Menu::Audio),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open scanner",
+                Command::OpenMenu(sidebar::Menu::Scanner),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open theme editor",
+                Command::OpenMenu(sidebar::Menu::ThemeEditor),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open rate limits",
+                Command::OpenMenu(sidebar::Menu::RateLimits),
+            ),
+            Entry::new(
+                Category::Action,
+                "Open data folder",
+                Command::OpenDataFolder,
+            ),
+            Entry::new(
+                Category::Action,
+                if self.paused_events.is_some() {
+                    "Resume live updates"
+                } else {
+                    "Pause live updates"
+                },
+                Command::ToggleLiveUpdates,
+            ),
+        ];
+
+        entries.extend(self.theme_choices().into_iter().map(|theme| {
+            Entry::new(
+                Category::Setting,
+                format!("Theme: {theme}"),
+                Command::SetTheme(data::Theme(theme)),
+            )
+        }));
+
+        entries.extend(
+            [data::UserTimezone::Utc, data::UserTimezone::Local]
+                .into_iter()
+                .map(|tz| {
+                    Entry::new(
+                        Category::Setting,
+                        format!("Time zone: {tz}"),
+                        Command::SetTimezone(tz),
+                    )
+                }),
+        );
+
+        entries.extend(
+            [sidebar::Position::Left, sidebar::Position::Right]
+                .into_iter()
+                .map(|pos| {
+                    Entry::new(
+                        Category::Setting,
+                        format!("Sidebar position: {pos}"),
+                        Command::SetSidebarPosition(pos),
+                    )
+                }),
+        );
+
+        let active_layout = self.layout_manager.active_layout_id().map(|id| id.unique);
+        entries.extend(self.layout_manager.layouts.iter().map(|layout| {
+            let entry = Entry::new(
+                Category::Layout,
+                layout.id.name.clone(),
+                Command::SelectLayout(layout.id.unique),
+            );
+
+            if active_layout == Some(layout.id.unique) {
+                entry.with_detail("active")
+            } else {
+                entry
+            }
+        }));
+
+        let mut tickers: Vec<_> = self
+            .sidebar
+            .tickers_info()
+            .values()
+            .filter_map(|info| *info)
+            .map(|info| {
+                let (symbol, market) = info.ticker.display_symbol_and_type();
+                let label = match market {
+                    exchange::adapter::MarketKind::Spot => symbol,
+                    exchange::adapter::MarketKind::LinearPerps
+                    | exchange::adapter::MarketKind::InversePerps => symbol + " PERP",
+                };
+
+                Entry::new(Category::Ticker, label, Command::SwitchTicker(info))
+                    .with_detail(info.ticker.exchange.to_string())
+            })
+            .collect();
+        tickers.sort_by(|a, b| a.label.cmp(&b.label));
+        entries.extend(tickers);
+
+        entries
+    }
+
+    fn view(&self, id: window::Id) -> Element<'_, Message> {
+        let dashboard = self.active_dashboard();
+        let sidebar_pos = self.sidebar.position();
+
+        let tickers_table = &self.sidebar.tickers_table;
+
+        let content = if id == self.main_window.id {
+            let sidebar_view = self
+                .sidebar
+                .view(
+                    self.audio_stream.volume(),
+                    self.paused_events.is_some(),
+                    &self.connection_health,
+                )
+                .map(Message::Sidebar);
+
+            let dashboard_view = dashboard
+                .view(&self.main_window, tickers_table, self.timezone)
+                .map(move |msg| Message::Dashboard {
+                    layout_id: None,
+                    event: msg,
+                });
+
+            let header_title = {
+                let logo = image(image::Handle::from_bytes(style::LOGO_BYTES))
+                    .width(18)
+                    .height(18);
+
+                iced::widget::center(
+                    row![
+                        logo,
+                        text("LUX CHART")
                             .font(iced::Font {
                                 weight: iced::font::Weight::Bold,
                                 ..Default::default()
@@ -591,10 +1917,31 @@ impl LuxChart {
                 .padding(8),
             ];
 
-            if let Some(menu) = self.sidebar.active_menu() {
+            let content = if let Some(menu) = self.sidebar.active_menu() {
                 self.view_with_modal(base.into(), dashboard, menu)
             } else {
                 base.into()
+            };
+
+            let content = if let Some(dialog) = &self.confirm_dialog {
+                let dialog_content =
+                    confirm_dialog_container(dialog.clone(), Message::ToggleDialogModal(None));
+
+                main_dialog_modal(content, dialog_content, Message::ToggleDialogModal(None))
+            } else {
+                content
+            };
+
+            if let Some(palette) = &self.command_palette {
+                main_dialog_modal(
+                    content,
+                    palette
+                        .view(&self.palette_entries())
+                        .map(Message::CommandPalette),
+                    Message::ToggleCommandPalette,
+                )
+            } else {
+                content
             }
         } else {
             container(
@@ -639,7 +1986,10 @@ impl LuxChart {
 
     fn subscription(&self) -> Subscription<Message> {
         let window_events = window::events().map(Message::WindowEvent);
-        let sidebar = self.sidebar.subscription().map(Message::Sidebar);
+        let sidebar = self
+            .sidebar
+            .subscription(self.is_idle)
+            .map(Message::Sidebar);
 
         let exchange_streams = self
             .active_dashboard()
@@ -647,24 +1997,100 @@ impl LuxChart {
             .map(Message::MarketWsEvent);
 
         let tick = iced::time::every(std::time::Duration::from_millis(100)).map(Message::Tick);
+        let clocks = iced::time::every(CLOCK_SAMPLE_INTERVAL).map(|_| Message::MeasureClocks);
 
         let hotkeys = keyboard::listen().filter_map(|event| {
-            let keyboard::Event::KeyPressed { key, .. } = event else {
+            let keyboard::Event::KeyPressed { key, modifiers, .. } = event else {
                 return None;
             };
-            match key {
+            match key.as_ref() {
                 keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::GoBack),
+                keyboard::Key::Character("k") if modifiers.command() => {
+                    Some(Message::ToggleCommandPalette)
+                }
+                keyboard::Key::Character("p") if modifiers.command() => {
+                    Some(Message::ToggleLiveUpdates)
+                }
+                keyboard::Key::Character("z" | "Z") if modifiers.command() => {
+                    Some(if modifiers.shift() {
+                        Message::Redo
+                    } else {
+                        Message::Undo
+                    })
+                }
+                keyboard::Key::Character(key) if modifiers.command() && modifiers.shift() => {
+                    let command = match key.to_ascii_lowercase().as_str() {
+                        "b" => data::paper::Command::Place {
+                            side: data::paper::Side::Buy,
+                            limit: None,
+                        },
+                        "s" => data::paper::Command::Place {
+                            side: data::paper::Side::Sell,
+                            limit: None,
+                        },
+                        "f" => data::paper::Command::Flatten,
+                        "x" => data::paper::Command::CancelAll,
+                        _ => return None,
+                    };
+                    Some(Message::PaperTrade(command))
+                }
                 _ => None,
             }
         });
 
-        Subscription::batch(vec![
+        // cursor moves only count as waking up, hovering alone doesn't keep the app busy
+        let user_activity = if self.is_idle {
+            iced::event::listen_with(|event, _status, _window| match event {
+                iced::Event::Mouse(_) | iced::Event::Keyboard(_) | iced::Event::Touch(_) => {
+                    Some(Message::UserActivity)
+                }
+                _ => None,
+            })
+        } else {
+            iced::event::listen_with(|event, _status, _window| match event {
+                iced::Event::Mouse(
+                    iced::mouse::Event::ButtonPressed(_) | iced::mouse::Event::WheelScrolled { .. },
+                )
+                | iced::Event::Keyboard(keyboard::Event::KeyPressed { .. })
+                | iced::Event::Touch(iced::touch::Event::FingerPressed { .. }) => {
+                    Some(Message::UserActivity)
+                }
+                _ => None,
+            })
+        };
+
+        let mut subscriptions = vec![
             exchange_streams,
             sidebar,
             window_events,
             tick,
+            clocks,
             hotkeys,
-        ])
+            user_activity,
+        ];
+
+        if self.auto_theme.switch == AutoSwitch::FollowSystem {
+            subscriptions.push(iced::system::theme_changes().map(Message::SystemThemeChanged));
+        }
+
+        if self.command_palette.is_some() {
+            subscriptions.push(keyboard::listen().filter_map(|event| {
+                let keyboard::Event::KeyPressed { key, .. } = event else {
+                    return None;
+                };
+                match key {
+                    keyboard::Key::Named(keyboard::key::Named::ArrowUp) => Some(
+                        Message::CommandPalette(command_palette::Message::Navigate(-1)),
+                    ),
+                    keyboard::Key::Named(keyboard::key::Named::ArrowDown) => Some(
+                        Message::CommandPalette(command_palette::Message::Navigate(1)),
+                    ),
+                    _ => None,
+                }
+            }));
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn active_dashboard(&self) -> &Dashboard {
@@ -718,19 +2144,105 @@ impl LuxChart {
         match menu {
             sidebar::Menu::Settings => {
                 let settings_modal = {
-                    let theme_picklist = {
-                        let mut themes: Vec<iced::Theme> = iced_core::Theme::ALL.to_vec();
+                    let theme_picklist =
+                        pick_list(self.theme_choices(), Some(self.theme.0.clone()), |theme| {
+                            Message::ThemeSelected(data::Theme(theme))
+                        });
+
+                    let auto_theme = {
+                        let auto = self.auto_theme.clone();
+                        let names: Vec<String> = self
+                            .theme_choices()
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect();
+
+                        let with = |f: fn(&mut AutoTheme, String)| {
+                            let auto = auto.clone();
+                            move |value: String| {
+                                let mut auto = auto.clone();
+                                f(&mut auto, value);
+                                Message::AutoThemeChanged(auto)
+                            }
+                        };
+
+                        let switch = {
+                            let auto = auto.clone();
+                            pick_list(AutoSwitch::ALL, Some(auto.switch), move |switch| {
+                                Message::AutoThemeChanged(AutoTheme {
+                                    switch,
+                                    ..auto.clone()
+                                })
+                            })
+                        };
 
-                        let default_theme = iced_core::Theme::Custom(default_theme().into());
-                        themes.push(default_theme);
+                        let mut col = column![
+                            row![text("Switch automatically"), switch]
+                                .spacing(8)
+                                .align_y(Alignment::Center)
+                        ]
+                        .spacing(8);
+
+                        if auto.switch != AutoSwitch::Off {
+                            let light = pick_list(
+                                names.clone(),
+                                Some(auto.light.clone()),
+                                with(|auto, name| auto.light = name),
+                            );
+                            let dark = pick_list(
+                                names,
+                                Some(auto.dark.clone()),
+                                with(|auto, name| auto.dark = name),
+                            );
 
-                        if let Some(custom_theme) = &self.theme_editor.custom_theme {
-                            themes.push(custom_theme.clone());
+                            col = col
+                                .push(
+                                    row![text("Light"), light]
+                                        .spacing(8)
+                                        .align_y(Alignment::Center),
+                                )
+                                .push(
+                                    row![text("Dark"), dark]
+                                        .spacing(8)
+                                        .align_y(Alignment::Center),
+                                );
                         }
 
-                        pick_list(themes, Some(self.theme.0.clone()), |theme| {
-                            Message::ThemeSelected(data::Theme(theme))
-                        })
+                        if auto.switch == AutoSwitch::Scheduled {
+                            let times = data::config::theme::LocalTime::all_steps();
+
+                            let light_from = {
+                                let auto = auto.clone();
+                                pick_list(times.clone(), Some(auto.light_from), move |light_from| {
+                                    Message::AutoThemeChanged(AutoTheme {
+                                        light_from,
+                                        ..auto.clone()
+                                    })
+                                })
+                            };
+                            let dark_from = {
+                                let auto = auto.clone();
+                                pick_list(times, Some(auto.dark_from), move |dark_from| {
+                                    Message::AutoThemeChanged(AutoTheme {
+                                        dark_from,
+                                        ..auto.clone()
+                                    })
+                                })
+                            };
+
+                            col = col.push(
+                                row![
+                                    text("Light from"),
+                                    light_from,
+                                    text("dark from"),
+                                    dark_from,
+                                    text("local time").style(style::secondary_text),
+                                ]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            );
+                        }
+                        col
                     };
 
                     let toggle_theme_editor = button(text("Theme editor")).on_press(
@@ -817,31 +2329,255 @@ impl LuxChart {
                         .style(style::modal_container)
                     };
 
-                    let trade_fetch_checkbox = {
-                        let is_active = exchange::fetcher::is_trade_fetch_enabled();
+                    let trade_fetch_checkboxes = {
+                        let checkboxes = exchange::adapter::ExchangeInclusive::ALL
+                            .into_iter()
+                            .filter(|venue| venue.supports_trade_fetch())
+                            .map(|venue| {
+                                let is_active = exchange::fetcher::is_trade_fetch_enabled(venue);
+
+                                let checkbox = iced::widget::checkbox(is_active)
+                                    .label(format!("Fetch trades ({venue})"))
+                                    .on_toggle(move |checked| {
+                                        if checked {
+                                            let confirm_dialog = screen::ConfirmDialog::new(
+                                                format!(
+                                                    "{}\n\nThis might be unreliable and take some time to complete. Proceed?",
+                                                    venue.trade_fetch_usage()
+                                                ),
+                                                Box::new(Message::ToggleTradeFetch(venue, true)),
+                                            );
+                                            Message::ToggleDialogModal(Some(confirm_dialog))
+                                        } else {
+                                            Message::ToggleTradeFetch(venue, false)
+                                        }
+                                    });
 
-                        let checkbox = iced::widget::checkbox(is_active)
-                            .label("Fetch trades (Binance)")
+                                tooltip(
+                                    checkbox,
+                                    Some(venue.trade_fetch_usage()),
+                                    TooltipPosition::Top,
+                                )
+                            });
+
+                        column(checkboxes).spacing(8)
+                    };
+
+                    let offline_mode = {
+                        let checkbox = iced::widget::checkbox(exchange::network::is_offline())
+                            .label("Offline mode")
+                            .on_toggle(Message::ToggleOfflineMode);
+
+                        let allowed_hosts = iced::widget::text_input(
+                            "Allowed hosts, e.g. binance.com",
+                            &self.allowed_hosts_input,
+                        )
+                        .on_input(Message::AllowedHostsChanged)
+                        .size(12);
+
+                        column![
+                            tooltip(
+                                checkbox,
+                                Some("Block every request and stream connection, except to the hosts below"),
+                                TooltipPosition::Top,
+                            ),
+                            allowed_hosts,
+                        ]
+                        .spacing(8)
+                    };
+
+                    let demo_mode = {
+                        let checkbox = iced::widget::checkbox(exchange::network::is_demo())
+                            .label("Demo mode")
                             .on_toggle(|checked| {
-                                if checked {
-                                    let confirm_dialog = screen::ConfirmDialog::new(
-                                        "This might be unreliable and take some time to complete. Proceed?"
-                                            .to_string(),
-                                        Box::new(Message::ToggleTradeFetch(true)),
-                                    );
-                                    Message::ToggleDialogModal(Some(confirm_dialog))
-                                } else {
-                                    Message::ToggleTradeFetch(false)
-                                }
+                                let confirm_dialog = screen::ConfirmDialog::new(
+                                    "Switching demo mode requires application restart".to_string(),
+                                    Box::new(Message::ApplyDemoMode(checked)),
+                                )
+                                .with_confirm_btn_text("Restart now".to_string());
+
+                                Message::ToggleDialogModal(Some(confirm_dialog))
                             });
 
                         tooltip(
                             checkbox,
-                            Some("Try to fetch trades for footprint charts"),
+                            Some(
+                                "List only a simulated exchange with generated trades, klines and depth, no requests are made",
+                            ),
                             TooltipPosition::Top,
                         )
                     };
 
+                    let proxy = {
+                        let draft = &self.proxy_draft;
+                        let is_enabled = exchange::network::proxy().is_some();
+
+                        let checkbox = iced::widget::checkbox(is_enabled)
+                            .label("Proxy")
+                            .on_toggle(Message::ApplyProxy);
+
+                        let kind = pick_list(
+                            exchange::network::ProxyKind::ALL,
+                            Some(draft.kind),
+                            |kind| {
+                                Message::ProxyDraftChanged(exchange::network::Proxy {
+                                    kind,
+                                    ..self.proxy_draft.clone()
+                                })
+                            },
+                        )
+                        .text_size(12);
+
+                        let host = iced::widget::text_input("Host", &draft.host)
+                            .on_input(|host| {
+                                Message::ProxyDraftChanged(exchange::network::Proxy {
+                                    host,
+                                    ..self.proxy_draft.clone()
+                                })
+                            })
+                            .size(12);
+
+                        let port = iced::widget::text_input("Port", &self.proxy_port_input)
+                            .on_input(Message::ProxyPortChanged)
+                            .width(64)
+                            .size(12);
+
+                        let username = iced::widget::text_input(
+                            "Username",
+                            draft.username.as_deref().unwrap_or_default(),
+                        )
+                        .on_input(|username| {
+                            Message::ProxyDraftChanged(exchange::network::Proxy {
+                                username: (!username.is_empty()).then_some(username),
+                                ..self.proxy_draft.clone()
+                            })
+                        })
+                        .size(12);
+
+                        let password = iced::widget::text_input(
+                            "Password",
+                            draft.password.as_deref().unwrap_or_default(),
+                        )
+                        .on_input(|password| {
+                            Message::ProxyDraftChanged(exchange::network::Proxy {
+                                password: (!password.is_empty()).then_some(password),
+                                ..self.proxy_draft.clone()
+                            })
+                        })
+                        .secure(true)
+                        .size(12);
+
+                        let apply =
+                            button(text("Apply").size(12)).on_press(Message::ApplyProxy(true));
+
+                        column![
+                            tooltip(
+                                checkbox,
+                                Some("Route requests and stream connections through an HTTP (CONNECT) or SOCKS5 proxy"),
+                                TooltipPosition::Top,
+                            ),
+                            row![kind, host, port].spacing(4),
+                            row![username, password].spacing(4),
+                            apply,
+                        ]
+                        .spacing(8)
+                    };
+
+                    let credentials = {
+                        let venue = self.credentials_venue;
+                        let draft = &self.credentials_draft;
+                        let is_saved = exchange::auth::credentials(venue).is_some();
+
+                        let venue_picker = pick_list(
+                            exchange::auth::VENUES,
+                            Some(venue),
+                            Message::CredentialsVenueChanged,
+                        )
+                        .text_size(12);
+
+                        let key = iced::widget::text_input("API key", &draft.key)
+                            .on_input(|key| {
+                                Message::CredentialsDraftChanged(exchange::auth::Credentials {
+                                    key,
+                                    ..self.credentials_draft.clone()
+                                })
+                            })
+                            .size(12);
+
+                        let secret = iced::widget::text_input("Secret", &draft.secret)
+                            .on_input(|secret| {
+                                Message::CredentialsDraftChanged(exchange::auth::Credentials {
+                                    secret,
+                                    ..self.credentials_draft.clone()
+                                })
+                            })
+                            .secure(true)
+                            .size(12);
+
+                        let mut fields = row![key, secret].spacing(4);
+                        if exchange::auth::needs_passphrase(venue) {
+                            fields = fields.push(
+                                iced::widget::text_input(
+                                    "Passphrase",
+                                    draft.passphrase.as_deref().unwrap_or_default(),
+                                )
+                                .on_input(|passphrase| {
+                                    Message::CredentialsDraftChanged(exchange::auth::Credentials {
+                                        passphrase: (!passphrase.is_empty()).then_some(passphrase),
+                                        ..self.credentials_draft.clone()
+                                    })
+                                })
+                                .secure(true)
+                                .size(12),
+                            );
+                        }
+
+                        let is_complete = draft.is_complete(venue);
+                        let actions = row![
+                            button(text("Test").size(12))
+                                .on_press_maybe(is_complete.then_some(Message::TestCredentials)),
+                            button(text("Save").size(12)).on_press_maybe(
+                                is_complete.then_some(Message::SaveCredentials(true))
+                            ),
+                            button(text("Remove").size(12)).on_press_maybe(
+                                is_saved.then_some(Message::SaveCredentials(false))
+                            ),
+                        ]
+                        .spacing(4);
+
+                        column![
+                            tooltip(
+                                row![text("API keys"), venue_picker]
+                                    .spacing(8)
+                                    .align_y(Alignment::Center),
+                                Some("Read-only keys are enough, they're kept encrypted in the data folder"),
+                                TooltipPosition::Top,
+                            ),
+                            fields,
+                            actions,
+                        ]
+                        .spacing(8)
+                    };
+
+                    let max_concurrent_requests = {
+                        let picker = pick_list(
+                            exchange::limiter::MAX_CONCURRENT_CHOICES,
+                            Some(exchange::limiter::max_concurrent()),
+                            Message::MaxConcurrentRequestsChanged,
+                        );
+
+                        row![
+                            tooltip(
+                                text("Parallel requests per exchange"),
+                                Some("Caps REST requests in flight to each exchange, lower it if many panes backfilling at once get you rate limited"),
+                                TooltipPosition::Top,
+                            ),
+                            picker,
+                        ]
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                    };
+
                     let open_data_folder = {
                         let button =
                             button(text("Open data folder")).on_press(Message::DataFolderRequested);
@@ -853,16 +2589,231 @@ impl LuxChart {
                         )
                     };
 
+                    let scheduled_exports = {
+                        let schedule = &self.export_schedule;
+                        let with = |f: fn(&mut data::export::ExportSchedule, bool)| {
+                            let schedule = schedule.clone();
+                            move |checked| {
+                                let mut schedule = schedule.clone();
+                                f(&mut schedule, checked);
+                                Message::ExportScheduleChanged(schedule)
+                            }
+                        };
+
+                        let enabled = iced::widget::checkbox(schedule.enabled)
+                            .label("Export daily")
+                            .on_toggle(with(|s, v| s.enabled = v));
+
+                        let time = {
+                            let schedule = schedule.clone();
+                            pick_list(
+                                data::chart::session::SessionTime::all_steps(),
+                                Some(schedule.time),
+                                move |time| {
+                                    Message::ExportScheduleChanged(data::export::ExportSchedule {
+                                        time,
+                                        ..schedule.clone()
+                                    })
+                                },
+                            )
+                        };
+
+                        let directory = {
+                            let schedule = schedule.clone();
+                            iced::widget::text_input(
+                                &schedule.directory().to_string_lossy(),
+                                &schedule
+                                    .directory
+                                    .as_ref()
+                                    .map(|dir| dir.to_string_lossy().into_owned())
+                                    .unwrap_or_default(),
+                            )
+                            .on_input(move |input| {
+                                Message::ExportScheduleChanged(data::export::ExportSchedule {
+                                    directory: (!input.trim().is_empty())
+                                        .then(|| std::path::PathBuf::from(input)),
+                                    ..schedule.clone()
+                                })
+                            })
+                            .size(12)
+                        };
+
+                        column![
+                            tooltip(
+                                enabled,
+                                Some("Export the last 24h of every time-based kline chart in the active layout"),
+                                TooltipPosition::Top,
+                            ),
+                            row![text("At (UTC)"), time].spacing(8).align_y(Alignment::Center),
+                            iced::widget::checkbox(schedule.kline_csv)
+                                .label("Klines (CSV)")
+                                .on_toggle(with(|s, v| s.kline_csv = v)),
+                            iced::widget::checkbox(schedule.volume_profile_svg)
+                                .label("Volume profile (SVG)")
+                                .on_toggle(with(|s, v| s.volume_profile_svg = v)),
+                            iced::widget::checkbox(schedule.session_stats_json)
+                                .label("Session stats (JSON)")
+                                .on_toggle(with(|s, v| s.session_stats_json = v)),
+                            directory,
+                            iced::widget::checkbox(schedule.copy_image_path)
+                                .label("Copy path of exported pane images")
+                                .on_toggle(with(|s, v| s.copy_image_path = v)),
+                            button(text("Export now")).on_press(Message::ExportNow),
+                        ]
+                        .spacing(8)
+                    };
+
+                    let idle_mode = {
+                        let settings = self.idle_settings;
+
+                        let enabled = iced::widget::checkbox(settings.after_mins.is_some())
+                            .label("Economy mode when idle")
+                            .on_toggle(move |enabled| {
+                                Message::IdleSettingsChanged(data::idle::IdleSettings {
+                                    after_mins: enabled
+                                        .then_some(data::idle::IdleSettings::DEFAULT_AFTER_MINS),
+                                    ..settings
+                                })
+                            });
+
+                        let mut col = column![tooltip(
+                            enabled,
+                            Some("Redraws less often and pauses ticker stats polling until the next input"),
+                            TooltipPosition::Top,
+                        )]
+                        .spacing(8);
+
+                        if let Some(after_mins) = settings.after_mins {
+                            let after = pick_list(
+                                data::idle::IdleSettings::AFTER_MINS_CHOICES,
+                                Some(after_mins),
+                                move |mins| {
+                                    Message::IdleSettingsChanged(data::idle::IdleSettings {
+                                        after_mins: Some(mins),
+                                        ..settings
+                                    })
+                                },
+                            );
+
+                            col = col
+                                .push(
+                                    row![text("After"), after, text("min without input")]
+                                        .spacing(8)
+                                        .align_y(Alignment::Center),
+                                )
+                                .push(
+                                    iced::widget::checkbox(settings.mute_audio)
+                                        .label("Mute sound alerts while idle")
+                                        .on_toggle(move |mute_audio| {
+                                            Message::IdleSettingsChanged(data::idle::IdleSettings {
+                                                mute_audio,
+                                                ..settings
+                                            })
+                                        }),
+                                );
+                        }
+                        col
+                    };
+
+                    let autosave = {
+                        let settings = self.autosave_settings;
+
+                        let enabled = iced::widget::checkbox(settings.is_enabled())
+                            .label("Autosave layouts")
+                            .on_toggle(move |enabled| {
+                                Message::AutosaveSettingsChanged(data::autosave::AutosaveSettings {
+                                    interval_secs: enabled.then_some(
+                                        data::autosave::AutosaveSettings::DEFAULT_INTERVAL_SECS,
+                                    ),
+                                })
+                            });
+
+                        let mut col = column![tooltip(
+                            enabled,
+                            Some("Also saves a few seconds after panes are added, removed or switch tickers"),
+                            TooltipPosition::Top,
+                        )]
+                        .spacing(8);
+
+                        if let Some(interval_secs) = settings.interval_secs {
+                            let every = pick_list(
+                                data::autosave::AutosaveSettings::INTERVAL_CHOICES,
+                                Some(interval_secs),
+                                |secs| {
+                                    Message::AutosaveSettingsChanged(
+                                        data::autosave::AutosaveSettings {
+                                            interval_secs: Some(secs),
+                                        },
+                                    )
+                                },
+                            );
+
+                            col = col.push(
+                                row![text("Every"), every, text("seconds")]
+                                    .spacing(8)
+                                    .align_y(Alignment::Center),
+                            );
+                        }
+                        col
+                    };
+
+                    let paper_trading = {
+                        let notional = iced::widget::text_input("1000", &self.paper_notional_input)
+                            .on_input(Message::PaperNotionalChanged)
+                            .width(96)
+                            .size(12);
+
+                        let fee = pick_list(
+                            data::paper::Config::FEE_BPS_CHOICES,
+                            Some(self.paper.config.fee_bps),
+                            Message::PaperFeeChanged,
+                        )
+                        .text_size(12);
+
+                        column![
+                            row![text("Order size"), notional, text("in quote currency")]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            row![text("Fee"), fee, text("bps per fill")]
+                                .spacing(8)
+                                .align_y(Alignment::Center),
+                            iced::widget::checkbox(self.paper.config.chart_trading)
+                                .label("Chart trading")
+                                .on_toggle(Message::ChartTradingToggled),
+                            text(
+                                "Click the price axis of a chart to place a limit, drag order \
+                                 lines to move them and click their x to cancel"
+                            )
+                            .size(11)
+                            .style(style::secondary_text),
+                            text(
+                                "Right click a chart or ladder price to place orders, \
+                                 Ctrl/Cmd+Shift+B or S to buy or sell at market on the focused \
+                                 pane, F to flatten, X to cancel its orders"
+                            )
+                            .size(11)
+                            .style(style::secondary_text),
+                            button(text("Reset account").size(12))
+                                .on_press(Message::ResetPaperAccount),
+                        ]
+                        .spacing(8)
+                    };
+
                     let column_content = split_column![
                         column![open_data_folder,].spacing(8),
                         column![text("Sidebar position").size(14), sidebar_pos,].spacing(12),
                         column![text("Time zone").size(14), timezone_picklist,].spacing(12),
                         column![text("Market data").size(14), size_in_quote_currency_checkbox,].spacing(12),
-                        column![text("Theme").size(14), theme_picklist,].spacing(12),
+                        column![text("Theme").size(14), theme_picklist, auto_theme,].spacing(12),
                         column![text("Interface scale").size(14), scale_factor,].spacing(12),
+                        column![text("Scheduled exports").size(14), scheduled_exports,].spacing(12),
+                        column![text("Performance").size(14), idle_mode,].spacing(12),
+                        column![text("Autosave").size(14), autosave,].spacing(12),
+                        column![text("Paper trading").size(14), paper_trading,].spacing(12),
+                        column![text("Network").size(14), offline_mode, demo_mode, proxy, credentials, max_concurrent_requests,].spacing(12),
                         column![
                             text("Experimental").size(14),
-                            column![trade_fetch_checkbox, toggle_theme_editor,].spacing(8),
+                            column![trade_fetch_checkboxes, toggle_theme_editor,].spacing(8),
                         ]
                         .spacing(12),
                         ; spacing = 16, align_x = Alignment::Start
@@ -887,27 +2838,14 @@ impl LuxChart {
                     sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
                 };
 
-                let base_content = dashboard_modal(
+                dashboard_modal(
                     base,
                     settings_modal,
                     Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
                     padding,
                     Alignment::End,
                     align_x,
-                );
-
-                if let Some(dialog) = &self.confirm_dialog {
-                    let dialog_content =
-                        confirm_dialog_container(dialog.clone(), Message::ToggleDialogModal(None));
-
-                    main_dialog_modal(
-                        base_content,
-                        dialog_content,
-                        Message::ToggleDialogModal(None),
-                    )
-                } else {
-                    base_content
-                }
+                )
             }
             sidebar::Menu::Layout => {
                 let main_window = self.main_window.id;
@@ -997,7 +2935,11 @@ impl LuxChart {
                     let col = column![
                         manage_pane,
                         rule::horizontal(1.0).style(style::split_ruler),
-                        self.layout_manager.view().map(Message::Layouts)
+                        self.layout_manager
+                            .view(self.autosave_settings.is_enabled())
+                            .map(Message::Layouts),
+                        rule::horizontal(1.0).style(style::split_ruler),
+                        self.replace_ticker.view().map(Message::ReplaceTicker),
                     ];
 
                     container(col.align_x(Alignment::Center).spacing(20))
@@ -1039,6 +2981,24 @@ impl LuxChart {
                     align_x,
                 )
             }
+            sidebar::Menu::Scanner => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(108)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(108)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.sidebar
+                        .scanner
+                        .view(self.timezone)
+                        .map(|msg| Message::Sidebar(dashboard::sidebar::Message::Scanner(msg))),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
             sidebar::Menu::ThemeEditor => {
                 let (align_x, padding) = match sidebar_pos {
                     sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
@@ -1056,9 +3016,216 @@ impl LuxChart {
                     align_x,
                 )
             }
+            sidebar::Menu::RateLimits => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).bottom(4)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).bottom(4)),
+                };
+
+                dashboard_modal(
+                    base,
+                    modal::rate_limits::view(
+                        exchange::limiter::limiter_status(),
+                        exchange::scheduler::queue_depth(),
+                    ),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::End,
+                    align_x,
+                )
+            }
         }
     }
 
+    fn serialized_layouts(&self) -> data::Layouts {
+        let layouts = self
+            .layout_manager
+            .layouts
+            .iter()
+            .map(|layout| data::Layout {
+                name: layout.id.name.clone(),
+                dashboard: data::Dashboard::from(&layout.dashboard),
+            })
+            .collect();
+
+        data::Layouts {
+            layouts,
+            active_layout: self
+                .layout_manager
+                .active_layout_id()
+                .map(|layout| layout.name.to_string()),
+        }
+    }
+
+    /// Writes every layout and the themes into one workspace file
+    fn export_workspace(&self) -> Task<Message> {
+        let workspace = data::config::workspace::Workspace::new(
+            self.serialized_layouts(),
+            self.theme.clone(),
+            self.theme_editor.custom_theme.clone().map(data::Theme),
+            self.theme_editor
+                .saved_themes
+                .iter()
+                .cloned()
+                .map(data::Theme)
+                .collect(),
+        );
+        let dir = data::config::workspace::Workspace::directory();
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        Task::perform(
+            async move { workspace.write(&dir, now).map_err(|err| err.to_string()) },
+            Message::WorkspaceExported,
+        )
+    }
+
+    /// Adds the layouts of an imported workspace next to the existing ones and applies its themes
+    fn import_workspace(&mut self, workspace: data::config::workspace::Workspace) -> Task<Message> {
+        let mut active = None;
+
+        for layout in &workspace.layouts {
+            let unique = uuid::Uuid::new_v4();
+            let id = LayoutId {
+                unique,
+                name: self.layout_manager.ensure_unique_name(&layout.name, unique),
+            };
+
+            if workspace.active_layout.as_ref() == Some(&layout.name) {
+                active = Some(unique);
+            }
+
+            self.layout_manager
+                .insert_layout(id, dashboard_from_config(&layout.dashboard, unique));
+        }
+
+        if let Some(custom_theme) = workspace.custom_theme {
+            self.theme_editor.custom_theme = Some(custom_theme.0);
+        }
+        for saved in workspace.saved_themes {
+            self.theme_editor.keep_saved(saved.0);
+        }
+
+        let count = workspace.layouts.len();
+        self.notifications.push(Toast::info(format!(
+            "Imported {count} layout{}",
+            if count == 1 { "" } else { "s" }
+        )));
+
+        let theme = self.update(Message::ThemeSelected(workspace.selected_theme));
+
+        match active {
+            Some(id) => theme.chain(self.update(Message::Layouts(
+                modal::layout_manager::Message::SelectActive(id),
+            ))),
+            None => theme,
+        }
+    }
+
+    /// Built-in themes of the theme manager followed by the custom ones
+    fn theme_choices(&self) -> Vec<iced::Theme> {
+        self.theme_manager
+            .get_available_themes()
+            .iter()
+            .map(|(_, theme)| theme.0.clone())
+            .chain(self.theme_editor.custom_themes().cloned())
+            .collect()
+    }
+
+    /// Switches to the light or dark theme once it's due, by name from the manager or the custom ones
+    fn apply_auto_theme(&mut self) -> Task<Message> {
+        let now = chrono::Local::now().time();
+
+        let Some(name) = self
+            .auto_theme
+            .due_theme(self.system_is_dark, now)
+            .map(str::to_string)
+        else {
+            return Task::none();
+        };
+        if self.theme.0.to_string() == name {
+            return Task::none();
+        }
+
+        let theme = if self.theme_manager.set_theme(&name) {
+            Some(self.theme_manager.get_current_theme().clone())
+        } else {
+            self.theme_editor
+                .custom_themes()
+                .find(|theme| theme.to_string() == name)
+                .cloned()
+                .map(data::Theme)
+        };
+
+        match theme {
+            Some(theme) => self.update(Message::ThemeSelected(theme)),
+            None => Task::none(),
+        }
+    }
+
+    /// Takes the panes of the active layout onto the undo stack, before they change
+    fn record_panes(&mut self) {
+        let main_window = self.main_window.id;
+
+        if let Some(layout) = self.layout_manager.active_layout_id().map(|id| id.unique) {
+            let grids = self.active_dashboard().grids(main_window);
+            self.history.record(Edit::Panes { layout, grids });
+        }
+    }
+
+    /// Applies an undo or redo entry, returning the entry that reverts it
+    fn revert(&mut self, edit: Edit) -> (Option<Edit>, Task<Message>) {
+        let main_window = self.main_window.id;
+
+        match edit {
+            Edit::Panes { layout, grids } => {
+                let Some(dashboard) = self.layout_manager.mut_dashboard(layout) else {
+                    return (None, Task::none());
+                };
+                let current = dashboard.grids(main_window);
+
+                let task = dashboard.restore_grids(main_window, grids).map(move |msg| {
+                    Message::Dashboard {
+                        layout_id: Some(layout),
+                        event: msg,
+                    }
+                });
+
+                (
+                    Some(Edit::Panes {
+                        layout,
+                        grids: current,
+                    }),
+                    task,
+                )
+            }
+            Edit::LayoutRemoved { index, layout } => {
+                let unique = layout.id.unique;
+                self.layout_manager.restore_layout(index, *layout);
+
+                (Some(Edit::LayoutRestored(unique)), Task::none())
+            }
+            Edit::LayoutRestored(unique) => {
+                let removed = self.layout_manager.remove_layout(unique);
+
+                (
+                    removed.map(|(index, layout)| Edit::LayoutRemoved {
+                        index,
+                        layout: Box::new(layout),
+                    }),
+                    Task::none(),
+                )
+            }
+        }
+    }
+
+    /// Collects the window positions first, the state is written once they arrive
+    fn autosave_state(&self) -> Task<Message> {
+        let mut windows: Vec<window::Id> = self.active_dashboard().popout.keys().copied().collect();
+        windows.push(self.main_window.id);
+
+        window::collect_window_specs(windows, Message::AutosaveRequested)
+    }
+
     fn save_state_to_disk(&mut self, windows: &HashMap<window::Id, WindowSpec>) {
         self.active_dashboard_mut()
             .popout
@@ -1071,25 +3238,7 @@ impl LuxChart {
 
         self.sidebar.sync_tickers_table_settings();
 
-        let mut ser_layouts = vec![];
-        for layout in &self.layout_manager.layouts {
-            if let Some(layout) = self.layout_manager.get(layout.id.unique) {
-                let serialized_dashboard = data::Dashboard::from(&layout.dashboard);
-                ser_layouts.push(data::Layout {
-                    name: layout.id.name.clone(),
-                    dashboard: serialized_dashboard,
-                });
-            }
-        }
-
-        let layouts = data::Layouts {
-            layouts: ser_layouts,
-            active_layout: self
-                .layout_manager
-                .active_layout_id()
-                .map(|layout| layout.name.to_string())
-                .clone(),
-        };
+        let layouts = self.serialized_layouts();
 
         let main_window_spec = windows
             .iter()
@@ -1102,12 +3251,23 @@ impl LuxChart {
             layouts,
             self.theme.clone(),
             self.theme_editor.custom_theme.clone().map(data::Theme),
+            self.theme_editor
+                .saved_themes
+                .iter()
+                .cloned()
+                .map(data::Theme)
+                .collect(),
+            self.auto_theme.clone(),
             main_window_spec,
             self.timezone,
             self.sidebar.state.clone(),
             self.ui_scale_factor,
             audio_cfg,
             self.volume_size_unit,
+            self.export_schedule.clone(),
+            self.idle_settings,
+            self.autosave_settings,
+            self.paper.config,
         );
 
         match serde_json::to_string(&state) {
@@ -1,13 +1,18 @@
 pub mod audio;
+pub mod command_palette;
 pub mod layout_manager;
 pub mod pane;
+pub mod rate_limits;
+pub mod replace_ticker;
 pub mod theme_editor;
 
+pub use command_palette::CommandPalette;
 use iced::widget::{center, container, mouse_area, opaque, stack};
 use iced::{Alignment, Color, Element, Length, padding};
 pub use layout_manager::LayoutManager;
 pub use pane::indicators;
 pub use pane::stream::{self, ModifierKind};
+pub use replace_ticker::ReplaceTicker;
 pub use theme_editor::ThemeEditor;
 
 pub fn main_dialog_modal<'a, Message>(
@@ -5,7 +5,7 @@ use crate::widget::{labeled_slider, tooltip};
 use data::audio::StreamCfg;
 use exchange::adapter::{Exchange, StreamKind, StreamTicksize};
 
-use exchange::{PushFrequency, Trade};
+use exchange::{Liquidation, PushFrequency, Trade};
 use iced::widget::{button, column, container, row, text};
 use iced::widget::{checkbox, slider, space};
 use iced::{Element, padding};
@@ -26,6 +26,7 @@ pub enum Message {
     ToggleStream(bool, (Exchange, exchange::Ticker)),
     ToggleCard(Exchange, exchange::Ticker),
     SetThreshold(Exchange, exchange::Ticker, data::audio::Threshold),
+    ToggleLiquidationSound(Exchange, exchange::Ticker, bool),
     RetryInit,
 }
 
@@ -134,6 +135,13 @@ impl AudioStream {
                     cfg.threshold = threshold;
                 }
             }
+            Message::ToggleLiquidationSound(exchange, ticker, enabled) => {
+                if let Some(streams) = self.streams.get_mut(&exchange)
+                    && let Some(cfg) = streams.get_mut(&ticker)
+                {
+                    cfg.liquidations = enabled;
+                }
+            }
             Message::RetryInit => match SoundCache::with_default_sounds(self.volume) {
                 Ok(cache) => {
                     self.cache = Some(cache);
@@ -281,6 +289,21 @@ impl AudioStream {
                                 );
                             }
                         }
+
+                        if ticker_info.is_perps() {
+                            column = column.push(
+                                container(
+                                    checkbox(cfg.liquidations)
+                                        .label("Play a sound on liquidations")
+                                        .on_toggle(move |enabled| {
+                                            Message::ToggleLiquidationSound(
+                                                exchange, ticker, enabled,
+                                            )
+                                        }),
+                                )
+                                .padding(8),
+                            );
+                        }
                     }
 
                     available_streams =
@@ -408,6 +431,29 @@ impl AudioStream {
         }
     }
 
+    /// Plays the liquidation sound if the stream opted into it
+    pub fn try_play_liquidation(
+        &mut self,
+        stream: &StreamKind,
+        _liquidation: &Liquidation,
+    ) -> Option<String> {
+        let cfg = self.should_play_sound(stream)?;
+        if !cfg.liquidations {
+            return None;
+        }
+
+        match self.play(SoundType::Liquidation) {
+            Ok(()) => None,
+            Err(err) => {
+                let msg = err.to_string();
+                log::error!("Audio play error: {msg}");
+
+                self.disable_audio(err)
+                    .then(|| format!("Audio disabled: {msg}"))
+            }
+        }
+    }
+
     /// Disables audio and stores the real error. Returns `true` if we just transitioned
     fn disable_audio(&mut self, err: AudioError) -> bool {
         let was_enabled = self.cache.is_some();
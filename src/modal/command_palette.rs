@@ -0,0 +1,236 @@
+use crate::style;
+
+use data::sidebar;
+use exchange::TickerInfo;
+use iced::{
+    Alignment, Element, Length, padding,
+    widget::{button, column, container, row, scrollable, space, text, text_input},
+};
+use uuid::Uuid;
+
+const MAX_RESULTS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Action,
+    Setting,
+    Layout,
+    Ticker,
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Category::Action => write!(f, "Action"),
+            Category::Setting => write!(f, "Setting"),
+            Category::Layout => write!(f, "Layout"),
+            Category::Ticker => write!(f, "Ticker"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    SwitchTicker(TickerInfo),
+    SelectLayout(Uuid),
+    AddLayout,
+    OpenMenu(sidebar::Menu),
+    SetTheme(data::Theme),
+    SetTimezone(data::UserTimezone),
+    SetSidebarPosition(sidebar::Position),
+    OpenDataFolder,
+    ToggleLiveUpdates,
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub category: Category,
+    pub label: String,
+    pub detail: Option<String>,
+    pub command: Command,
+}
+
+impl Entry {
+    pub fn new(category: Category, label: impl Into<String>, command: Command) -> Self {
+        Self {
+            category,
+            label: label.into(),
+            detail: None,
+            command,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    Navigate(isize),
+    Submit,
+    Run(Command),
+}
+
+pub enum Action {
+    Run(Command),
+}
+
+pub struct CommandPalette {
+    query: String,
+    selected: usize,
+    pub input_id: iced::widget::Id,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            selected: 0,
+            input_id: iced::widget::Id::unique(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message, entries: &[Entry]) -> Option<Action> {
+        match message {
+            Message::QueryChanged(query) => {
+                self.query = query;
+                self.selected = 0;
+            }
+            Message::Navigate(step) => {
+                let count = rank(entries, &self.query).len();
+                if count > 0 {
+                    self.selected = self.selected.saturating_add_signed(step).min(count - 1);
+                }
+            }
+            Message::Submit => {
+                return rank(entries, &self.query)
+                    .get(self.selected)
+                    .map(|entry| Action::Run(entry.command.clone()));
+            }
+            Message::Run(command) => return Some(Action::Run(command)),
+        }
+
+        None
+    }
+
+    pub fn view(&self, entries: &[Entry]) -> Element<'_, Message> {
+        let search_box = text_input("Search tickers, layouts, settings...", &self.query)
+            .id(self.input_id.clone())
+            .on_input(Message::QueryChanged)
+            .on_submit(Message::Submit)
+            .padding(8);
+
+        let ranked: Vec<Entry> = rank(entries, &self.query).into_iter().cloned().collect();
+
+        let results: Element<_> = if ranked.is_empty() {
+            container(text("No matches").size(13)).padding(8).into()
+        } else {
+            let rows = ranked.into_iter().enumerate().map(|(idx, entry)| {
+                let is_selected = idx == self.selected;
+
+                let badge = container(text(entry.category.to_string()).size(10))
+                    .padding([2, 6])
+                    .width(Length::Fixed(64.0))
+                    .align_x(Alignment::Center)
+                    .style(style::modal_container);
+
+                let mut content = row![badge, text(entry.label)]
+                    .spacing(8)
+                    .align_y(Alignment::Center);
+
+                if let Some(detail) = entry.detail {
+                    content = content
+                        .push(space::horizontal())
+                        .push(text(detail).size(11).style(style::secondary_text));
+                }
+
+                button(content.width(Length::Fill))
+                    .on_press(Message::Run(entry.command))
+                    .width(Length::Fill)
+                    .style(move |theme, status| {
+                        style::button::menu_body(theme, status, is_selected)
+                    })
+                    .into()
+            });
+
+            scrollable(column(rows).spacing(2).padding(padding::right(8)))
+                .height(Length::Shrink)
+                .into()
+        };
+
+        container(column![search_box, results].spacing(12))
+            .width(480)
+            .max_height(420)
+            .padding(16)
+            .style(style::dashboard_modal)
+            .into()
+    }
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ranks entries against the query, best match first, ties keep category order
+fn rank<'a>(entries: &'a [Entry], query: &str) -> Vec<&'a Entry> {
+    let query = query.trim().to_lowercase();
+
+    let mut scored: Vec<(u32, &Entry)> = entries
+        .iter()
+        .filter_map(|entry| match_score(&entry.label, &query).map(|score| (score, entry)))
+        .collect();
+
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then(a.category.cmp(&b.category))
+            .then(a.label.len().cmp(&b.label.len()))
+    });
+
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+fn match_score(label: &str, query: &str) -> Option<u32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let label = label.to_lowercase();
+
+    if label == query {
+        return Some(1000);
+    }
+    if label.starts_with(query) {
+        return Some(800);
+    }
+    if let Some(pos) = label.find(query) {
+        let at_word_start = label[..pos].ends_with([' ', ':', '/', '-']);
+        return Some(if at_word_start { 600 } else { 400 });
+    }
+
+    // subsequence match, penalized by the gaps between matched characters
+    let mut gaps = 0;
+    let mut chars = label.chars();
+    for q in query.chars() {
+        let mut skipped = 0;
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => skipped += 1,
+                None => return None,
+            }
+        }
+        gaps += skipped;
+    }
+
+    Some(200u32.saturating_sub(gaps * 4).max(1))
+}
@@ -10,6 +10,7 @@ use iced::widget::{
     tooltip::Position as TooltipPosition,
 };
 use iced::{Element, Theme, padding};
+use std::path::PathBuf;
 use std::vec;
 use uuid::Uuid;
 
@@ -31,17 +32,26 @@ pub enum Message {
     ToggleEditMode(Editing),
     CloneLayout(Uuid),
     Reorder(DragEvent),
+    ExportWorkspace,
+    ImportPathChanged(String),
+    ImportWorkspace,
 }
 
 pub enum Action {
     Select(Uuid),
     Clone(Uuid),
+    /// Layout taken out at `index`, kept so the removal can be undone
+    Removed(usize, Box<Layout>),
+    ExportWorkspace,
+    ImportWorkspace(PathBuf),
 }
 
 pub struct LayoutManager {
     pub layouts: Vec<Layout>,
     active_layout_id: Option<Uuid>,
     pub edit_mode: Editing,
+    /// Workspace file typed in to import
+    import_path: String,
 }
 
 impl LayoutManager {
@@ -58,6 +68,7 @@ impl LayoutManager {
             }],
             active_layout_id: Some(default_layout.unique),
             edit_mode: Editing::None,
+            import_path: String::new(),
         }
     }
 
@@ -66,6 +77,7 @@ impl LayoutManager {
             layouts,
             active_layout_id: active_layout.map(|l| l.unique),
             edit_mode: Editing::None,
+            import_path: String::new(),
         }
     }
 
@@ -89,6 +101,25 @@ impl LayoutManager {
         self.layouts.push(Layout { id, dashboard });
     }
 
+    /// Takes out a layout other than the active one, with the position it had
+    pub fn remove_layout(&mut self, id: Uuid) -> Option<(usize, Layout)> {
+        if Some(id) == self.active_layout_id {
+            return None;
+        }
+        let index = self
+            .layouts
+            .iter()
+            .position(|layout| layout.id.unique == id)?;
+
+        Some((index, self.layouts.remove(index)))
+    }
+
+    /// Puts a removed layout back where it was, renamed if its name got taken meanwhile
+    pub fn restore_layout(&mut self, index: usize, mut layout: Layout) {
+        layout.id.name = self.ensure_unique_name(&layout.id.name, layout.id.unique);
+        self.layouts.insert(index.min(self.layouts.len()), layout);
+    }
+
     pub fn generate_unique_layout_name(&self) -> String {
         let mut counter = 1;
         loop {
@@ -163,11 +194,10 @@ impl LayoutManager {
                 return Some(Action::Select(new_layout.unique));
             }
             Message::RemoveLayout(id) => {
-                if Some(id) == self.active_layout_id {
-                    return None;
-                }
-                self.layouts.retain(|layout| layout.id.unique != id);
+                let (index, layout) = self.remove_layout(id)?;
                 self.edit_mode = Editing::Preview;
+
+                return Some(Action::Removed(index, Box::new(layout)));
             }
             Message::SetLayoutName(id, new_name) => {
                 let unique_name = self.ensure_unique_name(&new_name, id);
@@ -191,12 +221,24 @@ impl LayoutManager {
                 return Some(Action::Clone(id));
             }
             Message::Reorder(event) => column_drag::reorder_vec(&mut self.layouts, &event),
+            Message::ExportWorkspace => return Some(Action::ExportWorkspace),
+            Message::ImportPathChanged(path) => self.import_path = path,
+            Message::ImportWorkspace => {
+                let path = self.import_path.trim();
+                if path.is_empty() {
+                    return None;
+                }
+
+                let path = PathBuf::from(path);
+                self.import_path.clear();
+                return Some(Action::ImportWorkspace(path));
+            }
         }
 
         None
     }
 
-    pub fn view(&self) -> Element<'_, Message> {
+    pub fn view(&self, autosave: bool) -> Element<'_, Message> {
         let mut content = column![].spacing(8);
 
         let is_edit_mode = self.edit_mode != Editing::None;
@@ -216,7 +258,11 @@ impl LayoutManager {
                 row![
                     tooltip(
                         button("i").style(style::button::info),
-                        Some("Layouts won't be saved if app exits abruptly"),
+                        Some(if autosave {
+                            "Layouts are autosaved, the latest changes may be lost if app exits abruptly"
+                        } else {
+                            "Layouts won't be saved if app exits abruptly"
+                        }),
                         TooltipPosition::Top,
                     ),
                     edit_btn,
@@ -288,7 +334,12 @@ impl LayoutManager {
                     .push(container(icon_text(Icon::Checkmark, 12)).padding(padding::right(16)));
             }
 
-            let styled_container = container(layout_row.align_y(iced::Alignment::Center))
+            let mut layout_column = column![layout_row.align_y(iced::Alignment::Center)];
+            if self.edit_mode == Editing::Preview {
+                layout_column = layout_column.push(pane_listing(&layout.dashboard));
+            }
+
+            let styled_container = container(layout_column)
                 .style(move |theme| {
                     let palette = theme.extended_palette();
                     let color = if is_active {
@@ -327,6 +378,7 @@ impl LayoutManager {
                     .width(iced::Length::Fill)
                     .on_press(Message::AddLayout),
             );
+            content = content.push(self.workspace_view());
         };
 
         scrollable::Scrollable::with_direction(
@@ -339,6 +391,55 @@ impl LayoutManager {
     }
 }
 
+impl LayoutManager {
+    fn workspace_view(&self) -> Element<'_, Message> {
+        let export_btn = tooltip(
+            button(text("Export workspace"))
+                .width(iced::Length::Fill)
+                .on_press(Message::ExportWorkspace),
+            Some("Saves all layouts and themes to one file in the workspaces folder"),
+            TooltipPosition::Top,
+        );
+
+        let import_input = text_input(
+            &data::config::workspace::Workspace::directory()
+                .join("workspace.json")
+                .to_string_lossy(),
+            &self.import_path,
+        )
+        .on_input(Message::ImportPathChanged)
+        .on_submit(Message::ImportWorkspace)
+        .size(12);
+
+        let import_btn = button(text("Import")).on_press_maybe(
+            (!self.import_path.trim().is_empty()).then_some(Message::ImportWorkspace),
+        );
+
+        column![
+            text("Workspace").size(14),
+            export_btn,
+            row![import_input, import_btn]
+                .spacing(4)
+                .align_y(iced::Alignment::Center),
+        ]
+        .spacing(8)
+        .padding(padding::top(8))
+        .into()
+    }
+}
+
+fn pane_listing<'a>(dashboard: &Dashboard) -> Element<'a, Message> {
+    let titles = dashboard
+        .pane_titles()
+        .into_iter()
+        .map(|title| text(title).size(11).style(style::secondary_text).into());
+
+    iced::widget::Column::with_children(titles)
+        .spacing(2)
+        .padding(padding::left(12).bottom(6))
+        .into()
+}
+
 fn create_delete_button<'a>(layout: &LayoutId) -> Element<'a, Message> {
     create_icon_button(
         style::Icon::TrashBin,
@@ -3,8 +3,12 @@ use iced::{
     widget::{container, mouse_area, opaque},
 };
 
+pub mod alerts;
+pub mod drawings;
 pub mod indicators;
 pub mod mini_tickers_list;
+pub mod price_menu;
+pub mod price_scale_menu;
 pub mod settings;
 pub mod stream;
 
@@ -14,8 +18,21 @@ pub enum Modal {
     MiniTickersList(mini_tickers_list::MiniPanel),
     Settings,
     Indicators,
+    /// Inputs of one indicator instance by id, opened from its row in the indicators list
+    IndicatorParams(u16),
     LinkGroup,
     Controls,
+    Alerts,
+    Drawings,
+    /// Right click menu of a price level, at the clicked position in the pane body
+    PriceMenu {
+        price: exchange::util::Price,
+        position: iced::Point,
+    },
+    /// Right click menu of the price axis, at the clicked height
+    PriceScaleMenu {
+        y: f32,
+    },
 }
 
 pub fn stack_modal<'a, Message>(
@@ -0,0 +1,390 @@
+use crate::screen::dashboard::pane::{self, Message};
+use crate::style::{self, Icon, icon_text};
+use crate::widget::button_with_tooltip;
+
+use data::chart::alert::{
+    AlertTrigger, Alerts, Combinator, CompositeAlert, Condition, ConditionOp, Metric, PriceAlert,
+};
+use exchange::Ticker;
+use iced::{
+    Alignment, Element, Length,
+    widget::{button, column, container, pane_grid, pick_list, row, scrollable, text, text_input},
+};
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Adds a crossing alert at the last traded price
+    Add,
+    PriceEdited(u32, String),
+    TriggerChanged(u32, AlertTrigger),
+    ToggleMute(u32),
+    Rearm(u32),
+    JumpTo(u32),
+    Delete(u32),
+    /// Opens the condition builder for a new composite alert
+    NewComposite,
+    Builder(BuilderAction),
+    SaveComposite,
+    DiscardComposite,
+}
+
+#[derive(Debug, Clone)]
+pub enum BuilderAction {
+    Combinator(Combinator),
+    AddCondition,
+    RemoveCondition(usize),
+    Metric(usize, Metric),
+    Op(usize, ConditionOp),
+    Value(usize, String),
+    Bars(usize, u16),
+}
+
+const BAR_CHOICES: [u16; 6] = [1, 2, 3, 5, 10, 20];
+
+/// Composite alert being put together, kept until it's saved or discarded
+#[derive(Debug, Clone, Default)]
+pub struct CompositeBuilder {
+    combinator: Combinator,
+    conditions: Vec<(Condition, String)>,
+}
+
+impl CompositeBuilder {
+    /// Starts from a single "price above" condition at `last_price`
+    pub fn new(last_price: Option<f32>) -> Self {
+        let mut builder = Self::default();
+        builder.push_condition(Metric::Price, last_price.unwrap_or_default());
+        builder
+    }
+
+    fn push_condition(&mut self, metric: Metric, value: f32) {
+        self.conditions.push((
+            Condition {
+                metric,
+                op: ConditionOp::Above,
+                value,
+                bars: 3,
+            },
+            value.to_string(),
+        ));
+    }
+
+    pub fn update(&mut self, action: BuilderAction) {
+        match action {
+            BuilderAction::Combinator(combinator) => self.combinator = combinator,
+            BuilderAction::AddCondition => self.push_condition(Metric::Cvd, 0.0),
+            BuilderAction::RemoveCondition(idx) => {
+                if idx < self.conditions.len() {
+                    self.conditions.remove(idx);
+                }
+            }
+            BuilderAction::Metric(idx, metric) => {
+                if let Some((condition, _)) = self.conditions.get_mut(idx) {
+                    condition.metric = metric;
+                }
+            }
+            BuilderAction::Op(idx, op) => {
+                if let Some((condition, _)) = self.conditions.get_mut(idx) {
+                    condition.op = op;
+                }
+            }
+            BuilderAction::Value(idx, input) => {
+                if let Some((condition, draft)) = self.conditions.get_mut(idx) {
+                    if let Ok(value) = input.parse::<f32>() {
+                        condition.value = value;
+                    }
+                    *draft = input;
+                }
+            }
+            BuilderAction::Bars(idx, bars) => {
+                if let Some((condition, _)) = self.conditions.get_mut(idx) {
+                    condition.bars = bars;
+                }
+            }
+        }
+    }
+
+    /// Conditions to save, `None` while empty or a threshold doesn't parse
+    pub fn build(&self) -> Option<(Combinator, Vec<Condition>)> {
+        let is_valid = !self.conditions.is_empty()
+            && self
+                .conditions
+                .iter()
+                .all(|(condition, draft)| condition.op.is_trend() || draft.parse::<f32>().is_ok());
+
+        is_valid.then(|| {
+            (
+                self.combinator,
+                self.conditions.iter().map(|(c, _)| *c).collect(),
+            )
+        })
+    }
+}
+
+/// Every alert the pane holds for `ticker`, with inline edit and management actions
+pub fn view<'a>(
+    pane: pane_grid::Pane,
+    alerts: &'a Alerts,
+    ticker: Option<Ticker>,
+    price_drafts: &'a [(u32, String)],
+    builder: Option<&'a CompositeBuilder>,
+) -> Element<'a, Message> {
+    let on_action = move |action| Message::PaneEvent(pane, pane::Event::Alerts(action));
+
+    let header = row![
+        text("Alerts").size(14),
+        iced::widget::space::horizontal(),
+        button(text("+ Add at last price").size(12))
+            .on_press_maybe(ticker.map(|_| on_action(Action::Add)))
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+    ]
+    .align_y(Alignment::Center);
+
+    let rows: Vec<Element<_>> = ticker
+        .map(|ticker| {
+            let price_rows = alerts.for_ticker(ticker).map(|alert| {
+                let draft = price_drafts
+                    .iter()
+                    .find(|(id, _)| *id == alert.id)
+                    .map(|(_, draft)| draft.as_str());
+                alert_row(alert, draft, on_action)
+            });
+
+            let composite_rows = alerts
+                .composites
+                .iter()
+                .filter(|alert| alert.ticker == ticker)
+                .map(|alert| composite_row(alert, on_action));
+
+            price_rows.chain(composite_rows).collect()
+        })
+        .unwrap_or_default();
+
+    let list: Element<_> = if rows.is_empty() {
+        container(text("No alerts on this ticker").size(12))
+            .padding(8)
+            .into()
+    } else {
+        scrollable(column(rows).spacing(4))
+            .height(Length::Shrink)
+            .into()
+    };
+
+    let composite: Element<_> = match builder {
+        Some(builder) => builder_view(builder, on_action),
+        None => button(text("+ Composite alert").size(12))
+            .on_press_maybe(ticker.map(|_| on_action(Action::NewComposite)))
+            .style(|theme, status| style::button::transparent(theme, status, false))
+            .into(),
+    };
+
+    container(column![header, list, composite].spacing(8))
+        .max_width(340)
+        .max_height(360)
+        .padding(16)
+        .style(style::chart_modal)
+        .into()
+}
+
+fn alert_row<'a>(
+    alert: &PriceAlert,
+    draft: Option<&'a str>,
+    on_action: impl Fn(Action) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let id = alert.id;
+    let tooltip_pos = iced::widget::tooltip::Position::Top;
+
+    let price_input = text_input("Price", draft.unwrap_or(&alert.price.to_string()))
+        .on_input(move |input| on_action(Action::PriceEdited(id, input)))
+        .size(12)
+        .width(Length::Fixed(88.0));
+
+    let trigger = pick_list(AlertTrigger::ALL, Some(alert.trigger), move |trigger| {
+        on_action(Action::TriggerChanged(id, trigger))
+    })
+    .text_size(12);
+
+    let mute_icon = if alert.muted {
+        Icon::SpeakerOff
+    } else {
+        Icon::SpeakerHigh
+    };
+
+    let status: Element<_> = if alert.is_armed() {
+        text("").into()
+    } else {
+        button(text("Fired").size(11))
+            .on_press(on_action(Action::Rearm(id)))
+            .style(|theme, status| style::button::transparent(theme, status, true))
+            .into()
+    };
+
+    row![
+        price_input,
+        trigger,
+        status,
+        iced::widget::space::horizontal(),
+        button_with_tooltip(
+            icon_text(Icon::Return, 12),
+            on_action(Action::JumpTo(id)),
+            Some("Jump to"),
+            tooltip_pos,
+            |theme, status| style::button::transparent(theme, status, false),
+        ),
+        button_with_tooltip(
+            icon_text(mute_icon, 12),
+            on_action(Action::ToggleMute(id)),
+            Some(if alert.muted { "Unmute" } else { "Mute" }),
+            tooltip_pos,
+            move |theme, status| style::button::transparent(theme, status, false),
+        ),
+        button_with_tooltip(
+            icon_text(Icon::TrashBin, 12),
+            on_action(Action::Delete(id)),
+            Some("Delete"),
+            tooltip_pos,
+            |theme, status| style::button::transparent(theme, status, false),
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn composite_row<'a>(
+    alert: &CompositeAlert,
+    on_action: impl Fn(Action) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let id = alert.id;
+    let tooltip_pos = iced::widget::tooltip::Position::Top;
+
+    let mute_icon = if alert.muted {
+        Icon::SpeakerOff
+    } else {
+        Icon::SpeakerHigh
+    };
+
+    let status: Element<_> = if alert.is_armed() {
+        text("").into()
+    } else {
+        button(text("Fired").size(11))
+            .on_press(on_action(Action::Rearm(id)))
+            .style(|theme, status| style::button::transparent(theme, status, true))
+            .into()
+    };
+
+    row![
+        text(alert.to_string()).size(12).width(Length::Fill),
+        status,
+        button_with_tooltip(
+            icon_text(mute_icon, 12),
+            on_action(Action::ToggleMute(id)),
+            Some(if alert.muted { "Unmute" } else { "Mute" }),
+            tooltip_pos,
+            move |theme, status| style::button::transparent(theme, status, false),
+        ),
+        button_with_tooltip(
+            icon_text(Icon::TrashBin, 12),
+            on_action(Action::Delete(id)),
+            Some("Delete"),
+            tooltip_pos,
+            |theme, status| style::button::transparent(theme, status, false),
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn builder_view<'a>(
+    builder: &'a CompositeBuilder,
+    on_action: impl Fn(Action) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let on_builder = move |action| on_action(Action::Builder(action));
+
+    let combinator = row![
+        text("Match").size(12),
+        pick_list(Combinator::ALL, Some(builder.combinator), move |c| {
+            on_builder(BuilderAction::Combinator(c))
+        })
+        .text_size(12),
+        text("of the conditions").size(12),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    let mut conditions = column![].spacing(4);
+
+    for (idx, (condition, draft)) in builder.conditions.iter().enumerate() {
+        let threshold: Element<_> = if condition.op.is_trend() {
+            row![
+                text("over").size(12),
+                pick_list(BAR_CHOICES, Some(condition.bars), move |bars| {
+                    on_builder(BuilderAction::Bars(idx, bars))
+                })
+                .text_size(12),
+                text("bars").size(12),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .into()
+        } else {
+            text_input("Value", draft)
+                .on_input(move |input| on_builder(BuilderAction::Value(idx, input)))
+                .size(12)
+                .width(Length::Fixed(80.0))
+                .into()
+        };
+
+        conditions = conditions.push(
+            row![
+                pick_list(Metric::ALL, Some(condition.metric), move |metric| {
+                    on_builder(BuilderAction::Metric(idx, metric))
+                })
+                .text_size(12),
+                pick_list(ConditionOp::ALL, Some(condition.op), move |op| {
+                    on_builder(BuilderAction::Op(idx, op))
+                })
+                .text_size(12),
+                threshold,
+                iced::widget::space::horizontal(),
+                button(icon_text(Icon::TrashBin, 12))
+                    .on_press(on_builder(BuilderAction::RemoveCondition(idx)))
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    let mut content =
+        column![text("New composite alert").size(13), combinator, conditions].spacing(8);
+
+    if builder
+        .conditions
+        .iter()
+        .any(|(c, _)| c.metric == Metric::OpenInterest)
+    {
+        content = content.push(text("Open interest conditions need the OI indicator").size(11));
+    }
+
+    content = content.push(
+        row![
+            button(text("+ Condition").size(12))
+                .on_press(on_builder(BuilderAction::AddCondition))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+            iced::widget::space::horizontal(),
+            button(text("Discard").size(12))
+                .on_press(on_action(Action::DiscardComposite))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+            button(text("Save").size(12))
+                .on_press_maybe(builder.build().map(|_| on_action(Action::SaveComposite))),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+    );
+
+    container(content)
+        .padding(8)
+        .style(style::modal_container)
+        .into()
+}
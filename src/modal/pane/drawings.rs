@@ -0,0 +1,76 @@
+use crate::screen::dashboard::pane::{self, Message};
+use crate::style;
+
+use data::chart::drawing::{DrawingTool, FIB_LEVEL_CHOICES};
+use iced::{
+    Element, Length,
+    widget::{button, checkbox, column, container, pane_grid, row, rule, text},
+};
+
+/// Drawing tool picker, the picked tool places one drawing on the next click or drag
+pub fn view<'a>(
+    pane: pane_grid::Pane,
+    active: Option<DrawingTool>,
+    drawing_count: usize,
+    fib_levels: &[f32],
+) -> Element<'a, Message> {
+    let mut tools = column![].spacing(2);
+
+    for tool in DrawingTool::ALL {
+        let is_active = active == Some(tool);
+
+        tools = tools.push(
+            button(text(tool.to_string()).size(12))
+                .width(Length::Fill)
+                .on_press(Message::PaneEvent(
+                    pane,
+                    pane::Event::DrawingToolSelected(tool),
+                ))
+                .style(move |theme, status| style::button::transparent(theme, status, is_active)),
+        );
+    }
+
+    let mut fib_choices = column![].spacing(4);
+
+    for chunk in FIB_LEVEL_CHOICES.chunks(3) {
+        let mut choices = row![].spacing(4);
+
+        for &level in chunk {
+            choices = choices.push(
+                checkbox(fib_levels.contains(&level))
+                    .label(level.to_string())
+                    .text_size(11)
+                    .on_toggle(move |_| {
+                        Message::PaneEvent(pane, pane::Event::FibLevelToggled(level))
+                    })
+                    .width(Length::Fill),
+            );
+        }
+        fib_choices = fib_choices.push(choices);
+    }
+
+    let clear_btn = button(text(format!("Clear drawings ({drawing_count})")).size(12))
+        .width(Length::Fill)
+        .on_press_maybe(
+            (drawing_count > 0).then_some(Message::PaneEvent(pane, pane::Event::ClearDrawings)),
+        )
+        .style(|theme, status| style::button::transparent(theme, status, false));
+
+    container(
+        column![
+            text("Drawing tools").size(13),
+            tools,
+            rule::horizontal(1.0).style(style::split_ruler),
+            text("Fib levels").size(12),
+            fib_choices,
+            rule::horizontal(1.0).style(style::split_ruler),
+            clear_btn,
+            text("Drag anchors to move, right click one to delete").size(11),
+        ]
+        .spacing(6),
+    )
+    .width(Length::Fixed(200.0))
+    .padding(12)
+    .style(style::chart_modal)
+    .into()
+}
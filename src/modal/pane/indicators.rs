@@ -1,25 +1,28 @@
+use crate::chart::kline::KlineChart;
+use crate::modal::pane::Modal;
 use crate::screen::dashboard::pane::{self, Message};
 use crate::style::{self, Icon, icon_text};
-use crate::widget::{column_drag, dragger_row};
+use crate::widget::{column_drag, dragger_row, labeled_slider};
 
-use data::chart::indicator::{Indicator, UiIndicator};
+use data::chart::indicator::{
+    Indicator, IndicatorParams, KlineIndicator, KlineInstance, UiIndicator,
+};
+use data::chart::moving_average::PRESET_COLORS;
 use iced::{
-    Element, Length, padding,
+    Alignment, Element, Length, padding,
     widget::{button, column, container, pane_grid, row, space, text},
 };
 
 pub fn view<'a, I>(
     pane: pane_grid::Pane,
-    state: &'a pane::State,
     selected: &[I],
     market_type: Option<exchange::adapter::MarketKind>,
 ) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
 {
-    let content_allows_dragging = matches!(state.content, pane::Content::Kline { .. });
     let content_row = if let Some(market) = market_type {
-        content_row(pane, selected, market, content_allows_dragging)
+        content_row(pane, selected, market)
     } else {
         column![].spacing(4).into()
     };
@@ -31,6 +34,231 @@ where
         .into()
 }
 
+/// Kline indicators are listed per instance, kinds with inputs stay available to add again
+pub fn kline_view<'a>(
+    pane: pane_grid::Pane,
+    chart: &KlineChart,
+    selected: &[KlineInstance],
+    market_type: Option<exchange::adapter::MarketKind>,
+) -> Element<'a, Message> {
+    let mut col = iced::widget::Column::new().spacing(4);
+
+    if market_type.is_some() && !selected.is_empty() {
+        col = col.push(instance_list(pane, selected));
+    }
+
+    if let Some(market) = market_type {
+        let available: Vec<KlineIndicator> = KlineIndicator::for_market(market)
+            .iter()
+            .filter(|kind| kind.has_params() || !selected.iter().any(|i| i.kind == **kind))
+            .copied()
+            .collect();
+        if !available.is_empty() {
+            col = col.push(available_list(pane, &available));
+        }
+    }
+
+    let content_row = column![
+        container(text("Indicators").size(14)).padding(padding::bottom(8)),
+        col
+    ]
+    .spacing(4);
+
+    container(column![content_row, overlays_row(pane, chart)].spacing(12))
+        .max_width(200)
+        .padding(16)
+        .style(style::chart_modal)
+        .into()
+}
+
+/// Popover editing the inputs of one instance, applied to the chart as they change
+pub fn params_view<'a>(
+    pane: pane_grid::Pane,
+    instance: KlineInstance,
+    defaults: IndicatorParams,
+) -> Element<'a, Message> {
+    let params = instance.params.unwrap_or(defaults);
+    let on_change = move |params: IndicatorParams| {
+        Message::PaneEvent(
+            pane,
+            pane::Event::IndicatorParamsChanged(instance.id, Some(params)),
+        )
+    };
+
+    let (min, max) = IndicatorParams::PERIOD_RANGE.into_inner();
+    let period_slider = |period: u16, set: fn(&mut IndicatorParams, u16)| {
+        labeled_slider(
+            "Period",
+            f32::from(min)..=f32::from(max),
+            f32::from(period),
+            move |value| {
+                let mut params = params;
+                set(&mut params, value as u16);
+                on_change(params)
+            },
+            |value| format!("{value}"),
+            Some(1.0),
+        )
+    };
+
+    let color_row = |color: Option<iced::Color>,
+                     set: fn(&mut IndicatorParams, Option<iced::Color>)| {
+        // cycles through the presets on each press
+        let next_color = color
+            .and_then(|color| PRESET_COLORS.iter().position(|preset| *preset == color))
+            .map_or(PRESET_COLORS[0], |pos| {
+                PRESET_COLORS[(pos + 1) % PRESET_COLORS.len()]
+            });
+
+        let swatch = button(
+            container(text(""))
+                .width(Length::Fixed(12.0))
+                .height(Length::Fixed(12.0)),
+        )
+        .padding(2)
+        .on_press({
+            let mut params = params;
+            set(&mut params, Some(next_color));
+            on_change(params)
+        })
+        .style(
+            move |theme: &iced::Theme, _status| iced::widget::button::Style {
+                background: Some(
+                    color
+                        .unwrap_or(theme.extended_palette().secondary.strong.color)
+                        .into(),
+                ),
+                border: iced::border::rounded(2),
+                ..Default::default()
+            },
+        );
+
+        let reset = button(text("Theme color"))
+            .on_press_maybe(color.is_some().then(|| {
+                let mut params = params;
+                set(&mut params, None);
+                on_change(params)
+            }))
+            .style(|theme, status| style::button::transparent(theme, status, false));
+
+        row![text("Color"), swatch, space::horizontal(), reset]
+            .spacing(8)
+            .align_y(Alignment::Center)
+    };
+
+    let inputs: Element<_> = match instance.kind {
+        KlineIndicator::Rsi => {
+            let overbought = labeled_slider(
+                "Overbought",
+                IndicatorParams::OVERBOUGHT_RANGE,
+                params.rsi_overbought,
+                move |rsi_overbought| {
+                    on_change(IndicatorParams {
+                        rsi_overbought,
+                        ..params
+                    })
+                },
+                |value| format!("{value:.0}"),
+                Some(1.0),
+            );
+            let oversold = labeled_slider(
+                "Oversold",
+                IndicatorParams::OVERSOLD_RANGE,
+                params.rsi_oversold,
+                move |rsi_oversold| {
+                    on_change(IndicatorParams {
+                        rsi_oversold,
+                        ..params
+                    })
+                },
+                |value| format!("{value:.0}"),
+                Some(1.0),
+            );
+
+            column![
+                period_slider(params.rsi_period, |p, period| p.rsi_period = period),
+                overbought,
+                oversold,
+                color_row(params.rsi_color, |p, color| p.rsi_color = color),
+            ]
+            .spacing(8)
+            .into()
+        }
+        KlineIndicator::Atr => column![
+            period_slider(params.atr_period, |p, period| p.atr_period = period),
+            color_row(params.atr_color, |p, color| p.atr_color = color),
+        ]
+        .spacing(8)
+        .into(),
+        KlineIndicator::Volume
+        | KlineIndicator::OpenInterest
+        | KlineIndicator::AggregatedOpenInterest => text("No inputs to edit").into(),
+    };
+
+    let back = button(icon_text(Icon::Return, 12))
+        .on_press(Message::PaneEvent(
+            pane,
+            pane::Event::ShowModal(Modal::Indicators),
+        ))
+        .style(|theme, status| style::button::transparent(theme, status, false));
+
+    let reset = button(text("Defaults"))
+        .on_press_maybe(instance.params.is_some().then_some(Message::PaneEvent(
+            pane,
+            pane::Event::IndicatorParamsChanged(instance.id, None),
+        )))
+        .style(|theme, status| style::button::transparent(theme, status, false));
+
+    container(
+        column![
+            row![
+                back,
+                text(instance.kind.to_string()).size(14),
+                space::horizontal(),
+                reset
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+            inputs,
+        ]
+        .spacing(12),
+    )
+    .width(Length::Fixed(240.0))
+    .padding(16)
+    .style(style::chart_modal)
+    .into()
+}
+
+fn overlays_row<'a>(pane: pane_grid::Pane, chart: &KlineChart) -> Element<'a, Message> {
+    let toggle_row = |label: &'static str, is_selected: bool, event: pane::Event| {
+        let content = if is_selected {
+            row![
+                text(label),
+                space::horizontal(),
+                container(icon_text(Icon::Checkmark, 12)),
+            ]
+        } else {
+            row![text(label)]
+        };
+
+        button(content.width(Length::Fill))
+            .on_press(Message::PaneEvent(pane, event))
+            .width(Length::Fill)
+            .style(move |theme, status| style::button::modifier(theme, status, is_selected))
+    };
+
+    column![
+        container(text("Overlays").size(14)).padding(padding::bottom(8)),
+        toggle_row(
+            "Support/Resistance",
+            chart.shows_support_resistance(),
+            pane::Event::ToggleSupportResistance,
+        ),
+    ]
+    .spacing(4)
+    .into()
+}
+
 fn build_indicator_row<'a, I>(
     pane: pane_grid::Pane,
     indicator: &I,
@@ -60,11 +288,49 @@ where
         .into()
 }
 
-fn selected_list<'a, I>(
-    pane: pane_grid::Pane,
-    selected: &[I],
-    reorderable: bool,
-) -> Element<'a, Message>
+/// Row of an added kline indicator, pressing it removes that instance only
+fn build_instance_row<'a>(pane: pane_grid::Pane, instance: &KlineInstance) -> Element<'a, Message> {
+    let mut content = row![text(instance.label()), space::horizontal()]
+        .spacing(4)
+        .align_y(Alignment::Center);
+    if instance.kind.has_params() {
+        content = content.push(
+            button(icon_text(Icon::Cog, 12))
+                .padding(0)
+                .on_press(Message::PaneEvent(
+                    pane,
+                    pane::Event::ShowModal(Modal::IndicatorParams(instance.id)),
+                ))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+        );
+    }
+
+    button(
+        content
+            .push(container(icon_text(Icon::Checkmark, 12)))
+            .width(Length::Fill),
+    )
+    .on_press(Message::PaneEvent(
+        pane,
+        pane::Event::RemoveIndicator(instance.id),
+    ))
+    .width(Length::Fill)
+    .style(|theme, status| style::button::modifier(theme, status, true))
+    .into()
+}
+
+fn instance_list<'a>(pane: pane_grid::Pane, selected: &[KlineInstance]) -> Element<'a, Message> {
+    let reorderable = selected.len() >= 2;
+
+    let elements: Vec<Element<_>> = selected
+        .iter()
+        .map(|instance| dragger_row(build_instance_row(pane, instance), reorderable))
+        .collect();
+
+    draggable_list(pane, elements, reorderable)
+}
+
+fn selected_list<'a, I>(pane: pane_grid::Pane, selected: &[I]) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
 {
@@ -72,10 +338,18 @@ where
         .iter()
         .map(|indicator| {
             let base = build_indicator_row(pane, indicator, true);
-            dragger_row(base, reorderable)
+            dragger_row(base, false)
         })
         .collect();
 
+    draggable_list(pane, elements, false)
+}
+
+fn draggable_list<'a>(
+    pane: pane_grid::Pane,
+    elements: Vec<Element<'a, Message>>,
+    reorderable: bool,
+) -> Element<'a, Message> {
     if reorderable {
         let mut draggable_column = column_drag::Column::new()
             .on_drag(move |event| Message::PaneEvent(pane, pane::Event::ReorderIndicator(event)))
@@ -112,15 +386,12 @@ fn content_row<'a, I>(
     pane: pane_grid::Pane,
     selected: &[I],
     market: exchange::adapter::MarketKind,
-    allows_drag: bool,
 ) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
 {
-    let reorderable = allows_drag && selected.len() >= 2;
-
     let selected_list = if !selected.is_empty() {
-        Some(selected_list(pane, selected, reorderable))
+        Some(selected_list(pane, selected))
     } else {
         None
     };
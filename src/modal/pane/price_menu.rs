@@ -0,0 +1,88 @@
+use crate::screen::dashboard::pane::{self, Message};
+use crate::style;
+
+use data::paper::{Command, Side};
+use exchange::util::{MinTicksize, Price};
+use iced::{
+    Element, Length,
+    widget::{button, column, container, pane_grid, rule, text},
+};
+
+/// Context menu of a price level, opened by right clicking a chart or the ladder
+pub fn view<'a>(
+    pane: pane_grid::Pane,
+    price: Price,
+    precision: MinTicksize,
+    is_pinned: bool,
+    has_pins: bool,
+) -> Element<'a, Message> {
+    let price_label = price.to_string(precision);
+
+    let pin_btn = button(
+        text(if is_pinned {
+            format!("Unpin {price_label}")
+        } else {
+            format!("Pin {price_label}")
+        })
+        .size(12),
+    )
+    .width(Length::Fill)
+    .on_press(Message::PaneEvent(pane, pane::Event::TogglePin(price)))
+    .style(|theme, status| style::button::transparent(theme, status, false));
+
+    let clear_btn = button(text("Clear pins").size(12))
+        .width(Length::Fill)
+        .on_press_maybe(has_pins.then_some(Message::PaneEvent(pane, pane::Event::ClearPins)))
+        .style(|theme, status| style::button::transparent(theme, status, false));
+
+    let paper_btn = |label: String, command: Command| {
+        button(text(label).size(12))
+            .width(Length::Fill)
+            .on_press(Message::PaneEvent(pane, pane::Event::PaperTrade(command)))
+            .style(|theme, status| style::button::transparent(theme, status, false))
+    };
+    let limit = Some(price);
+
+    container(
+        column![
+            pin_btn,
+            clear_btn,
+            rule::horizontal(1.0).style(style::split_ruler),
+            container(text("Paper trading").size(11).style(style::secondary_text)).padding([2, 10]),
+            paper_btn(
+                format!("Buy limit {price_label}"),
+                Command::Place {
+                    side: Side::Buy,
+                    limit,
+                },
+            ),
+            paper_btn(
+                format!("Sell limit {price_label}"),
+                Command::Place {
+                    side: Side::Sell,
+                    limit,
+                },
+            ),
+            paper_btn(
+                "Buy market".to_string(),
+                Command::Place {
+                    side: Side::Buy,
+                    limit: None,
+                },
+            ),
+            paper_btn(
+                "Sell market".to_string(),
+                Command::Place {
+                    side: Side::Sell,
+                    limit: None,
+                },
+            ),
+            paper_btn("Flatten".to_string(), Command::Flatten),
+        ]
+        .spacing(2),
+    )
+    .width(Length::Fixed(160.0))
+    .padding(4)
+    .style(style::chart_modal)
+    .into()
+}
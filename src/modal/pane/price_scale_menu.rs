@@ -0,0 +1,56 @@
+use crate::chart::PriceScaleAction;
+use crate::screen::dashboard::pane::{self, Message};
+use crate::style;
+
+use data::chart::PriceScale;
+use iced::{
+    Element, Length,
+    widget::{button, column, container, pane_grid, text},
+};
+
+/// Context menu of the price axis, opened by right clicking its gutter
+pub fn view<'a>(
+    pane: pane_grid::Pane,
+    price_scale: PriceScale,
+    supports_log: bool,
+) -> Element<'a, Message> {
+    let action_btn = |label: &'static str, action, is_active: bool| {
+        button(text(label).size(12))
+            .width(Length::Fill)
+            .on_press(Message::PaneEvent(pane, pane::Event::PriceScale(action)))
+            .style(move |theme, status| style::button::transparent(theme, status, is_active))
+    };
+
+    let mut items = column![
+        action_btn("Reset autoscale", PriceScaleAction::ResetAutoscale, false),
+        action_btn(
+            if price_scale.locked {
+                "Unlock scale"
+            } else {
+                "Lock scale"
+            },
+            PriceScaleAction::ToggleLock,
+            price_scale.locked,
+        ),
+        action_btn(
+            "Invert scale",
+            PriceScaleAction::ToggleInvert,
+            price_scale.inverted
+        ),
+    ]
+    .spacing(2);
+
+    if supports_log {
+        items = items.push(action_btn(
+            "Log scale",
+            PriceScaleAction::ToggleLog,
+            price_scale.log,
+        ));
+    }
+
+    container(items)
+        .width(Length::Fixed(140.0))
+        .padding(4)
+        .style(style::chart_modal)
+        .into()
+}
@@ -5,17 +5,24 @@ use crate::split_column;
 use crate::widget::{classic_slider_row, labeled_slider};
 use crate::{style, tooltip, widget::scrollable_content};
 
+use data::chart::colors::{ChartColors, ColorRole};
+use data::chart::comparison::{DerivedKind, DerivedSeries};
+use data::chart::divergence::DeltaDivergence;
 use data::chart::heatmap::HeatmapStudy;
-use data::chart::kline::FootprintStudy;
+use data::chart::kline::{FootprintStudy, ZoomLens};
+use data::chart::moving_average::{BollingerBands, MaKind, MovingAverage, PRESET_COLORS};
+use data::chart::session::{SessionSettings, SessionTime, SessionWindow};
 use data::chart::{
-    KlineChartKind,
-    heatmap::{self, CoalesceKind},
+    KlineChartKind, LiquidationMarkers, Navigation, TradingConfig, Watermark,
+    heatmap::{self, CoalesceKind, DepthNormalization},
     kline::ClusterKind,
 };
 use data::layout::pane::VisualConfig;
 use data::panel::ladder;
-use data::panel::timeandsales::{StackedBar, StackedBarRatio};
-use data::util::format_with_commas;
+use data::panel::timeandsales::{SideFilter, SizeTiers, StackedBar, StackedBarRatio};
+use data::panel::volume_profile::{self, Lookback};
+use data::util::{abbr_large_numbers, format_with_commas};
+use exchange::SerTicker;
 
 use iced::widget::{checkbox, space};
 use iced::{
@@ -41,10 +48,14 @@ where
 
 pub fn heatmap_cfg_view<'a>(
     cfg: heatmap::Config,
+    navigation: Navigation,
+    watermark: Watermark,
     pane: pane_grid::Pane,
     study_config: &'a study::Configurator<HeatmapStudy>,
     studies: &'a [HeatmapStudy],
     basis: data::chart::Basis,
+    colors: ChartColors,
+    can_revert: bool,
 ) -> Element<'a, Message> {
     let trade_size_slider = {
         let filter = cfg.trade_size_filter;
@@ -227,6 +238,92 @@ pub fn heatmap_cfg_view<'a>(
         col
     };
 
+    let depth_coloring_column = {
+        let normalization = cfg.depth_normalization;
+        let on_select = move |value| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config {
+                    depth_normalization: value,
+                    ..cfg
+                }),
+                false,
+            )
+        };
+
+        let pct = match normalization {
+            DepthNormalization::Percentile(pct) => pct,
+            _ => DepthNormalization::DEFAULT_PERCENTILE,
+        };
+
+        let modes = [
+            ("Linear", DepthNormalization::Linear),
+            ("Log", DepthNormalization::Log),
+            ("Percentile", DepthNormalization::Percentile(pct)),
+            ("Per column", DepthNormalization::PerColumn),
+        ]
+        .map(|(label, mode)| -> Element<'a, Message> {
+            radio(label, mode, Some(normalization), on_select)
+                .spacing(4)
+                .into()
+        });
+
+        let mut col = column![text("Depth coloring").size(14), row(modes).spacing(12),].spacing(8);
+
+        if let DepthNormalization::Percentile(pct) = normalization {
+            col = col.push(classic_slider_row(
+                text("Clip above"),
+                slider(DepthNormalization::PERCENTILE_RANGE, pct, move |value| {
+                    on_select(DepthNormalization::Percentile(value))
+                })
+                .into(),
+                Some(text(format!("{pct}th pct")).size(13)),
+            ));
+        }
+        col
+    };
+
+    let time_travel_column = {
+        let memory_mb = cfg.scrub_memory_mb;
+        let budget_slider = classic_slider_row(
+            text("Memory"),
+            slider(
+                0..=heatmap::Config::MAX_SCRUB_MEMORY_MB,
+                memory_mb,
+                move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Heatmap(heatmap::Config {
+                            scrub_memory_mb: value,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+            )
+            .step(8u32)
+            .into(),
+            Some(
+                text(if memory_mb == 0 {
+                    "Off".to_string()
+                } else {
+                    format!("{memory_mb} MB")
+                })
+                .size(13),
+            ),
+        );
+
+        column![
+            tooltip(
+                text("Time travel").size(14),
+                Some("Order books kept to scrub back through from the timeline under the chart"),
+                TooltipPosition::Top,
+            ),
+            budget_slider,
+        ]
+        .spacing(8)
+    };
+
     let trade_viz_column = {
         let dyn_checkbox = checkbox(cfg.trade_size_scale.is_some())
             .label("Dynamic circle radius")
@@ -258,12 +355,28 @@ pub fn heatmap_cfg_view<'a>(
     let content = split_column![
         size_filters_column,
         noise_filters_column,
+        depth_coloring_column,
+        time_travel_column,
         trade_viz_column,
         column![text("Studies").size(14), study_cfg].spacing(8),
-        row![
-            space::horizontal(),
-            sync_all_button(pane, VisualConfig::Heatmap(cfg))
-        ]
+        liquidations_column(cfg.liquidations, move |liquidations| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config { liquidations, ..cfg }),
+                false,
+            )
+        }),
+        navigation_column(pane, navigation),
+        watermark_column(pane, watermark),
+        colors_column(pane, colors, &ColorRole::HEATMAP),
+        sessions_column(cfg.sessions, false, move |sessions| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config { sessions, ..cfg }),
+                false,
+            )
+        }),
+        settings_actions(pane, VisualConfig::Heatmap(cfg), can_revert)
         ; spacing = 12, align_x = Alignment::Start
     ];
 
@@ -273,10 +386,11 @@ pub fn heatmap_cfg_view<'a>(
 pub fn timesales_cfg_view<'a>(
     cfg: timeandsales::Config,
     pane: pane_grid::Pane,
+    can_revert: bool,
 ) -> Element<'a, Message> {
     let trade_size_column = {
         let filter = cfg.trade_size_filter;
-        let slider = labeled_slider(
+        let size_slider = labeled_slider(
             "Trade",
             0.0..=50000.0,
             filter,
@@ -294,7 +408,223 @@ pub fn timesales_cfg_view<'a>(
             Some(500.0),
         );
 
-        column![text("Size filter").size(14), slider].spacing(8)
+        let size_cap = cfg.trade_size_cap.map(|cap| {
+            button(text(format!("Below ${}  ×", abbr_large_numbers(cap))).size(12))
+                .on_press(Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        trade_size_cap: None,
+                        ..cfg
+                    }),
+                    false,
+                ))
+                .style(|theme, status| style::button::transparent(theme, status, true))
+        });
+
+        let presets = row(timeandsales::Config::SIZE_FILTER_PRESETS.map(|preset| {
+            let label = if preset > 0.0 {
+                abbr_large_numbers(preset)
+            } else {
+                "All".to_string()
+            };
+
+            button(text(label).size(12))
+                .on_press(Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        trade_size_filter: preset,
+                        trade_size_cap: None,
+                        ..cfg
+                    }),
+                    false,
+                ))
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, filter == preset)
+                })
+                .into()
+        }))
+        .spacing(4);
+
+        let side_picklist = pick_list(SideFilter::ALL, Some(cfg.side_filter), move |side_filter| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::TimeAndSales(timeandsales::Config { side_filter, ..cfg }),
+                false,
+            )
+        });
+
+        let price_band = {
+            let band_checkbox = checkbox(cfg.price_band_pct.is_some())
+                .label("Price band")
+                .on_toggle(move |enabled| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            price_band_pct: enabled.then_some(0.5),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                });
+
+            let mut band_column = column![band_checkbox].spacing(8);
+
+            if let Some(band_pct) = cfg.price_band_pct {
+                let slider_ui = slider(
+                    timeandsales::Config::PRICE_BAND_RANGE,
+                    band_pct,
+                    move |value| {
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::TimeAndSales(timeandsales::Config {
+                                price_band_pct: Some(value),
+                                ..cfg
+                            }),
+                            false,
+                        )
+                    },
+                )
+                .step(0.05);
+
+                band_column = band_column.push(classic_slider_row(
+                    text("Within"),
+                    slider_ui.into(),
+                    Some(text(format!("±{band_pct:.2}% of last")).size(13)),
+                ));
+            }
+
+            band_column
+        };
+
+        column![
+            text("Size filter").size(14),
+            size_slider,
+            presets,
+            size_cap,
+            side_picklist,
+            price_band
+        ]
+        .spacing(8)
+    };
+
+    let clustering_column = {
+        let cluster_checkbox = checkbox(cfg.cluster_window_ms.is_some())
+            .label("Cluster prints")
+            .on_toggle(move |enabled| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        cluster_window_ms: enabled.then_some(100),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut cluster_column = column![
+            row![
+                text("Prints").size(14),
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Consecutive trades at the same price and side are summed into one row"),
+                    TooltipPosition::Top,
+                )
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+            cluster_checkbox
+        ]
+        .spacing(8);
+
+        if let Some(window_ms) = cfg.cluster_window_ms {
+            let window_picklist = pick_list(
+                timeandsales::Config::CLUSTER_WINDOW_CHOICES,
+                Some(window_ms),
+                move |window_ms| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            cluster_window_ms: Some(window_ms),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+            );
+
+            cluster_column = cluster_column.push(
+                row![
+                    text("Within").size(12),
+                    window_picklist,
+                    text("ms").size(12)
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        cluster_column
+    };
+
+    let size_tiers_column = {
+        let tiers = cfg.size_tiers;
+
+        let enable_checkbox = checkbox(tiers.enabled)
+            .label("Highlight large prints")
+            .on_toggle(move |enabled| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        size_tiers: SizeTiers { enabled, ..tiers },
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut tiers_column = column![text("Size tiers").size(14), enable_checkbox].spacing(8);
+
+        if tiers.enabled {
+            for (idx, threshold) in tiers.thresholds.into_iter().enumerate() {
+                let threshold_picklist = pick_list(
+                    SizeTiers::THRESHOLD_CHOICES,
+                    Some(threshold),
+                    move |value| {
+                        let mut thresholds = tiers.thresholds;
+                        thresholds[idx] = value;
+
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::TimeAndSales(timeandsales::Config {
+                                size_tiers: SizeTiers {
+                                    thresholds,
+                                    ..tiers
+                                },
+                                ..cfg
+                            }),
+                            false,
+                        )
+                    },
+                );
+
+                tiers_column = tiers_column.push(
+                    row![
+                        text(format!("Tier {}", idx + 1)).size(12),
+                        threshold_picklist,
+                        text(if threshold > 0.0 {
+                            format!(">${}", abbr_large_numbers(threshold))
+                        } else {
+                            "Off".to_string()
+                        })
+                        .size(12),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                );
+            }
+        }
+
+        tiers_column
     };
 
     let retention_minutes = (cfg.trade_retention.as_secs_f32() / 60.0).max(1.0);
@@ -430,9 +760,11 @@ pub fn timesales_cfg_view<'a>(
 
     let content = split_column![
         trade_size_column,
+        size_tiers_column,
+        clustering_column,
         history_column,
         stacked_bar,
-        row![space::horizontal(), sync_all_button(pane, VisualConfig::TimeAndSales(cfg))],
+        settings_actions(pane, VisualConfig::TimeAndSales(cfg), can_revert),
         ; spacing = 12, align_x = Alignment::Start
     ];
 
@@ -442,31 +774,282 @@ pub fn timesales_cfg_view<'a>(
 pub fn comparison_cfg_view<'a>(
     pane: pane_grid::Pane,
     chart: &'a ComparisonChart,
+    can_revert: bool,
 ) -> Element<'a, Message> {
     let series = &chart.series;
     let series_editor = &chart.series_editor;
 
-    let content = column![series_editor.view(series).map(move |msg| {
-        Message::PaneEvent(
-            pane,
-            Event::ComparisonChartInteraction(crate::chart::comparison::Message::Editor(msg)),
-        )
-    })];
+    let cfg = chart.serializable_config();
+    let merge_checkbox = {
+        let cfg_toggle = cfg.clone();
+        checkbox(cfg.merge_stablecoin_quotes)
+            .label("Merge USDT/USDC/FDUSD quotes of the same base")
+            .on_toggle(move |merge_stablecoin_quotes| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Comparison(data::chart::comparison::Config {
+                        merge_stablecoin_quotes,
+                        ..cfg_toggle.clone()
+                    }),
+                    false,
+                )
+            })
+    };
+
+    let correlation_checkbox = {
+        let cfg_toggle = cfg.clone();
+        checkbox(cfg.show_correlation)
+            .label("Show correlation with the base series")
+            .on_toggle(move |show_correlation| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Comparison(data::chart::comparison::Config {
+                        show_correlation,
+                        ..cfg_toggle.clone()
+                    }),
+                    false,
+                )
+            })
+    };
+
+    let content = column![
+        merge_checkbox,
+        correlation_checkbox,
+        derived_series_column(pane, &cfg, series),
+        series_editor.view(series).map(move |msg| {
+            Message::PaneEvent(
+                pane,
+                Event::ComparisonChartInteraction(crate::chart::comparison::Message::Editor(msg)),
+            )
+        }),
+        row![space::horizontal(), revert_button(pane, can_revert)],
+    ]
+    .spacing(12);
 
     cfg_view_container(320, content)
 }
 
+/// Leg of a derived series, shown by symbol and venue
+#[derive(Debug, Clone, PartialEq)]
+struct Leg(SerTicker);
+
+impl std::fmt::Display for Leg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.ticker.symbol_and_exchange_string())
+    }
+}
+
+fn derived_series_column<'a>(
+    pane: pane_grid::Pane,
+    cfg: &data::chart::comparison::Config,
+    series: &[crate::widget::chart::Series],
+) -> Element<'a, Message> {
+    let legs: Vec<Leg> = series
+        .iter()
+        .map(|s| Leg(SerTicker::from_parts(s.ticker_info.ticker)))
+        .collect();
+
+    let on_change = {
+        let cfg = cfg.clone();
+        move |derived: Vec<DerivedSeries>| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Comparison(data::chart::comparison::Config {
+                    derived,
+                    ..cfg.clone()
+                }),
+                false,
+            )
+        }
+    };
+
+    let mut col = column![text("Spreads and ratios").size(14)].spacing(8);
+
+    for (idx, derived) in cfg.derived.iter().enumerate() {
+        let edited = |edit: &dyn Fn(&mut DerivedSeries)| {
+            let mut all = cfg.derived.clone();
+            edit(&mut all[idx]);
+            on_change(all)
+        };
+
+        let next_color = PRESET_COLORS
+            .iter()
+            .position(|color| *color == derived.color)
+            .map_or(PRESET_COLORS[0], |pos| {
+                PRESET_COLORS[(pos + 1) % PRESET_COLORS.len()]
+            });
+        let color = derived.color;
+
+        let swatch = button(
+            container(text(""))
+                .width(Length::Fixed(12.0))
+                .height(Length::Fixed(12.0)),
+        )
+        .padding(2)
+        .on_press(edited(&|d| d.color = next_color))
+        .style(move |_theme, _status| iced::widget::button::Style {
+            background: Some(color.into()),
+            border: iced::border::rounded(2),
+            ..Default::default()
+        });
+
+        let leg_pick = |leg: &SerTicker, set: fn(&mut DerivedSeries, SerTicker)| {
+            let on_change = on_change.clone();
+            let all = cfg.derived.clone();
+            pick_list(legs.clone(), Some(Leg(leg.clone())), move |Leg(ser)| {
+                let mut all = all.clone();
+                set(&mut all[idx], ser);
+                on_change(all)
+            })
+        };
+
+        let kind = {
+            let on_change = on_change.clone();
+            let all = cfg.derived.clone();
+            pick_list(DerivedKind::ALL, Some(derived.kind), move |kind| {
+                let mut all = all.clone();
+                all[idx].kind = kind;
+                on_change(all)
+            })
+        };
+
+        col = col.push(
+            row![
+                swatch,
+                kind,
+                space::horizontal(),
+                button(text("Remove"))
+                    .on_press({
+                        let mut all = cfg.derived.clone();
+                        all.remove(idx);
+                        on_change(all)
+                    })
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+        col = col.push(
+            row![
+                text("A"),
+                leg_pick(&derived.a, |d, ser| d.a = ser),
+                text("B"),
+                leg_pick(&derived.b, |d, ser| d.b = ser),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    if let [a, b, ..] = legs.as_slice() {
+        let mut all = cfg.derived.clone();
+        all.push(DerivedSeries {
+            a: a.0.clone(),
+            b: b.0.clone(),
+            kind: DerivedKind::default(),
+            color: PRESET_COLORS[cfg.derived.len() % PRESET_COLORS.len()],
+        });
+
+        col = col.push(
+            button(text("+ Add spread or ratio"))
+                .on_press(on_change(all))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+        );
+    }
+
+    col.into()
+}
+
+#[derive(Debug, Clone)]
+pub enum MovingAverageAction {
+    Add,
+    Remove(usize),
+    Kind(usize, MaKind),
+    Period(usize, u16),
+    Color(usize, iced::Color),
+}
+
 pub fn kline_cfg_view<'a>(
     study_config: &'a study::Configurator<FootprintStudy>,
     cfg: data::chart::kline::Config,
+    moving_averages: &'a [MovingAverage],
+    navigation: Navigation,
+    watermark: Watermark,
     kind: &'a KlineChartKind,
     pane: pane_grid::Pane,
     basis: data::chart::Basis,
+    trading_cfg: Option<&TradingConfig>,
+    colors: ChartColors,
+    can_revert: bool,
 ) -> Element<'a, Message> {
+    let forming_bar = {
+        let picklist = pick_list(
+            data::chart::kline::FormingBar::ALL,
+            Some(cfg.forming_bar),
+            move |forming_bar| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Kline(data::chart::kline::Config { forming_bar, ..cfg }),
+                    false,
+                )
+            },
+        );
+
+        column![
+            text("Forming bar").size(14),
+            picklist,
+            text("Applies to candles and volume bars until the bar closes"),
+        ]
+        .spacing(8)
+    };
+
+    let bollinger = bollinger_column(cfg.bollinger, move |bollinger| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(data::chart::kline::Config { bollinger, ..cfg }),
+            false,
+        )
+    });
+
+    let delta_divergence = divergence_column(cfg.delta_divergence, move |delta_divergence| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(data::chart::kline::Config {
+                delta_divergence,
+                ..cfg
+            }),
+            false,
+        )
+    });
+
+    let sessions = move || {
+        sessions_column(
+            cfg.sessions,
+            matches!(kind, KlineChartKind::Footprint { .. }),
+            move |sessions| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Kline(data::chart::kline::Config { sessions, ..cfg }),
+                    false,
+                )
+            },
+        )
+    };
+
     let content = match kind {
-        KlineChartKind::Candles => column![text(
-            "This chart type doesn't have any configurations, WIP..."
-        )],
+        KlineChartKind::Candles => split_column![
+            forming_bar,
+            moving_averages_column(pane, moving_averages),
+            bollinger,
+            delta_divergence,
+            navigation_column(pane, navigation),
+            watermark_column(pane, watermark),
+            colors_column(pane, colors, &ColorRole::KLINE),
+            sessions(),
+            large_orders_column(pane, trading_cfg),
+            settings_actions(pane, VisualConfig::Kline(cfg), can_revert),
+            ; spacing = 12, align_x = Alignment::Start
+        ],
         KlineChartKind::Footprint {
             clusters,
             scaling,
@@ -519,19 +1102,243 @@ pub fn kline_cfg_view<'a>(
                 column![text("Cluster type").size(14), cluster_picklist].spacing(8),
                 column![text("Cluster scaling").size(14), scaling].spacing(8),
                 column![text("Studies").size(14), study_cfg].spacing(8),
-                row![
-                    space::horizontal(),
-                    sync_all_button(pane, VisualConfig::Kline(cfg))
-                ],
-                ; spacing = 12, align_x = Alignment::Start
-            ]
-        }
-    };
-
-    cfg_view_container(360, content)
+                zoom_lens_column(cfg.zoom_lens, move |zoom_lens| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config { zoom_lens, ..cfg }),
+                        false,
+                    )
+                }),
+                forming_bar,
+                moving_averages_column(pane, moving_averages),
+                bollinger,
+                delta_divergence,
+                navigation_column(pane, navigation),
+                watermark_column(pane, watermark),
+                colors_column(pane, colors, &ColorRole::KLINE),
+                liquidations_column(cfg.liquidations, move |liquidations| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(data::chart::kline::Config { liquidations, ..cfg }),
+                        false,
+                    )
+                }),
+                sessions(),
+                large_orders_column(pane, trading_cfg),
+                settings_actions(pane, VisualConfig::Kline(cfg), can_revert),
+                ; spacing = 12, align_x = Alignment::Start
+            ]
+        }
+    };
+
+    cfg_view_container(360, content)
+}
+
+fn liquidations_column<'a>(
+    markers: LiquidationMarkers,
+    on_change: impl Fn(LiquidationMarkers) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enabled = checkbox(markers.enabled)
+        .label("Show liquidations")
+        .on_toggle(move |enabled| on_change(LiquidationMarkers { enabled, ..markers }));
+
+    let mut col = column![
+        text("Liquidations").size(14),
+        enabled,
+        text("Perpetual markets only"),
+    ]
+    .spacing(8);
+
+    if markers.enabled {
+        col = col.push(labeled_slider(
+            "Min size",
+            0.0..=500_000.0,
+            markers.min_size,
+            move |min_size| {
+                on_change(LiquidationMarkers {
+                    min_size,
+                    ..markers
+                })
+            },
+            |value| format!(">${}", format_with_commas(*value)),
+            Some(1000.0),
+        ));
+    }
+
+    col.into()
+}
+
+fn bollinger_column<'a>(
+    bands: BollingerBands,
+    on_change: impl Fn(BollingerBands) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enabled = checkbox(bands.enabled)
+        .label("Show Bollinger bands")
+        .on_toggle(move |enabled| on_change(BollingerBands { enabled, ..bands }));
+
+    let mut col = column![text("Bollinger bands").size(14), enabled].spacing(8);
+
+    if bands.enabled {
+        let (min_period, max_period) = BollingerBands::PERIOD_RANGE.into_inner();
+
+        col = col
+            .push(labeled_slider(
+                "Period",
+                f32::from(min_period)..=f32::from(max_period),
+                f32::from(bands.period),
+                move |value| {
+                    on_change(BollingerBands {
+                        period: value as u16,
+                        ..bands
+                    })
+                },
+                |value| format!("{value}"),
+                Some(1.0),
+            ))
+            .push(labeled_slider(
+                "Std. deviations",
+                BollingerBands::MULTIPLIER_RANGE,
+                bands.multiplier,
+                move |multiplier| {
+                    on_change(BollingerBands {
+                        multiplier,
+                        ..bands
+                    })
+                },
+                |value| format!("{value:.1}"),
+                Some(0.1),
+            ));
+    }
+
+    col.into()
+}
+
+fn divergence_column<'a>(
+    study: DeltaDivergence,
+    on_change: impl Fn(DeltaDivergence) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enabled = checkbox(study.enabled)
+        .label("Mark delta divergences")
+        .on_toggle(move |enabled| on_change(DeltaDivergence { enabled, ..study }));
+
+    let mut col = column![text("Delta divergence").size(14), enabled].spacing(8);
+
+    if study.enabled {
+        let (min_lookback, max_lookback) = DeltaDivergence::LOOKBACK_RANGE.into_inner();
+
+        col = col.push(labeled_slider(
+            "Lookback",
+            f32::from(min_lookback)..=f32::from(max_lookback),
+            f32::from(study.lookback),
+            move |value| {
+                on_change(DeltaDivergence {
+                    lookback: value as u16,
+                    ..study
+                })
+            },
+            |value| format!("{value} bars"),
+            Some(1.0),
+        ));
+    }
+
+    col.into()
+}
+
+fn zoom_lens_column<'a>(
+    lens: ZoomLens,
+    on_change: impl Fn(ZoomLens) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enabled = checkbox(lens.enabled)
+        .label("Magnify cells while Alt is held")
+        .on_toggle(move |enabled| on_change(ZoomLens { enabled, ..lens }));
+
+    let mut col = column![text("Zoom lens").size(14), enabled].spacing(8);
+
+    if lens.enabled {
+        col = col.push(labeled_slider(
+            "Magnification",
+            ZoomLens::MAGNIFICATION_RANGE,
+            lens.magnification,
+            move |magnification| {
+                on_change(ZoomLens {
+                    magnification,
+                    ..lens
+                })
+            },
+            |value| format!("{value:.1}x"),
+            Some(0.5),
+        ));
+    }
+
+    col.into()
+}
+
+fn sessions_column<'a>(
+    settings: SessionSettings,
+    show_reset: bool,
+    on_change: impl Fn(SessionSettings) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let shading = checkbox(settings.show_shading)
+        .label("Shade sessions on time axis")
+        .on_toggle(move |show_shading| {
+            on_change(SessionSettings {
+                show_shading,
+                ..settings
+            })
+        });
+
+    let mut col = column![text("Sessions (UTC)").size(14), shading].spacing(8);
+
+    if show_reset {
+        col = col.push(
+            checkbox(settings.reset_studies)
+                .label("Reset studies at session open")
+                .on_toggle(move |reset_studies| {
+                    on_change(SessionSettings {
+                        reset_studies,
+                        ..settings
+                    })
+                }),
+        );
+    }
+
+    let with_window = move |idx: usize, window: SessionWindow| {
+        let mut manager = settings.manager;
+        manager.windows[idx] = window;
+        on_change(SessionSettings {
+            manager,
+            ..settings
+        })
+    };
+
+    for (idx, window) in settings.manager.windows.into_iter().enumerate() {
+        let enabled = checkbox(window.enabled)
+            .label(window.kind.to_string())
+            .on_toggle(move |enabled| with_window(idx, SessionWindow { enabled, ..window }))
+            .width(Length::Fixed(100.0));
+
+        let start = pick_list(SessionTime::all_steps(), Some(window.start), move |start| {
+            with_window(idx, SessionWindow { start, ..window })
+        });
+        let end = pick_list(SessionTime::all_steps(), Some(window.end), move |end| {
+            with_window(idx, SessionWindow { end, ..window })
+        });
+
+        col = col.push(
+            row![enabled, start, text("-"), end]
+                .spacing(4)
+                .align_y(Alignment::Center),
+        );
+    }
+
+    col.into()
 }
 
-pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+pub fn ladder_cfg_view<'a>(
+    cfg: ladder::Config,
+    pane: pane_grid::Pane,
+    can_revert: bool,
+) -> Element<'a, Message> {
     let display_options = {
         let spread = checkbox(cfg.show_spread)
             .label("Show Spread")
@@ -546,6 +1353,19 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
                 )
             });
 
+        let spread_history = checkbox(cfg.show_spread_history)
+            .label("Show Spread History")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_spread_history: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
         let chase_tracker = checkbox(cfg.show_chase_tracker)
             .label("Show Chase Tracker")
             .on_toggle(move |value| {
@@ -563,6 +1383,16 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
             text("Display Options").size(14),
             column![
                 spread,
+                row![
+                    spread_history,
+                    tooltip(
+                        button("i").style(style::button::info),
+                        Some("Bid-ask spread in ticks over the last 5 minutes, above the ladder.\nThe current value turns amber when wider than usual."),
+                        TooltipPosition::Top,
+                    )
+                ]
+                .align_y(Alignment::Center)
+                .spacing(4),
                 row![
                     chase_tracker,
                     tooltip(
@@ -604,19 +1434,490 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
 
     let history_column = column![text("History").size(14), retention_slider].spacing(8);
 
+    let trade_flow_column = {
+        let toggle = checkbox(cfg.show_trade_flow)
+            .label("Show Trade Flow")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_trade_flow: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let window_secs = cfg.trade_flow_window.as_secs().max(1);
+
+        let window_slider = classic_slider_row(
+            text("Decay window"),
+            slider(1.0..=120.0, window_secs as f32, move |new_secs| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        trade_flow_window: Duration::from_secs(new_secs.round().max(1.0) as u64),
+                        ..cfg
+                    }),
+                    false,
+                )
+            })
+            .step(1.0)
+            .into(),
+            Some(text(format!("{window_secs}s")).size(13)),
+        );
+
+        let mut col = column![
+            text("Trade Flow").size(14),
+            row![
+                toggle,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Executed volume per level split by aggressor side.\nOlder trades fade out over the decay window."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
+        ]
+        .spacing(8);
+
+        if cfg.show_trade_flow {
+            col = col.push(window_slider);
+        }
+        col
+    };
+
+    let imbalance_column = {
+        let toggle = checkbox(cfg.show_imbalance)
+            .label("Show Imbalance")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_imbalance: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let levels_slider = classic_slider_row(
+            text("Cumulative levels"),
+            slider(1.0..=20.0, cfg.imbalance_levels as f32, move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        imbalance_levels: value.round().max(1.0) as usize,
+                        ..cfg
+                    }),
+                    false,
+                )
+            })
+            .step(1.0)
+            .into(),
+            Some(text(cfg.imbalance_levels.to_string()).size(13)),
+        );
+
+        let threshold_slider = classic_slider_row(
+            text("Highlight at"),
+            slider(1.5..=10.0, cfg.imbalance_threshold, move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        imbalance_threshold: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            })
+            .step(0.5)
+            .into(),
+            Some(text(format!("{:.1}x", cfg.imbalance_threshold)).size(13)),
+        );
+
+        let mut col = column![
+            text("Imbalance").size(14),
+            row![
+                toggle,
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some("Size of each level over the opposite side's size at the same distance from the touch.\nLeft: the level alone, right: summed from the touch, within the cumulative levels."),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
+        ]
+        .spacing(8);
+
+        if cfg.show_imbalance {
+            col = col.push(levels_slider).push(threshold_slider);
+        }
+        col
+    };
+
     let content = split_column![
         display_options,
+        trade_flow_column,
+        imbalance_column,
         history_column,
-        row![
-            space::horizontal(),
-            sync_all_button(pane, VisualConfig::Ladder(cfg))
-        ],
+        settings_actions(pane, VisualConfig::Ladder(cfg), can_revert),
         ; spacing = 12, align_x = Alignment::Start
     ];
 
     cfg_view_container(320, content)
 }
 
+pub fn volume_profile_cfg_view<'a>(
+    cfg: volume_profile::Config,
+    pane: pane_grid::Pane,
+    can_revert: bool,
+) -> Element<'a, Message> {
+    let lookback_column = {
+        let buttons = row(Lookback::ALL.map(|lookback| {
+            button(text(lookback.to_string()).size(12))
+                .on_press(Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::VolumeProfile(volume_profile::Config { lookback, ..cfg }),
+                    false,
+                ))
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, cfg.lookback == lookback)
+                })
+                .into()
+        }))
+        .spacing(4);
+
+        column![
+            row![
+                text("Lookback").size(14),
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some(
+                        "Visible range follows the time window of a chart in the pane's link group"
+                    ),
+                    TooltipPosition::Top,
+                )
+            ]
+            .align_y(Alignment::Center)
+            .spacing(4),
+            buttons,
+        ]
+        .spacing(8)
+    };
+
+    let value_area_column = column![
+        text("Value area").size(14),
+        classic_slider_row(
+            text("Volume share"),
+            slider(
+                volume_profile::Config::VALUE_AREA_RANGE,
+                cfg.value_area,
+                move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::VolumeProfile(volume_profile::Config {
+                            value_area: value,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+            )
+            .step(0.05)
+            .into(),
+            Some(text(format!("{:.0}%", cfg.value_area * 100.0)).size(13)),
+        ),
+    ]
+    .spacing(8);
+
+    let content = split_column![
+        lookback_column,
+        value_area_column,
+        settings_actions(pane, VisualConfig::VolumeProfile(cfg), can_revert),
+        ; spacing = 12, align_x = Alignment::Start
+    ];
+
+    cfg_view_container(320, content)
+}
+
+fn navigation_column<'a>(pane: pane_grid::Pane, navigation: Navigation) -> Element<'a, Message> {
+    let on_change = move |navigation| {
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(crate::chart::Message::NavigationChanged(navigation)),
+        )
+    };
+
+    let snap = checkbox(navigation.snap_to_bars)
+        .label("Snap panning to whole bars")
+        .on_toggle(move |snap_to_bars| {
+            on_change(Navigation {
+                snap_to_bars,
+                ..navigation
+            })
+        });
+
+    let kinetic = checkbox(navigation.kinetic)
+        .label("Kinetic scrolling")
+        .on_toggle(move |kinetic| {
+            on_change(Navigation {
+                kinetic,
+                ..navigation
+            })
+        });
+
+    let mut col = column![text("Navigation").size(14), snap, kinetic].spacing(8);
+
+    if navigation.kinetic {
+        col = col.push(labeled_slider(
+            "Friction",
+            Navigation::FRICTION_RANGE,
+            navigation.friction,
+            move |friction| {
+                on_change(Navigation {
+                    friction,
+                    ..navigation
+                })
+            },
+            |value| format!("{:.0}%", value * 100.0),
+            Some(0.01),
+        ));
+    }
+
+    col.into()
+}
+
+/// Swatch per color role, pressing one cycles through the presets
+fn colors_column<'a>(
+    pane: pane_grid::Pane,
+    colors: ChartColors,
+    roles: &[ColorRole],
+) -> Element<'a, Message> {
+    let on_change = move |role, color| Message::PaneEvent(pane, Event::ColorChanged(role, color));
+
+    let mut col = column![text("Colors").size(14)].spacing(8);
+
+    for &role in roles {
+        let current = colors.get(role);
+
+        let swatch = button(
+            container(text(""))
+                .width(Length::Fixed(12.0))
+                .height(Length::Fixed(12.0)),
+        )
+        .padding(2)
+        .on_press(on_change(role, Some(colors.next_swatch(role))))
+        .style(
+            move |theme: &iced::Theme, _status| iced::widget::button::Style {
+                background: current.map(Into::into),
+                border: iced::border::rounded(2)
+                    .width(1)
+                    .color(theme.extended_palette().background.strong.color),
+                ..Default::default()
+            },
+        );
+
+        let mut entry = row![swatch, text(role.to_string())]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+        if current.is_some() {
+            entry = entry.push(space::horizontal()).push(
+                button(text("Theme"))
+                    .on_press(on_change(role, None))
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+            );
+        }
+        col = col.push(entry);
+    }
+
+    col.into()
+}
+
+fn watermark_column<'a>(pane: pane_grid::Pane, watermark: Watermark) -> Element<'a, Message> {
+    let on_change = move |watermark| {
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(crate::chart::Message::WatermarkChanged(watermark)),
+        )
+    };
+
+    let enabled = checkbox(watermark.enabled)
+        .label("Show symbol watermark")
+        .on_toggle(move |enabled| {
+            on_change(Watermark {
+                enabled,
+                ..watermark
+            })
+        });
+
+    let mut col = column![text("Watermark").size(14), enabled].spacing(8);
+
+    if watermark.enabled {
+        col = col.push(labeled_slider(
+            "Opacity",
+            Watermark::OPACITY_RANGE,
+            watermark.opacity,
+            move |opacity| {
+                on_change(Watermark {
+                    opacity,
+                    ..watermark
+                })
+            },
+            |value| format!("{:.0}%", value * 100.0),
+            Some(0.01),
+        ));
+    }
+
+    col.into()
+}
+
+fn moving_averages_column<'a>(
+    pane: pane_grid::Pane,
+    moving_averages: &'a [MovingAverage],
+) -> Element<'a, Message> {
+    let on_action = move |action| Message::PaneEvent(pane, Event::MovingAverages(action));
+
+    let mut col = column![text("Moving averages").size(14)].spacing(8);
+
+    for (idx, ma) in moving_averages.iter().enumerate() {
+        // cycles through the presets on each press
+        let next_color = PRESET_COLORS
+            .iter()
+            .position(|color| *color == ma.color)
+            .map_or(PRESET_COLORS[0], |pos| {
+                PRESET_COLORS[(pos + 1) % PRESET_COLORS.len()]
+            });
+        let color = ma.color;
+
+        let swatch = button(
+            container(text(""))
+                .width(Length::Fixed(12.0))
+                .height(Length::Fixed(12.0)),
+        )
+        .padding(2)
+        .on_press(on_action(MovingAverageAction::Color(idx, next_color)))
+        .style(move |_theme, _status| iced::widget::button::Style {
+            background: Some(color.into()),
+            border: iced::border::rounded(2),
+            ..Default::default()
+        });
+
+        let kind = pick_list(MaKind::ALL, Some(ma.kind), move |kind| {
+            on_action(MovingAverageAction::Kind(idx, kind))
+        });
+
+        let (min, max) = MovingAverage::PERIOD_RANGE.into_inner();
+        let period = labeled_slider(
+            "Period",
+            f32::from(min)..=f32::from(max),
+            f32::from(ma.period),
+            move |value| on_action(MovingAverageAction::Period(idx, value as u16)),
+            |value| format!("{value}"),
+            Some(1.0),
+        );
+
+        col = col.push(
+            row![
+                swatch,
+                kind,
+                space::horizontal(),
+                button(text("Remove"))
+                    .on_press(on_action(MovingAverageAction::Remove(idx)))
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+        col = col.push(period);
+    }
+
+    if moving_averages.len() < MovingAverage::MAX_COUNT {
+        col = col.push(
+            button(text("+ Add moving average"))
+                .on_press(on_action(MovingAverageAction::Add))
+                .style(|theme, status| style::button::transparent(theme, status, false)),
+        );
+    }
+
+    col.into()
+}
+
+fn large_orders_column<'a>(
+    pane: pane_grid::Pane,
+    trading_cfg: Option<&TradingConfig>,
+) -> Element<'a, Message> {
+    let (is_shown, threshold) = trading_cfg
+        .map_or((false, TradingConfig::default().volume_threshold), |cfg| {
+            (cfg.show_large_orders, cfg.volume_threshold)
+        });
+
+    let enable_checkbox = checkbox(is_shown)
+        .label("Show large order bubbles")
+        .on_toggle(move |_| Message::PaneEvent(pane, Event::ToggleLargeOrders));
+
+    // thresholds span several magnitudes across tickers, so the slider is logarithmic
+    let threshold_slider = {
+        let exponent = threshold.max(1.0).log10();
+
+        let slider_ui = slider(0.0..=6.0, exponent, move |new_exponent| {
+            Message::PaneEvent(
+                pane,
+                Event::LargeOrderThresholdChanged(10f32.powf(new_exponent).round()),
+            )
+        })
+        .step(0.05);
+
+        classic_slider_row(
+            text("Min size"),
+            slider_ui.into(),
+            Some(text(format!("≥ {}", abbr_large_numbers(threshold))).size(13)),
+        )
+    };
+
+    column![
+        row![
+            text("Large orders").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Bubbles mark price levels where a bar traded at least the minimum size on one side\nBubble area scales with volume, see the legend on the chart"),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        enable_checkbox,
+        threshold_slider,
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn settings_actions<'a>(
+    pane: pane_grid::Pane,
+    config: VisualConfig,
+    can_revert: bool,
+) -> Element<'a, Message> {
+    row![
+        revert_button(pane, can_revert),
+        space::horizontal(),
+        sync_all_button(pane, config),
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn revert_button<'a>(pane: pane_grid::Pane, can_revert: bool) -> Element<'a, Message> {
+    tooltip(
+        button("Revert")
+            .on_press_maybe(can_revert.then_some(Message::PaneEvent(pane, Event::RevertSettings))),
+        Some("Undo changes made since this menu was opened"),
+        TooltipPosition::Top,
+    )
+}
+
 fn sync_all_button<'a>(pane: pane_grid::Pane, config: VisualConfig) -> Element<'a, Message> {
     tooltip(
         button("Sync all").on_press(Message::VisualConfigChanged(pane, config, true)),
@@ -631,10 +1932,10 @@ pub mod study {
         style::{self, Icon, icon_text},
     };
     use data::chart::heatmap::{CLEANUP_THRESHOLD, HeatmapStudy, ProfileKind};
-    use data::chart::kline::FootprintStudy;
+    use data::chart::kline::{BarStatsRows, DEFAULT_MIN_STACK, FootprintStudy, PocScope};
     use iced::{
         Element, padding,
-        widget::{button, checkbox, column, container, row, slider, space, text},
+        widget::{button, checkbox, column, container, pick_list, row, slider, space, text},
     };
 
     #[derive(Debug, Clone, Copy)]
@@ -668,23 +1969,35 @@ pub mod study {
             on_change: impl Fn(Self) -> Message<Self> + Copy + 'a,
         ) -> Element<'a, Message<Self>> {
             match *self {
-                FootprintStudy::NPoC { lookback } => {
+                FootprintStudy::NPoC { lookback, scope } => {
                     let slider_ui = slider(10.0..=400.0, lookback as f32, move |new_value| {
                         on_change(FootprintStudy::NPoC {
                             lookback: new_value as usize,
+                            scope,
                         })
                     })
                     .step(10.0);
 
-                    column![text(format!("Lookback: {lookback} datapoints")), slider_ui]
-                        .padding(8)
-                        .spacing(4)
-                        .into()
+                    let scope_picklist = pick_list(PocScope::ALL, Some(scope), move |scope| {
+                        on_change(FootprintStudy::NPoC { lookback, scope })
+                    });
+
+                    column![
+                        text(format!("Lookback: {lookback} datapoints")),
+                        slider_ui,
+                        row![text("POC of"), scope_picklist]
+                            .spacing(8)
+                            .align_y(iced::Alignment::Center),
+                    ]
+                    .padding(8)
+                    .spacing(4)
+                    .into()
                 }
                 FootprintStudy::Imbalance {
                     threshold,
                     color_scale,
                     ignore_zeros,
+                    stacked,
                 } => {
                     let qty_threshold = {
                         let info_text = text(format!("Ask:Bid threshold: {threshold}%"));
@@ -695,6 +2008,7 @@ pub mod study {
                                     threshold: new_value as usize,
                                     color_scale,
                                     ignore_zeros,
+                                    stacked,
                                 })
                             })
                             .step(25.0);
@@ -717,6 +2031,7 @@ pub mod study {
                                         None
                                     },
                                     ignore_zeros,
+                                    stacked,
                                 })
                             });
 
@@ -728,6 +2043,7 @@ pub mod study {
                                         threshold,
                                         color_scale: Some(new_value as usize),
                                         ignore_zeros,
+                                        stacked,
                                     })
                                 })
                                 .step(50.0)
@@ -749,6 +2065,7 @@ pub mod study {
                                     threshold,
                                     color_scale,
                                     ignore_zeros: is_checked,
+                                    stacked,
                                 })
                             },
                         );
@@ -756,9 +2073,88 @@ pub mod study {
                         column![cbox].padding(8).spacing(4)
                     };
 
-                    split_column![qty_threshold, color_scaling, ignore_zeros_checkbox]
-                        .padding(4)
-                        .into()
+                    let stacked_levels = {
+                        let min_stack = stacked.unwrap_or(DEFAULT_MIN_STACK);
+
+                        let stacked_checkbox = checkbox(stacked.is_some())
+                            .label("Mark stacked imbalances")
+                            .on_toggle(move |is_enabled| {
+                                on_change(FootprintStudy::Imbalance {
+                                    threshold,
+                                    color_scale,
+                                    ignore_zeros,
+                                    stacked: is_enabled.then_some(min_stack),
+                                })
+                            });
+
+                        if stacked.is_some() {
+                            let stack_slider = column![
+                                text(format!("Level line from {min_stack} stacked")),
+                                slider(2.0..=8.0, min_stack as f32, move |new_value| {
+                                    on_change(FootprintStudy::Imbalance {
+                                        threshold,
+                                        color_scale,
+                                        ignore_zeros,
+                                        stacked: Some(new_value as usize),
+                                    })
+                                })
+                                .step(1.0)
+                            ]
+                            .spacing(2);
+
+                            column![stacked_checkbox, stack_slider]
+                                .padding(8)
+                                .spacing(8)
+                        } else {
+                            column![stacked_checkbox].padding(8)
+                        }
+                    };
+
+                    split_column![
+                        qty_threshold,
+                        color_scaling,
+                        ignore_zeros_checkbox,
+                        stacked_levels
+                    ]
+                    .padding(4)
+                    .into()
+                }
+                FootprintStudy::BarStats { rows } => {
+                    let row_checkbox =
+                        |label: &'static str,
+                         shown: bool,
+                         toggled: fn(BarStatsRows, bool) -> BarStatsRows| {
+                            checkbox(shown).label(label).on_toggle(move |is_checked| {
+                                on_change(FootprintStudy::BarStats {
+                                    rows: toggled(rows, is_checked),
+                                })
+                            })
+                        };
+
+                    column![
+                        text("Rows under each bar"),
+                        row_checkbox("Delta", rows.delta, |rows, delta| BarStatsRows {
+                            delta,
+                            ..rows
+                        }),
+                        row_checkbox("Volume", rows.volume, |rows, volume| BarStatsRows {
+                            volume,
+                            ..rows
+                        }),
+                        row_checkbox("Delta %", rows.delta_pct, |rows, delta_pct| {
+                            BarStatsRows { delta_pct, ..rows }
+                        }),
+                        row_checkbox("Trades", rows.trades, |rows, trades| BarStatsRows {
+                            trades,
+                            ..rows
+                        }),
+                        row_checkbox("Max delta", rows.max_delta, |rows, max_delta| {
+                            BarStatsRows { max_delta, ..rows }
+                        }),
+                    ]
+                    .padding(8)
+                    .spacing(4)
+                    .into()
                 }
             }
         }
@@ -822,9 +2218,11 @@ pub mod study {
                         let switch_kind = button(text("Switch to visible range")).on_press(
                             on_change(HeatmapStudy::VolumeProfile(ProfileKind::VisibleRange)),
                         );
+                        let switch_session = button(text("Per session"))
+                            .on_press(on_change(HeatmapStudy::VolumeProfile(ProfileKind::Session)));
 
                         column![
-                            row![space::horizontal(), switch_kind,],
+                            row![space::horizontal(), switch_kind, switch_session].spacing(4),
                             text(format!(
                                 "Window: {} datapoints ({})",
                                 datapoint_count, duration_text
@@ -841,12 +2239,27 @@ pub mod study {
                                 CLEANUP_THRESHOLD / 5_usize,
                             ))),
                         );
+                        let switch_session = button(text("Per session"))
+                            .on_press(on_change(HeatmapStudy::VolumeProfile(ProfileKind::Session)));
 
-                        column![row![space::horizontal(), switch_kind,],]
+                        column![row![space::horizontal(), switch_kind, switch_session].spacing(4),]
                             .padding(8)
                             .spacing(4)
                             .into()
                     }
+                    ProfileKind::Session => {
+                        let switch_kind = button(text("Switch to visible range")).on_press(
+                            on_change(HeatmapStudy::VolumeProfile(ProfileKind::VisibleRange)),
+                        );
+
+                        column![
+                            row![space::horizontal(), switch_kind].spacing(4),
+                            text("Accumulates from the latest session open in view"),
+                        ]
+                        .padding(8)
+                        .spacing(4)
+                        .into()
+                    }
                 },
             }
         }
@@ -3,6 +3,7 @@ use crate::{
     widget::numeric_input_box,
 };
 
+use data::aggr::{range, volume};
 use data::chart::Basis;
 use exchange::{
     TickMultiplier, TickerInfo, Timeframe,
@@ -16,7 +17,7 @@ use iced::{
 };
 use serde::{Deserialize, Serialize};
 
-const NUMERIC_INPUT_BUF_SIZE: usize = 5; // Max 5 digits for u16 (65535)
+const NUMERIC_INPUT_BUF_SIZE: usize = 8; // Max 8 digits, enough for volume targets
 
 const TICK_COUNT_MIN: u16 = 4;
 const TICK_COUNT_MAX: u16 = 1000;
@@ -24,6 +25,28 @@ const TICK_COUNT_MAX: u16 = 1000;
 const TICK_MULTIPLIER_MIN: u16 = 1;
 const TICK_MULTIPLIER_MAX: u16 = 2000;
 
+const DEPTH_RATES: [DepthRate; 6] = [
+    DepthRate(None),
+    DepthRate(Some(1)),
+    DepthRate(Some(2)),
+    DepthRate(Some(5)),
+    DepthRate(Some(10)),
+    DepthRate(Some(20)),
+];
+
+/// Depth updates per second a pane's streams are conflated to, `None` takes every one
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct DepthRate(pub Option<u16>);
+
+impl std::fmt::Display for DepthRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(rate) => write!(f, "{rate}/s"),
+            None => write!(f, "All"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub enum ModifierKind {
     Candlestick(Basis),
@@ -66,6 +89,10 @@ impl NumericInput {
         Self::from_str(&tc.0.to_string())
     }
 
+    pub fn from_volume(volume: u32) -> Self {
+        Self::from_str(&volume.to_string())
+    }
+
     pub fn to_display_string(self) -> String {
         if self.len == 0 {
             return String::new();
@@ -96,6 +123,24 @@ impl NumericInput {
             .and_then(|s| s.parse::<u16>().ok())
             .map(data::aggr::TickCount)
     }
+
+    pub fn parse_range_ticks(self) -> Option<u16> {
+        if self.len == 0 {
+            return None;
+        }
+        std::str::from_utf8(&self.buffer[..self.len as usize])
+            .ok()
+            .and_then(|s| s.parse::<u16>().ok())
+    }
+
+    pub fn parse_volume(self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        std::str::from_utf8(&self.buffer[..self.len as usize])
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+    }
 }
 
 impl Default for NumericInput {
@@ -122,12 +167,24 @@ pub enum SelectedTab {
         parsed_input: Option<data::aggr::TickCount>,
         is_input_valid: bool,
     },
+    VolumeTarget {
+        raw_input_buf: NumericInput,
+        parsed_input: Option<u32>,
+        is_input_valid: bool,
+    },
+    /// Range size is entered in ticks, the basis carries it in price units
+    RangeTicks {
+        raw_input_buf: NumericInput,
+        parsed_input: Option<u16>,
+        is_input_valid: bool,
+    },
 }
 
 pub enum Action {
     BasisSelected(Basis),
     TicksizeSelected(TickMultiplier),
     TabSelected(SelectedTab),
+    DepthRateSelected(Option<u16>),
 }
 
 #[derive(Debug, Clone)]
@@ -137,6 +194,9 @@ pub enum Message {
     TicksizeInputChanged(String),
     TicksizeSelected(TickMultiplier),
     TickCountInputChanged(String),
+    VolumeInputChanged(String),
+    RangeTicksInputChanged(String),
+    DepthRateSelected(Option<u16>),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -146,6 +206,8 @@ pub struct Modifier {
     kind: ModifierKind,
     base_ticksize: Option<f32>,
     exchange: Option<Exchange>,
+    /// Offered for panes drawing depth
+    depth_rate: Option<DepthRate>,
 }
 
 impl Modifier {
@@ -158,9 +220,15 @@ impl Modifier {
             view_mode: ViewMode::BasisSelection,
             base_ticksize: None,
             exchange: None,
+            depth_rate: None,
         }
     }
 
+    pub fn with_depth_rate(mut self, rate: Option<u16>) -> Self {
+        self.depth_rate = Some(DepthRate(rate));
+        self
+    }
+
     pub fn with_view_mode(mut self, view_mode: ViewMode) -> Self {
         self.view_mode = view_mode;
         self
@@ -224,9 +292,44 @@ impl Modifier {
     pub fn update(&mut self, message: Message) -> Option<Action> {
         match message {
             Message::TabSelected(tab) => Some(Action::TabSelected(tab)),
+            Message::DepthRateSelected(rate) => Some(Action::DepthRateSelected(rate)),
             Message::BasisSelected(basis) => match basis {
-                Basis::Time(_) | Basis::Volume(_) | Basis::Range(_) => {
-                    Some(Action::BasisSelected(basis))
+                Basis::Time(_) => Some(Action::BasisSelected(basis)),
+                Basis::Range(_) => {
+                    if let SelectedTab::RangeTicks {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } = &mut self.tab
+                    {
+                        *raw_input_buf = NumericInput::default();
+                        *parsed_input = None;
+                        *is_input_valid = true;
+
+                        Some(Action::BasisSelected(basis))
+                    } else {
+                        None
+                    }
+                }
+                Basis::Volume(new_volume) => {
+                    if let SelectedTab::VolumeTarget {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } = &mut self.tab
+                    {
+                        if *parsed_input == Some(new_volume) {
+                            *is_input_valid = true;
+                        } else {
+                            *raw_input_buf = NumericInput::default();
+                            *parsed_input = None;
+                            *is_input_valid = true;
+                        };
+
+                        Some(Action::BasisSelected(basis))
+                    } else {
+                        None
+                    }
                 }
                 Basis::Tick(new_tc) => {
                     if let SelectedTab::TickCount {
@@ -323,6 +426,64 @@ impl Modifier {
                 }
                 None
             }
+            Message::VolumeInputChanged(value_str) => {
+                if let SelectedTab::VolumeTarget {
+                    ref mut raw_input_buf,
+                    ref mut parsed_input,
+                    ref mut is_input_valid,
+                } = self.tab
+                {
+                    let numeric_value_str: String =
+                        value_str.chars().filter(char::is_ascii_digit).collect();
+
+                    *raw_input_buf = NumericInput::from_str(&numeric_value_str);
+                    *parsed_input = raw_input_buf.parse_volume();
+
+                    if raw_input_buf.is_empty() {
+                        *is_input_valid = true;
+                    } else {
+                        match parsed_input {
+                            Some(v) => {
+                                *is_input_valid =
+                                    *v >= volume::MIN_VOLUME && *v <= volume::MAX_VOLUME;
+                            }
+                            None => {
+                                *is_input_valid = false;
+                            }
+                        }
+                    }
+                }
+                None
+            }
+            Message::RangeTicksInputChanged(value_str) => {
+                if let SelectedTab::RangeTicks {
+                    ref mut raw_input_buf,
+                    ref mut parsed_input,
+                    ref mut is_input_valid,
+                } = self.tab
+                {
+                    let numeric_value_str: String =
+                        value_str.chars().filter(char::is_ascii_digit).collect();
+
+                    *raw_input_buf = NumericInput::from_str(&numeric_value_str);
+                    *parsed_input = raw_input_buf.parse_range_ticks();
+
+                    if raw_input_buf.is_empty() {
+                        *is_input_valid = true;
+                    } else {
+                        match parsed_input {
+                            Some(ticks) => {
+                                *is_input_valid = *ticks >= range::MIN_RANGE_TICKS
+                                    && *ticks <= range::MAX_RANGE_TICKS;
+                            }
+                            None => {
+                                *is_input_valid = false;
+                            }
+                        }
+                    }
+                }
+                None
+            }
         }
     }
 
@@ -365,17 +526,21 @@ impl Modifier {
                 };
 
                 if selected_basis.is_some() {
-                    let (timeframe_tab_is_selected, tick_count_tab_is_selected) = match self.tab {
-                        SelectedTab::Timeframe => (true, false),
-                        SelectedTab::TickCount { .. } => (false, true),
-                    };
+                    let timeframe_tab_is_selected = matches!(self.tab, SelectedTab::Timeframe);
+                    let tick_count_tab_is_selected =
+                        matches!(self.tab, SelectedTab::TickCount { .. });
+                    let volume_tab_is_selected =
+                        matches!(self.tab, SelectedTab::VolumeTarget { .. });
+                    let range_tab_is_selected = matches!(self.tab, SelectedTab::RangeTicks { .. });
 
                     let tabs_row = {
                         if allows_tick_basis {
-                            let is_timeframe_selected = matches!(
-                                selected_basis,
-                                Some(Basis::Time(_) | Basis::Volume(_) | Basis::Range(_))
-                            );
+                            let is_timeframe_selected =
+                                matches!(selected_basis, Some(Basis::Time(_)));
+                            let is_tick_selected = matches!(selected_basis, Some(Basis::Tick(_)));
+                            let is_volume_selected =
+                                matches!(selected_basis, Some(Basis::Volume(_)));
+                            let is_range_selected = matches!(selected_basis, Some(Basis::Range(_)));
 
                             let tab_button =
                                 |content: iced::widget::text::Text<'a>,
@@ -439,7 +604,59 @@ impl Modifier {
                                         Some(Message::TabSelected(tick_count_tab))
                                     },
                                     !tick_count_tab_is_selected,
-                                    !is_timeframe_selected,
+                                    is_tick_selected,
+                                ),
+                                tab_button(
+                                    text("Volume"),
+                                    if volume_tab_is_selected {
+                                        None
+                                    } else {
+                                        let volume_tab = match self.tab {
+                                            SelectedTab::VolumeTarget {
+                                                raw_input_buf,
+                                                parsed_input,
+                                                is_input_valid,
+                                            } => SelectedTab::VolumeTarget {
+                                                raw_input_buf,
+                                                parsed_input,
+                                                is_input_valid,
+                                            },
+                                            _ => SelectedTab::VolumeTarget {
+                                                raw_input_buf: NumericInput::default(),
+                                                parsed_input: None,
+                                                is_input_valid: true,
+                                            },
+                                        };
+                                        Some(Message::TabSelected(volume_tab))
+                                    },
+                                    !volume_tab_is_selected,
+                                    is_volume_selected,
+                                ),
+                                tab_button(
+                                    text("Range"),
+                                    if range_tab_is_selected {
+                                        None
+                                    } else {
+                                        let range_tab = match self.tab {
+                                            SelectedTab::RangeTicks {
+                                                raw_input_buf,
+                                                parsed_input,
+                                                is_input_valid,
+                                            } => SelectedTab::RangeTicks {
+                                                raw_input_buf,
+                                                parsed_input,
+                                                is_input_valid,
+                                            },
+                                            _ => SelectedTab::RangeTicks {
+                                                raw_input_buf: NumericInput::default(),
+                                                parsed_input: None,
+                                                is_input_valid: true,
+                                            },
+                                        };
+                                        Some(Message::TabSelected(range_tab))
+                                    },
+                                    !range_tab_is_selected,
+                                    is_range_selected,
                                 ),
                             ]
                             .spacing(4)
@@ -544,6 +761,100 @@ impl Modifier {
                         basis_selection_column = basis_selection_column.push(custom_input);
                         basis_selection_column = basis_selection_column.push(tick_count_grid);
                     }
+                    SelectedTab::VolumeTarget {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } => {
+                        let selected_volume = match selected_basis {
+                            Some(Basis::Volume(v)) => Some(Basis::Volume(v)),
+                            _ => None,
+                        };
+
+                        let volume_presets = volume::PRESETS.map(Basis::Volume);
+                        let volume_grid = modifiers_grid(
+                            &volume_presets,
+                            selected_volume,
+                            Message::BasisSelected,
+                            &create_button,
+                            3,
+                        );
+
+                        let custom_input = {
+                            let volume_to_submit = parsed_input
+                                .filter(|v| *v >= volume::MIN_VOLUME && *v <= volume::MAX_VOLUME);
+
+                            numeric_input_box::<_, Message>(
+                                "Custom: ",
+                                &format!("{}-{}", volume::MIN_VOLUME, volume::MAX_VOLUME),
+                                &raw_input_buf.to_display_string(),
+                                is_input_valid,
+                                Message::VolumeInputChanged,
+                                volume_to_submit.map(|v| Message::BasisSelected(Basis::Volume(v))),
+                            )
+                        };
+                        basis_selection_column = basis_selection_column.push(custom_input);
+                        basis_selection_column = basis_selection_column.push(volume_grid);
+                    }
+                    SelectedTab::RangeTicks {
+                        raw_input_buf,
+                        parsed_input,
+                        is_input_valid,
+                    } => {
+                        if let Some(info) = ticker_info {
+                            let min_tick = info.min_ticksize;
+
+                            let selected_range = match selected_basis {
+                                Some(Basis::Range(r)) => Some(Basis::Range(r)),
+                                _ => None,
+                            };
+
+                            let range_presets = range::TICK_PRESETS.map(|ticks| {
+                                Basis::Range(range::range_from_ticks(ticks, min_tick))
+                            });
+                            let range_grid = modifiers_grid(
+                                &range_presets,
+                                selected_range,
+                                Message::BasisSelected,
+                                &create_button,
+                                3,
+                            );
+
+                            let custom_input = {
+                                let range_to_submit = parsed_input.filter(|ticks| {
+                                    *ticks >= range::MIN_RANGE_TICKS
+                                        && *ticks <= range::MAX_RANGE_TICKS
+                                });
+
+                                numeric_input_box::<_, Message>(
+                                    "Ticks: ",
+                                    &format!(
+                                        "{}-{}",
+                                        range::MIN_RANGE_TICKS,
+                                        range::MAX_RANGE_TICKS
+                                    ),
+                                    &raw_input_buf.to_display_string(),
+                                    is_input_valid,
+                                    Message::RangeTicksInputChanged,
+                                    range_to_submit.map(|ticks| {
+                                        Message::BasisSelected(Basis::Range(
+                                            range::range_from_ticks(ticks, min_tick),
+                                        ))
+                                    }),
+                                )
+                            };
+                            basis_selection_column = basis_selection_column.push(custom_input);
+                            basis_selection_column = basis_selection_column.push(range_grid);
+                        } else {
+                            basis_selection_column = basis_selection_column
+                                .push(text("Ticker info is not available").size(13));
+                        }
+                    }
+                }
+
+                if let Some(rate) = self.depth_rate {
+                    basis_selection_column =
+                        basis_selection_column.push(depth_rate_section(rate, &create_button));
                 }
 
                 container(scrollable::Scrollable::with_direction(
@@ -620,6 +931,11 @@ impl Modifier {
                     }
                     ticksizes_column = ticksizes_column.push(tick_multiplier_grid);
 
+                    if let Some(rate) = self.depth_rate {
+                        ticksizes_column =
+                            ticksizes_column.push(depth_rate_section(rate, &create_button));
+                    }
+
                     if let Some(base_ticksize) = self.base_ticksize {
                         ticksizes_column = ticksizes_column.push(
                             row![
@@ -659,6 +975,29 @@ impl Modifier {
     }
 }
 
+fn depth_rate_section<'a>(
+    selected: DepthRate,
+    create_button_fn: &impl Fn(
+        iced::widget::text::Text<'a>,
+        Option<Message>,
+        bool,
+    ) -> iced::widget::Button<'a, Message>,
+) -> iced::widget::Column<'a, Message> {
+    column![
+        rule::horizontal(1).style(style::split_ruler),
+        text("Depth updates").size(13),
+        modifiers_grid(
+            &DEPTH_RATES,
+            Some(selected),
+            |rate| Message::DepthRateSelected(rate.0),
+            create_button_fn,
+            3,
+        ),
+    ]
+    .spacing(8)
+    .align_x(Horizontal::Center)
+}
+
 /// A `Column` grid of buttons from `items_source`.
 ///
 /// Buttons are arranged in rows of up to `items_per_row`.
@@ -728,7 +1067,24 @@ impl From<&ModifierKind> for SelectedTab {
             | ModifierKind::Heatmap(basis, _)
             | ModifierKind::Orderbook(basis, _)
             | ModifierKind::Comparison(basis) => match basis {
-                Basis::Time(_) | Basis::Volume(_) | Basis::Range(_) => SelectedTab::Timeframe,
+                Basis::Time(_) => SelectedTab::Timeframe,
+                Basis::Range(_) => SelectedTab::RangeTicks {
+                    raw_input_buf: NumericInput::default(),
+                    parsed_input: None,
+                    is_input_valid: true,
+                },
+                Basis::Volume(v) => {
+                    let is_custom = !volume::PRESETS.contains(v);
+                    SelectedTab::VolumeTarget {
+                        raw_input_buf: if is_custom {
+                            NumericInput::from_volume(*v)
+                        } else {
+                            NumericInput::default()
+                        },
+                        parsed_input: if is_custom { Some(*v) } else { None },
+                        is_input_valid: true,
+                    }
+                }
                 Basis::Tick(tc) => SelectedTab::TickCount {
                     raw_input_buf: if tc.is_custom() {
                         NumericInput::from_tick_count(*tc)
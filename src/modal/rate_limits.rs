@@ -0,0 +1,156 @@
+use crate::style;
+
+use exchange::adapter::ExchangeInclusive;
+use exchange::limiter::{LimiterStatus, REJECTION_WINDOW};
+use exchange::scheduler::QueueDepth;
+use iced::widget::{column, container, row, space, text};
+use iced::{Color, Element, Length, Theme};
+use std::time::Instant;
+
+/// Weight budget of each venue's REST limiter, so throttled fetches can be told apart
+/// from slow ones, and the historical fetches queued behind it
+pub fn view<'a, Message: 'a>(
+    statuses: Vec<LimiterStatus>,
+    queues: Vec<(ExchangeInclusive, QueueDepth)>,
+) -> Element<'a, Message> {
+    let now = Instant::now();
+
+    let venues = if statuses.is_empty() {
+        column![text("No requests made yet").size(12)]
+    } else {
+        column(statuses.into_iter().map(|status| venue_row(status, now))).spacing(8)
+    };
+
+    container(
+        column![
+            text("Rate limits").size(14),
+            text(format!(
+                "Rejections count the last {} minutes",
+                REJECTION_WINDOW.as_secs() / 60
+            ))
+            .size(11)
+            .style(style::secondary_text),
+            venues,
+            queued(queues),
+        ]
+        .spacing(8),
+    )
+    .max_width(320)
+    .padding(24)
+    .style(style::dashboard_modal)
+    .into()
+}
+
+fn segment<'a, Message: 'a>(
+    portion: u16,
+    color: impl Fn(&Theme) -> Color + 'a,
+) -> Element<'a, Message> {
+    container(space::horizontal())
+        .width(Length::FillPortion(portion))
+        .height(6)
+        .style(move |theme: &Theme| container::Style {
+            background: Some(color(theme).into()),
+            ..Default::default()
+        })
+        .into()
+}
+
+fn queued<'a, Message: 'a>(queues: Vec<(ExchangeInclusive, QueueDepth)>) -> Element<'a, Message> {
+    if queues.is_empty() {
+        return space::vertical().height(0).into();
+    }
+
+    let rows = queues.into_iter().map(|(venue, depth)| {
+        row![
+            text(venue.to_string()).size(12),
+            space::horizontal(),
+            text(format!(
+                "{} running · {} waiting ({} visible, {} background, {} prefetch)",
+                depth.running,
+                depth.queued(),
+                depth.visible,
+                depth.background,
+                depth.prefetch,
+            ))
+            .size(11)
+            .style(style::secondary_text),
+        ]
+        .spacing(8)
+        .into()
+    });
+
+    column![text("Fetch queue").size(13), column(rows).spacing(4)]
+        .spacing(6)
+        .into()
+}
+
+fn venue_row<'a, Message: 'a>(status: LimiterStatus, now: Instant) -> Element<'a, Message> {
+    let (gauge, budget) = match status.usage {
+        Some(usage) => {
+            let share = (usage.used as f32 / usage.limit.max(1) as f32).clamp(0.0, 1.0);
+            let filled = (share * 1000.0).round() as u16;
+
+            let bar = row![
+                segment(filled, move |theme| {
+                    let palette = theme.extended_palette();
+                    match share {
+                        s if s >= 0.9 => palette.danger.base.color,
+                        s if s >= 0.6 => palette.warning.base.color,
+                        _ => palette.success.base.color,
+                    }
+                }),
+                segment(1000 - filled, |theme| {
+                    theme.extended_palette().background.weak.color
+                }),
+            ];
+
+            (
+                Element::from(bar),
+                format!(
+                    "{} / {} · refills in {:.1}s",
+                    usage.used,
+                    usage.limit,
+                    usage.refill_in.as_secs_f32()
+                ),
+            )
+        }
+        None => (space::horizontal().into(), "No usage reported".to_string()),
+    };
+
+    let mut details = vec![format!("{} rejected", status.rejections)];
+    if let Some((wait, at)) = status.last_wait {
+        details.push(format!(
+            "waited {:.1}s, {}s ago",
+            wait.as_secs_f32(),
+            now.duration_since(at).as_secs()
+        ));
+    }
+
+    let rejected = status.rejections > 0;
+
+    container(
+        column![
+            row![
+                text(status.venue.to_string()).size(13),
+                space::horizontal(),
+                text(budget).size(11),
+            ],
+            gauge,
+            text(details.join(" · "))
+                .size(11)
+                .style(move |theme: &Theme| {
+                    if rejected {
+                        text::Style {
+                            color: Some(theme.extended_palette().danger.base.color),
+                        }
+                    } else {
+                        style::secondary_text(theme)
+                    }
+                }),
+        ]
+        .spacing(4),
+    )
+    .padding(8)
+    .style(style::modal_container)
+    .into()
+}
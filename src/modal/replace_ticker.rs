@@ -0,0 +1,153 @@
+use exchange::{Ticker, TickerInfo, adapter::MarketKind};
+use iced::{
+    Element, Length,
+    widget::{button, checkbox, column, combo_box, text},
+};
+
+/// Ticker picked in one of the combo boxes, shown with its venue since symbols repeat across them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Listing(pub TickerInfo);
+
+impl std::fmt::Display for Listing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (symbol, market) = self.0.ticker.display_symbol_and_type();
+        let suffix = match market {
+            MarketKind::Spot => "",
+            MarketKind::LinearPerps | MarketKind::InversePerps => " PERP",
+        };
+
+        write!(f, "{symbol}{suffix} · {}", self.0.ticker.exchange)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    FromSelected(Listing),
+    ToSelected(Listing),
+    ToggleAllLayouts(bool),
+    Replace,
+}
+
+pub enum Action {
+    Replace {
+        from: Ticker,
+        to: TickerInfo,
+        all_layouts: bool,
+    },
+}
+
+/// Swaps one ticker for another on every pane, watchlist and alert referencing it,
+/// e.g. when rolling over an expiring contract
+pub struct ReplaceTicker {
+    referenced: combo_box::State<Listing>,
+    listings: combo_box::State<Listing>,
+    from: Option<Listing>,
+    to: Option<Listing>,
+    all_layouts: bool,
+}
+
+impl ReplaceTicker {
+    pub fn new() -> Self {
+        Self {
+            referenced: combo_box::State::new(vec![]),
+            listings: combo_box::State::new(vec![]),
+            from: None,
+            to: None,
+            all_layouts: false,
+        }
+    }
+
+    /// Tickers the layouts use to pick from, and every listing to replace them with
+    pub fn set_options(&mut self, referenced: Vec<TickerInfo>, listings: Vec<TickerInfo>) {
+        let sorted = |tickers: Vec<TickerInfo>| {
+            let mut options: Vec<Listing> = tickers.into_iter().map(Listing).collect();
+            options.sort_by_cached_key(ToString::to_string);
+            options
+        };
+
+        self.from = self
+            .from
+            .filter(|from| referenced.iter().any(|ti| ti.ticker == from.0.ticker));
+        self.referenced = combo_box::State::new(sorted(referenced));
+        self.listings = combo_box::State::new(sorted(listings));
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::FromSelected(listing) => self.from = Some(listing),
+            Message::ToSelected(listing) => self.to = Some(listing),
+            Message::ToggleAllLayouts(all_layouts) => self.all_layouts = all_layouts,
+            Message::Replace => {
+                let (from, to) = self.from.zip(self.to)?;
+                self.from = None;
+                self.to = None;
+
+                return Some(Action::Replace {
+                    from: from.0.ticker,
+                    to: to.0,
+                    all_layouts: self.all_layouts,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let from = combo_box(
+            &self.referenced,
+            "Ticker in use",
+            self.from.as_ref(),
+            Message::FromSelected,
+        )
+        .width(Length::Fill);
+
+        let to = combo_box(
+            &self.listings,
+            "Replace with",
+            self.to.as_ref(),
+            Message::ToSelected,
+        )
+        .width(Length::Fill);
+
+        let can_replace = self
+            .from
+            .zip(self.to)
+            .is_some_and(|(from, to)| from.0.ticker != to.0.ticker);
+
+        let replace_btn = button(text("Replace everywhere").align_x(iced::Alignment::Center))
+            .width(Length::Fill)
+            .on_press_maybe(can_replace.then_some(Message::Replace));
+
+        column![
+            text("Replace ticker").size(14),
+            from,
+            to,
+            checkbox(self.all_layouts)
+                .label("In all layouts")
+                .on_toggle(Message::ToggleAllLayouts),
+            replace_btn,
+        ]
+        .spacing(8)
+        .into()
+    }
+}
+
+impl Default for ReplaceTicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Short summary for the toast once a replace went through
+pub fn summary(from: Ticker, to: TickerInfo, panes: usize, favorite: bool) -> String {
+    let mut summary = format!(
+        "Replaced {from} with {} in {panes} pane{}",
+        to.ticker,
+        if panes == 1 { "" } else { "s" }
+    );
+    if favorite {
+        summary.push_str(" and favorites");
+    }
+    summary
+}
@@ -1,6 +1,6 @@
 use iced::{
-    Alignment, Element,
-    widget::{button, column, container, pick_list, row, space, text_input::default},
+    Alignment, Element, Length,
+    widget::{button, column, container, pick_list, row, space, text, text_input::default},
 };
 
 use crate::{
@@ -42,6 +42,11 @@ pub enum Message {
     CloseRequested,
     Color(Hsva),
     HexInput(String),
+    NameInput(String),
+    /// Keeps the current palette under the typed name, replacing a saved theme of that name
+    SaveTheme,
+    ApplySaved(usize),
+    DeleteSaved(usize),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,18 +57,49 @@ pub enum Action {
 
 pub struct ThemeEditor {
     pub custom_theme: Option<iced_core::Theme>,
+    /// Named custom themes, offered next to the built-in ones
+    pub saved_themes: Vec<iced_core::Theme>,
     component: Component,
     hex_input: Option<String>,
     editing: Option<Hsva>,
+    name_input: String,
 }
 
 impl ThemeEditor {
-    pub fn new(custom_theme: Option<data::Theme>) -> Self {
+    pub fn new(custom_theme: Option<data::Theme>, saved_themes: Vec<data::Theme>) -> Self {
         Self {
             custom_theme: custom_theme.map(|theme| theme.0),
+            saved_themes: saved_themes.into_iter().map(|theme| theme.0).collect(),
             component: Component::Background,
             hex_input: None,
             editing: None,
+            name_input: String::new(),
+        }
+    }
+
+    /// Custom themes to pick from, the unsaved one first unless it was saved under its name
+    pub fn custom_themes(&self) -> impl Iterator<Item = &iced_core::Theme> {
+        let unsaved = self.custom_theme.as_ref().filter(|custom| {
+            !self
+                .saved_themes
+                .iter()
+                .any(|saved| saved.to_string() == custom.to_string())
+        });
+
+        unsaved.into_iter().chain(&self.saved_themes)
+    }
+
+    /// Saves a named theme, replacing the one saved under the same name
+    pub fn keep_saved(&mut self, named: iced_core::Theme) {
+        let name = named.to_string();
+
+        match self
+            .saved_themes
+            .iter_mut()
+            .find(|saved| saved.to_string() == name)
+        {
+            Some(saved) => *saved = named,
+            None => self.saved_themes.push(named),
         }
     }
 
@@ -134,6 +170,35 @@ impl ThemeEditor {
                 self.hex_input = Some(input);
                 action
             }
+            Message::NameInput(name) => {
+                self.name_input = name.chars().take(24).collect();
+                None
+            }
+            Message::SaveTheme => {
+                let name = self.name_input.trim().to_string();
+                if name.is_empty() {
+                    return None;
+                }
+                self.name_input.clear();
+
+                let named = iced_core::Theme::custom(name, theme.palette());
+                self.keep_saved(named.clone());
+
+                Some(Action::UpdateTheme(named))
+            }
+            Message::ApplySaved(index) => {
+                let saved = self.saved_themes.get(index)?.clone();
+                self.hex_input = None;
+                self.editing = None;
+
+                Some(Action::UpdateTheme(saved))
+            }
+            Message::DeleteSaved(index) => {
+                if index < self.saved_themes.len() {
+                    self.saved_themes.remove(index);
+                }
+                None
+            }
             Message::CloseRequested => Some(Action::Exit),
         }
     }
@@ -193,6 +258,8 @@ impl ThemeEditor {
             .spacing(8)
             .align_y(Alignment::Center),
             color_picker(hsva_in, Message::Color),
+            contrast_warnings(theme),
+            self.saved_view(theme),
         ]
         .spacing(10);
 
@@ -203,3 +270,61 @@ impl ThemeEditor {
             .into()
     }
 }
+
+impl ThemeEditor {
+    fn saved_view(&self, theme: &iced_core::Theme) -> Element<'_, Message> {
+        let name_input = iced::widget::text_input("Theme name", &self.name_input)
+            .on_input(Message::NameInput)
+            .on_submit(Message::SaveTheme)
+            .width(Length::Fill);
+
+        let save_btn = button(text("Save"))
+            .on_press_maybe((!self.name_input.trim().is_empty()).then_some(Message::SaveTheme));
+
+        let mut content = column![row![name_input, save_btn].spacing(4)].spacing(4);
+
+        let active = theme.to_string();
+        for (index, saved) in self.saved_themes.iter().enumerate() {
+            let name = saved.to_string();
+            let is_active = name == active;
+
+            content = content.push(
+                row![
+                    button(text(name).size(12))
+                        .width(Length::Fill)
+                        .style(move |theme, status| {
+                            style::button::transparent(theme, status, is_active)
+                        })
+                        .on_press(Message::ApplySaved(index)),
+                    button(icon_text(Icon::TrashBin, 12))
+                        .style(move |theme, status| {
+                            style::button::transparent(theme, status, false)
+                        })
+                        .on_press(Message::DeleteSaved(index)),
+                ]
+                .spacing(4)
+                .align_y(Alignment::Center),
+            );
+        }
+
+        content.into()
+    }
+}
+
+/// Roles of the palette hard to read on its background
+fn contrast_warnings<'a>(theme: &iced_core::Theme) -> Element<'a, Message> {
+    let low = data::config::theme::low_contrast_roles(&theme.palette());
+
+    column(low.into_iter().map(|(role, ratio)| {
+        text(format!(
+            "{role} has low contrast on the background ({ratio:.1}:1)"
+        ))
+        .size(11)
+        .style(|theme: &iced::Theme| text::Style {
+            color: Some(theme.extended_palette().danger.base.color),
+        })
+        .into()
+    }))
+    .spacing(2)
+    .into()
+}
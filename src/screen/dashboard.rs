@@ -1,5 +1,6 @@
 pub mod pane;
 pub mod panel;
+pub mod scanner;
 pub mod sidebar;
 pub mod tickers_table;
 
@@ -15,16 +16,22 @@ use crate::{
 };
 use data::{
     UserTimezone,
-    layout::{WindowSpec, pane::ContentKind},
+    layout::{
+        WindowSpec,
+        pane::{ContentKind, LinkGroup, SourceSwitch},
+    },
 };
 use exchange::{
-    Kline, PushFrequency, StreamPairKind, TickMultiplier, TickerInfo, Timeframe, Trade,
+    FundingInfo, Kline, PushFrequency, StreamPairKind, TickMultiplier, Ticker, TickerInfo,
+    TickerStats, Timeframe, Trade,
     adapter::{
-        self, AdapterError, Exchange, PersistStreamKind, ResolvedStream, StreamConfig, StreamKind,
-        StreamTicksize, UniqueStreams, binance, bybit, hyperliquid, okex,
+        self, AdapterError, Exchange, ExchangeInclusive, PersistStreamKind, ResolvedStream,
+        StreamConfig, StreamKind, StreamTicksize, UniqueStreams, binance, bybit, deribit,
+        hyperliquid, okex, simulated,
     },
     depth::Depth,
     fetcher::{FetchRange, FetchedData},
+    scheduler::{self, Priority},
 };
 
 use iced::{
@@ -36,7 +43,15 @@ use iced::{
     },
 };
 use iced_futures::futures::TryFutureExt;
-use std::{collections::HashMap, path::PathBuf, time::Instant, vec};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Instant,
+    vec,
+};
+
+/// Under the data directory, fetched klines persist here between sessions
+const KLINE_CACHE_DIR: &str = "market_data/klines/";
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -52,6 +67,67 @@ pub enum Message {
         data: FetchedData,
     },
     ResolveStreams(uuid::Uuid, Vec<PersistStreamKind>),
+    ResolveMergedFeed(uuid::Uuid, Ticker, Vec<Exchange>),
+    FundingUpdated(uuid::Uuid, Ticker, Result<FundingInfo, String>),
+    WatchlistStatsUpdated(
+        uuid::Uuid,
+        Exchange,
+        Result<HashMap<Ticker, TickerStats>, String>,
+    ),
+    HeatGridInfoUpdated(
+        uuid::Uuid,
+        Exchange,
+        Result<HashMap<Ticker, Option<TickerInfo>>, String>,
+    ),
+    HeatGridStatsUpdated(
+        uuid::Uuid,
+        Exchange,
+        Result<HashMap<Ticker, TickerStats>, String>,
+    ),
+    /// Pane body cropped out of a window screenshot, with the pane title
+    ImageCaptured(String, Result<iced::window::Screenshot, String>),
+}
+
+impl Message {
+    /// Panes added, removed or moved, or a pane's content swapped, worth an autosave
+    pub fn is_structural(&self) -> bool {
+        matches!(
+            self,
+            Message::Pane(
+                _,
+                pane::Message::ClosePane(_)
+                    | pane::Message::SplitPane(..)
+                    | pane::Message::ReplacePane(_)
+                    | pane::Message::Popout
+                    | pane::Message::Merge
+                    | pane::Message::PaneDragged(pane_grid::DragEvent::Dropped { .. })
+                    | pane::Message::PaneEvent(_, pane::Event::ContentSelected(_))
+            )
+        )
+    }
+
+    /// Closes a pane, swaps its content or stream, or deletes drawings, taken onto the undo stack
+    pub fn is_undoable(&self) -> bool {
+        use crate::modal::stream::Message as Modifier;
+
+        matches!(
+            self,
+            Message::Pane(
+                _,
+                pane::Message::ClosePane(_)
+                    | pane::Message::ReplacePane(_)
+                    | pane::Message::PaneEvent(
+                        _,
+                        pane::Event::ContentSelected(_)
+                            | pane::Event::ClearDrawings
+                            | pane::Event::ChartInteraction(chart::Message::DrawingRemoved(_))
+                            | pane::Event::StreamModifierChanged(
+                                Modifier::BasisSelected(_) | Modifier::TicksizeSelected(_)
+                            )
+                    )
+            )
+        )
+    }
 }
 
 pub struct Dashboard {
@@ -60,6 +136,14 @@ pub struct Dashboard {
     pub popout: HashMap<window::Id, (pane_grid::State<pane::State>, WindowSpec)>,
     pub streams: UniqueStreams,
     layout_id: uuid::Uuid,
+    /// Link groups whose time based charts follow each other's pan and zoom
+    pub synced_view_groups: Vec<LinkGroup>,
+    /// Pane whose hovered bar is mirrored on its link group
+    hovered_bar_source: Option<uuid::Uuid>,
+    /// When each venue's streams first dropped, cleared once they reconnect
+    disconnected_at: HashMap<Exchange, u64>,
+    /// Panes already handed to failover during the current outage of their venue
+    failover_attempted: HashSet<(Exchange, uuid::Uuid)>,
 }
 
 impl Default for Dashboard {
@@ -70,6 +154,10 @@ impl Default for Dashboard {
             streams: UniqueStreams::default(),
             popout: HashMap::new(),
             layout_id: uuid::Uuid::new_v4(),
+            synced_view_groups: vec![],
+            hovered_bar_source: None,
+            disconnected_at: HashMap::new(),
+            failover_attempted: HashSet::new(),
         }
     }
 }
@@ -87,6 +175,15 @@ pub enum Event {
         pane_id: uuid::Uuid,
         streams: Vec<PersistStreamKind>,
     },
+    ResolveMergedFeed {
+        pane_id: uuid::Uuid,
+        ticker: Ticker,
+        venues: Vec<Exchange>,
+    },
+    ImageCaptured(String, Result<iced::window::Screenshot, String>),
+    /// Order entry on a pane, for the simulated account
+    PaperTrade(TickerInfo, data::paper::Command),
+    ExportJournal,
 }
 
 impl Dashboard {
@@ -118,6 +215,7 @@ impl Dashboard {
         panes: Configuration<pane::State>,
         popout_windows: Vec<(Configuration<pane::State>, WindowSpec)>,
         layout_id: uuid::Uuid,
+        synced_view_groups: Vec<LinkGroup>,
     ) -> Self {
         let panes = pane_grid::State::with_configuration(panes);
 
@@ -136,6 +234,10 @@ impl Dashboard {
             streams: UniqueStreams::default(),
             popout,
             layout_id,
+            synced_view_groups,
+            hovered_bar_source: None,
+            disconnected_at: HashMap::new(),
+            failover_attempted: HashSet::new(),
         }
     }
 
@@ -172,6 +274,37 @@ impl Dashboard {
         Task::batch(open_popouts_tasks).chain(self.refresh_streams(main_window))
     }
 
+    /// Pane trees of the main window and every popout, to put back with `restore_grids`
+    pub fn grids(&self, main_window: window::Id) -> Vec<(window::Id, data::Pane)> {
+        std::iter::once((main_window, crate::layout::pane_tree(&self.panes)))
+            .chain(
+                self.popout
+                    .iter()
+                    .map(|(window, (panes, _))| (*window, crate::layout::pane_tree(panes))),
+            )
+            .collect()
+    }
+
+    /// Rebuilds the panes of each window still open, streams resolve again on the next ticks
+    pub fn restore_grids(
+        &mut self,
+        main_window: window::Id,
+        grids: Vec<(window::Id, data::Pane)>,
+    ) -> Task<Message> {
+        for (window, tree) in grids {
+            let restored = pane_grid::State::with_configuration(crate::layout::configuration(tree));
+
+            if window == main_window {
+                self.panes = restored;
+            } else if let Some((panes, _)) = self.popout.get_mut(&window) {
+                *panes = restored;
+            }
+        }
+        self.focus = None;
+
+        self.refresh_streams(main_window)
+    }
+
     pub fn update(
         &mut self,
         message: Message,
@@ -303,6 +436,13 @@ impl Dashboard {
                         state.content.change_visual_config(cfg);
                     }
                 }
+                pane::Message::ToggleViewSync(group) => {
+                    if let Some(idx) = self.synced_view_groups.iter().position(|g| *g == group) {
+                        self.synced_view_groups.remove(idx);
+                    } else {
+                        self.synced_view_groups.push(group);
+                    }
+                }
                 pane::Message::SwitchLinkGroup(pane, group) => {
                     if group.is_none() {
                         if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
@@ -339,7 +479,14 @@ impl Dashboard {
                             for stream in &streams {
                                 if let StreamKind::Kline { .. } = stream {
                                     return (
-                                        kline_fetch_task(*layout_id, pane_id, *stream, None, None),
+                                        kline_fetch_task(
+                                            *layout_id,
+                                            pane_id,
+                                            *stream,
+                                            None,
+                                            None,
+                                            Priority::Visible,
+                                        ),
                                         None,
                                     );
                                 }
@@ -358,6 +505,7 @@ impl Dashboard {
                         let Some(effect) = state.update(local) else {
                             return (Task::none(), None);
                         };
+                        let source = state.unique_id();
 
                         let task = match effect {
                             pane::Effect::RefreshStreams => self.refresh_streams(main_window.id),
@@ -365,6 +513,7 @@ impl Dashboard {
                                 state,
                                 *layout_id,
                                 reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                                Priority::Visible,
                             )
                             .chain(self.refresh_streams(main_window.id)),
                             pane::Effect::SwitchTickersInGroup(ticker_info) => {
@@ -373,6 +522,37 @@ impl Dashboard {
                             pane::Effect::FocusWidget(id) => {
                                 return (iced::widget::operation::focus(id), None);
                             }
+                            pane::Effect::CenterOnTime(group, time) => {
+                                self.center_group_on_time(main_window.id, group, time);
+                                Task::none()
+                            }
+                            pane::Effect::SyncHoveredBar(group, span) => {
+                                self.sync_hovered_bar(main_window.id, group, source, span);
+                                Task::none()
+                            }
+                            pane::Effect::SyncTimeWindow(group, window) => {
+                                self.sync_time_window(main_window.id, group, source, window);
+                                Task::none()
+                            }
+                            pane::Effect::OpenTicker(ticker_info) => {
+                                self.open_from_ticker_list(main_window.id, source, ticker_info)
+                            }
+                            pane::Effect::FilterTape(ticker_info, range) => {
+                                self.filter_tape_by_size(main_window.id, ticker_info, range);
+                                Task::none()
+                            }
+                            pane::Effect::CaptureImage(id, title) => {
+                                capture_pane_image(window, id, title)
+                            }
+                            pane::Effect::PaperTrade(ticker_info, command) => {
+                                return (
+                                    Task::none(),
+                                    Some(Event::PaperTrade(ticker_info, command)),
+                                );
+                            }
+                            pane::Effect::ExportJournal => {
+                                return (Task::none(), Some(Event::ExportJournal));
+                            }
                         };
                         return (task, None);
                     }
@@ -405,9 +585,60 @@ impl Dashboard {
                     Some(Event::ResolveStreams { pane_id, streams }),
                 );
             }
+            Message::ResolveMergedFeed(pane_id, ticker, venues) => {
+                return (
+                    Task::none(),
+                    Some(Event::ResolveMergedFeed {
+                        pane_id,
+                        ticker,
+                        venues,
+                    }),
+                );
+            }
             Message::Notification(toast) => {
                 return (Task::none(), Some(Event::Notification(toast)));
             }
+            Message::ImageCaptured(title, screenshot) => {
+                return (Task::none(), Some(Event::ImageCaptured(title, screenshot)));
+            }
+            Message::FundingUpdated(pane_id, ticker, result) => match result {
+                Ok(info) => {
+                    if let Some(state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id) {
+                        state.funding = Some((ticker, info));
+                    }
+                }
+                Err(err) => log::warn!("Failed to fetch funding for {ticker}: {err}"),
+            },
+            Message::WatchlistStatsUpdated(pane_id, exchange, result) => match result {
+                Ok(stats) => {
+                    if let Some(state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id)
+                        && let pane::Content::Watchlist(watchlist) = &mut state.content
+                    {
+                        watchlist.insert_stats(&stats);
+                    }
+                }
+                Err(err) => log::warn!("Failed to fetch watchlist stats for {exchange}: {err}"),
+            },
+            Message::HeatGridInfoUpdated(pane_id, exchange, result) => match result {
+                Ok(tickers_info) => {
+                    if let Some(state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id)
+                        && let pane::Content::HeatGrid(grid) = &mut state.content
+                    {
+                        grid.insert_tickers_info(exchange, tickers_info);
+                    }
+                }
+                Err(err) => log::warn!("Failed to fetch heat grid tickers for {exchange}: {err}"),
+            },
+            Message::HeatGridStatsUpdated(pane_id, exchange, result) => match result {
+                Ok(stats) => {
+                    if let Some(state) = self.get_mut_pane_state_by_uuid(main_window.id, pane_id)
+                        && let pane::Content::HeatGrid(grid) = &mut state.content
+                    {
+                        grid.insert_stats(exchange, stats);
+                    }
+                }
+                Err(err) => log::warn!("Failed to fetch heat grid stats for {exchange}: {err}"),
+            },
         }
 
         (Task::none(), None)
@@ -549,6 +780,20 @@ impl Dashboard {
             .map(|(_, _, state)| state)
     }
 
+    /// Titles of every pane, main window first, then popouts
+    pub fn pane_titles(&self) -> Vec<String> {
+        self.panes
+            .iter()
+            .map(|(_, state)| state)
+            .chain(
+                self.popout
+                    .values()
+                    .flat_map(|(panes, _)| panes.iter().map(|(_, state)| state)),
+            )
+            .map(pane::State::title)
+            .collect()
+    }
+
     fn iter_all_panes(
         &self,
         main_window: window::Id,
@@ -592,6 +837,7 @@ impl Dashboard {
                 main_window,
                 timezone,
                 tickers_table,
+                &self.synced_view_groups,
             )
         })
         .min_size(240)
@@ -625,6 +871,7 @@ impl Dashboard {
                         main_window,
                         timezone,
                         tickers_table,
+                        &self.synced_view_groups,
                     )
                 })
                 .on_click(pane::Message::PaneClicked),
@@ -690,7 +937,14 @@ impl Dashboard {
 
             for stream in &streams {
                 if let StreamKind::Kline { .. } = stream {
-                    return kline_fetch_task(self.layout_id, pane_id, *stream, None, None);
+                    return kline_fetch_task(
+                        self.layout_id,
+                        pane_id,
+                        *stream,
+                        None,
+                        None,
+                        Priority::Visible,
+                    );
                 }
             }
         }
@@ -726,7 +980,14 @@ impl Dashboard {
 
             for stream in &streams {
                 if let StreamKind::Kline { .. } = stream {
-                    return kline_fetch_task(self.layout_id, pane_id, *stream, None, None);
+                    return kline_fetch_task(
+                        self.layout_id,
+                        pane_id,
+                        *stream,
+                        None,
+                        None,
+                        Priority::Visible,
+                    );
                 }
             }
             return Task::none();
@@ -737,6 +998,238 @@ impl Dashboard {
         )))
     }
 
+    /// Focuses a main window pane showing `ticker_info` as `content_kind`, or splits
+    /// the focused pane to open one when there's none
+    pub fn open_or_focus_ticker(
+        &mut self,
+        main_window: window::Id,
+        ticker_info: TickerInfo,
+        content_kind: ContentKind,
+    ) -> Task<Message> {
+        let existing = self.panes.iter().find_map(|(pane, state)| {
+            (state.stream_pair() == Some(ticker_info) && state.content.kind() == content_kind)
+                .then_some(*pane)
+        });
+
+        if let Some(pane) = existing {
+            return self.focus_pane(main_window, pane);
+        }
+
+        let target = self
+            .focus
+            .filter(|(window, _)| *window == main_window)
+            .map(|(_, pane)| pane)
+            .or_else(|| self.panes.iter().next().map(|(pane, _)| *pane));
+
+        let Some(target) = target else {
+            return Task::none();
+        };
+
+        if let Some((pane, _)) =
+            self.panes
+                .split(pane_grid::Axis::Vertical, target, pane::State::new())
+        {
+            self.focus = Some((main_window, pane));
+            return self.init_focused_pane(main_window, ticker_info, content_kind);
+        }
+
+        Task::none()
+    }
+
+    /// Shows a ticker clicked on a watchlist or heat grid on the other panes of its link group,
+    /// or on a candlestick chart when it has none to show it on
+    fn open_from_ticker_list(
+        &mut self,
+        main_window: window::Id,
+        source: uuid::Uuid,
+        ticker_info: TickerInfo,
+    ) -> Task<Message> {
+        let group = self
+            .iter_all_panes(main_window)
+            .find(|(_, _, state)| state.unique_id() == source)
+            .and_then(|(_, _, state)| state.link_group);
+
+        let linked: Vec<(window::Id, pane_grid::Pane, ContentKind)> = self
+            .iter_all_panes(main_window)
+            .filter(|(_, _, state)| {
+                let kind = state.content.kind();
+
+                group.is_some()
+                    && state.link_group == group
+                    && !kind.is_ticker_list()
+                    && kind != ContentKind::Starter
+            })
+            .map(|(window, pane, state)| (window, pane, state.content.kind()))
+            .collect();
+
+        if linked.is_empty() {
+            return self.open_or_focus_ticker(
+                main_window,
+                ticker_info,
+                ContentKind::CandlestickChart,
+            );
+        }
+
+        let tasks: Vec<Task<Message>> = linked
+            .into_iter()
+            .map(|(window, pane, content_kind)| {
+                self.init_pane(main_window, window, pane, ticker_info, content_kind)
+            })
+            .collect();
+
+        Task::batch(tasks)
+    }
+
+    /// Scrolls the kline panes of `group` to the bar holding `time`
+    fn center_group_on_time(&mut self, main_window: window::Id, group: LinkGroup, time: u64) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.link_group == Some(group))
+            .for_each(|(_, _, state)| {
+                if let pane::Content::Kline {
+                    chart: Some(chart), ..
+                } = &mut state.content
+                {
+                    chart.center_on_time(time);
+                }
+            });
+    }
+
+    /// Lists only trades within `range` on the Time & Sales panes of the ticker, every size
+    /// again once the range is `None`
+    /// Ticker of the focused pane, what order entry hotkeys trade
+    pub fn focused_ticker(&self, main_window: window::Id) -> Option<TickerInfo> {
+        let (window, pane) = self.focus?;
+        self.get_pane(main_window, window, pane)?.stream_pair()
+    }
+
+    /// Hands the simulated orders and position of `ticker` to the charts showing it
+    pub fn set_paper_overlay(
+        &mut self,
+        main_window: window::Id,
+        ticker: Ticker,
+        overlay: &data::paper::Overlay,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.stream_pair().map(|info| info.ticker) == Some(ticker))
+            .for_each(|(_, _, state)| {
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut state.content {
+                    c.set_paper_overlay(overlay.clone());
+                }
+            });
+    }
+
+    /// Brings journal panes up to date with the simulated account
+    pub fn sync_journals(&mut self, main_window: window::Id, account: &data::paper::Account) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| {
+                if let pane::Content::Journal(journal) = &mut state.content {
+                    journal.sync(account);
+                }
+            });
+    }
+
+    pub fn clear_paper_overlays(&mut self, main_window: window::Id) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| {
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut state.content {
+                    c.set_paper_overlay(data::paper::Overlay::default());
+                }
+            });
+    }
+
+    fn filter_tape_by_size(
+        &mut self,
+        main_window: window::Id,
+        ticker_info: TickerInfo,
+        range: Option<(f32, Option<f32>)>,
+    ) {
+        let (trade_size_filter, trade_size_cap) = range.unwrap_or((0.0, None));
+
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.stream_pair() == Some(ticker_info))
+            .for_each(|(_, _, state)| {
+                if let pane::Content::TimeAndSales(Some(panel)) = &mut state.content {
+                    let config = data::panel::timeandsales::Config {
+                        trade_size_filter,
+                        trade_size_cap,
+                        ..panel.config
+                    };
+                    panel.set_config(config);
+                    state.settings.visual_config =
+                        Some(data::layout::pane::VisualConfig::TimeAndSales(config));
+                }
+            });
+    }
+
+    /// Moves the time based charts of `group` to the time window `source` was moved to,
+    /// volume profiles of the group follow it whether or not the view is synced
+    fn sync_time_window(
+        &mut self,
+        main_window: window::Id,
+        group: LinkGroup,
+        source: uuid::Uuid,
+        window: (u64, u64),
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.link_group == Some(group))
+            .for_each(|(_, _, state)| {
+                if let pane::Content::VolumeProfile(Some(panel)) = &mut state.content {
+                    panel.set_linked_window(window);
+                }
+            });
+
+        if !self.synced_view_groups.contains(&group) {
+            return;
+        }
+
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.link_group == Some(group) && state.unique_id() != source)
+            .for_each(|(_, _, state)| match &mut state.content {
+                pane::Content::Kline {
+                    chart: Some(chart), ..
+                } => chart.sync_time_window(window),
+                pane::Content::Heatmap {
+                    chart: Some(chart), ..
+                } => chart.sync_time_window(window),
+                _ => {}
+            });
+    }
+
+    /// Mirrors the bar hovered on `source` on the other panes of `group`
+    fn sync_hovered_bar(
+        &mut self,
+        main_window: window::Id,
+        group: LinkGroup,
+        source: uuid::Uuid,
+        span: Option<(u64, u64)>,
+    ) {
+        // charts off the cursor report `None` on every move, only the source may clear
+        match span {
+            Some(_) => self.hovered_bar_source = Some(source),
+            None if self.hovered_bar_source == Some(source) => self.hovered_bar_source = None,
+            None => return,
+        }
+
+        let time = span.map(|(start, _)| start);
+
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.link_group == Some(group))
+            .for_each(|(_, _, state)| {
+                let time = time.filter(|_| state.unique_id() != source);
+
+                match &mut state.content {
+                    pane::Content::TimeAndSales(Some(panel)) => panel.set_highlight(span),
+                    pane::Content::Kline {
+                        chart: Some(chart), ..
+                    } => chart.sync_crosshair(time),
+                    pane::Content::Heatmap {
+                        chart: Some(chart), ..
+                    } => chart.sync_crosshair(time),
+                    _ => {}
+                }
+            });
+    }
+
     pub fn switch_tickers_in_group(
         &mut self,
         main_window: window::Id,
@@ -758,7 +1251,7 @@ impl Dashboard {
             let pane_infos: Vec<(window::Id, pane_grid::Pane, ContentKind)> = self
                 .iter_all_panes_mut(main_window)
                 .filter_map(|(window, pane, state)| {
-                    if state.link_group == Some(group) {
+                    if state.link_group == Some(group) && !state.content.kind().is_ticker_list() {
                         Some((window, pane, state.content.kind()))
                     } else {
                         None
@@ -790,12 +1283,22 @@ impl Dashboard {
         }
     }
 
-    pub fn toggle_trade_fetch(&mut self, is_enabled: bool, main_window: &Window) {
-        exchange::fetcher::toggle_trade_fetch(is_enabled);
+    pub fn toggle_trade_fetch(
+        &mut self,
+        venue: ExchangeInclusive,
+        is_enabled: bool,
+        main_window: &Window,
+    ) {
+        exchange::fetcher::toggle_trade_fetch(venue, is_enabled);
 
         self.iter_all_panes_mut(main_window.id)
             .for_each(|(_, _, state)| {
-                if let pane::Content::Kline { chart, kind, .. } = &mut state.content
+                let is_venue = state
+                    .stream_pair()
+                    .is_some_and(|ti| ExchangeInclusive::of(ti.exchange()) == venue);
+
+                if is_venue
+                    && let pane::Content::Kline { chart, kind, .. } = &mut state.content
                     && matches!(kind, data::chart::KlineChartKind::Footprint { .. })
                     && let Some(c) = chart
                 {
@@ -816,27 +1319,11 @@ impl Dashboard {
         stream_type: StreamKind,
     ) -> Task<Message> {
         match data {
-            FetchedData::Trades { batch, until_time } => {
-                let last_trade_time = batch.last().map_or(0, |trade| trade.time);
-
-                if last_trade_time < until_time {
-                    if let Err(reason) =
-                        self.insert_fetched_trades(main_window, pane_id, &batch, false)
-                    {
-                        return self.handle_error(Some(pane_id), &reason, main_window);
-                    }
-                } else {
-                    let filtered_batch = batch
-                        .iter()
-                        .filter(|trade| trade.time <= until_time)
-                        .copied()
-                        .collect::<Vec<_>>();
-
-                    if let Err(reason) =
-                        self.insert_fetched_trades(main_window, pane_id, &filtered_batch, true)
-                    {
-                        return self.handle_error(Some(pane_id), &reason, main_window);
-                    }
+            FetchedData::Trades { batch, is_complete } => {
+                if let Err(reason) =
+                    self.insert_fetched_trades(main_window, pane_id, &batch, is_complete)
+                {
+                    return self.handle_error(Some(pane_id), &reason, main_window);
                 }
             }
             FetchedData::Klines { data, req_id } => {
@@ -861,6 +1348,15 @@ impl Dashboard {
                     }
                 }
             }
+            FetchedData::AggregatedOI { data, req_id } => {
+                if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
+                    pane_state.status = pane::Status::Ready;
+
+                    if let StreamKind::Kline { .. } = stream_type {
+                        pane_state.insert_hist_aggr_oi(req_id, &data);
+                    }
+                }
+            }
         }
 
         Task::none()
@@ -881,16 +1377,18 @@ impl Dashboard {
                 )
             })?;
 
-        match &mut pane_state.status {
+        let fetched = match &mut pane_state.status {
             pane::Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(count)) => {
                 *count += trades.len();
+                *count
             }
             _ => {
                 pane_state.status = pane::Status::Loading(
                     exchange::fetcher::InfoKind::FetchingTrades(trades.len()),
                 );
+                trades.len()
             }
-        }
+        };
 
         match &mut pane_state.content {
             pane::Content::Kline { chart, .. } => {
@@ -899,6 +1397,11 @@ impl Dashboard {
 
                     if is_batches_done {
                         pane_state.status = pane::Status::Ready;
+                        if fetched > 0 {
+                            pane_state
+                                .notifications
+                                .push(Toast::info(format!("Backfilled {fetched} trades")));
+                        }
                     }
                     Ok(())
                 } else {
@@ -907,6 +1410,19 @@ impl Dashboard {
                     ))
                 }
             }
+            pane::Content::VolumeProfile(Some(panel)) => {
+                panel.insert_fetched_trades(trades, is_batches_done);
+
+                if is_batches_done {
+                    pane_state.status = pane::Status::Ready;
+                    if fetched > 0 {
+                        pane_state
+                            .notifications
+                            .push(Toast::info(format!("Backfilled {fetched} trades")));
+                    }
+                }
+                Ok(())
+            }
             _ => Err(DashboardError::Unknown(
                 "No matching chart found for fetched trades".to_string(),
             )),
@@ -927,6 +1443,7 @@ impl Dashboard {
                     match &mut pane_state.content {
                         pane::Content::Kline { chart: Some(c), .. } => {
                             c.update_latest_kline(kline);
+                            pane_state.check_alerts(&[(kline.time, kline.close.to_f32())]);
                         }
                         pane::Content::Comparison(Some(c)) => {
                             c.update_latest_kline(&stream.ticker_info(), kline);
@@ -945,11 +1462,26 @@ impl Dashboard {
         }
     }
 
+    pub fn insert_liquidation(
+        &mut self,
+        stream: &StreamKind,
+        liquidation: exchange::Liquidation,
+        main_window: window::Id,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| state.matches_stream(stream))
+            .for_each(|(_, _, state)| match &mut state.content {
+                pane::Content::Heatmap { chart: Some(c), .. } => c.insert_liquidation(liquidation),
+                pane::Content::Kline { chart: Some(c), .. } => c.insert_liquidation(liquidation),
+                _ => {}
+            });
+    }
+
+    /// `depth` is `None` for trades whose depth update was conflated away
     pub fn update_depth_and_trades(
         &mut self,
         stream: &StreamKind,
-        depth_update_t: u64,
-        depth: &Depth,
+        depth: Option<(u64, &Arc<Depth>)>,
         trades_buffer: &[Trade],
         main_window: window::Id,
     ) -> Task<Message> {
@@ -957,26 +1489,66 @@ impl Dashboard {
 
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, pane_state)| {
-                if pane_state.matches_stream(stream) {
+                if pane_state.matches_stream(stream) || pane_state.matches_merged_stream(stream) {
+                    let merged = depth.and_then(|(_, depth)| pane_state.merge_depth(stream, depth));
+                    let depth = depth.map(|(depth_update_t, depth)| {
+                        (depth_update_t, merged.as_ref().unwrap_or(depth.as_ref()))
+                    });
+                    let venue = (!pane_state.merged_streams().is_empty())
+                        .then(|| stream.ticker_info().exchange());
+
                     match &mut pane_state.content {
                         pane::Content::Heatmap { chart, .. } => {
                             if let Some(c) = chart {
-                                c.insert_datapoint(trades_buffer, depth_update_t, depth);
+                                match depth {
+                                    Some((depth_update_t, depth)) => {
+                                        c.insert_datapoint(trades_buffer, depth_update_t, depth);
+                                    }
+                                    None => c.insert_trades(trades_buffer),
+                                }
                             }
                         }
                         pane::Content::Kline { chart, .. } => {
                             if let Some(c) = chart {
                                 c.insert_trades_buffer(trades_buffer);
                             }
+
+                            let prices: Vec<(u64, f32)> = trades_buffer
+                                .iter()
+                                .map(|trade| (trade.time, trade.price.to_f32()))
+                                .collect();
+                            pane_state.check_alerts(&prices);
                         }
                         pane::Content::TimeAndSales(panel) => {
                             if let Some(p) = panel {
-                                p.insert_buffer(trades_buffer);
+                                p.insert_buffer(trades_buffer, venue);
                             }
                         }
                         pane::Content::Ladder(panel) => {
                             if let Some(panel) = panel {
-                                panel.insert_buffers(depth_update_t, depth, trades_buffer);
+                                match depth {
+                                    Some((depth_update_t, depth)) => {
+                                        panel.insert_buffers(depth_update_t, depth, trades_buffer);
+                                    }
+                                    None => panel.insert_trades(trades_buffer),
+                                }
+                            }
+                        }
+                        pane::Content::VolumeProfile(panel) => {
+                            if let Some(p) = panel {
+                                p.insert_buffer(trades_buffer);
+                            }
+                        }
+                        pane::Content::TradeSizes(panel) => {
+                            if let Some(p) = panel {
+                                p.insert_buffer(trades_buffer);
+                            }
+                        }
+                        pane::Content::DepthInspector(inspector) => {
+                            if let Some(inspector) = inspector
+                                && let Some((depth_update_t, depth)) = depth
+                            {
+                                inspector.insert_depth(depth_update_t, depth);
                             }
                         }
                         _ => {
@@ -995,6 +1567,272 @@ impl Dashboard {
         }
     }
 
+    pub fn mark_disconnected(&mut self, exchange: Exchange) {
+        self.disconnected_at
+            .entry(exchange)
+            .or_insert_with(|| chrono::Utc::now().timestamp_millis() as u64);
+    }
+
+    /// Panes whose venue stayed down past their failover delay, with their ticker and the
+    /// venue to move to. Each pane comes up once per outage.
+    pub fn due_failovers(
+        &mut self,
+        main_window: window::Id,
+        now: u64,
+    ) -> Vec<(uuid::Uuid, Ticker, Exchange)> {
+        let due: Vec<(uuid::Uuid, Ticker, Exchange)> = self
+            .iter_all_panes(main_window)
+            .filter_map(|(_, _, state)| {
+                let failover = state.settings.failover?;
+                let ticker = state.stream_pair()?.ticker;
+                let disconnected_at = *self.disconnected_at.get(&ticker.exchange)?;
+
+                let is_due = ticker.exchange != failover.exchange
+                    && failover.is_due(disconnected_at, now)
+                    && !self
+                        .failover_attempted
+                        .contains(&(ticker.exchange, state.unique_id()));
+
+                is_due.then_some((state.unique_id(), ticker, failover.exchange))
+            })
+            .collect();
+
+        self.failover_attempted.extend(
+            due.iter()
+                .map(|(pane_id, ticker, _)| (ticker.exchange, *pane_id)),
+        );
+
+        due
+    }
+
+    /// Moves a pane's streams to `ticker_info` of another venue and annotates the switch
+    pub fn fail_over(
+        &mut self,
+        main_window: window::Id,
+        pane_id: uuid::Uuid,
+        ticker_info: TickerInfo,
+    ) -> Task<Message> {
+        let Some((window, pane, from, content_kind)) = self
+            .iter_all_panes(main_window)
+            .find(|(_, _, state)| state.unique_id() == pane_id)
+            .and_then(|(window, pane, state)| {
+                let from = state.stream_pair()?.exchange();
+                Some((window, pane, from, state.content.kind()))
+            })
+        else {
+            return Task::none();
+        };
+
+        let task = self.init_pane(main_window, window, pane, ticker_info, content_kind);
+
+        if let Some(state) = self.get_mut_pane(main_window, window, pane) {
+            state.record_source_switch(SourceSwitch {
+                time: chrono::Utc::now().timestamp_millis() as u64,
+                from,
+                to: ticker_info.exchange(),
+            });
+        }
+
+        task.chain(self.refresh_streams(main_window))
+    }
+
+    /// Fetches the bars kline charts of `exchange` missed while it was disconnected
+    pub fn backfill_after_reconnect(
+        &mut self,
+        exchange: Exchange,
+        main_window: window::Id,
+    ) -> Task<Message> {
+        self.failover_attempted
+            .retain(|(attempted, _)| *attempted != exchange);
+
+        let Some(since) = self.disconnected_at.remove(&exchange) else {
+            return Task::none();
+        };
+        let layout_id = self.layout_id;
+        let mut tasks = vec![];
+
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| {
+                if !state
+                    .stream_pair()
+                    .is_some_and(|ticker_info| ticker_info.exchange() == exchange)
+                {
+                    return;
+                }
+
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut state.content
+                    && let Some(chart::Action::RequestFetch(reqs)) = c.backfill_since(since)
+                {
+                    tasks.push(request_fetch_many(
+                        state,
+                        layout_id,
+                        reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                        Priority::Background,
+                    ));
+                }
+            });
+
+        Task::batch(tasks)
+    }
+
+    /// Flags panes streaming from `exchange` as closed, or clears the flag once it reopens
+    pub fn set_market_closed(
+        &mut self,
+        exchange: Exchange,
+        reopens_at: Option<u64>,
+        main_window: window::Id,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| {
+                state
+                    .stream_pair()
+                    .is_some_and(|ticker_info| ticker_info.exchange() == exchange)
+            })
+            .for_each(|(_, _, state)| match reopens_at {
+                Some(reopens_at) => state.status = pane::Status::MarketClosed(reopens_at),
+                None => {
+                    if matches!(state.status, pane::Status::MarketClosed(_)) {
+                        state.status = pane::Status::Ready;
+                    }
+                }
+            });
+    }
+
+    /// Shows the retry countdown on panes streaming from `exchange`, or clears it once connected
+    pub fn set_reconnecting(
+        &mut self,
+        exchange: Exchange,
+        retry: Option<(u32, u64)>,
+        main_window: window::Id,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(_, _, state)| {
+                state
+                    .stream_pair()
+                    .is_some_and(|ticker_info| ticker_info.exchange() == exchange)
+            })
+            .for_each(|(_, _, state)| match retry {
+                Some((attempt, retry_at)) => {
+                    // a scheduled close already explains the outage
+                    if !matches!(state.status, pane::Status::MarketClosed(_)) {
+                        state.status = pane::Status::Reconnecting { attempt, retry_at };
+                    }
+                }
+                None => {
+                    if matches!(state.status, pane::Status::Reconnecting { .. }) {
+                        state.status = pane::Status::Ready;
+                    }
+                }
+            });
+    }
+
+    /// Counts user-picked series colors that are hard to read on `background`
+    pub fn low_contrast_colors(&self, main_window: window::Id, background: iced::Color) -> usize {
+        self.iter_all_panes(main_window)
+            .map(|(_, _, state)| state.low_contrast_colors(background))
+            .sum()
+    }
+
+    pub fn remap_low_contrast_colors(&mut self, main_window: window::Id, background: iced::Color) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| state.remap_low_contrast_colors(background));
+    }
+
+    /// Counts panes still built on an outdated tick size of one of the `fresh` tickers
+    pub fn panes_with_stale_ticksize(
+        &self,
+        main_window: window::Id,
+        fresh: &[TickerInfo],
+    ) -> usize {
+        self.iter_all_panes(main_window)
+            .filter(|(_, _, state)| stale_ticker_info(state, fresh).is_some())
+            .count()
+    }
+
+    /// Rebuilds panes of the `fresh` tickers with their new tick size, refetching their history
+    pub fn apply_ticker_info(
+        &mut self,
+        main_window: window::Id,
+        fresh: &[TickerInfo],
+    ) -> Task<Message> {
+        let stale: Vec<(window::Id, pane_grid::Pane, TickerInfo, ContentKind)> = self
+            .iter_all_panes(main_window)
+            .filter_map(|(window, pane, state)| {
+                let ticker_info = stale_ticker_info(state, fresh)?;
+                Some((window, pane, ticker_info, state.content.kind()))
+            })
+            .collect();
+
+        let tasks: Vec<Task<Message>> = stale
+            .into_iter()
+            .map(|(window, pane, ticker_info, content_kind)| {
+                self.init_pane(main_window, window, pane, ticker_info, content_kind)
+            })
+            .collect();
+
+        Task::batch(tasks).chain(self.refresh_streams(main_window))
+    }
+
+    /// Tickers charted or listed by the panes of this layout, once each
+    pub fn referenced_tickers(&self, main_window: window::Id) -> Vec<TickerInfo> {
+        let mut referenced: Vec<TickerInfo> = vec![];
+
+        for (_, _, state) in self.iter_all_panes(main_window) {
+            let tickers: Vec<TickerInfo> = match &state.content {
+                pane::Content::Watchlist(watchlist) => watchlist.tickers().to_vec(),
+                pane::Content::Comparison(Some(chart)) => chart.selected_tickers().to_vec(),
+                _ => state.stream_pair().into_iter().collect(),
+            };
+
+            for ticker_info in tickers {
+                if !referenced.iter().any(|ti| ti.ticker == ticker_info.ticker) {
+                    referenced.push(ticker_info);
+                }
+            }
+        }
+
+        referenced
+    }
+
+    /// Swaps `from` for `to` on every pane, watchlist and alert of this layout,
+    /// along with how many panes it touched
+    pub fn replace_ticker(
+        &mut self,
+        main_window: window::Id,
+        from: Ticker,
+        to: TickerInfo,
+    ) -> (Task<Message>, usize) {
+        let mut touched = 0;
+        let mut charting: Vec<(window::Id, pane_grid::Pane, ContentKind)> = vec![];
+
+        for (window, pane, state) in self.iter_all_panes_mut(main_window) {
+            let mut changed = state.replace_ticker_refs(from, to);
+
+            let kind = state.content.kind();
+            if kind != ContentKind::ComparisonChart
+                && !kind.is_ticker_list()
+                && state.streams.first_ticker() == Some(from)
+            {
+                charting.push((window, pane, kind));
+                changed = true;
+            }
+
+            if changed {
+                touched += 1;
+            }
+        }
+
+        let tasks: Vec<Task<Message>> = charting
+            .into_iter()
+            .map(|(window, pane, content_kind)| {
+                self.init_pane(main_window, window, pane, to, content_kind)
+            })
+            .collect();
+
+        let task = Task::batch(tasks).chain(self.refresh_streams(main_window));
+        (task, touched)
+    }
+
     pub fn invalidate_all_panes(&mut self, main_window: window::Id) {
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, state)| {
@@ -1002,42 +1840,145 @@ impl Dashboard {
             });
     }
 
-    pub fn tick(&mut self, now: Instant, main_window: window::Id) -> Task<Message> {
+    /// Export snapshots of every time-based kline pane, popouts included
+    pub fn daily_snapshots(
+        &self,
+        main_window: window::Id,
+        from: u64,
+        to: u64,
+    ) -> Vec<data::export::DailySnapshot> {
+        self.iter_all_panes(main_window)
+            .filter_map(|(_, _, state)| match &state.content {
+                pane::Content::Kline {
+                    chart: Some(chart), ..
+                } => chart.daily_snapshot(from, to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Redraws panes due for it and polls what they show, `is_idle` redraws less often
+    /// and leaves out polling nobody is looking at
+    pub fn tick(&mut self, now: Instant, main_window: window::Id, is_idle: bool) -> Task<Message> {
         let mut tasks = vec![];
         let layout_id = self.layout_id;
+        let maximized = self.panes.maximized();
 
         self.iter_all_panes_mut(main_window)
-            .for_each(|(_window_id, _pane, state)| match state.tick(now) {
-                Some(pane::Action::Chart(action)) => match action {
-                    chart::Action::ErrorOccurred(err) => {
-                        state.status = pane::Status::Ready;
-                        state.notifications.push(Toast::error(err.to_string()));
+            .for_each(|(window_id, pane, state)| {
+                let pane_id = state.unique_id();
+                // panes behind a maximized one wait for those in view
+                let priority = if window_id == main_window && maximized.is_some_and(|m| m != pane) {
+                    Priority::Background
+                } else {
+                    Priority::Visible
+                };
+
+                if !is_idle && let Some(ticker) = state.funding_refresh_due(now) {
+                    tasks.push(Task::perform(
+                        adapter::fetch_funding_info(ticker),
+                        move |result| {
+                            Message::FundingUpdated(
+                                pane_id,
+                                ticker,
+                                result.map_err(|err| err.to_string()),
+                            )
+                        },
+                    ));
+                }
+
+                if !is_idle && let pane::Content::Watchlist(watchlist) = &mut state.content {
+                    for exchange in watchlist.refresh_due(now) {
+                        tasks.push(Task::perform(
+                            adapter::fetch_ticker_prices(exchange),
+                            move |result| {
+                                Message::WatchlistStatsUpdated(
+                                    pane_id,
+                                    exchange,
+                                    result.map_err(|err| err.to_string()),
+                                )
+                            },
+                        ));
                     }
-                    chart::Action::RequestFetch(reqs) => {
-                        tasks.push(request_fetch_many(
-                            state,
-                            layout_id,
-                            reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                }
+
+                if !is_idle
+                    && let pane::Content::HeatGrid(grid) = &mut state.content
+                    && let Some((exchange, needs_info)) = grid.refresh_due(now)
+                {
+                    if needs_info {
+                        tasks.push(Task::perform(
+                            adapter::fetch_ticker_info(exchange),
+                            move |result| {
+                                Message::HeatGridInfoUpdated(
+                                    pane_id,
+                                    exchange,
+                                    result.map_err(|err| err.to_string()),
+                                )
+                            },
                         ));
                     }
-                },
-                Some(pane::Action::Panel(_action)) => {}
-                Some(pane::Action::ResolveStreams(streams)) => {
-                    tasks.push(Task::done(Message::ResolveStreams(
-                        state.unique_id(),
-                        streams,
-                    )));
+                    tasks.push(Task::perform(
+                        adapter::fetch_ticker_prices(exchange),
+                        move |result| {
+                            Message::HeatGridStatsUpdated(
+                                pane_id,
+                                exchange,
+                                result.map_err(|err| err.to_string()),
+                            )
+                        },
+                    ));
                 }
-                Some(pane::Action::ResolveContent) => match state.stream_pair_kind() {
-                    Some(StreamPairKind::MultiSource(tickers)) => {
-                        state.set_content_and_streams(tickers, state.content.kind());
+
+                match state.tick(now, is_idle) {
+                    Some(pane::Action::Chart(action)) => match action {
+                        chart::Action::ErrorOccurred(err) => {
+                            state.status = pane::Status::Ready;
+                            state.notifications.push(Toast::error(err.to_string()));
+                        }
+                        chart::Action::RequestFetch(reqs) => {
+                            tasks.push(request_fetch_many(
+                                state,
+                                layout_id,
+                                reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                                priority,
+                            ));
+                        }
+                    },
+                    Some(pane::Action::Panel(action)) => match action {
+                        panel::Action::RequestFetch(reqs) => {
+                            tasks.push(request_fetch_many(
+                                state,
+                                layout_id,
+                                reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                                priority,
+                            ));
+                        }
+                    },
+                    Some(pane::Action::ResolveStreams(streams)) => {
+                        tasks.push(Task::done(Message::ResolveStreams(
+                            state.unique_id(),
+                            streams,
+                        )));
                     }
-                    Some(StreamPairKind::SingleSource(ticker)) => {
-                        state.set_content_and_streams(vec![ticker], state.content.kind());
+                    Some(pane::Action::ResolveMergedFeed(ticker, venues)) => {
+                        tasks.push(Task::done(Message::ResolveMergedFeed(
+                            state.unique_id(),
+                            ticker,
+                            venues,
+                        )));
                     }
+                    Some(pane::Action::ResolveContent) => match state.stream_pair_kind() {
+                        Some(StreamPairKind::MultiSource(tickers)) => {
+                            state.set_content_and_streams(tickers, state.content.kind());
+                        }
+                        Some(StreamPairKind::SingleSource(ticker)) => {
+                            state.set_content_and_streams(vec![ticker], state.content.kind());
+                        }
+                        None => {}
+                    },
                     None => {}
-                },
-                None => {}
+                }
             });
 
         Task::batch(tasks)
@@ -1100,11 +2041,106 @@ impl Dashboard {
     fn refresh_streams(&mut self, main_window: window::Id) -> Task<Message> {
         let all_pane_streams = self
             .iter_all_panes(main_window)
-            .flat_map(|(_, _, pane_state)| pane_state.streams.ready_iter().into_iter().flatten());
+            .flat_map(|(_, _, pane_state)| {
+                let ready = pane_state.streams.ready_iter().into_iter().flatten();
+                ready.chain(pane_state.merged_streams())
+            });
         self.streams = UniqueStreams::from(all_pane_streams);
 
+        exchange::feed::set_depth_rates(self.depth_rates(main_window));
+
         Task::none()
     }
+
+    /// Conflation of each listing's depth, the fastest any pane on it asks for
+    fn depth_rates(&self, main_window: window::Id) -> Vec<(TickerInfo, u16)> {
+        let mut rates: HashMap<TickerInfo, Option<u16>> = HashMap::new();
+
+        for (_, _, pane_state) in self.iter_all_panes(main_window) {
+            let rate = pane_state.settings.depth_rate;
+            let ready = pane_state.streams.ready_iter().into_iter().flatten();
+
+            for stream in ready.chain(pane_state.merged_streams()) {
+                if let StreamKind::DepthAndTrades { ticker_info, .. } = stream {
+                    rates
+                        .entry(*ticker_info)
+                        .and_modify(|current| *current = current.zip(rate).map(|(a, b)| a.max(b)))
+                        .or_insert(rate);
+                }
+            }
+        }
+
+        rates
+            .into_iter()
+            .filter_map(|(ticker_info, rate)| Some((ticker_info, rate?)))
+            .collect()
+    }
+
+    /// Hands the resolved listings of the merge venues to the pane and subscribes to them
+    pub fn set_merged_feed(
+        &mut self,
+        main_window: window::Id,
+        pane_id: uuid::Uuid,
+        ticker: Ticker,
+        venues: Vec<Exchange>,
+        listings: Vec<TickerInfo>,
+    ) -> Task<Message> {
+        if let Some(state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
+            state.set_merged_feed(ticker, venues, listings);
+        }
+        self.refresh_streams(main_window)
+    }
+}
+
+/// Screenshots `window` and crops it to the visible bounds of the widget `id`
+fn capture_pane_image(window: window::Id, id: iced::widget::Id, title: String) -> Task<Message> {
+    use iced::widget::selector;
+
+    selector::find(selector::id(id)).then(move |target| {
+        let title = title.clone();
+
+        let Some(bounds) = target.and_then(|target| target.visible_bounds()) else {
+            return Task::done(Message::ImageCaptured(
+                title,
+                Err("Pane isn't visible".to_string()),
+            ));
+        };
+
+        iced::window::screenshot(window).map(move |screenshot| {
+            // bounds are logical, the screenshot is in physical pixels
+            let scale = screenshot.scale_factor;
+            let x = (bounds.x * scale).round().max(0.0) as u32;
+            let y = (bounds.y * scale).round().max(0.0) as u32;
+            let region = iced::Rectangle {
+                x,
+                y,
+                width: ((bounds.width * scale).round() as u32)
+                    .min(screenshot.size.width.saturating_sub(x)),
+                height: ((bounds.height * scale).round() as u32)
+                    .min(screenshot.size.height.saturating_sub(y)),
+            };
+
+            Message::ImageCaptured(
+                title.clone(),
+                screenshot.crop(region).map_err(|err| err.to_string()),
+            )
+        })
+    })
+}
+
+/// Fresh info of the pane's ticker, when the pane still uses a different tick size
+fn stale_ticker_info(state: &pane::State, fresh: &[TickerInfo]) -> Option<TickerInfo> {
+    // comparison lines and ticker lists don't lay out rows by tick size
+    let kind = state.content.kind();
+    if kind == ContentKind::ComparisonChart || kind.is_ticker_list() {
+        return None;
+    }
+    let current = state.stream_pair()?;
+
+    fresh
+        .iter()
+        .find(|info| info.ticker == current.ticker && info.min_ticksize != current.min_ticksize)
+        .copied()
 }
 
 fn request_fetch(
@@ -1113,6 +2149,7 @@ fn request_fetch(
     req_id: uuid::Uuid,
     fetch: FetchRange,
     stream: Option<StreamKind>,
+    priority: Priority,
 ) -> Task<Message> {
     let pane_id = state.unique_id();
 
@@ -1139,10 +2176,11 @@ fn request_fetch(
                     stream,
                     Some(req_id),
                     Some((from, to)),
+                    priority,
                 );
             }
         }
-        FetchRange::OpenInterest(from, to) => {
+        FetchRange::OpenInterest(from, to) | FetchRange::AggregatedOpenInterest(from, to) => {
             let kline_stream = {
                 if let Some(s) = stream {
                     Some((s, pane_id))
@@ -1158,7 +2196,12 @@ fn request_fetch(
             };
 
             if let Some((stream, pane_uid)) = kline_stream {
-                return oi_fetch_task(layout_id, pane_uid, stream, Some(req_id), Some((from, to)));
+                let range = Some((from, to));
+                return if let FetchRange::AggregatedOpenInterest(..) = fetch {
+                    aggr_oi_fetch_task(layout_id, pane_uid, stream, Some(req_id), range, priority)
+                } else {
+                    oi_fetch_task(layout_id, pane_uid, stream, Some(req_id), range, priority)
+                };
             }
         }
         FetchRange::Trades(from_time, to_time) => {
@@ -1171,20 +2214,15 @@ fn request_fetch(
             });
 
             if let Some((ticker_info, pane_id, stream)) = trade_info {
-                let is_binance = matches!(
-                    ticker_info.exchange(),
-                    Exchange::BinanceSpot | Exchange::BinanceLinear | Exchange::BinanceInverse
-                );
-
-                if is_binance {
-                    let data_path = data::data_path(Some("market_data/binance/"));
+                let venue = ExchangeInclusive::of(ticker_info.exchange());
 
+                if exchange::fetcher::is_trade_fetch_enabled(venue) {
                     let (task, handle) = Task::sip(
-                        fetch_trades_batched(ticker_info, from_time, to_time, data_path),
+                        fetch_trades_batched(ticker_info, from_time, to_time),
                         move |batch| {
                             let data = FetchedData::Trades {
                                 batch,
-                                until_time: to_time,
+                                is_complete: false,
                             };
                             Message::DistributeFetchedData {
                                 layout_id,
@@ -1194,7 +2232,15 @@ fn request_fetch(
                             }
                         },
                         move |result| match result {
-                            Ok(()) => Message::ChangePaneStatus(pane_id, pane::Status::Ready),
+                            Ok(batch) => Message::DistributeFetchedData {
+                                layout_id,
+                                pane_id,
+                                data: FetchedData::Trades {
+                                    batch,
+                                    is_complete: true,
+                                },
+                                stream,
+                            },
                             Err(err) => Message::ErrorOccurred(
                                 Some(pane_id),
                                 DashboardError::Fetch(err.to_string()),
@@ -1203,10 +2249,20 @@ fn request_fetch(
                     )
                     .abortable();
 
-                    if let pane::Content::Kline { chart, .. } = &mut state.content
-                        && let Some(c) = chart
-                    {
-                        c.set_handle(handle.abort_on_drop());
+                    state.notifications.push(Toast::info(format!(
+                        "Backfilling {} trades over {}",
+                        ticker_info.ticker,
+                        exchange::schedule::format_countdown(to_time.saturating_sub(from_time)),
+                    )));
+
+                    match &mut state.content {
+                        pane::Content::Kline { chart: Some(c), .. } => {
+                            c.set_handle(handle.abort_on_drop());
+                        }
+                        pane::Content::VolumeProfile(Some(p)) => {
+                            p.set_handle(handle.abort_on_drop());
+                        }
+                        _ => {}
                     }
 
                     return task;
@@ -1222,10 +2278,13 @@ fn request_fetch_many(
     state: &mut pane::State,
     layout_id: uuid::Uuid,
     reqs: impl IntoIterator<Item = (uuid::Uuid, FetchRange, Option<StreamKind>)>,
+    priority: Priority,
 ) -> Task<Message> {
     let tasks = reqs
         .into_iter()
-        .map(|(req_id, fetch, stream)| request_fetch(state, layout_id, req_id, fetch, stream))
+        .map(|(req_id, fetch, stream)| {
+            request_fetch(state, layout_id, req_id, fetch, stream, priority)
+        })
         .collect::<Vec<_>>();
     Task::batch(tasks)
 }
@@ -1236,6 +2295,7 @@ fn oi_fetch_task(
     stream: StreamKind,
     req_id: Option<uuid::Uuid>,
     range: Option<(u64, u64)>,
+    priority: Priority,
 ) -> Task<Message> {
     let update_status = Task::done(Message::ChangePaneStatus(
         pane_id,
@@ -1247,7 +2307,7 @@ fn oi_fetch_task(
             ticker_info,
             timeframe,
         } => Task::perform(
-            adapter::fetch_open_interest(ticker_info.ticker, timeframe, range)
+            scheduler::fetch_open_interest(ticker_info.ticker, timeframe, range, priority)
                 .map_err(|err| format!("{err}")),
             move |result| match result {
                 Ok(oi) => {
@@ -1268,12 +2328,52 @@ fn oi_fetch_task(
     update_status.chain(fetch_task)
 }
 
+fn aggr_oi_fetch_task(
+    layout_id: uuid::Uuid,
+    pane_id: uuid::Uuid,
+    stream: StreamKind,
+    req_id: Option<uuid::Uuid>,
+    range: Option<(u64, u64)>,
+    priority: Priority,
+) -> Task<Message> {
+    let update_status = Task::done(Message::ChangePaneStatus(
+        pane_id,
+        pane::Status::Loading(exchange::fetcher::InfoKind::FetchingOI),
+    ));
+
+    let fetch_task = match stream {
+        StreamKind::Kline {
+            ticker_info,
+            timeframe,
+        } => Task::perform(
+            scheduler::fetch_aggregated_oi(ticker_info.ticker, timeframe, range, priority)
+                .map_err(|err| format!("{err}")),
+            move |result| match result {
+                Ok(oi) => {
+                    let data = FetchedData::AggregatedOI { data: oi, req_id };
+                    Message::DistributeFetchedData {
+                        layout_id,
+                        pane_id,
+                        data,
+                        stream,
+                    }
+                }
+                Err(err) => Message::ErrorOccurred(Some(pane_id), DashboardError::Fetch(err)),
+            },
+        ),
+        _ => Task::none(),
+    };
+
+    update_status.chain(fetch_task)
+}
+
 fn kline_fetch_task(
     layout_id: uuid::Uuid,
     pane_id: uuid::Uuid,
     stream: StreamKind,
     req_id: Option<uuid::Uuid>,
     range: Option<(u64, u64)>,
+    priority: Priority,
 ) -> Task<Message> {
     let update_status = Task::done(Message::ChangePaneStatus(
         pane_id,
@@ -1285,8 +2385,14 @@ fn kline_fetch_task(
             ticker_info,
             timeframe,
         } => Task::perform(
-            adapter::fetch_klines(ticker_info, timeframe, range)
-                .map_err(|err| err.to_user_message()),
+            scheduler::fetch_klines(
+                ticker_info,
+                timeframe,
+                range,
+                data::data_path(Some(KLINE_CACHE_DIR)),
+                priority,
+            )
+            .map_err(|err| err.to_user_message()),
             move |result| match result {
                 Ok(klines) => {
                     let data = FetchedData::Klines {
@@ -1311,31 +2417,24 @@ fn kline_fetch_task(
     update_status.chain(fetch_task)
 }
 
+/// Pages through the range with the venue's backfill, the last batch is the output
 pub fn fetch_trades_batched(
     ticker_info: TickerInfo,
     from_time: u64,
     to_time: u64,
-    data_path: PathBuf,
-) -> impl Straw<(), Vec<Trade>, AdapterError> {
+) -> impl Straw<Vec<Trade>, Vec<Trade>, AdapterError> {
     sipper(async move |mut progress| {
-        let mut latest_trade_t = from_time;
+        let mut backfill = data::backfill::Backfill::new(ticker_info, from_time, to_time);
 
-        while latest_trade_t < to_time {
-            match binance::fetch_trades(ticker_info, latest_trade_t, data_path.clone()).await {
-                Ok(batch) => {
-                    if batch.is_empty() {
-                        break;
-                    }
-
-                    latest_trade_t = batch.last().map_or(latest_trade_t, |trade| trade.time);
-
-                    let () = progress.send(batch).await;
-                }
-                Err(err) => return Err(err),
+        loop {
+            let batch = backfill.next_batch().await?;
+            if batch.is_complete {
+                return Ok(batch.trades);
+            }
+            if !batch.trades.is_empty() {
+                let () = progress.send(batch.trades).await;
             }
         }
-
-        Ok(())
     })
 }
 
@@ -1372,6 +2471,18 @@ pub fn depth_subscription(
                 |cfg: &StreamConfig<TickerInfo>| okex::connect_market_stream(cfg.id, cfg.push_freq);
             Subscription::run_with(config, builder)
         }
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            let builder = |cfg: &StreamConfig<TickerInfo>| {
+                deribit::connect_market_stream(cfg.id, cfg.push_freq)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::Simulated => {
+            let builder = |cfg: &StreamConfig<TickerInfo>| {
+                simulated::connect_market_stream(cfg.id, cfg.push_freq)
+            };
+            Subscription::run_with(config, builder)
+        }
         Exchange::Forex => Subscription::none(),
     }
 }
@@ -1406,6 +2517,18 @@ pub fn kline_subscription(
             };
             Subscription::run_with(config, builder)
         }
+        Exchange::DeribitLinear | Exchange::DeribitInverse => {
+            let builder = |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe)>>| {
+                deribit::connect_kline_stream(cfg.id.clone(), cfg.market_type)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::Simulated => {
+            let builder = |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe)>>| {
+                simulated::connect_kline_stream(cfg.id.clone())
+            };
+            Subscription::run_with(config, builder)
+        }
         Exchange::Forex => Subscription::none(),
     }
 }
@@ -10,7 +10,11 @@ use crate::{
         },
     },
     screen::dashboard::{
-        panel::{self, ladder::Ladder, timeandsales::TimeAndSales},
+        panel::{
+            self, depth_inspector::DepthInspector, heatgrid::HeatGrid, journal::Journal,
+            ladder::Ladder, timeandsales::TimeAndSales, trade_sizes::TradeSizes,
+            volume_profile::VolumeProfile, watchlist::Watchlist,
+        },
         tickers_table::TickersTable,
     },
     style::{self, Icon, icon_text},
@@ -20,23 +24,48 @@ use crate::{
 use data::{
     UserTimezone,
     chart::{
-        Basis, TradingConfig, ViewConfig,
-        indicator::{HeatmapIndicator, Indicator, KlineIndicator, UiIndicator},
+        Basis, Navigation, PriceScale, TradingConfig, ViewConfig, Watermark,
+        drawing::{Anchor, DrawingTool},
+        indicator::{
+            HeatmapIndicator, Indicator, IndicatorParams, KlineIndicator, KlineInstance,
+            UiIndicator,
+        },
+        moving_average::MovingAverage,
+    },
+    layout::pane::{
+        ContentKind, Failover, LinkGroup, PaneSetup, Settings, SourceSwitch, VisualConfig,
+        auto_title,
     },
-    layout::pane::{ContentKind, LinkGroup, PaneSetup, Settings, VisualConfig},
 };
 use exchange::{
-    Kline, OpenInterest, StreamPairKind, TickMultiplier, TickerInfo, Timeframe,
-    adapter::{MarketKind, PersistStreamKind, ResolvedStream, StreamKind, StreamTicksize},
+    FundingInfo, Kline, OpenInterest, PushFrequency, StreamPairKind, TickMultiplier, Ticker,
+    TickerInfo, Timeframe,
+    adapter::{
+        Exchange, MarketKind, PersistStreamKind, ResolvedStream, StreamKind, StreamTicksize,
+    },
+    composite::MergedDepth,
+    depth::Depth,
     fetcher::FetchRequests,
+    util::Price,
 };
 use iced::{
     Alignment, Element, Length, Renderer, Theme,
     alignment::Vertical,
     padding,
-    widget::{button, center, column, container, pane_grid, pick_list, row, text, tooltip},
+    widget::{
+        button, center, column, container, pane_grid, pick_list, row, text, text_input, tooltip,
+    },
 };
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often funding is refetched for perp panes
+const FUNDING_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_TITLE_LEN: usize = 40;
+/// Shorter wait used while the shown funding is missing or past its settlement
+const FUNDING_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// Slowest a pane redraws while the user is idle
+const IDLE_UPDATE_INTERVAL_MS: u64 = 5000;
 
 #[derive(Debug, Clone)]
 pub enum Effect {
@@ -44,6 +73,23 @@ pub enum Effect {
     RequestFetch(FetchRequests),
     SwitchTickersInGroup(TickerInfo),
     FocusWidget(iced::widget::Id),
+    /// Kline panes of the group should scroll to the trade time
+    CenterOnTime(LinkGroup, u64),
+    /// Time span of the bar hovered on a chart, marked on the other panes of the group:
+    /// prints on Time & Sales, a crosshair on charts
+    SyncHoveredBar(LinkGroup, Option<(u64, u64)>),
+    /// Time window a chart of the group was panned or zoomed to
+    SyncTimeWindow(LinkGroup, (u64, u64)),
+    /// Ticker clicked on a watchlist or heat grid, shown on the group's panes or a new chart
+    OpenTicker(TickerInfo),
+    /// Size range in quote currency the ticker's Time & Sales panes should list, `None`
+    /// as the upper bound for no limit, or no range to list every size again
+    FilterTape(TickerInfo, Option<(f32, Option<f32>)>),
+    /// Body of the pane to capture into an image, with the title to name it after
+    CaptureImage(iced::widget::Id, String),
+    /// Simulated order entry on the pane's ticker
+    PaperTrade(TickerInfo, data::paper::Command),
+    ExportJournal,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -52,6 +98,13 @@ pub enum Status {
     Ready,
     Loading(exchange::fetcher::InfoKind),
     Stale(String),
+    /// Venue is in scheduled downtime, reopens at the given unix timestamp (ms)
+    MarketClosed(u64),
+    /// Stream dropped and is retrying, next attempt is due at the given unix timestamp (ms)
+    Reconnecting {
+        attempt: u32,
+        retry_at: u64,
+    },
 }
 
 pub enum Action {
@@ -59,6 +112,8 @@ pub enum Action {
     Panel(panel::Action),
     ResolveStreams(Vec<PersistStreamKind>),
     ResolveContent,
+    /// Listings of the ticker on the merge venues are needed for the merged feed
+    ResolveMergedFeed(Ticker, Vec<Exchange>),
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +129,8 @@ pub enum Message {
     Popout,
     Merge,
     SwitchLinkGroup(pane_grid::Pane, Option<LinkGroup>),
+    /// Toggles syncing the time window across the charts of a group
+    ToggleViewSync(LinkGroup),
     VisualConfigChanged(pane_grid::Pane, VisualConfig, bool),
     PaneEvent(pane_grid::Pane, Event),
 }
@@ -85,15 +142,46 @@ pub enum Event {
     ContentSelected(ContentKind),
     ChartInteraction(super::chart::Message),
     PanelInteraction(super::panel::Message),
+    WatchlistInteraction(super::panel::watchlist::Message),
+    HeatGridInteraction(super::panel::heatgrid::Message),
+    JournalInteraction(super::panel::journal::Message),
+    DepthInspectorInteraction(super::panel::depth_inspector::Message),
+    TradeSizesInteraction(super::panel::trade_sizes::Message),
     ToggleIndicator(UiIndicator),
+    RemoveIndicator(u16),
+    IndicatorParamsChanged(u16, Option<IndicatorParams>),
+    ToggleSupportResistance,
+    ToggleLargeOrders,
+    LargeOrderThresholdChanged(f32),
     DeleteNotification(usize),
     ReorderIndicator(column_drag::DragEvent),
     ClusterKindSelected(data::chart::kline::ClusterKind),
     ClusterScalingSelected(data::chart::kline::ClusterScaling),
     StudyConfigurator(modal::pane::settings::study::StudyMessage),
+    RevertSettings,
     StreamModifierChanged(modal::stream::Message),
     ComparisonChartInteraction(super::chart::comparison::Message),
     MiniTickersListInteraction(modal::pane::mini_tickers_list::Message),
+    Alerts(modal::pane::alerts::Action),
+    MovingAverages(modal::pane::settings::MovingAverageAction),
+    TogglePin(Price),
+    ClearPins,
+    PaperTrade(data::paper::Command),
+    /// Picks a drawing tool, picking the active one again puts it away
+    DrawingToolSelected(DrawingTool),
+    ClearDrawings,
+    FibLevelToggled(f32),
+    PriceScale(super::chart::PriceScaleAction),
+    /// Custom title being typed, an empty one brings back the generated title
+    TitleChanged(String),
+    FailoverChanged(Option<Failover>),
+    MergeVenueToggled(Exchange, bool),
+    /// Heatmap scrubbed back to the book at a time, `None` goes back to live
+    HeatmapScrubbed(Option<u64>),
+    /// Pane color override, `None` goes back to the theme color
+    ColorChanged(data::chart::colors::ColorRole, Option<iced::Color>),
+    /// Saves the chart as shown, axes included, to a PNG
+    ExportImage,
 }
 
 pub struct State {
@@ -105,6 +193,36 @@ pub struct State {
     pub streams: ResolvedStream,
     pub status: Status,
     pub link_group: Option<LinkGroup>,
+    settings_snapshot: Option<SettingsSnapshot>,
+    /// Latest funding of the streamed perp, with the ticker it was fetched for
+    pub funding: Option<(Ticker, FundingInfo)>,
+    funding_requested_at: Option<Instant>,
+    /// Latest streamed price, the starting point for alert crossings
+    last_trade_price: Option<f32>,
+    /// Alert prices being typed in the alerts list, applied once they parse
+    alert_drafts: Vec<(u32, String)>,
+    composite_builder: Option<modal::pane::alerts::CompositeBuilder>,
+    merged_feed: MergedFeed,
+}
+
+/// Streams of the other venues merged into the pane, and the books they last sent
+#[derive(Default)]
+struct MergedFeed {
+    /// Ticker and merge venues the streams were resolved for
+    resolved_for: Option<(Ticker, Vec<Exchange>)>,
+    requested_at: Option<Instant>,
+    streams: Vec<StreamKind>,
+    depth: MergedDepth,
+}
+
+/// Pane configuration captured when the settings modal opens, so live edits can be reverted
+#[derive(Debug, Clone, PartialEq)]
+struct SettingsSnapshot {
+    visual_config: Option<VisualConfig>,
+    studies: Option<data::chart::Study>,
+    chart_kind: Option<data::chart::KlineChartKind>,
+    tick_multiply: Option<TickMultiplier>,
+    trading_config: Option<TradingConfig>,
 }
 
 impl State {
@@ -134,6 +252,463 @@ impl State {
         })
     }
 
+    fn primary_depth_stream(&self) -> Option<TickerInfo> {
+        self.streams
+            .find_ready_map(|stream| stream.as_depth_stream().map(|(info, ..)| info))
+    }
+
+    /// Ticker and venues whose listings should be resolved now, marking the request as sent
+    fn merged_feed_due(&mut self, now: Instant) -> Option<(Ticker, Vec<Exchange>)> {
+        let ticker = self.primary_depth_stream().map(|info| info.ticker)?;
+        let wanted = (ticker, self.settings.merge_venues.clone());
+
+        let feed = &mut self.merged_feed;
+        if feed.resolved_for.as_ref() == Some(&wanted) {
+            return None;
+        }
+
+        if wanted.1.is_empty() {
+            feed.streams.clear();
+            feed.depth.clear();
+            feed.resolved_for = Some(wanted);
+            return None;
+        }
+
+        let is_due = feed.requested_at.is_none_or(|requested_at| {
+            now.duration_since(requested_at) >= exchange::adapter::RESOLVE_RETRY_INTERVAL
+        });
+        if !is_due {
+            return None;
+        }
+
+        feed.requested_at = Some(now);
+        Some(wanted)
+    }
+
+    /// Streams of the other venues, empty until resolved for the streamed ticker
+    pub fn merged_streams(&self) -> &[StreamKind] {
+        let primary = self.primary_depth_stream().map(|info| info.ticker);
+
+        match &self.merged_feed.resolved_for {
+            Some((ticker, _)) if Some(*ticker) == primary => &self.merged_feed.streams,
+            _ => &[],
+        }
+    }
+
+    pub fn matches_merged_stream(&self, stream: &StreamKind) -> bool {
+        self.merged_streams().contains(stream)
+    }
+
+    /// Subscribes the merged feed to `listings`, the other venues' listings of `ticker`
+    pub fn set_merged_feed(
+        &mut self,
+        ticker: Ticker,
+        venues: Vec<Exchange>,
+        listings: Vec<TickerInfo>,
+    ) {
+        let feed = &mut self.merged_feed;
+
+        feed.streams = listings
+            .into_iter()
+            .map(|ticker_info| StreamKind::DepthAndTrades {
+                ticker_info,
+                depth_aggr: ticker_info
+                    .exchange()
+                    .stream_ticksize(None, TickMultiplier(1)),
+                push_freq: PushFrequency::ServerDefault,
+            })
+            .collect();
+        feed.depth.clear();
+        feed.resolved_for = Some((ticker, venues));
+        feed.requested_at = None;
+    }
+
+    /// Book of every venue summed, once the pane merges any. `None` leaves `depth` as is
+    pub fn merge_depth(&mut self, stream: &StreamKind, depth: &Arc<Depth>) -> Option<Depth> {
+        if self.merged_streams().is_empty() {
+            return None;
+        }
+        let min_ticksize = self.primary_depth_stream()?.min_ticksize;
+
+        Some(
+            self.merged_feed
+                .depth
+                .update(stream.ticker_info().exchange(), depth, min_ticksize),
+        )
+    }
+
+    /// Perp ticker whose funding should be fetched now, marking the request as sent
+    pub fn funding_refresh_due(&mut self, now: Instant) -> Option<Ticker> {
+        let ticker = self
+            .stream_pair()
+            .map(|ticker_info| ticker_info.ticker)
+            .filter(|ticker| ticker.market_type() != MarketKind::Spot);
+
+        let Some(ticker) = ticker else {
+            self.funding = None;
+            return None;
+        };
+
+        let now_ms = exchange::clock::now_ms(ticker.exchange);
+        let is_stale = self
+            .funding
+            .is_none_or(|(funded, info)| funded != ticker || info.next_funding_time <= now_ms);
+
+        let is_due = self.funding_requested_at.is_none_or(|requested_at| {
+            let elapsed = now.duration_since(requested_at);
+            elapsed >= FUNDING_REFRESH_INTERVAL || (is_stale && elapsed >= FUNDING_RETRY_INTERVAL)
+        });
+
+        if !is_due {
+            return None;
+        }
+
+        self.funding_requested_at = Some(now);
+        Some(ticker)
+    }
+
+    /// Hands the pinned levels of the streamed ticker to the heatmap or ladder that draws them
+    fn sync_pinned_levels(&mut self) {
+        let Some(ticker) = self.stream_pair().map(|info| info.ticker) else {
+            return;
+        };
+        let levels = self.settings.pinned_levels.for_ticker(ticker).collect();
+
+        match &mut self.content {
+            Content::Heatmap { chart: Some(c), .. } => c.set_pinned_levels(levels),
+            Content::Ladder(Some(p)) => p.set_pinned_levels(levels),
+            _ => {}
+        }
+    }
+
+    /// Hands the drawings of the streamed ticker to the kline chart
+    fn sync_drawings(&mut self) {
+        let Some(ticker) = self.stream_pair().map(|info| info.ticker) else {
+            return;
+        };
+
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            let drawings = &self.settings.drawings;
+            c.set_drawings(
+                drawings.for_ticker(ticker).cloned().collect(),
+                &drawings.fib_levels,
+            );
+        }
+    }
+
+    fn sync_colors(&mut self) {
+        let colors = self.settings.colors;
+
+        match &mut self.content {
+            Content::Kline { chart: Some(c), .. } => c.set_colors(colors),
+            Content::Heatmap { chart: Some(c), .. } => c.set_colors(colors),
+            _ => {}
+        }
+    }
+
+    fn sync_source_switches(&mut self) {
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            c.set_source_switches(&self.settings.source_switches);
+        }
+    }
+
+    /// Notes a failover venue change, to be annotated on the chart
+    pub fn record_source_switch(&mut self, switch: SourceSwitch) {
+        let switches = &mut self.settings.source_switches;
+        switches.push(switch);
+        if switches.len() > SourceSwitch::MAX_KEPT {
+            switches.remove(0);
+        }
+        self.sync_source_switches();
+    }
+
+    /// Points the watchlist entry, comparison series and alerts of `from` at `to`,
+    /// returning whether any changed. Panes charting `from` get reinitialized by the dashboard
+    pub fn replace_ticker_refs(&mut self, from: Ticker, to: TickerInfo) -> bool {
+        let mut changed = self.settings.alerts.replace_ticker(from, to.ticker) > 0;
+        if changed {
+            self.sync_alerts();
+        }
+
+        match &mut self.content {
+            Content::Watchlist(watchlist) => {
+                let replaced = watchlist.replace_ticker(from, to);
+                if replaced {
+                    self.settings.visual_config =
+                        Some(VisualConfig::Watchlist(watchlist.config.clone()));
+                    changed = true;
+                }
+            }
+            Content::Comparison(Some(chart)) => {
+                if let Some(old) = chart
+                    .selected_tickers()
+                    .iter()
+                    .find(|ti| ti.ticker == from)
+                    .copied()
+                {
+                    chart.remove_ticker(&old);
+                    let rebuilt = chart.add_ticker(&to);
+                    self.streams = ResolvedStream::Ready(rebuilt);
+                    changed = true;
+                }
+            }
+            _ => {}
+        }
+
+        changed
+    }
+
+    /// Fib retracements are anchored on swing highs and lows, so their anchors snap to bars
+    fn snap_anchor(&self, tool: Option<DrawingTool>, anchor: Anchor) -> Anchor {
+        match &self.content {
+            Content::Kline { chart: Some(c), .. } if tool == Some(DrawingTool::FibRetracement) => {
+                c.snap_to_ohlc(anchor)
+            }
+            _ => anchor,
+        }
+    }
+
+    fn update_drawings(&mut self, msg: super::chart::Message) {
+        use super::chart::Message;
+
+        let Some(ticker) = self.stream_pair().map(|info| info.ticker) else {
+            return;
+        };
+
+        match msg {
+            Message::DrawingPlaced(tool, start, end) => {
+                let anchors = [start, end].map(|anchor| self.snap_anchor(Some(tool), anchor));
+                self.settings.drawings.add(ticker, tool, anchors);
+
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.set_drawing_tool(None);
+                }
+            }
+            Message::AnchorDragged(id, idx, anchor) => {
+                let anchor = self.snap_anchor(self.settings.drawings.tool_of(id), anchor);
+                self.settings.drawings.move_anchor(id, idx, anchor);
+            }
+            Message::DrawingRemoved(id) => self.settings.drawings.remove(id),
+            _ => return,
+        }
+
+        self.sync_drawings();
+    }
+
+    fn update_alerts(&mut self, action: modal::pane::alerts::Action) {
+        use modal::pane::alerts::{Action, CompositeBuilder};
+
+        let last_price = self.last_trade_price.or(match &self.content {
+            Content::Kline { chart: Some(c), .. } => c.last_price(),
+            _ => None,
+        });
+
+        match action {
+            Action::Add => {
+                if let (Some(ticker_info), Some(price)) = (self.stream_pair(), last_price) {
+                    self.settings.alerts.add(
+                        ticker_info.ticker,
+                        price,
+                        data::chart::alert::AlertTrigger::Crossing,
+                    );
+                }
+            }
+            Action::PriceEdited(id, input) => {
+                self.alert_drafts.retain(|(draft_id, _)| *draft_id != id);
+
+                match input.parse::<f32>() {
+                    Ok(price) if price > 0.0 => {
+                        if let Some(alert) = self.settings.alerts.get_mut(id) {
+                            alert.price = price;
+                            alert.fired_at = None;
+                        }
+                    }
+                    _ => self.alert_drafts.push((id, input)),
+                }
+            }
+            Action::TriggerChanged(id, trigger) => {
+                if let Some(alert) = self.settings.alerts.get_mut(id) {
+                    alert.trigger = trigger;
+                }
+            }
+            Action::ToggleMute(id) => {
+                if let Some(alert) = self.settings.alerts.get_mut(id) {
+                    alert.muted = !alert.muted;
+                } else if let Some(alert) = self.settings.alerts.get_composite_mut(id) {
+                    alert.muted = !alert.muted;
+                }
+            }
+            Action::Rearm(id) => {
+                if let Some(alert) = self.settings.alerts.get_mut(id) {
+                    alert.fired_at = None;
+                } else if let Some(alert) = self.settings.alerts.get_composite_mut(id) {
+                    alert.fired_at = None;
+                }
+            }
+            Action::JumpTo(id) => {
+                let price = self
+                    .settings
+                    .alerts
+                    .items
+                    .iter()
+                    .find(|a| a.id == id)
+                    .map(|a| a.price);
+
+                if let (Some(price), Content::Kline { chart: Some(c), .. }) =
+                    (price, &mut self.content)
+                {
+                    c.center_on_price(price);
+                }
+            }
+            Action::Delete(id) => {
+                self.settings.alerts.remove(id);
+                self.alert_drafts.retain(|(draft_id, _)| *draft_id != id);
+            }
+            Action::NewComposite => {
+                self.composite_builder = Some(CompositeBuilder::new(last_price));
+            }
+            Action::Builder(action) => {
+                if let Some(builder) = &mut self.composite_builder {
+                    builder.update(action);
+                }
+            }
+            Action::SaveComposite => {
+                if let Some(ticker_info) = self.stream_pair()
+                    && let Some((combinator, conditions)) =
+                        self.composite_builder.as_ref().and_then(|b| b.build())
+                {
+                    self.settings
+                        .alerts
+                        .add_composite(ticker_info.ticker, combinator, conditions);
+                    self.composite_builder = None;
+                }
+            }
+            Action::DiscardComposite => self.composite_builder = None,
+        }
+
+        self.sync_alerts();
+    }
+
+    fn update_moving_averages(&mut self, action: modal::pane::settings::MovingAverageAction) {
+        use modal::pane::settings::MovingAverageAction;
+
+        let moving_averages = &mut self.settings.moving_averages;
+
+        match action {
+            MovingAverageAction::Add => {
+                if moving_averages.len() < MovingAverage::MAX_COUNT {
+                    let ma = MovingAverage::next_after(moving_averages);
+                    moving_averages.push(ma);
+                }
+            }
+            MovingAverageAction::Remove(idx) => {
+                if idx < moving_averages.len() {
+                    moving_averages.remove(idx);
+                }
+            }
+            MovingAverageAction::Kind(idx, kind) => {
+                if let Some(ma) = moving_averages.get_mut(idx) {
+                    ma.kind = kind;
+                }
+            }
+            MovingAverageAction::Period(idx, period) => {
+                if let Some(ma) = moving_averages.get_mut(idx) {
+                    ma.period = period;
+                }
+            }
+            MovingAverageAction::Color(idx, color) => {
+                if let Some(ma) = moving_averages.get_mut(idx) {
+                    ma.color = color;
+                }
+            }
+        }
+
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            c.set_moving_averages(&self.settings.moving_averages);
+        }
+    }
+
+    /// Pushes the pane's alerts to the chart that draws them
+    fn sync_alerts(&mut self) {
+        if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+            c.set_alerts(&self.settings.alerts);
+        }
+    }
+
+    /// Fires alerts crossed by streamed `(time, price)` updates, in arrival order,
+    /// then composites against the chart state the updates left behind
+    pub fn check_alerts(&mut self, prices: &[(u64, f32)]) {
+        let Some(&(time, last)) = prices.last() else {
+            return;
+        };
+        let Some(ticker_info) = self.stream_pair() else {
+            return;
+        };
+        let ticker = ticker_info.ticker;
+
+        let prev = self.last_trade_price.replace(last);
+        let mut fired = 0;
+
+        if let Some(prev) = prev.filter(|_| !self.settings.alerts.items.is_empty()) {
+            for alert in self
+                .settings
+                .alerts
+                .evaluate(ticker, prev, prices.iter().copied())
+            {
+                fired += 1;
+                if !alert.muted {
+                    self.notifications.push(Toast::info(format!(
+                        "{ticker} {} {}",
+                        alert.trigger.to_string().to_lowercase(),
+                        alert.price
+                    )));
+                }
+            }
+        }
+
+        if let Some(lookback) = self.settings.alerts.composite_lookback(ticker)
+            && let Content::Kline { chart: Some(c), .. } = &self.content
+        {
+            let state = c.market_state(lookback);
+
+            for alert in self
+                .settings
+                .alerts
+                .evaluate_composites(ticker, time, &state)
+            {
+                fired += 1;
+                if !alert.muted {
+                    self.notifications
+                        .push(Toast::info(format!("{ticker}: {alert}")));
+                }
+            }
+        }
+
+        if fired > 0 {
+            self.sync_alerts();
+        }
+    }
+
+    fn funding_label(&self) -> Option<String> {
+        let ticker = self.stream_pair()?.ticker;
+        let (funded, info) = self.funding?;
+        if funded != ticker {
+            return None;
+        }
+
+        let now_ms = exchange::clock::now_ms(ticker.exchange);
+        let mut label = format!(
+            "Funding {:.4}% in {}",
+            info.rate * 100.0,
+            exchange::schedule::format_countdown(info.next_funding_time.saturating_sub(now_ms))
+        );
+        if let Some(predicted) = info.predicted_rate {
+            label.push_str(&format!(" (next {:.4}%)", predicted * 100.0));
+        }
+
+        Some(label)
+    }
+
     pub fn stream_pair_kind(&self) -> Option<StreamPairKind> {
         let ready_streams = self.streams.ready_iter()?;
         let mut unique = vec![];
@@ -278,7 +853,21 @@ impl State {
                         .visual_config
                         .clone()
                         .and_then(|cfg| cfg.ladder());
-                    let content = Content::Ladder(Some(Ladder::new(
+                    let content = Content::Ladder(Some(Box::new(Ladder::new(
+                        config,
+                        derived_plan.ticker_info,
+                        derived_plan.tick_size,
+                    ))));
+
+                    (content, vec![depth_stream(&derived_plan)])
+                }
+                ContentKind::VolumeProfile => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.volume_profile());
+                    let content = Content::VolumeProfile(Some(VolumeProfile::new(
                         config,
                         derived_plan.ticker_info,
                         derived_plan.tick_size,
@@ -286,6 +875,37 @@ impl State {
 
                     (content, vec![depth_stream(&derived_plan)])
                 }
+                ContentKind::DepthInspector => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.depth_inspector());
+                    let content = Content::DepthInspector(Some(DepthInspector::new(
+                        config,
+                        derived_plan.ticker_info,
+                    )));
+
+                    (content, vec![depth_stream(&derived_plan)])
+                }
+                ContentKind::TradeSizes => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.trade_sizes());
+                    let content = Content::TradeSizes(Some(TradeSizes::new(
+                        config,
+                        derived_plan.ticker_info,
+                    )));
+
+                    let temp = PaneSetup {
+                        push_freq: exchange::PushFrequency::ServerDefault,
+                        ..derived_plan
+                    };
+
+                    (content, vec![depth_stream(&temp)])
+                }
                 ContentKind::ComparisonChart => {
                     let config = self
                         .settings
@@ -311,12 +931,45 @@ impl State {
 
                     (content, streams)
                 }
+                ContentKind::Watchlist => {
+                    let mut watchlist = match std::mem::take(&mut self.content) {
+                        Content::Watchlist(watchlist) => watchlist,
+                        _ => Watchlist::new(
+                            self.settings
+                                .visual_config
+                                .clone()
+                                .and_then(|cfg| cfg.watchlist()),
+                        ),
+                    };
+                    tickers.iter().for_each(|ti| watchlist.add_ticker(*ti));
+
+                    (Content::Watchlist(watchlist), vec![])
+                }
+                ContentKind::HeatGrid => match std::mem::take(&mut self.content) {
+                    Content::HeatGrid(grid) => (Content::HeatGrid(grid), vec![]),
+                    _ => {
+                        let config = self
+                            .settings
+                            .visual_config
+                            .clone()
+                            .and_then(|cfg| cfg.heatgrid());
+                        (Content::HeatGrid(HeatGrid::new(config)), vec![])
+                    }
+                },
+                ContentKind::Journal => match std::mem::take(&mut self.content) {
+                    Content::Journal(journal) => (Content::Journal(journal), vec![]),
+                    _ => (Content::Journal(Journal::new()), vec![]),
+                },
                 ContentKind::Starter => unreachable!(),
             }
         };
 
         self.content = content;
         self.streams = ResolvedStream::Ready(streams.clone());
+        self.sync_pinned_levels();
+        self.sync_drawings();
+        self.sync_source_switches();
+        self.sync_colors();
 
         streams
     }
@@ -335,6 +988,24 @@ impl State {
         }
     }
 
+    pub fn insert_hist_aggr_oi(
+        &mut self,
+        req_id: Option<uuid::Uuid>,
+        oi: &[exchange::composite::AggregatedOpenInterest],
+    ) {
+        match &mut self.content {
+            Content::Kline { chart, .. } => {
+                let Some(chart) = chart else {
+                    panic!("Kline chart wasn't initialized when inserting open interest");
+                };
+                chart.insert_aggregated_oi(req_id, oi);
+            }
+            _ => {
+                log::error!("pane content not candlestick");
+            }
+        }
+    }
+
     pub fn insert_hist_klines(
         &mut self,
         req_id: Option<uuid::Uuid>,
@@ -364,7 +1035,7 @@ impl State {
                     let (raw_trades, tick_size) = (chart.raw_trades(), chart.tick_size());
                     let layout = chart.chart_layout();
 
-                    *chart = KlineChart::new(
+                    **chart = KlineChart::new(
                         layout,
                         Basis::Time(timeframe),
                         tick_size,
@@ -373,7 +1044,12 @@ impl State {
                         indicators,
                         ticker_info,
                         chart.kind(),
+                        Some(chart.visual_config()),
                     );
+                    chart.set_alerts(&self.settings.alerts);
+                    chart.set_moving_averages(&self.settings.moving_averages);
+                    chart.set_source_switches(&self.settings.source_switches);
+                    chart.set_colors(self.settings.colors);
                 }
             }
             Content::Comparison(chart) => {
@@ -405,6 +1081,23 @@ impl State {
         }
     }
 
+    /// Custom title if one was set, otherwise the generated one
+    pub fn title(&self) -> String {
+        self.settings
+            .custom_title
+            .clone()
+            .unwrap_or_else(|| self.generated_title())
+    }
+
+    /// Title built from the streams and the basis, regardless of a custom one
+    fn generated_title(&self) -> String {
+        auto_title(
+            self.content.kind(),
+            self.streams.first_ticker(),
+            self.settings.selected_basis,
+        )
+    }
+
     fn has_stream(&self) -> bool {
         match &self.streams {
             ResolvedStream::Ready(streams) => !streams.is_empty(),
@@ -422,6 +1115,7 @@ impl State {
         main_window: &'a Window,
         timezone: UserTimezone,
         tickers_table: &'a TickersTable,
+        synced_view_groups: &'a [LinkGroup],
     ) -> pane_grid::Content<'a, Message, Theme, Renderer> {
         let mut stream_info_element = if Content::Starter == self.content {
             row![]
@@ -431,6 +1125,10 @@ impl State {
             })]
         };
 
+        if let Some(title) = &self.settings.custom_title {
+            stream_info_element = stream_info_element.push(text(title.clone()).size(13));
+        }
+
         if let Some(kind) = self.stream_pair_kind() {
             let (base_ti, extra) = match kind {
                 StreamPairKind::MultiSource(list) => (list[0], list.len().saturating_sub(1)),
@@ -468,6 +1166,35 @@ impl State {
                 .padding([4, 10]);
 
             stream_info_element = stream_info_element.push(tickers_list_btn);
+
+            if let Some(funding) = self.funding_label() {
+                stream_info_element = stream_info_element.push(text(funding).size(12));
+            }
+        } else if let Content::Watchlist(watchlist) = &self.content {
+            let label = match watchlist.tickers().len() {
+                0 => "Add tickers".to_string(),
+                1 => "1 ticker".to_string(),
+                n => format!("{n} tickers"),
+            };
+
+            let tickers_list_btn = button(text(label).size(13))
+                .on_press(Message::PaneEvent(
+                    id,
+                    Event::ShowModal(Modal::MiniTickersList(MiniPanel::new())),
+                ))
+                .style(|theme, status| {
+                    style::button::modifier(
+                        theme,
+                        status,
+                        !matches!(self.modal, Some(Modal::MiniTickersList(_))),
+                    )
+                })
+                .padding([4, 10]);
+
+            stream_info_element = stream_info_element.push(tickers_list_btn);
+        } else if let Content::HeatGrid(grid) = &self.content {
+            stream_info_element =
+                stream_info_element.push(text(grid.config.exchange.to_string()).size(13));
         } else if !matches!(self.content, Content::Starter) && !self.has_stream() {
             let content = row![text("Choose a ticker").size(13)]
                 .align_y(Alignment::Center)
@@ -553,6 +1280,7 @@ impl State {
                     || column![].into(),
                     None,
                     tickers_table,
+                    synced_view_groups,
                 )
             }
             Content::Comparison(chart) => {
@@ -572,7 +1300,7 @@ impl State {
                         Message::PaneEvent(id, Event::ComparisonChartInteraction(message))
                     });
 
-                    let settings_modal = || comparison_cfg_view(id, c);
+                    let settings_modal = || comparison_cfg_view(id, c, self.has_unsaved_settings());
 
                     self.compose_stack_view(
                         base,
@@ -582,6 +1310,7 @@ impl State {
                         settings_modal,
                         Some(c.selected_tickers()),
                         tickers_table,
+                        synced_view_groups,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::ComparisonChart);
@@ -593,6 +1322,7 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 }
             }
@@ -602,8 +1332,13 @@ impl State {
                         Message::PaneEvent(id, Event::PanelInteraction(message))
                     });
 
-                    let settings_modal =
-                        || modal::pane::settings::timesales_cfg_view(panel.config, id);
+                    let settings_modal = || {
+                        modal::pane::settings::timesales_cfg_view(
+                            panel.config,
+                            id,
+                            self.has_unsaved_settings(),
+                        )
+                    };
 
                     self.compose_stack_view(
                         base,
@@ -613,6 +1348,7 @@ impl State {
                         settings_modal,
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::TimeAndSales);
@@ -624,9 +1360,98 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 }
             }
+            Content::Watchlist(watchlist) => {
+                let base = watchlist.view().map(move |message| {
+                    Message::PaneEvent(id, Event::WatchlistInteraction(message))
+                });
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    Some(watchlist.tickers()),
+                    tickers_table,
+                    synced_view_groups,
+                )
+            }
+            Content::DepthInspector(inspector) => {
+                let base = if let Some(inspector) = inspector {
+                    inspector.view().map(move |message| {
+                        Message::PaneEvent(id, Event::DepthInspectorInteraction(message))
+                    })
+                } else {
+                    uninitialized_base(ContentKind::DepthInspector)
+                };
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                    synced_view_groups,
+                )
+            }
+            Content::TradeSizes(panel) => {
+                let base = if let Some(panel) = panel {
+                    panel.view().map(move |message| {
+                        Message::PaneEvent(id, Event::TradeSizesInteraction(message))
+                    })
+                } else {
+                    uninitialized_base(ContentKind::TradeSizes)
+                };
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                    synced_view_groups,
+                )
+            }
+            Content::HeatGrid(grid) => {
+                let base = grid.view().map(move |message| {
+                    Message::PaneEvent(id, Event::HeatGridInteraction(message))
+                });
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                    synced_view_groups,
+                )
+            }
+            Content::Journal(journal) => {
+                let base = journal
+                    .view(timezone)
+                    .map(move |message| Message::PaneEvent(id, Event::JournalInteraction(message)));
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                    synced_view_groups,
+                )
+            }
             Content::Ladder(panel) => {
                 if let Some(panel) = panel {
                     let basis = self
@@ -651,12 +1476,17 @@ impl State {
 
                     stream_info_element = stream_info_element.push(modifiers);
 
-                    let base = panel::view(panel, timezone).map(move |message| {
+                    let base = panel.view(timezone).map(move |message| {
                         Message::PaneEvent(id, Event::PanelInteraction(message))
                     });
 
-                    let settings_modal =
-                        || modal::pane::settings::ladder_cfg_view(panel.config, id);
+                    let settings_modal = || {
+                        modal::pane::settings::ladder_cfg_view(
+                            panel.config,
+                            id,
+                            self.has_unsaved_settings(),
+                        )
+                    };
 
                     self.compose_stack_view(
                         base,
@@ -666,6 +1496,7 @@ impl State {
                         settings_modal,
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::Ladder);
@@ -677,6 +1508,67 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        synced_view_groups,
+                    )
+                }
+            }
+            Content::VolumeProfile(panel) => {
+                if let Some(panel) = panel {
+                    let basis = self
+                        .settings
+                        .selected_basis
+                        .unwrap_or(Basis::default_heatmap_time(self.stream_pair()));
+                    let tick_multiply = self.settings.tick_multiply.unwrap_or(TickMultiplier(50));
+
+                    let kind = ModifierKind::Orderbook(basis, tick_multiply);
+
+                    let base_ticksize = tick_multiply.base(panel.tick_size());
+                    let exchange = self.stream_pair().map(|ti| ti.ticker.exchange);
+
+                    let modifiers = ticksize_modifier(
+                        id,
+                        base_ticksize,
+                        tick_multiply,
+                        modifier,
+                        kind,
+                        exchange,
+                    );
+
+                    stream_info_element = stream_info_element.push(modifiers);
+
+                    let base = panel::view(panel, timezone).map(move |message| {
+                        Message::PaneEvent(id, Event::PanelInteraction(message))
+                    });
+
+                    let settings_modal = || {
+                        modal::pane::settings::volume_profile_cfg_view(
+                            panel.config,
+                            id,
+                            self.has_unsaved_settings(),
+                        )
+                    };
+
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        settings_modal,
+                        None,
+                        tickers_table,
+                        synced_view_groups,
+                    )
+                } else {
+                    let base = uninitialized_base(ContentKind::VolumeProfile);
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        || column![].into(),
+                        None,
+                        tickers_table,
+                        synced_view_groups,
                     )
                 }
             }
@@ -711,23 +1603,32 @@ impl State {
 
                     stream_info_element = stream_info_element.push(modifiers);
 
-                    let base = chart::view(chart, indicators, timezone).map(move |message| {
-                        Message::PaneEvent(id, Event::ChartInteraction(message))
-                    });
+                    let mut base =
+                        chart::view(&**chart, indicators, timezone).map(move |message| {
+                            Message::PaneEvent(id, Event::ChartInteraction(message))
+                        });
+                    if let Some(scrubber) = chart.scrubber() {
+                        let scrubber = scrubber
+                            .map(move |time| Message::PaneEvent(id, Event::HeatmapScrubbed(time)));
+                        base = column![base, scrubber].into();
+                    }
                     let settings_modal = || {
                         heatmap_cfg_view(
                             chart.visual_config(),
+                            chart.chart_layout().navigation,
+                            chart.chart_layout().watermark,
                             id,
                             chart.study_configurator(),
                             &chart.studies,
                             basis,
+                            self.settings.colors,
+                            self.has_unsaved_settings(),
                         )
                     };
 
                     let indicator_modal = if self.modal == Some(Modal::Indicators) {
                         Some(modal::indicators::view(
                             id,
-                            self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
                         ))
@@ -743,6 +1644,7 @@ impl State {
                         settings_modal,
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 } else {
                     let base = uninitialized_base(ContentKind::HeatmapChart);
@@ -754,6 +1656,7 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 }
             }
@@ -807,28 +1710,43 @@ impl State {
                         }
                     }
 
-                    let base = chart::view(chart, indicators, timezone).map(move |message| {
+                    let base = chart::view(&**chart, indicators, timezone).map(move |message| {
                         Message::PaneEvent(id, Event::ChartInteraction(message))
                     });
                     let settings_modal = || {
                         kline_cfg_view(
                             chart.study_configurator(),
-                            data::chart::kline::Config {},
+                            chart.visual_config(),
+                            &self.settings.moving_averages,
+                            chart.chart_layout().navigation,
+                            chart.chart_layout().watermark,
                             chart_kind,
                             id,
                             chart.basis(),
+                            chart.trading_config(),
+                            self.settings.colors,
+                            self.has_unsaved_settings(),
                         )
                     };
 
-                    let indicator_modal = if self.modal == Some(Modal::Indicators) {
-                        Some(modal::indicators::view(
+                    let indicator_modal = match self.modal {
+                        Some(Modal::Indicators) => Some(modal::indicators::kline_view(
                             id,
-                            self,
+                            chart,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
-                        ))
-                    } else {
-                        None
+                        )),
+                        Some(Modal::IndicatorParams(instance_id)) => indicators
+                            .iter()
+                            .find(|i| i.id == instance_id)
+                            .map(|instance| {
+                                modal::indicators::params_view(
+                                    id,
+                                    *instance,
+                                    chart.visual_config().indicator_params,
+                                )
+                            }),
+                        _ => None,
                     };
 
                     self.compose_stack_view(
@@ -839,6 +1757,7 @@ impl State {
                         settings_modal,
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 } else {
                     let content_kind = match chart_kind {
@@ -856,6 +1775,7 @@ impl State {
                         || column![].into(),
                         None,
                         tickers_table,
+                        synced_view_groups,
                     )
                 }
             }
@@ -875,10 +1795,26 @@ impl State {
             Status::Stale(msg) => {
                 stream_info_element = stream_info_element.push(text(msg));
             }
+            Status::MarketClosed(reopens_at) => {
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                stream_info_element = stream_info_element.push(text(format!(
+                    "Market closed, reopens in {}",
+                    exchange::schedule::format_countdown(reopens_at.saturating_sub(now))
+                )));
+            }
+            Status::Reconnecting { attempt, retry_at } => {
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                let secs = retry_at.saturating_sub(now).div_ceil(1000);
+                stream_info_element = stream_info_element.push(text(if secs == 0 {
+                    format!("Reconnecting... attempt {attempt}")
+                } else {
+                    format!("Connection lost, retry {attempt} in {secs}s")
+                }));
+            }
             Status::Ready => {}
         }
 
-        let content = pane_grid::Content::new(body)
+        let content = pane_grid::Content::new(container(body).id(self.capture_id()))
             .style(move |theme| style::pane_background(theme, is_focused));
 
         let controls = {
@@ -930,38 +1866,293 @@ impl State {
             Event::ShowModal(requested_modal) => {
                 return self.show_modal_with_focus(requested_modal);
             }
-            Event::HideModal => {
+            Event::HideModal => {
+                self.modal = None;
+                self.settings_snapshot = None;
+            }
+            Event::ContentSelected(kind) => {
+                self.content = Content::placeholder(kind);
+
+                if matches!(kind, ContentKind::HeatGrid | ContentKind::Journal) {
+                    // no ticker of their own, nothing to choose
+                    self.streams = ResolvedStream::Ready(vec![]);
+                } else if !matches!(kind, ContentKind::Starter) {
+                    self.streams = if kind == ContentKind::Watchlist {
+                        ResolvedStream::Ready(vec![])
+                    } else {
+                        ResolvedStream::waiting(vec![])
+                    };
+                    let modal = Modal::MiniTickersList(MiniPanel::new());
+
+                    if let Some(effect) = self.show_modal_with_focus(modal) {
+                        return Some(effect);
+                    }
+                }
+            }
+            Event::ChartInteraction(
+                msg @ (super::chart::Message::DrawingPlaced(..)
+                | super::chart::Message::AnchorDragged(..)
+                | super::chart::Message::DrawingRemoved(_)),
+            ) => self.update_drawings(msg),
+            Event::ChartInteraction(super::chart::Message::PriceScaleMenu(y)) => {
+                self.modal = Some(Modal::PriceScaleMenu { y });
+            }
+            Event::ChartInteraction(
+                msg @ (super::chart::Message::OrderPlaced(_)
+                | super::chart::Message::OrderMoved(..)
+                | super::chart::Message::OrderCancelled(_)),
+            ) => {
+                if let Some(command) = msg.paper_command()
+                    && let Some(ticker_info) = self.stream_pair()
+                {
+                    return Some(Effect::PaperTrade(ticker_info, command));
+                }
+            }
+            Event::ChartInteraction(msg) => match &mut self.content {
+                Content::Heatmap { chart: Some(c), .. } => {
+                    if let super::chart::Message::PriceMenu(position, price) = msg {
+                        self.modal = Some(Modal::PriceMenu { price, position });
+                    } else {
+                        super::chart::update(&mut **c, &msg);
+                    }
+
+                    if let super::chart::Message::BarHovered(bar) = msg
+                        && let Some(group) = self.link_group
+                    {
+                        let span = bar.and_then(|bar| c.bar_time_range(bar));
+                        return Some(Effect::SyncHoveredBar(group, span));
+                    }
+
+                    if msg.moves_time_axis()
+                        && let Some(group) = self.link_group
+                        && let Some(window) = c.time_window()
+                    {
+                        return Some(Effect::SyncTimeWindow(group, window));
+                    }
+                }
+                Content::Kline { chart: Some(c), .. } => {
+                    if let super::chart::Message::PriceMenu(position, price) = msg {
+                        self.modal = Some(Modal::PriceMenu { price, position });
+                    } else {
+                        super::chart::update(&mut **c, &msg);
+                    }
+
+                    if let super::chart::Message::BarHovered(bar) = msg
+                        && let Some(group) = self.link_group
+                    {
+                        let span = bar.and_then(|bar| c.bar_time_range(bar));
+                        return Some(Effect::SyncHoveredBar(group, span));
+                    }
+
+                    if msg.moves_time_axis()
+                        && let Some(group) = self.link_group
+                        && let Some(window) = c.time_window()
+                    {
+                        return Some(Effect::SyncTimeWindow(group, window));
+                    }
+                }
+                _ => {}
+            },
+            Event::WatchlistInteraction(msg) => {
+                if let Content::Watchlist(watchlist) = &mut self.content {
+                    match watchlist.update(msg) {
+                        Some(super::panel::watchlist::Action::Open(ticker_info)) => {
+                            return Some(Effect::OpenTicker(ticker_info));
+                        }
+                        Some(super::panel::watchlist::Action::ConfigChanged) => {
+                            self.settings.visual_config =
+                                Some(VisualConfig::Watchlist(watchlist.config.clone()));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Event::HeatGridInteraction(msg) => {
+                if let Content::HeatGrid(grid) = &mut self.content {
+                    match grid.update(msg) {
+                        Some(super::panel::heatgrid::Action::Open(ticker_info)) => {
+                            return Some(Effect::OpenTicker(ticker_info));
+                        }
+                        Some(super::panel::heatgrid::Action::ConfigChanged) => {
+                            self.settings.visual_config = Some(VisualConfig::HeatGrid(grid.config));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Event::JournalInteraction(msg) => {
+                if let Content::Journal(journal) = &mut self.content
+                    && let Some(super::panel::journal::Action::Export) = journal.update(msg)
+                {
+                    return Some(Effect::ExportJournal);
+                }
+            }
+            Event::DepthInspectorInteraction(msg) => {
+                if let Content::DepthInspector(Some(inspector)) = &mut self.content
+                    && let Some(super::panel::depth_inspector::Action::ConfigChanged) =
+                        inspector.update(msg)
+                {
+                    self.settings.visual_config =
+                        Some(VisualConfig::DepthInspector(inspector.config));
+                }
+            }
+            Event::TradeSizesInteraction(msg) => {
+                if let Content::TradeSizes(Some(panel)) = &mut self.content {
+                    match panel.update(msg) {
+                        Some(super::panel::trade_sizes::Action::FilterTape(ticker_info, range)) => {
+                            return Some(Effect::FilterTape(ticker_info, range));
+                        }
+                        Some(super::panel::trade_sizes::Action::ConfigChanged) => {
+                            self.settings.visual_config =
+                                Some(VisualConfig::TradeSizes(panel.config));
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Event::PanelInteraction(msg) => match &mut self.content {
+                Content::Ladder(Some(p)) => match msg {
+                    super::panel::Message::PriceMenu(position, price) => {
+                        self.modal = Some(Modal::PriceMenu { price, position });
+                    }
+                    _ => super::panel::update(&mut **p, msg),
+                },
+                Content::TimeAndSales(Some(p)) => match msg {
+                    super::panel::Message::PrintClicked(time) => {
+                        return self
+                            .link_group
+                            .map(|group| Effect::CenterOnTime(group, time));
+                    }
+                    _ => super::panel::update(p, msg),
+                },
+                Content::VolumeProfile(Some(p)) => super::panel::update(p, msg),
+                _ => {}
+            },
+            Event::ToggleIndicator(ind) => {
+                self.content.toggle_indicator(ind);
+            }
+            Event::RemoveIndicator(id) => {
+                self.content.remove_indicator(id);
+                if self.modal == Some(Modal::IndicatorParams(id)) {
+                    self.modal = Some(Modal::Indicators);
+                }
+            }
+            Event::IndicatorParamsChanged(id, params) => {
+                self.content.set_indicator_params(id, params);
+            }
+            Event::ToggleSupportResistance => {
+                if let Content::Kline {
+                    chart: Some(c),
+                    layout,
+                    ..
+                } = &mut self.content
+                {
+                    c.toggle_support_resistance();
+                    *layout = c.chart_layout();
+                }
+            }
+            Event::ToggleLargeOrders => {
+                if let Content::Kline {
+                    chart: Some(c),
+                    layout,
+                    ..
+                } = &mut self.content
+                {
+                    c.toggle_large_orders();
+                    *layout = c.chart_layout();
+                }
+            }
+            Event::LargeOrderThresholdChanged(threshold) => {
+                if let Content::Kline {
+                    chart: Some(c),
+                    layout,
+                    ..
+                } = &mut self.content
+                {
+                    c.set_large_order_threshold(threshold);
+                    *layout = c.chart_layout();
+                }
+            }
+            Event::Alerts(action) => self.update_alerts(action),
+            Event::TogglePin(price) => {
+                if let Some(ticker_info) = self.stream_pair() {
+                    self.settings
+                        .pinned_levels
+                        .toggle(ticker_info.ticker, price);
+                    self.sync_pinned_levels();
+                }
                 self.modal = None;
             }
-            Event::ContentSelected(kind) => {
-                self.content = Content::placeholder(kind);
-
-                if !matches!(kind, ContentKind::Starter) {
-                    self.streams = ResolvedStream::waiting(vec![]);
-                    let modal = Modal::MiniTickersList(MiniPanel::new());
-
-                    if let Some(effect) = self.show_modal_with_focus(modal) {
-                        return Some(effect);
+            Event::DrawingToolSelected(tool) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    let next = (c.drawing_tool() != Some(tool)).then_some(tool);
+                    c.set_drawing_tool(next);
+                }
+                self.modal = None;
+            }
+            Event::PriceScale(action) => {
+                match &mut self.content {
+                    Content::Kline { chart: Some(c), .. } => {
+                        super::chart::update(&mut **c, &super::chart::Message::PriceScale(action));
+                    }
+                    Content::Heatmap { chart: Some(c), .. } => {
+                        super::chart::update(&mut **c, &super::chart::Message::PriceScale(action));
                     }
+                    Content::Comparison(Some(c)) => c.update_price_scale(action),
+                    _ => {}
                 }
+                self.modal = None;
             }
-            Event::ChartInteraction(msg) => match &mut self.content {
-                Content::Heatmap { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+            Event::TitleChanged(title) => {
+                let title: String = title.chars().take(MAX_TITLE_LEN).collect();
+                self.settings.custom_title = (!title.trim().is_empty()).then_some(title);
+            }
+            Event::FailoverChanged(failover) => {
+                self.settings.failover = failover;
+            }
+            Event::MergeVenueToggled(venue, enabled) => {
+                self.settings.merge_venues.retain(|merged| *merged != venue);
+                if enabled {
+                    self.settings.merge_venues.push(venue);
                 }
-                Content::Kline { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+            }
+            Event::HeatmapScrubbed(time) => {
+                if let Content::Heatmap { chart: Some(c), .. } = &mut self.content {
+                    c.scrub_to(time);
+                }
+            }
+            Event::ExportImage => {
+                self.modal = None;
+                return Some(Effect::CaptureImage(self.capture_id(), self.title()));
+            }
+            Event::ColorChanged(role, color) => {
+                self.settings.colors.set(role, color);
+                self.sync_colors();
+            }
+            Event::FibLevelToggled(level) => {
+                self.settings.drawings.toggle_fib_level(level);
+                self.sync_drawings();
+            }
+            Event::ClearDrawings => {
+                if let Some(ticker_info) = self.stream_pair() {
+                    self.settings.drawings.clear(ticker_info.ticker);
+                    self.sync_drawings();
                 }
-                _ => {}
-            },
-            Event::PanelInteraction(msg) => match &mut self.content {
-                Content::Ladder(Some(p)) => super::panel::update(p, msg),
-                Content::TimeAndSales(Some(p)) => super::panel::update(p, msg),
-                _ => {}
-            },
-            Event::ToggleIndicator(ind) => {
-                self.content.toggle_indicator(ind);
             }
+            Event::PaperTrade(command) => {
+                self.modal = None;
+                if let Some(ticker_info) = self.stream_pair() {
+                    return Some(Effect::PaperTrade(ticker_info, command));
+                }
+            }
+            Event::ClearPins => {
+                if let Some(ticker_info) = self.stream_pair() {
+                    self.settings.pinned_levels.clear(ticker_info.ticker);
+                    self.sync_pinned_levels();
+                }
+                self.modal = None;
+            }
+            Event::MovingAverages(action) => self.update_moving_averages(action),
             Event::DeleteNotification(idx) => {
                 if idx < self.notifications.len() {
                     self.notifications.remove(idx);
@@ -1006,6 +2197,12 @@ impl State {
                     }
                 }
             },
+            Event::RevertSettings => {
+                let effect = self.revert_settings();
+                self.settings_snapshot = Some(self.snapshot_settings());
+
+                return effect;
+            }
             Event::StreamModifierChanged(message) => {
                 if let Some(Modal::StreamModifier(mut modifier)) = self.modal.take() {
                     let mut effect: Option<Effect> = None;
@@ -1017,47 +2214,12 @@ impl State {
                             }
                             modal::stream::Action::TicksizeSelected(tm) => {
                                 modifier.update_kind_with_multiplier(tm);
-                                self.settings.tick_multiply = Some(tm);
-
-                                if let Some(ticker) = self.stream_pair() {
-                                    match &mut self.content {
-                                        Content::Kline { chart: Some(c), .. } => {
-                                            c.change_tick_size(
-                                                tm.multiply_with_min_tick_size(ticker),
-                                            );
-                                            c.reset_request_handler();
-                                        }
-                                        Content::Heatmap { chart: Some(c), .. } => {
-                                            c.change_tick_size(
-                                                tm.multiply_with_min_tick_size(ticker),
-                                            );
-                                        }
-                                        Content::Ladder(Some(p)) => {
-                                            p.set_tick_size(tm.multiply_with_min_tick_size(ticker));
-                                        }
-                                        _ => {}
-                                    }
-                                }
-
-                                let is_client = self
-                                    .stream_pair()
-                                    .map(|ti| ti.exchange().is_depth_client_aggr())
-                                    .unwrap_or(false);
-
-                                if let Some(mut it) = self.streams.ready_iter_mut() {
-                                    for s in &mut it {
-                                        if let StreamKind::DepthAndTrades { depth_aggr, .. } = s {
-                                            *depth_aggr = if is_client {
-                                                StreamTicksize::Client
-                                            } else {
-                                                StreamTicksize::ServerSide(tm)
-                                            };
-                                        }
-                                    }
-                                }
-                                if !is_client {
-                                    effect = Some(Effect::RefreshStreams);
-                                }
+                                effect = self.set_tick_multiplier(tm);
+                            }
+                            modal::stream::Action::DepthRateSelected(rate) => {
+                                modifier = modifier.with_depth_rate(rate);
+                                self.settings.depth_rate = rate;
+                                effect = Some(Effect::RefreshStreams);
                             }
                             modal::stream::Action::BasisSelected(new_basis) => {
                                 modifier.update_kind_with_basis(new_basis);
@@ -1140,7 +2302,9 @@ impl State {
                                                         effect = Some(Effect::RequestFetch(fetch));
                                                     }
                                                 }
-                                                Basis::Tick(_) => {
+                                                Basis::Tick(_)
+                                                | Basis::Volume(_)
+                                                | Basis::Range(_) => {
                                                     let depth_aggr = if base_ticker
                                                         .exchange()
                                                         .is_depth_client_aggr()
@@ -1164,7 +2328,6 @@ impl State {
                                                     c.set_basis(new_basis);
                                                     effect = Some(Effect::RefreshStreams);
                                                 }
-                                                Basis::Volume(_) | Basis::Range(_) => todo!(),
                                             }
                                         }
                                     }
@@ -1217,6 +2380,9 @@ impl State {
                         super::chart::comparison::Action::OpenSeriesEditor => {
                             self.modal = Some(Modal::Settings);
                         }
+                        super::chart::comparison::Action::OpenPriceScaleMenu(y) => {
+                            self.modal = Some(Modal::PriceScaleMenu { y });
+                        }
                         super::chart::comparison::Action::RemoveSeries(ti) => {
                             let rebuilt = chart.remove_ticker(&ti);
                             self.streams = ResolvedStream::Ready(rebuilt);
@@ -1233,6 +2399,22 @@ impl State {
                     self.modal = Some(Modal::MiniTickersList(mini_panel.clone()));
 
                     let crate::modal::pane::mini_tickers_list::Action::RowSelected(sel) = action;
+
+                    if let Content::Watchlist(watchlist) = &mut self.content {
+                        match sel {
+                            crate::modal::pane::mini_tickers_list::RowSelection::Add(ti)
+                            | crate::modal::pane::mini_tickers_list::RowSelection::Switch(ti) => {
+                                watchlist.add_ticker(ti);
+                            }
+                            crate::modal::pane::mini_tickers_list::RowSelection::Remove(ti) => {
+                                watchlist.remove_ticker(ti.ticker);
+                            }
+                        }
+                        self.settings.visual_config =
+                            Some(VisualConfig::Watchlist(watchlist.config.clone()));
+                        return None;
+                    }
+
                     match sel {
                         crate::modal::pane::mini_tickers_list::RowSelection::Add(ti) => {
                             if let Content::Comparison(chart) = &mut self.content
@@ -1313,6 +2495,37 @@ impl State {
                 modal_btn_style(Modal::Indicators),
             ));
         }
+        if !treat_as_starter && matches!(&self.content, Content::Kline { .. }) {
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::SpeakerHigh, 12),
+                show_modal(Modal::Alerts),
+                Some("Alerts"),
+                tooltip_pos,
+                modal_btn_style(Modal::Alerts),
+            ));
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::Edit, 12),
+                show_modal(Modal::Drawings),
+                Some("Drawing tools"),
+                tooltip_pos,
+                modal_btn_style(Modal::Drawings),
+            ));
+        }
+
+        if !treat_as_starter
+            && matches!(
+                &self.content,
+                Content::Heatmap { .. } | Content::Kline { .. } | Content::Comparison(_)
+            )
+        {
+            buttons = buttons.push(button_with_tooltip(
+                icon_text(Icon::ExternalLink, 12),
+                Message::PaneEvent(pane, Event::ExportImage),
+                Some("Export image"),
+                tooltip_pos,
+                control_btn_style(false),
+            ));
+        }
 
         if is_popout {
             buttons = buttons.push(button_with_tooltip(
@@ -1372,6 +2585,7 @@ impl State {
         settings_modal: F,
         selected_tickers: Option<&'a [TickerInfo]>,
         tickers_table: &'a TickersTable,
+        synced_view_groups: &'a [LinkGroup],
     ) -> Element<'a, Message>
     where
         F: FnOnce() -> Element<'a, Message>,
@@ -1386,7 +2600,20 @@ impl State {
 
         match &self.modal {
             Some(Modal::LinkGroup) => {
-                let content = link_group_modal(pane, self.link_group);
+                let is_view_synced = self
+                    .link_group
+                    .is_some_and(|group| synced_view_groups.contains(&group));
+                let content = link_group_modal(
+                    pane,
+                    self.link_group,
+                    is_view_synced,
+                    self.settings.custom_title.as_deref().unwrap_or_default(),
+                    self.generated_title(),
+                    self.stream_pair().map(|info| info.exchange()),
+                    self.settings.failover,
+                    self.primary_depth_stream().map(|info| info.exchange()),
+                    &self.settings.merge_venues,
+                );
 
                 stack_modal(
                     base,
@@ -1433,13 +2660,90 @@ impl State {
                 padding::right(12).left(12),
                 Alignment::End,
             ),
-            Some(Modal::Indicators) => stack_modal(
+            Some(Modal::Indicators | Modal::IndicatorParams(_)) => stack_modal(
                 base,
                 indicator_modal.unwrap_or_else(|| column![].into()),
                 on_blur,
                 padding::right(12).left(12),
                 Alignment::End,
             ),
+            Some(Modal::Alerts) => stack_modal(
+                base,
+                modal::pane::alerts::view(
+                    pane,
+                    &self.settings.alerts,
+                    self.stream_pair().map(|info| info.ticker),
+                    &self.alert_drafts,
+                    self.composite_builder.as_ref(),
+                ),
+                on_blur,
+                padding::right(12).left(12),
+                Alignment::End,
+            ),
+            Some(Modal::Drawings) => {
+                let (active, count) = match (&self.content, self.stream_pair()) {
+                    (Content::Kline { chart: Some(c), .. }, Some(ticker_info)) => (
+                        c.drawing_tool(),
+                        self.settings
+                            .drawings
+                            .for_ticker(ticker_info.ticker)
+                            .count(),
+                    ),
+                    _ => (None, 0),
+                };
+
+                stack_modal(
+                    base,
+                    modal::pane::drawings::view(
+                        pane,
+                        active,
+                        count,
+                        &self.settings.drawings.fib_levels,
+                    ),
+                    on_blur,
+                    padding::right(12).left(12),
+                    Alignment::End,
+                )
+            }
+            Some(Modal::PriceScaleMenu { y }) => {
+                let (price_scale, supports_log) = match &self.content {
+                    Content::Kline { chart: Some(c), .. } => (c.chart_layout().price_scale, true),
+                    Content::Heatmap { chart: Some(c), .. } => {
+                        (c.chart_layout().price_scale, false)
+                    }
+                    Content::Comparison(Some(c)) => (c.config.price_scale, true),
+                    _ => return base,
+                };
+
+                stack_modal(
+                    base,
+                    modal::pane::price_scale_menu::view(pane, price_scale, supports_log),
+                    on_blur,
+                    padding::top(*y).right(8),
+                    Alignment::End,
+                )
+            }
+            Some(Modal::PriceMenu { price, position }) => match self.stream_pair() {
+                Some(ticker_info) => {
+                    let pins = &self.settings.pinned_levels;
+                    let ticker = ticker_info.ticker;
+
+                    stack_modal(
+                        base,
+                        modal::pane::price_menu::view(
+                            pane,
+                            *price,
+                            ticker_info.min_ticksize,
+                            pins.is_pinned(ticker, *price),
+                            pins.for_ticker(ticker).next().is_some(),
+                        ),
+                        on_blur,
+                        padding::top(position.y).left(position.x),
+                        Alignment::Start,
+                    )
+                }
+                None => base,
+            },
             Some(Modal::Controls) => stack_modal(
                 base,
                 if let Some(controls) = compact_controls {
@@ -1460,6 +2764,15 @@ impl State {
     }
 
     fn show_modal_with_focus(&mut self, requested_modal: Modal) -> Option<Effect> {
+        let requested_modal = match requested_modal {
+            Modal::StreamModifier(modifier)
+                if matches!(self.content, Content::Heatmap { .. } | Content::Ladder(_)) =>
+            {
+                Modal::StreamModifier(modifier.with_depth_rate(self.settings.depth_rate))
+            }
+            requested_modal => requested_modal,
+        };
+
         let should_toggle_close = match (&self.modal, &requested_modal) {
             (Some(Modal::StreamModifier(open)), Modal::StreamModifier(req)) => {
                 open.view_mode == req.view_mode
@@ -1470,6 +2783,7 @@ impl State {
 
         if should_toggle_close {
             self.modal = None;
+            self.settings_snapshot = None;
             return None;
         }
 
@@ -1478,10 +2792,113 @@ impl State {
             _ => None,
         };
 
+        if requested_modal == Modal::Settings && self.settings_snapshot.is_none() {
+            self.settings_snapshot = Some(self.snapshot_settings());
+        }
+
         self.modal = Some(requested_modal);
         focus_widget_id.map(Effect::FocusWidget)
     }
 
+    fn snapshot_settings(&self) -> SettingsSnapshot {
+        SettingsSnapshot {
+            visual_config: self.content.visual_config(),
+            studies: self.content.studies(),
+            chart_kind: self.content.chart_kind(),
+            tick_multiply: self.settings.tick_multiply,
+            trading_config: self.content.trading_config(),
+        }
+    }
+
+    pub fn has_unsaved_settings(&self) -> bool {
+        self.settings_snapshot
+            .as_ref()
+            .is_some_and(|snapshot| *snapshot != self.snapshot_settings())
+    }
+
+    fn revert_settings(&mut self) -> Option<Effect> {
+        let snapshot = self.settings_snapshot.clone()?;
+
+        if let Some(cfg) = snapshot.visual_config {
+            self.settings.visual_config = Some(cfg.clone());
+            self.content.change_visual_config(cfg);
+        }
+        if let Some(studies) = snapshot.studies {
+            self.content.update_studies(studies);
+        }
+        if let Some(data::chart::KlineChartKind::Footprint {
+            clusters, scaling, ..
+        }) = snapshot.chart_kind
+            && let Content::Kline { chart, kind, .. } = &mut self.content
+            && let Some(c) = chart
+        {
+            c.set_cluster_kind(clusters);
+            c.set_cluster_scaling(scaling);
+            *kind = c.kind.clone();
+        }
+        if let Content::Kline {
+            chart: Some(c),
+            layout,
+            ..
+        } = &mut self.content
+            && c.trading_config() != snapshot.trading_config.as_ref()
+        {
+            c.set_trading_config(snapshot.trading_config);
+            *layout = c.chart_layout();
+        }
+
+        match snapshot.tick_multiply {
+            Some(tm) if self.settings.tick_multiply != Some(tm) => self.set_tick_multiplier(tm),
+            _ => None,
+        }
+    }
+
+    fn set_tick_multiplier(&mut self, tm: TickMultiplier) -> Option<Effect> {
+        self.settings.tick_multiply = Some(tm);
+
+        if let Some(ticker) = self.stream_pair() {
+            match &mut self.content {
+                Content::Kline { chart: Some(c), .. } => {
+                    c.change_tick_size(tm.multiply_with_min_tick_size(ticker));
+                    c.reset_request_handler();
+                }
+                Content::Heatmap { chart: Some(c), .. } => {
+                    c.change_tick_size(tm.multiply_with_min_tick_size(ticker));
+                }
+                Content::Ladder(Some(p)) => {
+                    p.set_tick_size(tm.multiply_with_min_tick_size(ticker));
+                }
+                Content::VolumeProfile(Some(p)) => {
+                    p.set_tick_size(tm.multiply_with_min_tick_size(ticker));
+                }
+                _ => {}
+            }
+        }
+
+        let is_client = self
+            .stream_pair()
+            .map(|ti| ti.exchange().is_depth_client_aggr())
+            .unwrap_or(false);
+
+        if let Some(mut it) = self.streams.ready_iter_mut() {
+            for s in &mut it {
+                if let StreamKind::DepthAndTrades { depth_aggr, .. } = s {
+                    *depth_aggr = if is_client {
+                        StreamTicksize::Client
+                    } else {
+                        StreamTicksize::ServerSide(tm)
+                    };
+                }
+            }
+        }
+
+        if is_client {
+            None
+        } else {
+            Some(Effect::RefreshStreams)
+        }
+    }
+
     pub fn invalidate(&mut self, now: Instant) -> Option<Action> {
         match &mut self.content {
             Content::Heatmap { chart, .. } => chart
@@ -1496,7 +2913,20 @@ impl State {
             Content::Ladder(panel) => panel
                 .as_mut()
                 .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
-            Content::Starter => None,
+            Content::VolumeProfile(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::TradeSizes(panel) => {
+                if let Some(panel) = panel {
+                    panel.invalidate(Some(now));
+                }
+                None
+            }
+            Content::Starter
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_)
+            | Content::DepthInspector(_) => None,
             Content::Comparison(chart) => chart
                 .as_mut()
                 .and_then(|c| c.invalidate(Some(now)).map(Action::Chart)),
@@ -1505,7 +2935,10 @@ impl State {
 
     pub fn update_interval(&self) -> Option<u64> {
         match &self.content {
-            Content::Kline { .. } | Content::Comparison(_) => Some(1000),
+            Content::Kline { .. }
+            | Content::Comparison(_)
+            | Content::VolumeProfile(_)
+            | Content::TradeSizes(_) => Some(1000),
             Content::Heatmap { chart, .. } => {
                 if let Some(chart) = chart {
                     chart.basis_interval()
@@ -1514,7 +2947,11 @@ impl State {
                 }
             }
             Content::Ladder(_) | Content::TimeAndSales(_) => Some(100),
-            Content::Starter => None,
+            Content::Starter
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_)
+            | Content::DepthInspector(_) => None,
         }
     }
 
@@ -1522,8 +2959,14 @@ impl State {
         self.content.last_tick()
     }
 
-    pub fn tick(&mut self, now: Instant) -> Option<Action> {
-        let invalidate_interval: Option<u64> = self.update_interval();
+    pub fn tick(&mut self, now: Instant, is_idle: bool) -> Option<Action> {
+        let invalidate_interval: Option<u64> = self.update_interval().map(|interval_ms| {
+            if is_idle && interval_ms > 0 {
+                interval_ms.max(IDLE_UPDATE_INTERVAL_MS)
+            } else {
+                interval_ms
+            }
+        });
         let last_tick: Option<Instant> = self.last_tick();
 
         if let Some(streams) = self.streams.due_streams_to_resolve(now) {
@@ -1534,6 +2977,10 @@ impl State {
             return Some(Action::ResolveContent);
         }
 
+        if let Some((ticker, venues)) = self.merged_feed_due(now) {
+            return Some(Action::ResolveMergedFeed(ticker, venues));
+        }
+
         match (invalidate_interval, last_tick) {
             (Some(interval_ms), Some(previous_tick_time)) => {
                 if interval_ms > 0 {
@@ -1557,6 +3004,33 @@ impl State {
     pub fn unique_id(&self) -> uuid::Uuid {
         self.id
     }
+
+    /// Widget id of the pane body, what an image export captures
+    pub fn capture_id(&self) -> iced::widget::Id {
+        iced::widget::Id::from(format!("pane-body-{}", self.id))
+    }
+
+    pub fn low_contrast_colors(&self, background: iced::Color) -> usize {
+        match &self.content {
+            Content::Comparison(Some(chart)) => chart.low_contrast_series(background).len(),
+            _ => 0,
+        }
+    }
+
+    pub fn remap_low_contrast_colors(&mut self, background: iced::Color) {
+        if let Content::Comparison(Some(chart)) = &mut self.content {
+            let remaps = chart.low_contrast_series(background);
+            if remaps.is_empty() {
+                return;
+            }
+
+            for (ticker_info, color) in remaps {
+                chart.set_series_color(ticker_info, color);
+            }
+            self.settings.visual_config =
+                Some(VisualConfig::Comparison(chart.serializable_config()));
+        }
+    }
 }
 
 impl Default for State {
@@ -1570,6 +3044,13 @@ impl Default for State {
             notifications: vec![],
             status: Status::Ready,
             link_group: None,
+            settings_snapshot: None,
+            funding: None,
+            funding_requested_at: None,
+            last_trade_price: None,
+            alert_drafts: vec![],
+            composite_builder: None,
+            merged_feed: MergedFeed::default(),
         }
     }
 }
@@ -1579,20 +3060,26 @@ pub enum Content {
     #[default]
     Starter,
     Heatmap {
-        chart: Option<HeatmapChart>,
+        chart: Option<Box<HeatmapChart>>,
         indicators: Vec<HeatmapIndicator>,
         layout: data::chart::ViewConfig,
         studies: Vec<data::chart::heatmap::HeatmapStudy>,
     },
     Kline {
-        chart: Option<KlineChart>,
-        indicators: Vec<KlineIndicator>,
+        chart: Option<Box<KlineChart>>,
+        indicators: Vec<KlineInstance>,
         layout: data::chart::ViewConfig,
         kind: data::chart::KlineChartKind,
     },
     TimeAndSales(Option<TimeAndSales>),
-    Ladder(Option<Ladder>),
+    Ladder(Option<Box<Ladder>>),
+    VolumeProfile(Option<VolumeProfile>),
+    DepthInspector(Option<DepthInspector>),
+    TradeSizes(Option<TradeSizes>),
     Comparison(Option<ComparisonChart>),
+    Watchlist(Watchlist),
+    HeatGrid(HeatGrid),
+    Journal(Journal),
 }
 
 impl Content {
@@ -1629,6 +3116,9 @@ impl Content {
                     show_volume_histogram: true,
                     show_price_levels: true,
                     show_market_depth: false,
+                    navigation: Navigation::default(),
+                    watermark: Watermark::default(),
+                    price_scale: PriceScale::default(),
                 },
                 vec![],
             )
@@ -1650,7 +3140,7 @@ impl Content {
         );
 
         Content::Heatmap {
-            chart: Some(chart),
+            chart: Some(Box::new(chart)),
             indicators: enabled_indicators,
             layout,
             studies: prev_studies,
@@ -1700,11 +3190,11 @@ impl Content {
         let enabled_indicators = {
             let available = KlineIndicator::for_market(ticker_info.market_type());
             prev_indis.map_or_else(
-                || vec![KlineIndicator::Volume],
+                || vec![KlineInstance::new(0, KlineIndicator::Volume)],
                 |indis| {
                     indis
                         .into_iter()
-                        .filter(|i| available.contains(i))
+                        .filter(|i| available.contains(&i.kind))
                         .collect()
                 },
             )
@@ -1741,9 +3231,12 @@ impl Content {
                 show_volume_histogram: true,
                 show_price_levels: true,
                 show_market_depth: false,
+                navigation: Navigation::default(),
+                watermark: Watermark::default(),
+                price_scale: PriceScale::default(),
             });
 
-        let chart = KlineChart::new(
+        let mut chart = KlineChart::new(
             layout.clone(),
             basis,
             tick_size,
@@ -1752,10 +3245,13 @@ impl Content {
             &enabled_indicators,
             ticker_info,
             &determined_chart_kind,
+            settings.visual_config.clone().and_then(|cfg| cfg.kline()),
         );
+        chart.set_alerts(&settings.alerts);
+        chart.set_moving_averages(&settings.moving_averages);
 
         Content::Kline {
-            chart: Some(chart),
+            chart: Some(Box::new(chart)),
             indicators: enabled_indicators,
             layout,
             kind: determined_chart_kind,
@@ -1767,7 +3263,7 @@ impl Content {
             ContentKind::Starter => Content::Starter,
             ContentKind::CandlestickChart => Content::Kline {
                 chart: None,
-                indicators: vec![KlineIndicator::Volume],
+                indicators: vec![KlineInstance::new(0, KlineIndicator::Volume)],
                 kind: data::chart::KlineChartKind::Candles,
                 layout: ViewConfig {
                     splits: vec![],
@@ -1776,11 +3272,14 @@ impl Content {
                     show_volume_histogram: true,
                     show_price_levels: true,
                     show_market_depth: false,
+                    navigation: Navigation::default(),
+                    watermark: Watermark::default(),
+                    price_scale: PriceScale::default(),
                 },
             },
             ContentKind::FootprintChart => Content::Kline {
                 chart: None,
-                indicators: vec![KlineIndicator::Volume],
+                indicators: vec![KlineInstance::new(0, KlineIndicator::Volume)],
                 kind: data::chart::KlineChartKind::Footprint {
                     clusters: data::chart::kline::ClusterKind::default(),
                     scaling: data::chart::kline::ClusterScaling::default(),
@@ -1793,6 +3292,9 @@ impl Content {
                     show_volume_histogram: true,
                     show_price_levels: true,
                     show_market_depth: true,
+                    navigation: Navigation::default(),
+                    watermark: Watermark::default(),
+                    price_scale: PriceScale::default(),
                 },
             },
             ContentKind::HeatmapChart => Content::Heatmap {
@@ -1806,11 +3308,20 @@ impl Content {
                     show_volume_histogram: true,
                     show_price_levels: true,
                     show_market_depth: false,
+                    navigation: Navigation::default(),
+                    watermark: Watermark::default(),
+                    price_scale: PriceScale::default(),
                 },
             },
             ContentKind::ComparisonChart => Content::Comparison(None),
             ContentKind::TimeAndSales => Content::TimeAndSales(None),
             ContentKind::Ladder => Content::Ladder(None),
+            ContentKind::VolumeProfile => Content::VolumeProfile(None),
+            ContentKind::DepthInspector => Content::DepthInspector(None),
+            ContentKind::TradeSizes => Content::TradeSizes(None),
+            ContentKind::Watchlist => Content::Watchlist(Watchlist::new(None)),
+            ContentKind::HeatGrid => Content::HeatGrid(HeatGrid::new(None)),
+            ContentKind::Journal => Content::Journal(Journal::new()),
         }
     }
 
@@ -1820,8 +3331,21 @@ impl Content {
             Content::Kline { chart, .. } => Some(chart.as_ref()?.last_update()),
             Content::TimeAndSales(panel) => Some(panel.as_ref()?.last_update()),
             Content::Ladder(panel) => Some(panel.as_ref()?.last_update()),
+            Content::VolumeProfile(panel) => Some(panel.as_ref()?.last_update()),
+            Content::TradeSizes(panel) => Some(panel.as_ref()?.last_update()),
             Content::Comparison(chart) => Some(chart.as_ref()?.last_update()),
-            Content::Starter => None,
+            Content::Starter
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_)
+            | Content::DepthInspector(_) => None,
+        }
+    }
+
+    pub fn trading_config(&self) -> Option<TradingConfig> {
+        match self {
+            Content::Kline { chart: Some(c), .. } => c.trading_config().cloned(),
+            _ => None,
         }
     }
 
@@ -1861,25 +3385,62 @@ impl Content {
                     return;
                 };
 
-                if indicators.contains(&ind) {
-                    indicators.retain(|i| i != &ind);
+                // kinds with inputs can be added again with different ones
+                if !ind.has_params()
+                    && let Some(existing) = indicators.iter().find(|i| i.kind == ind)
+                {
+                    let id = existing.id;
+                    indicators.retain(|i| i.id != id);
+                    chart.remove_indicator(id);
                 } else {
-                    indicators.push(ind);
+                    let instance = KlineInstance::new(KlineInstance::next_id(indicators), ind);
+                    indicators.push(instance);
+                    chart.add_indicator(instance);
                 }
-                chart.toggle_indicator(ind);
             }
             _ => panic!("indicator toggle on {indicator:?} pane",),
         }
     }
 
+    pub fn remove_indicator(&mut self, id: u16) {
+        if let Content::Kline {
+            chart, indicators, ..
+        } = self
+        {
+            indicators.retain(|i| i.id != id);
+            if let Some(chart) = chart {
+                chart.remove_indicator(id);
+            }
+        }
+    }
+
+    pub fn set_indicator_params(&mut self, id: u16, params: Option<IndicatorParams>) {
+        if let Content::Kline {
+            chart, indicators, ..
+        } = self
+            && let Some(instance) = indicators.iter_mut().find(|i| i.id == id)
+        {
+            instance.params = params;
+            if let Some(chart) = chart {
+                chart.set_indicator_params(id, params);
+            }
+        }
+    }
+
     pub fn reorder_indicators(&mut self, event: &column_drag::DragEvent) {
         match self {
             Content::Heatmap { indicators, .. } => column_drag::reorder_vec(indicators, event),
             Content::Kline { indicators, .. } => column_drag::reorder_vec(indicators, event),
             Content::TimeAndSales(_)
             | Content::Ladder(_)
+            | Content::VolumeProfile(_)
+            | Content::DepthInspector(_)
+            | Content::TradeSizes(_)
             | Content::Starter
-            | Content::Comparison(_) => {
+            | Content::Comparison(_)
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_) => {
                 panic!("indicator reorder on {} pane", self)
             }
         }
@@ -1890,19 +3451,61 @@ impl Content {
             (Content::Heatmap { chart: Some(c), .. }, VisualConfig::Heatmap(cfg)) => {
                 c.set_visual_config(cfg);
             }
+            (Content::Kline { chart: Some(c), .. }, VisualConfig::Kline(cfg)) => {
+                c.set_visual_config(cfg);
+            }
             (Content::TimeAndSales(Some(panel)), VisualConfig::TimeAndSales(cfg)) => {
-                panel.config = cfg;
+                panel.set_config(cfg);
             }
             (Content::Ladder(Some(panel)), VisualConfig::Ladder(cfg)) => {
                 panel.config = cfg;
             }
+            (Content::VolumeProfile(Some(panel)), VisualConfig::VolumeProfile(cfg)) => {
+                panel.set_config(cfg);
+            }
+            (Content::DepthInspector(Some(inspector)), VisualConfig::DepthInspector(cfg)) => {
+                inspector.config = cfg;
+            }
+            (Content::TradeSizes(Some(panel)), VisualConfig::TradeSizes(cfg)) => {
+                panel.set_config(cfg);
+            }
             (Content::Comparison(Some(chart)), VisualConfig::Comparison(cfg)) => {
-                chart.config = cfg;
+                chart.apply_config(cfg);
+            }
+            (Content::Watchlist(watchlist), VisualConfig::Watchlist(cfg)) => {
+                watchlist.config = cfg;
+            }
+            (Content::HeatGrid(grid), VisualConfig::HeatGrid(cfg)) => {
+                grid.config = cfg;
             }
             _ => {}
         }
     }
 
+    pub fn visual_config(&self) -> Option<VisualConfig> {
+        match self {
+            Content::Heatmap { chart: Some(c), .. } => {
+                Some(VisualConfig::Heatmap(c.visual_config()))
+            }
+            Content::Kline { chart: Some(c), .. } => Some(VisualConfig::Kline(c.visual_config())),
+            Content::TimeAndSales(Some(panel)) => Some(VisualConfig::TimeAndSales(panel.config)),
+            Content::Ladder(Some(panel)) => Some(VisualConfig::Ladder(panel.config)),
+            Content::VolumeProfile(Some(panel)) => Some(VisualConfig::VolumeProfile(panel.config)),
+            Content::DepthInspector(Some(inspector)) => {
+                Some(VisualConfig::DepthInspector(inspector.config))
+            }
+            Content::TradeSizes(Some(panel)) => Some(VisualConfig::TradeSizes(panel.config)),
+            Content::Comparison(Some(chart)) => {
+                Some(VisualConfig::Comparison(chart.serializable_config()))
+            }
+            Content::Watchlist(watchlist) => {
+                Some(VisualConfig::Watchlist(watchlist.config.clone()))
+            }
+            Content::HeatGrid(grid) => Some(VisualConfig::HeatGrid(grid.config)),
+            _ => None,
+        }
+    }
+
     pub fn studies(&self) -> Option<data::chart::Study> {
         match &self {
             Content::Heatmap { studies, .. } => Some(data::chart::Study::Heatmap(studies.clone())),
@@ -1915,8 +3518,14 @@ impl Content {
             }
             Content::TimeAndSales(_)
             | Content::Ladder(_)
+            | Content::VolumeProfile(_)
+            | Content::DepthInspector(_)
+            | Content::TradeSizes(_)
             | Content::Starter
-            | Content::Comparison(_) => None,
+            | Content::Comparison(_)
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_) => None,
         }
     }
 
@@ -1961,7 +3570,13 @@ impl Content {
             },
             Content::TimeAndSales(_) => ContentKind::TimeAndSales,
             Content::Ladder(_) => ContentKind::Ladder,
+            Content::VolumeProfile(_) => ContentKind::VolumeProfile,
+            Content::DepthInspector(_) => ContentKind::DepthInspector,
+            Content::TradeSizes(_) => ContentKind::TradeSizes,
             Content::Comparison(_) => ContentKind::ComparisonChart,
+            Content::Watchlist(_) => ContentKind::Watchlist,
+            Content::HeatGrid(_) => ContentKind::HeatGrid,
+            Content::Journal(_) => ContentKind::Journal,
             Content::Starter => ContentKind::Starter,
         }
     }
@@ -1972,8 +3587,14 @@ impl Content {
             Content::Kline { chart, .. } => chart.is_some(),
             Content::TimeAndSales(panel) => panel.is_some(),
             Content::Ladder(panel) => panel.is_some(),
+            Content::VolumeProfile(panel) => panel.is_some(),
+            Content::DepthInspector(inspector) => inspector.is_some(),
+            Content::TradeSizes(panel) => panel.is_some(),
             Content::Comparison(chart) => chart.is_some(),
-            Content::Starter => true,
+            Content::Starter
+            | Content::Watchlist(_)
+            | Content::HeatGrid(_)
+            | Content::Journal(_) => true,
         }
     }
 }
@@ -1993,6 +3614,12 @@ impl PartialEq for Content {
                 | (Content::Kline { .. }, Content::Kline { .. })
                 | (Content::TimeAndSales(_), Content::TimeAndSales(_))
                 | (Content::Ladder(_), Content::Ladder(_))
+                | (Content::VolumeProfile(_), Content::VolumeProfile(_))
+                | (Content::DepthInspector(_), Content::DepthInspector(_))
+                | (Content::TradeSizes(_), Content::TradeSizes(_))
+                | (Content::Watchlist(_), Content::Watchlist(_))
+                | (Content::HeatGrid(_), Content::HeatGrid(_))
+                | (Content::Journal(_), Content::Journal(_))
         )
     }
 }
@@ -2000,8 +3627,24 @@ impl PartialEq for Content {
 fn link_group_modal<'a>(
     pane: pane_grid::Pane,
     selected_group: Option<LinkGroup>,
+    is_view_synced: bool,
+    custom_title: &str,
+    generated_title: String,
+    exchange: Option<Exchange>,
+    failover: Option<Failover>,
+    depth_exchange: Option<Exchange>,
+    merge_venues: &[Exchange],
 ) -> Element<'a, Message> {
-    let mut grid = column![].spacing(4);
+    let title_input = text_input(&generated_title, custom_title)
+        .on_input(move |value| Message::PaneEvent(pane, Event::TitleChanged(value)))
+        .size(12);
+
+    let mut grid = column![
+        text("Title").size(12),
+        title_input,
+        text("Link group").size(12)
+    ]
+    .spacing(4);
     let rows = LinkGroup::ALL.chunks(3);
 
     for row_groups in rows {
@@ -2032,6 +3675,31 @@ fn link_group_modal<'a>(
         grid = grid.push(button_row);
     }
 
+    if let Some(group) = selected_group {
+        let sync_checkbox = iced::widget::checkbox(is_view_synced)
+            .label("Sync zoom & pan")
+            .on_toggle(move |_| Message::ToggleViewSync(group))
+            .text_size(12);
+
+        grid = grid.push(widget::tooltip(
+            sync_checkbox,
+            Some("Time based charts of the group follow each other's visible time window"),
+            tooltip::Position::Bottom,
+        ));
+    }
+
+    if let Some(exchange) = exchange
+        && let Some(controls) = failover_controls(pane, exchange, failover)
+    {
+        grid = grid.push(controls);
+    }
+
+    if let Some(exchange) = depth_exchange
+        && let Some(controls) = merge_controls(pane, exchange, merge_venues)
+    {
+        grid = grid.push(controls);
+    }
+
     container(grid)
         .max_width(240)
         .padding(16)
@@ -2039,6 +3707,119 @@ fn link_group_modal<'a>(
         .into()
 }
 
+fn failover_controls<'a>(
+    pane: pane_grid::Pane,
+    exchange: Exchange,
+    failover: Option<Failover>,
+) -> Option<Element<'a, Message>> {
+    let venues: Vec<Exchange> = Exchange::ALL
+        .into_iter()
+        .filter(|venue| {
+            *venue != exchange && venue.is_active() && venue.market_type() == exchange.market_type()
+        })
+        .collect();
+    let first_venue = venues.first().copied()?;
+
+    let on_change = move |failover| Message::PaneEvent(pane, Event::FailoverChanged(failover));
+
+    let toggle = iced::widget::checkbox(failover.is_some())
+        .label("Fail over")
+        .on_toggle(move |enabled| {
+            on_change(enabled.then(|| Failover {
+                exchange: first_venue,
+                ..Failover::default()
+            }))
+        })
+        .text_size(12);
+
+    let mut controls = column![widget::tooltip(
+        toggle,
+        Some("Switches the streams to the same symbol on another venue while this one stays down"),
+        tooltip::Position::Bottom,
+    )]
+    .spacing(4);
+
+    if let Some(failover) = failover {
+        controls = controls.push(
+            row![
+                pick_list(venues, Some(failover.exchange), move |venue| {
+                    on_change(Some(Failover {
+                        exchange: venue,
+                        ..failover
+                    }))
+                })
+                .text_size(12),
+                text("after").size(12),
+                pick_list(
+                    Failover::AFTER_SECS_CHOICES,
+                    Some(failover.after_secs),
+                    move |after_secs| on_change(Some(Failover {
+                        after_secs,
+                        ..failover
+                    }))
+                )
+                .text_size(12),
+                text("s").size(12),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    Some(controls.into())
+}
+
+fn merge_controls<'a>(
+    pane: pane_grid::Pane,
+    exchange: Exchange,
+    merge_venues: &[Exchange],
+) -> Option<Element<'a, Message>> {
+    let venues = Exchange::ALL
+        .into_iter()
+        .filter(|venue| {
+            *venue != exchange && venue.is_active() && venue.market_type() == exchange.market_type()
+        })
+        .map(|venue| {
+            let toggle = iced::widget::checkbox(merge_venues.contains(&venue))
+                .label(venue.to_string())
+                .on_toggle(move |enabled| {
+                    Message::PaneEvent(pane, Event::MergeVenueToggled(venue, enabled))
+                })
+                .text_size(12);
+
+            row![
+                toggle,
+                container(iced::widget::space::horizontal())
+                    .width(8)
+                    .height(8)
+                    .style(move |_: &Theme| container::Style {
+                        background: Some(style::venue_color(venue).into()),
+                        border: iced::Border {
+                            radius: 4.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .into()
+        })
+        .collect::<Vec<Element<'a, Message>>>();
+
+    if venues.is_empty() {
+        return None;
+    }
+
+    let label = widget::tooltip(
+        text("Merge venues").size(12),
+        Some("Adds trades and depth of the same asset on other venues to the pane"),
+        tooltip::Position::Bottom,
+    );
+
+    Some(column![label, column(venues).spacing(2)].spacing(4).into())
+}
+
 fn ticksize_modifier<'a>(
     id: pane_grid::Pane,
     base_ticksize: f32,
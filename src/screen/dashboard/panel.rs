@@ -1,8 +1,15 @@
+pub mod depth_inspector;
+pub mod heatgrid;
+pub mod journal;
 pub mod ladder;
 pub mod timeandsales;
+pub mod trade_sizes;
+pub mod volume_profile;
+pub mod watchlist;
 
+use exchange::util::Price;
 use iced::{
-    Element, padding,
+    Element, Point, padding,
     widget::{canvas, center, container, text},
 };
 use std::time::Instant;
@@ -12,9 +19,16 @@ pub enum Message {
     Scrolled(f32),
     ResetScroll,
     Invalidate(Option<Instant>),
+    /// A print was clicked, carries its trade time for linked charts
+    PrintClicked(u64),
+    /// Right click on a price row, with the cursor position
+    PriceMenu(Point, Price),
 }
 
-pub enum Action {}
+pub enum Action {
+    /// Backfill the panel needs from the exchange
+    RequestFetch(exchange::fetcher::FetchRequests),
+}
 
 pub trait Panel: canvas::Program<Message> {
     fn scroll(&mut self, scroll: f32);
@@ -51,5 +65,6 @@ pub fn update<T: Panel>(panel: &mut T, message: Message) {
         Message::Invalidate(now) => {
             panel.invalidate(now);
         }
+        Message::PrintClicked(_) | Message::PriceMenu(..) => {}
     }
 }
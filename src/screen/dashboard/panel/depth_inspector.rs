@@ -0,0 +1,341 @@
+use crate::style;
+
+use data::panel::depth_inspector::{Config, LevelChange, Side, apply, diff};
+use exchange::TickerInfo;
+use exchange::depth::Depth;
+use exchange::util::Price;
+use iced::{
+    Alignment, Element, Length, Theme,
+    widget::{button, center, column, pick_list, row, rule, scrollable, space, text},
+};
+use std::collections::VecDeque;
+
+/// Span the update rate and the largest changes are measured over
+const RATE_WINDOW_MS: u64 = 1_000;
+const TEXT_SIZE: f32 = 11.0;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleFreeze,
+    StepBack,
+    StepForward,
+    HistoryChanged(usize),
+    RowsChanged(usize),
+}
+
+pub enum Action {
+    /// History or row count changed and should be saved
+    ConfigChanged,
+}
+
+/// Levels of a depth update that differ from the book before it
+struct Update {
+    time: u64,
+    changes: Vec<LevelChange>,
+}
+
+/// Book as it was after `cursor` of the recorded updates, moved through one update at a time
+struct Frozen {
+    cursor: usize,
+    book: Depth,
+}
+
+/// Book the stream keeps for one ticker, with the diffs that led to it, for checking an
+/// adapter's snapshot and diff handling
+pub struct DepthInspector {
+    pub config: Config,
+    ticker_info: TickerInfo,
+    book: Depth,
+    /// Oldest first, at most `config.history`
+    history: VecDeque<Update>,
+    /// Update times and change counts within the rate window of the latest update
+    recent: VecDeque<(u64, usize)>,
+    /// While set, incoming updates only count towards the rate, the next one after
+    /// resuming carries everything that changed meanwhile
+    frozen: Option<Frozen>,
+}
+
+impl DepthInspector {
+    pub fn new(config: Option<Config>, ticker_info: TickerInfo) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            ticker_info,
+            book: Depth::default(),
+            history: VecDeque::new(),
+            recent: VecDeque::new(),
+            frozen: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ToggleFreeze => {
+                self.frozen = match self.frozen.take() {
+                    Some(_) => None,
+                    None => Some(Frozen {
+                        cursor: self.history.len(),
+                        book: self.book.clone(),
+                    }),
+                };
+            }
+            Message::StepBack => {
+                if let Some(frozen) = &mut self.frozen
+                    && frozen.cursor > 0
+                {
+                    frozen.cursor -= 1;
+                    apply(
+                        &mut frozen.book,
+                        &self.history[frozen.cursor].changes,
+                        false,
+                    );
+                }
+            }
+            Message::StepForward => {
+                if let Some(frozen) = &mut self.frozen
+                    && frozen.cursor < self.history.len()
+                {
+                    apply(&mut frozen.book, &self.history[frozen.cursor].changes, true);
+                    frozen.cursor += 1;
+                }
+            }
+            Message::HistoryChanged(history) => {
+                self.config.history = history;
+                self.trim_history();
+                return Some(Action::ConfigChanged);
+            }
+            Message::RowsChanged(rows) => {
+                self.config.rows = rows;
+                return Some(Action::ConfigChanged);
+            }
+        }
+
+        None
+    }
+
+    pub fn insert_depth(&mut self, update_t: u64, depth: &Depth) {
+        if self.frozen.is_some() {
+            self.recent.push_back((update_t, 0));
+            self.prune_recent(update_t);
+            return;
+        }
+
+        let changes = diff(&self.book, depth);
+        apply(&mut self.book, &changes, true);
+
+        self.recent.push_back((update_t, changes.len()));
+        self.prune_recent(update_t);
+
+        self.history.push_back(Update {
+            time: update_t,
+            changes,
+        });
+        self.trim_history();
+    }
+
+    fn prune_recent(&mut self, latest: u64) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|(time, _)| *time + RATE_WINDOW_MS < latest)
+        {
+            self.recent.pop_front();
+        }
+    }
+
+    fn trim_history(&mut self) {
+        // stepping stays within what was recorded when freezing
+        if self.frozen.is_some() {
+            return;
+        }
+        while self.history.len() > self.config.history {
+            self.history.pop_front();
+        }
+    }
+
+    /// Book shown and how many of the recorded updates it includes
+    fn shown(&self) -> (&Depth, usize) {
+        match &self.frozen {
+            Some(frozen) => (&frozen.book, frozen.cursor),
+            None => (&self.book, self.history.len()),
+        }
+    }
+
+    /// Largest changes of the updates within the rate window before the shown one
+    fn largest_changes(&self, shown: usize) -> Vec<LevelChange> {
+        let Some(last) = shown.checked_sub(1).map(|idx| &self.history[idx]) else {
+            return vec![];
+        };
+
+        let mut changes: Vec<LevelChange> = self
+            .history
+            .range(..shown)
+            .rev()
+            .take_while(|update| update.time + RATE_WINDOW_MS >= last.time)
+            .flat_map(|update| update.changes.iter().copied())
+            .collect();
+
+        changes.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()));
+        changes.truncate(self.config.rows);
+        changes
+    }
+
+    fn format_price(&self, price: Price) -> String {
+        price.to_string(self.ticker_info.min_ticksize)
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let is_frozen = self.frozen.is_some();
+        let (book, shown) = self.shown();
+
+        let controls = row![
+            button(text(if is_frozen { "Resume" } else { "Freeze" }).size(12))
+                .on_press(Message::ToggleFreeze),
+            button(text("<").size(12))
+                .on_press_maybe((is_frozen && shown > 0).then_some(Message::StepBack)),
+            button(text(">").size(12)).on_press_maybe(
+                (is_frozen && shown < self.history.len()).then_some(Message::StepForward)
+            ),
+            text(format!("{shown}/{}", self.history.len())).size(12),
+            space::horizontal(),
+            text("History").size(12),
+            pick_list(
+                Config::HISTORY_CHOICES,
+                Some(self.config.history),
+                Message::HistoryChanged
+            )
+            .text_size(12),
+            text("Rows").size(12),
+            pick_list(
+                Config::ROWS_CHOICES,
+                Some(self.config.rows),
+                Message::RowsChanged
+            )
+            .text_size(12),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        if self.history.is_empty() {
+            return column![controls, center(text("Waiting for data...").size(16))]
+                .spacing(4)
+                .padding(4)
+                .into();
+        }
+
+        let updates_per_sec = self.recent.len();
+        let changes_per_sec: usize = self.recent.iter().map(|(_, count)| count).sum();
+        let shown_update = shown.checked_sub(1).map(|idx| &self.history[idx]);
+
+        let stats = row![
+            mono(format!(
+                "Bids {} · Asks {}",
+                book.bids.len(),
+                book.asks.len()
+            )),
+            mono(format!("{updates_per_sec} upd/s · {changes_per_sec} chg/s")),
+            mono(shown_update.map_or("-".to_string(), |update| {
+                format!("t {} · {} chg", update.time, update.changes.len())
+            })),
+        ]
+        .spacing(12);
+
+        let changed_now = |side: Side, price: Price| {
+            shown_update.is_some_and(|update| {
+                update
+                    .changes
+                    .iter()
+                    .any(|c| c.side == side && c.price == price)
+            })
+        };
+
+        let level_row = |side: Side, price: &Price, qty: &f32| {
+            let is_changed = changed_now(side, *price);
+            mono(format!(
+                "{:>14} {:>12}",
+                self.format_price(*price),
+                data::util::abbr_large_numbers(*qty)
+            ))
+            .style(move |theme: &Theme| {
+                let palette = theme.extended_palette();
+                let color = match side {
+                    Side::Bid => palette.success.base.color,
+                    Side::Ask => palette.danger.base.color,
+                };
+                text::Style {
+                    color: Some(if is_changed {
+                        color
+                    } else {
+                        palette.background.base.text
+                    }),
+                }
+            })
+            .into()
+        };
+
+        let rows = self.config.rows;
+        let asks: Vec<Element<_>> = book
+            .asks
+            .iter()
+            .take(rows)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(|(price, qty)| level_row(Side::Ask, price, qty))
+            .collect();
+        let bids: Vec<Element<_>> = book
+            .bids
+            .iter()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .take(rows)
+            .map(|(price, qty)| level_row(Side::Bid, price, qty))
+            .collect();
+
+        let book_column = column![
+            mono("Book".to_string()),
+            column(asks),
+            rule::horizontal(1.0).style(style::split_ruler),
+            column(bids),
+        ]
+        .spacing(2)
+        .width(Length::FillPortion(1));
+
+        let changes = self
+            .largest_changes(shown)
+            .into_iter()
+            .map(|change| {
+                mono(format!(
+                    "{} {:>14} {:>10} > {:<10} {}{}",
+                    change.side,
+                    self.format_price(change.price),
+                    data::util::abbr_large_numbers(change.before),
+                    data::util::abbr_large_numbers(change.after),
+                    if change.delta() > 0.0 { "+" } else { "" },
+                    data::util::abbr_large_numbers(change.delta()),
+                ))
+                .into()
+            })
+            .collect::<Vec<Element<_>>>();
+
+        let changes_column = column![
+            mono("Largest changes, last 1s".to_string()),
+            column(changes).spacing(2),
+        ]
+        .spacing(2)
+        .width(Length::FillPortion(1));
+
+        column![
+            controls,
+            stats,
+            scrollable(row![book_column, changes_column].spacing(12)).height(Length::Fill),
+        ]
+        .spacing(6)
+        .padding(4)
+        .into()
+    }
+}
+
+fn mono<'a>(content: String) -> text::Text<'a> {
+    text(content).size(TEXT_SIZE).font(style::AZERET_MONO)
+}
@@ -0,0 +1,262 @@
+use crate::style;
+
+use data::panel::heatgrid::{Config, squarify};
+use exchange::{Ticker, TickerInfo, TickerStats, adapter::Exchange};
+use iced::{
+    Alignment, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, mouse,
+    widget::{
+        canvas::{self, Cache, Geometry, Path, Stroke},
+        center, column, pick_list, row, space, text,
+    },
+};
+use rustc_hash::FxHashMap;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+/// 24h change drawn at full color, larger moves are clamped to it
+const FULL_COLOR_CHANGE_PCT: f32 = 8.0;
+const LABEL_MIN_SIZE: Size = Size::new(48.0, 28.0);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ExchangeChanged(Exchange),
+    MaxTilesChanged(usize),
+    Open(Ticker),
+}
+
+pub enum Action {
+    /// Exchange or tile count changed and should be saved
+    ConfigChanged,
+    Open(TickerInfo),
+}
+
+/// Tickers of one exchange tiled by 24h volume and colored by 24h change
+pub struct HeatGrid {
+    pub config: Config,
+    tickers_info: FxHashMap<Ticker, TickerInfo>,
+    /// Tiled tickers, by volume descending
+    tiles: Vec<(Ticker, TickerStats)>,
+    requested_at: Option<Instant>,
+    cache: Cache,
+}
+
+impl HeatGrid {
+    pub fn new(config: Option<Config>) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            tickers_info: FxHashMap::default(),
+            tiles: vec![],
+            requested_at: None,
+            cache: Cache::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ExchangeChanged(exchange) => {
+                if exchange == self.config.exchange {
+                    return None;
+                }
+                self.config.exchange = exchange;
+                self.tickers_info.clear();
+                self.tiles.clear();
+                self.requested_at = None;
+                self.cache.clear();
+            }
+            Message::MaxTilesChanged(max_tiles) => {
+                self.config.max_tiles = max_tiles;
+                // refetch rather than keeping every ticker of the venue around
+                self.requested_at = None;
+            }
+            Message::Open(ticker) => {
+                return self.tickers_info.get(&ticker).copied().map(Action::Open);
+            }
+        }
+
+        Some(Action::ConfigChanged)
+    }
+
+    /// Exchange to fetch stats of, once every refresh interval,
+    /// and whether its ticker metadata is still missing
+    pub fn refresh_due(&mut self, now: Instant) -> Option<(Exchange, bool)> {
+        if self
+            .requested_at
+            .is_some_and(|at| now.duration_since(at) < REFRESH_INTERVAL)
+        {
+            return None;
+        }
+        self.requested_at = Some(now);
+
+        Some((self.config.exchange, self.tickers_info.is_empty()))
+    }
+
+    pub fn insert_tickers_info(
+        &mut self,
+        exchange: Exchange,
+        tickers_info: HashMap<Ticker, Option<TickerInfo>>,
+    ) {
+        if exchange != self.config.exchange {
+            return;
+        }
+
+        self.tickers_info = tickers_info
+            .into_iter()
+            .filter_map(|(ticker, info)| Some((ticker, info?)))
+            .collect();
+    }
+
+    pub fn insert_stats(&mut self, exchange: Exchange, stats: HashMap<Ticker, TickerStats>) {
+        if exchange != self.config.exchange {
+            return;
+        }
+
+        self.tiles = self.config.top_by_volume(stats.into_iter());
+        self.cache.clear();
+    }
+
+    fn layout(&self, size: Size) -> Vec<Rectangle> {
+        let weights: Vec<f32> = self.tiles.iter().map(|(_, s)| s.daily_volume).collect();
+
+        squarify(&weights, size.width, size.height)
+            .into_iter()
+            .map(|[x, y, width, height]| Rectangle {
+                x,
+                y,
+                width,
+                height,
+            })
+            .collect()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let controls = row![
+            pick_list(
+                Exchange::ALL,
+                Some(self.config.exchange),
+                Message::ExchangeChanged
+            )
+            .text_size(12),
+            space::horizontal(),
+            text("Tiles").size(12),
+            pick_list(
+                Config::MAX_TILES_CHOICES,
+                Some(self.config.max_tiles),
+                Message::MaxTilesChanged
+            )
+            .text_size(12),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let grid: Element<_> = if self.tiles.is_empty() {
+            center(text("Fetching tickers...").size(12)).into()
+        } else {
+            canvas::Canvas::new(self)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        };
+
+        column![controls, grid].spacing(4).padding(4).into()
+    }
+}
+
+impl canvas::Program<Message> for HeatGrid {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return None;
+        };
+        let cursor_position = cursor.position_in(bounds)?;
+
+        self.layout(bounds.size())
+            .iter()
+            .position(|tile| tile.contains(cursor_position))
+            .map(|idx| canvas::Action::publish(Message::Open(self.tiles[idx].0)).and_capture())
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.extended_palette();
+
+        let tiles = self.cache.draw(renderer, bounds.size(), |frame| {
+            let border = Stroke::default()
+                .with_color(palette.background.base.color)
+                .with_width(1.0);
+
+            for ((ticker, stats), tile) in self.tiles.iter().zip(self.layout(bounds.size())) {
+                let change = stats.daily_price_chg;
+                let intensity = (change.abs() / FULL_COLOR_CHANGE_PCT).min(1.0);
+                let color = if change >= 0.0 {
+                    palette.success.base.color
+                } else {
+                    palette.danger.base.color
+                };
+
+                frame.fill_rectangle(
+                    tile.position(),
+                    tile.size(),
+                    color.scale_alpha(0.15 + 0.85 * intensity),
+                );
+                frame.stroke(&Path::rectangle(tile.position(), tile.size()), border);
+
+                if tile.width < LABEL_MIN_SIZE.width || tile.height < LABEL_MIN_SIZE.height {
+                    continue;
+                }
+
+                let center = tile.center();
+                let text_size = (tile.height / 4.0).clamp(10.0, 18.0);
+
+                frame.fill_text(canvas::Text {
+                    content: ticker.display_symbol_and_type().0,
+                    position: Point::new(center.x, center.y - text_size * 0.6),
+                    size: text_size.into(),
+                    color: palette.background.base.text,
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..canvas::Text::default()
+                });
+                frame.fill_text(canvas::Text {
+                    content: data::util::pct_change(change),
+                    position: Point::new(center.x, center.y + text_size * 0.6),
+                    size: (text_size * 0.8).into(),
+                    color: palette.background.base.text,
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..canvas::Text::default()
+                });
+            }
+        });
+
+        vec![tiles]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::style::{self, Icon, icon_text};
+
+use data::UserTimezone;
+use data::paper::{
+    Account, Side,
+    journal::{Stats, Trade},
+};
+use data::util::format_duration_ms;
+use iced::{
+    Alignment, Element, Length, Theme,
+    widget::{button, center, column, container, row, scrollable, space, text},
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Export,
+}
+
+pub enum Action {
+    /// Write the journal to a CSV file
+    Export,
+}
+
+/// Simulated trades of the paper account, newest first, above their aggregated statistics
+pub struct Journal {
+    trades: Vec<Trade>,
+    stats: Stats,
+    /// Account revision the trades were copied at, `None` before the first sync
+    revision: Option<u64>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self {
+            trades: vec![],
+            stats: Stats::default(),
+            revision: None,
+        }
+    }
+
+    /// Copies the account's journal when it changed since the last sync
+    pub fn sync(&mut self, account: &Account) {
+        if self.revision == Some(account.revision()) {
+            return;
+        }
+        self.revision = Some(account.revision());
+        self.trades = account.journal().to_vec();
+        self.stats = Stats::new(&self.trades);
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::Export => Some(Action::Export),
+        }
+    }
+
+    pub fn view(&self, timezone: UserTimezone) -> Element<'_, Message> {
+        if self.trades.is_empty() {
+            return center(
+                column![
+                    text("No paper trades yet").size(16),
+                    text("Trades of the paper account are listed here as they close").size(12),
+                ]
+                .spacing(8)
+                .align_x(Alignment::Center),
+            )
+            .into();
+        }
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+
+        let header = row![
+            header_cell("Ticker", 3),
+            header_cell("Entry", 4),
+            header_cell("Exit", 2),
+            header_cell("Held", 2),
+            header_cell("Net P&L", 2),
+            header_cell("MAE", 2),
+            header_cell("MFE", 2),
+        ]
+        .spacing(2);
+
+        let rows = self
+            .trades
+            .iter()
+            .rev()
+            .map(|trade| trade_row(trade, timezone, now));
+
+        container(
+            column![
+                self.stats_bar(),
+                header,
+                scrollable(column(rows).spacing(2)).style(style::scroll_bar),
+            ]
+            .spacing(4),
+        )
+        .padding(4)
+        .into()
+    }
+
+    fn stats_bar(&self) -> Element<'_, Message> {
+        let stats = &self.stats;
+
+        let win_rate = stats
+            .win_rate()
+            .map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+        let profit_factor = match (stats.profit_factor(), stats.gross_profit > 0.0) {
+            (Some(factor), _) => format!("{factor:.2}"),
+            (None, true) => "∞".to_string(),
+            (None, false) => "-".to_string(),
+        };
+
+        let stat = |label: &'static str, value: String| {
+            column![
+                text(label).size(10).style(style::secondary_text),
+                text(value).size(12),
+            ]
+            .spacing(2)
+        };
+
+        row![
+            stat("Trades", stats.trades.to_string()),
+            stat("Win rate", win_rate),
+            stat("Profit factor", profit_factor),
+            stat("Net", format!("{:+.2}", stats.net)),
+            stat("Fees", format!("{:.2}", stats.fees)),
+            stat("Avg held", format_duration_ms(stats.avg_duration)),
+            space::horizontal(),
+            button(
+                row![icon_text(Icon::Folder, 11), text("CSV").size(11)]
+                    .spacing(4)
+                    .align_y(Alignment::Center)
+            )
+            .padding([2, 6])
+            .on_press(Message::Export)
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center)
+        .into()
+    }
+}
+
+fn header_cell<'a>(label: &'a str, portion: u16) -> Element<'a, Message> {
+    text(label)
+        .size(11)
+        .style(style::secondary_text)
+        .width(Length::FillPortion(portion))
+        .into()
+}
+
+fn trade_row<'a>(trade: &Trade, timezone: UserTimezone, now: u64) -> Element<'a, Message> {
+    let pnl_color = |value: f32| {
+        move |theme: &Theme| {
+            let palette = theme.extended_palette();
+            iced::widget::text::Style {
+                color: Some(if value >= 0.0 {
+                    palette.success.base.color
+                } else {
+                    palette.danger.base.color
+                }),
+            }
+        }
+    };
+
+    let side = match trade.side {
+        Side::Buy => "Long",
+        Side::Sell => "Short",
+    };
+    let entry = format!(
+        "{} {:.4} @ {}",
+        timezone.format_crosshair_timestamp(trade.entry_time as i64, 60_000),
+        trade.qty,
+        price_label(trade.avg_entry),
+    );
+    let exit = if trade.is_open() {
+        "open".to_string()
+    } else {
+        trade.avg_exit().map_or("-".to_string(), price_label)
+    };
+
+    row![
+        row![
+            icon_text(style::exchange_icon(trade.ticker.exchange), 12),
+            text(format!(
+                "{} {side}",
+                trade.ticker.display_symbol_and_type().0
+            ))
+            .size(12),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .width(Length::FillPortion(3)),
+        text(entry).size(12).width(Length::FillPortion(4)),
+        text(exit).size(12).width(Length::FillPortion(2)),
+        text(format_duration_ms(trade.duration(now)))
+            .size(12)
+            .width(Length::FillPortion(2)),
+        text(format!("{:+.2}", trade.net()))
+            .size(12)
+            .style(pnl_color(trade.net()))
+            .width(Length::FillPortion(2)),
+        text(format!("{:.2}", trade.mae))
+            .size(12)
+            .style(pnl_color(trade.mae))
+            .width(Length::FillPortion(2)),
+        text(format!("{:+.2}", trade.mfe))
+            .size(12)
+            .style(pnl_color(trade.mfe))
+            .width(Length::FillPortion(2)),
+    ]
+    .spacing(2)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn price_label(price: f32) -> String {
+    let decimals = match price.abs() {
+        p if p >= 1_000.0 => 1,
+        p if p >= 10.0 => 2,
+        p if p >= 1.0 => 4,
+        _ => 6,
+    };
+    format!("{price:.decimals$}")
+}
@@ -1,13 +1,20 @@
 use super::Message;
 use crate::style;
-use data::panel::ladder::{ChaseTracker, Config, GroupedDepth, Side, TradeStore};
+use data::chart::pin::pin_label;
+use data::panel::ladder::{
+    ChaseTracker, Config, GroupedDepth, Imbalance, SPREAD_HISTORY_WINDOW_MS, Side, SpreadHistory,
+    TradeFlow, TradeStore, side_imbalances,
+};
 use exchange::Trade;
 use exchange::util::{Price, PriceStep};
 use exchange::{TickerInfo, depth::Depth};
 
+use iced::theme::palette::Extended;
 use iced::widget::canvas::{self, Path, Stroke, Text};
-use iced::{Alignment, Event, Point, Rectangle, Renderer, Size, Theme, mouse};
+use iced::widget::column;
+use iced::{Alignment, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, mouse};
 
+use rustc_hash::FxHashMap;
 use std::collections::BTreeMap;
 use std::time::{Duration, Instant};
 
@@ -19,6 +26,10 @@ const ROW_HEIGHT: f32 = 16.0;
 const ORDER_QTY_COLS_WIDTH: f32 = 0.60;
 /// Uses half of the width for each side of the trade quantity columns
 const TRADE_QTY_COLS_WIDTH: f32 = 0.20;
+/// Width taken by the trade flow column when shown, on top of the ratios above
+const TRADE_FLOW_COL_WIDTH: f32 = 0.15;
+/// Width taken by the imbalance column when shown, on top of the ratios above
+const IMBALANCE_COL_WIDTH: f32 = 0.15;
 
 const COL_PADDING: f32 = 4.0;
 /// Used for calculating layout with texts inside the price column
@@ -30,6 +41,10 @@ const CHASE_CIRCLE_RADIUS: f32 = 4.0;
 /// Maximum interval between chase updates to consider them part of the same chase
 const CHASE_MIN_INTERVAL: Duration = Duration::from_millis(200);
 
+const SPREAD_CHART_HEIGHT: f32 = 36.0;
+/// Width kept right of the spread line for the current value
+const SPREAD_LABEL_WIDTH: f32 = 56.0;
+
 impl super::Panel for Ladder {
     fn scroll(&mut self, delta: f32) {
         self.scroll_px += delta;
@@ -63,15 +78,21 @@ pub struct Ladder {
     last_exchange_ts_ms: Option<u64>,
     orderbook: [GroupedDepth; 2],
     trades: TradeStore,
+    trade_flow: TradeFlow,
     pending_tick_size: Option<PriceStep>,
     raw_price_spread: Option<Price>,
+    spread_history: SpreadHistory,
+    pinned_levels: Vec<Price>,
 }
 
 impl Ladder {
     pub fn new(config: Option<Config>, ticker_info: TickerInfo, tick_size: f32) -> Self {
+        let config = config.unwrap_or_default();
+
         Self {
             trades: TradeStore::new(),
-            config: config.unwrap_or_default(),
+            trade_flow: TradeFlow::new(config.trade_flow_window),
+            config,
             ticker_info,
             cache: canvas::Cache::default(),
             last_tick: Instant::now(),
@@ -80,22 +101,46 @@ impl Ladder {
             last_exchange_ts_ms: None,
             orderbook: [GroupedDepth::new(), GroupedDepth::new()],
             raw_price_spread: None,
+            spread_history: SpreadHistory::default(),
             pending_tick_size: None,
+            pinned_levels: vec![],
         }
     }
 
-    pub fn insert_buffers(&mut self, update_t: u64, depth: &Depth, trades_buffer: &[Trade]) {
-        if let Some(next) = self.pending_tick_size.take() {
-            self.tick_size = next;
-            self.trades.rebuild_grouped(self.tick_size);
+    pub fn view(&self, timezone: data::UserTimezone) -> Element<'_, Message> {
+        let ladder = super::view(self, timezone);
+
+        if !self.config.show_spread_history || super::Panel::is_empty(self) {
+            return ladder;
         }
 
+        let spread_chart = canvas::Canvas::new(SpreadChart {
+            history: &self.spread_history,
+        })
+        .width(Length::Fill)
+        .height(SPREAD_CHART_HEIGHT);
+
+        column![spread_chart, ladder].into()
+    }
+
+    pub fn set_pinned_levels(&mut self, levels: Vec<Price>) {
+        self.pinned_levels = levels;
+        self.invalidate(Some(Instant::now()));
+    }
+
+    pub fn insert_buffers(&mut self, update_t: u64, depth: &Depth, trades_buffer: &[Trade]) {
+        self.apply_pending_tick_size();
+
         let raw_best_bid = depth.bids.last_key_value().map(|(p, _)| *p);
         let raw_best_ask = depth.asks.first_key_value().map(|(p, _)| *p);
         self.raw_price_spread = match (raw_best_bid, raw_best_ask) {
             (Some(bid), Some(ask)) => Some(ask - bid),
             _ => None,
         };
+        if let Some(spread) = self.raw_price_spread {
+            let ticks = spread.to_f32() / self.min_tick_size();
+            self.spread_history.push(update_t, ticks.round().max(0.0));
+        }
 
         if self.config.show_chase_tracker {
             let max_int = CHASE_MIN_INTERVAL;
@@ -108,10 +153,40 @@ impl Ladder {
             self.chase_tracker_mut(Side::Ask).reset();
         }
 
+        self.ingest_trades(update_t, trades_buffer);
+        self.regroup_from_depth(depth);
+    }
+
+    /// Trades whose depth update was conflated away, the book stays as last drawn
+    pub fn insert_trades(&mut self, trades_buffer: &[Trade]) {
+        let Some(update_t) = trades_buffer.last().map(|trade| trade.time) else {
+            return;
+        };
+
+        self.apply_pending_tick_size();
+        self.ingest_trades(update_t, trades_buffer);
+    }
+
+    fn apply_pending_tick_size(&mut self) {
+        if let Some(next) = self.pending_tick_size.take() {
+            self.tick_size = next;
+            self.trades.rebuild_grouped(self.tick_size);
+            self.trade_flow
+                .rebuild(self.config.trade_flow_window, &self.trades.raw, next);
+        }
+    }
+
+    fn ingest_trades(&mut self, update_t: u64, trades_buffer: &[Trade]) {
         let step = self.tick_size;
         self.trades.insert_trades(trades_buffer, step);
 
-        self.regroup_from_depth(depth);
+        if self.trade_flow.window() == self.config.trade_flow_window {
+            self.trade_flow.insert_trades(trades_buffer, step);
+        } else {
+            self.trade_flow
+                .rebuild(self.config.trade_flow_window, &self.trades.raw, step);
+        }
+        self.trade_flow.prune(update_t);
 
         self.last_exchange_ts_ms = Some(update_t);
 
@@ -127,6 +202,14 @@ impl Ladder {
         self.trades.trade_qty_at(price)
     }
 
+    fn trade_flow_at(&self, price: Price) -> (f32, f32) {
+        if !self.config.show_trade_flow {
+            return (0.0, 0.0);
+        }
+        self.trade_flow
+            .qty_at(price, self.last_exchange_ts_ms.unwrap_or(0))
+    }
+
     pub fn last_update(&self) -> Instant {
         self.last_tick
     }
@@ -200,6 +283,34 @@ impl Ladder {
     fn format_quantity(&self, qty: f32) -> String {
         data::util::abbr_large_numbers(qty)
     }
+
+    /// Imbalance of each grouped level, pairing the n-th bid and ask from the touch
+    fn imbalances(&self) -> FxHashMap<Price, Imbalance> {
+        if !self.config.show_imbalance {
+            return FxHashMap::default();
+        }
+
+        let bids: Vec<(Price, f32)> = self
+            .grouped_bids()
+            .iter()
+            .rev()
+            .map(|(p, q)| (*p, *q))
+            .collect();
+        let asks: Vec<(Price, f32)> = self.grouped_asks().iter().map(|(p, q)| (*p, *q)).collect();
+
+        let bid_qtys: Vec<f32> = bids.iter().map(|(_, q)| *q).collect();
+        let ask_qtys: Vec<f32> = asks.iter().map(|(_, q)| *q).collect();
+        let levels = self.config.imbalance_levels;
+
+        let bid_side = side_imbalances(&bid_qtys, &ask_qtys, levels);
+        let ask_side = side_imbalances(&ask_qtys, &bid_qtys, levels);
+
+        bids.iter()
+            .zip(bid_side)
+            .chain(asks.iter().zip(ask_side))
+            .filter_map(|((price, _), imbalance)| Some((*price, imbalance?)))
+            .collect()
+    }
 }
 
 impl canvas::Program<Message> for Ladder {
@@ -212,11 +323,18 @@ impl canvas::Program<Message> for Ladder {
         bounds: iced::Rectangle,
         cursor: iced_core::mouse::Cursor,
     ) -> Option<canvas::Action<Message>> {
-        let _cursor_position = cursor.position_in(bounds)?;
+        let cursor_position = cursor.position_in(bounds)?;
 
         match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                let msg = match self.price_at(cursor_position.y, bounds.height) {
+                    Some(price) => Message::PriceMenu(cursor_position, price),
+                    None => Message::ResetScroll,
+                };
+                Some(canvas::Action::publish(msg).and_capture())
+            }
             Event::Mouse(mouse::Event::ButtonPressed(
-                mouse::Button::Middle | mouse::Button::Left | mouse::Button::Right,
+                mouse::Button::Middle | mouse::Button::Left,
             )) => Some(canvas::Action::publish(Message::ResetScroll).and_capture()),
             Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
                 let scroll_amount = match delta {
@@ -252,6 +370,7 @@ impl canvas::Program<Message> for Ladder {
                 let cols = self.column_ranges(bounds.width, layout.price_px);
 
                 let (visible_rows, maxima) = self.visible_rows(bounds, &grid);
+                let imbalances = self.imbalances();
 
                 let mut spread_row: Option<(f32, f32)> = None;
                 let mut best_bid_y: Option<f32> = None;
@@ -274,6 +393,38 @@ impl canvas::Program<Message> for Ladder {
                         _ => {}
                     }
 
+                    if let Some(flow_col) = cols.flow
+                        && matches!(visible_row.row, DomRow::Ask { .. } | DomRow::Bid { .. })
+                    {
+                        Self::draw_flow_cell(
+                            frame,
+                            flow_col,
+                            visible_row.y,
+                            visible_row.flow_buy,
+                            visible_row.flow_sell,
+                            maxima.vis_max_flow_qty,
+                            bid_color,
+                            ask_color,
+                            text_color,
+                        );
+                    }
+
+                    if let Some(imbalance_col) = cols.imbalance
+                        && let DomRow::Ask { price, .. } | DomRow::Bid { price, .. } =
+                            &visible_row.row
+                        && let Some(imbalance) = imbalances.get(price)
+                    {
+                        Self::draw_imbalance_cell(
+                            frame,
+                            imbalance_col,
+                            visible_row.y,
+                            *imbalance,
+                            self.config.imbalance_threshold,
+                            palette.warning.base.color,
+                            text_color,
+                        );
+                    }
+
                     match visible_row.row {
                         DomRow::Ask { price, qty } => {
                             self.draw_row(
@@ -402,6 +553,12 @@ impl canvas::Program<Message> for Ladder {
                 };
                 draw_vsplit(cols.sell.1, spread_row);
                 draw_vsplit(cols.buy.0, spread_row);
+                if let Some((flow_start, _)) = cols.flow {
+                    draw_vsplit(flow_start - COL_PADDING * 0.5, None);
+                }
+                if let Some((imbalance_start, _)) = cols.imbalance {
+                    draw_vsplit(imbalance_start - COL_PADDING * 0.5, None);
+                }
 
                 if let Some((top, bottom)) = spread_row {
                     let y_top: f32 = top.floor() + 0.5;
@@ -429,6 +586,8 @@ impl canvas::Program<Message> for Ladder {
                         divider_color,
                     );
                 }
+
+                self.draw_pinned_levels(frame, &grid, bounds, palette);
             }
         });
 
@@ -440,6 +599,7 @@ impl canvas::Program<Message> for Ladder {
 struct Maxima {
     vis_max_order_qty: f32,
     vis_max_trade_qty: f32,
+    vis_max_flow_qty: f32,
 }
 
 struct VisibleRow {
@@ -447,6 +607,8 @@ struct VisibleRow {
     y: f32,
     buy_t: f32,
     sell_t: f32,
+    flow_buy: f32,
+    flow_sell: f32,
 }
 
 struct ColumnRanges {
@@ -455,6 +617,8 @@ struct ColumnRanges {
     price: (f32, f32),
     buy: (f32, f32),
     ask_order: (f32, f32),
+    flow: Option<(f32, f32)>,
+    imbalance: Option<(f32, f32)>,
 }
 
 struct PriceLayout {
@@ -463,7 +627,7 @@ struct PriceLayout {
 }
 
 impl Ladder {
-    // [BidOrderQty][SellQty][ Price ][BuyQty][AskOrderQty]
+    // [BidOrderQty][SellQty][ Price ][BuyQty][AskOrderQty](TradeFlow)(Imbalance)
     const NUMBER_OF_COLUMN_GAPS: f32 = 4.0;
 
     fn price_sample_text(&self, grid: &PriceGrid) -> String {
@@ -494,13 +658,26 @@ impl Ladder {
     }
 
     fn column_ranges(&self, width: f32, price_px: f32) -> ColumnRanges {
-        let total_gutter_width = COL_PADDING * Self::NUMBER_OF_COLUMN_GAPS;
+        let show_flow = self.config.show_trade_flow;
+        let show_imbalance = self.config.show_imbalance;
+
+        let column_gaps = Self::NUMBER_OF_COLUMN_GAPS
+            + if show_flow { 1.0 } else { 0.0 }
+            + if show_imbalance { 1.0 } else { 0.0 };
+        let total_gutter_width = COL_PADDING * column_gaps;
         let usable_width = (width - total_gutter_width).max(0.0);
 
         let price_width = price_px.min(usable_width);
 
+        let flow_ratio = if show_flow { TRADE_FLOW_COL_WIDTH } else { 0.0 };
+        let imbalance_ratio = if show_imbalance {
+            IMBALANCE_COL_WIDTH
+        } else {
+            0.0
+        };
+
         let rest = (usable_width - price_width).max(0.0);
-        let rest_ratio = ORDER_QTY_COLS_WIDTH + TRADE_QTY_COLS_WIDTH; // 0.80
+        let rest_ratio = ORDER_QTY_COLS_WIDTH + TRADE_QTY_COLS_WIDTH + flow_ratio + imbalance_ratio;
 
         let order_share = if rest_ratio > 0.0 {
             (ORDER_QTY_COLS_WIDTH / rest_ratio) * rest
@@ -538,6 +715,20 @@ impl Ladder {
 
         let ask_order_end = cursor_x + ask_order_width;
         let ask_order_range = (cursor_x, ask_order_end);
+        cursor_x = ask_order_end + COL_PADDING;
+
+        let flow_range = show_flow.then(|| {
+            let flow_width = (flow_ratio / rest_ratio) * rest;
+            (cursor_x, cursor_x + flow_width)
+        });
+        if let Some((_, flow_end)) = flow_range {
+            cursor_x = flow_end + COL_PADDING;
+        }
+
+        let imbalance_range = show_imbalance.then(|| {
+            let imbalance_width = (imbalance_ratio / rest_ratio) * rest;
+            (cursor_x, cursor_x + imbalance_width)
+        });
 
         ColumnRanges {
             bid_order: bid_order_range,
@@ -545,6 +736,8 @@ impl Ladder {
             price: price_range,
             buy: buy_trades_range,
             ask_order: ask_order_range,
+            flow: flow_range,
+            imbalance: imbalance_range,
         }
     }
 
@@ -662,6 +855,107 @@ impl Ladder {
         );
     }
 
+    /// Sell aggressors grow left and buy aggressors grow right from the column center
+    fn draw_flow_cell(
+        frame: &mut iced::widget::canvas::Frame,
+        (x_start, x_end): (f32, f32),
+        y: f32,
+        buy_qty: f32,
+        sell_qty: f32,
+        max_flow_qty: f32,
+        buy_color: iced::Color,
+        sell_color: iced::Color,
+        text_color: iced::Color,
+    ) {
+        let mid_x = (x_start + x_end) * 0.5;
+
+        Self::fill_bar(
+            frame,
+            (x_start, mid_x),
+            y,
+            ROW_HEIGHT,
+            sell_qty,
+            max_flow_qty,
+            sell_color,
+            false,
+            0.45,
+        );
+        Self::fill_bar(
+            frame,
+            (mid_x, x_end),
+            y,
+            ROW_HEIGHT,
+            buy_qty,
+            max_flow_qty,
+            buy_color,
+            true,
+            0.45,
+        );
+
+        let total = buy_qty + sell_qty;
+        if max_flow_qty > 0.0 && total >= max_flow_qty * 0.05 {
+            Self::draw_cell_text(
+                frame,
+                &data::util::abbr_large_numbers(total),
+                mid_x,
+                y,
+                text_color,
+                Alignment::Center,
+            );
+        }
+    }
+
+    /// Level ratio on the left half, cumulative ratio on the right half while within range.
+    /// Halves at or over the threshold get the highlight color.
+    fn draw_imbalance_cell(
+        frame: &mut iced::widget::canvas::Frame,
+        (x_start, x_end): (f32, f32),
+        y: f32,
+        imbalance: Imbalance,
+        threshold: f32,
+        highlight_color: iced::Color,
+        text_color: iced::Color,
+    ) {
+        let mid_x = (x_start + x_end) * 0.5;
+        let format_ratio = |ratio: f32| {
+            if ratio >= 10.0 {
+                format!("{ratio:.0}")
+            } else {
+                format!("{ratio:.1}")
+            }
+        };
+
+        let halves = [
+            (Some(imbalance.level), (x_start, mid_x)),
+            (imbalance.cumulative, (mid_x, x_end)),
+        ];
+
+        for (ratio, (start, end)) in halves {
+            let Some(ratio) = ratio else {
+                continue;
+            };
+
+            if ratio >= threshold {
+                frame.fill_rectangle(
+                    Point::new(start, y),
+                    Size::new(end - start, ROW_HEIGHT),
+                    iced::Color {
+                        a: 0.35,
+                        ..highlight_color
+                    },
+                );
+            }
+            Self::draw_cell_text(
+                frame,
+                &format_ratio(ratio),
+                (start + end) * 0.5,
+                y,
+                text_color,
+                Alignment::Center,
+            );
+        }
+    }
+
     fn fill_bar(
         frame: &mut iced::widget::canvas::Frame,
         (x_start, x_end): (f32, f32),
@@ -713,6 +1007,70 @@ impl Ladder {
         });
     }
 
+    /// Outlines pinned rows in view, pins out of view get a label stacked at the edge they're past
+    fn draw_pinned_levels(
+        &self,
+        frame: &mut iced::widget::canvas::Frame,
+        grid: &PriceGrid,
+        bounds: Rectangle,
+        palette: &Extended,
+    ) {
+        if self.pinned_levels.is_empty() {
+            return;
+        }
+
+        let mid_price = match (self.best_price(Side::Bid), self.best_price(Side::Ask)) {
+            (Some(bid), Some(ask)) => Some(Price::from_units((bid.units + ask.units) / 2)),
+            (bid, ask) => bid.or(ask),
+        };
+        let color = palette.primary.base.color;
+        let half_row = ROW_HEIGHT / 2.0;
+
+        let (mut above, mut below) = (0.0, 0.0);
+
+        for &price in &self.pinned_levels {
+            let Some(y) = self.price_to_screen_y(price, grid, bounds.height) else {
+                continue;
+            };
+
+            if y >= half_row && y <= bounds.height - half_row {
+                frame.stroke(
+                    &Path::rectangle(
+                        Point::new(0.5, y - half_row + 0.5),
+                        Size::new(bounds.width - 1.0, ROW_HEIGHT - 1.0),
+                    ),
+                    Stroke::default().with_color(color).with_width(1.0),
+                );
+                continue;
+            }
+
+            let label = pin_label(price, mid_price, self.ticker_info.min_ticksize);
+            let (row_top, content) = if y < half_row {
+                above += ROW_HEIGHT;
+                (above - ROW_HEIGHT, format!("\u{25B2} {label}"))
+            } else {
+                below += ROW_HEIGHT;
+                (bounds.height - below, format!("\u{25BC} {label}"))
+            };
+
+            frame.fill_rectangle(
+                Point::new(0.0, row_top),
+                Size::new(bounds.width, ROW_HEIGHT),
+                palette.background.weak.color,
+            );
+            frame.fill_text(Text {
+                content,
+                position: Point::new(bounds.width / 2.0, row_top + half_row),
+                color,
+                size: (TEXT_SIZE - 1.0).into(),
+                font: style::AZERET_MONO,
+                align_x: Alignment::Center.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+        }
+    }
+
     fn draw_chase_trail(
         &self,
         frame: &mut iced::widget::canvas::Frame,
@@ -811,6 +1169,8 @@ impl Ladder {
                         y: top_y_screen,
                         buy_t: 0.0,
                         sell_t: 0.0,
+                        flow_buy: 0.0,
+                        flow_sell: 0.0,
                     });
                 }
                 continue;
@@ -835,6 +1195,8 @@ impl Ladder {
             maxima.vis_max_order_qty = maxima.vis_max_order_qty.max(order_qty);
             let (buy_t, sell_t) = self.trade_qty_at(price);
             maxima.vis_max_trade_qty = maxima.vis_max_trade_qty.max(buy_t.max(sell_t));
+            let (flow_buy, flow_sell) = self.trade_flow_at(price);
+            maxima.vis_max_flow_qty = maxima.vis_max_flow_qty.max(flow_buy.max(flow_sell));
 
             let row = if is_bid {
                 DomRow::Bid {
@@ -853,6 +1215,8 @@ impl Ladder {
                 y: top_y_screen,
                 buy_t,
                 sell_t,
+                flow_buy,
+                flow_sell,
             });
         }
 
@@ -860,6 +1224,14 @@ impl Ladder {
         (visible, maxima)
     }
 
+    /// Price of the row at a screen y, `None` over the spread row or an empty book
+    fn price_at(&self, y: f32, bounds_height: f32) -> Option<Price> {
+        let grid = self.build_price_grid()?;
+        let offset = y - bounds_height * 0.5 + self.scroll_px + ROW_HEIGHT / 2.0;
+
+        grid.index_to_price((offset / ROW_HEIGHT).floor() as i32)
+    }
+
     fn price_to_screen_y(&self, price: Price, grid: &PriceGrid, bounds_height: f32) -> Option<f32> {
         let mid_screen_y = bounds_height * 0.5;
         let scroll = self.scroll_px;
@@ -911,3 +1283,104 @@ impl PriceGrid {
         (idx as f32) * ROW_HEIGHT - ROW_HEIGHT * 0.5
     }
 }
+
+/// Rolling bid-ask spread in ticks, drawn above the ladder
+struct SpreadChart<'a> {
+    history: &'a SpreadHistory,
+}
+
+impl canvas::Program<Message> for SpreadChart<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: iced_core::mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let palette = theme.extended_palette();
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        let Some(latest) = self.history.latest() else {
+            return vec![frame.into_geometry()];
+        };
+
+        let chart_width = (bounds.width - SPREAD_LABEL_WIDTH).max(0.0);
+        let (top, bottom) = (4.0, bounds.height - 4.0);
+        // a one tick floor keeps a locked book from spanning the whole height
+        let max_ticks = self.history.max().max(1.0);
+
+        let start_time = latest.time.saturating_sub(SPREAD_HISTORY_WINDOW_MS);
+        let x_of = |time: u64| {
+            (time.saturating_sub(start_time)) as f32 / SPREAD_HISTORY_WINDOW_MS as f32 * chart_width
+        };
+        let y_of = |ticks: f32| bottom - (ticks / max_ticks).min(1.0) * (bottom - top);
+
+        if let Some(mean) = self.history.mean() {
+            let y = y_of(mean).floor() + 0.5;
+            frame.stroke(
+                &Path::line(Point::new(0.0, y), Point::new(chart_width, y)),
+                Stroke::default()
+                    .with_color(style::split_ruler(theme).color)
+                    .with_width(1.0),
+            );
+        }
+
+        // spread swings within a bucket
+        for sample in self.history.samples() {
+            if sample.max > sample.min {
+                let (y_max, y_min) = (y_of(sample.max), y_of(sample.min));
+                frame.fill_rectangle(
+                    Point::new(x_of(sample.time), y_max),
+                    Size::new(1.0, y_min - y_max),
+                    palette.secondary.weak.color,
+                );
+            }
+        }
+
+        let line = Path::new(|builder| {
+            for (idx, sample) in self.history.samples().iter().enumerate() {
+                let point = Point::new(x_of(sample.time), y_of(sample.last));
+                if idx == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default()
+                .with_color(palette.secondary.strong.color)
+                .with_width(1.0),
+        );
+
+        // the current spread, colored once it's wider than usual
+        let current_color = if self
+            .history
+            .mean()
+            .is_some_and(|mean| latest.last > mean * 1.5)
+        {
+            palette.warning.base.color
+        } else {
+            palette.primary.base.color
+        };
+        let current = Point::new(x_of(latest.time), y_of(latest.last));
+
+        frame.fill(&Path::circle(current, 2.5), current_color);
+        frame.fill_text(Text {
+            content: format!("{}t", latest.last),
+            position: Point::new(bounds.width - 4.0, bounds.height / 2.0),
+            color: current_color,
+            size: TEXT_SIZE.into(),
+            font: style::AZERET_MONO,
+            align_x: Alignment::End.into(),
+            align_y: Alignment::Center.into(),
+            ..Default::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
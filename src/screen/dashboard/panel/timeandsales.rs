@@ -2,7 +2,12 @@ use super::Message;
 use crate::style;
 use data::config::theme::{darken, lighten};
 pub use data::panel::timeandsales::Config;
-use data::panel::timeandsales::{HistAgg, StackedBar, StackedBarRatio, TradeDisplay, TradeEntry};
+use data::panel::timeandsales::{
+    HistAgg, MAX_PRINT_HISTORY, PRINT_HISTORY_MS, PrintRecord, StackedBar, StackedBarRatio,
+    TradeDisplay, TradeEntry,
+};
+use exchange::adapter::Exchange;
+use exchange::util::Price;
 use exchange::{TickerInfo, Trade, volume_size_unit};
 
 use iced::widget::canvas::{self, Text};
@@ -80,6 +85,12 @@ pub struct TimeAndSales {
     cache: canvas::Cache,
     last_tick: Instant,
     scroll_offset: f32,
+    /// Every print of the last `PRINT_HISTORY_MS`, unfiltered
+    print_history: VecDeque<PrintRecord>,
+    /// Trade time span of the bar hovered on a linked chart
+    highlight: Option<(u64, u64)>,
+    /// Price of the latest print, center of the price band filter
+    last_price: Option<Price>,
 }
 
 impl TimeAndSales {
@@ -95,12 +106,45 @@ impl TimeAndSales {
             cache: canvas::Cache::default(),
             last_tick: Instant::now(),
             scroll_offset: 0.0,
+            print_history: VecDeque::new(),
+            highlight: None,
+            last_price: None,
         }
     }
 
-    pub fn insert_buffer(&mut self, trades_buffer: &[Trade]) {
-        let size_filter = self.config.trade_size_filter;
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.max_filtered_qty = self.max_shown_qty();
+        self.cache.clear();
+    }
+
+    /// Size of a print in quote currency, as filters and size tiers compare it
+    fn quote_size(&self, trade: &TradeDisplay) -> f32 {
+        let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
+
+        self.ticker_info
+            .market_type()
+            .qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy)
+    }
+
+    fn is_shown(&self, trade: &TradeDisplay) -> bool {
+        self.config.shows(
+            self.quote_size(trade),
+            trade.price,
+            trade.is_sell,
+            self.last_price,
+        )
+    }
+
+    fn max_shown_qty(&self) -> f32 {
+        self.recent_trades
+            .iter()
+            .filter(|t| self.is_shown(&t.display))
+            .map(|t| t.display.qty)
+            .fold(0.0, f32::max)
+    }
 
+    pub fn insert_buffer(&mut self, trades_buffer: &[Trade], venue: Option<Exchange>) {
         let target_trades = if self.is_paused {
             &mut self.paused_trades_buffer
         } else {
@@ -117,33 +161,60 @@ impl TimeAndSales {
                 trade_time_ms as i64 / 1000,
                 (trade_time_ms % 1000) as u32 * 1_000_000,
             ) {
-                let trade_display = TradeDisplay {
-                    time_str: trade_time.format("%M:%S.%3f").to_string(),
-                    price: trade.price,
+                self.last_price = Some(trade.price);
+
+                self.print_history.push_back(PrintRecord {
+                    ts_ms: trade_time_ms,
                     qty: trade.qty,
                     is_sell: trade.is_sell,
-                };
+                });
 
-                let trade_size_value = market_type.qty_in_quote_value(
-                    trade_display.qty,
-                    trade.price,
-                    size_in_quote_ccy,
-                );
+                let is_clustered = self.config.cluster_window_ms.is_some_and(|window_ms| {
+                    target_trades.back().is_some_and(|last| {
+                        last.continues(trade.price, trade.is_sell, venue, trade_time_ms, window_ms)
+                    })
+                });
 
-                if trade_size_value >= size_filter {
-                    self.max_filtered_qty = self.max_filtered_qty.max(trade_display.qty);
+                if is_clustered && let Some(last) = target_trades.back_mut() {
+                    if !self.is_paused {
+                        self.hist_agg.remove(&last.display);
+                    }
+                    last.display.qty += trade.qty;
+                } else {
+                    target_trades.push_back(TradeEntry {
+                        ts_ms: trade_time_ms,
+                        display: TradeDisplay {
+                            time_str: trade_time.format("%M:%S.%3f").to_string(),
+                            price: trade.price,
+                            qty: trade.qty,
+                            is_sell: trade.is_sell,
+                            venue,
+                        },
+                    });
                 }
 
-                target_trades.push_back(TradeEntry {
-                    ts_ms: trade_time_ms,
-                    display: trade_display,
-                });
+                let Some(last) = target_trades.back() else {
+                    continue;
+                };
 
-                if !self.is_paused
-                    && let Some(last) = target_trades.back()
-                {
+                if !self.is_paused {
                     self.hist_agg.add(&last.display);
                 }
+
+                let print_size_value = market_type.qty_in_quote_value(
+                    last.display.qty,
+                    last.display.price,
+                    size_in_quote_ccy,
+                );
+
+                if self.config.shows(
+                    print_size_value,
+                    last.display.price,
+                    last.display.is_sell,
+                    self.last_price,
+                ) {
+                    self.max_filtered_qty = self.max_filtered_qty.max(last.display.qty);
+                }
             }
         }
 
@@ -151,6 +222,67 @@ impl TimeAndSales {
             self.prune_by_time(None);
         }
         self.prune_paused_by_time(None);
+        self.prune_print_history();
+    }
+
+    pub fn set_highlight(&mut self, highlight: Option<(u64, u64)>) {
+        if self.highlight != highlight {
+            self.highlight = highlight;
+            self.cache.clear();
+        }
+    }
+
+    /// Print count and buy/sell size within the highlighted span
+    fn highlight_summary(&self) -> Option<(usize, f32, f32)> {
+        let (from, to) = self.highlight?;
+
+        let start = self.print_history.partition_point(|p| p.ts_ms < from);
+        let (count, buy, sell) = self
+            .print_history
+            .range(start..)
+            .take_while(|p| p.ts_ms < to)
+            .fold((0, 0.0, 0.0), |(count, buy, sell), p| {
+                if p.is_sell {
+                    (count + 1, buy, sell + p.qty)
+                } else {
+                    (count + 1, buy + p.qty, sell)
+                }
+            });
+
+        Some((count, buy, sell))
+    }
+
+    fn prune_print_history(&mut self) {
+        let Some(newest) = self.print_history.back().map(|p| p.ts_ms) else {
+            return;
+        };
+        let cutoff = newest.saturating_sub(PRINT_HISTORY_MS);
+
+        while self
+            .print_history
+            .front()
+            .is_some_and(|p| p.ts_ms < cutoff || self.print_history.len() > MAX_PRINT_HISTORY)
+        {
+            self.print_history.pop_front();
+        }
+    }
+
+    /// Feed rows as drawn, newest first
+    fn feed(&self) -> impl Iterator<Item = &TradeEntry> {
+        self.recent_trades
+            .iter()
+            .filter(|t| self.is_shown(&t.display))
+            .rev()
+    }
+
+    /// Feed row under `y` of the canvas
+    fn entry_at(&self, y: f32) -> Option<&TradeEntry> {
+        let row_y = y + self.scroll_offset - self.stacked_bar_height();
+        if row_y < 0.0 {
+            return None;
+        }
+
+        self.feed().nth((row_y / TRADE_ROW_HEIGHT).floor() as usize)
     }
 
     pub fn last_update(&self) -> Instant {
@@ -206,8 +338,6 @@ impl TimeAndSales {
             return;
         }
 
-        let size_filter = self.config.trade_size_filter;
-
         let mut popped_any = false;
         while let Some(front) = self.recent_trades.front() {
             if front.ts_ms >= low_cutoff {
@@ -219,22 +349,7 @@ impl TimeAndSales {
         }
 
         if popped_any {
-            let market_type = self.ticker_info.market_type();
-            let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
-
-            self.max_filtered_qty = self
-                .recent_trades
-                .iter()
-                .filter(|t| {
-                    let trade_size = market_type.qty_in_quote_value(
-                        t.display.qty,
-                        t.display.price,
-                        size_in_quote_ccy,
-                    );
-                    trade_size >= size_filter
-                })
-                .map(|e| e.display.qty)
-                .fold(0.0, f32::max);
+            self.max_filtered_qty = self.max_shown_qty();
 
             let stacked_bar_h = self.stacked_bar_height();
             let total_content_height =
@@ -307,7 +422,10 @@ impl canvas::Program<Message> for TimeAndSales {
                         if self.is_paused && paused_box.contains(cursor_position) {
                             Some(canvas::Action::publish(Message::ResetScroll).and_capture())
                         } else {
-                            None
+                            self.entry_at(cursor_position.y).map(|entry| {
+                                canvas::Action::publish(Message::PrintClicked(entry.ts_ms))
+                                    .and_capture()
+                            })
                         }
                     }
                     _ => None,
@@ -342,8 +460,6 @@ impl canvas::Program<Message> for TimeAndSales {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
-        let market_type = self.ticker_info.market_type();
-
         let palette = theme.extended_palette();
         let is_scroll_paused = self.is_paused;
         let stacked_bar_h = self.stacked_bar_height();
@@ -445,22 +561,7 @@ impl canvas::Program<Message> for TimeAndSales {
             let start_index = (row_scroll_offset / row_height).floor() as usize;
             let visible_rows = (bounds.height / row_height).ceil() as usize;
 
-            let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
-
-            let trades_to_draw = self
-                .recent_trades
-                .iter()
-                .filter(|t| {
-                    let trade_size = market_type.qty_in_quote_value(
-                        t.display.qty,
-                        t.display.price,
-                        size_in_quote_ccy,
-                    );
-                    trade_size >= self.config.trade_size_filter
-                })
-                .rev()
-                .skip(start_index)
-                .take(visible_rows + 2);
+            let trades_to_draw = self.feed().skip(start_index).take(visible_rows + 2);
 
             let create_text =
                 |content: String, position: Point, align_x: Alignment, color: iced::Color| Text {
@@ -519,6 +620,58 @@ impl canvas::Program<Message> for TimeAndSales {
                     bg_color.scale_alpha(bg_color_alpha.min(0.9)),
                 );
 
+                if let Some(tier) = self.config.size_tiers.tier_of(self.quote_size(trade)) {
+                    let tier_color = match tier {
+                        0 => palette.primary.base.color,
+                        1 => palette.warning.base.color,
+                        _ => palette.secondary.strong.color,
+                    };
+
+                    frame.fill_rectangle(
+                        Point {
+                            x: 0.0,
+                            y: y_position,
+                        },
+                        Size {
+                            width: row_width,
+                            height: row_height,
+                        },
+                        tier_color.scale_alpha(0.45),
+                    );
+                    text_color = palette.background.base.text.scale_alpha(text_color.a);
+                }
+
+                if self
+                    .highlight
+                    .is_some_and(|(from, to)| (from..to).contains(&entry.ts_ms))
+                {
+                    frame.fill_rectangle(
+                        Point {
+                            x: 0.0,
+                            y: y_position,
+                        },
+                        Size {
+                            width: 3.0,
+                            height: row_height,
+                        },
+                        palette.primary.base.color,
+                    );
+                }
+
+                if let Some(venue) = trade.venue {
+                    frame.fill_rectangle(
+                        Point {
+                            x: row_width - 3.0,
+                            y: y_position,
+                        },
+                        Size {
+                            width: 3.0,
+                            height: row_height,
+                        },
+                        style::venue_color(venue),
+                    );
+                }
+
                 let trade_time = create_text(
                     trade.time_str.clone(),
                     Point {
@@ -553,6 +706,36 @@ impl canvas::Program<Message> for TimeAndSales {
                 frame.fill_text(trade_qty);
             }
 
+            if let Some((count, buy, sell)) = self.highlight_summary() {
+                let footer_y = bounds.height - TRADE_ROW_HEIGHT;
+
+                frame.fill_rectangle(
+                    Point {
+                        x: 0.0,
+                        y: footer_y,
+                    },
+                    Size {
+                        width: row_width,
+                        height: TRADE_ROW_HEIGHT,
+                    },
+                    palette.background.weak.color,
+                );
+
+                frame.fill_text(create_text(
+                    format!(
+                        "Bar: {count} prints, {} / {}",
+                        data::util::abbr_large_numbers(buy),
+                        data::util::abbr_large_numbers(sell),
+                    ),
+                    Point {
+                        x: 8.0,
+                        y: footer_y,
+                    },
+                    Alignment::Start,
+                    palette.background.weak.text,
+                ));
+            }
+
             if is_scroll_paused {
                 let pause_overlay_height = self.pause_overlay_height();
                 let pause_overlay_y = 0.0;
@@ -0,0 +1,305 @@
+use crate::chart::indicator::plot::bar::{BarClass, fill_bar};
+use crate::chart::indicator::plot::{PlotTooltip, YScale};
+use crate::style;
+
+use data::panel::trade_sizes::{BUCKET_COUNT, Bin, Config, Histogram, Window, bucket_bounds};
+use exchange::{TickerInfo, Trade, volume_size_unit};
+use iced::{
+    Alignment, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, mouse,
+    widget::{
+        canvas::{self, Cache, Geometry},
+        center, column, pick_list, row, space, text,
+    },
+};
+use std::time::Instant;
+
+const TEXT_SIZE: f32 = 11.0;
+const LABEL_HEIGHT: f32 = 16.0;
+const BAR_WIDTH_FACTOR: f32 = 0.8;
+/// Headroom above the tallest bar, as a share of it
+const BAR_PADDING: f32 = 0.1;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    WindowChanged(Window),
+    BucketClicked(usize),
+}
+
+pub enum Action {
+    /// Window changed and should be saved
+    ConfigChanged,
+    /// Time & Sales of the ticker should list only trades of this size range in quote
+    /// currency, or drop the range when `None`
+    FilterTape(TickerInfo, Option<(f32, Option<f32>)>),
+}
+
+/// Trades of one ticker counted by size over a rolling window, buys and sells apart
+pub struct TradeSizes {
+    pub config: Config,
+    ticker_info: TickerInfo,
+    histogram: Histogram,
+    /// Bins of the current window, by size ascending
+    bins: [Bin; BUCKET_COUNT],
+    /// Bucket the linked tape is filtered to
+    selected: Option<usize>,
+    cache: Cache,
+    last_tick: Instant,
+}
+
+impl TradeSizes {
+    pub fn new(config: Option<Config>, ticker_info: TickerInfo) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            ticker_info,
+            histogram: Histogram::default(),
+            bins: [Bin::default(); BUCKET_COUNT],
+            selected: None,
+            cache: Cache::default(),
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::WindowChanged(window) => {
+                self.config.window = window;
+                self.invalidate(Some(Instant::now()));
+                Some(Action::ConfigChanged)
+            }
+            Message::BucketClicked(bucket) => {
+                self.selected = if self.selected == Some(bucket) {
+                    None
+                } else {
+                    Some(bucket)
+                };
+                self.cache.clear();
+
+                Some(Action::FilterTape(
+                    self.ticker_info,
+                    self.selected.map(bucket_bounds),
+                ))
+            }
+        }
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.invalidate(Some(Instant::now()));
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.last_tick
+    }
+
+    pub fn insert_buffer(&mut self, trades_buffer: &[Trade]) {
+        let market_type = self.ticker_info.market_type();
+        let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
+
+        for trade in trades_buffer {
+            let size = market_type.qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy);
+            self.histogram.insert(trade.time, size, trade.is_sell);
+        }
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) {
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        // the longest window is kept so switching to it doesn't start over
+        self.histogram
+            .prune_before(now_ms.saturating_sub(Window::H1.duration_ms()));
+
+        self.bins = self
+            .histogram
+            .bins_since(now_ms.saturating_sub(self.config.window.duration_ms()));
+
+        self.cache.clear();
+        if let Some(now) = now {
+            self.last_tick = now;
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let total: u32 = self.bins.iter().map(Bin::count).sum();
+
+        let mut controls = row![
+            text("Window").size(12),
+            pick_list(
+                Window::ALL,
+                Some(self.config.window),
+                Message::WindowChanged
+            )
+            .text_size(12),
+            text(format!("{total} trades")).size(12),
+            space::horizontal(),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        if let Some(bucket) = self.selected {
+            controls = controls.push(
+                text(format!("Tape: {}", bucket_label(bucket)))
+                    .size(12)
+                    .style(style::secondary_text),
+            );
+        }
+
+        let histogram: Element<_> = if self.histogram.is_empty() {
+            center(text("Waiting for trades...").size(12)).into()
+        } else {
+            canvas::Canvas::new(self)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        };
+
+        column![controls, histogram].spacing(4).padding(4).into()
+    }
+
+    /// Bucket under `x`, with buckets spread evenly over `width`
+    fn bucket_at(x: f32, width: f32) -> Option<usize> {
+        let bucket = (x / (width / BUCKET_COUNT as f32)).floor();
+        (0.0..BUCKET_COUNT as f32)
+            .contains(&bucket)
+            .then_some(bucket as usize)
+    }
+}
+
+/// Size range of a bucket as in `$1k-3k`
+fn bucket_label(bucket: usize) -> String {
+    match bucket_bounds(bucket) {
+        (low, Some(high)) => format!(
+            "${}-{}",
+            data::util::abbr_large_numbers(low),
+            data::util::abbr_large_numbers(high)
+        ),
+        (low, None) => format!("${}+", data::util::abbr_large_numbers(low)),
+    }
+}
+
+impl canvas::Program<Message> for TradeSizes {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let cursor_position = cursor.position_in(bounds)?;
+
+                Self::bucket_at(cursor_position.x, bounds.width).map(|bucket| {
+                    canvas::Action::publish(Message::BucketClicked(bucket)).and_capture()
+                })
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let palette = theme.extended_palette();
+        let slot_width = bounds.width / BUCKET_COUNT as f32;
+        let bar_width = slot_width * BAR_WIDTH_FACTOR;
+
+        let max_count = self.bins.iter().map(Bin::count).max().unwrap_or_default();
+        let scale = YScale {
+            min: 0.0,
+            max: (max_count as f32 * (1.0 + BAR_PADDING)).max(1.0),
+            px_height: (bounds.height - LABEL_HEIGHT).max(0.0),
+        };
+
+        let bars = self.cache.draw(renderer, bounds.size(), |frame| {
+            for (bucket, bin) in self.bins.iter().enumerate() {
+                let slot_left = bucket as f32 * slot_width;
+
+                if self.selected == Some(bucket) {
+                    frame.fill_rectangle(
+                        Point::new(slot_left, 0.0),
+                        Size::new(slot_width, scale.px_height),
+                        palette.background.weak.color,
+                    );
+                }
+
+                fill_bar(
+                    frame,
+                    theme,
+                    BarClass::Overlay {
+                        overlay: bin.buys as f32 - bin.sells as f32,
+                    },
+                    (slot_left + (slot_width - bar_width) / 2.0, bar_width),
+                    &scale,
+                    (0.0, bin.count() as f32),
+                    1.0,
+                );
+
+                let (low, _) = bucket_bounds(bucket);
+                frame.fill_text(canvas::Text {
+                    content: data::util::abbr_large_numbers(low),
+                    position: Point::new(
+                        slot_left + slot_width / 2.0,
+                        bounds.height - LABEL_HEIGHT / 2.0,
+                    ),
+                    size: TEXT_SIZE.into(),
+                    color: palette.background.weak.text,
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..canvas::Text::default()
+                });
+            }
+        });
+
+        let mut geometries = vec![bars];
+
+        if let Some(cursor_position) = cursor.position_in(bounds)
+            && let Some(bucket) = Self::bucket_at(cursor_position.x, bounds.width)
+        {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+            let bin = &self.bins[bucket];
+
+            frame.fill_rectangle(
+                Point::new(bucket as f32 * slot_width, 0.0),
+                Size::new(slot_width, scale.px_height),
+                palette.background.strong.color.scale_alpha(0.2),
+            );
+
+            let tooltip = PlotTooltip::new(format!(
+                "{}\nBuys: {} (${})\nSells: {} (${})",
+                bucket_label(bucket),
+                bin.buys,
+                data::util::abbr_large_numbers(bin.buy_size),
+                bin.sells,
+                data::util::abbr_large_numbers(bin.sell_size),
+            ));
+            tooltip.draw(&mut frame, theme, bounds, cursor_position.x);
+
+            geometries.push(frame.into_geometry());
+        }
+
+        geometries
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
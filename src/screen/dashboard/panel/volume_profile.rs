@@ -0,0 +1,343 @@
+use super::Message;
+use crate::style;
+pub use data::panel::volume_profile::Config;
+use data::panel::volume_profile::{Level, Lookback, Profile, value_area};
+use exchange::adapter::ExchangeInclusive;
+use exchange::fetcher::{FetchRange, FetchRequests, FetchSpec, RequestHandler};
+use exchange::util::{Price, PriceStep};
+use exchange::{TickerInfo, Trade};
+
+use iced::task::Handle;
+use iced::widget::canvas::{self, Text};
+use iced::{Alignment, Event, Point, Rectangle, Renderer, Size, Theme, mouse};
+use std::time::Instant;
+
+const TEXT_SIZE: f32 = 11.0;
+const ROW_HEIGHT: f32 = 16.0;
+const HEADER_HEIGHT: f32 = 18.0;
+const PRICE_COL_WIDTH: f32 = 80.0;
+/// Kept while following a chart's visible range, since it can be moved anywhere in it
+const VISIBLE_RANGE_RETENTION_MS: u64 = 7 * 86_400_000;
+
+impl super::Panel for VolumeProfile {
+    fn scroll(&mut self, delta: f32) {
+        self.scroll_px += delta;
+        self.cache.clear();
+    }
+
+    fn reset_scroll(&mut self) {
+        self.scroll_px = 0.0;
+        self.cache.clear();
+    }
+
+    fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        VolumeProfile::invalidate(self, now)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.profile.is_empty()
+    }
+}
+
+/// Composite volume per price of one ticker over a lookback, built from live trades and
+/// trades backfilled for the lookback where the venue allows it
+pub struct VolumeProfile {
+    pub config: Config,
+    ticker_info: TickerInfo,
+    tick_size: PriceStep,
+    profile: Profile,
+    /// Rows of the current window, by price ascending
+    levels: Vec<Level>,
+    /// POC and value area bounds as indices into `levels`
+    value_area: Option<(usize, usize, usize)>,
+    /// Time window of a chart in the link group, for the visible range lookback
+    linked_window: Option<(u64, u64)>,
+    request_handler: RequestHandler,
+    fetching_trades: (bool, Option<Handle>),
+    /// Earliest time trades were requested back to
+    backfilled_from: Option<u64>,
+    scroll_px: f32,
+    cache: canvas::Cache,
+    last_tick: Instant,
+}
+
+impl VolumeProfile {
+    pub fn new(config: Option<Config>, ticker_info: TickerInfo, tick_size: f32) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            ticker_info,
+            tick_size: PriceStep::from_f32(tick_size),
+            profile: Profile::default(),
+            levels: vec![],
+            value_area: None,
+            linked_window: None,
+            request_handler: RequestHandler::new(),
+            fetching_trades: (false, None),
+            backfilled_from: None,
+            scroll_px: 0.0,
+            cache: canvas::Cache::default(),
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.invalidate(Some(Instant::now()));
+    }
+
+    pub fn tick_size(&self) -> f32 {
+        self.tick_size.to_f32_lossy()
+    }
+
+    pub fn set_tick_size(&mut self, tick_size: f32) {
+        self.tick_size = PriceStep::from_f32(tick_size);
+        self.scroll_px = 0.0;
+        self.invalidate(Some(Instant::now()));
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.last_tick
+    }
+
+    pub fn insert_buffer(&mut self, trades_buffer: &[Trade]) {
+        self.profile.insert_trades(trades_buffer);
+    }
+
+    pub fn insert_fetched_trades(&mut self, trades: &[Trade], is_batches_done: bool) {
+        self.profile.insert_trades(trades);
+
+        if is_batches_done {
+            self.fetching_trades = (false, None);
+        }
+    }
+
+    pub fn set_handle(&mut self, handle: Handle) {
+        self.fetching_trades.1 = Some(handle);
+    }
+
+    pub fn set_linked_window(&mut self, window: (u64, u64)) {
+        self.linked_window = Some(window);
+        if self.config.lookback == Lookback::VisibleRange {
+            self.invalidate(Some(Instant::now()));
+        }
+    }
+
+    /// Time window the profile sums, everything held while no chart reported one
+    fn window(&self, now_ms: u64) -> (u64, u64) {
+        match self.config.lookback.duration_ms() {
+            Some(duration) => (now_ms.saturating_sub(duration), now_ms),
+            None => self.linked_window.unwrap_or((0, now_ms)),
+        }
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let retention = self
+            .config
+            .lookback
+            .duration_ms()
+            .unwrap_or(VISIBLE_RANGE_RETENTION_MS);
+        self.profile.prune_before(now_ms.saturating_sub(retention));
+
+        let (from, to) = self.window(now_ms);
+        self.levels = self.profile.levels(from, to, self.tick_size);
+        self.value_area = value_area(&self.levels, self.config.value_area);
+
+        self.cache.clear();
+        if let Some(now) = now {
+            self.last_tick = now;
+        }
+
+        self.backfill_request(from, now_ms)
+            .map(super::Action::RequestFetch)
+    }
+
+    /// Trades before what was already requested, once the stream delivered a first one
+    fn backfill_request(&mut self, from: u64, now_ms: u64) -> Option<FetchRequests> {
+        let venue = ExchangeInclusive::of(self.ticker_info.exchange());
+        if self.fetching_trades.0 || !exchange::fetcher::is_trade_fetch_enabled(venue) {
+            return None;
+        }
+
+        let until = self.backfilled_from.or(self.profile.earliest())?;
+        let from = from.max(now_ms.saturating_sub(VISIBLE_RANGE_RETENTION_MS));
+        if from >= until {
+            return None;
+        }
+
+        let range = FetchRange::Trades(from, until);
+        match self.request_handler.add_request(range) {
+            Ok(Some(req_id)) => {
+                self.fetching_trades = (true, None);
+                self.backfilled_from = Some(from);
+
+                Some(FetchRequests::from([FetchSpec {
+                    req_id,
+                    fetch: range,
+                    stream: None,
+                }]))
+            }
+            Ok(None) => None,
+            Err(reason) => {
+                log::error!("Failed to request {:?}: {}", range, reason);
+                None
+            }
+        }
+    }
+
+    fn format_price(&self, price: Price) -> String {
+        price.to_string(self.ticker_info.min_ticksize)
+    }
+
+    /// Top of the highest row, so the POC sits at the middle before scrolling
+    fn first_row_y(&self, height: f32) -> f32 {
+        let poc_from_top = self
+            .value_area
+            .map_or(0, |(poc, _, _)| self.levels.len() - 1 - poc);
+        let body_mid = HEADER_HEIGHT + (height - HEADER_HEIGHT) / 2.0;
+
+        body_mid - (poc_from_top as f32 + 0.5) * ROW_HEIGHT - self.scroll_px
+    }
+}
+
+impl canvas::Program<Message> for VolumeProfile {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &iced::Event,
+        bounds: iced::Rectangle,
+        cursor: iced_core::mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        cursor.position_in(bounds)?;
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) => {
+                Some(canvas::Action::publish(Message::ResetScroll).and_capture())
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let scroll_amount = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => -(*y) * ROW_HEIGHT,
+                    mouse::ScrollDelta::Pixels { y, .. } => -*y,
+                };
+
+                Some(canvas::Action::publish(Message::Scrolled(scroll_amount)).and_capture())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: iced_core::mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let palette = theme.extended_palette();
+
+        let content = self.cache.draw(renderer, bounds.size(), |frame| {
+            let text = |content: String, position: Point, align_x: Alignment, color| Text {
+                content,
+                position,
+                size: TEXT_SIZE.into(),
+                font: style::AZERET_MONO,
+                color,
+                align_x: align_x.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            };
+
+            let max_total = self.levels.iter().map(Level::total).fold(0.0, f32::max);
+            let bar_width = (bounds.width - PRICE_COL_WIDTH).max(0.0);
+            let first_row_y = self.first_row_y(bounds.height);
+
+            for (from_top, level) in self.levels.iter().rev().enumerate() {
+                let y = first_row_y + from_top as f32 * ROW_HEIGHT;
+                if y + ROW_HEIGHT < HEADER_HEIGHT || y > bounds.height {
+                    continue;
+                }
+                let idx = self.levels.len() - 1 - from_top;
+
+                let in_value_area = self
+                    .value_area
+                    .is_some_and(|(_, low, high)| (low..=high).contains(&idx));
+                if in_value_area {
+                    frame.fill_rectangle(
+                        Point::new(0.0, y),
+                        Size::new(bounds.width, ROW_HEIGHT),
+                        palette.background.weak.color,
+                    );
+                }
+
+                if max_total > 0.0 {
+                    let buy_w = bar_width * level.buy / max_total;
+                    let sell_w = bar_width * level.sell / max_total;
+                    let bar_y = y + 1.0;
+                    let bar_h = ROW_HEIGHT - 2.0;
+
+                    frame.fill_rectangle(
+                        Point::new(PRICE_COL_WIDTH, bar_y),
+                        Size::new(buy_w, bar_h),
+                        palette.success.weak.color,
+                    );
+                    frame.fill_rectangle(
+                        Point::new(PRICE_COL_WIDTH + buy_w, bar_y),
+                        Size::new(sell_w, bar_h),
+                        palette.danger.weak.color,
+                    );
+                }
+
+                let is_poc = self.value_area.is_some_and(|(poc, _, _)| poc == idx);
+                let price_color = if is_poc {
+                    palette.primary.base.color
+                } else {
+                    palette.background.base.text
+                };
+
+                frame.fill_text(text(
+                    self.format_price(level.price),
+                    Point::new(PRICE_COL_WIDTH - 6.0, y + ROW_HEIGHT / 2.0),
+                    Alignment::End,
+                    price_color,
+                ));
+                frame.fill_text(text(
+                    data::util::abbr_large_numbers(level.total()),
+                    Point::new(PRICE_COL_WIDTH + 4.0, y + ROW_HEIGHT / 2.0),
+                    Alignment::Start,
+                    palette.background.base.text,
+                ));
+            }
+
+            frame.fill_rectangle(
+                Point::ORIGIN,
+                Size::new(bounds.width, HEADER_HEIGHT),
+                palette.background.base.color,
+            );
+
+            let mut header = self.config.lookback.to_string();
+            if let Some((poc, low, high)) = self.value_area {
+                header.push_str(&format!(
+                    " · POC {} · VA {}-{}",
+                    self.format_price(self.levels[poc].price),
+                    self.format_price(self.levels[low].price),
+                    self.format_price(self.levels[high].price),
+                ));
+            }
+            if self.fetching_trades.0 {
+                header.push_str(" · backfilling");
+            }
+
+            frame.fill_text(text(
+                header,
+                Point::new(6.0, HEADER_HEIGHT / 2.0),
+                Alignment::Start,
+                palette.background.weak.text,
+            ));
+        });
+
+        vec![content]
+    }
+}
@@ -0,0 +1,262 @@
+use crate::style::{self, Icon, icon_text};
+
+use data::panel::watchlist::{Config, SortColumn};
+use data::tickers_table::{PriceChangeDirection, TickerDisplayData, compute_display_data};
+use exchange::{Ticker, TickerInfo, TickerStats, adapter::Exchange};
+use iced::{
+    Alignment, Element, Length, Theme,
+    widget::{button, center, column, container, row, scrollable, space, text},
+};
+use rustc_hash::FxHashMap;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SortBy(SortColumn),
+    Remove(Ticker),
+    Open(TickerInfo),
+}
+
+pub enum Action {
+    /// Tickers or sorting changed and should be saved
+    ConfigChanged,
+    Open(TickerInfo),
+}
+
+/// Live price, 24h change and volume of user picked tickers
+pub struct Watchlist {
+    pub config: Config,
+    stats: FxHashMap<Ticker, TickerStats>,
+    /// Price before the latest refresh, to mark the digits that changed
+    prev_prices: FxHashMap<Ticker, f32>,
+    requested_at: Option<Instant>,
+}
+
+impl Watchlist {
+    pub fn new(config: Option<Config>) -> Self {
+        Self {
+            config: config.unwrap_or_default(),
+            stats: FxHashMap::default(),
+            prev_prices: FxHashMap::default(),
+            requested_at: None,
+        }
+    }
+
+    pub fn tickers(&self) -> &[TickerInfo] {
+        &self.config.tickers
+    }
+
+    pub fn add_ticker(&mut self, ticker_info: TickerInfo) {
+        let is_new = !self.config.tickers.contains(&ticker_info);
+        self.config.add(ticker_info);
+
+        // fetch right away instead of waiting for the next refresh
+        if is_new && !self.stats.contains_key(&ticker_info.ticker) {
+            self.requested_at = None;
+        }
+    }
+
+    pub fn remove_ticker(&mut self, ticker: Ticker) {
+        self.config.remove(ticker);
+        self.stats.remove(&ticker);
+        self.prev_prices.remove(&ticker);
+    }
+
+    /// Swaps a listed ticker for another, fetching the new one's stats right away
+    pub fn replace_ticker(&mut self, from: Ticker, to: TickerInfo) -> bool {
+        if !self.config.replace(from, to) {
+            return false;
+        }
+
+        self.stats.remove(&from);
+        self.prev_prices.remove(&from);
+        self.requested_at = None;
+        true
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::SortBy(column) => self.config.sort_by(column),
+            Message::Remove(ticker) => self.remove_ticker(ticker),
+            Message::Open(ticker_info) => return Some(Action::Open(ticker_info)),
+        }
+
+        Some(Action::ConfigChanged)
+    }
+
+    /// Venues to fetch ticker stats from, once every refresh interval
+    pub fn refresh_due(&mut self, now: Instant) -> Vec<Exchange> {
+        if self.config.tickers.is_empty()
+            || self
+                .requested_at
+                .is_some_and(|at| now.duration_since(at) < REFRESH_INTERVAL)
+        {
+            return vec![];
+        }
+        self.requested_at = Some(now);
+
+        let mut venues: Vec<Exchange> = vec![];
+        for ticker_info in &self.config.tickers {
+            let exchange = ticker_info.ticker.exchange;
+            if !venues.contains(&exchange) {
+                venues.push(exchange);
+            }
+        }
+        venues
+    }
+
+    /// Fetched stats of a whole venue, keeps the ones of listed tickers
+    pub fn insert_stats(&mut self, stats: &HashMap<Ticker, TickerStats>) {
+        for ticker_info in &self.config.tickers {
+            let ticker = ticker_info.ticker;
+
+            if let Some(new) = stats.get(&ticker)
+                && let Some(old) = self.stats.insert(ticker, *new)
+                && old.mark_price != new.mark_price
+            {
+                self.prev_prices.insert(ticker, old.mark_price);
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        if self.config.tickers.is_empty() {
+            return center(
+                column![
+                    text("Watchlist is empty").size(16),
+                    text("Add tickers from the ticker list above").size(12),
+                ]
+                .spacing(8)
+                .align_x(Alignment::Center),
+            )
+            .into();
+        }
+
+        let header_cell = |column: SortColumn, width: Length| {
+            let is_sorted = self.config.sort_by == column;
+            let icon = match (is_sorted, self.config.sort_desc) {
+                (false, _) => Icon::Sort,
+                (true, true) => Icon::SortDesc,
+                (true, false) => Icon::SortAsc,
+            };
+
+            button(row![text(column.label()).size(11), icon_text(icon, 10)].spacing(2))
+                .width(width)
+                .padding(2)
+                .on_press(Message::SortBy(column))
+                .style(move |theme, status| style::button::transparent(theme, status, is_sorted))
+        };
+
+        let header = row![
+            header_cell(SortColumn::Ticker, Length::FillPortion(3)),
+            header_cell(SortColumn::Price, Length::FillPortion(3)),
+            header_cell(SortColumn::Change, Length::FillPortion(2)),
+            header_cell(SortColumn::Volume, Length::FillPortion(2)),
+            space::horizontal().width(20),
+        ]
+        .spacing(2);
+
+        let rows = self
+            .config
+            .sorted(|ticker| self.stats.get(ticker).copied())
+            .into_iter()
+            .map(|(ticker_info, stats)| {
+                let display = stats.map(|stats| {
+                    let display = compute_display_data(
+                        &ticker_info.ticker,
+                        &stats,
+                        self.prev_prices.get(&ticker_info.ticker).copied(),
+                    );
+                    (stats.daily_price_chg >= 0.0, display)
+                });
+                watchlist_row(ticker_info, display)
+            });
+
+        container(
+            column![
+                header,
+                scrollable(column(rows).spacing(2)).style(style::scroll_bar),
+            ]
+            .spacing(4),
+        )
+        .padding(4)
+        .into()
+    }
+}
+
+fn watchlist_row<'a>(
+    ticker_info: TickerInfo,
+    display: Option<(bool, TickerDisplayData)>,
+) -> Element<'a, Message> {
+    let ticker = ticker_info.ticker;
+
+    let symbol = row![
+        icon_text(style::exchange_icon(ticker.exchange), 12),
+        text(ticker.display_symbol_and_type().0).size(12),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center)
+    .width(Length::FillPortion(3));
+
+    let cells: Element<_> = match display {
+        Some((is_up, display)) => {
+            let direction = display.price_change_direction;
+
+            row![
+                row![
+                    text(display.price_unchanged_part).size(12),
+                    text(display.price_changed_part)
+                        .size(12)
+                        .style(move |theme: &Theme| {
+                            let palette = theme.extended_palette();
+                            iced::widget::text::Style {
+                                color: Some(match direction {
+                                    PriceChangeDirection::Increased => palette.success.base.color,
+                                    PriceChangeDirection::Decreased => palette.danger.base.color,
+                                    PriceChangeDirection::Unchanged => palette.background.base.text,
+                                }),
+                            }
+                        }),
+                ]
+                .width(Length::FillPortion(3)),
+                text(display.daily_change_pct)
+                    .size(12)
+                    .style(move |theme: &Theme| {
+                        let palette = theme.extended_palette();
+                        iced::widget::text::Style {
+                            color: Some(if is_up {
+                                palette.success.base.color
+                            } else {
+                                palette.danger.base.color
+                            }),
+                        }
+                    })
+                    .width(Length::FillPortion(2)),
+                text(display.volume_display)
+                    .size(12)
+                    .width(Length::FillPortion(2)),
+            ]
+            .spacing(2)
+            .into()
+        }
+        None => text("…").size(12).width(Length::FillPortion(7)).into(),
+    };
+
+    let open_btn = button(row![symbol, cells].spacing(2).align_y(Alignment::Center))
+        .padding([2, 4])
+        .on_press(Message::Open(ticker_info))
+        .style(|theme, status| style::button::transparent(theme, status, false));
+
+    row![
+        open_btn,
+        button(icon_text(Icon::Close, 10))
+            .width(20)
+            .on_press(Message::Remove(ticker))
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
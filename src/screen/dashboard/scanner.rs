@@ -0,0 +1,393 @@
+use crate::style::{self, Icon, icon_text};
+
+use data::scanner::{self, Hit, Settings};
+use exchange::{
+    Kline, Ticker, TickerInfo, Timeframe,
+    scheduler::{self, Priority},
+};
+use iced::{
+    Alignment, Element, Length, Subscription, Task,
+    widget::{button, column, container, pick_list, row, rule, scrollable, space, text},
+};
+
+const CHECK_INTERVAL_SECS: u64 = 5;
+/// Time given to exchanges to finalize a bar before it's scanned
+const SETTLE_MS: u64 = 3_000;
+const MAX_HITS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Ticker,
+    Signal,
+    Value,
+    Time,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SettingsChanged(Settings),
+    Tick,
+    /// Bars fetched to judge the bar opened at the given time
+    Scanned(TickerInfo, u64, Result<Vec<Kline>, String>),
+    SortBy(SortColumn),
+    Dismiss(usize),
+    DismissAll,
+    OpenChart(Ticker),
+}
+
+pub enum Action {
+    Fetch(Task<Message>),
+    OpenChart(TickerInfo),
+}
+
+/// Checks the watchlist for user-defined conditions each time a bar closes
+pub struct Scanner {
+    pub settings: Settings,
+    hits: Vec<(TickerInfo, Hit)>,
+    sort_by: SortColumn,
+    sort_desc: bool,
+    last_scanned_bar: Option<u64>,
+}
+
+impl Scanner {
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            settings,
+            hits: vec![],
+            sort_by: SortColumn::Time,
+            sort_desc: true,
+            last_scanned_bar: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message, watchlist: &[TickerInfo]) -> Option<Action> {
+        match message {
+            Message::SettingsChanged(settings) => {
+                if settings.timeframe != self.settings.timeframe {
+                    self.last_scanned_bar = None;
+                }
+                self.settings = settings;
+            }
+            Message::Tick => {
+                if !self.settings.enabled || watchlist.is_empty() {
+                    return None;
+                }
+
+                let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+                let bar_time =
+                    scanner::last_closed_bar(self.settings.timeframe, now_ms - SETTLE_MS);
+
+                if self.last_scanned_bar == Some(bar_time) {
+                    return None;
+                }
+                self.last_scanned_bar = Some(bar_time);
+
+                let timeframe = self.settings.timeframe;
+                let range = scanner::fetch_range(&self.settings, bar_time);
+
+                let tasks = watchlist.iter().map(|ticker_info| {
+                    let ticker_info = *ticker_info;
+
+                    Task::perform(
+                        scheduler::fetch_klines(
+                            ticker_info,
+                            timeframe,
+                            Some(range),
+                            data::data_path(Some(super::KLINE_CACHE_DIR)),
+                            Priority::Prefetch,
+                        ),
+                        move |result| {
+                            let result = result.map_err(|err| err.to_string());
+                            Message::Scanned(ticker_info, bar_time, result)
+                        },
+                    )
+                });
+
+                return Some(Action::Fetch(Task::batch(tasks)));
+            }
+            Message::Scanned(ticker_info, bar_time, result) => {
+                // a timeframe change or a later bar made this one stale
+                if self.last_scanned_bar != Some(bar_time) {
+                    return None;
+                }
+
+                match result {
+                    Ok(mut klines) => {
+                        klines.sort_by_key(|k| k.time);
+
+                        for signal in scanner::scan(&self.settings, &klines, bar_time) {
+                            self.push_hit(
+                                ticker_info,
+                                Hit {
+                                    ticker: ticker_info.ticker,
+                                    signal,
+                                    time: bar_time,
+                                },
+                            );
+                        }
+                        self.sort_hits();
+                    }
+                    Err(err) => {
+                        log::warn!("Scanner fetch failed for {}: {err}", ticker_info.ticker)
+                    }
+                }
+            }
+            Message::SortBy(column) => {
+                if self.sort_by == column {
+                    self.sort_desc = !self.sort_desc;
+                } else {
+                    self.sort_by = column;
+                    self.sort_desc = true;
+                }
+                self.sort_hits();
+            }
+            Message::Dismiss(idx) => {
+                if idx < self.hits.len() {
+                    self.hits.remove(idx);
+                }
+            }
+            Message::DismissAll => self.hits.clear(),
+            Message::OpenChart(ticker) => {
+                return self
+                    .hits
+                    .iter()
+                    .find(|(_, hit)| hit.ticker == ticker)
+                    .map(|(ticker_info, _)| Action::OpenChart(*ticker_info));
+            }
+        }
+
+        None
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        if self.settings.enabled {
+            iced::time::every(std::time::Duration::from_secs(CHECK_INTERVAL_SECS))
+                .map(|_| Message::Tick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn push_hit(&mut self, ticker_info: TickerInfo, hit: Hit) {
+        let is_duplicate = self.hits.iter().any(|(_, h)| {
+            h.ticker == hit.ticker && h.time == hit.time && h.signal.label() == hit.signal.label()
+        });
+        if is_duplicate {
+            return;
+        }
+
+        if self.hits.len() >= MAX_HITS
+            && let Some(oldest) = self
+                .hits
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, h))| h.time)
+                .map(|(idx, _)| idx)
+        {
+            self.hits.remove(oldest);
+        }
+
+        self.hits.push((ticker_info, hit));
+    }
+
+    fn sort_hits(&mut self) {
+        let sort_by = self.sort_by;
+
+        self.hits.sort_by(|(_, a), (_, b)| {
+            let ordering = match sort_by {
+                SortColumn::Ticker => a.ticker.to_string().cmp(&b.ticker.to_string()),
+                SortColumn::Signal => a.signal.label().cmp(b.signal.label()),
+                SortColumn::Value => a.signal.value().total_cmp(&b.signal.value()),
+                SortColumn::Time => a.time.cmp(&b.time),
+            };
+            // ties keep the latest hits on top
+            ordering.then_with(|| b.time.cmp(&a.time))
+        });
+
+        if self.sort_desc {
+            self.hits.reverse();
+        }
+    }
+
+    pub fn view(&self, timezone: data::UserTimezone) -> Element<'_, Message> {
+        let settings = self.settings;
+
+        let enable_btn = button(
+            text(if settings.enabled {
+                "Running"
+            } else {
+                "Paused"
+            })
+            .size(12),
+        )
+        .on_press(Message::SettingsChanged(Settings {
+            enabled: !settings.enabled,
+            ..settings
+        }))
+        .style(move |theme, status| style::button::transparent(theme, status, settings.enabled));
+
+        let header = row![
+            text("Scanner").size(14),
+            space::horizontal(),
+            pick_list(
+                Settings::TIMEFRAME_CHOICES,
+                Some(settings.timeframe),
+                move |timeframe| Message::SettingsChanged(Settings {
+                    timeframe,
+                    ..settings
+                })
+            )
+            .text_size(12),
+            enable_btn,
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let conditions = column![
+            condition_row(
+                "Move of",
+                settings.price_change_pct,
+                &Settings::PRICE_CHANGE_CHOICES,
+                "%",
+                move |price_change_pct| Message::SettingsChanged(Settings {
+                    price_change_pct,
+                    ..settings
+                }),
+            ),
+            condition_row(
+                "Volume over",
+                settings.volume_multiple,
+                &Settings::VOLUME_MULTIPLE_CHOICES,
+                "x 20-bar avg",
+                move |volume_multiple| Message::SettingsChanged(Settings {
+                    volume_multiple,
+                    ..settings
+                }),
+            ),
+            button(text("New day high").size(12))
+                .on_press(Message::SettingsChanged(Settings {
+                    session_high: !settings.session_high,
+                    ..settings
+                }))
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, settings.session_high)
+                }),
+        ]
+        .spacing(4);
+
+        let column_header = |label: &'static str, column: SortColumn, width: Length| {
+            let is_sorted = self.sort_by == column;
+            let icon = match (is_sorted, self.sort_desc) {
+                (false, _) => Icon::Sort,
+                (true, true) => Icon::SortDesc,
+                (true, false) => Icon::SortAsc,
+            };
+
+            button(row![text(label).size(11), icon_text(icon, 10)].spacing(2))
+                .width(width)
+                .padding(2)
+                .on_press(Message::SortBy(column))
+                .style(move |theme, status| style::button::transparent(theme, status, is_sorted))
+        };
+
+        let table_header = row![
+            column_header("Ticker", SortColumn::Ticker, Length::FillPortion(3)),
+            column_header("Signal", SortColumn::Signal, Length::FillPortion(2)),
+            column_header("Value", SortColumn::Value, Length::FillPortion(2)),
+            column_header("Bar", SortColumn::Time, Length::FillPortion(2)),
+            space::horizontal().width(44),
+        ]
+        .spacing(2);
+
+        let results: Element<_> = if self.hits.is_empty() {
+            container(text("No hits yet").size(12)).padding(8).into()
+        } else {
+            let rows = self
+                .hits
+                .iter()
+                .enumerate()
+                .map(|(idx, (_, hit))| hit_row(idx, hit, settings.timeframe, timezone));
+
+            scrollable(column(rows).spacing(2))
+                .height(Length::Fixed(240.0))
+                .style(style::scroll_bar)
+                .into()
+        };
+
+        let dismiss_all = button(text("Dismiss all").size(12))
+            .on_press_maybe((!self.hits.is_empty()).then_some(Message::DismissAll))
+            .style(|theme, status| style::button::transparent(theme, status, false));
+
+        container(
+            column![
+                header,
+                conditions,
+                rule::horizontal(1.0).style(style::split_ruler),
+                table_header,
+                results,
+                dismiss_all,
+            ]
+            .spacing(8),
+        )
+        .width(Length::Fixed(360.0))
+        .padding(16)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
+
+fn condition_row<'a>(
+    label: &'a str,
+    value: Option<f32>,
+    choices: &'static [f32],
+    unit: &'a str,
+    on_change: impl Fn(Option<f32>) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let fallback = choices.first().copied().unwrap_or_default();
+
+    row![
+        button(text(label).size(12))
+            .on_press(on_change(match value {
+                Some(_) => None,
+                None => Some(fallback),
+            }))
+            .style(move |theme, status| style::button::transparent(theme, status, value.is_some())),
+        pick_list(choices, value, move |v| on_change(Some(v))).text_size(12),
+        text(unit).size(12),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn hit_row(
+    idx: usize,
+    hit: &Hit,
+    timeframe: Timeframe,
+    timezone: data::UserTimezone,
+) -> Element<'_, Message> {
+    let ticker = hit.ticker;
+    let bar_time = timezone.format_timestamp((hit.time / 1000) as i64, timeframe);
+
+    row![
+        text(ticker.to_string())
+            .size(12)
+            .width(Length::FillPortion(3)),
+        text(hit.signal.label())
+            .size(12)
+            .width(Length::FillPortion(2)),
+        text(hit.signal.to_string())
+            .size(12)
+            .width(Length::FillPortion(2)),
+        text(bar_time).size(12).width(Length::FillPortion(2)),
+        button(icon_text(Icon::ChartOutline, 12))
+            .on_press(Message::OpenChart(ticker))
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+        button(icon_text(Icon::Close, 12))
+            .on_press(Message::Dismiss(idx))
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+    ]
+    .spacing(2)
+    .align_y(Alignment::Center)
+    .into()
+}
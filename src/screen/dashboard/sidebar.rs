@@ -1,3 +1,4 @@
+use super::scanner::{self, Scanner};
 use super::tickers_table::{self, TickersTable};
 use crate::{
     TooltipPosition,
@@ -5,25 +6,30 @@ use crate::{
     style::{Icon, icon_text},
     widget::button_with_tooltip,
 };
+use data::connection::{ConnectionHealth, Level, LinkState};
 use data::sidebar;
 
 use iced::{
-    Alignment, Element, Subscription, Task,
+    Alignment, Element, Length, Subscription, Task,
     widget::responsive,
-    widget::{column, image, row, space},
+    widget::{button, column, container, image, row, space, text},
 };
 use rustc_hash::FxHashMap;
+use std::time::Instant;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ToggleSidebarMenu(Option<sidebar::Menu>),
     SetSidebarPosition(sidebar::Position),
     TickersTable(super::tickers_table::Message),
+    Scanner(scanner::Message),
+    ToggleLiveUpdates,
 }
 
 pub struct Sidebar {
     pub state: data::Sidebar,
     pub tickers_table: TickersTable,
+    pub scanner: Scanner,
 }
 
 pub enum Action {
@@ -32,6 +38,13 @@ pub enum Action {
         Option<data::layout::pane::ContentKind>,
     ),
     ErrorOccurred(data::InternalError),
+    OpenMovers(
+        Vec<(exchange::TickerInfo, f32)>,
+        data::layout::pane::ContentKind,
+    ),
+    OpenChart(exchange::TickerInfo, data::layout::pane::ContentKind),
+    ToggleLiveUpdates,
+    TickSizeChanged(Vec<(exchange::TickerInfo, exchange::TickerInfo)>),
 }
 
 impl Sidebar {
@@ -47,6 +60,7 @@ impl Sidebar {
             Self {
                 state: state.sidebar.clone(),
                 tickers_table,
+                scanner: Scanner::new(state.sidebar.scanner),
             },
             initial_fetch.map(Message::TickersTable),
         )
@@ -79,6 +93,34 @@ impl Sidebar {
                     Some(tickers_table::Action::FocusWidget(id)) => {
                         return (iced::widget::operation::focus(id), None);
                     }
+                    Some(tickers_table::Action::OpenMovers(movers, kind)) => {
+                        return (Task::none(), Some(Action::OpenMovers(movers, kind)));
+                    }
+                    Some(tickers_table::Action::TickSizeChanged(changed)) => {
+                        return (Task::none(), Some(Action::TickSizeChanged(changed)));
+                    }
+                    None => {}
+                }
+            }
+            Message::ToggleLiveUpdates => {
+                return (Task::none(), Some(Action::ToggleLiveUpdates));
+            }
+            Message::Scanner(msg) => {
+                let watchlist = self.watchlist();
+
+                match self.scanner.update(msg, &watchlist) {
+                    Some(scanner::Action::Fetch(task)) => {
+                        return (task.map(Message::Scanner), None);
+                    }
+                    Some(scanner::Action::OpenChart(ticker_info)) => {
+                        return (
+                            Task::none(),
+                            Some(Action::OpenChart(
+                                ticker_info,
+                                data::layout::pane::ContentKind::CandlestickChart,
+                            )),
+                        );
+                    }
                     None => {}
                 }
             }
@@ -87,7 +129,12 @@ impl Sidebar {
         (Task::none(), None)
     }
 
-    pub fn view(&self, audio_volume: Option<f32>) -> Element<'_, Message> {
+    pub fn view(
+        &self,
+        audio_volume: Option<f32>,
+        is_paused: bool,
+        health: &ConnectionHealth,
+    ) -> Element<'_, Message> {
         let state = &self.state;
 
         let tooltip_position = if state.position == sidebar::Position::Left {
@@ -98,7 +145,13 @@ impl Sidebar {
 
         let is_table_open = self.tickers_table.is_shown;
 
-        let nav_buttons = self.nav_buttons(is_table_open, audio_volume, tooltip_position);
+        let nav_buttons = self
+            .nav_buttons(is_table_open, audio_volume, is_paused, tooltip_position)
+            .push(connection_indicator(
+                health,
+                self.is_menu_active(sidebar::Menu::RateLimits),
+                tooltip_position,
+            ));
 
         let tickers_table = if is_table_open {
             column![responsive(move |size| self
@@ -118,14 +171,20 @@ impl Sidebar {
         .into()
     }
 
-    pub fn subscription(&self) -> Subscription<Message> {
-        self.tickers_table.subscription().map(Message::TickersTable)
+    pub fn subscription(&self, is_idle: bool) -> Subscription<Message> {
+        Subscription::batch([
+            self.tickers_table
+                .subscription(is_idle)
+                .map(Message::TickersTable),
+            self.scanner.subscription().map(Message::Scanner),
+        ])
     }
 
     fn nav_buttons(
         &self,
         is_table_open: bool,
         audio_volume: Option<f32>,
+        is_paused: bool,
         tooltip_position: TooltipPosition,
     ) -> iced::widget::Column<'_, Message> {
         let settings_modal_button = {
@@ -189,6 +248,35 @@ impl Sidebar {
             )
         };
 
+        let scanner_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Scanner);
+
+            button_with_tooltip(
+                icon_text(Icon::ChartOutline, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Scanner)),
+                Some("Scanner"),
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
+        let pause_btn = button_with_tooltip(
+            text(if is_paused { "\u{25B6}" } else { "\u{2016}" })
+                .size(14)
+                .width(24)
+                .align_x(Alignment::Center),
+            Message::ToggleLiveUpdates,
+            Some(if is_paused {
+                "Resume live updates"
+            } else {
+                "Pause live updates"
+            }),
+            tooltip_position,
+            move |theme, status| crate::style::button::transparent(theme, status, is_paused),
+        );
+
         let logo = {
             image(image::Handle::from_bytes(crate::style::LOGO_BYTES))
                 .width(28)
@@ -201,6 +289,8 @@ impl Sidebar {
             ticker_search_button,
             layout_modal_button,
             audio_btn,
+            scanner_btn,
+            pause_btn,
             space::vertical(),
             settings_modal_button,
         ]
@@ -241,9 +331,113 @@ impl Sidebar {
     pub fn sync_tickers_table_settings(&mut self) {
         let settings = &self.tickers_table.settings();
         self.state.tickers_table = Some(settings.clone());
+        self.state.scanner = self.scanner.settings;
+    }
+
+    /// Favorited tickers whose info has been fetched
+    fn watchlist(&self) -> Vec<exchange::TickerInfo> {
+        let tickers_info = self.tickers_info();
+
+        self.tickers_table
+            .favorited_tickers
+            .iter()
+            .filter_map(|ticker| tickers_info.get(ticker).copied().flatten())
+            .collect()
+    }
+
+    pub fn replace_favorite(&mut self, from: exchange::Ticker, to: exchange::Ticker) -> bool {
+        self.tickers_table.replace_favorite(from, to)
     }
 
     pub fn tickers_info(&self) -> &FxHashMap<exchange::Ticker, Option<exchange::TickerInfo>> {
         &self.tickers_table.tickers_info
     }
 }
+
+/// Dot colored by the worst exchange connection, detailing each of them on hover and
+/// opening the rate limits on click
+fn connection_indicator<'a>(
+    health: &ConnectionHealth,
+    is_active: bool,
+    tooltip_position: TooltipPosition,
+) -> Element<'a, Message> {
+    let now = Instant::now();
+    let level = health.level(now);
+
+    let dot =
+        container(space::horizontal())
+            .width(8)
+            .height(8)
+            .style(move |theme: &iced::Theme| {
+                let palette = theme.extended_palette();
+                let color = match level {
+                    Some(Level::Healthy) => palette.success.base.color,
+                    Some(Level::Stale) => palette.warning.base.color,
+                    Some(Level::Down) => palette.danger.base.color,
+                    None => palette.background.strong.color,
+                };
+
+                container::Style {
+                    background: Some(color.into()),
+                    border: iced::Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            });
+
+    let details = if level.is_none() {
+        column![text("No live streams")]
+    } else {
+        column(health.iter().map(|(exchange, health)| {
+            let state = match health.state {
+                LinkState::Connected => "connected".to_string(),
+                LinkState::Disconnected => "disconnected".to_string(),
+                LinkState::Reconnecting { attempt, retry_at } => format!(
+                    "retry {attempt} in {}s",
+                    retry_at.saturating_duration_since(now).as_secs()
+                ),
+            };
+
+            let mut stats = vec![format!("{:.0} msg/s", health.message_rate(now))];
+            if let Some(at) = health.last_message_at {
+                stats.push(format!(
+                    "last {:.1}s ago",
+                    now.duration_since(at).as_secs_f32()
+                ));
+            }
+            if let Some(latency) = health.latency {
+                stats.push(format!("ping {} ms", latency.as_millis()));
+            }
+            if let Some(latency) = health.rest_latency {
+                stats.push(format!("REST {} ms", latency.as_millis()));
+            }
+            if let Some(offset) = health.clock_offset_ms {
+                stats.push(format!("clock {offset:+} ms"));
+            }
+
+            column![
+                text(format!("{exchange}: {state}")),
+                text(stats.join(" · ")).size(11),
+            ]
+            .spacing(2)
+            .into()
+        }))
+        .spacing(6)
+    };
+
+    let details = details.push(text("Click for rate limits").size(11));
+
+    iced::widget::tooltip(
+        button(container(dot).center_x(Length::Fixed(24.0)).padding(4))
+            .padding(0)
+            .on_press(Message::ToggleSidebarMenu(Some(sidebar::Menu::RateLimits)))
+            .style(move |theme, status| {
+                crate::style::button::transparent(theme, status, is_active)
+            }),
+        container(details).style(crate::style::tooltip).padding(8),
+        tooltip_position,
+    )
+    .into()
+}
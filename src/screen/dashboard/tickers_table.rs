@@ -6,20 +6,22 @@ use data::{
     InternalError,
     layout::pane::ContentKind,
     tickers_table::{
-        PriceChangeDirection, Settings, SortOptions, TickerDisplayData, TickerRowData,
-        compute_display_data,
+        MoveTracker, MoverAlert, MoverPane, PriceChangeDirection, Settings, SortOptions,
+        TickerDisplayData, TickerRowData, compute_display_data, merge_stablecoin_stats,
     },
 };
 use exchange::{
     Ticker, TickerInfo, TickerStats,
     adapter::{Exchange, ExchangeInclusive, MarketKind, fetch_ticker_info, fetch_ticker_prices},
+    composite::{self, StablecoinMarket},
+    network::is_demo,
 };
 use iced::{
     Alignment, Element, Length, Renderer, Size, Subscription, Task, Theme,
     alignment::{self, Horizontal, Vertical},
     padding,
     widget::{
-        Button, Space, button, column, container, row, rule,
+        Button, Space, button, column, container, pick_list, row, rule,
         scrollable::{self, AbsoluteOffset},
         space, text, text_input,
     },
@@ -29,6 +31,8 @@ use std::{cmp::Ordering, collections::HashMap};
 
 const ACTIVE_UPDATE_INTERVAL: u64 = 13;
 const INACTIVE_UPDATE_INTERVAL: u64 = 300;
+/// Exchanges rarely change a symbol's precision, its metadata is refetched this often
+const TICKERS_INFO_REFRESH_INTERVAL: u64 = 1800;
 
 /// Number of extra cards to render for visibility during scrolling
 const OVERSCAN_BUFFER: isize = 3;
@@ -42,7 +46,7 @@ const SORT_AND_FILTER_HEIGHT: f32 = 200.0;
 
 const COMPACT_ROW_HEIGHT: f32 = 28.0;
 
-const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 5] = [
+const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 7] = [
     (ExchangeInclusive::Bybit, Exchange::BybitLinear, "Bybit"),
     (
         ExchangeInclusive::Binance,
@@ -56,11 +60,18 @@ const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 5] = [
     ),
     (ExchangeInclusive::Okex, Exchange::OkexLinear, "OKX"),
     (ExchangeInclusive::Forex, Exchange::Forex, "Forex"),
+    (
+        ExchangeInclusive::Deribit,
+        Exchange::DeribitInverse,
+        "Deribit",
+    ),
+    (ExchangeInclusive::Simulated, Exchange::Simulated, "Demo"),
 ];
 
 pub fn fetch_tickers_info() -> Task<Message> {
     let fetch_tasks = Exchange::ALL
         .iter()
+        .filter(|exchange| exchange.is_active())
         .map(|exchange| {
             Task::perform(fetch_ticker_info(*exchange), move |result| match result {
                 Ok(ticker_info) => Message::UpdateTickersInfo(*exchange, ticker_info),
@@ -77,6 +88,10 @@ pub enum Action {
     ErrorOccurred(data::InternalError),
     Fetch(Task<Message>),
     FocusWidget(iced::widget::Id),
+    /// Favorited tickers that moved past the mover alert threshold, with their % move
+    OpenMovers(Vec<(TickerInfo, f32)>, ContentKind),
+    /// Tickers whose tick size changed on a metadata refresh, as `(previous, fresh)`
+    TickSizeChanged(Vec<(TickerInfo, TickerInfo)>),
 }
 
 #[derive(Debug, Clone)]
@@ -92,7 +107,10 @@ pub enum Message {
     ToggleExchangeFilter(ExchangeInclusive),
     ToggleTable,
     ToggleFavorites,
+    ToggleStablecoinMerge,
+    MoverAlertChanged(MoverAlert),
     FetchForTickerStats(Option<Exchange>),
+    RefreshTickersInfo,
     UpdateTickersInfo(Exchange, HashMap<Ticker, Option<TickerInfo>>),
     UpdateTickerStats(Exchange, HashMap<Ticker, TickerStats>),
     ErrorOccurred(data::InternalError),
@@ -114,6 +132,13 @@ pub struct TickersTable {
     show_favorites: bool,
     row_index: FxHashMap<Ticker, usize>,
     pending_stats_batches: usize,
+    merge_stablecoin_quotes: bool,
+    /// Combined stats of each merged market, keyed by the listing that represents it
+    merged_stats: FxHashMap<Ticker, TickerStats>,
+    /// Listings folded into another row's merged market
+    merged_away: FxHashSet<Ticker>,
+    mover_alert: MoverAlert,
+    move_tracker: MoveTracker,
 }
 
 impl TickersTable {
@@ -134,11 +159,27 @@ impl TickersTable {
                 scroll_offset: AbsoluteOffset::default(),
                 is_shown: false,
                 tickers_info: FxHashMap::default(),
-                selected_exchanges: settings.selected_exchanges.iter().cloned().collect(),
-                selected_markets: settings.selected_markets.iter().cloned().collect(),
+                // filters saved outside demo mode would hide the simulated listings
+                selected_exchanges: settings
+                    .selected_exchanges
+                    .iter()
+                    .cloned()
+                    .chain(is_demo().then_some(ExchangeInclusive::Simulated))
+                    .collect(),
+                selected_markets: settings
+                    .selected_markets
+                    .iter()
+                    .cloned()
+                    .chain(is_demo().then_some(MarketKind::Spot))
+                    .collect(),
                 show_favorites: settings.show_favorites,
                 row_index: FxHashMap::default(),
                 pending_stats_batches: 0,
+                merge_stablecoin_quotes: settings.merge_stablecoin_quotes,
+                merged_stats: FxHashMap::default(),
+                merged_away: FxHashSet::default(),
+                mover_alert: settings.mover_alert,
+                move_tracker: MoveTracker::default(),
             },
             fetch_tickers_info(),
         )
@@ -151,6 +192,8 @@ impl TickersTable {
             selected_sort_option: self.selected_sort_option,
             selected_exchanges: self.selected_exchanges.iter().cloned().collect(),
             selected_markets: self.selected_markets.iter().cloned().collect(),
+            merge_stablecoin_quotes: self.merge_stablecoin_quotes,
+            mover_alert: self.mover_alert,
         }
     }
 
@@ -191,6 +234,13 @@ impl TickersTable {
             Message::ToggleFavorites => {
                 self.show_favorites = !self.show_favorites;
             }
+            Message::ToggleStablecoinMerge => {
+                self.merge_stablecoin_quotes = !self.merge_stablecoin_quotes;
+                self.rebuild_stablecoin_merges();
+            }
+            Message::MoverAlertChanged(alert) => {
+                self.mover_alert = alert;
+            }
             Message::TickerSelected(ticker, content) => {
                 let ticker_info = self.tickers_info.get(&ticker).cloned().flatten();
 
@@ -215,6 +265,8 @@ impl TickersTable {
                             compute_display_data(&row.ticker, &row.stats, None),
                         );
                     }
+                    self.merged_stats.clear();
+                    self.rebuild_stablecoin_merges();
 
                     return Some(Action::FocusWidget("full_ticker_search_box".into()));
                 }
@@ -253,7 +305,10 @@ impl TickersTable {
                 return Some(Action::Fetch(task));
             }
             Message::UpdateTickerStats(exchange, stats) => {
+                let movers = self.detect_movers(&stats);
+
                 self.update_ticker_rows(exchange, stats);
+                self.rebuild_stablecoin_merges();
 
                 if self.pending_stats_batches > 0 {
                     self.pending_stats_batches -= 1;
@@ -261,9 +316,21 @@ impl TickersTable {
                 if self.pending_stats_batches == 0 {
                     self.sort_ticker_rows();
                 }
+
+                if !movers.is_empty() {
+                    let kind = self.mover_alert.pane.content_kind();
+                    return Some(Action::OpenMovers(movers, kind));
+                }
+            }
+            Message::RefreshTickersInfo => {
+                return Some(Action::Fetch(fetch_tickers_info()));
             }
             Message::UpdateTickersInfo(exchange, info) => {
-                self.update_ticker_info(exchange, info);
+                let changed = self.update_ticker_info(exchange, info);
+                if !changed.is_empty() {
+                    // stats get picked up by the next periodic fetch
+                    return Some(Action::TickSizeChanged(changed));
+                }
 
                 let task =
                     Task::perform(fetch_ticker_prices(exchange), move |result| match result {
@@ -423,13 +490,23 @@ impl TickersTable {
         .into()
     }
 
-    pub fn subscription(&self) -> Subscription<Message> {
-        iced::time::every(std::time::Duration::from_secs(if self.is_shown {
+    pub fn subscription(&self, is_idle: bool) -> Subscription<Message> {
+        // mover alerts need frequent samples even while the table is hidden or nobody's around
+        let is_active = (self.is_shown && !is_idle) || self.mover_alert.enabled;
+
+        let stats = iced::time::every(std::time::Duration::from_secs(if is_active {
             ACTIVE_UPDATE_INTERVAL
         } else {
             INACTIVE_UPDATE_INTERVAL
         }))
-        .map(|_| Message::FetchForTickerStats(None))
+        .map(|_| Message::FetchForTickerStats(None));
+
+        let tickers_info = iced::time::every(std::time::Duration::from_secs(
+            TICKERS_INFO_REFRESH_INTERVAL,
+        ))
+        .map(|_| Message::RefreshTickersInfo);
+
+        Subscription::batch([stats, tickers_info])
     }
 
     fn sort_ticker_rows(&mut self) {
@@ -505,6 +582,21 @@ impl TickersTable {
         }
     }
 
+    /// Moves the favorite of `from` over to `to`, if it was favorited
+    pub fn replace_favorite(&mut self, from: Ticker, to: Ticker) -> bool {
+        if !self.favorited_tickers.remove(&from) {
+            return false;
+        }
+        self.favorited_tickers.insert(to);
+
+        for ticker in [from, to] {
+            if let Some(&idx) = self.row_index.get(&ticker) {
+                self.ticker_rows[idx].is_favorited = ticker == to;
+            }
+        }
+        true
+    }
+
     fn ticker_card_container<'a>(
         &self,
         exchange: Exchange,
@@ -526,6 +618,89 @@ impl TickersTable {
         }
     }
 
+    /// Favorited tickers in `stats` whose move crossed the mover alert threshold
+    fn detect_movers(&mut self, stats: &HashMap<Ticker, TickerStats>) -> Vec<(TickerInfo, f32)> {
+        if !self.mover_alert.enabled {
+            return vec![];
+        }
+
+        let favorited = &self.favorited_tickers;
+        self.move_tracker
+            .retain(|ticker| favorited.contains(ticker));
+
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let mut movers = vec![];
+
+        for (ticker, stats) in stats.iter().filter(|(t, _)| favorited.contains(*t)) {
+            if let Some(change_pct) =
+                self.move_tracker
+                    .record(*ticker, stats.mark_price, now_ms, &self.mover_alert)
+                && let Some(Some(ticker_info)) = self.tickers_info.get(ticker)
+            {
+                movers.push((*ticker_info, change_pct));
+            }
+        }
+
+        movers
+    }
+
+    fn mover_alert_controls(&self) -> Element<'_, Message> {
+        let alert = self.mover_alert;
+
+        let toggle = button(text("Open movers").align_x(Alignment::Center))
+            .on_press(Message::MoverAlertChanged(MoverAlert {
+                enabled: !alert.enabled,
+                ..alert
+            }))
+            .style(move |theme, status| style::button::transparent(theme, status, alert.enabled));
+
+        let options = row![
+            pick_list(
+                MoverAlert::THRESHOLD_CHOICES,
+                Some(alert.threshold_pct),
+                move |pct| {
+                    Message::MoverAlertChanged(MoverAlert {
+                        threshold_pct: pct,
+                        ..alert
+                    })
+                }
+            )
+            .text_size(12),
+            text("% in").size(12),
+            pick_list(
+                MoverAlert::WINDOW_CHOICES,
+                Some(alert.window_mins),
+                move |mins| {
+                    Message::MoverAlertChanged(MoverAlert {
+                        window_mins: mins,
+                        ..alert
+                    })
+                }
+            )
+            .text_size(12),
+            text("min").size(12),
+            pick_list(MoverPane::ALL, Some(alert.pane), move |pane| {
+                Message::MoverAlertChanged(MoverAlert { pane, ..alert })
+            })
+            .text_size(12),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        column![toggle, options]
+            .spacing(4)
+            .align_x(Alignment::Center)
+            .into()
+    }
+
+    fn stablecoin_merge_btn(&self) -> Button<'_, Message> {
+        let selected = self.merge_stablecoin_quotes;
+
+        button(text("Merge USD").align_x(Alignment::Center))
+            .on_press(Message::ToggleStablecoinMerge)
+            .style(move |theme, status| style::button::transparent(theme, status, selected))
+    }
+
     fn market_filter_btn<'a>(&'a self, label: &'a str, market: MarketKind) -> Button<'a, Message> {
         let selected = self.selected_markets.contains(&market);
 
@@ -567,14 +742,25 @@ impl TickersTable {
             .into()
     }
 
+    /// Returns the tickers whose tick size differs from what was stored, as `(previous, fresh)`
     fn update_ticker_info(
         &mut self,
         _exchange: Exchange,
         info: HashMap<Ticker, Option<TickerInfo>>,
-    ) {
+    ) -> Vec<(TickerInfo, TickerInfo)> {
+        let mut changed = vec![];
+
         for (ticker, ticker_info) in info.into_iter() {
+            if let Some(fresh) = ticker_info
+                && let Some(Some(previous)) = self.tickers_info.get(&ticker)
+                && previous.min_ticksize != fresh.min_ticksize
+            {
+                changed.push((*previous, fresh));
+            }
             self.tickers_info.insert(ticker, ticker_info);
         }
+
+        changed
     }
 
     fn update_ticker_rows(&mut self, exchange: Exchange, stats: HashMap<Ticker, TickerStats>) {
@@ -613,6 +799,75 @@ impl TickersTable {
         }
     }
 
+    /// Folds USDT/USDC/FDUSD listings of the same base into the row with the highest
+    /// volume, showing their volume-weighted price and summed volume
+    fn rebuild_stablecoin_merges(&mut self) {
+        let previous = std::mem::take(&mut self.merged_stats);
+        self.merged_away.clear();
+
+        if self.merge_stablecoin_quotes {
+            let mut markets: FxHashMap<StablecoinMarket, Vec<usize>> = FxHashMap::default();
+            for (idx, row) in self.ticker_rows.iter().enumerate() {
+                if let Some(market) = composite::stablecoin_market(&row.ticker) {
+                    markets.entry(market).or_default().push(idx);
+                }
+            }
+
+            for (market, members) in markets {
+                if members.len() < 2 {
+                    continue;
+                }
+
+                let stats: Vec<TickerStats> =
+                    members.iter().map(|&i| self.ticker_rows[i].stats).collect();
+                let Some(merged) = merge_stablecoin_stats(&stats) else {
+                    continue;
+                };
+
+                let Some(lead) = members.iter().copied().max_by(|&a, &b| {
+                    self.ticker_rows[a]
+                        .stats
+                        .daily_volume
+                        .total_cmp(&self.ticker_rows[b].stats.daily_volume)
+                }) else {
+                    continue;
+                };
+                let lead_ticker = self.ticker_rows[lead].ticker;
+
+                self.merged_away.extend(
+                    members
+                        .iter()
+                        .filter(|&&i| i != lead)
+                        .map(|&i| self.ticker_rows[i].ticker),
+                );
+
+                let previous_price = previous.get(&lead_ticker).map(|s| s.mark_price);
+                let mut display = compute_display_data(&lead_ticker, &merged, previous_price);
+                display.display_ticker = market.label();
+
+                self.display_cache.insert(lead_ticker, display);
+                self.merged_stats.insert(lead_ticker, merged);
+            }
+        }
+
+        // rows that no longer lead a merged market show their own stats again
+        for ticker in previous.keys() {
+            if !self.merged_stats.contains_key(ticker)
+                && let Some(&idx) = self.row_index.get(ticker)
+            {
+                self.display_cache.insert(
+                    *ticker,
+                    compute_display_data(ticker, &self.ticker_rows[idx].stats, None),
+                );
+            }
+        }
+    }
+
+    /// Stats shown for a row, merged across stablecoin quotes when it leads a merged market
+    fn row_stats<'a>(&'a self, row: &'a TickerRowData) -> &'a TickerStats {
+        self.merged_stats.get(&row.ticker).unwrap_or(&row.stats)
+    }
+
     fn sep_block_height(&self, fav_n: usize) -> f32 {
         if self.show_favorites {
             FAVORITES_SEPARATOR_HEIGHT
@@ -727,7 +982,10 @@ impl TickersTable {
 
         let exchange_filters = {
             let mut col = column![];
-            for (exchange_inclusive, exchange_logo, label) in EXCHANGE_FILTERS {
+            for (exchange_inclusive, exchange_logo, label) in EXCHANGE_FILTERS
+                .into_iter()
+                .filter(|(_, exchange_logo, _)| exchange_logo.is_active())
+            {
                 col = col.push(self.exchange_filter_btn(exchange_inclusive, exchange_logo, label));
             }
             col.spacing(4)
@@ -756,11 +1014,14 @@ impl TickersTable {
                 spot_market_button.width(Length::Fill),
                 linear_markets_btn.width(Length::Fill),
                 inverse_markets_btn.width(Length::Fill),
+                self.stablecoin_merge_btn().width(Length::Fill),
             ]
             .spacing(4),
             rule::horizontal(1.0).style(style::split_ruler),
             exchange_filters,
             rule::horizontal(1.0).style(style::split_ruler),
+            self.mover_alert_controls(),
+            rule::horizontal(1.0).style(style::split_ruler),
             text(if total == 0 {
                 "No tickers match filters".to_string()
             } else {
@@ -1038,6 +1299,7 @@ impl TickersTable {
                 .iter()
                 .filter(|row| {
                     row.is_favorited
+                        && !self.merged_away.contains(&row.ticker)
                         && !excluded.is_some_and(|ex| ex.contains(&row.ticker))
                         && matches_market(row)
                         && matches_exchange(row)
@@ -1052,16 +1314,13 @@ impl TickersTable {
         fav_rows.sort_by(|(a, ra), (b, rb)| {
             (ra.bucket, ra.pos)
                 .cmp(&(rb.bucket, rb.pos))
-                .then_with(|| match self.selected_sort_option {
-                    SortOptions::VolumeDesc => {
-                        b.stats.daily_volume.total_cmp(&a.stats.daily_volume)
-                    }
-                    SortOptions::VolumeAsc => a.stats.daily_volume.total_cmp(&b.stats.daily_volume),
-                    SortOptions::ChangeDesc => {
-                        b.stats.daily_price_chg.total_cmp(&a.stats.daily_price_chg)
-                    }
-                    SortOptions::ChangeAsc => {
-                        a.stats.daily_price_chg.total_cmp(&b.stats.daily_price_chg)
+                .then_with(|| {
+                    let (a, b) = (self.row_stats(a), self.row_stats(b));
+                    match self.selected_sort_option {
+                        SortOptions::VolumeDesc => b.daily_volume.total_cmp(&a.daily_volume),
+                        SortOptions::VolumeAsc => a.daily_volume.total_cmp(&b.daily_volume),
+                        SortOptions::ChangeDesc => b.daily_price_chg.total_cmp(&a.daily_price_chg),
+                        SortOptions::ChangeAsc => a.daily_price_chg.total_cmp(&b.daily_price_chg),
                     }
                 })
                 .then_with(|| ra.len.cmp(&rb.len))
@@ -1074,6 +1333,7 @@ impl TickersTable {
             .iter()
             .filter(|row| {
                 (!self.show_favorites || !row.is_favorited)
+                    && !self.merged_away.contains(&row.ticker)
                     && !excluded.is_some_and(|ex| ex.contains(&row.ticker))
                     && matches_market(row)
                     && matches_exchange(row)
@@ -1085,16 +1345,13 @@ impl TickersTable {
         rest_rows.sort_by(|(a, ra), (b, rb)| {
             (ra.bucket, ra.pos)
                 .cmp(&(rb.bucket, rb.pos))
-                .then_with(|| match self.selected_sort_option {
-                    SortOptions::VolumeDesc => {
-                        b.stats.daily_volume.total_cmp(&a.stats.daily_volume)
-                    }
-                    SortOptions::VolumeAsc => a.stats.daily_volume.total_cmp(&b.stats.daily_volume),
-                    SortOptions::ChangeDesc => {
-                        b.stats.daily_price_chg.total_cmp(&a.stats.daily_price_chg)
-                    }
-                    SortOptions::ChangeAsc => {
-                        a.stats.daily_price_chg.total_cmp(&b.stats.daily_price_chg)
+                .then_with(|| {
+                    let (a, b) = (self.row_stats(a), self.row_stats(b));
+                    match self.selected_sort_option {
+                        SortOptions::VolumeDesc => b.daily_volume.total_cmp(&a.daily_volume),
+                        SortOptions::VolumeAsc => a.daily_volume.total_cmp(&b.daily_volume),
+                        SortOptions::ChangeDesc => b.daily_price_chg.total_cmp(&a.daily_price_chg),
+                        SortOptions::ChangeAsc => a.daily_price_chg.total_cmp(&b.daily_price_chg),
                     }
                 })
                 .then_with(|| ra.len.cmp(&rb.len))
@@ -1334,6 +1591,9 @@ fn expanded_ticker_card<'a>(
             init_content_button(ContentKind::ComparisonChart, *ticker, 180.0),
             init_content_button(ContentKind::TimeAndSales, *ticker, 160.0),
             init_content_button(ContentKind::Ladder, *ticker, 160.0),
+            init_content_button(ContentKind::VolumeProfile, *ticker, 160.0),
+            init_content_button(ContentKind::DepthInspector, *ticker, 160.0),
+            init_content_button(ContentKind::TradeSizes, *ticker, 160.0),
         ]
         .width(Length::Fill)
         .spacing(2)
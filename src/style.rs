@@ -1,4 +1,4 @@
-use exchange::adapter::Exchange;
+use exchange::adapter::{Exchange, ExchangeInclusive};
 
 use iced::font::{Family, Stretch, Weight};
 use iced::theme::palette::Extended;
@@ -110,7 +110,23 @@ pub fn exchange_icon(exchange: Exchange) -> Icon {
         }
         Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => Icon::HyperliquidLogo,
         Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => Icon::OkexLogo,
-        Exchange::Forex => Icon::ChartOutline,
+        Exchange::Forex
+        | Exchange::DeribitLinear
+        | Exchange::DeribitInverse
+        | Exchange::Simulated => Icon::ChartOutline,
+    }
+}
+
+/// Tells apart prints of different venues in panes that merge them
+pub fn venue_color(exchange: Exchange) -> Color {
+    match ExchangeInclusive::of(exchange) {
+        ExchangeInclusive::Bybit => Color::from_rgb8(255, 110, 40),
+        ExchangeInclusive::Binance => Color::from_rgb8(240, 185, 11),
+        ExchangeInclusive::Hyperliquid => Color::from_rgb8(80, 210, 193),
+        ExchangeInclusive::Okex => Color::from_rgb8(210, 210, 210),
+        ExchangeInclusive::Forex => Color::from_rgb8(130, 130, 240),
+        ExchangeInclusive::Deribit => Color::from_rgb8(20, 160, 255),
+        ExchangeInclusive::Simulated => Color::from_rgb8(200, 120, 220),
     }
 }
 
@@ -122,6 +138,14 @@ pub fn title_text(theme: &Theme) -> iced::widget::text::Style {
     }
 }
 
+pub fn secondary_text(theme: &Theme) -> iced::widget::text::Style {
+    let palette = theme.extended_palette();
+
+    iced::widget::text::Style {
+        color: Some(palette.background.strong.color),
+    }
+}
+
 pub fn tooltip(theme: &Theme) -> Style {
     let palette = theme.extended_palette();
 
@@ -25,6 +25,8 @@ pub struct Series {
     pub name: Option<String>,
     pub points: Vec<(u64, f32)>,
     pub color: iced::Color,
+    /// Computed from two other series, named after its legs
+    pub derived: bool,
 }
 
 impl Series {
@@ -34,6 +36,7 @@ impl Series {
             name,
             points: Vec::new(),
             color,
+            derived: false,
         }
     }
 }
@@ -43,6 +46,16 @@ pub trait SeriesLike {
     fn points(&self) -> &[(u64, f32)];
     fn color(&self) -> iced::Color;
     fn ticker_info(&self) -> &TickerInfo;
+
+    /// Text of the legend row
+    fn label(&self) -> String {
+        self.ticker_info().ticker.symbol_and_exchange_string()
+    }
+
+    /// Can't be edited or removed from the legend
+    fn is_derived(&self) -> bool {
+        false
+    }
 }
 
 impl SeriesLike for Series {
@@ -65,12 +78,28 @@ impl SeriesLike for Series {
     fn ticker_info(&self) -> &TickerInfo {
         &self.ticker_info
     }
+
+    fn label(&self) -> String {
+        if self.derived {
+            self.name()
+        } else {
+            self.ticker_info.ticker.symbol_and_exchange_string()
+        }
+    }
+
+    fn is_derived(&self) -> bool {
+        self.derived
+    }
 }
 
 /// Compute a "nice" step close to range/target using 1/2/5*10^k
 fn nice_step(range: f32, target: usize) -> f32 {
     let target = target.max(2) as f32;
-    let raw = (range / target).max(f32::EPSILON);
+    // keeps `nice * base` finite for infinite or huge ranges, clamp lets NaN through
+    let raw = match range / target {
+        raw if raw.is_nan() => f32::EPSILON,
+        raw => raw.clamp(f32::EPSILON, f32::MAX / 10.0),
+    };
     let power = raw.log10().floor();
     let base = 10f32.powf(power);
     let n = raw / base;
@@ -89,8 +118,13 @@ fn nice_step(range: f32, target: usize) -> f32 {
 fn ticks(min: f32, max: f32, target: usize) -> (Vec<f32>, f32) {
     let span = (max - min).abs().max(1e-6);
     let step = nice_step(span, target);
-    let start = (min / step).floor() * step;
-    let end = (max / step).ceil() * step;
+    if !min.is_finite() || !max.is_finite() {
+        return (Vec::new(), step);
+    }
+
+    // rounding out to the step can overflow near the ends of the f32 range
+    let start = ((min / step).floor() * step).clamp(f32::MIN, f32::MAX);
+    let end = ((max / step).ceil() * step).clamp(f32::MIN, f32::MAX);
 
     let mut v = Vec::new();
     let mut t = start;
@@ -99,7 +133,13 @@ fn ticks(min: f32, max: f32, target: usize) -> (Vec<f32>, f32) {
             break;
         }
         v.push(t);
-        t += step;
+
+        let next = t + step;
+        // step is below the precision of `t`, it would repeat the same tick
+        if !next.is_finite() || next <= t {
+            break;
+        }
+        t = next;
     }
     (v, step)
 }
@@ -192,10 +232,8 @@ fn time_ticks(min_x: u64, max_x: u64, px_per_ms: f32, min_px: f32) -> (Vec<u64>,
         }
     }
     // Align first tick to the step boundary >= min_x
-    let first = if min_x.is_multiple_of(step) {
-        min_x
-    } else {
-        (min_x / step + 1) * step
+    let Some(first) = min_x.div_ceil(step).checked_mul(step) else {
+        return (Vec::new(), step);
     };
     let mut out = Vec::new();
     let mut t = first;
@@ -204,10 +242,14 @@ fn time_ticks(min_x: u64, max_x: u64, px_per_ms: f32, min_px: f32) -> (Vec<u64>,
             break;
         }
         out.push(t);
-        t = t.saturating_add(step);
-        if (t - first) > span + step {
+
+        let Some(next) = t.checked_add(step) else {
+            break;
+        };
+        if (next - first) > span.saturating_add(step) {
             break;
         }
+        t = next;
     }
     (out, step)
 }
@@ -249,6 +291,65 @@ pub mod domain {
         })
     }
 
+    /// Pearson correlation of bar-to-bar returns between `min_x` and `max_x`,
+    /// over the timestamps both series have a point at
+    pub fn correlation(a: &[(u64, f32)], b: &[(u64, f32)], min_x: u64, max_x: u64) -> Option<f32> {
+        let visible = |pts: &[(u64, f32)]| {
+            let start = pts.partition_point(|(x, _)| *x < min_x);
+            let end = pts.partition_point(|(x, _)| *x <= max_x);
+            pts[start..end.max(start)].to_vec()
+        };
+        let (a, b) = (visible(a), visible(b));
+
+        let mut pairs: Vec<(f32, f32)> = Vec::with_capacity(a.len().min(b.len()));
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].0.cmp(&b[j].0) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    pairs.push((a[i].1, b[j].1));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        let returns: Vec<(f64, f64)> = pairs
+            .windows(2)
+            .filter(|w| w[0].0 != 0.0 && w[0].1 != 0.0)
+            .map(|w| {
+                (
+                    f64::from(w[1].0 / w[0].0) - 1.0,
+                    f64::from(w[1].1 / w[0].1) - 1.0,
+                )
+            })
+            .filter(|(ra, rb)| ra.is_finite() && rb.is_finite())
+            .collect();
+        if returns.len() < 3 {
+            return None;
+        }
+
+        let n = returns.len() as f64;
+        let mean_a = returns.iter().map(|(ra, _)| ra).sum::<f64>() / n;
+        let mean_b = returns.iter().map(|(_, rb)| rb).sum::<f64>() / n;
+
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for (ra, rb) in &returns {
+            let (da, db) = (ra - mean_a, rb - mean_b);
+            cov += da * db;
+            var_a += da * da;
+            var_b += db * db;
+        }
+
+        let denom = (var_a * var_b).sqrt();
+        if denom <= f64::EPSILON {
+            return None;
+        }
+
+        Some((cov / denom).clamp(-1.0, 1.0) as f32)
+    }
+
     pub fn window(
         series: &[&[(u64, f32)]],
         zoom: super::Zoom,
@@ -284,7 +385,7 @@ pub mod domain {
             if d >= 0 {
                 v.saturating_add(d as u64)
             } else {
-                v.saturating_sub((-d) as u64)
+                v.saturating_sub(d.unsigned_abs())
             }
         };
 
@@ -308,12 +409,22 @@ pub mod domain {
         let left = right.saturating_sub(span);
 
         let left = align_floor(left, dt);
-        let right = align_ceil(right, dt);
+        let mut right = align_ceil(right, dt);
+        // panned all the way to 0, keep a span to map x onto
+        if right <= left {
+            right = left.saturating_add(dt.max(1));
+        }
 
         Some((left, right))
     }
 
-    pub fn pct_domain(series: &[&[(u64, f32)]], min_x: u64, max_x: u64) -> Option<(f32, f32)> {
+    /// Range of the change from `base_x` over the points between `min_x` and `max_x`
+    pub fn pct_domain(
+        series: &[&[(u64, f32)]],
+        base_x: u64,
+        min_x: u64,
+        max_x: u64,
+    ) -> Option<(f32, f32)> {
         let mut min_pct = f32::INFINITY;
         let mut max_pct = f32::NEG_INFINITY;
         let mut any = false;
@@ -323,15 +434,18 @@ pub mod domain {
                 continue;
             }
 
-            let y0 = interpolate_y_at(pts, min_x).unwrap_or(0.0);
-            if y0 == 0.0 {
+            let y0 = interpolate_y_at(pts, base_x).unwrap_or(0.0);
+            if y0 == 0.0 || !y0.is_finite() {
                 continue;
             }
 
             let mut has_visible = false;
             for (_x, y) in pts.iter().filter(|(x, _)| *x >= min_x && *x <= max_x) {
-                has_visible = true;
                 let pct = ((*y / y0) - 1.0) * 100.0;
+                if !pct.is_finite() {
+                    continue;
+                }
+                has_visible = true;
                 if pct < min_pct {
                     min_pct = pct;
                 }
@@ -361,7 +475,245 @@ pub mod domain {
         }
 
         let span = (max_pct - min_pct).max(1e-6);
-        let pad = span * 0.05;
+        let pad = if span.is_finite() { span * 0.05 } else { 0.0 };
         Some((min_pct - pad, max_pct + pad))
     }
+
+    /// Largest-triangle-three-buckets: `threshold` of the points, keeping the ends and, from each
+    /// bucket in between, the one spanning the largest triangle with the last kept point and the
+    /// next bucket's average, so peaks and troughs survive the thinning
+    pub fn lttb(points: &[(u64, f32)], threshold: usize) -> Vec<(u64, f32)> {
+        let n = points.len();
+        if n <= threshold.max(2) {
+            return points.to_vec();
+        }
+        if threshold < 3 {
+            return vec![points[0], points[n - 1]];
+        }
+
+        // relative to the first point, so the area products stay well within f64 precision
+        let origin = points[0].0;
+        let xy = |(x, y): (u64, f32)| ((x - origin) as f64, f64::from(y));
+
+        let every = (n - 2) as f64 / (threshold - 2) as f64;
+        let mut sampled = Vec::with_capacity(threshold);
+        sampled.push(points[0]);
+        let mut kept = 0;
+
+        for bucket in 0..threshold - 2 {
+            let start = (bucket as f64 * every) as usize + 1;
+            let end = (((bucket + 1) as f64 * every) as usize + 1).min(n - 1);
+            let next_end = (((bucket + 2) as f64 * every) as usize + 1).min(n);
+
+            let next = &points[end..next_end.max(end + 1)];
+            let (sum_x, sum_y) = next.iter().fold((0.0, 0.0), |(sx, sy), p| {
+                let (x, y) = xy(*p);
+                (sx + x, sy + y)
+            });
+            let (avg_x, avg_y) = (sum_x / next.len() as f64, sum_y / next.len() as f64);
+
+            let (ax, ay) = xy(points[kept]);
+            let mut best = (start, f64::NEG_INFINITY);
+            for (idx, p) in points
+                .iter()
+                .enumerate()
+                .take(end.max(start + 1))
+                .skip(start)
+            {
+                let (x, y) = xy(*p);
+                let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+                if area > best.1 {
+                    best = (idx, area);
+                }
+            }
+
+            kept = best.0;
+            sampled.push(points[kept]);
+        }
+
+        sampled.push(points[n - 1]);
+        sampled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::domain::{correlation, interpolate_y_at, lttb, pct_domain, window};
+    use super::*;
+    use proptest::prelude::*;
+
+    const DAY_MS: u64 = 86_400_000;
+
+    /// Sorted, deduplicated timestamps with prices, anywhere in the u64 range
+    fn series(max_len: usize) -> impl Strategy<Value = Vec<(u64, f32)>> {
+        (
+            prop_oneof![Just(0u64), Just(u64::MAX - DAY_MS), any::<u64>()],
+            prop::collection::vec((1u64..DAY_MS, 0.0001f32..1e9), 1..max_len),
+        )
+            .prop_map(|(start, steps)| {
+                let mut x = start;
+                steps
+                    .into_iter()
+                    .map(|(dx, y)| {
+                        x = x.saturating_add(dx);
+                        (x, y)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .prop_map(|mut pts| {
+                pts.dedup_by_key(|(x, _)| *x);
+                pts
+            })
+    }
+
+    fn any_f32() -> impl Strategy<Value = f32> {
+        prop_oneof![
+            Just(0.0f32),
+            Just(f32::NAN),
+            Just(f32::INFINITY),
+            Just(f32::MAX),
+            Just(f32::MIN_POSITIVE),
+            any::<f32>(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn nice_step_is_finite_and_positive(range in any_f32(), target in 0usize..200) {
+            let step = nice_step(range, target);
+
+            prop_assert!(step.is_finite() && step > 0.0, "step {step} for range {range}");
+        }
+
+        #[test]
+        fn ticks_never_label_nan(min in any_f32(), max in any_f32(), target in 0usize..50) {
+            let (ticks, step) = ticks(min, max, target);
+
+            prop_assert!(step.is_finite());
+            prop_assert!(ticks.iter().all(|t| t.is_finite()));
+            prop_assert!(ticks.windows(2).all(|w| w[0] < w[1]));
+            prop_assert!(ticks.iter().all(|t| !format_pct(*t, step, true).contains("NaN")));
+        }
+
+        #[test]
+        fn time_ticks_are_aligned_and_increasing(
+            min_x in any::<u64>(),
+            span in prop_oneof![Just(0u64), Just(u64::MAX), 0u64..365 * DAY_MS],
+            px_per_ms in any_f32(),
+        ) {
+            let max_x = min_x.saturating_add(span);
+            let (ticks, step) = time_ticks(min_x, max_x, px_per_ms, 80.0);
+
+            prop_assert!(step > 0);
+            prop_assert!(ticks.len() <= 2001);
+            prop_assert!(ticks.windows(2).all(|w| w[0] < w[1]));
+            prop_assert!(ticks.iter().all(|t| (min_x..=max_x).contains(t)));
+            prop_assert!(ticks.iter().all(|t| t.is_multiple_of(step)));
+        }
+
+        #[test]
+        fn interpolation_stays_between_neighbours(pts in series(32), x in any::<u64>()) {
+            let last = pts.last().unwrap().0;
+
+            match interpolate_y_at(&pts, x) {
+                None => prop_assert!(x > last),
+                Some(y) => {
+                    prop_assert!(y.is_finite());
+
+                    let i = pts.iter().position(|(px, _)| *px >= x).unwrap();
+                    let (lo, hi) = if i == 0 {
+                        (pts[0].1, pts[0].1)
+                    } else {
+                        (pts[i - 1].1.min(pts[i].1), pts[i - 1].1.max(pts[i].1))
+                    };
+                    // rounding of the lerp itself
+                    let tol = lo.abs().max(hi.abs()) * 1e-6;
+                    prop_assert!(y >= lo - tol && y <= hi + tol, "{y} outside {lo}..={hi}");
+                }
+            }
+        }
+
+        #[test]
+        fn window_keeps_a_span(
+            pts in series(32),
+            zoom in 0usize..500,
+            pan_points in prop_oneof![Just(f32::NAN), Just(-1e12f32), Just(1e12f32), -500.0f32..500.0],
+            dt in prop_oneof![Just(0u64), Just(1_000u64), Just(60_000u64), Just(DAY_MS)],
+        ) {
+            let (left, right) = window(&[&pts], Zoom::points(zoom), pan_points, dt).unwrap();
+
+            prop_assert!(left < right, "empty window {left}..{right}");
+        }
+
+        #[test]
+        fn lttb_keeps_the_ends_within_budget(pts in series(400), threshold in 0usize..300) {
+            let sampled = lttb(&pts, threshold);
+
+            prop_assert_eq!(sampled.first(), pts.first());
+            prop_assert_eq!(sampled.last(), pts.last());
+            prop_assert!(sampled.len() <= pts.len().min(threshold.max(2)));
+            // a subsequence of the input, in order
+            let mut rest = pts.iter();
+            prop_assert!(sampled.iter().all(|p| rest.any(|q| q == p)));
+        }
+
+        #[test]
+        fn single_point_has_a_window(x in any::<u64>(), y in 0.0001f32..1e9) {
+            let pts = [(x, y)];
+            let (left, right) = window(&[&pts], Zoom::all(), 0.0, 60_000).unwrap();
+
+            prop_assert!(left < right);
+            prop_assert!(pct_domain(&[&pts], left, left, right).is_some_and(|(lo, hi)| lo < hi));
+        }
+
+        #[test]
+        fn pct_domain_is_finite_and_contains_zero(
+            pts in series(32),
+            y in any_f32(),
+            from in any::<u64>(),
+            span in any::<u64>(),
+        ) {
+            let mut pts = pts;
+            // a corrupt print somewhere in the series
+            let mid = pts.len() / 2;
+            pts[mid].1 = y;
+
+            let to = from.saturating_add(span);
+
+            if let Some((lo, hi)) = pct_domain(&[&pts], from, from, to) {
+                prop_assert!(lo.is_finite() && hi.is_finite(), "{lo}..{hi}");
+                prop_assert!(lo < hi);
+                prop_assert!(lo <= 0.0 && hi >= 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_series_have_no_domain() {
+        assert_eq!(window(&[], Zoom::all(), 0.0, 1_000), None);
+        assert_eq!(window(&[&[]], Zoom::all(), 0.0, 1_000), None);
+        assert_eq!(pct_domain(&[&[]], 0, 0, u64::MAX), None);
+        assert_eq!(interpolate_y_at(&[], 0), None);
+    }
+
+    #[test]
+    fn correlation_of_returns_over_shared_timestamps() {
+        let a = [(0, 100.0), (1, 110.0), (2, 99.0), (3, 120.0), (4, 118.0)];
+        let same_moves: Vec<(u64, f32)> = a.iter().map(|(x, y)| (*x, y * 2.0)).collect();
+        let opposite = [(0, 100.0), (1, 90.0), (2, 99.0), (3, 80.0), (4, 82.0)];
+
+        assert!((correlation(&a, &same_moves, 0, 4).unwrap() - 1.0).abs() < 1e-4);
+        assert!(correlation(&a, &opposite, 0, 4).unwrap() < -0.9);
+        // too few shared bars in the window
+        assert_eq!(correlation(&a, &same_moves, 3, 4), None);
+    }
+
+    #[test]
+    fn anchored_domain_is_relative_to_the_anchor() {
+        let pts = [(0, 100.0), (1_000, 200.0), (2_000, 150.0)];
+
+        let (lo, hi) = pct_domain(&[&pts], 1_000, 0, 2_000).unwrap();
+        assert!(lo < -50.0 && lo > -55.0, "{lo}");
+        assert!(hi > 0.0 && hi < 5.0, "{hi}");
+    }
 }
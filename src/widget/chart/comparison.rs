@@ -1,9 +1,11 @@
+use crate::chart::text_cache::label_width;
 use crate::style;
 use crate::widget::chart::SeriesLike;
 use crate::widget::chart::Zoom;
 use crate::widget::chart::domain;
 
 use data::UserTimezone;
+use data::chart::PriceScale;
 use exchange::{TickerInfo, Timeframe};
 
 use iced::advanced::widget::tree::{self, Tree};
@@ -11,11 +13,13 @@ use iced::advanced::{self, Clipboard, Layout, Shell, Widget, layout, renderer};
 use iced::theme::palette::Extended;
 use iced::widget::canvas;
 use iced::{
-    Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector, mouse, window,
+    Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Theme, Vector, keyboard,
+    mouse, window,
 };
 use iced_core::renderer::Quad;
 
 use chrono::TimeZone;
+use std::cell::RefCell;
 
 const Y_AXIS_GUTTER: f32 = 66.0; // px
 const X_AXIS_HEIGHT: f32 = 24.0;
@@ -27,6 +31,8 @@ const ZOOM_STEP_PCT: f32 = 0.05; // 5% per scroll "line"
 
 /// Gap breaker to avoid drawing across missing data
 const GAP_BREAK_MULTIPLIER: f32 = 3.0;
+/// Visible points kept per pixel of plot width, beyond that lines are thinned with LTTB
+const POINTS_PER_PX: f32 = 2.0;
 
 pub const DEFAULT_ZOOM_POINTS: usize = 150;
 pub const MIN_ZOOM_POINTS: usize = 2;
@@ -35,8 +41,6 @@ pub const MAX_ZOOM_POINTS: usize = 5000;
 const LEGEND_PADDING: f32 = 4.0;
 const LEGEND_LINE_H: f32 = TEXT_SIZE + 6.0;
 
-const CHAR_W: f32 = TEXT_SIZE * 0.64;
-
 const ICON_BOX: f32 = TEXT_SIZE + 8.0;
 const ICON_SPACING: f32 = 4.0;
 const ICON_GAP_AFTER_TEXT: f32 = 8.0;
@@ -48,6 +52,10 @@ pub enum LineComparisonEvent {
     SeriesCog(TickerInfo),
     SeriesRemove(TickerInfo),
     XAxisDoubleClick,
+    /// Right click on the y-axis, at a height of the axis
+    YAxisMenu(f32),
+    /// Alt-click on the plot pins the 0% baseline to that time, the marker's close button clears it
+    AnchorChanged(Option<u64>),
 }
 
 struct State {
@@ -60,6 +68,17 @@ struct State {
     last_cache_rev: u64,
     // Track previous click for double-click detection
     previous_click: Option<iced_core::mouse::Click>,
+    modifiers: keyboard::Modifiers,
+    thinned: RefCell<Option<Thinned>>,
+}
+
+/// What the visible points were thinned for: zoom, pan (bits), version and plot width (px)
+type ThinKey = (Zoom, u32, u64, u32);
+
+/// Visible points of every series as drawn, each split into runs at data gaps
+struct Thinned {
+    key: ThinKey,
+    series: Vec<Vec<Vec<(u64, f32)>>>,
 }
 
 impl Default for State {
@@ -73,6 +92,8 @@ impl Default for State {
             last_cursor: None,
             last_cache_rev: 0,
             previous_click: None,
+            modifiers: keyboard::Modifiers::default(),
+            thinned: RefCell::new(None),
         }
     }
 }
@@ -94,6 +115,10 @@ pub struct LineComparison<'a, S> {
     timeframe: Timeframe,
     timezone: UserTimezone,
     version: u64,
+    price_scale: PriceScale,
+    locked_range: Option<(f32, f32)>,
+    anchor: Option<u64>,
+    show_correlation: bool,
 }
 
 impl<'a, S> LineComparison<'a, S>
@@ -109,6 +134,10 @@ where
             pan: 0.0,
             timezone: UserTimezone::Utc,
             version: 0,
+            price_scale: PriceScale::default(),
+            locked_range: None,
+            anchor: None,
+            show_correlation: false,
         }
     }
 
@@ -127,6 +156,29 @@ where
         self
     }
 
+    /// `locked_range` keeps the y-axis on a percent range instead of fitting the visible series
+    pub fn with_price_scale(
+        mut self,
+        price_scale: PriceScale,
+        locked_range: Option<(f32, f32)>,
+    ) -> Self {
+        self.price_scale = price_scale;
+        self.locked_range = locked_range;
+        self
+    }
+
+    /// Time the series are rebased to, the left edge of the view when `None`
+    pub fn with_anchor(mut self, anchor: Option<u64>) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Lists each series' correlation with the first one over the visible window
+    pub fn with_correlation(mut self, show: bool) -> Self {
+        self.show_correlation = show;
+        self
+    }
+
     pub fn version(mut self, rev: u64) -> Self {
         self.version = rev;
         self
@@ -222,7 +274,11 @@ where
         let all_points: Vec<&[(u64, f32)]> = self.series.iter().map(|s| s.points()).collect();
 
         let (min_x, max_x) = domain::window(&all_points, self.zoom, pan_points, dt)?;
-        let (min_pct, max_pct) = domain::pct_domain(&all_points, min_x, max_x)?;
+        let base_x = self.anchor.unwrap_or(min_x);
+        let (min_pct, max_pct) = match self.locked_range {
+            Some(range) => range,
+            None => domain::pct_domain(&all_points, base_x, min_x, max_x)?,
+        };
 
         Some(((min_x, max_x), (min_pct, max_pct)))
     }
@@ -243,9 +299,11 @@ where
             regions,
             min_x,
             max_x,
+            base_x: self.anchor.unwrap_or(min_x),
             min_pct,
             max_pct,
             px_per_ms,
+            price_scale: self.price_scale,
         };
 
         let total_ticks = (plot.height / TEXT_SIZE / 3.).floor() as usize;
@@ -265,6 +323,27 @@ where
         let mut end_labels = self.collect_end_labels(&ctx, step);
         let plot_rect = ctx.plot_rect();
 
+        let anchor = self
+            .anchor
+            .filter(|anchor| (ctx.min_x..=ctx.max_x).contains(anchor))
+            .map(|anchor| {
+                let x = plot_rect.x + ctx.map_x(anchor);
+                let clear_x = (x - ICON_BOX * 0.5).clamp(
+                    plot_rect.x,
+                    (plot_rect.x + plot_rect.width - ICON_BOX).max(plot_rect.x),
+                );
+
+                AnchorMarker {
+                    x,
+                    clear: Rectangle {
+                        x: clear_x,
+                        y: plot_rect.y + plot_rect.height - ICON_BOX - 4.0,
+                        width: ICON_BOX,
+                        height: ICON_BOX,
+                    },
+                }
+            });
+
         resolve_label_overlaps(&mut end_labels, plot_rect);
 
         let cursor_root_local = cursor.position_in(layout.bounds());
@@ -289,7 +368,7 @@ where
                     .clamp(ctx.min_x, ctx.max_x);
 
                     let t = ((local.y - plot_rect.y) / plot_rect.height).clamp(0.0, 1.0);
-                    let pct = ctx.min_pct + (1.0 - t) * (ctx.max_pct - ctx.min_pct);
+                    let pct = ctx.t_to_pct(1.0 - t);
                     Some(CursorInfo {
                         x_domain: snapped_x,
                         y_pct: pct,
@@ -363,15 +442,14 @@ where
         if should_draw_crosshair && let Some(ci) = cursor_info {
             let plot_rect = ctx.plot_rect();
 
-            let t =
-                ((ci.y_pct - ctx.min_pct) / (ctx.max_pct - ctx.min_pct).max(1e-6)).clamp(0.0, 1.0);
+            let t = ctx.pct_to_t(ci.y_pct).clamp(0.0, 1.0);
             let cy_px = plot_rect.y + plot_rect.height - t * plot_rect.height;
 
             let pct_str = super::format_pct(ci.y_pct, step, true);
-            let pct_est_w = (pct_str.len() as f32) * (TEXT_SIZE * 0.6) + 10.0;
+            let pct_w = label_width(&pct_str) + 10.0;
 
             let gutter_w = ctx.gutter_width();
-            let y_w = pct_est_w.clamp(40.0, gutter_w - 8.0);
+            let y_w = pct_w.clamp(40.0, gutter_w - 8.0);
             let y_h = TEXT_SIZE + 6.0;
 
             let ylbl_x_right = ctx.regions.y_axis.x + gutter_w - 2.0;
@@ -388,6 +466,8 @@ where
             });
         }
 
+        let correlations = self.correlations(&ctx);
+
         Some(Scene {
             ctx,
             y_ticks: ticks,
@@ -400,6 +480,29 @@ where
             hovering_legend,
             hovered_icon,
             hovered_row,
+            anchor,
+            correlations,
+        })
+    }
+
+    /// Correlation of every other series with the base one, `None` when hidden
+    fn correlations(&self, ctx: &PlotContext) -> Option<Correlations> {
+        let (base, others) = self.series.split_first()?;
+        if !self.show_correlation || others.is_empty() {
+            return None;
+        }
+
+        let rows = others
+            .iter()
+            .map(|s| {
+                let rho = domain::correlation(base.points(), s.points(), ctx.min_x, ctx.max_x);
+                (s.name(), s.color(), rho)
+            })
+            .collect();
+
+        Some(Correlations {
+            base: base.name(),
+            rows,
         })
     }
 
@@ -422,18 +525,19 @@ where
             LegendMode::Compact { include_pct } => (false, include_pct),
         };
 
-        let mut max_chars: usize = 0;
-        let mut max_name_chars: usize = 0;
+        let mut text_w: f32 = 0.0;
+        let mut max_name_w: f32 = 0.0;
         let mut rows_count: usize = 0;
 
         for s in self.series.iter() {
             rows_count += 1;
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len();
-            max_name_chars = max_name_chars.max(name_len);
+            let label = s.label();
+            let name_w = label_width(&label);
+            max_name_w = max_name_w.max(name_w);
 
-            let pct_len = if include_pct_in_width {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
+            let pct_str = if include_pct_in_width {
+                domain::interpolate_y_at(s.points(), ctx.base_x)
                     .filter(|&y0| y0 != 0.0)
                     .and_then(|y0| {
                         cursor_x.and_then(|cx| {
@@ -443,29 +547,24 @@ where
                             })
                         })
                     })
-                    .map(|s| s.len())
-                    .unwrap_or(0)
             } else {
-                0
+                None
             };
 
-            let total = if pct_len > 0 {
-                name_len + 1 + pct_len
-            } else {
-                name_len
+            let row_w = match pct_str {
+                Some(pct) => label_width(&format!("{label} {pct}")),
+                None => name_w,
             };
-            max_chars = max_chars.max(total);
+            text_w = text_w.max(row_w);
         }
 
-        let text_w = (max_chars as f32) * CHAR_W;
-
         let icons_pack_w = if include_icons {
             2.0 * ICON_BOX + ICON_SPACING
         } else {
             0.0
         };
         let min_for_icons = if include_icons {
-            (max_name_chars as f32) * CHAR_W + ICON_GAP_AFTER_TEXT + icons_pack_w
+            max_name_w + ICON_GAP_AFTER_TEXT + icons_pack_w
         } else {
             0.0
         };
@@ -503,11 +602,11 @@ where
         for (i, s) in self.series.iter().take(visible_rows).enumerate() {
             let y_center = row_top + line_h * 0.5;
 
-            // Base ticker (i == 0) cannot be removed
-            let has_close = i != 0;
+            // Base ticker (i == 0) cannot be removed, derived series are edited in the settings
+            let has_close = i != 0 && !s.is_derived();
+            let has_cog = !s.is_derived();
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len() as f32;
-            let text_end_x = x_left + name_len * CHAR_W;
+            let text_end_x = x_left + label_width(&s.label());
 
             let (cog, close, row_width) = if include_icons {
                 let icons_pack_w = if has_close {
@@ -586,11 +685,12 @@ where
 
             rows.push(LegendRowHit {
                 ticker: *s.ticker_info(),
-                cog,
+                cog: if has_cog { cog } else { Rectangle::default() },
                 close,
                 y_center,
                 row_rect,
                 has_close,
+                has_cog,
             });
             row_top += line_h;
         }
@@ -621,21 +721,8 @@ where
                 None => continue,
             };
 
-            let idx_right = pts.iter().position(|(x, _)| *x >= ctx.min_x);
-            let y0 = match idx_right {
-                Some(0) => pts[0].1,
-                Some(i) => {
-                    let (x0, y0) = pts[i - 1];
-                    let (x2, y2) = pts[i];
-                    let dx = (x2.saturating_sub(x0)) as f32;
-                    if dx > 0.0 {
-                        let t = (ctx.min_x.saturating_sub(x0)) as f32 / dx;
-                        y0 + (y2 - y0) * t.clamp(0.0, 1.0)
-                    } else {
-                        y0
-                    }
-                }
-                None => continue,
+            let Some(y0) = domain::interpolate_y_at(pts, ctx.base_x) else {
+                continue;
             };
 
             if y0 == 0.0 {
@@ -847,7 +934,32 @@ where
                             return;
                         }
 
-                        if let Some(scene) = self.compute_scene(layout, cursor)
+                        let scene = self.compute_scene(layout, cursor);
+
+                        if let Some(scene) = scene.as_ref() {
+                            if scene
+                                .anchor
+                                .is_some_and(|anchor| anchor.clear.contains(cursor_pos))
+                            {
+                                shell.publish(M::from(LineComparisonEvent::AnchorChanged(None)));
+                                shell.capture_event();
+                                state.clear_all_caches();
+                                return;
+                            }
+
+                            if state.modifiers.alt()
+                                && let Some(ci) = scene.cursor
+                            {
+                                shell.publish(M::from(LineComparisonEvent::AnchorChanged(Some(
+                                    ci.x_domain,
+                                ))));
+                                shell.capture_event();
+                                state.clear_all_caches();
+                                return;
+                            }
+                        }
+
+                        if let Some(scene) = scene
                             && let Some(legend) = scene.legend.as_ref()
                         {
                             for row in &legend.rows {
@@ -873,6 +985,13 @@ where
                             state.last_cursor = Some(cursor_pos);
                         }
                     }
+                    mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                        if matches!(zone, HitZone::YAxis) {
+                            let y = cursor_pos.y - regions.y_axis.y;
+                            shell.publish(M::from(LineComparisonEvent::YAxisMenu(y)));
+                            shell.capture_event();
+                        }
+                    }
                     mouse::Event::ButtonReleased(mouse::Button::Left) => {
                         state.is_panning = false;
                         state.last_cursor = None;
@@ -902,6 +1021,9 @@ where
                     _ => {}
                 }
             }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                tree.state.downcast_mut::<State>().modifiers = *modifiers;
+            }
             Event::Window(window::Event::RedrawRequested(_)) => {
                 let state = tree.state.downcast_mut::<State>();
 
@@ -933,12 +1055,13 @@ where
 
         let bounds = layout.bounds();
         let palette = theme.extended_palette();
+        let cursor_local = cursor.position_in(bounds);
 
         renderer.with_translation(Vector::new(bounds.x, bounds.y), |r| {
             let plot_rect = scene.ctx.plot_rect();
 
             let plot_geom = state.plot_cache.draw(r, plot_rect.size(), |frame| {
-                self.fill_main_geometry(frame, &scene.ctx);
+                self.fill_main_geometry(frame, &scene.ctx, &state.thinned);
             });
 
             let splitter_color = palette.background.strong.color.scale_alpha(0.25);
@@ -1008,6 +1131,10 @@ where
                     scene.hovered_icon,
                     scene.hovered_row,
                 );
+                fill_correlation_table(frame, &scene, palette);
+                if let Some(anchor) = scene.anchor {
+                    fill_anchor_marker(frame, &scene.ctx, anchor, cursor_local, palette);
+                }
                 if !(scene.hovering_legend && scene.hovered_row.is_some()) {
                     self.fill_crosshair(frame, &scene, palette);
                 }
@@ -1051,6 +1178,13 @@ where
     ) -> advanced::mouse::Interaction {
         if let Some(cursor_in_layout) = cursor.position_in(layout.bounds()) {
             if let Some(scene) = self.compute_scene(layout, cursor) {
+                if scene
+                    .anchor
+                    .is_some_and(|anchor| anchor.clear.contains(cursor_in_layout))
+                {
+                    return advanced::mouse::Interaction::Pointer;
+                }
+
                 if let Some(legend) = scene.legend.as_ref() {
                     for row in &legend.rows {
                         if row.cog.contains(cursor_in_layout)
@@ -1088,28 +1222,54 @@ where
     S: SeriesLike,
 {
     #[allow(unused_assignments)]
-    fn fill_main_geometry(&self, frame: &mut canvas::Frame, ctx: &PlotContext) {
-        for s in self.series.iter() {
+    fn fill_main_geometry(
+        &self,
+        frame: &mut canvas::Frame,
+        ctx: &PlotContext,
+        thinned: &RefCell<Option<Thinned>>,
+    ) {
+        let gap_thresh: u64 = ((self.dt_ms_est() as f32) * GAP_BREAK_MULTIPLIER)
+            .max(1.0)
+            .round() as u64;
+
+        let plot_width = ctx.plot_rect().width;
+        let key = (
+            self.zoom,
+            self.pan.to_bits(),
+            self.version,
+            plot_width.round() as u32,
+        );
+        let mut thinned = thinned.borrow_mut();
+        if thinned
+            .as_ref()
+            .is_none_or(|t| t.key != key || t.series.len() != self.series.len())
+        {
+            let budget = ((plot_width * POINTS_PER_PX) as usize).max(3);
+            let series = self
+                .series
+                .iter()
+                .map(|s| {
+                    let pts = s.points();
+                    let start = pts.partition_point(|(x, _)| *x < ctx.min_x);
+                    let end = pts.partition_point(|(x, _)| *x <= ctx.max_x);
+                    thin_runs(&pts[start..end.max(start)], gap_thresh, budget)
+                })
+                .collect();
+            *thinned = Some(Thinned { key, series });
+        }
+        let Some(thinned) = thinned.as_ref() else {
+            return;
+        };
+
+        for (s, runs) in self.series.iter().zip(&thinned.series) {
             let pts = s.points();
             if pts.is_empty() {
                 continue;
             }
 
             let idx_right = pts.iter().position(|(x, _)| *x >= ctx.min_x);
-            let y0 = match idx_right {
-                Some(0) => pts[0].1,
-                Some(i) => {
-                    let (x0, y0_) = pts[i - 1];
-                    let (x1, y1_) = pts[i];
-                    let dx = (x1.saturating_sub(x0)) as f32;
-                    if dx > 0.0 {
-                        let t = (ctx.min_x.saturating_sub(x0)) as f32 / dx;
-                        y0_ + (y1_ - y0_) * t.clamp(0.0, 1.0)
-                    } else {
-                        y0_
-                    }
-                }
-                None => continue,
+            let Some(y0) = domain::interpolate_y_at(pts, ctx.base_x) else {
+                continue;
             };
 
             if y0 == 0.0 {
@@ -1118,15 +1278,12 @@ where
 
             let mut builder = canvas::path::Builder::new();
 
-            let gap_thresh: u64 = ((self.dt_ms_est() as f32) * GAP_BREAK_MULTIPLIER)
-                .max(1.0)
-                .round() as u64;
-
             let mut prev_x: Option<u64> = None;
             match idx_right {
                 Some(ir) if ir > 0 => {
+                    let y_left = domain::interpolate_y_at(pts, ctx.min_x).unwrap_or(y0);
                     let px0 = ctx.map_x(ctx.min_x);
-                    let py0 = ctx.map_y(0.0);
+                    let py0 = ctx.map_y(((y_left / y0) - 1.0) * 100.0);
                     builder.move_to(Point::new(px0, py0));
                     prev_x = Some(ctx.min_x);
                 }
@@ -1143,18 +1300,18 @@ where
                 _ => continue,
             }
 
-            let start_idx = idx_right.unwrap_or(pts.len());
-
-            for (x, y) in pts.iter().skip(start_idx) {
-                if *x > ctx.max_x {
-                    break;
-                }
-                let pct = ((*y / y0) - 1.0) * 100.0;
-                let px = ctx.map_x(*x);
+            // only the first point of a run can sit across a gap, the rest always connect
+            for (x, y, first) in runs.iter().flat_map(|run| {
+                run.iter()
+                    .enumerate()
+                    .map(|(idx, (x, y))| (*x, *y, idx == 0))
+            }) {
+                let pct = ((y / y0) - 1.0) * 100.0;
+                let px = ctx.map_x(x);
                 let py = ctx.map_y(pct);
 
                 let connect = match prev_x {
-                    Some(prev) => x.saturating_sub(prev) <= gap_thresh,
+                    Some(prev) => !first || x.saturating_sub(prev) <= gap_thresh,
                     None => false,
                 };
 
@@ -1163,7 +1320,7 @@ where
                 } else {
                     builder.move_to(Point::new(px, py));
                 }
-                prev_x = Some(*x);
+                prev_x = Some(x);
             }
 
             let path = builder.build();
@@ -1230,7 +1387,7 @@ where
 
             let sym_right = split_x - 1.0;
             let sym_h = TEXT_SIZE + 4.0;
-            let sym_w = (label.symbol.len() as f32) * CHAR_W + 8.0;
+            let sym_w = label_width(&label.symbol) + 8.0;
             let sym_rect = Rectangle {
                 x: sym_right - sym_w,
                 y: label.pos.y - sym_h * 0.5,
@@ -1300,9 +1457,9 @@ where
             let label_ts = Self::to_tz_ms(t, self.timezone);
             let label = super::format_time_label(label_ts, step_ms);
 
-            let est_w = (label.len() as f32) * CHAR_W + 8.0;
-            let left = x_local - est_w * 0.5;
-            let right = x_local + est_w * 0.5;
+            let label_w = label_width(&label) + 8.0;
+            let left = x_local - label_w * 0.5;
+            let right = x_local + label_w * 0.5;
 
             if left <= last_right {
                 continue;
@@ -1369,7 +1526,7 @@ where
                 let pct_str = if hovering_legend {
                     None
                 } else {
-                    domain::interpolate_y_at(s.points(), ctx.min_x)
+                    domain::interpolate_y_at(s.points(), ctx.base_x)
                         .filter(|&y0| y0 != 0.0)
                         .and_then(|y0| {
                             cursor_x.and_then(|cx| {
@@ -1381,7 +1538,7 @@ where
                         })
                 };
 
-                let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
+                let symbol_and_exchange = s.label();
                 let content = if let Some(pct) = pct_str {
                     format!("{symbol_and_exchange} {pct}")
                 } else {
@@ -1406,19 +1563,21 @@ where
                         _ => (icon_normal, icon_normal),
                     };
 
-                    frame.fill_text(canvas::Text {
-                        content: char::from(style::Icon::Cog).to_string(),
-                        position: Point {
-                            x: row.cog.center_x(),
-                            y,
-                        },
-                        color: cog_col,
-                        size: TEXT_SIZE.into(),
-                        font: style::ICONS_FONT,
-                        align_x: iced::Alignment::Center.into(),
-                        align_y: iced::Alignment::Center.into(),
-                        ..Default::default()
-                    });
+                    if row.has_cog {
+                        frame.fill_text(canvas::Text {
+                            content: char::from(style::Icon::Cog).to_string(),
+                            position: Point {
+                                x: row.cog.center_x(),
+                                y,
+                            },
+                            color: cog_col,
+                            size: TEXT_SIZE.into(),
+                            font: style::ICONS_FONT,
+                            align_x: iced::Alignment::Center.into(),
+                            align_y: iced::Alignment::Center.into(),
+                            ..Default::default()
+                        });
+                    }
 
                     if row.has_close {
                         frame.fill_text(canvas::Text {
@@ -1440,45 +1599,41 @@ where
             return;
         }
 
-        let mut max_chars: usize = 0;
-        let mut rows_count: usize = 0;
-
-        for s in self.series.iter() {
-            rows_count += 1;
-
-            let pct_len = if hovering_legend {
-                0
-            } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
-                        cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
+        let contents: Vec<String> = self
+            .series
+            .iter()
+            .map(|s| {
+                let pct_str = if hovering_legend {
+                    None
+                } else {
+                    domain::interpolate_y_at(s.points(), ctx.base_x)
+                        .filter(|&y0| y0 != 0.0)
+                        .and_then(|y0| {
+                            cursor_x.and_then(|cx| {
+                                domain::interpolate_y_at(s.points(), cx).map(|yc| {
+                                    let pct = ((yc / y0) - 1.0) * 100.0;
+                                    super::format_pct(pct, step, true)
+                                })
                             })
                         })
-                    })
-                    .map(|s| s.len())
-                    .unwrap_or(0)
-            };
+                };
 
-            let name_len = s.ticker_info().ticker.symbol_and_exchange_string().len();
-            let total = if pct_len > 0 {
-                name_len + 1 + pct_len
-            } else {
-                name_len
-            };
-            if total > max_chars {
-                max_chars = total;
-            }
-        }
+                let symbol_and_exchange = s.label();
+                if let Some(pct) = pct_str {
+                    format!("{symbol_and_exchange} {pct}")
+                } else {
+                    symbol_and_exchange
+                }
+            })
+            .collect();
+        let rows_count = contents.len();
 
         let plot_rect = ctx.plot_rect();
 
-        let max_chars_f = max_chars as f32;
-        let char_w = TEXT_SIZE * 0.64;
-        let text_w = max_chars_f * char_w;
+        let text_w = contents
+            .iter()
+            .map(|content| label_width(content))
+            .fold(0.0, f32::max);
         let bg_w = (text_w + padding * 2.0).clamp(80.0, (plot_rect.width * 0.6).max(80.0));
 
         let rows_count_f = rows_count as f32;
@@ -1494,33 +1649,11 @@ where
         let mut y = plot_rect.y + padding + TEXT_SIZE * 0.5;
         let x0 = plot_rect.x + padding;
 
-        for s in self.series.iter() {
+        for (s, content) in self.series.iter().zip(contents) {
             if y > plot_rect.y + plot_rect.height - TEXT_SIZE {
                 break;
             }
 
-            let pct_str = if hovering_legend {
-                None
-            } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
-                        cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
-                            })
-                        })
-                    })
-            };
-
-            let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
-            let content = if let Some(pct) = pct_str {
-                format!("{symbol_and_exchange} {pct}")
-            } else {
-                symbol_and_exchange
-            };
-
             frame.fill_text(canvas::Text {
                 content,
                 position: Point::new(x0, y),
@@ -1547,8 +1680,7 @@ where
             let dx = ci.x_domain.saturating_sub(ctx.min_x) as f32;
             plot_rect.x + dx * ctx.px_per_ms
         };
-        let y_span = (ctx.max_pct - ctx.min_pct).max(1e-6);
-        let t = ((ci.y_pct - ctx.min_pct) / y_span).clamp(0.0, 1.0);
+        let t = ctx.pct_to_t(ci.y_pct).clamp(0.0, 1.0);
         let cy = plot_rect.y + plot_rect.height - t * plot_rect.height;
 
         let stroke = style::dashed_line_from_palette(palette);
@@ -1570,8 +1702,7 @@ where
         let text_col = palette.secondary.base.text;
         let bg_col = palette.secondary.base.color;
 
-        let est_w = (time_str.len() as f32) * (TEXT_SIZE * 0.67) + 12.0;
-        let label_w = est_w.clamp(100.0, 240.0);
+        let label_w = label_width(&time_str) + 12.0;
         let label_h = TEXT_SIZE + 6.0;
 
         let time_x = cx.clamp(
@@ -1627,6 +1758,131 @@ where
     }
 }
 
+/// Correlations with the base series in the top right corner of the plot
+fn fill_correlation_table(frame: &mut canvas::Frame, scene: &Scene, palette: &Extended) {
+    let Some(correlations) = scene.correlations.as_ref() else {
+        return;
+    };
+
+    let plot_rect = scene.ctx.plot_rect();
+    let header = format!("ρ vs {}", correlations.base);
+    let rows: Vec<(String, Color)> = correlations
+        .rows
+        .iter()
+        .map(|(name, color, rho)| {
+            let value = rho.map_or_else(|| "-".to_string(), |rho| format!("{rho:+.2}"));
+            (format!("{name} {value}"), *color)
+        })
+        .collect();
+
+    let text_w = rows
+        .iter()
+        .map(|(row, _)| label_width(row))
+        .chain(std::iter::once(label_width(&header)))
+        .fold(0.0, f32::max);
+    let width = text_w + LEGEND_PADDING * 2.0;
+    let visible_rows = rows.len().min(
+        (((plot_rect.height * 0.6 - LEGEND_PADDING * 2.0) / LEGEND_LINE_H).floor() as usize)
+            .saturating_sub(1),
+    );
+    let height = (visible_rows + 1) as f32 * LEGEND_LINE_H + LEGEND_PADDING * 2.0;
+
+    let bg = Rectangle {
+        x: plot_rect.x + plot_rect.width - width - 4.0,
+        y: plot_rect.y + 4.0,
+        width,
+        height,
+    };
+    frame.fill_rectangle(
+        bg.position(),
+        bg.size(),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+
+    let lines = std::iter::once((header, palette.background.base.text)).chain(rows);
+    for (i, (content, color)) in lines.take(visible_rows + 1).enumerate() {
+        frame.fill_text(canvas::Text {
+            content,
+            position: Point::new(
+                bg.x + bg.width - LEGEND_PADDING,
+                bg.y + LEGEND_PADDING + (i as f32 + 0.5) * LEGEND_LINE_H,
+            ),
+            color,
+            size: TEXT_SIZE.into(),
+            font: style::AZERET_MONO,
+            align_x: iced::Alignment::End.into(),
+            align_y: iced::Alignment::Center.into(),
+            ..Default::default()
+        });
+    }
+}
+
+/// Dashed line at the rebase anchor, with the button clearing it
+fn fill_anchor_marker(
+    frame: &mut canvas::Frame,
+    ctx: &PlotContext,
+    anchor: AnchorMarker,
+    cursor: Option<Point>,
+    palette: &Extended,
+) {
+    let plot_rect = ctx.plot_rect();
+    let color = palette.primary.base.color;
+
+    let mut b = canvas::path::Builder::new();
+    b.move_to(Point::new(anchor.x, plot_rect.y));
+    b.line_to(Point::new(anchor.x, anchor.clear.y));
+    frame.stroke(
+        &b.build(),
+        canvas::Stroke {
+            line_dash: canvas::LineDash {
+                segments: &[4.0, 4.0],
+                offset: 0,
+            },
+            ..canvas::Stroke::default()
+                .with_color(color.scale_alpha(0.8))
+                .with_width(1.0)
+        },
+    );
+
+    let hovered = cursor.is_some_and(|cursor| anchor.clear.contains(cursor));
+    frame.fill_rectangle(
+        anchor.clear.position(),
+        anchor.clear.size(),
+        if hovered {
+            palette.primary.strong.color
+        } else {
+            color
+        },
+    );
+    frame.fill_text(canvas::Text {
+        content: char::from(style::Icon::Close).to_string(),
+        position: anchor.clear.center(),
+        color: palette.primary.base.text,
+        size: TEXT_SIZE.into(),
+        font: style::ICONS_FONT,
+        align_x: iced::Alignment::Center.into(),
+        align_y: iced::Alignment::Center.into(),
+        ..Default::default()
+    });
+}
+
+/// Splits `visible` at gaps wider than `gap`, thinning the runs down to about `budget` points
+/// in all, each by its share of the points
+fn thin_runs(visible: &[(u64, f32)], gap: u64, budget: usize) -> Vec<Vec<(u64, f32)>> {
+    let total = visible.len();
+
+    visible
+        .chunk_by(|a, b| b.0.saturating_sub(a.0) <= gap)
+        .map(|run| {
+            if total <= budget {
+                run.to_vec()
+            } else {
+                domain::lttb(run, (budget * run.len()).div_ceil(total))
+            }
+        })
+        .collect()
+}
+
 struct EndLabel {
     pos: Point,
     bg_color: Color,
@@ -1768,9 +2024,12 @@ struct PlotContext {
     regions: Regions,
     min_x: u64,
     max_x: u64,
+    /// Time every series is 0% at
+    base_x: u64,
     min_pct: f32,
     max_pct: f32,
     px_per_ms: f32,
+    price_scale: PriceScale,
 }
 
 impl PlotContext {
@@ -1787,9 +2046,45 @@ impl PlotContext {
         dx * self.px_per_ms
     }
 
+    /// Change as laid out on the axis, log scales space equal ratios of price equally
+    fn scaled(&self, pct: f32) -> f32 {
+        if self.price_scale.log {
+            (1.0 + pct / 100.0).max(f32::MIN_POSITIVE).ln()
+        } else {
+            pct
+        }
+    }
+
+    /// Share of the plot height below `pct`, 0 at the bottom edge
+    fn pct_to_t(&self, pct: f32) -> f32 {
+        let (lo, hi) = (self.scaled(self.min_pct), self.scaled(self.max_pct));
+        let t = (self.scaled(pct) - lo) / (hi - lo).max(1e-6);
+
+        if self.price_scale.inverted {
+            1.0 - t
+        } else {
+            t
+        }
+    }
+
+    fn t_to_pct(&self, t: f32) -> f32 {
+        let t = if self.price_scale.inverted {
+            1.0 - t
+        } else {
+            t
+        };
+        let (lo, hi) = (self.scaled(self.min_pct), self.scaled(self.max_pct));
+        let value = lo + t * (hi - lo);
+
+        if self.price_scale.log {
+            (value.exp() - 1.0) * 100.0
+        } else {
+            value
+        }
+    }
+
     fn map_y(&self, pct: f32) -> f32 {
-        let span = (self.max_pct - self.min_pct).max(1e-6);
-        let t = (pct - self.min_pct) / span;
+        let t = self.pct_to_t(pct);
         let plot = self.plot_rect();
         plot.height - t.clamp(0.0, 1.0) * plot.height
     }
@@ -1813,6 +2108,21 @@ struct Scene {
     hovering_legend: bool,
     hovered_icon: Option<(usize, IconKind)>,
     hovered_row: Option<usize>,
+    anchor: Option<AnchorMarker>,
+    correlations: Option<Correlations>,
+}
+
+struct Correlations {
+    base: String,
+    /// Name, color and correlation of each other series, `None` without enough shared bars
+    rows: Vec<(String, Color, Option<f32>)>,
+}
+
+/// Rebase anchor within the view, with the button clearing it at the foot of its line
+#[derive(Debug, Clone, Copy)]
+struct AnchorMarker {
+    x: f32,
+    clear: Rectangle,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1823,6 +2133,7 @@ struct LegendRowHit {
     y_center: f32,
     row_rect: Rectangle,
     has_close: bool,
+    has_cog: bool,
 }
 
 #[derive(Debug, Clone)]